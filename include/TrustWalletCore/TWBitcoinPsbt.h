@@ -0,0 +1,53 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+#pragma once
+
+#include "TWBase.h"
+#include "TWCoinType.h"
+#include "TWData.h"
+
+TW_EXTERN_C_BEGIN
+
+/// Partially Signed Bitcoin Transaction (BIP174 and BIP370) multi-party signing.
+TW_EXPORT_STRUCT
+struct TWBitcoinPsbt;
+
+/// Creates a PSBT from a transaction builder and fills in the information required to sign it.
+///
+/// \param coin Bitcoin-compatible coin type.
+/// \param input The serialized data of `TW.BitcoinV2.Proto.PsbtCreatingInput`.
+/// \return The serialized data of a `TW.BitcoinV2.Proto.PsbtOutput` proto object.
+TW_EXPORT_STATIC_METHOD
+TWData* _Nonnull TWBitcoinPsbtCreate(enum TWCoinType coin, TWData* _Nonnull input);
+
+/// Adds partial signatures of the given private keys to a PSBT.
+///
+/// \param input The serialized data of `TW.BitcoinV2.Proto.PsbtSigningInput`.
+/// \return The serialized data of a `TW.BitcoinV2.Proto.PsbtOutput` proto object.
+TW_EXPORT_STATIC_METHOD
+TWData* _Nonnull TWBitcoinPsbtSign(TWData* _Nonnull input);
+
+/// Combines PSBTs of the same transaction signed by different parties.
+///
+/// \param input The serialized data of `TW.BitcoinV2.Proto.PsbtCombiningInput`.
+/// \return The serialized data of a `TW.BitcoinV2.Proto.PsbtOutput` proto object.
+TW_EXPORT_STATIC_METHOD
+TWData* _Nonnull TWBitcoinPsbtCombine(TWData* _Nonnull input);
+
+/// Builds the final scriptSig and witness of every PSBT input from the partial signatures.
+///
+/// \param input The serialized data of `TW.BitcoinV2.Proto.Psbt`.
+/// \return The serialized data of a `TW.BitcoinV2.Proto.PsbtOutput` proto object.
+TW_EXPORT_STATIC_METHOD
+TWData* _Nonnull TWBitcoinPsbtFinalize(TWData* _Nonnull input);
+
+/// Extracts the signed network transaction from a finalized PSBT.
+///
+/// \param input The serialized data of `TW.BitcoinV2.Proto.Psbt`.
+/// \return The serialized data of a `TW.BitcoinV2.Proto.SigningOutput` proto object.
+TW_EXPORT_STATIC_METHOD
+TWData* _Nonnull TWBitcoinPsbtExtract(TWData* _Nonnull input);
+
+TW_EXTERN_C_END
@@ -25,6 +25,22 @@ struct TWEthereumAbi;
 TW_EXPORT_STATIC_METHOD
 TWData* _Nonnull TWEthereumAbiDecodeContractCall(enum TWCoinType coin, TWData* _Nonnull input);
 
+/// Decode an event log (`eth_getLogs` or transaction receipt entry) according to a given ABI.
+///
+/// \param coin EVM-compatible coin type.
+/// \param input The serialized data of `TW.EthereumAbi.Proto.LogDecodingInput`.
+/// \return The serialized data of a `TW.EthereumAbi.Proto.LogDecodingOutput` proto object.
+TW_EXPORT_STATIC_METHOD
+TWData* _Nonnull TWEthereumAbiDecodeLog(enum TWCoinType coin, TWData* _Nonnull input);
+
+/// Decode revert data as `Error(string)`, `Panic(uint256)` or a custom error declared in a given ABI.
+///
+/// \param coin EVM-compatible coin type.
+/// \param input The serialized data of `TW.EthereumAbi.Proto.RevertDecodingInput`.
+/// \return The serialized data of a `TW.EthereumAbi.Proto.RevertDecodingOutput` proto object.
+TW_EXPORT_STATIC_METHOD
+TWData* _Nonnull TWEthereumAbiDecodeRevert(enum TWCoinType coin, TWData* _Nonnull input);
+
 /// Decode a function input or output data according to a given ABI.
 ///
 /// \param coin EVM-compatible coin type.
@@ -49,6 +65,22 @@ TWData* _Nonnull TWEthereumAbiDecodeValue(enum TWCoinType coin, TWData* _Nonnull
 TW_EXPORT_STATIC_METHOD
 TWData* _Nonnull TWEthereumAbiEncodeFunction(enum TWCoinType coin, TWData* _Nonnull input);
 
+/// Encode tokens in the non-standard packed mode, as Solidity `abi.encodePacked` does.
+///
+/// \param coin EVM-compatible coin type.
+/// \param input The serialized data of `TW.EthereumAbi.Proto.PackedEncodingInput`.
+/// \return The serialized data of a `TW.EthereumAbi.Proto.PackedEncodingOutput` proto object.
+TW_EXPORT_STATIC_METHOD
+TWData* _Nonnull TWEthereumAbiEncodePacked(enum TWCoinType coin, TWData* _Nonnull input);
+
+/// Compute the EIP-712 domain separator, message hash and signing hash of a typed data structure.
+///
+/// \param coin EVM-compatible coin type.
+/// \param input The serialized data of `TW.EthereumAbi.Proto.TypedDataHashingInput`.
+/// \return The serialized data of a `TW.EthereumAbi.Proto.TypedDataHashingOutput` proto object.
+TW_EXPORT_STATIC_METHOD
+TWData* _Nonnull TWEthereumAbiHashTypedData(enum TWCoinType coin, TWData* _Nonnull input);
+
 /// Encode function to Eth ABI binary
 ///
 /// \param fn Non-null Eth abi function
@@ -0,0 +1,26 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+#pragma once
+
+#include "TWBase.h"
+#include "TWCoinType.h"
+#include "TWData.h"
+
+TW_EXTERN_C_BEGIN
+
+/// EIP-7702 authorization signer.
+TW_EXPORT_STRUCT
+struct TWEthereumAuthorization;
+
+/// Signs an EIP-7702 authorization.
+/// Returns the authorization pre-hash only if no private key is specified (external signing).
+///
+/// \param coin EVM-compatible coin type.
+/// \param input Non-null serialized `Ethereum::Proto::AuthorizationSigningInput`.
+/// \return serialized `Ethereum::Proto::AuthorizationSigningOutput`.
+TW_EXPORT_STATIC_METHOD
+TWData* _Nonnull TWEthereumAuthorizationSign(enum TWCoinType coin, TWData* _Nonnull input);
+
+TW_EXTERN_C_END
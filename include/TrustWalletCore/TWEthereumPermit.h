@@ -0,0 +1,36 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+#pragma once
+
+#include "TWBase.h"
+#include "TWCoinType.h"
+#include "TWData.h"
+
+TW_EXTERN_C_BEGIN
+
+/// EIP-2612 and Permit2 permit signer.
+TW_EXPORT_STRUCT
+struct TWEthereumPermit;
+
+/// Builds and signs an EIP-2612 or Permit2 permit.
+/// Returns the EIP-712 typed data and its pre-hash only if no private key is specified (external signing).
+///
+/// \param coin EVM-compatible coin type.
+/// \param input Non-null serialized `Ethereum::Proto::PermitSigningInput`.
+/// \return serialized `Ethereum::Proto::PermitSigningOutput`.
+TW_EXPORT_STATIC_METHOD
+TWData* _Nonnull TWEthereumPermitSign(enum TWCoinType coin, TWData* _Nonnull input);
+
+/// Builds the `permit(...)` call of an EIP-2612 or Permit2 permit signed externally.
+/// The signature must be produced by the permit owner over the `pre_hash` returned by `TWEthereumPermitSign`.
+///
+/// \param coin EVM-compatible coin type.
+/// \param input Non-null serialized `Ethereum::Proto::PermitSigningInput` with the `owner` set.
+/// \param signature Non-null 65 bytes `r || s || v` signature.
+/// \return serialized `Ethereum::Proto::PermitSigningOutput`.
+TW_EXPORT_STATIC_METHOD
+TWData* _Nonnull TWEthereumPermitCompile(enum TWCoinType coin, TWData* _Nonnull input, TWData* _Nonnull signature);
+
+TW_EXTERN_C_END
@@ -0,0 +1,26 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+#pragma once
+
+#include "TWBase.h"
+#include "TWCoinType.h"
+#include "TWData.h"
+
+TW_EXTERN_C_BEGIN
+
+/// Sign-In with Ethereum (EIP-4361) message verifier.
+TW_EXPORT_STRUCT
+struct TWEthereumSiwe;
+
+/// Verifies a Sign-In with Ethereum (EIP-4361) message signature.
+/// Returns an encoded EIP-1271 `isValidSignature` call to verify smart contract account signatures.
+///
+/// \param coin EVM-compatible coin type.
+/// \param input Non-null serialized `Ethereum::Proto::SiweVerifyingInput`.
+/// \return serialized `Ethereum::Proto::SiweVerifyingOutput`.
+TW_EXPORT_STATIC_METHOD
+TWData* _Nonnull TWEthereumSiweVerify(enum TWCoinType coin, TWData* _Nonnull input);
+
+TW_EXTERN_C_END
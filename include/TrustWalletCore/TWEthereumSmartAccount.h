@@ -0,0 +1,26 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+#pragma once
+
+#include "TWBase.h"
+#include "TWCoinType.h"
+#include "TWData.h"
+
+TW_EXTERN_C_BEGIN
+
+/// ERC-4337 smart account utilities.
+TW_EXPORT_STRUCT
+struct TWEthereumSmartAccount;
+
+/// Derives the counterfactual address of an ERC-4337 smart account.
+/// Also returns the factory call data to be set as the user operation `factory_data` or `init_code`.
+///
+/// \param coin EVM-compatible coin type.
+/// \param input Non-null serialized `Ethereum::Proto::SmartAccountAddressInput`.
+/// \return serialized `Ethereum::Proto::SmartAccountAddressOutput`.
+TW_EXPORT_STATIC_METHOD
+TWData* _Nonnull TWEthereumSmartAccountAddress(enum TWCoinType coin, TWData* _Nonnull input);
+
+TW_EXTERN_C_END
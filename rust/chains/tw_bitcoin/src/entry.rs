@@ -1,6 +1,8 @@
 use crate::modules::compiler::BitcoinCompiler;
+use crate::modules::message_signer::BitcoinMessageSigner;
 use crate::modules::planner::BitcoinPlanner;
 use crate::modules::signer::BitcoinSigner;
+use crate::modules::transaction_decoder::BitcoinTransactionDecoder;
 use crate::modules::transaction_util::BitcoinTransactionUtil;
 use std::str::FromStr;
 use tw_coin_entry::coin_context::CoinContext;
@@ -8,8 +10,6 @@ use tw_coin_entry::coin_entry::{CoinEntry, PublicKeyBytes, SignatureBytes};
 use tw_coin_entry::derivation::Derivation;
 use tw_coin_entry::error::prelude::*;
 use tw_coin_entry::modules::json_signer::NoJsonSigner;
-use tw_coin_entry::modules::message_signer::NoMessageSigner;
-use tw_coin_entry::modules::transaction_decoder::NoTransactionDecoder;
 use tw_coin_entry::modules::wallet_connector::NoWalletConnector;
 use tw_keypair::tw::PublicKey;
 use tw_proto::BitcoinV2::Proto;
@@ -27,9 +27,9 @@ impl CoinEntry for BitcoinEntry {
     // Optional modules:
     type JsonSigner = NoJsonSigner;
     type PlanBuilder = BitcoinPlanner;
-    type MessageSigner = NoMessageSigner;
+    type MessageSigner = BitcoinMessageSigner;
     type WalletConnector = NoWalletConnector;
-    type TransactionDecoder = NoTransactionDecoder;
+    type TransactionDecoder = BitcoinTransactionDecoder;
     type TransactionUtil = BitcoinTransactionUtil;
 
     #[inline]
@@ -92,6 +92,16 @@ impl CoinEntry for BitcoinEntry {
         Some(BitcoinPlanner)
     }
 
+    #[inline]
+    fn message_signer(&self) -> Option<Self::MessageSigner> {
+        Some(BitcoinMessageSigner)
+    }
+
+    #[inline]
+    fn transaction_decoder(&self) -> Option<Self::TransactionDecoder> {
+        Some(BitcoinTransactionDecoder)
+    }
+
     #[inline]
     fn transaction_util(&self) -> Option<Self::TransactionUtil> {
         Some(BitcoinTransactionUtil)
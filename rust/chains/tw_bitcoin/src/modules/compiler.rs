@@ -2,6 +2,8 @@
 //
 // Copyright © 2017 Trust Wallet.
 
+use crate::modules::fee_bumping::child_pays_for_parent::ChildPaysForParentBuilder;
+use crate::modules::fee_bumping::replace_by_fee::ReplaceByFeeBuilder;
 use crate::modules::protobuf_builder::ProtobufBuilder;
 use crate::modules::psbt_request::PsbtRequest;
 use crate::modules::signing_request::SigningRequestBuilder;
@@ -47,6 +49,16 @@ impl BitcoinCompiler {
                 TxPlanner::plan(request)?.unsigned_tx
             },
             TransactionType::psbt(ref psbt) => PsbtRequest::build(&input, psbt)?.unsigned_tx,
+            TransactionType::replace_by_fee(ref rbf) => {
+                let tx_builder = ReplaceByFeeBuilder::tx_builder(coin, &input, rbf)?;
+                let request = SigningRequestBuilder::build(coin, &input, &tx_builder)?;
+                TxPlanner::plan(request)?.unsigned_tx
+            },
+            TransactionType::child_pays_for_parent(ref cpfp) => {
+                let tx_builder = ChildPaysForParentBuilder::tx_builder(coin, &input, cpfp)?;
+                let request = SigningRequestBuilder::build(coin, &input, &tx_builder)?;
+                TxPlanner::plan(request)?.unsigned_tx
+            },
             TransactionType::None => {
                 return SigningError::err(SigningErrorType::Error_invalid_params)
                     .context("Either `TransactionBuilder` or `Psbt` should be set")
@@ -93,6 +105,14 @@ impl BitcoinCompiler {
                 Self::compile_with_tx_builder(coin, &input, tx, signatures)
             },
             TransactionType::psbt(ref psbt) => Self::compile_psbt(coin, &input, psbt, signatures),
+            TransactionType::replace_by_fee(ref rbf) => {
+                let tx = ReplaceByFeeBuilder::tx_builder(coin, &input, rbf)?;
+                Self::compile_with_tx_builder(coin, &input, &tx, signatures)
+            },
+            TransactionType::child_pays_for_parent(ref cpfp) => {
+                let tx = ChildPaysForParentBuilder::tx_builder(coin, &input, cpfp)?;
+                Self::compile_with_tx_builder(coin, &input, &tx, signatures)
+            },
             TransactionType::None => SigningError::err(SigningErrorType::Error_invalid_params)
                 .context("No transaction type specified"),
         }
@@ -0,0 +1,98 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::modules::fee_bumping::{
+    decode_transaction, fee_rate_for, plan_tx_builder, ProtoDustPolicy,
+};
+use std::borrow::Cow;
+use tw_coin_entry::coin_context::CoinContext;
+use tw_coin_entry::error::prelude::*;
+use tw_proto::BitcoinV2::Proto;
+use tw_proto::BitcoinV2::Proto::mod_ChildPaysForParent::OneOfdust_policy as CpfpDustPolicy;
+use tw_utxo::modules::fee_estimator::FeeEstimator;
+
+pub struct ChildPaysForParentBuilder;
+
+impl ChildPaysForParentBuilder {
+    /// Builds a child transaction that spends the given output of an unconfirmed parent transaction,
+    /// and pays a fee high enough so the parent and child transactions together
+    /// have at least the target package fee rate.
+    pub fn tx_builder<'a>(
+        coin: &dyn CoinContext,
+        input: &Proto::SigningInput<'a>,
+        cpfp: &Proto::ChildPaysForParent<'a>,
+    ) -> SigningResult<Proto::TransactionBuilder<'a>> {
+        let parent_tx = decode_transaction(&cpfp.parent_transaction)?;
+
+        let parent_output = parent_tx
+            .outputs
+            .get(cpfp.parent_output_index as usize)
+            .or_tw_err(SigningErrorType::Error_invalid_params)
+            .with_context(|| {
+                format!(
+                    "Parent transaction has no output at index {}",
+                    cpfp.parent_output_index
+                )
+            })?;
+        let parent_utxo_proto = cpfp
+            .parent_output
+            .as_ref()
+            .or_tw_err(SigningErrorType::Error_invalid_params)
+            .context("No parent output claiming script provided")?;
+
+        // Transaction ID in the little-endian order.
+        let mut parent_hash = parent_tx.txid();
+        parent_hash.reverse();
+
+        let parent_utxo = Proto::Input {
+            out_point: Some(Proto::OutPoint {
+                hash: Cow::from(parent_hash),
+                vout: cpfp.parent_output_index,
+            }),
+            value: parent_output.value,
+            ..parent_utxo_proto.clone()
+        };
+
+        let mut inputs = Vec::with_capacity(cpfp.extra_inputs.len() + 1);
+        inputs.push(parent_utxo);
+        inputs.extend(cpfp.extra_inputs.iter().cloned());
+
+        let mut tx_builder = Proto::TransactionBuilder {
+            inputs,
+            input_selector: Proto::InputSelector::UseAll,
+            fee_per_vb: cpfp.package_fee_per_vb,
+            max_amount_output: cpfp.output.clone(),
+            dust_policy: Self::dust_policy(&cpfp.dust_policy),
+            ..Proto::TransactionBuilder::default()
+        };
+
+        // Estimate the child transaction vsize. It doesn't depend on the fee rate
+        // as all the UTXOs are spent and the only output receives the rest.
+        let child_plan = plan_tx_builder(coin, input, &tx_builder)?;
+
+        // The child transaction should pay for the parent too.
+        let package_fee = FeeEstimator::estimate_fee(&parent_tx, cpfp.package_fee_per_vb)?
+            + child_plan.fee_estimate;
+        let child_fee = package_fee - cpfp.parent_fee;
+
+        // The child fee rate should not be lower than the package fee rate,
+        // even if the parent pays enough already.
+        let child_fee_rate = fee_rate_for(child_fee, child_plan.vsize_estimate)?;
+        tx_builder.fee_per_vb = child_fee_rate.max(cpfp.package_fee_per_vb);
+
+        Ok(tx_builder)
+    }
+
+    fn dust_policy(proto: &CpfpDustPolicy) -> ProtoDustPolicy {
+        match proto {
+            CpfpDustPolicy::fixed_dust_threshold(fixed) => {
+                ProtoDustPolicy::fixed_dust_threshold(*fixed)
+            },
+            CpfpDustPolicy::dynamic_dust_min_relay_fee_per_kvb(fee_rate) => {
+                ProtoDustPolicy::dynamic_dust_min_relay_fee_per_kvb(*fee_rate)
+            },
+            CpfpDustPolicy::None => ProtoDustPolicy::None,
+        }
+    }
+}
@@ -0,0 +1,57 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+//! Fee bumping of unconfirmed transactions.
+//!
+//! Both [`replace_by_fee::ReplaceByFeeBuilder`] and [`child_pays_for_parent::ChildPaysForParentBuilder`]
+//! produce a standard [`Proto::TransactionBuilder`] that is planned, signed or compiled as usual.
+
+use crate::modules::signing_request::SigningRequestBuilder;
+use tw_coin_entry::coin_context::CoinContext;
+use tw_coin_entry::error::prelude::*;
+use tw_proto::BitcoinV2::Proto;
+use tw_utxo::decode::decode;
+use tw_utxo::modules::tx_planner::TxPlanner;
+use tw_utxo::modules::utxo_selector::SelectPlan;
+use tw_utxo::transaction::standard_transaction::Transaction;
+use tw_utxo::transaction::transaction_parts::Amount;
+
+pub mod child_pays_for_parent;
+pub mod replace_by_fee;
+
+type ProtoDustPolicy = Proto::mod_TransactionBuilder::OneOfdust_policy;
+
+/// Parses a signed transaction to be bumped.
+fn decode_transaction(encoded: &[u8]) -> SigningResult<Transaction> {
+    decode(encoded).context("Error decoding the transaction to be bumped")
+}
+
+/// Plans the transaction to get its estimated fee and vsize.
+fn plan_tx_builder(
+    coin: &dyn CoinContext,
+    input: &Proto::SigningInput,
+    tx_builder: &Proto::TransactionBuilder,
+) -> SigningResult<SelectPlan> {
+    let request = SigningRequestBuilder::build(coin, input, tx_builder)?;
+    Ok(TxPlanner::plan(request)?.plan)
+}
+
+/// Returns the smallest fee rate at which a transaction of `vsize` pays at least `fee`.
+fn fee_rate_for(fee: Amount, vsize: usize) -> SigningResult<Amount> {
+    let vsize = Amount::try_from(vsize)
+        .ok()
+        .filter(|vsize| *vsize > 0)
+        .or_tw_err(SigningErrorType::Error_internal)
+        .context("Invalid transaction vsize")?;
+    Ok((fee + vsize - 1) / vsize)
+}
+
+fn transaction_version(version: i32) -> SigningResult<Proto::TransactionVersion> {
+    match version {
+        1 => Ok(Proto::TransactionVersion::V1),
+        2 => Ok(Proto::TransactionVersion::V2),
+        _ => SigningError::err(SigningErrorType::Error_not_supported)
+            .context(format!("Unsupported transaction version: {version}")),
+    }
+}
@@ -0,0 +1,241 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::modules::fee_bumping::{
+    decode_transaction, fee_rate_for, plan_tx_builder, transaction_version, ProtoDustPolicy,
+};
+use crate::modules::signing_request::SigningRequestBuilder;
+use crate::modules::tx_builder::output_protobuf::OutputProtobuf;
+use crate::modules::tx_builder::utxo_protobuf::parse_out_point;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use tw_coin_entry::coin_context::CoinContext;
+use tw_coin_entry::error::prelude::*;
+use tw_encoding::hex::ToHex;
+use tw_proto::BitcoinV2::Proto;
+use tw_proto::BitcoinV2::Proto::mod_ReplaceByFee::OneOfdust_policy as RbfDustPolicy;
+use tw_utxo::modules::utxo_selector::SelectPlan;
+use tw_utxo::transaction::transaction_interface::TransactionInterface;
+use tw_utxo::transaction::transaction_parts::{Amount, OutPoint};
+
+/// The max `nSequence` value that signals replaceability (BIP125).
+pub const MAX_BIP125_RBF_SEQUENCE: u32 = 0xFFFF_FFFD;
+/// Bitcoin Core's default `-incrementalrelayfee` is 1000 satoshis per kvB.
+pub const DEFAULT_INCREMENTAL_RELAY_FEE_PER_VB: Amount = 1;
+/// The replacement fee rate can be raised several times as the transaction size may change.
+const MAX_FEE_RATE_ATTEMPTS: usize = 3;
+
+pub struct ReplaceByFeeBuilder;
+
+impl ReplaceByFeeBuilder {
+    /// Builds a replacement transaction that spends the same UTXOs as the original transaction,
+    /// sends the same amounts to the same recipients, but pays a higher fee.
+    ///
+    /// All the original UTXOs are always spent, so the replacement conflicts with the original transaction.
+    /// The increased fee is paid by reducing the change output and spending additional UTXOs if needed.
+    /// The fee rate is raised if it's not enough to satisfy BIP125 rules:
+    /// * The replacement pays an absolute fee of at least the original fee (rule #3).
+    /// * The replacement pays for its own bandwidth at the incremental relay fee rate (rule #4).
+    /// * The replacement fee rate is higher than the original one (rule #6).
+    pub fn tx_builder<'a>(
+        coin: &dyn CoinContext,
+        input: &Proto::SigningInput<'a>,
+        rbf: &Proto::ReplaceByFee<'a>,
+    ) -> SigningResult<Proto::TransactionBuilder<'a>> {
+        let original_tx = decode_transaction(&rbf.original_transaction)?;
+        let chain_info = SigningRequestBuilder::chain_info(coin, &input.chain_info)?;
+
+        // Prepare a map of the provided UTXOs `{ OutPoint -> Input }`.
+        let mut utxos = HashMap::with_capacity(rbf.inputs.len());
+        for utxo in rbf.inputs.iter() {
+            let out_point = parse_out_point(&utxo.out_point)?;
+            if utxos.insert(out_point, utxo).is_some() {
+                return SigningError::err(SigningErrorType::Error_invalid_utxo)
+                    .context("Provided duplicate UTXOs with the same OutPoint");
+            }
+        }
+
+        // Spend the same UTXOs as the original transaction first.
+        let mut original_inputs = Vec::with_capacity(original_tx.inputs.len());
+        let mut original_total_in: Amount = 0;
+        for original_input in original_tx.inputs.iter() {
+            let out_point = original_input.previous_output;
+            let utxo = utxos
+                .remove(&out_point)
+                .or_tw_err(SigningErrorType::Error_missing_input_utxos)
+                .with_context(|| {
+                    format!(
+                        "UTXO spent by the original transaction is not provided: {}",
+                        display_out_point(&out_point)
+                    )
+                })?;
+
+            original_total_in += utxo.value;
+            original_inputs.push(Self::signaling_input(utxo, original_input.sequence));
+        }
+
+        // Additional UTXOs can be spent if the original ones don't cover the increased fee.
+        let mut additional_inputs = Vec::with_capacity(utxos.len());
+        for utxo in rbf.inputs.iter() {
+            let out_point = parse_out_point(&utxo.out_point)?;
+            if utxos.contains_key(&out_point) {
+                let sequence = utxo
+                    .sequence
+                    .as_ref()
+                    .map(|seq| seq.sequence)
+                    .unwrap_or(u32::MAX);
+                additional_inputs.push(Self::signaling_input(utxo, sequence));
+            }
+        }
+
+        let original_total_out = original_tx
+            .outputs
+            .iter()
+            .map(|output| output.value)
+            .sum::<Amount>();
+        let original_fee = original_total_in - original_total_out;
+        if original_fee < 0 {
+            return SigningError::err(SigningErrorType::Error_invalid_utxo_amount)
+                .context("Provided UTXO amounts are less than the original transaction outputs");
+        }
+
+        // Keep all the original outputs except the change.
+        // If the original transaction has no change, it will be added if there is enough left.
+        let change_script_pubkey = rbf
+            .change_output
+            .as_ref()
+            .map(|change_output| {
+                OutputProtobuf::new(&chain_info, change_output)
+                    .output_from_proto()
+                    .context("Error creating Change Output from Proto")
+            })
+            .transpose()?
+            .map(|change_output| change_output.script_pubkey);
+
+        let mut change_found = false;
+        let mut outputs = Vec::with_capacity(original_tx.outputs.len());
+        for output in original_tx.outputs.iter() {
+            if !change_found && Some(&output.script_pubkey) == change_script_pubkey.as_ref() {
+                change_found = true;
+                continue;
+            }
+            outputs.push(Proto::Output {
+                value: output.value,
+                to_recipient: Proto::mod_Output::OneOfto_recipient::custom_script_pubkey(
+                    Cow::from(output.script_pubkey.to_vec()),
+                ),
+            });
+        }
+
+        let mut tx_builder = Proto::TransactionBuilder {
+            version: transaction_version(original_tx.version)?,
+            lock_time: original_tx.locktime,
+            inputs: original_inputs,
+            outputs,
+            // Spend all the original UTXOs, and the additional ones appended by `plan_spending_original_inputs`.
+            input_selector: Proto::InputSelector::UseAll,
+            fee_per_vb: rbf.fee_per_vb,
+            change_output: rbf.change_output.clone(),
+            dust_policy: Self::dust_policy(&rbf.dust_policy),
+            ..Proto::TransactionBuilder::default()
+        };
+
+        let incremental_relay_fee = match rbf.incremental_relay_fee_per_vb {
+            0 => DEFAULT_INCREMENTAL_RELAY_FEE_PER_VB,
+            fee_rate => fee_rate,
+        };
+
+        for _ in 0..MAX_FEE_RATE_ATTEMPTS {
+            let plan = Self::plan_spending_original_inputs(
+                coin,
+                input,
+                &mut tx_builder,
+                original_tx.inputs.len(),
+                &additional_inputs,
+            )?;
+
+            let min_fee = original_fee + incremental_relay_fee * plan.vsize_estimate as Amount;
+            let pays_higher_fee_rate = Self::fee_rate_is_higher(
+                (plan.fee_estimate, plan.vsize_estimate),
+                (original_fee, original_tx.vsize()),
+            );
+            if plan.fee_estimate >= min_fee && pays_higher_fee_rate {
+                return Ok(tx_builder);
+            }
+
+            // Raise the fee rate and try again as the transaction may change.
+            let min_fee_rate = fee_rate_for(min_fee, plan.vsize_estimate)?;
+            tx_builder.fee_per_vb = min_fee_rate.max(tx_builder.fee_per_vb + 1);
+        }
+
+        SigningError::err(SigningErrorType::Error_wrong_fee)
+            .context("Cannot find a fee rate to replace the original transaction")
+    }
+
+    /// Plans the transaction that spends all the original UTXOs,
+    /// and appends as few additional UTXOs as needed to cover the outputs and the fee.
+    /// The `tx_builder` inputs are updated accordingly.
+    fn plan_spending_original_inputs<'a>(
+        coin: &dyn CoinContext,
+        input: &Proto::SigningInput<'a>,
+        tx_builder: &mut Proto::TransactionBuilder<'a>,
+        original_inputs_len: usize,
+        additional_inputs: &[Proto::Input<'a>],
+    ) -> SigningResult<SelectPlan> {
+        let mut additional_count = 0;
+        loop {
+            tx_builder.inputs.truncate(original_inputs_len);
+            tx_builder
+                .inputs
+                .extend_from_slice(&additional_inputs[..additional_count]);
+
+            match plan_tx_builder(coin, input, tx_builder) {
+                Err(e)
+                    if *e.error_type() == SigningErrorType::Error_not_enough_utxos
+                        && additional_count < additional_inputs.len() =>
+                {
+                    additional_count += 1;
+                },
+                result => return result,
+            }
+        }
+    }
+
+    /// Returns the UTXO that signals replaceability (BIP125).
+    /// Note that lower `nSequence` values (e.g. relative lock-times) are kept as is.
+    fn signaling_input<'a>(utxo: &Proto::Input<'a>, sequence: u32) -> Proto::Input<'a> {
+        Proto::Input {
+            sequence: Some(Proto::mod_Input::Sequence {
+                sequence: sequence.min(MAX_BIP125_RBF_SEQUENCE),
+            }),
+            ..utxo.clone()
+        }
+    }
+
+    /// Checks if `fee / vsize` is greater than `original_fee / original_vsize`.
+    fn fee_rate_is_higher(
+        (fee, vsize): (Amount, usize),
+        (original_fee, original_vsize): (Amount, usize),
+    ) -> bool {
+        fee as i128 * original_vsize as i128 > original_fee as i128 * vsize as i128
+    }
+
+    fn dust_policy(proto: &RbfDustPolicy) -> ProtoDustPolicy {
+        match proto {
+            RbfDustPolicy::fixed_dust_threshold(fixed) => {
+                ProtoDustPolicy::fixed_dust_threshold(*fixed)
+            },
+            RbfDustPolicy::dynamic_dust_min_relay_fee_per_kvb(fee_rate) => {
+                ProtoDustPolicy::dynamic_dust_min_relay_fee_per_kvb(*fee_rate)
+            },
+            RbfDustPolicy::None => ProtoDustPolicy::None,
+        }
+    }
+}
+
+fn display_out_point(out_point: &OutPoint) -> String {
+    let mut txid = out_point.hash.to_vec();
+    txid.reverse();
+    format!("{}:{}", txid.to_hex(), out_point.index)
+}
@@ -0,0 +1,365 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::modules::message_signer::SignerAddress;
+use bitcoin::hashes::Hash;
+use bitcoin::sighash::{Prevouts, SighashCache, TapSighashType};
+use tw_coin_entry::error::prelude::*;
+use tw_hash::hasher::sha256_d;
+use tw_hash::sha2::sha256;
+use tw_hash::H256;
+use tw_keypair::{ecdsa, schnorr};
+use tw_memory::Data;
+use tw_utxo::decode::decode;
+use tw_utxo::encode::encode;
+use tw_utxo::modules::keys_manager::KeysManager;
+use tw_utxo::modules::sighash_computer::{SighashComputer, TxPreimage, UtxoSighash};
+use tw_utxo::modules::sighash_verifier::SighashVerifier;
+use tw_utxo::modules::tx_signer::TxSigner;
+use tw_utxo::script::standard_script::opcodes::{OP_PUSHBYTES_0, OP_RETURN};
+use tw_utxo::script::{Script, Witness};
+use tw_utxo::sighash::SighashType;
+use tw_utxo::transaction::standard_transaction::builder::{TransactionBuilder, UtxoBuilder};
+use tw_utxo::transaction::standard_transaction::{
+    Transaction, TransactionInput, TransactionOutput,
+};
+use tw_utxo::transaction::transaction_parts::OutPoint;
+use tw_utxo::transaction::unsigned_transaction::UnsignedTransaction;
+
+const BIP322_TAG: &[u8] = b"BIP0322-signed-message";
+const SCHNORR_SIGNATURE_LEN: usize = 64;
+
+/// `to_sign` transaction fields that can be chosen by the signer of a full signature.
+#[derive(Default)]
+struct ToSignParams {
+    version: u32,
+    locktime: u32,
+    sequence: u32,
+    sighash_ty: SighashType,
+}
+
+/// BIP322 generic message signatures.
+/// Only P2WPKH and P2TR (key path) addresses are supported.
+pub struct Bip322Message;
+
+impl Bip322Message {
+    /// Returns `tagged_hash("BIP0322-signed-message", message)`.
+    pub fn message_hash(message: &str) -> H256 {
+        let tag_hash = sha256(BIP322_TAG);
+        let mut data = Vec::with_capacity(tag_hash.len() * 2 + message.len());
+        data.extend_from_slice(&tag_hash);
+        data.extend_from_slice(&tag_hash);
+        data.extend_from_slice(message.as_bytes());
+
+        H256::try_from(sha256(&data).as_slice()).expect("sha256 hash length is 32 bytes")
+    }
+
+    /// Returns the sighash of the default `to_sign` transaction.
+    pub fn preimage_hash(
+        message: &str,
+        address: &SignerAddress,
+        public_key: &ecdsa::secp256k1::PublicKey,
+    ) -> SigningResult<H256> {
+        let sighash = Self::sighash(message, address, Some(public_key), &ToSignParams::default())?;
+        Ok(sighash.sighash)
+    }
+
+    /// Returns the witness stack of the signed `to_sign` transaction.
+    pub fn sign_simple(
+        message: &str,
+        address: &SignerAddress,
+        private_key: &[u8],
+        dangerous_use_fixed_schnorr_rng: bool,
+    ) -> SigningResult<Data> {
+        let to_sign = Self::sign(
+            message,
+            address,
+            private_key,
+            dangerous_use_fixed_schnorr_rng,
+        )?;
+        Ok(encode(&to_sign.inputs[0].witness))
+    }
+
+    /// Returns the signed `to_sign` transaction.
+    pub fn sign_full(
+        message: &str,
+        address: &SignerAddress,
+        private_key: &[u8],
+        dangerous_use_fixed_schnorr_rng: bool,
+    ) -> SigningResult<Data> {
+        let to_sign = Self::sign(
+            message,
+            address,
+            private_key,
+            dangerous_use_fixed_schnorr_rng,
+        )?;
+        Ok(to_sign.encode_out())
+    }
+
+    /// Verifies either a simple or a full signature.
+    pub fn verify(message: &str, address: &SignerAddress, signature: &[u8]) -> SigningResult<()> {
+        let (witness, params) = match decode::<Witness>(signature) {
+            Ok(witness) => (witness, ToSignParams::default()),
+            Err(_) => Self::decode_full_signature(message, address, signature)?,
+        };
+
+        match (address, witness.as_items()) {
+            (SignerAddress::P2WPKH(_), [signature, public_key]) => {
+                let (sighash_ty, der_signature) = signature
+                    .as_slice()
+                    .split_last()
+                    .or_tw_err(SigningErrorType::Error_input_parse)
+                    .context("Empty P2WPKH witness signature")?;
+                let params = ToSignParams {
+                    sighash_ty: SighashType::from_u32(*sighash_ty as u32)?,
+                    ..params
+                };
+
+                let signer_pubkey = ecdsa::secp256k1::PublicKey::try_from(public_key.as_slice())
+                    .into_tw()
+                    .context("Invalid P2WPKH witness public key")?;
+                let sighash = Self::sighash(message, address, Some(&signer_pubkey), &params)?;
+                SighashVerifier::<Transaction>::verify_ecdsa_signature(
+                    &sighash,
+                    public_key.as_slice(),
+                    der_signature,
+                )
+            },
+            (SignerAddress::P2TR(tweaked_pubkey), [signature]) => {
+                let mut sighash = Self::sighash(message, address, None, &params)?;
+                // 65 bytes signature is followed by an explicit sighash type.
+                if let Some(hash_ty) = signature.as_slice().get(SCHNORR_SIGNATURE_LEN) {
+                    sighash.sighash =
+                        Self::taproot_sighash_with_type(message, address, &params, *hash_ty)?;
+                }
+
+                let signature_len = signature.len().min(SCHNORR_SIGNATURE_LEN);
+                SighashVerifier::<Transaction>::verify_schnorr_signature(
+                    &sighash,
+                    tweaked_pubkey.as_slice(),
+                    &signature.as_slice()[..signature_len],
+                )
+            },
+            (SignerAddress::P2WPKH(_) | SignerAddress::P2TR(_), _) => {
+                SigningError::err(SigningErrorType::Error_script_witness_program)
+                    .context("Unexpected number of BIP322 witness items")
+            },
+            _ => Self::unsupported_address(),
+        }
+    }
+
+    fn sign(
+        message: &str,
+        address: &SignerAddress,
+        private_key: &[u8],
+        dangerous_use_fixed_schnorr_rng: bool,
+    ) -> SigningResult<Transaction> {
+        let ecdsa_private = ecdsa::secp256k1::PrivateKey::try_from(private_key)
+            .into_tw()
+            .context("Invalid ecdsa secp256k1 private key")?;
+        let public_key = ecdsa_private.public();
+        if !address.is_owned_by(&public_key.compressed()) {
+            return SigningError::err(SigningErrorType::Error_invalid_address)
+                .context("The address doesn't belong to the private key");
+        }
+
+        let mut keys_manager = KeysManager::default();
+        match address {
+            SignerAddress::P2WPKH(_) => {
+                keys_manager.add_ecdsa_private(ecdsa_private);
+            },
+            SignerAddress::P2TR(_) => {
+                let schnorr_private = schnorr::PrivateKey::try_from(private_key)
+                    .into_tw()
+                    .context("Invalid schnorr private key")?;
+                if dangerous_use_fixed_schnorr_rng {
+                    keys_manager.add_schnorr_private(schnorr_private.no_aux_rand());
+                } else {
+                    keys_manager.add_schnorr_private(schnorr_private);
+                }
+            },
+            _ => return Self::unsupported_address(),
+        }
+
+        let to_sign = Self::to_sign_tx(
+            message,
+            address,
+            Some(&public_key),
+            &ToSignParams::default(),
+        )?;
+        TxSigner::sign_tx(to_sign, &keys_manager).context("Error signing BIP322 'to_sign' tx")
+    }
+
+    /// Decodes a full signature, i.e. the signed `to_sign` transaction,
+    /// and checks that it spends the `to_spend` transaction to an `OP_RETURN` output.
+    fn decode_full_signature(
+        message: &str,
+        address: &SignerAddress,
+        signature: &[u8],
+    ) -> SigningResult<(Witness, ToSignParams)> {
+        let to_sign: Transaction =
+            decode(signature).context("Signature is neither a BIP322 simple nor full signature")?;
+
+        let to_spend_out_point = OutPoint {
+            hash: Self::to_spend_txid(message, address),
+            index: 0,
+        };
+        let (input, output) = match (to_sign.inputs.as_slice(), to_sign.outputs.as_slice()) {
+            ([input], [output]) => (input, output),
+            _ => {
+                return SigningError::err(SigningErrorType::Error_input_parse)
+                    .context("BIP322 'to_sign' tx must have exactly one input and one output")
+            },
+        };
+
+        if input.previous_output != to_spend_out_point || !input.script_sig.is_empty() {
+            return SigningError::err(SigningErrorType::Error_input_parse)
+                .context("BIP322 'to_sign' tx must spend the 'to_spend' tx output");
+        }
+        if output.value != 0 || output.script_pubkey != op_return_script() {
+            return SigningError::err(SigningErrorType::Error_input_parse)
+                .context("BIP322 'to_sign' tx must have a single empty OP_RETURN output");
+        }
+
+        let params = ToSignParams {
+            version: to_sign.version as u32,
+            locktime: to_sign.locktime,
+            sequence: input.sequence,
+            ..ToSignParams::default()
+        };
+        Ok((input.witness.clone(), params))
+    }
+
+    fn sighash(
+        message: &str,
+        address: &SignerAddress,
+        public_key: Option<&ecdsa::secp256k1::PublicKey>,
+        params: &ToSignParams,
+    ) -> SigningResult<UtxoSighash> {
+        let to_sign = Self::to_sign_tx(message, address, public_key, params)?;
+        let TxPreimage { mut sighashes } = SighashComputer::preimage_tx(&to_sign)?;
+        sighashes
+            .pop()
+            .or_tw_err(SigningErrorType::Error_internal)
+            .context("BIP322 'to_sign' tx must have one sighash")
+    }
+
+    /// Computes the Taproot key-path sighash with an explicit sighash type.
+    /// The `bitcoin` crate is used as `tw_utxo` always serializes `SIGHASH_ALL` as `SIGHASH_DEFAULT`.
+    fn taproot_sighash_with_type(
+        message: &str,
+        address: &SignerAddress,
+        params: &ToSignParams,
+        hash_ty: u8,
+    ) -> SigningResult<H256> {
+        let hash_ty = match TapSighashType::from_consensus_u8(hash_ty) {
+            Ok(TapSighashType::Default) | Err(_) => {
+                return SigningError::err(SigningErrorType::Error_input_parse)
+                    .context(format!("Invalid Taproot signature sighash type: {hash_ty}"))
+            },
+            Ok(hash_ty) => hash_ty,
+        };
+
+        let to_sign = Self::to_sign_tx(message, address, None, params)?;
+        let tx: bitcoin::Transaction =
+            bitcoin::consensus::deserialize(&to_sign.transaction().encode_out())
+                .tw_err(|_| SigningErrorType::Error_internal)
+                .context("Error converting BIP322 'to_sign' tx")?;
+        let prevout = bitcoin::TxOut {
+            value: 0,
+            script_pubkey: bitcoin::ScriptBuf::from_bytes(address.script_pubkey().to_vec()),
+        };
+
+        let sighash = SighashCache::new(&tx)
+            .taproot_key_spend_signature_hash(0, &Prevouts::All(&[prevout]), hash_ty)
+            .tw_err(|_| SigningErrorType::Error_internal)
+            .context("Error computing Taproot sighash")?;
+        Ok(H256::from(sighash.to_byte_array()))
+    }
+
+    /// Builds the virtual `to_sign` transaction spending the `to_spend` transaction output.
+    /// The public key is required for P2WPKH addresses.
+    fn to_sign_tx(
+        message: &str,
+        address: &SignerAddress,
+        public_key: Option<&ecdsa::secp256k1::PublicKey>,
+        params: &ToSignParams,
+    ) -> SigningResult<UnsignedTransaction<Transaction>> {
+        let utxo_builder = UtxoBuilder::default()
+            .prev_txid(Self::to_spend_txid(message, address))
+            .prev_index(0)
+            .sequence(params.sequence)
+            .amount(0)
+            .sighash_type(params.sighash_ty);
+
+        let (input, utxo) = match (address, public_key) {
+            (SignerAddress::P2WPKH(_), Some(public_key)) => utxo_builder.p2wpkh(public_key)?,
+            (SignerAddress::P2WPKH(_), None) => {
+                return SigningError::err(SigningErrorType::Error_internal)
+                    .context("P2WPKH 'to_sign' tx requires a public key")
+            },
+            (SignerAddress::P2TR(tweaked_pubkey), _) => {
+                let tweaked_pubkey = schnorr::XOnlyPublicKey::try_from(tweaked_pubkey.as_slice())
+                    .into_tw()
+                    .context("Invalid Taproot output public key")?;
+                utxo_builder.p2tr_key_path_with_tweaked_pubkey(&tweaked_pubkey)?
+            },
+            _ => return Self::unsupported_address(),
+        };
+
+        if utxo.prevout_script_pubkey != address.script_pubkey() {
+            return SigningError::err(SigningErrorType::Error_invalid_address)
+                .context("The public key doesn't belong to the address");
+        }
+
+        let mut builder = TransactionBuilder::default();
+        builder
+            .version(params.version)
+            .lock_time(params.locktime)
+            .push_input(input, utxo)
+            .push_output(TransactionOutput {
+                value: 0,
+                script_pubkey: op_return_script(),
+            });
+        builder.build()
+    }
+
+    /// Returns the hash of the virtual `to_spend` transaction in the little-endian order.
+    fn to_spend_txid(message: &str, address: &SignerAddress) -> H256 {
+        let mut script_sig = Script::new();
+        script_sig.push(OP_PUSHBYTES_0);
+        script_sig.push_slice(Self::message_hash(message).as_slice());
+
+        let to_spend = Transaction {
+            version: 0,
+            inputs: vec![TransactionInput {
+                previous_output: OutPoint {
+                    hash: H256::default(),
+                    index: u32::MAX,
+                },
+                sequence: 0,
+                script_sig,
+                witness: Witness::default(),
+            }],
+            outputs: vec![TransactionOutput {
+                value: 0,
+                script_pubkey: address.script_pubkey(),
+            }],
+            locktime: 0,
+        };
+
+        let txid = sha256_d(&to_spend.encode_out());
+        H256::try_from(txid.as_slice()).expect("sha256d hash length is 32 bytes")
+    }
+
+    fn unsupported_address<T>() -> SigningResult<T> {
+        SigningError::err(SigningErrorType::Error_not_supported)
+            .context("BIP322 signatures are only supported for P2WPKH and P2TR addresses")
+    }
+}
+
+/// `OP_RETURN` without any data.
+fn op_return_script() -> Script {
+    Script::from(vec![OP_RETURN])
+}
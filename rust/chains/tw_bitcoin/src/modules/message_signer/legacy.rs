@@ -0,0 +1,120 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::modules::message_signer::{public_key_hash, SignerAddress};
+use std::ops::RangeInclusive;
+use tw_coin_entry::error::prelude::*;
+use tw_hash::hasher::sha256_d;
+use tw_hash::H256;
+use tw_keypair::ecdsa::secp256k1;
+use tw_keypair::traits::SigningKeyTrait;
+use tw_memory::Data;
+use tw_utxo::encode::compact_integer::CompactInteger;
+use tw_utxo::encode::stream::Stream;
+
+const MESSAGE_PREFIX: &str = "Bitcoin Signed Message:\n";
+const SIGNATURE_LEN: usize = 65;
+
+/// BIP137 signature header bytes, the recovery ID is added to the header.
+const HEADER_P2PKH_UNCOMPRESSED: u8 = 27;
+const HEADER_P2PKH_COMPRESSED: u8 = 31;
+const HEADER_P2SH_P2WPKH: u8 = 35;
+const HEADER_P2WPKH: u8 = 39;
+const HEADER_RANGE: RangeInclusive<u8> = HEADER_P2PKH_UNCOMPRESSED..=HEADER_P2WPKH + 3;
+
+/// "Bitcoin Signed Message" compact signatures (BIP137).
+pub struct LegacyMessage;
+
+impl LegacyMessage {
+    /// Returns `sha256d(varint(prefix.len) || prefix || varint(message.len) || message)`.
+    pub fn message_hash(message: &str) -> H256 {
+        let mut stream = Stream::new();
+        stream
+            .append(&CompactInteger::from(MESSAGE_PREFIX.len()))
+            .append_raw_slice(MESSAGE_PREFIX.as_bytes())
+            .append(&CompactInteger::from(message.len()))
+            .append_raw_slice(message.as_bytes());
+
+        let hash = sha256_d(&stream.out());
+        H256::try_from(hash.as_slice()).expect("sha256d hash length is 32 bytes")
+    }
+
+    /// Checks whether the signature is a 65 bytes compact signature with a valid header.
+    pub fn is_legacy_signature(signature: &[u8]) -> bool {
+        signature.len() == SIGNATURE_LEN && HEADER_RANGE.contains(&signature[0])
+    }
+
+    /// Signs the message with a compressed public key.
+    /// The header byte depends on the address type as specified in BIP137.
+    pub fn sign(message: &str, address: &SignerAddress, private_key: &[u8]) -> SigningResult<Data> {
+        let private_key = secp256k1::PrivateKey::try_from(private_key)
+            .into_tw()
+            .context("Invalid ecdsa secp256k1 private key")?;
+
+        let header = match address {
+            SignerAddress::P2PKH(_) => HEADER_P2PKH_COMPRESSED,
+            SignerAddress::P2SH(_) => HEADER_P2SH_P2WPKH,
+            SignerAddress::P2WPKH(_) => HEADER_P2WPKH,
+            SignerAddress::P2TR(_) => {
+                return SigningError::err(SigningErrorType::Error_not_supported)
+                    .context("Legacy message signatures are not supported for Taproot addresses, use BIP322 instead");
+            },
+        };
+
+        if !address.is_owned_by(&private_key.public().compressed()) {
+            return SigningError::err(SigningErrorType::Error_invalid_address)
+                .context("The address doesn't belong to the private key");
+        }
+
+        let signature = private_key
+            .sign(Self::message_hash(message))
+            .into_tw()
+            .context("Error signing the message hash")?;
+
+        let mut result = Vec::with_capacity(SIGNATURE_LEN);
+        result.push(header + signature.v());
+        result.extend_from_slice(signature.r().as_slice());
+        result.extend_from_slice(signature.s().as_slice());
+        Ok(result)
+    }
+
+    /// Recovers the public key from the signature and checks if it owns the address.
+    /// Please note the header type isn't required to match the address type,
+    /// as some wallets sign segwit addresses with a P2PKH header.
+    pub fn verify(message: &str, address: &SignerAddress, signature: &[u8]) -> SigningResult<()> {
+        if !Self::is_legacy_signature(signature) {
+            return SigningError::err(SigningErrorType::Error_input_parse)
+                .context("Expected a 65 bytes compact signature");
+        }
+
+        let header = signature[0];
+        let recovery_id = (header - HEADER_P2PKH_UNCOMPRESSED) % 4;
+        let compressed = header >= HEADER_P2PKH_COMPRESSED;
+
+        let r = H256::try_from(&signature[1..33]).expect("'r' length is 32 bytes");
+        let s = H256::try_from(&signature[33..65]).expect("'s' length is 32 bytes");
+        // Some wallets don't normalize the `s` value, but the public key recovery accepts low `s` only.
+        let signature = secp256k1::Signature::try_from_parts(r, s, recovery_id)
+            .into_tw()
+            .context("Invalid compact signature")?
+            .normalize_s();
+
+        let public_key = secp256k1::PublicKey::recover(signature, Self::message_hash(message))
+            .into_tw()
+            .context("Error recovering the public key from the signature")?;
+
+        let is_owned = if compressed {
+            address.is_owned_by(&public_key.compressed())
+        } else {
+            let pubkey_hash = public_key_hash(public_key.uncompressed().as_slice());
+            matches!(address, SignerAddress::P2PKH(expected) if *expected == pubkey_hash)
+        };
+
+        if !is_owned {
+            return SigningError::err(SigningErrorType::Error_signing)
+                .context("The signature doesn't belong to the address");
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,222 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::modules::message_signer::bip322::Bip322Message;
+use crate::modules::message_signer::legacy::LegacyMessage;
+use std::borrow::Cow;
+use tw_coin_entry::coin_context::CoinContext;
+use tw_coin_entry::error::prelude::*;
+use tw_coin_entry::modules::message_signer::MessageSigner;
+use tw_coin_entry::signing_output_error;
+use tw_encoding::base64::{self, STANDARD};
+use tw_hash::ripemd::bitcoin_hash_160;
+use tw_hash::{H160, H256, H264};
+use tw_keypair::ecdsa;
+use tw_proto::BitcoinV2::Proto;
+use tw_proto::TxCompiler::Proto as CompilerProto;
+use tw_utxo::address::standard_bitcoin::StandardBitcoinAddress;
+use tw_utxo::script::standard_script::conditions;
+use tw_utxo::script::Script;
+
+pub mod bip322;
+pub mod legacy;
+
+/// The address a message is signed for.
+#[derive(Clone, Debug)]
+pub enum SignerAddress {
+    /// Public key hash of a P2PKH address.
+    P2PKH(H160),
+    /// Script hash of a P2SH address.
+    P2SH(H160),
+    /// Public key hash of a P2WPKH address.
+    P2WPKH(H160),
+    /// Tweaked x-only public key of a P2TR address.
+    P2TR(H256),
+}
+
+impl SignerAddress {
+    pub fn parse(coin: &dyn CoinContext, address: &str) -> SigningResult<SignerAddress> {
+        let address = StandardBitcoinAddress::from_str_with_coin_and_prefix(coin, address, None)
+            .into_tw()
+            .context("Invalid signer address")?;
+
+        match address {
+            StandardBitcoinAddress::Legacy(legacy) => {
+                if Some(legacy.prefix()) == coin.p2pkh_prefix() {
+                    Ok(SignerAddress::P2PKH(legacy.payload()))
+                } else if Some(legacy.prefix()) == coin.p2sh_prefix() {
+                    Ok(SignerAddress::P2SH(legacy.payload()))
+                } else {
+                    SigningError::err(SigningErrorType::Error_invalid_address)
+                        .context(format!("The '{legacy}' address has unexpected prefix"))
+                }
+            },
+            StandardBitcoinAddress::Segwit(segwit) => H160::try_from(segwit.witness_program())
+                .map(SignerAddress::P2WPKH)
+                .tw_err(|_| SigningErrorType::Error_not_supported)
+                .context("Only P2WPKH segwit addresses are supported"),
+            StandardBitcoinAddress::Taproot(taproot) => H256::try_from(taproot.witness_program())
+                .map(SignerAddress::P2TR)
+                .tw_err(|_| SigningErrorType::Error_invalid_address)
+                .context("Taproot address must contain a 32 bytes public key"),
+        }
+    }
+
+    pub fn script_pubkey(&self) -> Script {
+        match self {
+            SignerAddress::P2PKH(pubkey_hash) => conditions::new_p2pkh(pubkey_hash),
+            SignerAddress::P2SH(script_hash) => conditions::new_p2sh(script_hash),
+            SignerAddress::P2WPKH(pubkey_hash) => conditions::new_p2wpkh(pubkey_hash),
+            SignerAddress::P2TR(tweaked_pubkey) => {
+                conditions::new_p2tr_dangerous_assume_tweaked(tweaked_pubkey)
+            },
+        }
+    }
+
+    /// Checks whether the address can be derived from the compressed public key.
+    /// P2SH addresses are considered as P2SH-P2WPKH, P2TR addresses are expected to be key-path only.
+    pub fn is_owned_by(&self, public_key: &H264) -> bool {
+        let pubkey_hash = public_key_hash(public_key.as_slice());
+        let expected_script = match self {
+            SignerAddress::P2PKH(_) => conditions::new_p2pkh(&pubkey_hash),
+            SignerAddress::P2SH(_) => {
+                let redeem_script = conditions::new_p2wpkh(&pubkey_hash);
+                conditions::new_p2sh(&public_key_hash(redeem_script.as_slice()))
+            },
+            SignerAddress::P2WPKH(_) => conditions::new_p2wpkh(&pubkey_hash),
+            SignerAddress::P2TR(_) => conditions::new_p2tr_key_path(public_key),
+        };
+        expected_script == self.script_pubkey()
+    }
+}
+
+#[derive(Default)]
+pub struct BitcoinMessageSigner;
+
+impl MessageSigner for BitcoinMessageSigner {
+    type MessageSigningInput<'a> = Proto::MessageSigningInput<'a>;
+    type MessagePreSigningOutput = CompilerProto::PreSigningOutput<'static>;
+    type MessageSigningOutput = Proto::MessageSigningOutput<'static>;
+    type MessageVerifyingInput<'a> = Proto::MessageVerifyingInput<'a>;
+
+    fn message_preimage_hashes(
+        &self,
+        coin: &dyn CoinContext,
+        input: Self::MessageSigningInput<'_>,
+    ) -> Self::MessagePreSigningOutput {
+        Self::message_preimage_hashes_impl(coin, input)
+            .unwrap_or_else(|e| signing_output_error!(CompilerProto::PreSigningOutput, e))
+    }
+
+    fn sign_message(
+        &self,
+        coin: &dyn CoinContext,
+        input: Self::MessageSigningInput<'_>,
+    ) -> Self::MessageSigningOutput {
+        Self::sign_message_impl(coin, input)
+            .unwrap_or_else(|e| signing_output_error!(Proto::MessageSigningOutput, e))
+    }
+
+    fn verify_message(
+        &self,
+        coin: &dyn CoinContext,
+        input: Self::MessageVerifyingInput<'_>,
+    ) -> bool {
+        Self::verify_message_impl(coin, input).is_ok()
+    }
+}
+
+impl BitcoinMessageSigner {
+    fn message_preimage_hashes_impl(
+        coin: &dyn CoinContext,
+        input: Proto::MessageSigningInput<'_>,
+    ) -> SigningResult<CompilerProto::PreSigningOutput<'static>> {
+        use Proto::MessageSignatureFormat;
+
+        let address = SignerAddress::parse(coin, &input.address)?;
+        let hash = match input.format {
+            MessageSignatureFormat::Legacy => LegacyMessage::message_hash(&input.message),
+            MessageSignatureFormat::Bip322Simple | MessageSignatureFormat::Bip322Full => {
+                let public_key = Self::signer_public_key(&input)?;
+                Bip322Message::preimage_hash(&input.message, &address, &public_key)?
+            },
+        };
+
+        Ok(CompilerProto::PreSigningOutput {
+            data: Cow::Owned(hash.to_vec()),
+            data_hash: Cow::Owned(hash.to_vec()),
+            ..CompilerProto::PreSigningOutput::default()
+        })
+    }
+
+    fn sign_message_impl(
+        coin: &dyn CoinContext,
+        input: Proto::MessageSigningInput<'_>,
+    ) -> SigningResult<Proto::MessageSigningOutput<'static>> {
+        use Proto::MessageSignatureFormat;
+
+        let address = SignerAddress::parse(coin, &input.address)?;
+        let private_key = input.private_key.as_ref();
+
+        let signature = match input.format {
+            MessageSignatureFormat::Legacy => {
+                LegacyMessage::sign(&input.message, &address, private_key)?
+            },
+            MessageSignatureFormat::Bip322Simple => Bip322Message::sign_simple(
+                &input.message,
+                &address,
+                private_key,
+                input.dangerous_use_fixed_schnorr_rng,
+            )?,
+            MessageSignatureFormat::Bip322Full => Bip322Message::sign_full(
+                &input.message,
+                &address,
+                private_key,
+                input.dangerous_use_fixed_schnorr_rng,
+            )?,
+        };
+
+        Ok(Proto::MessageSigningOutput {
+            signature: Cow::Owned(base64::encode(&signature, STANDARD)),
+            ..Proto::MessageSigningOutput::default()
+        })
+    }
+
+    fn verify_message_impl(
+        coin: &dyn CoinContext,
+        input: Proto::MessageVerifyingInput<'_>,
+    ) -> SigningResult<()> {
+        let address = SignerAddress::parse(coin, &input.address)?;
+        let signature = base64::decode(&input.signature, STANDARD)
+            .tw_err(|_| SigningErrorType::Error_input_parse)
+            .context("Message signature must be base64 encoded")?;
+
+        if LegacyMessage::is_legacy_signature(&signature) {
+            LegacyMessage::verify(&input.message, &address, &signature)
+        } else {
+            Bip322Message::verify(&input.message, &address, &signature)
+        }
+    }
+
+    /// Returns the signer public key from either `private_key` or `public_key`.
+    fn signer_public_key(
+        input: &Proto::MessageSigningInput<'_>,
+    ) -> SigningResult<ecdsa::secp256k1::PublicKey> {
+        if !input.private_key.is_empty() {
+            let private_key = ecdsa::secp256k1::PrivateKey::try_from(input.private_key.as_ref())
+                .into_tw()
+                .context("Invalid ecdsa secp256k1 private key")?;
+            return Ok(private_key.public());
+        }
+
+        ecdsa::secp256k1::PublicKey::try_from(input.public_key.as_ref())
+            .into_tw()
+            .context("Neither a valid private key nor public key is provided")
+    }
+}
+
+fn public_key_hash(data: &[u8]) -> H160 {
+    let hash = bitcoin_hash_160(data);
+    H160::try_from(hash.as_slice()).expect("hash length is 20 bytes")
+}
@@ -3,11 +3,14 @@
 // Copyright © 2017 Trust Wallet.
 
 pub mod compiler;
+pub mod fee_bumping;
+pub mod message_signer;
 pub mod planner;
 pub mod protobuf_builder;
 pub mod psbt;
 pub mod psbt_request;
 pub mod signer;
 pub mod signing_request;
+pub mod transaction_decoder;
 pub mod transaction_util;
 pub mod tx_builder;
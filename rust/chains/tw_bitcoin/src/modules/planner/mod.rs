@@ -2,6 +2,8 @@
 //
 // Copyright © 2017 Trust Wallet.
 
+use crate::modules::fee_bumping::child_pays_for_parent::ChildPaysForParentBuilder;
+use crate::modules::fee_bumping::replace_by_fee::ReplaceByFeeBuilder;
 use crate::modules::signing_request::SigningRequestBuilder;
 use crate::modules::tx_builder::utxo_protobuf::parse_out_point;
 use std::borrow::Cow;
@@ -30,6 +32,14 @@ impl BitcoinPlanner {
             TransactionType::psbt(ref psbt) => {
                 psbt_planner::PsbtPlanner::plan_psbt(coin, input, psbt)
             },
+            TransactionType::replace_by_fee(ref rbf) => {
+                let tx = ReplaceByFeeBuilder::tx_builder(coin, input, rbf)?;
+                Self::plan_with_tx_builder(coin, input, &tx)
+            },
+            TransactionType::child_pays_for_parent(ref cpfp) => {
+                let tx = ChildPaysForParentBuilder::tx_builder(coin, input, cpfp)?;
+                Self::plan_with_tx_builder(coin, input, &tx)
+            },
             TransactionType::None => SigningError::err(SigningErrorType::Error_invalid_params)
                 .context("Either `TransactionBuilder` or `Psbt` should be set"),
         }
@@ -88,6 +98,7 @@ impl BitcoinPlanner {
             vsize_estimate: plan.vsize_estimate as u64,
             fee_estimate: plan.fee_estimate,
             change: plan.change,
+            waste: plan.waste,
             ..Proto::TransactionPlan::default()
         })
     }
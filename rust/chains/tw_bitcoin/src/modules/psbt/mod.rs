@@ -0,0 +1,176 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::modules::protobuf_builder::ProtobufBuilder;
+use crate::modules::psbt::psbt_creator::PsbtCreator;
+use crate::modules::psbt::psbt_finalizer::PsbtFinalizer;
+use crate::modules::psbt::psbt_signer::PsbtSigner;
+use bitcoin::psbt::Psbt;
+use std::borrow::Cow;
+use tw_coin_entry::coin_context::CoinContext;
+use tw_coin_entry::error::prelude::*;
+use tw_coin_entry::signing_output_error;
+use tw_proto::BitcoinV2::Proto;
+use tw_utxo::decode::decode;
+use tw_utxo::transaction::standard_transaction::Transaction;
+use tw_utxo::transaction::transaction_interface::TransactionInterface;
+
+pub mod psbt_creator;
+pub mod psbt_finalizer;
+pub mod psbt_signer;
+pub mod psbt_v2;
+
+/// Implements the PSBT roles required for multi-party signing:
+/// Creator, Updater, Signer, Combiner, Input Finalizer and Transaction Extractor.
+pub struct BitcoinPsbt;
+
+impl BitcoinPsbt {
+    pub fn create(
+        coin: &dyn CoinContext,
+        input: &Proto::PsbtCreatingInput,
+    ) -> Proto::PsbtOutput<'static> {
+        Self::create_impl(coin, input)
+            .unwrap_or_else(|e| signing_output_error!(Proto::PsbtOutput, e))
+    }
+
+    pub fn create_impl(
+        coin: &dyn CoinContext,
+        input: &Proto::PsbtCreatingInput,
+    ) -> SigningResult<Proto::PsbtOutput<'static>> {
+        let psbt = PsbtCreator::create(coin, input)?;
+        Self::psbt_output(&psbt, input.version)
+    }
+
+    pub fn sign(input: &Proto::PsbtSigningInput) -> Proto::PsbtOutput<'static> {
+        Self::sign_impl(input).unwrap_or_else(|e| signing_output_error!(Proto::PsbtOutput, e))
+    }
+
+    pub fn sign_impl(input: &Proto::PsbtSigningInput) -> SigningResult<Proto::PsbtOutput<'static>> {
+        let (mut psbt, version) = Self::parse_psbt(&input.psbt)?;
+        PsbtSigner::sign(
+            &mut psbt,
+            &input.private_keys,
+            input.dangerous_use_fixed_schnorr_rng,
+        )?;
+        Self::psbt_output(&psbt, version)
+    }
+
+    pub fn combine(input: &Proto::PsbtCombiningInput) -> Proto::PsbtOutput<'static> {
+        Self::combine_impl(input).unwrap_or_else(|e| signing_output_error!(Proto::PsbtOutput, e))
+    }
+
+    pub fn combine_impl(
+        input: &Proto::PsbtCombiningInput,
+    ) -> SigningResult<Proto::PsbtOutput<'static>> {
+        let mut psbts = input.psbts.iter();
+        let (mut combined, version) = psbts
+            .next()
+            .or_tw_err(SigningErrorType::Error_invalid_params)
+            .context("No PSBTs provided")
+            .and_then(|first| psbt_v2::deserialize(&first.psbt))?;
+
+        for psbt in psbts {
+            let (psbt, _) = psbt_v2::deserialize(&psbt.psbt)?;
+            combined
+                .combine(psbt)
+                .tw_err(|_| SigningErrorType::Error_invalid_params)
+                .context("Cannot combine PSBTs of different transactions")?;
+        }
+
+        Self::psbt_output(&combined, version)
+    }
+
+    pub fn finalize(input: &Proto::Psbt) -> Proto::PsbtOutput<'static> {
+        Self::finalize_impl(input).unwrap_or_else(|e| signing_output_error!(Proto::PsbtOutput, e))
+    }
+
+    pub fn finalize_impl(input: &Proto::Psbt) -> SigningResult<Proto::PsbtOutput<'static>> {
+        let (mut psbt, version) = psbt_v2::deserialize(&input.psbt)?;
+        PsbtFinalizer::finalize(&mut psbt)?;
+        Self::psbt_output(&psbt, version)
+    }
+
+    pub fn extract(input: &Proto::Psbt) -> Proto::SigningOutput<'static> {
+        Self::extract_impl(input).unwrap_or_else(|e| signing_output_error!(Proto::SigningOutput, e))
+    }
+
+    /// Extracts the signed network transaction from a finalized PSBT (BIP174 Transaction Extractor role).
+    pub fn extract_impl(input: &Proto::Psbt) -> SigningResult<Proto::SigningOutput<'static>> {
+        let (psbt, _) = psbt_v2::deserialize(&input.psbt)?;
+
+        let not_finalized = psbt.inputs.iter().position(|input| {
+            input.final_script_sig.is_none() && input.final_script_witness.is_none()
+        });
+        if let Some(index) = not_finalized {
+            return SigningError::err(SigningErrorType::Error_signatures_count)
+                .context(format!("PSBT input #{index} is not finalized"));
+        }
+
+        let fee = psbt
+            .fee()
+            .tw_err(|_| SigningErrorType::Error_invalid_utxo_amount)
+            .context("Error calculating PSBT fee")?
+            .to_sat()
+            .try_into()
+            .tw_err(|_| SigningErrorType::Error_invalid_utxo_amount)
+            .context("PSBT fee is too large")?;
+
+        let psbt_bytes = input.psbt.to_vec();
+        let signed_tx: Transaction = decode(&bitcoin::consensus::serialize(&psbt.extract_tx()))
+            .context("Error decoding the extracted transaction")?;
+
+        Ok(Proto::SigningOutput {
+            transaction: Some(ProtobufBuilder::tx_to_proto(&signed_tx)),
+            encoded: Cow::from(signed_tx.encode_out()),
+            txid: Cow::from(signed_tx.txid()),
+            vsize: signed_tx.vsize() as u64,
+            fee,
+            weight: signed_tx.weight() as u64,
+            psbt: Some(Proto::Psbt {
+                psbt: Cow::from(psbt_bytes),
+            }),
+            ..Proto::SigningOutput::default()
+        })
+    }
+
+    fn parse_psbt(psbt: &Option<Proto::Psbt>) -> SigningResult<(Psbt, Proto::PsbtVersion)> {
+        let psbt = psbt
+            .as_ref()
+            .or_tw_err(SigningErrorType::Error_invalid_params)
+            .context("No PSBT provided")?;
+        psbt_v2::deserialize(&psbt.psbt)
+    }
+
+    fn psbt_output(
+        psbt: &Psbt,
+        version: Proto::PsbtVersion,
+    ) -> SigningResult<Proto::PsbtOutput<'static>> {
+        Ok(Proto::PsbtOutput {
+            psbt: Some(Proto::Psbt {
+                psbt: Cow::from(psbt_v2::serialize(psbt, version)?),
+            }),
+            ..Proto::PsbtOutput::default()
+        })
+    }
+}
+
+/// Finalizes the [Partially Signed Bitcoin Transaction](Psbt)
+/// by updating the final `script_sig` and/or `witness`.
+pub fn update_psbt_signed(psbt: &mut Psbt, signed_tx: &Transaction) {
+    for (signed_txin, utxo_psbt) in signed_tx.inputs.iter().zip(psbt.inputs.iter_mut()) {
+        if !signed_txin.script_sig.is_empty() {
+            utxo_psbt.final_script_sig = Some(bitcoin::ScriptBuf::from_bytes(
+                signed_txin.script_sig.to_vec(),
+            ));
+        }
+
+        if !signed_txin.witness.is_empty() {
+            let mut final_witness = bitcoin::Witness::new();
+            for witness_item in signed_txin.witness.as_items() {
+                final_witness.push(bitcoin::ScriptBuf::from_bytes(witness_item.to_vec()));
+            }
+            utxo_psbt.final_script_witness = Some(final_witness);
+        }
+    }
+}
@@ -0,0 +1,287 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::modules::fee_bumping::child_pays_for_parent::ChildPaysForParentBuilder;
+use crate::modules::fee_bumping::replace_by_fee::ReplaceByFeeBuilder;
+use crate::modules::signing_request::SigningRequestBuilder;
+use bitcoin::bip32::{DerivationPath, Fingerprint, KeySource};
+use bitcoin::hashes::Hash;
+use bitcoin::psbt::{Psbt, PsbtSighashType};
+use bitcoin::secp256k1::{PublicKey, XOnlyPublicKey};
+use bitcoin::taproot::{TapLeafHash, TapNodeHash};
+use std::collections::HashMap;
+use std::str::FromStr;
+use tw_coin_entry::coin_context::CoinContext;
+use tw_coin_entry::error::prelude::*;
+use tw_hash::ripemd::bitcoin_hash_160;
+use tw_hash::{H160, H264};
+use tw_proto::BitcoinV2::Proto;
+use tw_utxo::modules::tx_planner::TxPlanner;
+use tw_utxo::modules::utxo_selector::SelectResult;
+use tw_utxo::script::standard_script::conditions;
+use tw_utxo::script::Script;
+use tw_utxo::signing_mode::SigningMethod;
+use tw_utxo::taproot::ControlBlock;
+use tw_utxo::transaction::UtxoToSign;
+
+/// A public key with its BIP32 derivation.
+struct KeyOrigin {
+    public_key: PublicKey,
+    source: KeySource,
+}
+
+impl KeyOrigin {
+    fn x_only(&self) -> XOnlyPublicKey {
+        self.public_key.x_only_public_key().0
+    }
+
+    fn compressed(&self) -> H264 {
+        H264::from(self.public_key.serialize())
+    }
+
+    fn pubkey_hash(&self) -> H160 {
+        let pubkey_hash = bitcoin_hash_160(self.compressed().as_slice());
+        H160::try_from(pubkey_hash.as_slice()).expect("hash length is 20 bytes")
+    }
+}
+
+/// Creates a PSBT and fills in the information required to sign each input (BIP174 Creator and Updater roles).
+pub struct PsbtCreator;
+
+impl PsbtCreator {
+    pub fn create(coin: &dyn CoinContext, input: &Proto::PsbtCreatingInput) -> SigningResult<Psbt> {
+        let signing_input = input
+            .signing_input
+            .as_ref()
+            .or_tw_err(SigningErrorType::Error_invalid_params)
+            .context("No signing input provided")?;
+
+        let tx_builder = Self::tx_builder(coin, signing_input)?;
+        let request = SigningRequestBuilder::build(coin, signing_input, &tx_builder)?;
+        let SelectResult { unsigned_tx, .. } = TxPlanner::plan(request)?;
+
+        let key_origins: Vec<_> = input
+            .bip32_derivations
+            .iter()
+            .map(Self::key_origin)
+            .collect::<SigningResult<_>>()?;
+        let previous_txs = Self::previous_transactions(&input.previous_transactions)?;
+
+        let tx: bitcoin::Transaction =
+            bitcoin::consensus::deserialize(&unsigned_tx.transaction().encode_out())
+                .tw_err(|_| SigningErrorType::Error_internal)
+                .context("Error converting the unsigned transaction")?;
+        let mut psbt = Psbt::from_unsigned_tx(tx)
+            .tw_err(|_| SigningErrorType::Error_internal)
+            .context("Error creating PSBT from the unsigned transaction")?;
+
+        let inputs = psbt
+            .inputs
+            .iter_mut()
+            .zip(psbt.unsigned_tx.input.iter())
+            .zip(unsigned_tx.input_args());
+        for ((psbt_input, txin), utxo) in inputs {
+            Self::update_input(psbt_input, txin, utxo, &previous_txs, &key_origins)?;
+        }
+
+        let outputs = psbt.outputs.iter_mut().zip(psbt.unsigned_tx.output.iter());
+        for (psbt_output, txout) in outputs {
+            Self::update_output(psbt_output, txout, &key_origins);
+        }
+
+        Ok(psbt)
+    }
+
+    fn tx_builder<'a>(
+        coin: &dyn CoinContext,
+        input: &Proto::SigningInput<'a>,
+    ) -> SigningResult<Proto::TransactionBuilder<'a>> {
+        use Proto::mod_SigningInput::OneOftransaction as TransactionType;
+
+        match input.transaction {
+            TransactionType::builder(ref tx) => Ok(tx.clone()),
+            TransactionType::replace_by_fee(ref rbf) => {
+                ReplaceByFeeBuilder::tx_builder(coin, input, rbf)
+            },
+            TransactionType::child_pays_for_parent(ref cpfp) => {
+                ChildPaysForParentBuilder::tx_builder(coin, input, cpfp)
+            },
+            TransactionType::psbt(_) => SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("Cannot create a PSBT from another PSBT"),
+            TransactionType::None => SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("No transaction type specified"),
+        }
+    }
+
+    fn update_input(
+        psbt_input: &mut bitcoin::psbt::Input,
+        txin: &bitcoin::TxIn,
+        utxo: &UtxoToSign,
+        previous_txs: &HashMap<bitcoin::Txid, bitcoin::Transaction>,
+        key_origins: &[KeyOrigin],
+    ) -> SigningResult<()> {
+        let prev_out = bitcoin::TxOut {
+            value: utxo.amount as u64,
+            script_pubkey: bitcoin::ScriptBuf::from_bytes(utxo.prevout_script_pubkey.to_vec()),
+        };
+
+        let out_point = txin.previous_output;
+        match previous_txs.get(&out_point.txid) {
+            Some(prev_tx) => {
+                if prev_tx.output.get(out_point.vout as usize) != Some(&prev_out) {
+                    return SigningError::err(SigningErrorType::Error_invalid_utxo).context(
+                        format!("Previous transaction output doesn't match the UTXO: {out_point}"),
+                    );
+                }
+                psbt_input.non_witness_utxo = Some(prev_tx.clone());
+            },
+            None if utxo.signing_method == SigningMethod::Legacy => {
+                return SigningError::err(SigningErrorType::Error_missing_input_utxos).context(
+                    format!(
+                        "Previous transaction is required to spend a non-segwit UTXO: {out_point}"
+                    ),
+                );
+            },
+            None => (),
+        }
+
+        if utxo.signing_method != SigningMethod::Legacy {
+            psbt_input.witness_utxo = Some(prev_out);
+        }
+
+        // UTXO is signed with the redeem or witness script as the scriptCode.
+        let signing_script = bitcoin::ScriptBuf::from_bytes(utxo.script_pubkey.to_vec());
+        if conditions::is_p2sh(&utxo.prevout_script_pubkey) {
+            psbt_input.redeem_script = Some(signing_script);
+        } else if conditions::is_p2wsh(&utxo.prevout_script_pubkey) {
+            psbt_input.witness_script = Some(signing_script);
+        }
+
+        // Taproot script-path UTXO is spent by revealing the leaf script committed to the script tree.
+        if let Some(ref control_block) = utxo.control_block {
+            Self::update_tap_script(psbt_input, &utxo.script_pubkey, control_block)?;
+        }
+
+        if !utxo.sighash_ty.is_default_taproot_sighash() {
+            psbt_input.sighash_type =
+                Some(PsbtSighashType::from_u32(utxo.sighash_ty.raw_sighash()));
+        }
+
+        for origin in key_origins {
+            let is_spender = utxo
+                .spender_public_keys
+                .iter()
+                .any(|spender| spender.as_slice() == origin.compressed().as_slice());
+
+            match (utxo.signing_method, utxo.leaf_hash_code_separator) {
+                (SigningMethod::Legacy | SigningMethod::Segwit, _) if is_spender => {
+                    psbt_input
+                        .bip32_derivation
+                        .insert(origin.public_key, origin.source.clone());
+                },
+                // Taproot key-path spender is the tweaked public key, so compare the output scripts.
+                (SigningMethod::Taproot, None)
+                    if utxo.prevout_script_pubkey
+                        == conditions::new_p2tr_key_path(&origin.compressed()) =>
+                {
+                    psbt_input.tap_internal_key = Some(origin.x_only());
+                    psbt_input
+                        .tap_key_origins
+                        .insert(origin.x_only(), (Vec::new(), origin.source.clone()));
+                },
+                (SigningMethod::Taproot, Some((leaf_hash, _))) if is_spender => {
+                    let leaf_hash = TapLeafHash::from_byte_array(leaf_hash.take());
+                    psbt_input
+                        .tap_key_origins
+                        .insert(origin.x_only(), (vec![leaf_hash], origin.source.clone()));
+                },
+                _ => (),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Adds the leaf script with its control block, the internal key and the merkle root of the script tree.
+    fn update_tap_script(
+        psbt_input: &mut bitcoin::psbt::Input,
+        leaf_script: &Script,
+        control_block: &ControlBlock,
+    ) -> SigningResult<()> {
+        let merkle_root = control_block.merkle_root(leaf_script);
+        let control_block = bitcoin::taproot::ControlBlock::decode(&control_block.serialize())
+            .tw_err(|_| SigningErrorType::Error_internal)
+            .context("Error converting the Taproot control block")?;
+
+        psbt_input.tap_internal_key = Some(control_block.internal_key);
+        psbt_input.tap_merkle_root = Some(TapNodeHash::from_byte_array(merkle_root.take()));
+        let leaf = (
+            bitcoin::ScriptBuf::from_bytes(leaf_script.to_vec()),
+            control_block.leaf_version,
+        );
+        psbt_input.tap_scripts.insert(control_block, leaf);
+        Ok(())
+    }
+
+    /// Adds BIP32 derivations of the keys that the output is sent to, e.g. a change output.
+    fn update_output(
+        psbt_output: &mut bitcoin::psbt::Output,
+        txout: &bitcoin::TxOut,
+        key_origins: &[KeyOrigin],
+    ) {
+        let script_pubkey = Script::from(txout.script_pubkey.to_bytes());
+
+        for origin in key_origins {
+            let pubkey = origin.compressed();
+            let pubkey_hash = origin.pubkey_hash();
+
+            if script_pubkey == conditions::new_p2pk(&pubkey)
+                || script_pubkey == conditions::new_p2pkh(&pubkey_hash)
+                || script_pubkey == conditions::new_p2wpkh(&pubkey_hash)
+            {
+                psbt_output
+                    .bip32_derivation
+                    .insert(origin.public_key, origin.source.clone());
+            } else if script_pubkey == conditions::new_p2tr_key_path(&pubkey) {
+                psbt_output.tap_internal_key = Some(origin.x_only());
+                psbt_output
+                    .tap_key_origins
+                    .insert(origin.x_only(), (Vec::new(), origin.source.clone()));
+            }
+        }
+    }
+
+    fn key_origin(derivation: &Proto::Bip32Derivation) -> SigningResult<KeyOrigin> {
+        let public_key = PublicKey::from_slice(&derivation.public_key)
+            .tw_err(|_| SigningErrorType::Error_invalid_params)
+            .context("Invalid BIP32 derivation public key")?;
+        let fingerprint: [u8; 4] = derivation
+            .master_fingerprint
+            .as_ref()
+            .try_into()
+            .tw_err(|_| SigningErrorType::Error_invalid_params)
+            .context("Master key fingerprint must be exactly 4 bytes")?;
+        let path = DerivationPath::from_str(&derivation.derivation_path)
+            .tw_err(|_| SigningErrorType::Error_invalid_params)
+            .context("Invalid BIP32 derivation path")?;
+
+        Ok(KeyOrigin {
+            public_key,
+            source: (Fingerprint::from(fingerprint), path),
+        })
+    }
+
+    fn previous_transactions<T: AsRef<[u8]>>(
+        txs: &[T],
+    ) -> SigningResult<HashMap<bitcoin::Txid, bitcoin::Transaction>> {
+        txs.iter()
+            .map(|tx| {
+                let tx: bitcoin::Transaction = bitcoin::consensus::deserialize(tx.as_ref())
+                    .tw_err(|_| SigningErrorType::Error_input_parse)
+                    .context("Error decoding a previous transaction")?;
+                Ok((tx.txid(), tx))
+            })
+            .collect()
+    }
+}
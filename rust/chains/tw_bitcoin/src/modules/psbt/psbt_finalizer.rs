@@ -0,0 +1,285 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use bitcoin::key::XOnlyPublicKey;
+use bitcoin::psbt::Psbt;
+use bitcoin::taproot::{LeafVersion, TapLeafHash};
+use tw_coin_entry::error::prelude::*;
+use tw_hash::ripemd::bitcoin_hash_160;
+use tw_hash::{H160, H264};
+use tw_keypair::ecdsa::der;
+use tw_utxo::script::standard_script::{claims, conditions};
+use tw_utxo::script::{Script, Witness};
+use tw_utxo::sighash::SighashType;
+use tw_utxo::signature::BitcoinEcdsaSignature;
+
+const OP_CHECKSIG: u8 = 0xac;
+const X_ONLY_PUBKEY_LEN: usize = 32;
+
+/// Builds the final `script_sig` and `witness` of every input from the partial signatures
+/// (BIP174 Input Finalizer role).
+pub struct PsbtFinalizer;
+
+impl PsbtFinalizer {
+    /// Finalizes all inputs that are not finalized yet.
+    /// Returns an error if any input doesn't have enough signatures to be finalized.
+    pub fn finalize(psbt: &mut Psbt) -> SigningResult<()> {
+        for index in 0..psbt.inputs.len() {
+            let input = &psbt.inputs[index];
+            if is_finalized(input) {
+                continue;
+            }
+
+            let (script_sig, witness) = Self::finalize_input(psbt, index)
+                .with_context(|| format!("Error finalizing the input #{index}"))?;
+
+            let input = &mut psbt.inputs[index];
+            // Only the UTXO and unknown fields are kept after the input being finalized.
+            *input = bitcoin::psbt::Input {
+                non_witness_utxo: input.non_witness_utxo.take(),
+                witness_utxo: input.witness_utxo.take(),
+                final_script_sig: (!script_sig.is_empty())
+                    .then(|| bitcoin::ScriptBuf::from_bytes(script_sig.to_vec())),
+                final_script_witness: (!witness.is_empty()).then(|| to_bitcoin_witness(&witness)),
+                proprietary: std::mem::take(&mut input.proprietary),
+                unknown: std::mem::take(&mut input.unknown),
+                ..bitcoin::psbt::Input::default()
+            };
+        }
+        Ok(())
+    }
+
+    fn finalize_input(psbt: &Psbt, index: usize) -> SigningResult<(Script, Witness)> {
+        let input = &psbt.inputs[index];
+        let prevout = psbt
+            .spend_utxo(index)
+            .tw_err(|_| SigningErrorType::Error_invalid_utxo)
+            .context("Neither 'witness_utxo' nor 'non_witness_utxo' are set")?;
+        let script_pubkey = Script::from(prevout.script_pubkey.to_bytes());
+
+        if conditions::is_p2sh(&script_pubkey) {
+            let redeem_script = required_script(&input.redeem_script, "redeem_script")?;
+
+            if conditions::is_p2wpkh(&redeem_script) {
+                let witness = Self::p2wpkh_witness(input, &redeem_script)?;
+                Ok((claims::new_p2sh(Vec::new(), redeem_script), witness))
+            } else if conditions::is_p2wsh(&redeem_script) {
+                let witness = Self::p2wsh_witness(input)?;
+                Ok((claims::new_p2sh(Vec::new(), redeem_script), witness))
+            } else {
+                let items = Self::claim_items(input, &redeem_script)?;
+                Ok((claims::new_p2sh(items, redeem_script), Witness::default()))
+            }
+        } else if conditions::is_p2wsh(&script_pubkey) {
+            Ok((Script::default(), Self::p2wsh_witness(input)?))
+        } else if conditions::is_p2wpkh(&script_pubkey) {
+            let witness = Self::p2wpkh_witness(input, &script_pubkey)?;
+            Ok((Script::default(), witness))
+        } else if conditions::is_p2tr(&script_pubkey) {
+            Ok((Script::default(), Self::p2tr_witness(input)?))
+        } else {
+            let items = Self::claim_items(input, &script_pubkey)?;
+            let mut script_sig = Script::new();
+            for item in items {
+                script_sig.push_slice(item.as_slice());
+            }
+            Ok((script_sig, Witness::default()))
+        }
+    }
+
+    fn p2wpkh_witness(input: &bitcoin::psbt::Input, script: &Script) -> SigningResult<Witness> {
+        let pubkey_hash = conditions::match_p2wpkh(script)
+            .or_tw_err(SigningErrorType::Error_script_output)
+            .context("Expected P2WPKH script")?;
+        let (pubkey, sig) = find_signature_by_hash(input, &pubkey_hash)?;
+        Ok(claims::new_p2wpkh(&sig, pubkey))
+    }
+
+    fn p2wsh_witness(input: &bitcoin::psbt::Input) -> SigningResult<Witness> {
+        let witness_script = required_script(&input.witness_script, "witness_script")?;
+        let items = Self::claim_items(input, &witness_script)?;
+        Ok(claims::new_p2wsh(items, witness_script))
+    }
+
+    /// Returns the items that satisfy a P2PK, P2PKH or multisig script.
+    fn claim_items(input: &bitcoin::psbt::Input, script: &Script) -> SigningResult<Vec<Script>> {
+        if let Some(pubkey) = conditions::match_p2pk(script) {
+            let sig = find_signature(input, pubkey)?
+                .or_tw_err(SigningErrorType::Error_signatures_count)
+                .context("No signature for the P2PK public key")?;
+            Ok(vec![Script::from(sig.serialize())])
+        } else if let Some(pubkey_hash) = conditions::match_p2pkh(script) {
+            let (pubkey, sig) = find_signature_by_hash(input, &pubkey_hash)?;
+            Ok(vec![
+                Script::from(sig.serialize()),
+                Script::from(pubkey.into_vec()),
+            ])
+        } else if let Some((threshold, pubkeys)) = conditions::match_multisig(script) {
+            // Signatures must be in the same order as the public keys in the script.
+            let mut sigs = Vec::with_capacity(threshold as usize);
+            for pubkey in pubkeys {
+                if sigs.len() == threshold as usize {
+                    break;
+                }
+                if let Some(sig) = find_signature(input, pubkey)? {
+                    sigs.push(sig);
+                }
+            }
+
+            if sigs.len() < threshold as usize {
+                return SigningError::err(SigningErrorType::Error_signatures_count).context(
+                    format!(
+                        "Expected at least {threshold} multisig signatures, found {}",
+                        sigs.len()
+                    ),
+                );
+            }
+            Ok(claims::new_multisig_items(&sigs))
+        } else {
+            SigningError::err(SigningErrorType::Error_not_supported)
+                .context("Only P2PK, P2PKH and multisig scripts can be finalized")
+        }
+    }
+
+    fn p2tr_witness(input: &bitcoin::psbt::Input) -> SigningResult<Witness> {
+        if let Some(sig) = input.tap_key_sig {
+            return Ok(claims::new_p2tr_key_path(sig.to_vec()));
+        }
+
+        for (control_block, (leaf_script, leaf_version)) in input.tap_scripts.iter() {
+            let leaf_hash = TapLeafHash::from_script(leaf_script, *leaf_version);
+            let script = Script::from(leaf_script.to_bytes());
+            if let Some(items) = tapscript_items(input, &script, *leaf_version, leaf_hash) {
+                return Ok(claims::new_p2tr_script_path(
+                    items,
+                    script,
+                    control_block.serialize(),
+                ));
+            }
+        }
+
+        SigningError::err(SigningErrorType::Error_signatures_count)
+            .context("Neither Taproot key path signature nor satisfiable script path is set")
+    }
+}
+
+fn is_finalized(input: &bitcoin::psbt::Input) -> bool {
+    input.final_script_sig.is_some() || input.final_script_witness.is_some()
+}
+
+fn required_script(script: &Option<bitcoin::ScriptBuf>, name: &str) -> SigningResult<Script> {
+    script
+        .as_ref()
+        .map(|script| Script::from(script.to_bytes()))
+        .or_tw_err(SigningErrorType::Error_script_redeem)
+        .with_context(|| format!("'{name}' is not set"))
+}
+
+/// Returns the witness items that satisfy a single-key or `OP_CHECKSIGADD` multisig tapscript,
+/// or `None` if there are not enough signatures.
+fn tapscript_items(
+    input: &bitcoin::psbt::Input,
+    script: &Script,
+    leaf_version: LeafVersion,
+    leaf_hash: TapLeafHash,
+) -> Option<Vec<Script>> {
+    if leaf_version != LeafVersion::TapScript {
+        return None;
+    }
+    let find_sig = |pubkey: &[u8]| {
+        let pubkey = XOnlyPublicKey::from_slice(pubkey).ok()?;
+        input.tap_script_sigs.get(&(pubkey, leaf_hash))
+    };
+
+    if let Some(pubkey) = match_tapscript_single_key(script) {
+        let sig = find_sig(pubkey)?;
+        return Some(vec![Script::from(sig.to_vec())]);
+    }
+
+    let (threshold, pubkeys) = conditions::match_tapscript_multisig(script)?;
+    // `OP_NUMEQUAL` requires exactly `threshold` valid signatures,
+    // so only the first `threshold` signatures in the script order are kept.
+    let mut sigs_count = 0;
+    let sigs: Vec<_> = pubkeys
+        .iter()
+        .map(|pubkey| {
+            if sigs_count == threshold as usize {
+                return None;
+            }
+            let sig = find_sig(pubkey.as_slice())?;
+            sigs_count += 1;
+            Some(sig)
+        })
+        .collect();
+    if sigs_count < threshold as usize {
+        return None;
+    }
+
+    // The first public key of the script consumes the top stack item, so the items are reversed.
+    // Missing and extra signatures are replaced with empty items.
+    Some(
+        sigs.into_iter()
+            .rev()
+            .map(|sig| {
+                sig.map(|sig| Script::from(sig.to_vec()))
+                    .unwrap_or_default()
+            })
+            .collect(),
+    )
+}
+
+/// Returns the x-only public key of a `<x-only pubkey> OP_CHECKSIG` tapscript.
+pub(crate) fn match_tapscript_single_key(script: &Script) -> Option<&[u8]> {
+    let bytes = script.as_slice();
+    let is_single_key = bytes.len() == X_ONLY_PUBKEY_LEN + 2
+        && bytes[0] == X_ONLY_PUBKEY_LEN as u8
+        && bytes[X_ONLY_PUBKEY_LEN + 1] == OP_CHECKSIG;
+    is_single_key.then(|| &bytes[1..=X_ONLY_PUBKEY_LEN])
+}
+
+fn find_signature(
+    input: &bitcoin::psbt::Input,
+    pubkey: &[u8],
+) -> SigningResult<Option<BitcoinEcdsaSignature>> {
+    input
+        .partial_sigs
+        .iter()
+        .find(|(key, _)| key.to_bytes() == pubkey)
+        .map(|(_, sig)| to_bitcoin_ecdsa_signature(sig))
+        .transpose()
+}
+
+fn find_signature_by_hash(
+    input: &bitcoin::psbt::Input,
+    pubkey_hash: &H160,
+) -> SigningResult<(H264, BitcoinEcdsaSignature)> {
+    let (pubkey, sig) = input
+        .partial_sigs
+        .iter()
+        .find(|(key, _)| bitcoin_hash_160(&key.to_bytes()) == pubkey_hash.as_slice())
+        .or_tw_err(SigningErrorType::Error_signatures_count)
+        .context("No signature for the public key hash")?;
+
+    let pubkey = H264::try_from(pubkey.to_bytes().as_slice())
+        .tw_err(|_| SigningErrorType::Error_not_supported)
+        .context("Uncompressed public keys are not supported")?;
+    Ok((pubkey, to_bitcoin_ecdsa_signature(sig)?))
+}
+
+fn to_bitcoin_ecdsa_signature(
+    sig: &bitcoin::ecdsa::Signature,
+) -> SigningResult<BitcoinEcdsaSignature> {
+    let der_sig = der::Signature::from_bytes(&sig.sig.serialize_der())
+        .into_tw()
+        .context("Invalid partial signature")?;
+    BitcoinEcdsaSignature::new(der_sig, SighashType::from_u32(sig.hash_ty.to_u32())?)
+}
+
+fn to_bitcoin_witness(witness: &Witness) -> bitcoin::Witness {
+    let mut final_witness = bitcoin::Witness::new();
+    for witness_item in witness.as_items() {
+        final_witness.push(witness_item.as_slice());
+    }
+    final_witness
+}
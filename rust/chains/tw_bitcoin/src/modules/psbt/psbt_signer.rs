@@ -0,0 +1,296 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::modules::psbt::psbt_finalizer::match_tapscript_single_key;
+use bitcoin::hashes::Hash;
+use bitcoin::key::XOnlyPublicKey;
+use bitcoin::psbt::Psbt;
+use bitcoin::sighash::{Prevouts, SighashCache, TapSighash, TapSighashType};
+use bitcoin::taproot::{self, LeafVersion, TapLeafHash};
+use bitcoin::ScriptBuf;
+use tw_coin_entry::error::prelude::*;
+use tw_hash::ripemd::bitcoin_hash_160;
+use tw_hash::{H160, H256};
+use tw_keypair::traits::SigningKeyTrait;
+use tw_keypair::{ecdsa, schnorr};
+use tw_utxo::script::standard_script::conditions;
+use tw_utxo::script::Script;
+
+/// A private key of the signer that can sign both ECDSA and Taproot inputs.
+struct SignerKey<'a> {
+    private: &'a [u8],
+    ecdsa: ecdsa::secp256k1::PrivateKey,
+    public_key: bitcoin::PublicKey,
+    x_only: XOnlyPublicKey,
+    dangerous_use_fixed_schnorr_rng: bool,
+}
+
+impl<'a> SignerKey<'a> {
+    fn new(private: &'a [u8], dangerous_use_fixed_schnorr_rng: bool) -> SigningResult<Self> {
+        let ecdsa = ecdsa::secp256k1::PrivateKey::try_from(private)
+            .into_tw()
+            .context("Invalid ecdsa secp256k1 private key")?;
+        let public_key = bitcoin::PublicKey::from_slice(ecdsa.public().compressed().as_slice())
+            .tw_err(|_| SigningErrorType::Error_internal)
+            .context("Error converting the public key")?;
+        let x_only = XOnlyPublicKey::from(public_key.inner);
+
+        Ok(SignerKey {
+            private,
+            ecdsa,
+            public_key,
+            x_only,
+            dangerous_use_fixed_schnorr_rng,
+        })
+    }
+
+    /// Returns the untweaked schnorr private key that signs Taproot script-path inputs.
+    fn schnorr(&self) -> SigningResult<schnorr::PrivateKey> {
+        let private_key = schnorr::PrivateKey::try_from(self.private)
+            .into_tw()
+            .context("Invalid schnorr private key")?;
+        if self.dangerous_use_fixed_schnorr_rng {
+            return Ok(private_key.no_aux_rand());
+        }
+        Ok(private_key)
+    }
+}
+
+fn sign_schnorr(
+    private_key: &schnorr::PrivateKey,
+    sighash: TapSighash,
+    hash_ty: TapSighashType,
+) -> SigningResult<taproot::Signature> {
+    let sig = private_key
+        .sign(H256::from(sighash.to_byte_array()))
+        .into_tw()
+        .context("Error signing sighash with a schnorr private key")?;
+    let sig = bitcoin::secp256k1::schnorr::Signature::from_slice(sig.bytes().as_slice())
+        .tw_err(|_| SigningErrorType::Error_internal)
+        .context("Error converting the signature")?;
+    Ok(taproot::Signature { sig, hash_ty })
+}
+
+/// Adds partial signatures to a PSBT (BIP174 Signer role).
+/// ECDSA inputs are signed into `partial_sigs`,
+/// Taproot inputs are signed into `tap_key_sig` (key path) or `tap_script_sigs` (script path).
+pub struct PsbtSigner;
+
+impl PsbtSigner {
+    /// Signs every input that the given private keys are involved in.
+    /// Returns an error if none of the inputs can be signed by the keys.
+    pub fn sign<P: AsRef<[u8]>>(
+        psbt: &mut Psbt,
+        private_keys: &[P],
+        dangerous_use_fixed_schnorr_rng: bool,
+    ) -> SigningResult<()> {
+        let unsigned_tx = psbt.unsigned_tx.clone();
+        let prevouts = Self::prevouts(psbt)?;
+        let mut sighash_cache = SighashCache::new(&unsigned_tx);
+        let mut signed_any = false;
+
+        for private in private_keys {
+            let key = SignerKey::new(private.as_ref(), dangerous_use_fixed_schnorr_rng)?;
+
+            for index in 0..psbt.inputs.len() {
+                let input = &psbt.inputs[index];
+                if input.final_script_sig.is_some() || input.final_script_witness.is_some() {
+                    continue;
+                }
+
+                signed_any |= if prevouts[index].script_pubkey.is_v1_p2tr() {
+                    Self::sign_taproot_input(psbt, index, &key, &prevouts, &mut sighash_cache)?
+                } else {
+                    Self::sign_ecdsa_input(psbt, index, &key, &prevouts, &mut sighash_cache)?
+                };
+            }
+        }
+
+        if !signed_any {
+            return SigningError::err(SigningErrorType::Error_missing_private_key)
+                .context("None of the PSBT inputs can be signed with the given private keys");
+        }
+        Ok(())
+    }
+
+    /// Returns the outputs spent by the inputs, as all of them are committed to a Taproot sighash.
+    fn prevouts(psbt: &Psbt) -> SigningResult<Vec<bitcoin::TxOut>> {
+        (0..psbt.inputs.len())
+            .map(|index| {
+                psbt.spend_utxo(index)
+                    .cloned()
+                    .tw_err(|_| SigningErrorType::Error_invalid_utxo)
+                    .with_context(|| {
+                        format!(
+                        "Neither 'witness_utxo' nor 'non_witness_utxo' are set for the input #{index}"
+                    )
+                    })
+            })
+            .collect()
+    }
+
+    fn sign_ecdsa_input(
+        psbt: &mut Psbt,
+        index: usize,
+        key: &SignerKey,
+        prevouts: &[bitcoin::TxOut],
+        sighash_cache: &mut SighashCache<&bitcoin::Transaction>,
+    ) -> SigningResult<bool> {
+        if !Self::is_ecdsa_signer(&psbt.inputs[index], &prevouts[index], &key.public_key) {
+            return Ok(false);
+        }
+
+        let (message, hash_ty) = psbt
+            .sighash_ecdsa(index, sighash_cache)
+            .tw_err(|_| SigningErrorType::Error_invalid_params)
+            .with_context(|| format!("Error computing sighash of the input #{index}"))?;
+
+        let der_sig = key
+            .ecdsa
+            .sign(H256::from(*message.as_ref()))
+            .into_tw()
+            .context("Error signing sighash with a secp256k1 private key")?
+            .to_der()
+            .into_tw()
+            .context("Error getting ASN.1 DER-encoded signature")?;
+        let sig = bitcoin::secp256k1::ecdsa::Signature::from_der(&der_sig.der_bytes())
+            .tw_err(|_| SigningErrorType::Error_internal)
+            .context("Error converting the signature")?;
+
+        psbt.inputs[index]
+            .partial_sigs
+            .insert(key.public_key, bitcoin::ecdsa::Signature { sig, hash_ty });
+        Ok(true)
+    }
+
+    /// Signs the key path if the output key is the signer's public key tweaked with the script tree merkle root,
+    /// and every leaf script of `tap_scripts` that the signer's public key is involved in.
+    fn sign_taproot_input(
+        psbt: &mut Psbt,
+        index: usize,
+        key: &SignerKey,
+        prevouts: &[bitcoin::TxOut],
+        sighash_cache: &mut SighashCache<&bitcoin::Transaction>,
+    ) -> SigningResult<bool> {
+        let input = &psbt.inputs[index];
+        let hash_ty = input
+            .taproot_hash_ty()
+            .tw_err(|_| SigningErrorType::Error_invalid_params)
+            .with_context(|| format!("Invalid Taproot sighash type of the input #{index}"))?;
+        let all_prevouts = Prevouts::All(prevouts);
+        let mut signed = false;
+
+        let merkle_root = input.tap_merkle_root;
+        let secp = bitcoin::secp256k1::Secp256k1::verification_only();
+        if prevouts[index].script_pubkey == ScriptBuf::new_v1_p2tr(&secp, key.x_only, merkle_root) {
+            let sighash = sighash_cache
+                .taproot_key_spend_signature_hash(index, &all_prevouts, hash_ty)
+                .tw_err(|_| SigningErrorType::Error_invalid_params)
+                .with_context(|| format!("Error computing sighash of the input #{index}"))?;
+            // Key path is signed with the private key tweaked by the script tree merkle root.
+            let tweak = merkle_root.map(|root| H256::from(root.to_byte_array()));
+            let sig = sign_schnorr(&key.schnorr()?.tweak(tweak), sighash, hash_ty)?;
+            psbt.inputs[index].tap_key_sig = Some(sig);
+            signed = true;
+        }
+
+        let leaves: Vec<_> = psbt.inputs[index]
+            .tap_scripts
+            .values()
+            .filter(|(_, leaf_version)| *leaf_version == LeafVersion::TapScript)
+            .map(|(leaf_script, leaf_version)| {
+                let leaf_hash = TapLeafHash::from_script(leaf_script, *leaf_version);
+                (leaf_hash, Script::from(leaf_script.to_bytes()))
+            })
+            .filter(|(leaf_hash, leaf_script)| {
+                Self::is_tapscript_signer(&psbt.inputs[index], leaf_script, *leaf_hash, key)
+            })
+            .collect();
+
+        // Script path is signed with the untweaked private key.
+        for (leaf_hash, _) in leaves {
+            let sighash = sighash_cache
+                .taproot_script_spend_signature_hash(index, &all_prevouts, leaf_hash, hash_ty)
+                .tw_err(|_| SigningErrorType::Error_invalid_params)
+                .with_context(|| format!("Error computing sighash of the input #{index}"))?;
+            let sig = sign_schnorr(&key.schnorr()?, sighash, hash_ty)?;
+            psbt.inputs[index]
+                .tap_script_sigs
+                .insert((key.x_only, leaf_hash), sig);
+            signed = true;
+        }
+
+        Ok(signed)
+    }
+
+    /// Checks whether the public key is required to sign a non-Taproot input.
+    fn is_ecdsa_signer(
+        input: &bitcoin::psbt::Input,
+        prevout: &bitcoin::TxOut,
+        public_key: &bitcoin::PublicKey,
+    ) -> bool {
+        if input.bip32_derivation.contains_key(&public_key.inner) {
+            return true;
+        }
+
+        let scripts = [
+            Some(&prevout.script_pubkey),
+            input.redeem_script.as_ref(),
+            input.witness_script.as_ref(),
+        ];
+        scripts
+            .into_iter()
+            .flatten()
+            .any(|script| involves_public_key(&Script::from(script.to_bytes()), public_key))
+    }
+
+    /// Checks whether the public key is listed in the leaf BIP32 derivations
+    /// or is required by a single-key or multisig leaf script.
+    fn is_tapscript_signer(
+        input: &bitcoin::psbt::Input,
+        leaf_script: &Script,
+        leaf_hash: TapLeafHash,
+        key: &SignerKey,
+    ) -> bool {
+        let is_derived = input
+            .tap_key_origins
+            .get(&key.x_only)
+            .is_some_and(|(leaf_hashes, _)| leaf_hashes.contains(&leaf_hash));
+        if is_derived {
+            return true;
+        }
+
+        let x_only = key.x_only.serialize();
+        if let Some(pubkey) = match_tapscript_single_key(leaf_script) {
+            pubkey == x_only.as_slice()
+        } else if let Some((_threshold, pubkeys)) =
+            conditions::match_tapscript_multisig(leaf_script)
+        {
+            pubkeys
+                .iter()
+                .any(|pubkey| pubkey.as_slice() == x_only.as_slice())
+        } else {
+            false
+        }
+    }
+}
+
+/// Checks whether the public key can satisfy a P2PK, P2PKH, P2WPKH or multisig script.
+fn involves_public_key(script: &Script, public_key: &bitcoin::PublicKey) -> bool {
+    let pubkey_bytes = public_key.to_bytes();
+    let pubkey_hash = bitcoin_hash_160(&pubkey_bytes);
+    let is_pubkey_hash = |hash: H160| hash.as_slice() == pubkey_hash.as_slice();
+
+    if let Some(pubkey) = conditions::match_p2pk(script) {
+        pubkey == pubkey_bytes.as_slice()
+    } else if let Some(hash) = conditions::match_p2pkh(script) {
+        is_pubkey_hash(hash)
+    } else if let Some(hash) = conditions::match_p2wpkh(script) {
+        is_pubkey_hash(hash)
+    } else if let Some((_threshold, pubkeys)) = conditions::match_multisig(script) {
+        pubkeys.contains(&pubkey_bytes.as_slice())
+    } else {
+        false
+    }
+}
@@ -0,0 +1,455 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+//! BIP370 (PSBT version 2) serialization.
+//! https://github.com/bitcoin/bips/blob/master/bip-0370.mediawiki
+//!
+//! The `bitcoin` crate supports version 0 PSBTs only.
+//! That's why a version 2 PSBT is converted to version 0 by moving the per-input and per-output
+//! transaction fields to the global unsigned transaction, and vice versa.
+//! Other version 2 fields (e.g. `PSBT_GLOBAL_TX_MODIFIABLE`) are kept as unknown key-value pairs.
+
+use bitcoin::psbt::Psbt;
+use tw_coin_entry::error::prelude::*;
+use tw_hash::H256;
+use tw_memory::Data;
+use tw_proto::BitcoinV2::Proto;
+use tw_utxo::decode::decode;
+use tw_utxo::decode::reader::Reader;
+use tw_utxo::encode::compact_integer::CompactInteger;
+use tw_utxo::encode::encode;
+use tw_utxo::encode::stream::Stream;
+use tw_utxo::script::{Script, Witness};
+use tw_utxo::transaction::standard_transaction::{
+    Transaction, TransactionInput, TransactionOutput,
+};
+use tw_utxo::transaction::transaction_parts::{Amount, OutPoint};
+
+const PSBT_MAGIC: &[u8] = b"psbt\xff";
+
+const PSBT_GLOBAL_UNSIGNED_TX: u8 = 0x00;
+const PSBT_GLOBAL_TX_VERSION: u8 = 0x02;
+const PSBT_GLOBAL_FALLBACK_LOCKTIME: u8 = 0x03;
+const PSBT_GLOBAL_INPUT_COUNT: u8 = 0x04;
+const PSBT_GLOBAL_OUTPUT_COUNT: u8 = 0x05;
+const PSBT_GLOBAL_TX_MODIFIABLE: u8 = 0x06;
+const PSBT_GLOBAL_VERSION: u8 = 0xFB;
+
+const PSBT_IN_PREVIOUS_TXID: u8 = 0x0E;
+const PSBT_IN_OUTPUT_INDEX: u8 = 0x0F;
+const PSBT_IN_SEQUENCE: u8 = 0x10;
+const PSBT_IN_REQUIRED_TIME_LOCKTIME: u8 = 0x11;
+const PSBT_IN_REQUIRED_HEIGHT_LOCKTIME: u8 = 0x12;
+
+const PSBT_OUT_AMOUNT: u8 = 0x03;
+const PSBT_OUT_SCRIPT: u8 = 0x04;
+
+const PSBT_V0: u32 = 0;
+const PSBT_V2: u32 = 2;
+
+/// Fields that must be excluded from version 0 PSBTs.
+const PSBT_V2_GLOBAL_FIELDS: [u8; 5] = [
+    PSBT_GLOBAL_TX_VERSION,
+    PSBT_GLOBAL_FALLBACK_LOCKTIME,
+    PSBT_GLOBAL_INPUT_COUNT,
+    PSBT_GLOBAL_OUTPUT_COUNT,
+    PSBT_GLOBAL_TX_MODIFIABLE,
+];
+const PSBT_V2_INPUT_FIELDS: [u8; 5] = [
+    PSBT_IN_PREVIOUS_TXID,
+    PSBT_IN_OUTPUT_INDEX,
+    PSBT_IN_SEQUENCE,
+    PSBT_IN_REQUIRED_TIME_LOCKTIME,
+    PSBT_IN_REQUIRED_HEIGHT_LOCKTIME,
+];
+const PSBT_V2_OUTPUT_FIELDS: [u8; 2] = [PSBT_OUT_AMOUNT, PSBT_OUT_SCRIPT];
+
+const DEFAULT_SEQUENCE: u32 = u32::MAX;
+
+/// Deserializes a PSBT of any supported version.
+/// Returns the PSBT converted to version 0, and the original version.
+pub fn deserialize(bytes: &[u8]) -> SigningResult<(Psbt, Proto::PsbtVersion)> {
+    let raw = RawPsbt::decode(bytes).context("Error deserializing PSBT")?;
+    let version = match raw.version()? {
+        PSBT_V0 => {
+            raw.check_no_v2_fields()
+                .context("Error deserializing PSBT")?;
+            Proto::PsbtVersion::PsbtV0
+        },
+        PSBT_V2 => Proto::PsbtVersion::PsbtV2,
+        version => {
+            return SigningError::err(SigningErrorType::Error_not_supported)
+                .context(format!("PSBT version {version} is not supported"))
+        },
+    };
+
+    let psbt = Psbt::deserialize(&raw.into_v0()?.encode())
+        .tw_err(|_| SigningErrorType::Error_input_parse)
+        .context("Error deserializing PSBT")?;
+    Ok((psbt, version))
+}
+
+/// Serializes the PSBT in the given version format.
+pub fn serialize(psbt: &Psbt, version: Proto::PsbtVersion) -> SigningResult<Data> {
+    let raw = RawPsbt::decode(&psbt.serialize()).context("Error re-serializing PSBT")?;
+    let raw = match version {
+        Proto::PsbtVersion::PsbtV0 => raw.into_v0()?.without_v2_fields(),
+        Proto::PsbtVersion::PsbtV2 => raw.into_v2()?,
+    };
+    Ok(raw.encode())
+}
+
+/// A key-value map as it is serialized in a PSBT.
+#[derive(Default)]
+struct KeyValueMap {
+    pairs: Vec<(Data, Data)>,
+}
+
+impl KeyValueMap {
+    fn decode(reader: &mut Reader) -> SigningResult<Self> {
+        let mut map = KeyValueMap::default();
+        loop {
+            // An empty key is a map separator.
+            let key: Data = reader.read()?;
+            if key.is_empty() {
+                return Ok(map);
+            }
+            let value: Data = reader.read()?;
+
+            if map.pairs.iter().any(|(existing, _)| *existing == key) {
+                return SigningError::err(SigningErrorType::Error_input_parse)
+                    .context("PSBT contains duplicate keys");
+            }
+            map.pairs.push((key, value));
+        }
+    }
+
+    fn encode(&self, stream: &mut Stream) {
+        for (key, value) in self.pairs.iter() {
+            stream.append(key).append(value);
+        }
+        stream.append(&0_u8);
+    }
+
+    /// Returns a value of the given key type without key data.
+    fn get(&self, key_type: u8) -> Option<&Data> {
+        self.pairs
+            .iter()
+            .find(|(key, _)| key.as_slice() == [key_type])
+            .map(|(_, value)| value)
+    }
+
+    /// Removes and returns a value of the given key type without key data.
+    fn remove(&mut self, key_type: u8) -> Option<Data> {
+        let idx = self
+            .pairs
+            .iter()
+            .position(|(key, _)| key.as_slice() == [key_type])?;
+        Some(self.pairs.remove(idx).1)
+    }
+
+    fn insert(&mut self, key_type: u8, value: Data) {
+        self.remove(key_type);
+        self.pairs.push((vec![key_type], value));
+    }
+
+    fn decode_value<T: tw_utxo::decode::Decodable>(
+        &self,
+        key_type: u8,
+    ) -> SigningResult<Option<T>> {
+        self.get(key_type)
+            .map(|value| decode(value))
+            .transpose()
+            .with_context(|| format!("Invalid PSBT value of the {key_type:#04x} key type"))
+    }
+
+    fn require_value<T: tw_utxo::decode::Decodable>(&self, key_type: u8) -> SigningResult<T> {
+        self.decode_value(key_type)?
+            .or_tw_err(SigningErrorType::Error_input_parse)
+            .with_context(|| format!("PSBT value of the {key_type:#04x} key type is required"))
+    }
+
+    /// Sorts the pairs by keys, so the key types go in ascending order.
+    fn sort(&mut self) {
+        self.pairs.sort_by(|(a, _), (b, _)| a.cmp(b));
+    }
+}
+
+/// A PSBT as a list of key-value maps.
+struct RawPsbt {
+    global: KeyValueMap,
+    inputs: Vec<KeyValueMap>,
+    outputs: Vec<KeyValueMap>,
+}
+
+impl RawPsbt {
+    fn decode(bytes: &[u8]) -> SigningResult<Self> {
+        let mut reader = Reader::new(bytes);
+        if reader.read_slice(PSBT_MAGIC.len())? != PSBT_MAGIC {
+            return SigningError::err(SigningErrorType::Error_input_parse)
+                .context("Invalid PSBT magic bytes");
+        }
+
+        let global = KeyValueMap::decode(&mut reader)?;
+        let version = global.decode_value(PSBT_GLOBAL_VERSION)?.unwrap_or(PSBT_V0);
+
+        // The number of inputs and outputs is declared differently in version 0 and 2 PSBTs.
+        let (inputs_count, outputs_count) = match (version, global.get(PSBT_GLOBAL_UNSIGNED_TX)) {
+            (PSBT_V2, Some(_)) => {
+                return SigningError::err(SigningErrorType::Error_input_parse)
+                    .context("PSBT version 2 must not contain an unsigned transaction")
+            },
+            (PSBT_V2, None) => {
+                let inputs_count: CompactInteger = global.require_value(PSBT_GLOBAL_INPUT_COUNT)?;
+                let outputs_count: CompactInteger =
+                    global.require_value(PSBT_GLOBAL_OUTPUT_COUNT)?;
+                (inputs_count.to_len()?, outputs_count.to_len()?)
+            },
+            (_, Some(unsigned_tx)) => {
+                let unsigned_tx: Transaction =
+                    decode(unsigned_tx).context("Invalid PSBT unsigned transaction")?;
+                (unsigned_tx.inputs.len(), unsigned_tx.outputs.len())
+            },
+            (_, None) => {
+                return SigningError::err(SigningErrorType::Error_input_parse)
+                    .context("PSBT unsigned transaction is required")
+            },
+        };
+
+        let inputs = (0..inputs_count)
+            .map(|_| KeyValueMap::decode(&mut reader))
+            .collect::<SigningResult<_>>()?;
+        let outputs = (0..outputs_count)
+            .map(|_| KeyValueMap::decode(&mut reader))
+            .collect::<SigningResult<_>>()?;
+
+        if !reader.is_finished() {
+            return SigningError::err(SigningErrorType::Error_input_parse)
+                .context("Unexpected trailing bytes after PSBT");
+        }
+
+        Ok(RawPsbt {
+            global,
+            inputs,
+            outputs,
+        })
+    }
+
+    fn encode(&self) -> Data {
+        let mut stream = Stream::new();
+        stream.append_raw_slice(PSBT_MAGIC);
+        self.global.encode(&mut stream);
+        for map in self.inputs.iter().chain(self.outputs.iter()) {
+            map.encode(&mut stream);
+        }
+        stream.out()
+    }
+
+    fn version(&self) -> SigningResult<u32> {
+        Ok(self
+            .global
+            .decode_value(PSBT_GLOBAL_VERSION)?
+            .unwrap_or(PSBT_V0))
+    }
+
+    /// Checks that a version 0 PSBT doesn't contain the fields allowed in version 2 PSBTs only.
+    fn check_no_v2_fields(&self) -> SigningResult<()> {
+        let has_v2_fields = PSBT_V2_GLOBAL_FIELDS
+            .iter()
+            .any(|key_type| self.global.get(*key_type).is_some())
+            || self.inputs.iter().any(|input| {
+                PSBT_V2_INPUT_FIELDS
+                    .iter()
+                    .any(|key_type| input.get(*key_type).is_some())
+            })
+            || self.outputs.iter().any(|output| {
+                PSBT_V2_OUTPUT_FIELDS
+                    .iter()
+                    .any(|key_type| output.get(*key_type).is_some())
+            });
+
+        if has_v2_fields {
+            return SigningError::err(SigningErrorType::Error_input_parse)
+                .context("PSBT version 0 must not contain version 2 fields");
+        }
+        Ok(())
+    }
+
+    /// Converts the PSBT to version 0 if it's not yet.
+    /// Version 0 PSBTs are kept as is.
+    fn into_v0(mut self) -> SigningResult<Self> {
+        if self.global.get(PSBT_GLOBAL_UNSIGNED_TX).is_some() {
+            return Ok(self);
+        }
+
+        let unsigned_tx = self.unsigned_tx_v2()?;
+        self.global
+            .insert(PSBT_GLOBAL_UNSIGNED_TX, unsigned_tx.encode_out());
+
+        for key_type in [
+            PSBT_GLOBAL_TX_VERSION,
+            PSBT_GLOBAL_FALLBACK_LOCKTIME,
+            PSBT_GLOBAL_INPUT_COUNT,
+            PSBT_GLOBAL_OUTPUT_COUNT,
+            PSBT_GLOBAL_VERSION,
+        ] {
+            self.global.remove(key_type);
+        }
+        for input in self.inputs.iter_mut() {
+            for key_type in [
+                PSBT_IN_PREVIOUS_TXID,
+                PSBT_IN_OUTPUT_INDEX,
+                PSBT_IN_SEQUENCE,
+            ] {
+                input.remove(key_type);
+            }
+        }
+        for output in self.outputs.iter_mut() {
+            for key_type in [PSBT_OUT_AMOUNT, PSBT_OUT_SCRIPT] {
+                output.remove(key_type);
+            }
+        }
+
+        Ok(self)
+    }
+
+    /// Removes the fields that are allowed in version 2 PSBTs only.
+    fn without_v2_fields(mut self) -> Self {
+        self.global.remove(PSBT_GLOBAL_TX_MODIFIABLE);
+        for input in self.inputs.iter_mut() {
+            input.remove(PSBT_IN_REQUIRED_TIME_LOCKTIME);
+            input.remove(PSBT_IN_REQUIRED_HEIGHT_LOCKTIME);
+        }
+        self
+    }
+
+    /// Converts the PSBT to version 2 if it's not yet.
+    fn into_v2(mut self) -> SigningResult<Self> {
+        let Some(unsigned_tx) = self.global.remove(PSBT_GLOBAL_UNSIGNED_TX) else {
+            return Ok(self);
+        };
+        let unsigned_tx: Transaction =
+            decode(&unsigned_tx).context("Invalid PSBT unsigned transaction")?;
+
+        self.global.insert(
+            PSBT_GLOBAL_TX_VERSION,
+            unsigned_tx.version.to_le_bytes().to_vec(),
+        );
+        self.global.insert(
+            PSBT_GLOBAL_FALLBACK_LOCKTIME,
+            unsigned_tx.locktime.to_le_bytes().to_vec(),
+        );
+        self.global.insert(
+            PSBT_GLOBAL_INPUT_COUNT,
+            encode(&CompactInteger::from(unsigned_tx.inputs.len())),
+        );
+        self.global.insert(
+            PSBT_GLOBAL_OUTPUT_COUNT,
+            encode(&CompactInteger::from(unsigned_tx.outputs.len())),
+        );
+        self.global
+            .insert(PSBT_GLOBAL_VERSION, PSBT_V2.to_le_bytes().to_vec());
+        self.global.sort();
+
+        for (input, txin) in self.inputs.iter_mut().zip(unsigned_tx.inputs.iter()) {
+            input.insert(PSBT_IN_PREVIOUS_TXID, txin.previous_output.hash.into_vec());
+            input.insert(
+                PSBT_IN_OUTPUT_INDEX,
+                txin.previous_output.index.to_le_bytes().to_vec(),
+            );
+            input.insert(PSBT_IN_SEQUENCE, txin.sequence.to_le_bytes().to_vec());
+            input.sort();
+        }
+
+        for (output, txout) in self.outputs.iter_mut().zip(unsigned_tx.outputs.iter()) {
+            output.insert(PSBT_OUT_AMOUNT, txout.value.to_le_bytes().to_vec());
+            output.insert(PSBT_OUT_SCRIPT, txout.script_pubkey.to_vec());
+            output.sort();
+        }
+
+        Ok(self)
+    }
+
+    /// Constructs the unsigned transaction from the version 2 PSBT fields.
+    fn unsigned_tx_v2(&self) -> SigningResult<Transaction> {
+        let version: i32 = self.global.require_value(PSBT_GLOBAL_TX_VERSION)?;
+        let inputs = self
+            .inputs
+            .iter()
+            .map(|input| {
+                let hash: H256 = input.require_value(PSBT_IN_PREVIOUS_TXID)?;
+                let index: u32 = input.require_value(PSBT_IN_OUTPUT_INDEX)?;
+                let sequence = input
+                    .decode_value(PSBT_IN_SEQUENCE)?
+                    .unwrap_or(DEFAULT_SEQUENCE);
+                Ok(TransactionInput {
+                    previous_output: OutPoint { hash, index },
+                    sequence,
+                    script_sig: Script::default(),
+                    witness: Witness::default(),
+                })
+            })
+            .collect::<SigningResult<_>>()?;
+
+        let outputs = self
+            .outputs
+            .iter()
+            .map(|output| {
+                let value: Amount = output.require_value(PSBT_OUT_AMOUNT)?;
+                let script_pubkey = output
+                    .get(PSBT_OUT_SCRIPT)
+                    .or_tw_err(SigningErrorType::Error_input_parse)
+                    .context("PSBT output script is required")?;
+                Ok(TransactionOutput {
+                    value,
+                    script_pubkey: Script::from(script_pubkey.clone()),
+                })
+            })
+            .collect::<SigningResult<_>>()?;
+
+        Ok(Transaction {
+            version,
+            inputs,
+            outputs,
+            locktime: self.locktime_v2()?,
+        })
+    }
+
+    /// Determines the transaction lock-time as described in BIP370.
+    /// https://github.com/bitcoin/bips/blob/master/bip-0370.mediawiki#determining-lock-time
+    fn locktime_v2(&self) -> SigningResult<u32> {
+        let mut time_locktimes = Vec::new();
+        let mut height_locktimes = Vec::new();
+        let mut all_support_time = true;
+        let mut all_support_height = true;
+
+        for input in self.inputs.iter() {
+            let time: Option<u32> = input.decode_value(PSBT_IN_REQUIRED_TIME_LOCKTIME)?;
+            let height: Option<u32> = input.decode_value(PSBT_IN_REQUIRED_HEIGHT_LOCKTIME)?;
+            if time.is_none() && height.is_none() {
+                continue;
+            }
+
+            all_support_time &= time.is_some();
+            all_support_height &= height.is_some();
+            time_locktimes.extend(time);
+            height_locktimes.extend(height);
+        }
+
+        if time_locktimes.is_empty() && height_locktimes.is_empty() {
+            let fallback = self.global.decode_value(PSBT_GLOBAL_FALLBACK_LOCKTIME)?;
+            return Ok(fallback.unwrap_or_default());
+        }
+
+        // Height is preferred if both lock-time types are supported by all inputs.
+        if all_support_height {
+            Ok(height_locktimes.into_iter().max().unwrap_or_default())
+        } else if all_support_time {
+            Ok(time_locktimes.into_iter().max().unwrap_or_default())
+        } else {
+            SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("PSBT inputs require incompatible lock-time types")
+        }
+    }
+}
@@ -2,6 +2,7 @@
 //
 // Copyright © 2017 Trust Wallet.
 
+use crate::modules::psbt::psbt_v2;
 use crate::modules::psbt_request::output_psbt::OutputPsbt;
 use crate::modules::psbt_request::utxo_psbt::UtxoPsbt;
 use crate::modules::signing_request::SigningRequestBuilder;
@@ -17,14 +18,14 @@ pub mod utxo_psbt;
 
 pub struct PsbtRequest {
     pub psbt: Psbt,
+    /// The PSBT serialization format that should be kept in the signed PSBT.
+    pub version: Proto::PsbtVersion,
     pub unsigned_tx: UnsignedTransaction<Transaction>,
 }
 
 impl PsbtRequest {
     pub fn build(input: &Proto::SigningInput, psbt_input: &Proto::Psbt) -> SigningResult<Self> {
-        let psbt = Psbt::deserialize(&psbt_input.psbt)
-            .tw_err(|_| SigningErrorType::Error_input_parse)
-            .context("Error deserializing PSBT")?;
+        let (psbt, psbt_version) = psbt_v2::deserialize(&psbt_input.psbt)?;
 
         let version = psbt
             .unsigned_tx
@@ -58,6 +59,10 @@ impl PsbtRequest {
         }
 
         let unsigned_tx = builder.build()?;
-        Ok(PsbtRequest { psbt, unsigned_tx })
+        Ok(PsbtRequest {
+            psbt,
+            version: psbt_version,
+            unsigned_tx,
+        })
     }
 }
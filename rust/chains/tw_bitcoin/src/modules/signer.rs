@@ -2,8 +2,10 @@
 //
 // Copyright © 2017 Trust Wallet.
 
+use crate::modules::fee_bumping::child_pays_for_parent::ChildPaysForParentBuilder;
+use crate::modules::fee_bumping::replace_by_fee::ReplaceByFeeBuilder;
 use crate::modules::protobuf_builder::ProtobufBuilder;
-use crate::modules::psbt::update_psbt_signed;
+use crate::modules::psbt::{psbt_v2, update_psbt_signed};
 use crate::modules::psbt_request::PsbtRequest;
 use crate::modules::signing_request::SigningRequestBuilder;
 use std::borrow::Cow;
@@ -41,6 +43,14 @@ impl BitcoinSigner {
         match input.transaction {
             TransactionType::builder(ref tx) => Self::sign_with_tx_builder(coin, input, tx),
             TransactionType::psbt(ref psbt) => Self::sign_psbt(coin, input, psbt),
+            TransactionType::replace_by_fee(ref rbf) => {
+                let tx = ReplaceByFeeBuilder::tx_builder(coin, input, rbf)?;
+                Self::sign_with_tx_builder(coin, input, &tx)
+            },
+            TransactionType::child_pays_for_parent(ref cpfp) => {
+                let tx = ChildPaysForParentBuilder::tx_builder(coin, input, cpfp)?;
+                Self::sign_with_tx_builder(coin, input, &tx)
+            },
             TransactionType::None => SigningError::err(SigningErrorType::Error_invalid_params)
                 .context("Either `TransactionBuilder` or `Psbt` should be set"),
         }
@@ -83,6 +93,7 @@ impl BitcoinSigner {
     ) -> SigningResult<Proto::SigningOutput<'static>> {
         let PsbtRequest {
             mut psbt,
+            version,
             unsigned_tx,
         } = PsbtRequest::build(input, psbt_input)?;
 
@@ -108,7 +119,7 @@ impl BitcoinSigner {
             fee,
             weight: signed_tx.weight() as u64,
             psbt: Some(Proto::Psbt {
-                psbt: Cow::from(psbt.serialize()),
+                psbt: Cow::from(psbt_v2::serialize(&psbt, version)?),
             }),
             ..Proto::SigningOutput::default()
         })
@@ -32,6 +32,8 @@ impl SigningRequestBuilder {
         let chain_info = Self::chain_info(coin, &input.chain_info)?;
         let dust_policy = Self::dust_policy(&transaction_builder.dust_policy)?;
         let fee_per_vbyte = transaction_builder.fee_per_vb;
+        let long_term_fee_per_vbyte =
+            Some(transaction_builder.long_term_fee_per_vb).filter(|fee_rate| *fee_rate != 0);
         let version = Self::transaction_version(&transaction_builder.version);
 
         let public_keys = Self::get_public_keys(input)?;
@@ -65,6 +67,7 @@ impl SigningRequestBuilder {
                 ty: RequestType::SendMax { unsigned_tx },
                 dust_policy,
                 fee_per_vbyte,
+                long_term_fee_per_vbyte,
             });
         }
 
@@ -98,6 +101,7 @@ impl SigningRequestBuilder {
             },
             dust_policy,
             fee_per_vbyte,
+            long_term_fee_per_vbyte,
         })
     }
 
@@ -122,6 +126,10 @@ impl SigningRequestBuilder {
             Proto::InputSelector::SelectAscending => InputSelector::Ascending,
             Proto::InputSelector::SelectInOrder => InputSelector::InOrder,
             Proto::InputSelector::SelectDescending => InputSelector::Descending,
+            Proto::InputSelector::BranchAndBound => InputSelector::BranchAndBound,
+            Proto::InputSelector::Knapsack => InputSelector::Knapsack,
+            Proto::InputSelector::SingleRandomDraw => InputSelector::SingleRandomDraw,
+            Proto::InputSelector::MinimizeWaste => InputSelector::MinimizeWaste,
             Proto::InputSelector::UseAll => InputSelector::UseAll,
         }
     }
@@ -129,6 +137,11 @@ impl SigningRequestBuilder {
     fn dust_policy(proto: &ProtoDustPolicy) -> SigningResult<DustPolicy> {
         match proto {
             ProtoDustPolicy::fixed_dust_threshold(fixed) => Ok(DustPolicy::FixedAmount(*fixed)),
+            ProtoDustPolicy::dynamic_dust_min_relay_fee_per_kvb(fee_rate) => {
+                Ok(DustPolicy::DynamicDust {
+                    min_relay_fee_per_kvb: *fee_rate,
+                })
+            },
             ProtoDustPolicy::None => SigningError::err(SigningErrorType::Error_invalid_params)
                 .context("No dust policy provided"),
         }
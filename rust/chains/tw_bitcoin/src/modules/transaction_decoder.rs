@@ -0,0 +1,99 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::modules::protobuf_builder::ProtobufBuilder;
+use crate::modules::signing_request::SigningRequestBuilder;
+use crate::modules::tx_builder::script_parser::{StandardScript, StandardScriptParser};
+use crate::modules::tx_builder::BitcoinChainInfo;
+use tw_coin_entry::coin_context::CoinContext;
+use tw_coin_entry::error::prelude::*;
+use tw_coin_entry::modules::transaction_decoder::TransactionDecoder;
+use tw_coin_entry::signing_output_error;
+use tw_proto::BitcoinV2::Proto;
+use tw_proto::BitcoinV2::Proto::mod_Output::mod_OutputBuilder::OneOfvariant as BuilderType;
+use tw_proto::BitcoinV2::Proto::mod_Output::OneOfto_recipient as ToRecipientProto;
+use tw_utxo::decode::decode;
+use tw_utxo::script::standard_script::conditions;
+use tw_utxo::script::Script;
+use tw_utxo::transaction::standard_transaction::{Transaction, TransactionOutput};
+use tw_utxo::transaction::transaction_interface::TransactionInterface;
+
+pub struct BitcoinTransactionDecoder;
+
+impl TransactionDecoder for BitcoinTransactionDecoder {
+    type Output = Proto::DecodingTransactionOutput<'static>;
+
+    fn decode_transaction(&self, coin: &dyn CoinContext, tx: &[u8]) -> Self::Output {
+        Self::decode_transaction_impl(coin, tx)
+            .unwrap_or_else(|e| signing_output_error!(Proto::DecodingTransactionOutput, e))
+    }
+}
+
+impl BitcoinTransactionDecoder {
+    fn decode_transaction_impl(
+        coin: &dyn CoinContext,
+        tx: &[u8],
+    ) -> SigningResult<Proto::DecodingTransactionOutput<'static>> {
+        let chain_info = SigningRequestBuilder::chain_info(coin, &None)?;
+
+        let decoded_tx: Transaction =
+            decode(tx).context("Error decoding a legacy or segwit transaction")?;
+
+        let outputs = decoded_tx
+            .outputs
+            .iter()
+            .map(|output| Self::output_to_proto(output, &chain_info))
+            .collect();
+
+        Ok(Proto::DecodingTransactionOutput {
+            transaction: Some(ProtobufBuilder::tx_to_proto(&decoded_tx)),
+            outputs,
+            txid: decoded_tx.txid().into(),
+            vsize: decoded_tx.vsize() as u64,
+            weight: decoded_tx.weight() as u64,
+            ..Proto::DecodingTransactionOutput::default()
+        })
+    }
+
+    fn output_to_proto(
+        output: &TransactionOutput,
+        chain_info: &BitcoinChainInfo,
+    ) -> Proto::Output<'static> {
+        let to_recipient = Self::output_recipient(&output.script_pubkey, chain_info)
+            // Cannot classify the output scriptPubkey. Return it as is.
+            .unwrap_or_else(|| {
+                ToRecipientProto::custom_script_pubkey(output.script_pubkey.to_vec().into())
+            });
+
+        Proto::Output {
+            value: output.value,
+            to_recipient,
+        }
+    }
+
+    fn output_recipient(
+        script_pubkey: &Script,
+        chain_info: &BitcoinChainInfo,
+    ) -> Option<ToRecipientProto<'static>> {
+        let builder =
+            |variant| ToRecipientProto::builder(Proto::mod_Output::OutputBuilder { variant });
+
+        // P2PK doesn't have its own address, so return the public key as is.
+        if let Some(pubkey) = conditions::match_p2pk(script_pubkey) {
+            return Some(builder(BuilderType::p2pk(pubkey.to_vec().into())));
+        }
+        if let Some(data) = conditions::match_op_return_data(script_pubkey) {
+            return Some(builder(BuilderType::op_return(data.to_vec().into())));
+        }
+
+        match StandardScriptParser.parse(script_pubkey).ok()? {
+            // OP_RETURN with a non-standard payload.
+            StandardScript::OpReturn(_) => None,
+            standard => standard
+                .try_to_address(chain_info)
+                .ok()?
+                .map(|addr| ToRecipientProto::to_address(addr.to_string().into())),
+        }
+    }
+}
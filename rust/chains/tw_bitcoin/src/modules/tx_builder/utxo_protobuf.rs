@@ -18,6 +18,7 @@ use tw_utxo::address::standard_bitcoin::StandardBitcoinAddress;
 use tw_utxo::address::taproot::TaprootAddress;
 use tw_utxo::script::Script;
 use tw_utxo::sighash::SighashType;
+use tw_utxo::taproot::{ControlBlock, TaprootLeaf, TaprootTree, TAPSCRIPT_LEAF_VERSION};
 use tw_utxo::transaction::standard_transaction::builder::UtxoBuilder;
 use tw_utxo::transaction::standard_transaction::TransactionInput;
 use tw_utxo::transaction::transaction_parts::OutPoint;
@@ -48,13 +49,15 @@ impl<'a> UtxoProtobuf<'a> {
 
         match self.input.claiming_script {
             ScriptType::script_builder(ref builder) => match builder.variant {
-                // BuilderType::p2sh(ref redeem_script) => self.p2sh(redeem_script.to_vec()),
+                BuilderType::p2sh(ref script_hash) => self.p2sh(script_hash),
                 BuilderType::p2pk(ref pubkey) => self.p2pk(pubkey),
                 BuilderType::p2pkh(ref pubkey_or_hash) => self.p2pkh(pubkey_or_hash),
-                // BuilderType::p2wsh(ref redeem_script) => self.p2wsh(redeem_script.to_vec()),
+                BuilderType::p2wsh(ref script_hash) => self.p2wsh(script_hash),
                 BuilderType::p2wpkh(ref pubkey_or_hash) => self.p2wpkh(pubkey_or_hash),
                 BuilderType::p2tr_key_path(ref key_path) => self.p2tr_key_path(key_path),
-                // BuilderType::p2tr_script_path(ref script) => self.p2tr_script_path(script),
+                BuilderType::p2tr_script_path(ref script_path) => {
+                    self.p2tr_script_path(script_path)
+                },
                 BuilderType::brc20_inscribe(ref inscription) => self.brc20_inscribe(inscription),
                 BuilderType::None => SigningError::err(SigningErrorType::Error_invalid_params)
                     .context("No Input Builder type provided"),
@@ -66,11 +69,14 @@ impl<'a> UtxoProtobuf<'a> {
         }
     }
 
-    // TODO next iteration
-    // pub fn p2sh(&self, redeem_script: Data) -> SigningResult<(TransactionInput, UtxoToSign)> {
-    //     let redeem_script = Script::from(redeem_script);
-    //     self.prepare_builder()?.p2sh(redeem_script)
-    // }
+    pub fn p2sh(
+        &self,
+        script_hash: &Proto::mod_Input::InputScriptHash,
+    ) -> SigningResult<(TransactionInput, UtxoToSign)> {
+        let redeem_script = Script::from(script_hash.redeem_script.to_vec());
+        let signers = Self::get_script_hash_signers(script_hash).context("P2SH builder")?;
+        self.prepare_builder()?.p2sh(redeem_script, &signers)
+    }
 
     pub fn p2pk(&self, pubkey: &[u8]) -> SigningResult<(TransactionInput, UtxoToSign)> {
         let pubkey = ecdsa::secp256k1::PublicKey::try_from(pubkey)
@@ -88,11 +94,14 @@ impl<'a> UtxoProtobuf<'a> {
         self.prepare_builder()?.p2pkh(&pubkey_hash)
     }
 
-    // TODO next iteration
-    // pub fn p2wsh(&self, redeem_script: Data) -> SigningResult<(TransactionInput, UtxoToSign)> {
-    //     let script = Script::from(redeem_script);
-    //     self.prepare_builder()?.p2wsh(script)
-    // }
+    pub fn p2wsh(
+        &self,
+        script_hash: &Proto::mod_Input::InputScriptHash,
+    ) -> SigningResult<(TransactionInput, UtxoToSign)> {
+        let witness_script = Script::from(script_hash.redeem_script.to_vec());
+        let signers = Self::get_script_hash_signers(script_hash).context("P2WSH builder")?;
+        self.prepare_builder()?.p2wsh(witness_script, &signers)
+    }
 
     pub fn p2wpkh(
         &self,
@@ -107,16 +116,30 @@ impl<'a> UtxoProtobuf<'a> {
         self.prepare_builder()?.p2tr_key_path(&public_key)
     }
 
-    // TODO next iteration
-    // pub fn p2tr_script_path(
-    //     &self,
-    //     taproot_script_path: &Proto::mod_Input::InputTaprootScriptPath,
-    // ) -> SigningResult<(TransactionInput, UtxoToSign)> {
-    //     let payload = Script::from(taproot_script_path.payload.to_vec());
-    //     // let x = taproot_script_path.
-    //     self.prepare_builder()?
-    //         .p2tr_script_path(payload, taproot_script_path.control_block.to_vec())
-    // }
+    pub fn p2tr_script_path(
+        &self,
+        script_path: &Proto::mod_Input::InputTaprootScriptPath,
+    ) -> SigningResult<(TransactionInput, UtxoToSign)> {
+        let payload = Script::from(script_path.payload.to_vec());
+        let signers = script_path
+            .signers
+            .iter()
+            .map(|signer| {
+                schnorr::PublicKey::try_from(signer.as_ref())
+                    .into_tw()
+                    .context("Expected a valid compressed schnorr signer public key")
+            })
+            .collect::<SigningResult<Vec<_>>>()?;
+
+        let control_block = if script_path.control_block.is_empty() {
+            Self::build_control_block(script_path, &payload)?
+        } else {
+            ControlBlock::decode(&script_path.control_block)?
+        };
+
+        self.prepare_builder()?
+            .p2tr_script_path(payload, &control_block, &signers)
+    }
 
     pub fn brc20_inscribe(
         &self,
@@ -151,8 +174,10 @@ impl<'a> UtxoProtobuf<'a> {
                 builder.p2tr_key_path_with_tweaked_pubkey(&tweaked_pubkey)
             },
             StandardScript::P2SH(_) | StandardScript::P2WSH(_) => {
-                SigningError::err(SigningErrorType::Error_not_supported)
-                    .context("P2SH and P2WSH scriptPubkey's are not supported yet")
+                SigningError::err(SigningErrorType::Error_script_redeem).context(
+                    "P2SH and P2WSH scriptPubkey's can only be spent via 'Input.InputBuilder.p2sh' or 'Input.InputBuilder.p2wsh'.\
+                     That is because the scriptPubkey does not provide redeem script but its hash",
+                )
             },
             StandardScript::OpReturn(_) => SigningError::err(SigningErrorType::Error_invalid_utxo)
                 .context("Cannot spend an OP_RETURN output"),
@@ -262,6 +287,62 @@ impl<'a> UtxoProtobuf<'a> {
             .sighash_type(sighash_ty))
     }
 
+    /// Builds a Taproot script tree from the given leaves,
+    /// and constructs a control block of the spent leaf script.
+    fn build_control_block(
+        script_path: &Proto::mod_Input::InputTaprootScriptPath,
+        payload: &Script,
+    ) -> SigningResult<ControlBlock> {
+        let internal_pubkey =
+            schnorr::PublicKey::try_from(script_path.internal_public_key.as_ref())
+                .into_tw()
+                .context("Expected a valid Taproot internal public key")?;
+
+        let leaves = script_path
+            .leaves
+            .iter()
+            .map(|leaf| {
+                let version = match leaf.leaf_version {
+                    0 => TAPSCRIPT_LEAF_VERSION,
+                    version => u8::try_from(version)
+                        .tw_err(|_| SigningErrorType::Error_invalid_params)
+                        .context("Taproot leaf version must fit in a byte")?,
+                };
+                let depth = u8::try_from(leaf.depth)
+                    .tw_err(|_| SigningErrorType::Error_invalid_params)
+                    .context("Taproot leaf depth is too large")?;
+                Ok(TaprootLeaf {
+                    script: Script::from(leaf.script.to_vec()),
+                    version,
+                    depth,
+                })
+            })
+            .collect::<SigningResult<Vec<_>>>()?;
+
+        let spent_leaf = leaves
+            .iter()
+            .find(|leaf| leaf.script == *payload)
+            .or_tw_err(SigningErrorType::Error_invalid_params)
+            .context("The spent leaf script must be one of the Taproot script tree leaves")?;
+
+        TaprootTree::new(&internal_pubkey, &leaves)?
+            .control_block(&spent_leaf.script, spent_leaf.version)
+    }
+
+    fn get_script_hash_signers(
+        script_hash: &Proto::mod_Input::InputScriptHash,
+    ) -> SigningResult<Vec<ecdsa::secp256k1::PublicKey>> {
+        script_hash
+            .signers
+            .iter()
+            .map(|signer| {
+                ecdsa::secp256k1::PublicKey::try_from(signer.as_ref())
+                    .into_tw()
+                    .context("Expected a valid ecdsa secp256k1 signer public key")
+            })
+            .collect()
+    }
+
     /// Tries to convert [`Proto::PublicKeyOrHash`] to [`Hash<N>`].
     /// Please note `P2PKH` and `P2WPKH` use the same `ripemd(sha256(x))` hash function.
     fn get_ecdsa_pubkey_from_proto(
@@ -33,6 +33,13 @@ impl From<Address> for EthAddress {
     }
 }
 
+impl From<EthAddress> for Address {
+    #[inline]
+    fn from(addr: EthAddress) -> Self {
+        Address(addr)
+    }
+}
+
 impl FromStr for Address {
     type Err = AddressError;
 
@@ -15,10 +15,13 @@ pub fn compile_instructions(
 }
 
 fn position(keys: &[SolanaAddress], key: &SolanaAddress) -> SigningResult<u8> {
-    keys.iter()
+    let position = keys
+        .iter()
         .position(|k| k == key)
-        .map(|k| k as u8)
-        .or_tw_err(SigningErrorType::Error_internal)
+        .or_tw_err(SigningErrorType::Error_internal)?;
+    u8::try_from(position)
+        .tw_err(|_| SigningErrorType::Error_tx_too_big)
+        .context("Too many accounts in the transaction")
 }
 
 /// https://github.com/solana-labs/solana/blob/4b65cc8eef6ef79cb9b9cbc534a99b4900e58cf7/sdk/program/src/message/legacy.rs#L72-L84
@@ -6,6 +6,9 @@
 
 use crate::address::SolanaAddress;
 use crate::instruction::Instruction;
+use crate::transaction::v0::{
+    AddressLookupTableAccount, LoadedAddresses, MessageAddressTableLookup,
+};
 use crate::transaction::MessageHeader;
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
@@ -15,8 +18,21 @@ use tw_coin_entry::error::prelude::*;
 struct CompiledKeyMeta {
     is_signer: bool,
     is_writable: bool,
+    is_invoked: bool,
 }
 
+impl CompiledKeyMeta {
+    /// Signers and program IDs cannot be loaded via address table lookups.
+    fn is_lookup_eligible(&self) -> bool {
+        !self.is_signer && !self.is_invoked
+    }
+}
+
+/// Each account loaded via a lookup table takes 1 byte index instead of 32 bytes key,
+/// but every table lookup costs 34 bytes (table key and two index list lengths).
+/// So a table lookup reduces the message size only if it loads 2 accounts at least.
+const MIN_ACCOUNTS_PER_TABLE_LOOKUP: usize = 2;
+
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub(crate) struct CompiledKeys {
     ordered_keys: Vec<SolanaAddress>,
@@ -32,6 +48,7 @@ impl CompiledKeys {
             CompiledKeyMeta {
                 is_signer: true,
                 is_writable: true,
+                is_invoked: false,
             },
         );
         // Fee payer must be the first account in the keys list.
@@ -60,12 +77,116 @@ impl CompiledKeys {
             if matches!(meta_entry, Entry::Vacant(_)) {
                 self.ordered_keys.push(ix.program_id);
             }
-            meta_entry.or_default();
+            meta_entry.or_default().is_invoked = true;
         }
 
         self
     }
 
+    /// Moves non-signer accounts found in the given lookup tables from the static account keys into table lookups.
+    /// Tables that load more accounts are preferred, and tables that do not reduce the message size are skipped.
+    pub fn try_extract_table_lookups(
+        &mut self,
+        lookup_tables: &[AddressLookupTableAccount],
+    ) -> SigningResult<(Vec<MessageAddressTableLookup>, LoadedAddresses)> {
+        let mut remaining_tables: Vec<_> = lookup_tables.iter().collect();
+        let mut table_lookups = Vec::default();
+        let mut loaded_addresses = LoadedAddresses::default();
+
+        loop {
+            // Find the first table that loads the most accounts.
+            let best_table = remaining_tables
+                .iter()
+                .enumerate()
+                .map(|(idx, table)| (idx, self.count_keys_found_in_lookup_table(table)))
+                .fold(
+                    None,
+                    |best: Option<(usize, usize)>, (idx, count)| match best {
+                        Some((_, best_count)) if best_count >= count => best,
+                        _ => Some((idx, count)),
+                    },
+                );
+
+            let Some((table_idx, keys_count)) = best_table else {
+                break;
+            };
+            if keys_count < MIN_ACCOUNTS_PER_TABLE_LOOKUP {
+                break;
+            }
+
+            let table = remaining_tables.remove(table_idx);
+            let (writable_indexes, writable_keys) =
+                self.try_drain_keys_found_in_lookup_table(table, |meta| meta.is_writable)?;
+            let (readonly_indexes, readonly_keys) =
+                self.try_drain_keys_found_in_lookup_table(table, |meta| !meta.is_writable)?;
+
+            table_lookups.push(MessageAddressTableLookup {
+                account_key: table.key,
+                writable_indexes,
+                readonly_indexes,
+            });
+            loaded_addresses.writable.extend(writable_keys);
+            loaded_addresses.readonly.extend(readonly_keys);
+        }
+
+        Ok((table_lookups, loaded_addresses))
+    }
+
+    fn count_keys_found_in_lookup_table(&self, lookup_table: &AddressLookupTableAccount) -> usize {
+        self.ordered_keys
+            .iter()
+            .filter(|key| {
+                let meta = self.key_meta_map.get(key).copied().unwrap_or_default();
+                meta.is_lookup_eligible() && lookup_table.addresses.contains(key)
+            })
+            .count()
+    }
+
+    /// https://github.com/solana-labs/solana/blob/4b65cc8eef6ef79cb9b9cbc534a99b4900e58cf7/sdk/program/src/message/compiled_keys.rs#L142-L171
+    fn try_drain_keys_found_in_lookup_table<F>(
+        &mut self,
+        lookup_table: &AddressLookupTableAccount,
+        key_meta_filter: F,
+    ) -> SigningResult<(Vec<u8>, Vec<SolanaAddress>)>
+    where
+        F: Fn(&CompiledKeyMeta) -> bool,
+    {
+        let mut lookup_table_indexes = Vec::default();
+        let mut drained_keys = Vec::default();
+
+        for search_key in self.ordered_keys.iter() {
+            let meta = self
+                .key_meta_map
+                .get(search_key)
+                .copied()
+                .unwrap_or_default();
+            if !meta.is_lookup_eligible() || !key_meta_filter(&meta) {
+                continue;
+            }
+
+            let Some(key_index) = lookup_table
+                .addresses
+                .iter()
+                .position(|key| key == search_key)
+            else {
+                continue;
+            };
+
+            let lookup_table_index = u8::try_from(key_index)
+                .tw_err(|_| SigningErrorType::Error_invalid_params)
+                .context("Address lookup table index overflow")?;
+            lookup_table_indexes.push(lookup_table_index);
+            drained_keys.push(*search_key);
+        }
+
+        self.ordered_keys.retain(|key| !drained_keys.contains(key));
+        for key in drained_keys.iter() {
+            self.key_meta_map.remove(key);
+        }
+
+        Ok((lookup_table_indexes, drained_keys))
+    }
+
     pub fn try_into_message_components(self) -> SigningResult<(MessageHeader, Vec<SolanaAddress>)> {
         let try_into_u8 = |num: usize| -> SigningResult<u8> {
             u8::try_from(num).tw_err(|_| SigningErrorType::Error_tx_too_big)
@@ -121,3 +242,112 @@ impl CompiledKeys {
         Ok((header, static_account_keys))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instruction::AccountMeta;
+    use std::str::FromStr;
+
+    fn address(s: &str) -> SolanaAddress {
+        SolanaAddress::from_str(s).unwrap()
+    }
+
+    fn table(key: &str, addresses: &[SolanaAddress]) -> AddressLookupTableAccount {
+        AddressLookupTableAccount {
+            key: address(key),
+            addresses: addresses.to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_try_extract_table_lookups() {
+        let payer = address("GymAh18wHuFTytfSJWi8eYTA9x5S3sNb9CJSGBWoPRE3");
+        let signer = address("2oKoYSAHgveX91917v4DUEuN8BNKXDg8KJWpaGyEay9V");
+        let writable = address("EN2sCsJ1WDV8UFqsiTXHcUPUxQ4juE71eCknHYYMifkd");
+        let readonly_1 = address("71e8mDsh3PR6gN64zL1HjwuxyKpgRXrPDUJT7XXojsVd");
+        let readonly_2 = address("SRMuApVNdxXokk5GT7XD5cUUgXMBCoAz2LHeuAoKWRt");
+        let program_id = address("11111111111111111111111111111111");
+
+        let ix = Instruction::new(
+            program_id,
+            Vec::default(),
+            vec![
+                AccountMeta::new(signer, true),
+                AccountMeta::readonly(readonly_2, false),
+                AccountMeta::new(writable, false),
+                AccountMeta::readonly(readonly_1, false),
+            ],
+        );
+        let mut compiled_keys = CompiledKeys::with_fee_payer(payer).compile(&[ix]);
+
+        let lookup_tables = [
+            // Loads 2 accounts.
+            table(
+                "5cFsmTCEfmvpBUBHqsWZnf9n5vTWLYH2LT8X7HdShwxP",
+                &[writable, readonly_1],
+            ),
+            // Loads 3 accounts, signers and program IDs are ignored.
+            table(
+                "FeXRmSWmwChZbB2EC7Qjw9XKk28yBrPj3k3nzT1DKfak",
+                &[signer, readonly_1, program_id, readonly_2, writable],
+            ),
+        ];
+        let (lookups, loaded) = compiled_keys
+            .try_extract_table_lookups(&lookup_tables)
+            .unwrap();
+
+        assert_eq!(
+            lookups,
+            vec![MessageAddressTableLookup {
+                account_key: lookup_tables[1].key,
+                writable_indexes: vec![4],
+                readonly_indexes: vec![3, 1],
+            }]
+        );
+        assert_eq!(loaded.writable, vec![writable]);
+        assert_eq!(loaded.readonly, vec![readonly_2, readonly_1]);
+
+        let (header, static_keys) = compiled_keys.try_into_message_components().unwrap();
+        assert_eq!(
+            header,
+            MessageHeader {
+                num_required_signatures: 2,
+                num_readonly_signed_accounts: 0,
+                num_readonly_unsigned_accounts: 1,
+            }
+        );
+        assert_eq!(static_keys, vec![payer, signer, program_id]);
+    }
+
+    #[test]
+    fn test_try_extract_table_lookups_single_account() {
+        let payer = address("GymAh18wHuFTytfSJWi8eYTA9x5S3sNb9CJSGBWoPRE3");
+        let recipient = address("EN2sCsJ1WDV8UFqsiTXHcUPUxQ4juE71eCknHYYMifkd");
+        let program_id = address("11111111111111111111111111111111");
+
+        let ix = Instruction::new(
+            program_id,
+            Vec::default(),
+            vec![
+                AccountMeta::new(payer, true),
+                AccountMeta::new(recipient, false),
+            ],
+        );
+        let mut compiled_keys = CompiledKeys::with_fee_payer(payer).compile(&[ix]);
+
+        // A single account lookup would increase the message size.
+        let lookup_tables = [table(
+            "FeXRmSWmwChZbB2EC7Qjw9XKk28yBrPj3k3nzT1DKfak",
+            &[recipient],
+        )];
+        let (lookups, loaded) = compiled_keys
+            .try_extract_table_lookups(&lookup_tables)
+            .unwrap();
+        assert!(lookups.is_empty());
+        assert_eq!(loaded, LoadedAddresses::default());
+
+        let (_, static_keys) = compiled_keys.try_into_message_components().unwrap();
+        assert_eq!(static_keys, vec![payer, recipient, program_id]);
+    }
+}
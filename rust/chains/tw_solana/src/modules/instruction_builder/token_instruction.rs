@@ -6,11 +6,68 @@ use crate::address::SolanaAddress;
 use crate::defined_addresses::*;
 use crate::instruction::{AccountMeta, Instruction};
 use std::mem::size_of;
+use tw_coin_entry::error::prelude::*;
 use tw_memory::Data;
 
 /// Instructions supported by the token program.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TokenInstruction {
+    /// Transfers tokens from one account to another either directly or via a
+    /// delegate.  If this account is associated with the native mint then equal
+    /// amounts of SOL and Tokens will be transferred to the destination
+    /// account.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   * Single owner/delegate
+    ///   0. `[writable]` The source account.
+    ///   1. `[writable]` The destination account.
+    ///   2. `[signer]` The source account's owner/delegate.
+    ///
+    ///   * Multisignature owner/delegate
+    ///   0. `[writable]` The source account.
+    ///   1. `[writable]` The destination account.
+    ///   2. `[]` The source account's multisignature owner/delegate.
+    ///   3. ..3+M `[signer]` M signer accounts.
+    Transfer {
+        /// The amount of tokens to transfer.
+        amount: u64,
+    },
+    /// Approves a delegate.  A delegate is given the authority over tokens on
+    /// behalf of the source account's owner.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   * Single owner
+    ///   0. `[writable]` The source account.
+    ///   1. `[]` The delegate.
+    ///   2. `[signer]` The source account owner.
+    ///
+    ///   * Multisignature owner
+    ///   0. `[writable]` The source account.
+    ///   1. `[]` The delegate.
+    ///   2. `[]` The source account's multisignature owner.
+    ///   3. ..3+M `[signer]` M signer accounts
+    Approve {
+        /// The amount of tokens the delegate is approved for.
+        amount: u64,
+    },
+    /// Close an account by transferring all its SOL to the destination account.
+    /// Non-native accounts may only be closed if its token amount is zero.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   * Single owner
+    ///   0. `[writable]` The account to close.
+    ///   1. `[writable]` The destination account.
+    ///   2. `[signer]` The account's owner.
+    ///
+    ///   * Multisignature owner
+    ///   0. `[writable]` The account to close.
+    ///   1. `[writable]` The destination account.
+    ///   2. `[]` The account's multisignature owner.
+    ///   3. ..3+M `[signer]` M signer accounts.
+    CloseAccount,
     /// Transfers tokens from one account to another either directly or via a
     /// delegate.  If this account is associated with the native mint then equal
     /// amounts of SOL and Tokens will be transferred to the destination
@@ -40,9 +97,67 @@ pub enum TokenInstruction {
         /// Expected number of base 10 digits to the right of the decimal place.
         decimals: u8,
     },
+    /// Token-2022 `TransferFee` extension instruction.
+    /// Transfers tokens from one account to another either directly or via a
+    /// delegate, asserting the expected fee withheld by the mint.
+    ///
+    /// Accounts expected by this instruction are the same as for `TransferChecked`.
+    TransferCheckedWithFee {
+        /// The amount of tokens to transfer.
+        amount: u64,
+        /// Expected number of base 10 digits to the right of the decimal place.
+        decimals: u8,
+        /// Expected fee assessed on this transfer, calculated off-chain based on
+        /// the transfer_fee_basis_points and maximum_fee of the mint.
+        fee: u64,
+    },
 }
 
 impl TokenInstruction {
+    /// https://github.com/solana-labs/solana-program-library/blob/5418cf9b90d5c9ff5bff9f55fd17651f66c98902/token/program/src/instruction.rs
+    const TRANSFER: u8 = 3;
+    const APPROVE: u8 = 4;
+    const CLOSE_ACCOUNT: u8 = 9;
+    const TRANSFER_CHECKED: u8 = 12;
+    /// https://github.com/solana-labs/solana-program-library/blob/5418cf9b90d5c9ff5bff9f55fd17651f66c98902/token/program-2022/src/extension/transfer_fee/instruction.rs
+    const TRANSFER_FEE_EXTENSION: u8 = 26;
+    const TRANSFER_CHECKED_WITH_FEE: u8 = 1;
+
+    /// Unpacks a byte buffer into a [TokenInstruction](enum.TokenInstruction.html).
+    /// Returns `None` if the instruction is not supported or the buffer is invalid.
+    pub fn unpack(input: &[u8]) -> Option<Self> {
+        let (&tag, rest) = input.split_first()?;
+        match tag {
+            Self::TRANSFER => Some(Self::Transfer {
+                amount: unpack_u64(rest)?,
+            }),
+            Self::APPROVE => Some(Self::Approve {
+                amount: unpack_u64(rest)?,
+            }),
+            Self::CLOSE_ACCOUNT => Some(Self::CloseAccount),
+            Self::TRANSFER_CHECKED => {
+                let amount = unpack_u64(rest)?;
+                let &decimals = rest.get(size_of::<u64>())?;
+                Some(Self::TransferChecked { amount, decimals })
+            },
+            Self::TRANSFER_FEE_EXTENSION => {
+                let (&fee_tag, rest) = rest.split_first()?;
+                if fee_tag != Self::TRANSFER_CHECKED_WITH_FEE {
+                    return None;
+                }
+                let amount = unpack_u64(rest)?;
+                let &decimals = rest.get(size_of::<u64>())?;
+                let fee = unpack_u64(rest.get(size_of::<u64>() + 1..)?)?;
+                Some(Self::TransferCheckedWithFee {
+                    amount,
+                    decimals,
+                    fee,
+                })
+            },
+            _ => None,
+        }
+    }
+
     /// Packs a [TokenInstruction](enum.TokenInstruction.html) into a byte
     /// buffer.
     ///
@@ -53,18 +168,115 @@ impl TokenInstruction {
     pub fn pack(&self) -> Vec<u8> {
         let mut buf = Vec::with_capacity(size_of::<Self>());
         match self {
+            &Self::Transfer { amount } => {
+                buf.push(Self::TRANSFER);
+                buf.extend_from_slice(&amount.to_le_bytes());
+            },
+            &Self::Approve { amount } => {
+                buf.push(Self::APPROVE);
+                buf.extend_from_slice(&amount.to_le_bytes());
+            },
+            Self::CloseAccount => buf.push(Self::CLOSE_ACCOUNT),
             &Self::TransferChecked { amount, decimals } => {
                 // https://github.com/solana-labs/solana-program-library/blob/5418cf9b90d5c9ff5bff9f55fd17651f66c98902/token/program-2022/src/instruction.rs#L334-L339
                 // https://github.com/trustwallet/wallet-core/blob/cd5a27481d2181e63362cb57e2b2160506cce163/src/Solana/Instruction.h#L37
-                buf.push(12);
+                buf.push(Self::TRANSFER_CHECKED);
+                buf.extend_from_slice(&amount.to_le_bytes());
+                buf.push(decimals);
+            },
+            &Self::TransferCheckedWithFee {
+                amount,
+                decimals,
+                fee,
+            } => {
+                buf.push(Self::TRANSFER_FEE_EXTENSION);
+                buf.push(Self::TRANSFER_CHECKED_WITH_FEE);
                 buf.extend_from_slice(&amount.to_le_bytes());
                 buf.push(decimals);
+                buf.extend_from_slice(&fee.to_le_bytes());
             },
         };
         buf
     }
 }
 
+fn unpack_u64(input: &[u8]) -> Option<u64> {
+    let bytes = input.get(..size_of::<u64>())?;
+    Some(u64::from_le_bytes(bytes.try_into().ok()?))
+}
+
+/// Maximum fee basis points (100%).
+pub const MAX_FEE_BASIS_POINTS: u16 = 10_000;
+
+/// Token-2022 `TransferFee` mint extension config effective in the current epoch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransferFeeConfig {
+    /// Amount of transfer collected as fees, expressed as basis points of the transfer amount.
+    pub transfer_fee_basis_points: u16,
+    /// Maximum fee assessed on transfers.
+    pub maximum_fee: u64,
+}
+
+impl TransferFeeConfig {
+    /// Calculates the fee withheld from the transferred `amount`, rounding up.
+    /// https://github.com/solana-labs/solana-program-library/blob/5418cf9b90d5c9ff5bff9f55fd17651f66c98902/token/program-2022/src/extension/transfer_fee/mod.rs
+    pub fn calculate_fee(&self, amount: u64) -> u64 {
+        if self.transfer_fee_basis_points == 0 || amount == 0 {
+            return 0;
+        }
+        let numerator = amount as u128 * self.transfer_fee_basis_points as u128;
+        let raw_fee = numerator.div_ceil(MAX_FEE_BASIS_POINTS as u128);
+        // `raw_fee` never exceeds `amount` as basis points are checked to be not greater than 100%.
+        u64::try_from(raw_fee)
+            .unwrap_or(u64::MAX)
+            .min(self.maximum_fee)
+    }
+}
+
+/// Token-2022 `TransferHook` mint extension.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransferHook {
+    /// The transfer hook program ID.
+    pub program_id: SolanaAddress,
+    /// Extra accounts required by the hook program, resolved from its validation account.
+    pub extra_accounts: Vec<AccountMeta>,
+}
+
+impl TransferHook {
+    const EXTRA_ACCOUNT_METAS_SEED: &'static [u8] = b"extra-account-metas";
+
+    /// Returns the address of the validation account storing the extra account metas for the `mint`.
+    pub fn extra_account_metas_address(&self, mint: SolanaAddress) -> AddressResult<SolanaAddress> {
+        SolanaAddress::find_program_address(
+            &[Self::EXTRA_ACCOUNT_METAS_SEED, mint.bytes().as_slice()],
+            self.program_id,
+        )
+        .ok_or(AddressError::InvalidInput)
+    }
+
+    /// Appends the accounts required to invoke the hook program to the `transfer` instruction:
+    /// the extra accounts, the hook program ID and the validation account.
+    /// https://github.com/solana-labs/solana-program-library/blob/5418cf9b90d5c9ff5bff9f55fd17651f66c98902/token/transfer-hook/interface/src/offchain.rs
+    pub fn append_accounts(
+        &self,
+        mut transfer: Instruction,
+        mint: SolanaAddress,
+    ) -> AddressResult<Instruction> {
+        let validation_account = self.extra_account_metas_address(mint)?;
+
+        transfer
+            .accounts
+            .extend(self.extra_accounts.iter().cloned());
+        transfer
+            .accounts
+            .push(AccountMeta::readonly(self.program_id, false));
+        transfer
+            .accounts
+            .push(AccountMeta::readonly(validation_account, false));
+        Ok(transfer)
+    }
+}
+
 pub struct TokenInstructionBuilder;
 
 impl TokenInstructionBuilder {
@@ -109,4 +321,74 @@ impl TokenInstructionBuilder {
         let data = TokenInstruction::TransferChecked { amount, decimals }.pack();
         Instruction::new(token_program_id, data, account_metas)
     }
+
+    /// transfer_checked_with_fee() solana-program-library/token/program-2022/src/extension/transfer_fee/instruction.rs
+    pub fn transfer_checked_with_fee(
+        sender_token_pubkey: SolanaAddress,
+        token_mint_pubkey: SolanaAddress,
+        recipient_token_pubkey: SolanaAddress,
+        signer: SolanaAddress,
+        amount: u64,
+        decimals: u8,
+        fee: u64,
+    ) -> Instruction {
+        let account_metas = vec![
+            AccountMeta::new(sender_token_pubkey, false),
+            AccountMeta::readonly(token_mint_pubkey, false),
+            AccountMeta::new(recipient_token_pubkey, false),
+            AccountMeta::new(signer, true),
+        ];
+
+        let data = TokenInstruction::TransferCheckedWithFee {
+            amount,
+            decimals,
+            fee,
+        }
+        .pack();
+        Instruction::new(*TOKEN_2022_PROGRAM_ID_ADDRESS, data, account_metas)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transfer_fee_calculate_fee() {
+        let config = TransferFeeConfig {
+            transfer_fee_basis_points: 50,
+            maximum_fee: 5000,
+        };
+        assert_eq!(config.calculate_fee(0), 0);
+        // Rounded up.
+        assert_eq!(config.calculate_fee(1), 1);
+        assert_eq!(config.calculate_fee(200_000), 1000);
+        assert_eq!(config.calculate_fee(200_001), 1001);
+        // Capped at the maximum fee.
+        assert_eq!(config.calculate_fee(u64::MAX), 5000);
+
+        let zero_fee = TransferFeeConfig {
+            transfer_fee_basis_points: 0,
+            maximum_fee: 5000,
+        };
+        assert_eq!(zero_fee.calculate_fee(1_000_000), 0);
+    }
+
+    #[test]
+    fn test_pack_unpack_transfer_checked_with_fee() {
+        let instruction = TokenInstruction::TransferCheckedWithFee {
+            amount: 1_000_000_000,
+            decimals: 9,
+            fee: 5000,
+        };
+        let packed = instruction.pack();
+        assert_eq!(
+            packed,
+            [26, 1, 0x00, 0xca, 0x9a, 0x3b, 0, 0, 0, 0, 9, 0x88, 0x13, 0, 0, 0, 0, 0, 0]
+        );
+        assert_eq!(TokenInstruction::unpack(&packed), Some(instruction));
+
+        // Missing the fee.
+        assert_eq!(TokenInstruction::unpack(&packed[..11]), None);
+    }
 }
@@ -0,0 +1,34 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::modules::instruction_decoder::{DecodedInstructionType, InstructionAccounts};
+use tw_proto::Solana::Proto::mod_DecodedInstruction as Decoded;
+
+/// https://github.com/solana-labs/solana-program-library/blob/master/associated-token-account/program/src/instruction.rs
+const CREATE: u8 = 0;
+const CREATE_IDEMPOTENT: u8 = 1;
+
+pub struct AssociatedTokenInstructionDecoder;
+
+impl AssociatedTokenInstructionDecoder {
+    pub fn decode(accounts: &InstructionAccounts, data: &[u8]) -> Option<DecodedInstructionType> {
+        // Empty instruction data is treated as `Create` for backward compatibility.
+        let idempotent = match data {
+            [] | [CREATE] => false,
+            [CREATE_IDEMPOTENT] => true,
+            _ => return None,
+        };
+
+        Some(DecodedInstructionType::create_associated_token_account(
+            Decoded::CreateAssociatedTokenAccount {
+                funding_account: accounts.get(0)?,
+                associated_token_account: accounts.get(1)?,
+                wallet: accounts.get(2)?,
+                mint: accounts.get(3)?,
+                token_program_id: accounts.get(5)?,
+                idempotent,
+            },
+        ))
+    }
+}
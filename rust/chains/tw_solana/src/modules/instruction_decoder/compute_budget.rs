@@ -0,0 +1,28 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::modules::instruction_builder::compute_budget_instruction::ComputeBudgetInstruction;
+use crate::modules::instruction_decoder::DecodedInstructionType;
+use borsh::BorshDeserialize;
+use tw_proto::Solana::Proto::mod_DecodedInstruction as Decoded;
+
+pub struct ComputeBudgetInstructionDecoder;
+
+impl ComputeBudgetInstructionDecoder {
+    pub fn decode(data: &[u8]) -> Option<DecodedInstructionType> {
+        match ComputeBudgetInstruction::try_from_slice(data).ok()? {
+            ComputeBudgetInstruction::SetComputeUnitLimit(units) => {
+                Some(DecodedInstructionType::set_compute_unit_limit(
+                    Decoded::SetComputeUnitLimit { units },
+                ))
+            },
+            ComputeBudgetInstruction::SetComputeUnitPrice(micro_lamports) => {
+                Some(DecodedInstructionType::set_compute_unit_price(
+                    Decoded::SetComputeUnitPrice { micro_lamports },
+                ))
+            },
+            _ => None,
+        }
+    }
+}
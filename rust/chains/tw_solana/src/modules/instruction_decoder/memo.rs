@@ -0,0 +1,20 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::modules::instruction_decoder::{DecodedInstructionType, InstructionAccounts};
+use tw_proto::Solana::Proto::mod_DecodedInstruction as Decoded;
+
+pub struct MemoInstructionDecoder;
+
+impl MemoInstructionDecoder {
+    /// The memo program accepts UTF-8 data only.
+    /// All the accounts passed to the instruction are required to sign the transaction.
+    pub fn decode(accounts: &InstructionAccounts, data: &[u8]) -> Option<DecodedInstructionType> {
+        let memo = String::from_utf8(data.to_vec()).ok()?;
+        Some(DecodedInstructionType::memo(Decoded::Memo {
+            memo: memo.into(),
+            signers: accounts.get_from(0),
+        }))
+    }
+}
@@ -0,0 +1,361 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::address::SolanaAddress;
+use crate::defined_addresses::*;
+use crate::transaction::versioned::VersionedMessage;
+use crate::transaction::CompiledInstruction;
+use std::borrow::Cow;
+use tw_proto::Solana::Proto;
+
+pub mod associated_token;
+pub mod compute_budget;
+pub mod memo;
+pub mod stake;
+pub mod system;
+pub mod token;
+
+use associated_token::AssociatedTokenInstructionDecoder;
+use compute_budget::ComputeBudgetInstructionDecoder;
+use memo::MemoInstructionDecoder;
+use stake::StakeInstructionDecoder;
+use system::SystemInstructionDecoder;
+use token::TokenInstructionDecoder;
+
+type DecodedInstructionType = Proto::mod_DecodedInstruction::OneOfinstruction<'static>;
+
+/// Accounts passed to an instruction.
+pub struct InstructionAccounts {
+    /// `None` if the account is loaded from an address lookup table or its index is invalid.
+    accounts: Vec<Option<SolanaAddress>>,
+}
+
+impl InstructionAccounts {
+    /// Returns the base58 encoded account at the given position,
+    /// or an empty string if the account is loaded from an address lookup table or its index is invalid.
+    /// Returns `None` if the instruction has fewer accounts.
+    pub fn get(&self, position: usize) -> Option<Cow<'static, str>> {
+        let account = self.accounts.get(position)?;
+        Some(Self::encode(account))
+    }
+
+    /// Returns the base58 encoded accounts starting from the given position.
+    pub fn get_from(&self, position: usize) -> Vec<Cow<'static, str>> {
+        self.accounts
+            .iter()
+            .skip(position)
+            .map(Self::encode)
+            .collect()
+    }
+
+    fn encode(account: &Option<SolanaAddress>) -> Cow<'static, str> {
+        account
+            .map(|addr| Cow::from(addr.to_string()))
+            .unwrap_or_default()
+    }
+}
+
+pub struct InstructionDecoder;
+
+impl InstructionDecoder {
+    /// Decodes the message instructions of known programs into a human-readable form.
+    pub fn decode_message(message: &VersionedMessage) -> Vec<Proto::DecodedInstruction<'static>> {
+        let static_keys = message.account_keys();
+        let loaded_keys_count = match message {
+            VersionedMessage::Legacy(_) => 0,
+            VersionedMessage::V0(v0) => v0
+                .address_table_lookups
+                .iter()
+                .map(|lookup| lookup.writable_indexes.len() + lookup.readonly_indexes.len())
+                .sum(),
+        };
+
+        message
+            .instructions()
+            .iter()
+            .map(|ix| Self::decode_instruction(ix, static_keys, loaded_keys_count))
+            .collect()
+    }
+
+    fn decode_instruction(
+        ix: &CompiledInstruction,
+        static_keys: &[SolanaAddress],
+        loaded_keys_count: usize,
+    ) -> Proto::DecodedInstruction<'static> {
+        let program_id = static_keys.get(ix.program_id_index as usize).copied();
+
+        let total_keys_count = static_keys.len() + loaded_keys_count;
+        // Account positions define their roles, so the instruction can't be decoded
+        // if any of the account indexes is invalid.
+        let has_invalid_accounts = ix
+            .accounts
+            .iter()
+            .any(|idx| *idx as usize >= total_keys_count);
+        let accounts = ix
+            .accounts
+            .iter()
+            .map(|idx| static_keys.get(*idx as usize).copied())
+            .collect();
+        let accounts = InstructionAccounts { accounts };
+
+        let decoded = program_id
+            .filter(|_| !has_invalid_accounts)
+            .and_then(|program_id| Self::decode_known_program(program_id, &accounts, &ix.data))
+            .unwrap_or_else(|| {
+                DecodedInstructionType::unknown(Proto::mod_DecodedInstruction::Unknown {
+                    accounts: accounts.get_from(0),
+                    data: Cow::from(ix.data.clone()),
+                })
+            });
+
+        Proto::DecodedInstruction {
+            program_id: program_id
+                .map(|program_id| Cow::from(program_id.to_string()))
+                .unwrap_or_default(),
+            instruction: decoded,
+        }
+    }
+
+    /// Returns `None` if the program is unknown, or the instruction is not supported or invalid.
+    fn decode_known_program(
+        program_id: SolanaAddress,
+        accounts: &InstructionAccounts,
+        data: &[u8],
+    ) -> Option<DecodedInstructionType> {
+        if program_id == *SYSTEM_PROGRAM_ID_ADDRESS {
+            SystemInstructionDecoder::decode(accounts, data)
+        } else if program_id == *TOKEN_PROGRAM_ID_ADDRESS
+            || program_id == *TOKEN_2022_PROGRAM_ID_ADDRESS
+        {
+            TokenInstructionDecoder::decode(accounts, data)
+        } else if program_id == *ASSOCIATED_TOKEN_PROGRAM_ID_ADDRESS {
+            AssociatedTokenInstructionDecoder::decode(accounts, data)
+        } else if program_id == *STAKE_PROGRAM_ID_ADDRESS {
+            StakeInstructionDecoder::decode(accounts, data)
+        } else if program_id == *COMPUTE_BUDGET_ADDRESS {
+            ComputeBudgetInstructionDecoder::decode(data)
+        } else if program_id == *MEMO_PROGRAM_ID_ADDRESS {
+            MemoInstructionDecoder::decode(accounts, data)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instruction::{AccountMeta, Instruction};
+    use crate::modules::compiled_instructions::compile_instructions;
+    use crate::modules::compiled_keys::CompiledKeys;
+    use crate::modules::instruction_builder::stake_instruction::{
+        Authorized, Lockup, StakeInstructionBuilder,
+    };
+    use crate::modules::instruction_builder::system_instruction::SystemInstructionBuilder;
+    use crate::modules::instruction_builder::token_instruction::TokenInstruction;
+    use crate::transaction::legacy;
+    use tw_proto::Solana::Proto::mod_DecodedInstruction as Decoded;
+
+    const PAYER: &str = "B1iGmDJdvmxyUiYM8UEo2Uw2D58EmUrw4KyLYMmrhf8V";
+    const RECIPIENT: &str = "EN2sCsJ1WDV8UFqsiTXHcUPUxQ4juE71eCknHYYMifkd";
+    const NONCE: &str = "6vNrYPFEiS9mQR3ykWcxgukXDjNAY4Tc2LLfBxqQVKk6";
+    const STAKE: &str = "6XMLCn47d5kPi3g4YcjqFvDuxWnpVADpN2tXpeRc4XUB";
+    const VOTE: &str = "4jpwTqt1qZoR7u6u639z2AngYFGN3nakvKhowcnRZDEC";
+
+    fn compile_message(instructions: &[Instruction]) -> legacy::Message {
+        let compiled_keys =
+            CompiledKeys::with_fee_payer(SolanaAddress::from(PAYER)).compile(instructions);
+        let (header, account_keys) = compiled_keys.try_into_message_components().unwrap();
+        let instructions = compile_instructions(instructions, &account_keys).unwrap();
+        legacy::Message {
+            header,
+            account_keys,
+            recent_blockhash: Default::default(),
+            instructions,
+        }
+    }
+
+    fn decode(instructions: &[Instruction]) -> Vec<Proto::DecodedInstruction<'static>> {
+        let message = VersionedMessage::Legacy(compile_message(instructions));
+        InstructionDecoder::decode_message(&message)
+    }
+
+    #[test]
+    fn test_decode_invalid_account_index() {
+        let payer = SolanaAddress::from(PAYER);
+        let mut message = compile_message(&[SystemInstructionBuilder::transfer(
+            payer,
+            SolanaAddress::from(RECIPIENT),
+            42,
+        )]);
+        // The valid accounts must not be shifted to the `from` and `to` positions.
+        message.instructions[0].accounts.insert(0, u8::MAX);
+        let data = message.instructions[0].data.clone();
+
+        let decoded = InstructionDecoder::decode_message(&VersionedMessage::Legacy(message));
+        let expected = DecodedInstructionType::unknown(Decoded::Unknown {
+            accounts: vec!["".into(), PAYER.into(), RECIPIENT.into()],
+            data: data.into(),
+        });
+        assert_eq!(decoded.len(), 1);
+        assert_eq!(decoded[0].instruction, expected);
+    }
+
+    #[test]
+    fn test_decode_system_instructions() {
+        let payer = SolanaAddress::from(PAYER);
+        let nonce = SolanaAddress::from(NONCE);
+
+        let decoded = decode(&[
+            SystemInstructionBuilder::advance_nonce_account(nonce, payer),
+            SystemInstructionBuilder::transfer(payer, SolanaAddress::from(RECIPIENT), 42),
+            SystemInstructionBuilder::withdraw_nonce_account(nonce, payer, payer, 100),
+            SystemInstructionBuilder::memo("Hello"),
+        ]);
+
+        let expected = [
+            DecodedInstructionType::system_advance_nonce_account(
+                Decoded::SystemAdvanceNonceAccount {
+                    nonce_account: NONCE.into(),
+                    nonce_authority: PAYER.into(),
+                },
+            ),
+            DecodedInstructionType::system_transfer(Decoded::SystemTransfer {
+                from: PAYER.into(),
+                to: RECIPIENT.into(),
+                lamports: 42,
+            }),
+            DecodedInstructionType::system_withdraw_nonce_account(
+                Decoded::SystemWithdrawNonceAccount {
+                    nonce_account: NONCE.into(),
+                    recipient: PAYER.into(),
+                    nonce_authority: PAYER.into(),
+                    lamports: 100,
+                },
+            ),
+            DecodedInstructionType::memo(Decoded::Memo {
+                memo: "Hello".into(),
+                signers: Vec::default(),
+            }),
+        ];
+        let actual: Vec<_> = decoded.into_iter().map(|ix| ix.instruction).collect();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_decode_stake_instructions() {
+        let payer = SolanaAddress::from(PAYER);
+        let stake = SolanaAddress::from(STAKE);
+        let authorized = Authorized {
+            staker: payer,
+            withdrawer: payer,
+        };
+
+        let decoded = decode(&[
+            StakeInstructionBuilder::stake_initialize(stake, authorized, Lockup::default()),
+            StakeInstructionBuilder::delegate(stake, SolanaAddress::from(VOTE), payer),
+            StakeInstructionBuilder::deactivate(stake, payer),
+            StakeInstructionBuilder::withdraw(stake, payer, payer, 1000, None),
+        ]);
+
+        let expected = [
+            DecodedInstructionType::stake_initialize(Decoded::StakeInitialize {
+                stake_account: STAKE.into(),
+                staker: PAYER.into(),
+                withdrawer: PAYER.into(),
+                lockup_unix_timestamp: 0,
+                lockup_epoch: 0,
+                lockup_custodian: "11111111111111111111111111111111".into(),
+            }),
+            DecodedInstructionType::stake_delegate(Decoded::StakeDelegate {
+                stake_account: STAKE.into(),
+                vote_account: VOTE.into(),
+                stake_authority: PAYER.into(),
+            }),
+            DecodedInstructionType::stake_deactivate(Decoded::StakeDeactivate {
+                stake_account: STAKE.into(),
+                stake_authority: PAYER.into(),
+            }),
+            DecodedInstructionType::stake_withdraw(Decoded::StakeWithdraw {
+                stake_account: STAKE.into(),
+                recipient: PAYER.into(),
+                withdraw_authority: PAYER.into(),
+                lamports: 1000,
+            }),
+        ];
+        let actual: Vec<_> = decoded.into_iter().map(|ix| ix.instruction).collect();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_decode_token_instructions() {
+        let payer = SolanaAddress::from(PAYER);
+        let source = SolanaAddress::from(NONCE);
+        let destination = SolanaAddress::from(RECIPIENT);
+        let token_ix = |ix: TokenInstruction, accounts: Vec<AccountMeta>| {
+            Instruction::new(*TOKEN_2022_PROGRAM_ID_ADDRESS, ix.pack(), accounts)
+        };
+
+        let decoded = decode(&[
+            token_ix(
+                TokenInstruction::Transfer { amount: 10 },
+                vec![
+                    AccountMeta::new(source, false),
+                    AccountMeta::new(destination, false),
+                    AccountMeta::readonly(payer, true),
+                ],
+            ),
+            token_ix(
+                TokenInstruction::Approve { amount: 20 },
+                vec![
+                    AccountMeta::new(source, false),
+                    AccountMeta::readonly(destination, false),
+                    AccountMeta::readonly(payer, true),
+                ],
+            ),
+            token_ix(
+                TokenInstruction::CloseAccount,
+                vec![
+                    AccountMeta::new(source, false),
+                    AccountMeta::new(payer, false),
+                    AccountMeta::readonly(payer, true),
+                ],
+            ),
+            // Not enough accounts.
+            token_ix(
+                TokenInstruction::CloseAccount,
+                vec![AccountMeta::new(source, false)],
+            ),
+        ]);
+
+        assert!(decoded
+            .iter()
+            .all(|ix| ix.program_id == "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb"));
+        let expected = [
+            DecodedInstructionType::token_transfer(Decoded::TokenTransfer {
+                source: NONCE.into(),
+                destination: RECIPIENT.into(),
+                authority: PAYER.into(),
+                amount: 10,
+            }),
+            DecodedInstructionType::token_approve(Decoded::TokenApprove {
+                source: NONCE.into(),
+                delegate: RECIPIENT.into(),
+                owner: PAYER.into(),
+                amount: 20,
+            }),
+            DecodedInstructionType::token_close_account(Decoded::TokenCloseAccount {
+                account: NONCE.into(),
+                destination: PAYER.into(),
+                owner: PAYER.into(),
+            }),
+            DecodedInstructionType::unknown(Decoded::Unknown {
+                accounts: vec![NONCE.into()],
+                data: vec![9].into(),
+            }),
+        ];
+        let actual: Vec<_> = decoded.into_iter().map(|ix| ix.instruction).collect();
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,59 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::modules::instruction_builder::stake_instruction::StakeInstruction;
+use crate::modules::instruction_decoder::{DecodedInstructionType, InstructionAccounts};
+use tw_proto::Solana::Proto::mod_DecodedInstruction as Decoded;
+
+pub struct StakeInstructionDecoder;
+
+impl StakeInstructionDecoder {
+    pub fn decode(accounts: &InstructionAccounts, data: &[u8]) -> Option<DecodedInstructionType> {
+        let instruction: StakeInstruction = bincode::deserialize(data).ok()?;
+
+        let decoded = match instruction {
+            StakeInstruction::Initialize(authorized, lockup) => {
+                DecodedInstructionType::stake_initialize(Decoded::StakeInitialize {
+                    stake_account: accounts.get(0)?,
+                    staker: authorized.staker.to_string().into(),
+                    withdrawer: authorized.withdrawer.to_string().into(),
+                    lockup_unix_timestamp: lockup.unix_timestamp,
+                    lockup_epoch: lockup.epoch,
+                    lockup_custodian: lockup.custodian.to_string().into(),
+                })
+            },
+            StakeInstruction::DelegateStake => {
+                DecodedInstructionType::stake_delegate(Decoded::StakeDelegate {
+                    stake_account: accounts.get(0)?,
+                    vote_account: accounts.get(1)?,
+                    stake_authority: accounts.get(5)?,
+                })
+            },
+            StakeInstruction::Deactivate => {
+                DecodedInstructionType::stake_deactivate(Decoded::StakeDeactivate {
+                    stake_account: accounts.get(0)?,
+                    stake_authority: accounts.get(2)?,
+                })
+            },
+            StakeInstruction::Withdraw(lamports) => {
+                DecodedInstructionType::stake_withdraw(Decoded::StakeWithdraw {
+                    stake_account: accounts.get(0)?,
+                    recipient: accounts.get(1)?,
+                    withdraw_authority: accounts.get(4)?,
+                    lamports,
+                })
+            },
+            StakeInstruction::Split(lamports) => {
+                DecodedInstructionType::stake_split(Decoded::StakeSplit {
+                    stake_account: accounts.get(0)?,
+                    new_stake_account: accounts.get(1)?,
+                    stake_authority: accounts.get(2)?,
+                    lamports,
+                })
+            },
+            _ => return None,
+        };
+        Some(decoded)
+    }
+}
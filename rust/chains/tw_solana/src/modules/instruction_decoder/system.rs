@@ -0,0 +1,90 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::modules::instruction_builder::system_instruction::SystemInstruction;
+use crate::modules::instruction_decoder::{DecodedInstructionType, InstructionAccounts};
+use tw_proto::Solana::Proto::mod_DecodedInstruction as Decoded;
+
+pub struct SystemInstructionDecoder;
+
+impl SystemInstructionDecoder {
+    pub fn decode(accounts: &InstructionAccounts, data: &[u8]) -> Option<DecodedInstructionType> {
+        let instruction: SystemInstruction = bincode::deserialize(data).ok()?;
+
+        let decoded = match instruction {
+            SystemInstruction::Transfer { lamports } => {
+                DecodedInstructionType::system_transfer(Decoded::SystemTransfer {
+                    from: accounts.get(0)?,
+                    to: accounts.get(1)?,
+                    lamports,
+                })
+            },
+            SystemInstruction::CreateAccount {
+                lamports,
+                space,
+                owner,
+            } => DecodedInstructionType::system_create_account(Decoded::SystemCreateAccount {
+                funding_account: accounts.get(0)?,
+                new_account: accounts.get(1)?,
+                lamports,
+                space,
+                owner: owner.to_string().into(),
+            }),
+            SystemInstruction::CreateAccountWithSeed {
+                base,
+                seed,
+                lamports,
+                space,
+                owner,
+            } => DecodedInstructionType::system_create_account_with_seed(
+                Decoded::SystemCreateAccountWithSeed {
+                    funding_account: accounts.get(0)?,
+                    new_account: accounts.get(1)?,
+                    base: base.to_string().into(),
+                    seed: seed.into(),
+                    lamports,
+                    space,
+                    owner: owner.to_string().into(),
+                },
+            ),
+            SystemInstruction::AdvanceNonceAccount => {
+                DecodedInstructionType::system_advance_nonce_account(
+                    Decoded::SystemAdvanceNonceAccount {
+                        nonce_account: accounts.get(0)?,
+                        nonce_authority: accounts.get(2)?,
+                    },
+                )
+            },
+            SystemInstruction::WithdrawNonceAccount(lamports) => {
+                DecodedInstructionType::system_withdraw_nonce_account(
+                    Decoded::SystemWithdrawNonceAccount {
+                        nonce_account: accounts.get(0)?,
+                        recipient: accounts.get(1)?,
+                        nonce_authority: accounts.get(4)?,
+                        lamports,
+                    },
+                )
+            },
+            SystemInstruction::InitializeNonceAccount(authority) => {
+                DecodedInstructionType::system_initialize_nonce_account(
+                    Decoded::SystemInitializeNonceAccount {
+                        nonce_account: accounts.get(0)?,
+                        nonce_authority: authority.to_string().into(),
+                    },
+                )
+            },
+            SystemInstruction::AuthorizeNonceAccount(new_authority) => {
+                DecodedInstructionType::system_authorize_nonce_account(
+                    Decoded::SystemAuthorizeNonceAccount {
+                        nonce_account: accounts.get(0)?,
+                        nonce_authority: accounts.get(1)?,
+                        new_nonce_authority: new_authority.to_string().into(),
+                    },
+                )
+            },
+            _ => return None,
+        };
+        Some(decoded)
+    }
+}
@@ -0,0 +1,66 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::modules::instruction_builder::token_instruction::TokenInstruction;
+use crate::modules::instruction_decoder::{DecodedInstructionType, InstructionAccounts};
+use tw_proto::Solana::Proto::mod_DecodedInstruction as Decoded;
+
+/// Decodes both SPL Token and Token-2022 instructions as they share the same layout.
+pub struct TokenInstructionDecoder;
+
+impl TokenInstructionDecoder {
+    pub fn decode(accounts: &InstructionAccounts, data: &[u8]) -> Option<DecodedInstructionType> {
+        let decoded = match TokenInstruction::unpack(data)? {
+            TokenInstruction::Transfer { amount } => {
+                DecodedInstructionType::token_transfer(Decoded::TokenTransfer {
+                    source: accounts.get(0)?,
+                    destination: accounts.get(1)?,
+                    authority: accounts.get(2)?,
+                    amount,
+                })
+            },
+            TokenInstruction::Approve { amount } => {
+                DecodedInstructionType::token_approve(Decoded::TokenApprove {
+                    source: accounts.get(0)?,
+                    delegate: accounts.get(1)?,
+                    owner: accounts.get(2)?,
+                    amount,
+                })
+            },
+            TokenInstruction::CloseAccount => {
+                DecodedInstructionType::token_close_account(Decoded::TokenCloseAccount {
+                    account: accounts.get(0)?,
+                    destination: accounts.get(1)?,
+                    owner: accounts.get(2)?,
+                })
+            },
+            TokenInstruction::TransferChecked { amount, decimals } => {
+                DecodedInstructionType::token_transfer_checked(Decoded::TokenTransferChecked {
+                    source: accounts.get(0)?,
+                    mint: accounts.get(1)?,
+                    destination: accounts.get(2)?,
+                    authority: accounts.get(3)?,
+                    amount,
+                    decimals: decimals as u32,
+                })
+            },
+            TokenInstruction::TransferCheckedWithFee {
+                amount,
+                decimals,
+                fee,
+            } => DecodedInstructionType::token_transfer_checked_with_fee(
+                Decoded::TokenTransferCheckedWithFee {
+                    source: accounts.get(0)?,
+                    mint: accounts.get(1)?,
+                    destination: accounts.get(2)?,
+                    authority: accounts.get(3)?,
+                    amount,
+                    decimals: decimals as u32,
+                    fee,
+                },
+            ),
+        };
+        Some(decoded)
+    }
+}
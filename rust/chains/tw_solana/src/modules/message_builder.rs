@@ -5,7 +5,7 @@
 use crate::address::SolanaAddress;
 use crate::blockhash::Blockhash;
 use crate::defined_addresses::{TOKEN_2022_PROGRAM_ID_ADDRESS, TOKEN_PROGRAM_ID_ADDRESS};
-use crate::instruction::Instruction;
+use crate::instruction::{AccountMeta, Instruction};
 use crate::modules::compiled_instructions::compile_instructions;
 use crate::modules::compiled_keys::CompiledKeys;
 use crate::modules::instruction_builder::compute_budget_instruction::{UnitLimit, UnitPrice};
@@ -13,9 +13,12 @@ use crate::modules::instruction_builder::stake_instruction::{
     DepositStakeArgs, StakeInstructionBuilder,
 };
 use crate::modules::instruction_builder::system_instruction::SystemInstructionBuilder;
-use crate::modules::instruction_builder::token_instruction::TokenInstructionBuilder;
+use crate::modules::instruction_builder::token_instruction::{
+    TokenInstructionBuilder, TransferFeeConfig, TransferHook, MAX_FEE_BASIS_POINTS,
+};
 use crate::modules::instruction_builder::InstructionBuilder;
 use crate::modules::PubkeySignatureMap;
+use crate::transaction::v0::{AddressLookupTableAccount, LOOKUP_TABLE_MAX_ADDRESSES};
 use crate::transaction::versioned::VersionedMessage;
 use crate::transaction::{legacy, v0, CompiledInstruction, MessageHeader, Signature};
 use std::borrow::Cow;
@@ -76,12 +79,27 @@ impl<'a> MessageBuilder<'a> {
 
         let instructions = self.build_instructions()?;
 
-        // Please note the fee payer can be different from the actual signer.
-        let (message_header, account_keys) = CompiledKeys::with_fee_payer(self.fee_payer()?)
-            .compile(&instructions)
-            .try_into_message_components()?;
+        let lookup_tables = self.address_lookup_tables()?;
+        if !self.input.v0_msg && !lookup_tables.is_empty() {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("Address lookup tables can be used in v0 messages only");
+        }
 
-        let compiled_instructions = compile_instructions(&instructions, &account_keys)?;
+        // Please note the fee payer can be different from the actual signer.
+        let mut compiled_keys =
+            CompiledKeys::with_fee_payer(self.fee_payer()?).compile(&instructions);
+        let (address_table_lookups, loaded_addresses) =
+            compiled_keys.try_extract_table_lookups(&lookup_tables)?;
+        let (message_header, account_keys) = compiled_keys.try_into_message_components()?;
+
+        // Instruction account indexes refer to the static account keys followed by the loaded addresses.
+        let all_keys: Vec<_> = account_keys
+            .iter()
+            .chain(loaded_addresses.writable.iter())
+            .chain(loaded_addresses.readonly.iter())
+            .copied()
+            .collect();
+        let compiled_instructions = compile_instructions(&instructions, &all_keys)?;
 
         if self.input.v0_msg {
             Ok(VersionedMessage::V0(v0::Message {
@@ -89,7 +107,7 @@ impl<'a> MessageBuilder<'a> {
                 account_keys,
                 recent_blockhash: self.recent_blockhash()?.to_bytes(),
                 instructions: compiled_instructions,
-                address_table_lookups: Vec::default(),
+                address_table_lookups,
             }))
         } else {
             Ok(VersionedMessage::Legacy(legacy::Message {
@@ -372,15 +390,17 @@ impl<'a> MessageBuilder<'a> {
             .context("Invalid token decimals. Expected lower than 256")?;
 
         let references = Self::parse_references(&token_transfer.references)?;
-        let transfer_instruction = TokenInstructionBuilder::transfer_checked(
+        let transfer_instruction = Self::transfer_checked_instruction(
             sender_token_address,
             token_mint_address,
             recipient_token_address,
             signer,
             token_transfer.amount,
             decimals,
-            match_program_id(token_transfer.token_program_id),
-        )
+            token_transfer.token_program_id,
+            token_transfer.token_2022_extensions.as_ref(),
+            token_transfer.memo.as_ref(),
+        )?
         .with_references(references);
 
         let mut builder = InstructionBuilder::default();
@@ -436,15 +456,17 @@ impl<'a> MessageBuilder<'a> {
             recipient_token_address,
             match_program_id(create_and_transfer.token_program_id),
         );
-        let transfer_instruction = TokenInstructionBuilder::transfer_checked(
+        let transfer_instruction = Self::transfer_checked_instruction(
             sender_token_address,
             token_mint_address,
             recipient_token_address,
             signer,
             create_and_transfer.amount,
             decimals,
-            match_program_id(create_and_transfer.token_program_id),
-        )
+            create_and_transfer.token_program_id,
+            create_and_transfer.token_2022_extensions.as_ref(),
+            create_and_transfer.memo.as_ref(),
+        )?
         .with_references(references);
 
         let mut builder = InstructionBuilder::default();
@@ -605,6 +627,154 @@ impl<'a> MessageBuilder<'a> {
             .map(|proto| proto.limit)
     }
 
+    fn address_lookup_tables(&self) -> SigningResult<Vec<AddressLookupTableAccount>> {
+        self.input
+            .address_lookup_tables
+            .iter()
+            .map(Self::address_lookup_table_from_proto)
+            .collect()
+    }
+
+    fn address_lookup_table_from_proto(
+        table: &Proto::AddressLookupTable<'_>,
+    ) -> SigningResult<AddressLookupTableAccount> {
+        let key = SolanaAddress::from_str(&table.account_key)
+            .into_tw()
+            .context("Invalid address lookup table account")?;
+
+        if table.addresses.len() > LOOKUP_TABLE_MAX_ADDRESSES {
+            return SigningError::err(SigningErrorType::Error_invalid_params).context(format!(
+                "Address lookup table cannot hold more than {LOOKUP_TABLE_MAX_ADDRESSES} addresses"
+            ));
+        }
+
+        let addresses = table
+            .addresses
+            .iter()
+            .map(|addr| SolanaAddress::from_str(addr).map_err(SigningError::from))
+            .collect::<SigningResult<Vec<_>>>()
+            .context("Invalid address lookup table address(es)")?;
+
+        Ok(AddressLookupTableAccount { key, addresses })
+    }
+
+    /// Returns a `transfer_checked` instruction,
+    /// or a Token-2022 transfer instruction respecting the mint and recipient account extensions.
+    #[allow(clippy::too_many_arguments)]
+    fn transfer_checked_instruction(
+        sender_token_address: SolanaAddress,
+        token_mint_address: SolanaAddress,
+        recipient_token_address: SolanaAddress,
+        signer: SolanaAddress,
+        amount: u64,
+        decimals: u8,
+        token_program_id: Proto::TokenProgramId,
+        extensions: Option<&Proto::Token2022Extensions>,
+        memo: &str,
+    ) -> SigningResult<Instruction> {
+        let extensions = match extensions {
+            Some(extensions) => extensions,
+            None => {
+                return Ok(TokenInstructionBuilder::transfer_checked(
+                    sender_token_address,
+                    token_mint_address,
+                    recipient_token_address,
+                    signer,
+                    amount,
+                    decimals,
+                    match_program_id(token_program_id),
+                ))
+            },
+        };
+
+        if token_program_id != Proto::TokenProgramId::Token2022Program {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("Token-2022 extensions can be used with the Token-2022 program only");
+        }
+        // The memo instruction is always placed right before the transfer.
+        if extensions.recipient_requires_memo && memo.is_empty() {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("The recipient token account requires a memo on incoming transfers");
+        }
+
+        let transfer_instruction = match extensions.transfer_fee {
+            Some(ref transfer_fee) => {
+                let fee = Self::transfer_fee_config_from_proto(transfer_fee)?.calculate_fee(amount);
+                TokenInstructionBuilder::transfer_checked_with_fee(
+                    sender_token_address,
+                    token_mint_address,
+                    recipient_token_address,
+                    signer,
+                    amount,
+                    decimals,
+                    fee,
+                )
+            },
+            None => TokenInstructionBuilder::transfer_checked(
+                sender_token_address,
+                token_mint_address,
+                recipient_token_address,
+                signer,
+                amount,
+                decimals,
+                *TOKEN_2022_PROGRAM_ID_ADDRESS,
+            ),
+        };
+
+        match extensions.transfer_hook {
+            Some(ref transfer_hook) => Self::transfer_hook_from_proto(transfer_hook)?
+                .append_accounts(transfer_instruction, token_mint_address)
+                .into_tw()
+                .context("Error finding the transfer hook validation account"),
+            None => Ok(transfer_instruction),
+        }
+    }
+
+    fn transfer_fee_config_from_proto(
+        transfer_fee: &Proto::TransferFeeConfig,
+    ) -> SigningResult<TransferFeeConfig> {
+        let transfer_fee_basis_points = u16::try_from(transfer_fee.transfer_fee_basis_points)
+            .ok()
+            .filter(|basis_points| *basis_points <= MAX_FEE_BASIS_POINTS)
+            .or_tw_err(SigningErrorType::Error_invalid_params)
+            .with_context(|| {
+                format!("Transfer fee basis points must not be greater than {MAX_FEE_BASIS_POINTS}")
+            })?;
+
+        Ok(TransferFeeConfig {
+            transfer_fee_basis_points,
+            maximum_fee: transfer_fee.maximum_fee,
+        })
+    }
+
+    fn transfer_hook_from_proto(
+        transfer_hook: &Proto::TransferHook,
+    ) -> SigningResult<TransferHook> {
+        let program_id = SolanaAddress::from_str(transfer_hook.program_id.as_ref())
+            .into_tw()
+            .context("Invalid transfer hook program ID")?;
+
+        let extra_accounts = transfer_hook
+            .extra_accounts
+            .iter()
+            .map(|account| {
+                let pubkey = SolanaAddress::from_str(account.address.as_ref())
+                    .into_tw()
+                    .context("Invalid transfer hook extra account")?;
+                Ok(AccountMeta {
+                    pubkey,
+                    is_signer: account.is_signer,
+                    is_writable: account.is_writable,
+                })
+            })
+            .collect::<SigningResult<Vec<_>>>()?;
+
+        Ok(TransferHook {
+            program_id,
+            extra_accounts,
+        })
+    }
+
     fn parse_references(refs: &[Cow<'_, str>]) -> SigningResult<Vec<SolanaAddress>> {
         refs.iter()
             .map(|addr| SolanaAddress::from_str(addr).map_err(SigningError::from))
@@ -0,0 +1,204 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::address::SolanaAddress;
+use crate::modules::message_signer::offchain::OffchainMessage;
+use crate::SOLANA_ALPHABET;
+use std::borrow::Cow;
+use std::str::FromStr;
+use tw_coin_entry::coin_context::CoinContext;
+use tw_coin_entry::error::prelude::*;
+use tw_coin_entry::modules::message_signer::MessageSigner;
+use tw_coin_entry::signing_output_error;
+use tw_encoding::base58;
+use tw_hash::H256;
+use tw_keypair::ed25519;
+use tw_keypair::traits::{KeyPairTrait, SigningKeyTrait, VerifyingKeyTrait};
+use tw_memory::Data;
+use tw_proto::Solana::Proto;
+use tw_proto::TxCompiler::Proto as CompilerProto;
+
+pub mod offchain;
+
+#[derive(Default)]
+pub struct SolanaMessageSigner;
+
+impl MessageSigner for SolanaMessageSigner {
+    type MessageSigningInput<'a> = Proto::MessageSigningInput<'a>;
+    type MessagePreSigningOutput = CompilerProto::PreSigningOutput<'static>;
+    type MessageSigningOutput = Proto::MessageSigningOutput<'static>;
+    type MessageVerifyingInput<'a> = Proto::MessageVerifyingInput<'a>;
+
+    fn message_preimage_hashes(
+        &self,
+        _coin: &dyn CoinContext,
+        input: Self::MessageSigningInput<'_>,
+    ) -> Self::MessagePreSigningOutput {
+        Self::message_preimage_hashes_impl(input)
+            .unwrap_or_else(|e| signing_output_error!(CompilerProto::PreSigningOutput, e))
+    }
+
+    fn sign_message(
+        &self,
+        _coin: &dyn CoinContext,
+        input: Self::MessageSigningInput<'_>,
+    ) -> Self::MessageSigningOutput {
+        Self::sign_message_impl(input)
+            .unwrap_or_else(|e| signing_output_error!(Proto::MessageSigningOutput, e))
+    }
+
+    fn verify_message(
+        &self,
+        _coin: &dyn CoinContext,
+        input: Self::MessageVerifyingInput<'_>,
+    ) -> bool {
+        Self::verify_message_impl(input).unwrap_or_default()
+    }
+}
+
+impl SolanaMessageSigner {
+    fn message_preimage_hashes_impl(
+        input: Proto::MessageSigningInput<'_>,
+    ) -> SigningResult<CompilerProto::PreSigningOutput<'static>> {
+        // The private key is optional here, so the preimage can be signed externally.
+        // The off-chain message signers must be set in the preamble in this case.
+        let signer = if input.private_key.is_empty() {
+            None
+        } else {
+            let key_pair = Self::signing_key_pair(&input)?;
+            Some(SolanaAddress::with_public_key_bytes(
+                key_pair.public().to_bytes(),
+            ))
+        };
+        let preimage =
+            Self::message_preimage(&input.message, input.format, &input.preamble, signer)?;
+
+        // There is no hashing for ed25519, the preimage is signed as is.
+        Ok(CompilerProto::PreSigningOutput {
+            data: Cow::Owned(preimage.clone()),
+            data_hash: Cow::Owned(preimage),
+            ..CompilerProto::PreSigningOutput::default()
+        })
+    }
+
+    fn sign_message_impl(
+        input: Proto::MessageSigningInput<'_>,
+    ) -> SigningResult<Proto::MessageSigningOutput<'static>> {
+        let key_pair = Self::signing_key_pair(&input)?;
+        let signer = SolanaAddress::with_public_key_bytes(key_pair.public().to_bytes());
+        let preimage =
+            Self::message_preimage(&input.message, input.format, &input.preamble, Some(signer))?;
+
+        let signature = key_pair.sign(preimage)?;
+        Ok(Proto::MessageSigningOutput {
+            signature: Cow::Owned(base58::encode(
+                signature.to_bytes().as_slice(),
+                SOLANA_ALPHABET,
+            )),
+            ..Proto::MessageSigningOutput::default()
+        })
+    }
+
+    fn verify_message_impl(input: Proto::MessageVerifyingInput<'_>) -> SigningResult<bool> {
+        let signer = SolanaAddress::from_str(&input.address)
+            .into_tw()
+            .context("Invalid signer address")?;
+        let public_key = ed25519::sha512::PublicKey::try_from(signer.bytes().as_slice())?;
+
+        let signature = base58::decode(&input.signature, SOLANA_ALPHABET)
+            .tw_err(|_| SigningErrorType::Error_input_parse)
+            .context("Message signature must be base58 encoded")?;
+        let signature = ed25519::Signature::try_from(signature.as_slice())?;
+
+        match input.format {
+            Proto::MessageSigningFormat::MessageSigningFormat_Raw => {
+                Ok(public_key.verify(signature, input.message.as_bytes().to_vec()))
+            },
+            Proto::MessageSigningFormat::MessageSigningFormat_OffChain => {
+                let offchain_message =
+                    Self::offchain_message(&input.message, &input.preamble, Some(signer))?;
+                // The message could be signed in any format it is allowed in,
+                // not necessarily in the most restrictive one.
+                let is_valid = offchain_message.allowed_formats().any(|format| {
+                    let preimage = offchain_message.serialize_with_format(format);
+                    public_key.verify(signature.clone(), preimage)
+                });
+                Ok(is_valid)
+            },
+        }
+    }
+
+    /// Returns the bytes to be signed by the `signer` account.
+    fn message_preimage(
+        message: &str,
+        format: Proto::MessageSigningFormat,
+        preamble: &Option<Proto::OffChainMessagePreamble<'_>>,
+        signer: Option<SolanaAddress>,
+    ) -> SigningResult<Data> {
+        match format {
+            Proto::MessageSigningFormat::MessageSigningFormat_Raw => {
+                Ok(message.as_bytes().to_vec())
+            },
+            Proto::MessageSigningFormat::MessageSigningFormat_OffChain => {
+                let offchain_message = Self::offchain_message(message, preamble, signer)?;
+                Ok(offchain_message.serialize())
+            },
+        }
+    }
+
+    /// Builds the off-chain message signed by the `signer` account.
+    /// If `signer` is not known, the message signers must be set in the `preamble`.
+    fn offchain_message(
+        message: &str,
+        preamble: &Option<Proto::OffChainMessagePreamble<'_>>,
+        signer: Option<SolanaAddress>,
+    ) -> SigningResult<OffchainMessage> {
+        let default_preamble = Proto::OffChainMessagePreamble::default();
+        let preamble = preamble.as_ref().unwrap_or(&default_preamble);
+
+        let application_domain = if preamble.application_domain.is_empty() {
+            H256::default()
+        } else {
+            let domain = base58::decode(&preamble.application_domain, SOLANA_ALPHABET)
+                .tw_err(|_| SigningErrorType::Error_input_parse)
+                .context("Application domain must be base58 encoded")?;
+            H256::try_from(domain.as_slice())
+                .tw_err(|_| SigningErrorType::Error_invalid_params)
+                .context("Application domain must be 32 bytes long")?
+        };
+
+        let signers = if preamble.signers.is_empty() {
+            let signer = signer
+                .or_tw_err(SigningErrorType::Error_invalid_params)
+                .context("Either the private key or the off-chain message signers must be set")?;
+            vec![signer]
+        } else {
+            preamble
+                .signers
+                .iter()
+                .map(|addr| SolanaAddress::from_str(addr))
+                .collect::<AddressResult<Vec<_>>>()
+                .into_tw()
+                .context("Invalid off-chain message signer address")?
+        };
+
+        if let Some(signer) = signer {
+            if !signers.contains(&signer) {
+                return SigningError::err(SigningErrorType::Error_invalid_params).with_context(
+                    || format!("'{signer}' is not in the off-chain message signers"),
+                );
+            }
+        }
+
+        OffchainMessage::new(application_domain, signers, message.to_string())
+    }
+
+    fn signing_key_pair(
+        input: &Proto::MessageSigningInput<'_>,
+    ) -> SigningResult<ed25519::sha512::KeyPair> {
+        ed25519::sha512::KeyPair::try_from(input.private_key.as_ref())
+            .into_tw()
+            .context("Invalid ed25519 private key")
+    }
+}
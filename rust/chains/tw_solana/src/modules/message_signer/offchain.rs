@@ -0,0 +1,131 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+//! Solana off-chain message format.
+//! https://github.com/anza-xyz/agave/blob/master/docs/src/proposals/off-chain-message-signing.md
+
+use crate::address::SolanaAddress;
+use tw_coin_entry::error::prelude::*;
+use tw_hash::H256;
+use tw_memory::Data;
+
+/// Prevents the message from being interpreted as a transaction.
+pub const SIGNING_DOMAIN: &[u8] = b"\xffsolana offchain";
+/// The only header version defined at the moment.
+pub const HEADER_VERSION: u8 = 0;
+/// Maximum size of the whole serialized message that can be signed by a hardware wallet.
+pub const MAX_LEDGER_MESSAGE_SIZE: usize = 1232;
+
+/// Describes the characters allowed in the message and its maximum length.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum OffchainMessageFormat {
+    /// Printable ASCII characters (0x20..=0x7e) only, fits [`MAX_LEDGER_MESSAGE_SIZE`].
+    RestrictedAscii = 0,
+    /// Any UTF-8 characters, fits [`MAX_LEDGER_MESSAGE_SIZE`].
+    LimitedUtf8 = 1,
+    /// Any UTF-8 characters, up to `u16::MAX` bytes long.
+    ExtendedUtf8 = 2,
+}
+
+pub struct OffchainMessage {
+    application_domain: H256,
+    signers: Vec<SolanaAddress>,
+    message: String,
+}
+
+impl OffchainMessage {
+    pub fn new(
+        application_domain: H256,
+        signers: Vec<SolanaAddress>,
+        message: String,
+    ) -> SigningResult<OffchainMessage> {
+        if signers.is_empty() {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("Off-chain message must have at least one signer");
+        }
+        if signers.len() > u8::MAX as usize {
+            return SigningError::err(SigningErrorType::Error_invalid_params).with_context(|| {
+                format!("Off-chain message can have at most {} signers", u8::MAX)
+            });
+        }
+        if message.is_empty() {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("Off-chain message must not be empty");
+        }
+        if message.len() > u16::MAX as usize {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .with_context(|| format!("Off-chain message is longer than {} bytes", u16::MAX));
+        }
+
+        Ok(OffchainMessage {
+            application_domain,
+            signers,
+            message,
+        })
+    }
+
+    pub fn signers(&self) -> &[SolanaAddress] {
+        &self.signers
+    }
+
+    /// Returns the most restrictive format the message fits into.
+    pub fn format(&self) -> OffchainMessageFormat {
+        let fits_ledger = self.preamble_len() + self.message.len() <= MAX_LEDGER_MESSAGE_SIZE;
+        let is_printable_ascii = self
+            .message
+            .bytes()
+            .all(|byte| (0x20..=0x7e).contains(&byte));
+
+        match (fits_ledger, is_printable_ascii) {
+            (true, true) => OffchainMessageFormat::RestrictedAscii,
+            (true, false) => OffchainMessageFormat::LimitedUtf8,
+            (false, _) => OffchainMessageFormat::ExtendedUtf8,
+        }
+    }
+
+    /// Returns every format the message can be serialized in, starting from the most restrictive one.
+    pub fn allowed_formats(&self) -> impl Iterator<Item = OffchainMessageFormat> {
+        let min_format = self.format();
+        [
+            OffchainMessageFormat::RestrictedAscii,
+            OffchainMessageFormat::LimitedUtf8,
+            OffchainMessageFormat::ExtendedUtf8,
+        ]
+        .into_iter()
+        .filter(move |format| *format as u8 >= min_format as u8)
+    }
+
+    /// Serializes the message preamble followed by the message body
+    /// in the most restrictive format the message fits into.
+    pub fn serialize(&self) -> Data {
+        self.serialize_with_format(self.format())
+    }
+
+    /// Serializes the message preamble with the given `format` followed by the message body.
+    pub fn serialize_with_format(&self, format: OffchainMessageFormat) -> Data {
+        let mut data = Vec::with_capacity(self.preamble_len() + self.message.len());
+
+        data.extend_from_slice(SIGNING_DOMAIN);
+        data.push(HEADER_VERSION);
+        data.extend_from_slice(self.application_domain.as_slice());
+        data.push(format as u8);
+        // The number of signers is checked at [`OffchainMessage::new`].
+        data.push(self.signers.len() as u8);
+        for signer in self.signers.iter() {
+            data.extend_from_slice(signer.bytes().as_slice());
+        }
+        // The message length is checked at [`OffchainMessage::new`].
+        data.extend_from_slice(&(self.message.len() as u16).to_le_bytes());
+        data.extend_from_slice(self.message.as_bytes());
+
+        data
+    }
+
+    fn preamble_len(&self) -> usize {
+        // signing domain + header version + application domain + format + signers count
+        // + signers + message length.
+        SIGNING_DOMAIN.len() + 1 + H256::LEN + 1 + 1 + self.signers.len() * H256::LEN + 2
+    }
+}
@@ -9,7 +9,9 @@ use tw_keypair::ed25519;
 pub mod compiled_instructions;
 pub mod compiled_keys;
 pub mod instruction_builder;
+pub mod instruction_decoder;
 pub mod message_builder;
+pub mod message_signer;
 pub mod proto_builder;
 pub mod transaction_decoder;
 pub mod transaction_util;
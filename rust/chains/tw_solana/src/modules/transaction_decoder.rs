@@ -2,6 +2,7 @@
 //
 // Copyright © 2017 Trust Wallet.
 
+use crate::modules::instruction_decoder::InstructionDecoder;
 use crate::modules::proto_builder::ProtoBuilder;
 use crate::transaction::versioned::VersionedTransaction;
 use tw_coin_entry::coin_context::CoinContext;
@@ -30,9 +31,11 @@ impl SolanaTransactionDecoder {
             .tw_err(|_| SigningErrorType::Error_input_parse)
             .context("Error decoding transaction as 'bincode'")?;
         let transaction = ProtoBuilder::build_from_tx(&decoded_tx);
+        let instructions = InstructionDecoder::decode_message(&decoded_tx.message);
 
         Ok(Proto::DecodingTransactionOutput {
             transaction: Some(transaction),
+            instructions,
             ..Proto::DecodingTransactionOutput::default()
         })
     }
@@ -20,6 +20,28 @@ pub struct MessageAddressTableLookup {
     pub readonly_indexes: Vec<u8>,
 }
 
+/// The maximum number of addresses that a lookup table can hold.
+pub const LOOKUP_TABLE_MAX_ADDRESSES: usize = 256;
+
+/// Contents of an address lookup table account.
+/// https://github.com/solana-labs/solana/blob/4b65cc8eef6ef79cb9b9cbc534a99b4900e58cf7/sdk/program/src/address_lookup_table_account.rs
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct AddressLookupTableAccount {
+    /// Address lookup table account key
+    pub key: SolanaAddress,
+    /// Addresses stored in the lookup table
+    pub addresses: Vec<SolanaAddress>,
+}
+
+/// Addresses loaded via address table lookups, in the order they follow the static message `account_keys`.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct LoadedAddresses {
+    /// List of addresses for writable loaded accounts
+    pub writable: Vec<SolanaAddress>,
+    /// List of addresses for read-only loaded accounts
+    pub readonly: Vec<SolanaAddress>,
+}
+
 #[derive(Serialize, Deserialize, Default, Debug, PartialEq, Eq, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Message {
@@ -3,17 +3,30 @@
 // Copyright © 2017 Trust Wallet.
 
 use crate::address::SuiAddress;
-use crate::transaction::sui_types::{ObjectDigest, ObjectID, ObjectRef, SequenceNumber};
+use crate::transaction::command::{Argument, Command};
+use crate::transaction::programmable_transaction::ProgrammableTransaction;
+use crate::transaction::sui_types::{
+    CallArg, ObjectArg, ObjectDigest, ObjectID, ObjectRef, SequenceNumber,
+};
 use crate::transaction::transaction_builder::TransactionBuilder;
 use crate::transaction::transaction_data::TransactionData;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::TypeTag;
+use move_core_types::parser::parse_type_tag;
 use std::borrow::Cow;
 use std::str::FromStr;
 use tw_coin_entry::error::prelude::*;
 use tw_encoding::base64::{self, STANDARD};
+use tw_encoding::bcs;
 use tw_keypair::ed25519;
 use tw_keypair::traits::KeyPairTrait;
 use tw_memory::Data;
+use tw_number::U256;
 use tw_proto::Sui::Proto;
+use tw_proto::Sui::Proto::mod_Argument::OneOfargument as ArgumentType;
+use tw_proto::Sui::Proto::mod_CallArg::OneOfarg as CallArgType;
+use tw_proto::Sui::Proto::mod_Command::OneOfcommand as CommandType;
+use tw_proto::Sui::Proto::mod_PureValue::OneOfvalue as PureValueType;
 use tw_proto::Sui::Proto::mod_SigningInput::OneOftransaction_payload as TransactionType;
 
 pub enum TWTransaction {
@@ -53,6 +66,7 @@ impl<'a> TWTransactionBuilder<'a> {
             TransactionType::transfer_object(ref transfer_obj) => {
                 self.transfer_object_from_proto(transfer_obj)
             },
+            TransactionType::programmable_transaction(ref pt) => self.programmable_from_proto(pt),
             TransactionType::None => SigningError::err(SigningErrorType::Error_invalid_params),
         }?;
         Ok(TWTransaction::Transaction(tx_data))
@@ -182,6 +196,39 @@ impl<'a> TWTransactionBuilder<'a> {
         )
     }
 
+    fn programmable_from_proto(
+        &self,
+        pt: &Proto::ProgrammableTransaction<'_>,
+    ) -> SigningResult<TransactionData> {
+        let signer = self.signer_address()?;
+
+        let inputs = pt
+            .inputs
+            .iter()
+            .enumerate()
+            .map(|(i, input)| {
+                Self::call_arg_from_proto(input).with_context(|| format!("Invalid input #{i}"))
+            })
+            .collect::<SigningResult<_>>()?;
+        let commands = pt
+            .commands
+            .iter()
+            .enumerate()
+            .map(|(i, command)| {
+                Self::command_from_proto(command).with_context(|| format!("Invalid command #{i}"))
+            })
+            .collect::<SigningResult<_>>()?;
+        let gas = Self::build_coins(&pt.gas)?;
+
+        TransactionBuilder::programmable(
+            signer,
+            ProgrammableTransaction { inputs, commands },
+            gas,
+            self.input.gas_budget,
+            self.input.reference_gas_price,
+        )
+    }
+
     fn signer_address(&self) -> SigningResult<SuiAddress> {
         if self.input.private_key.is_empty() {
             SuiAddress::from_str(&self.input.signer)
@@ -212,6 +259,187 @@ impl<'a> TWTransactionBuilder<'a> {
         Ok((object_id, version, object_digest))
     }
 
+    fn call_arg_from_proto(input: &Proto::CallArg) -> SigningResult<CallArg> {
+        match input.arg {
+            CallArgType::pure_value(ref pure) => {
+                Self::pure_value_from_proto(pure).map(CallArg::Pure)
+            },
+            CallArgType::imm_or_owned_object(ref obj) => {
+                let obj = Self::build_coin(obj)?;
+                Ok(CallArg::Object(ObjectArg::ImmOrOwnedObject(obj)))
+            },
+            CallArgType::shared_object(ref shared) => {
+                let id = ObjectID::from_str(&shared.object_id).context("Invalid Object ID")?;
+                Ok(CallArg::Object(ObjectArg::SharedObject {
+                    id,
+                    initial_shared_version: SequenceNumber(shared.initial_shared_version),
+                    mutable: shared.mutable,
+                }))
+            },
+            CallArgType::receiving(ref obj) => {
+                let obj = Self::build_coin(obj)?;
+                Ok(CallArg::Object(ObjectArg::Receiving(obj)))
+            },
+            CallArgType::None => SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("No input argument specified"),
+        }
+    }
+
+    fn pure_value_from_proto(pure: &Proto::PureValue) -> SigningResult<Data> {
+        let encoded = match pure.value {
+            PureValueType::bool_value(value) => bcs::encode(&value)?,
+            PureValueType::u8_value(value) => {
+                let value = u8::try_from(value)
+                    .tw_err(|_| SigningErrorType::Error_invalid_params)
+                    .context("u8 value is out of range")?;
+                bcs::encode(&value)?
+            },
+            PureValueType::u16_value(value) => {
+                let value = u16::try_from(value)
+                    .tw_err(|_| SigningErrorType::Error_invalid_params)
+                    .context("u16 value is out of range")?;
+                bcs::encode(&value)?
+            },
+            PureValueType::u32_value(value) => bcs::encode(&value)?,
+            PureValueType::u64_value(value) => bcs::encode(&value)?,
+            PureValueType::u128_value(ref value) => {
+                let value = u128::from_str(value)
+                    .tw_err(|_| SigningErrorType::Error_invalid_params)
+                    .context("Invalid u128 value")?;
+                bcs::encode(&value)?
+            },
+            PureValueType::u256_value(ref value) => {
+                let value = U256::from_str(value)
+                    .tw_err(|_| SigningErrorType::Error_invalid_params)
+                    .context("Invalid u256 value")?;
+                // BCS encodes `u256` as 32 little-endian bytes.
+                value.to_little_endian().to_vec()
+            },
+            PureValueType::address(ref addr) => {
+                let addr = SuiAddress::from_str(addr)
+                    .into_tw()
+                    .context("Invalid address value")?;
+                bcs::encode(&addr)?
+            },
+            PureValueType::string_value(ref value) => bcs::encode(value.as_ref())?,
+            PureValueType::byte_vector(ref value) => bcs::encode(value.as_ref())?,
+            PureValueType::bcs_encoded(ref value) => value.to_vec(),
+            PureValueType::None => {
+                return SigningError::err(SigningErrorType::Error_invalid_params)
+                    .context("No pure value specified")
+            },
+        };
+        Ok(encoded)
+    }
+
+    fn command_from_proto(command: &Proto::Command) -> SigningResult<Command> {
+        match command.command {
+            CommandType::move_call(ref call) => {
+                let package = ObjectID::from_str(&call.package).context("Invalid package ID")?;
+                let module = Self::parse_identifier(&call.module).context("Invalid module name")?;
+                let function =
+                    Self::parse_identifier(&call.function).context("Invalid function name")?;
+                let type_arguments = call
+                    .type_arguments
+                    .iter()
+                    .map(|type_tag| Self::parse_type_tag(type_tag))
+                    .collect::<SigningResult<_>>()?;
+                let arguments = Self::build_arguments(&call.arguments)?;
+                Ok(Command::move_call(
+                    package,
+                    module,
+                    function,
+                    type_arguments,
+                    arguments,
+                ))
+            },
+            CommandType::transfer_objects(ref transfer) => {
+                let objects = Self::build_arguments(&transfer.objects)?;
+                let address = Self::require_argument(&transfer.address)
+                    .context("No 'address' argument specified")?;
+                Ok(Command::TransferObjects(objects, address))
+            },
+            CommandType::split_coins(ref split) => {
+                let coin =
+                    Self::require_argument(&split.coin).context("No 'coin' argument specified")?;
+                let amounts = Self::build_arguments(&split.amounts)?;
+                Ok(Command::SplitCoins(coin, amounts))
+            },
+            CommandType::merge_coins(ref merge) => {
+                let destination = Self::require_argument(&merge.destination)
+                    .context("No 'destination' argument specified")?;
+                let sources = Self::build_arguments(&merge.sources)?;
+                Ok(Command::MergeCoins(destination, sources))
+            },
+            CommandType::publish(ref publish) => {
+                let modules = publish.modules.iter().map(|m| m.to_vec()).collect();
+                let dependencies = publish
+                    .dependencies
+                    .iter()
+                    .map(|dep| ObjectID::from_str(dep).context("Invalid dependency ID"))
+                    .collect::<SigningResult<_>>()?;
+                Ok(Command::Publish(modules, dependencies))
+            },
+            CommandType::make_move_vec(ref make_vec) => {
+                let element_type = if make_vec.element_type.is_empty() {
+                    None
+                } else {
+                    Some(Self::parse_type_tag(&make_vec.element_type)?)
+                };
+                if element_type.is_none() && make_vec.elements.is_empty() {
+                    return SigningError::err(SigningErrorType::Error_invalid_params)
+                        .context("Element type must be specified for an empty vector");
+                }
+                let elements = Self::build_arguments(&make_vec.elements)?;
+                Ok(Command::MakeMoveVec(element_type, elements))
+            },
+            CommandType::None => SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("No command specified"),
+        }
+    }
+
+    fn build_arguments(args: &[Proto::Argument]) -> SigningResult<Vec<Argument>> {
+        args.iter().map(Self::build_argument).collect()
+    }
+
+    fn require_argument(maybe_arg: &Option<Proto::Argument>) -> SigningResult<Argument> {
+        let arg = maybe_arg
+            .as_ref()
+            .or_tw_err(SigningErrorType::Error_invalid_params)?;
+        Self::build_argument(arg)
+    }
+
+    fn build_argument(arg: &Proto::Argument) -> SigningResult<Argument> {
+        let to_index = |idx: u32| {
+            u16::try_from(idx)
+                .tw_err(|_| SigningErrorType::Error_invalid_params)
+                .context("Argument index is out of range")
+        };
+        match arg.argument {
+            ArgumentType::gas_coin(_) => Ok(Argument::GasCoin),
+            ArgumentType::input(idx) => Ok(Argument::Input(to_index(idx)?)),
+            ArgumentType::result(idx) => Ok(Argument::Result(to_index(idx)?)),
+            ArgumentType::nested_result(ref nested) => Ok(Argument::NestedResult(
+                to_index(nested.command_index)?,
+                to_index(nested.result_index)?,
+            )),
+            ArgumentType::None => SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("No argument specified"),
+        }
+    }
+
+    fn parse_identifier(s: &str) -> SigningResult<Identifier> {
+        Identifier::new(s)
+            .tw_err(|_| SigningErrorType::Error_invalid_params)
+            .with_context(|| format!("Invalid Move identifier: '{s}'"))
+    }
+
+    fn parse_type_tag(s: &str) -> SigningResult<TypeTag> {
+        parse_type_tag(s)
+            .tw_err(|_| SigningErrorType::Error_invalid_params)
+            .with_context(|| format!("Invalid Move type tag: '{s}'"))
+    }
+
     fn parse_addresses(addresses: &[Cow<'_, str>]) -> SigningResult<Vec<SuiAddress>> {
         let mut res = Vec::with_capacity(addresses.len());
         for addr in addresses {
@@ -220,3 +448,71 @@ impl<'a> TWTransactionBuilder<'a> {
         Ok(res)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tw_encoding::hex::ToHex;
+
+    fn encode_pure(value: PureValueType) -> SigningResult<String> {
+        let pure = Proto::PureValue { value };
+        TWTransactionBuilder::pure_value_from_proto(&pure).map(|encoded| encoded.to_hex())
+    }
+
+    #[test]
+    fn test_pure_value_from_proto() {
+        assert_eq!(encode_pure(PureValueType::bool_value(true)).unwrap(), "01");
+        assert_eq!(encode_pure(PureValueType::u8_value(255)).unwrap(), "ff");
+        assert_eq!(
+            encode_pure(PureValueType::u16_value(0x1234)).unwrap(),
+            "3412"
+        );
+        assert_eq!(
+            encode_pure(PureValueType::u32_value(1)).unwrap(),
+            "01000000"
+        );
+        assert_eq!(
+            encode_pure(PureValueType::u64_value(1000)).unwrap(),
+            "e803000000000000"
+        );
+        assert_eq!(
+            encode_pure(PureValueType::u128_value("1000".into())).unwrap(),
+            "e8030000000000000000000000000000"
+        );
+        assert_eq!(
+            encode_pure(PureValueType::u256_value("0x0102".into())).unwrap(),
+            "0201000000000000000000000000000000000000000000000000000000000000"
+        );
+        assert_eq!(
+            encode_pure(PureValueType::address(
+                "0x54e80d76d790c277f5a44f3ce92f53d26f5894892bf395dee6375988876be6b2".into()
+            ))
+            .unwrap(),
+            "54e80d76d790c277f5a44f3ce92f53d26f5894892bf395dee6375988876be6b2"
+        );
+        // Length-prefixed.
+        assert_eq!(
+            encode_pure(PureValueType::string_value("Sui".into())).unwrap(),
+            "03537569"
+        );
+        assert_eq!(
+            encode_pure(PureValueType::byte_vector(vec![0xde, 0xad].into())).unwrap(),
+            "02dead"
+        );
+        // Used as is.
+        assert_eq!(
+            encode_pure(PureValueType::bcs_encoded(vec![0x01, 0x02].into())).unwrap(),
+            "0102"
+        );
+    }
+
+    #[test]
+    fn test_pure_value_from_proto_error() {
+        encode_pure(PureValueType::u8_value(256)).unwrap_err();
+        encode_pure(PureValueType::u16_value(0x10000)).unwrap_err();
+        encode_pure(PureValueType::u128_value("-1".into())).unwrap_err();
+        encode_pure(PureValueType::u256_value("0xzz".into())).unwrap_err();
+        encode_pure(PureValueType::address("0x5".into())).unwrap_err();
+        encode_pure(PureValueType::None).unwrap_err();
+    }
+}
@@ -6,6 +6,7 @@ use crate::transaction::sui_types::ObjectID;
 use move_core_types::identifier::Identifier;
 use move_core_types::language_storage::TypeTag;
 use serde::{Deserialize, Serialize};
+use std::iter;
 
 /// A single command in a programmable transaction.
 #[derive(Debug, Deserialize, Serialize)]
@@ -48,6 +49,21 @@ impl Command {
             arguments,
         }))
     }
+
+    /// Returns all arguments used by the command.
+    pub fn arguments(&self) -> Vec<&Argument> {
+        match self {
+            Command::MoveCall(call) => call.arguments.iter().collect(),
+            Command::TransferObjects(objects, address) => {
+                objects.iter().chain(iter::once(address)).collect()
+            },
+            Command::SplitCoins(coin, args) | Command::MergeCoins(coin, args) => {
+                iter::once(coin).chain(args).collect()
+            },
+            Command::Publish(_, _) => Vec::new(),
+            Command::MakeMoveVec(_, elements) => elements.iter().collect(),
+        }
+    }
 }
 
 /// The command for calling a Move function, either an entry function or a public
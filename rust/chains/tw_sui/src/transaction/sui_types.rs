@@ -72,6 +72,8 @@ pub enum ObjectArg {
         initial_shared_version: SequenceNumber,
         mutable: bool,
     },
+    // A Move object that's sent to another object and is being received.
+    Receiving(ObjectRef),
 }
 
 impl ObjectArg {
@@ -83,7 +85,9 @@ impl ObjectArg {
 
     pub fn id(&self) -> ObjectID {
         match self {
-            ObjectArg::ImmOrOwnedObject((id, _, _)) | ObjectArg::SharedObject { id, .. } => *id,
+            ObjectArg::ImmOrOwnedObject((id, _, _))
+            | ObjectArg::SharedObject { id, .. }
+            | ObjectArg::Receiving((id, _, _)) => *id,
         }
     }
 }
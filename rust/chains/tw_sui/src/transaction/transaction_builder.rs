@@ -8,7 +8,9 @@ use crate::constants::{
     WITHDRAW_STAKE_FUN_NAME,
 };
 use crate::transaction::command::Command;
-use crate::transaction::programmable_transaction::ProgrammableTransactionBuilder;
+use crate::transaction::programmable_transaction::{
+    ProgrammableTransaction, ProgrammableTransactionBuilder,
+};
 use crate::transaction::sui_types::{CallArg, ObjectArg, ObjectRef};
 use crate::transaction::transaction_data::{TransactionData, TransactionKind};
 use tw_coin_entry::error::prelude::*;
@@ -164,6 +166,36 @@ impl TransactionBuilder {
         ))
     }
 
+    /// Build a transaction from an arbitrary programmable transaction.
+    /// The gas objects can not appear in the transaction inputs, `Argument::GasCoin` should be used instead.
+    pub fn programmable(
+        signer: SuiAddress,
+        pt: ProgrammableTransaction,
+        gas: Vec<ObjectRef>,
+        gas_budget: u64,
+        gas_price: u64,
+    ) -> SigningResult<TransactionData> {
+        if gas.is_empty() {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("No gas objects specified");
+        }
+
+        let is_gas_input = |input: &CallArg| match input {
+            CallArg::Object(obj) => gas.iter().any(|coin| coin.0 == obj.id()),
+            CallArg::Pure(_) => false,
+        };
+        if pt.inputs.iter().any(is_gas_input) {
+            return SigningError::err(SigningErrorType::Error_invalid_params).context(
+                "Gas coin is in inputs of Programmable transaction, use GasCoin argument instead!",
+            );
+        }
+
+        pt.validate_arguments()?;
+        Ok(TransactionData::new_programmable(
+            signer, gas, pt, gas_budget, gas_price,
+        ))
+    }
+
     pub fn transfer_object(
         signer: SuiAddress,
         object: ObjectRef,
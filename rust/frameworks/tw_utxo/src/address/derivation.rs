@@ -9,6 +9,7 @@ pub const SEGWIT_DERIVATION_PATH_TYPE: ChildIndex = ChildIndex::Hardened(84);
 
 pub enum BitcoinDerivation {
     Legacy,
+    NestedSegwit,
     Segwit,
 }
 
@@ -22,6 +23,7 @@ impl BitcoinDerivation {
             // Please note that testnet derivation is no longer supported. Instead, use address prefix.
             Derivation::Default | Derivation::Testnet => (),
             Derivation::Segwit => return BitcoinDerivation::Segwit,
+            Derivation::NestedSegwit => return BitcoinDerivation::NestedSegwit,
             Derivation::Legacy => return BitcoinDerivation::Legacy,
         }
 
@@ -32,6 +34,7 @@ impl BitcoinDerivation {
 
         match default_derivation.name {
             Derivation::Segwit => BitcoinDerivation::Segwit,
+            Derivation::NestedSegwit => BitcoinDerivation::NestedSegwit,
             Derivation::Default if derivation_path_type == Some(SEGWIT_DERIVATION_PATH_TYPE) => {
                 BitcoinDerivation::Segwit
             },
@@ -2,6 +2,7 @@
 //
 // Copyright © 2017 Trust Wallet.
 
+use crate::script::standard_script::conditions;
 use crate::script::Script;
 use std::fmt;
 use std::str::FromStr;
@@ -66,6 +67,27 @@ impl LegacyAddress {
         LegacyAddress::new(p2sh_prefix, &script_hash)
     }
 
+    /// Derives a P2WPKH-nested-in-P2SH address (BIP49).
+    pub fn p2sh_p2wpkh_with_coin_and_prefix(
+        coin: &dyn CoinContext,
+        public_key: &tw::PublicKey,
+        prefix: Option<BitcoinBase58Prefix>,
+    ) -> AddressResult<LegacyAddress> {
+        let p2sh_prefix = match prefix {
+            Some(prefix) => prefix.p2sh,
+            None => coin.p2sh_prefix().ok_or(AddressError::InvalidRegistry)?,
+        };
+
+        let ecdsa_public_key = public_key
+            .to_secp256k1()
+            .ok_or(AddressError::PublicKeyTypeMismatch)?;
+
+        let h = sha256_ripemd(ecdsa_public_key.compressed().as_slice());
+        let public_key_hash: H160 = h.as_slice().try_into().expect("hash length is 20 bytes");
+        let redeem_script = conditions::new_p2wpkh(&public_key_hash);
+        LegacyAddress::p2sh_with_prefix_byte(&redeem_script, p2sh_prefix)
+    }
+
     pub fn from_str_with_coin_and_prefix(
         coin: &dyn CoinContext,
         s: &str,
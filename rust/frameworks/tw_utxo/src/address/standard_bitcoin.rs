@@ -106,6 +106,18 @@ impl StandardBitcoinAddress {
         maybe_prefix: Option<StandardBitcoinPrefix>,
     ) -> AddressResult<StandardBitcoinAddress> {
         match maybe_prefix {
+            // P2WPKH nested in P2SH is the only script type that can be derived with a Base58 prefix
+            // other than P2PKH.
+            Some(StandardBitcoinPrefix::Base58(prefix))
+                if derivation == Derivation::NestedSegwit =>
+            {
+                return LegacyAddress::p2sh_p2wpkh_with_coin_and_prefix(
+                    coin,
+                    public_key,
+                    Some(prefix),
+                )
+                .map(StandardBitcoinAddress::Legacy);
+            },
             Some(StandardBitcoinPrefix::Base58(prefix)) => {
                 return LegacyAddress::p2pkh_with_coin_and_prefix(coin, public_key, Some(prefix))
                     .map(StandardBitcoinAddress::Legacy);
@@ -123,6 +135,10 @@ impl StandardBitcoinAddress {
                 LegacyAddress::p2pkh_with_coin_and_prefix(coin, public_key, None)
                     .map(StandardBitcoinAddress::Legacy)
             },
+            BitcoinDerivation::NestedSegwit => {
+                LegacyAddress::p2sh_p2wpkh_with_coin_and_prefix(coin, public_key, None)
+                    .map(StandardBitcoinAddress::Legacy)
+            },
             BitcoinDerivation::Segwit => {
                 SegwitAddress::p2wpkh_with_coin_and_prefix(coin, public_key, None)
                     .map(StandardBitcoinAddress::Segwit)
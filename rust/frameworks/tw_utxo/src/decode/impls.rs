@@ -0,0 +1,80 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::decode::reader::Reader;
+use crate::decode::Decodable;
+use tw_coin_entry::error::prelude::*;
+use tw_hash::Hash;
+use tw_memory::Data;
+
+impl Decodable for Data {
+    fn decode(reader: &mut Reader) -> SigningResult<Self> {
+        let len = reader.read_len()?;
+        reader.read_slice(len).map(<[u8]>::to_vec)
+    }
+}
+
+impl<const N: usize> Decodable for Hash<N> {
+    #[inline]
+    fn decode(reader: &mut Reader) -> SigningResult<Self> {
+        let bytes = reader.read_slice(N)?;
+        Hash::try_from(bytes)
+            .tw_err(|_| SigningErrorType::Error_internal)
+            .context("Expected exactly N bytes")
+    }
+}
+
+macro_rules! impl_decodable_for_int {
+    ($int:ty) => {
+        impl Decodable for $int {
+            #[inline]
+            fn decode(reader: &mut Reader) -> SigningResult<Self> {
+                let bytes = reader.read_slice(std::mem::size_of::<$int>())?;
+                let bytes = bytes
+                    .try_into()
+                    .tw_err(|_| SigningErrorType::Error_internal)
+                    .context("Expected exactly size_of bytes")?;
+                Ok(<$int>::from_le_bytes(bytes))
+            }
+        }
+    };
+}
+
+impl_decodable_for_int!(u8);
+impl_decodable_for_int!(i32);
+impl_decodable_for_int!(i64);
+impl_decodable_for_int!(u16);
+impl_decodable_for_int!(u32);
+impl_decodable_for_int!(u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decode::decode;
+    use tw_encoding::hex::DecodeHex;
+
+    #[test]
+    fn test_reader_read() {
+        let bytes = vec![1_u8, 2, 0, 3, 0, 0, 0, 4, 0, 0, 0, 0, 0, 0, 0];
+        let mut reader = Reader::new(&bytes);
+
+        assert_eq!(reader.read::<u8>().unwrap(), 1);
+        assert_eq!(reader.read::<u16>().unwrap(), 2);
+        assert_eq!(reader.read::<u32>().unwrap(), 3);
+        assert_eq!(reader.read::<u64>().unwrap(), 4);
+        assert!(reader.is_finished());
+        reader.read::<u8>().unwrap_err();
+    }
+
+    #[test]
+    fn test_bytes_deserialize() {
+        let encoded = "020145".decode_hex().unwrap();
+        assert_eq!(decode::<Data>(&encoded).unwrap(), vec![0x01, 0x45]);
+
+        // Not enough bytes.
+        decode::<Data>(&encoded[..2]).unwrap_err();
+        // Trailing bytes.
+        decode::<Data>(&"02014500".decode_hex().unwrap()).unwrap_err();
+    }
+}
@@ -0,0 +1,31 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::decode::reader::Reader;
+use tw_coin_entry::error::prelude::*;
+
+pub mod impls;
+pub mod reader;
+
+/// Deserializes the struct from the given bytes.
+/// Returns an error if the bytes are not consumed entirely.
+pub fn decode<T>(bytes: &[u8]) -> SigningResult<T>
+where
+    T: Decodable,
+{
+    let mut reader = Reader::new(bytes);
+    let t = reader.read()?;
+    if !reader.is_finished() {
+        return SigningError::err(SigningErrorType::Error_input_parse).context(format!(
+            "Unexpected trailing {} bytes after the decoded data",
+            reader.remaining()
+        ));
+    }
+    Ok(t)
+}
+
+pub trait Decodable: Sized {
+    /// Deserialize the struct from the beginning of the reader.
+    fn decode(reader: &mut Reader) -> SigningResult<Self>;
+}
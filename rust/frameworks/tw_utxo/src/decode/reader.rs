@@ -0,0 +1,80 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::decode::Decodable;
+use crate::encode::compact_integer::CompactInteger;
+use tw_coin_entry::error::prelude::*;
+
+/// Reader used for deserialization of Bitcoin structures.
+pub struct Reader<'a> {
+    buffer: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(buffer: &'a [u8]) -> Self {
+        Reader {
+            buffer,
+            position: 0,
+        }
+    }
+
+    /// Deserializes the struct from the current position of the reader.
+    pub fn read<T>(&mut self) -> SigningResult<T>
+    where
+        T: Decodable,
+    {
+        T::decode(self)
+    }
+
+    /// Reads exactly `len` raw bytes.
+    pub fn read_slice(&mut self, len: usize) -> SigningResult<&'a [u8]> {
+        let bytes = self
+            .peek_slice(len)
+            .or_tw_err(SigningErrorType::Error_input_parse)
+            .with_context(|| {
+                format!(
+                    "Unexpected end of data: expected {len} bytes, but only {} left",
+                    self.remaining()
+                )
+            })?;
+        self.position += len;
+        Ok(bytes)
+    }
+
+    /// Returns the next `len` raw bytes without moving the reader forward.
+    pub fn peek_slice(&self, len: usize) -> Option<&'a [u8]> {
+        let end = self.position.checked_add(len)?;
+        self.buffer.get(self.position..end)
+    }
+
+    /// Reads a length prefix, and then the list of deserializable structs.
+    pub fn read_list<T>(&mut self) -> SigningResult<Vec<T>>
+    where
+        T: Decodable,
+    {
+        let len = self.read_len()?;
+        // Do not trust the length prefix when allocating memory, as each item takes at least one byte.
+        let mut list = Vec::with_capacity(len.min(self.remaining()));
+        for _ in 0..len {
+            list.push(self.read()?);
+        }
+        Ok(list)
+    }
+
+    /// Reads a [`CompactInteger`] length prefix.
+    pub fn read_len(&mut self) -> SigningResult<usize> {
+        let len: CompactInteger = self.read()?;
+        len.to_len()
+    }
+
+    /// Returns the number of bytes left to read.
+    pub fn remaining(&self) -> usize {
+        self.buffer.len() - self.position
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.remaining() == 0
+    }
+}
@@ -2,21 +2,99 @@
 //
 // Copyright © 2017 Trust Wallet.
 
+use crate::encode::Encodable;
+use crate::script::standard_script::conditions;
+use crate::script::Script;
 use crate::transaction::transaction_parts::Amount;
 
 pub mod dust_filter;
 
+/// The size of an outpoint, `nSequence` and a `scriptSig` length prefix.
+const INPUT_BASE_SIZE: usize = 32 + 4 + 1 + 4;
+/// The estimated size of a `scriptSig` spending a P2PKH output.
+const P2PKH_SCRIPT_SIG_SIZE: usize = 107;
+/// The value (8 bytes) of a transaction output.
+const OUTPUT_VALUE_SIZE: usize = 8;
+/// Witness data is discounted by the factor of 4.
+const WITNESS_SCALE_FACTOR: usize = 4;
+
 /// Transaction dust amount calculator.
-/// Later, we plan to add support for `DynamicDust` policy with a `min_relay_fee` amount.
 #[derive(Clone, Copy)]
 pub enum DustPolicy {
     FixedAmount(Amount),
+    /// Dust threshold depends on the output script type and the given relay fee rate in satoshis per 1000 vbytes.
+    /// An output is dust if spending it would cost more than its value at the relay fee rate.
+    /// https://github.com/bitcoin/bitcoin/blob/v27.0/src/policy/policy.cpp#L26-L63
+    DynamicDust {
+        min_relay_fee_per_kvb: Amount,
+    },
 }
 
 impl DustPolicy {
-    pub fn dust_threshold(&self) -> Amount {
+    /// Returns a dust threshold of an output with the given `scriptPubkey`.
+    pub fn dust_threshold(&self, script_pubkey: &Script) -> Amount {
         match self {
             DustPolicy::FixedAmount(amount) => *amount,
+            DustPolicy::DynamicDust {
+                min_relay_fee_per_kvb,
+            } => {
+                if conditions::is_op_return(script_pubkey) {
+                    // OP_RETURN outputs are unspendable, so they can't be dust.
+                    return 0;
+                }
+
+                // The size of the output itself and of the input spending the output.
+                let output_size = OUTPUT_VALUE_SIZE + script_pubkey.encoded_size();
+                let spend_size = if conditions::is_witness_program(script_pubkey) {
+                    INPUT_BASE_SIZE + P2PKH_SCRIPT_SIG_SIZE / WITNESS_SCALE_FACTOR
+                } else {
+                    INPUT_BASE_SIZE + P2PKH_SCRIPT_SIG_SIZE
+                };
+
+                let size = (output_size + spend_size) as Amount;
+                (size * min_relay_fee_per_kvb + 999) / 1000
+            },
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tw_hash::{H160, H256};
+
+    /// Bitcoin Core's `DUST_RELAY_TX_FEE`.
+    const DUST_RELAY_TX_FEE: Amount = 3000;
+
+    #[test]
+    fn test_dynamic_dust_threshold() {
+        let policy = DustPolicy::DynamicDust {
+            min_relay_fee_per_kvb: DUST_RELAY_TX_FEE,
+        };
+
+        let p2pkh = conditions::new_p2pkh(&H160::default());
+        let p2sh = conditions::new_p2sh(&H160::default());
+        let p2wpkh = conditions::new_p2wpkh(&H160::default());
+        let p2wsh = conditions::new_p2wsh(&H256::default());
+        let p2tr = conditions::new_p2tr_dangerous_assume_tweaked(&H256::from(
+            "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+        ));
+        let op_return = conditions::new_op_return(b"hello");
+
+        assert_eq!(policy.dust_threshold(&p2pkh), 546);
+        assert_eq!(policy.dust_threshold(&p2sh), 540);
+        assert_eq!(policy.dust_threshold(&p2wpkh), 294);
+        assert_eq!(policy.dust_threshold(&p2wsh), 330);
+        assert_eq!(policy.dust_threshold(&p2tr), 330);
+        assert_eq!(policy.dust_threshold(&op_return), 0);
+    }
+
+    #[test]
+    fn test_fixed_dust_threshold() {
+        let policy = DustPolicy::FixedAmount(1000);
+        let p2tr = conditions::new_p2tr_dangerous_assume_tweaked(&H256::from(
+            "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+        ));
+        assert_eq!(policy.dust_threshold(&p2tr), 1000);
+    }
+}
@@ -2,18 +2,26 @@
 //
 // Copyright © 2017 Trust Wallet.
 
+use crate::decode::reader::Reader;
+use crate::decode::Decodable;
 use crate::encode::stream::Stream;
 use crate::encode::Encodable;
 use std::ops::RangeInclusive;
+use tw_coin_entry::error::prelude::*;
 
 const ONE_BYTE_RANGE: RangeInclusive<u64> = 0..=0xFC;
 const TWO_BYTES_RANGE: RangeInclusive<u64> = 0xFD..=0xFFFF;
 const FOUR_BYTES_RANGE: RangeInclusive<u64> = 0x10000..=0xFFFF_FFFF;
+const EIGHT_BYTES_RANGE: RangeInclusive<u64> = 0x1_0000_0000..=u64::MAX;
 
 const TWO_BYTES_FLAG: u8 = 0xFD_u8;
 const FOUR_BYTES_FLAG: u8 = 0xFE_u8;
 const EIGHT_BYTES_FLAG: u8 = 0xFF_u8;
 
+/// The maximum length of a list or byte array that can be deserialized.
+/// https://github.com/bitcoin/bitcoin/blob/v27.0/src/serialize.h#L32
+const MAX_LEN: u64 = 0x0200_0000;
+
 /// A type of variable-length integer commonly used in the Bitcoin P2P protocol and Bitcoin serialized data structures.
 #[derive(Default, Debug, Clone, Copy, PartialEq)]
 pub struct CompactInteger(u64);
@@ -24,6 +32,19 @@ impl From<usize> for CompactInteger {
     }
 }
 
+impl CompactInteger {
+    /// Converts the integer to a length of a list or byte array.
+    pub fn to_len(self) -> SigningResult<usize> {
+        if self.0 > MAX_LEN {
+            return SigningError::err(SigningErrorType::Error_input_parse)
+                .context(format!("Length prefix is too large: {}", self.0));
+        }
+        usize::try_from(self.0)
+            .tw_err(|_| SigningErrorType::Error_input_parse)
+            .context("Length prefix does not fit usize")
+    }
+}
+
 impl Encodable for CompactInteger {
     fn encode(&self, stream: &mut Stream) {
         let v = self.0;
@@ -55,6 +76,24 @@ impl Encodable for CompactInteger {
     }
 }
 
+impl Decodable for CompactInteger {
+    fn decode(reader: &mut Reader) -> SigningResult<Self> {
+        let (v, range) = match reader.read::<u8>()? {
+            TWO_BYTES_FLAG => (reader.read::<u16>()? as u64, TWO_BYTES_RANGE),
+            FOUR_BYTES_FLAG => (reader.read::<u32>()? as u64, FOUR_BYTES_RANGE),
+            EIGHT_BYTES_FLAG => (reader.read::<u64>()?, EIGHT_BYTES_RANGE),
+            byte => return Ok(CompactInteger(byte as u64)),
+        };
+
+        // The integer must be encoded in the shortest possible form.
+        if !range.contains(&v) {
+            return SigningError::err(SigningErrorType::Error_input_parse)
+                .context(format!("Non-canonical compact integer encoding: {v}"));
+        }
+        Ok(CompactInteger(v))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -79,4 +118,33 @@ mod tests {
 
         assert_eq!(stream.out(), expected);
     }
+
+    #[test]
+    fn test_compact_integer_decode() {
+        let encoded = [
+            0_u8, 0xfc, 0xfd, 0xfd, 0x00, 0xfd, 0xff, 0xff, 0xfe, 0x00, 0x00, 0x01, 0x00, 0xfe,
+            0xff, 0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
+        ];
+        let mut reader = Reader::new(&encoded);
+
+        let expected = [0, 0xfc, 0xfd, 0xffff, 0x10000, 0xffff_ffff, 0x1_0000_0000];
+        for value in expected {
+            assert_eq!(
+                reader.read::<CompactInteger>().unwrap(),
+                CompactInteger(value)
+            );
+        }
+        assert!(reader.is_finished());
+    }
+
+    #[test]
+    fn test_compact_integer_decode_non_canonical() {
+        for encoded in [
+            vec![0xfd, 0xfc, 0x00],
+            vec![0xfe, 0xff, 0xff, 0x00, 0x00],
+            vec![0xff, 0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x00],
+        ] {
+            Reader::new(&encoded).read::<CompactInteger>().unwrap_err();
+        }
+    }
 }
@@ -4,6 +4,7 @@
 
 pub mod address;
 pub mod constants;
+pub mod decode;
 pub mod dust;
 pub mod encode;
 pub mod modules;
@@ -12,4 +13,5 @@ pub mod sighash;
 pub mod signature;
 pub mod signing_mode;
 pub mod spending_data;
+pub mod taproot;
 pub mod transaction;
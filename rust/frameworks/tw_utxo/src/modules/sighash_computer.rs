@@ -17,7 +17,8 @@ use tw_hash::H256;
 
 #[derive(Debug, Clone)]
 pub struct TxPreimage {
-    /// Transaction signatures in the same order as the transaction UTXOs.
+    /// Transaction sighashes in the same order as the transaction UTXOs and their signers.
+    /// See [`UtxoToSign::spender_public_keys`].
     pub sighashes: Vec<UtxoSighash>,
 }
 
@@ -53,77 +54,74 @@ where
     Transaction: TransactionPreimage + TransactionInterface,
 {
     /// Computes sighashes of [`SighashComputer::transaction`].
+    /// Each UTXO results in as many sighashes as many signers it requires.
     pub fn preimage_tx(
         unsigned_tx: &UnsignedTransaction<Transaction>,
     ) -> SigningResult<TxPreimage> {
-        unsigned_tx
-            .input_args()
-            .iter()
-            .enumerate()
-            .map(|(input_index, utxo)| {
-                let signing_method = utxo.signing_method;
-
-                let utxo_args = UtxoPreimageArgs {
-                    input_index,
-                    script_pubkey: utxo.script_pubkey.clone(),
-                    amount: utxo.amount,
-                    // TODO move `leaf_hash_code_separator` to `UtxoTaprootPreimageArgs`.
-                    leaf_hash_code_separator: utxo.leaf_hash_code_separator,
-                    sighash_ty: utxo.sighash_ty,
-                    tx_hasher: utxo.tx_hasher,
-                    signing_method,
-                };
-
-                let (sighash, taproot_tweak) = match signing_method {
-                    SigningMethod::Legacy | SigningMethod::Segwit => {
-                        let sighash = unsigned_tx.transaction().preimage_tx(&utxo_args)?;
-                        (sighash, None)
-                    },
-                    SigningMethod::Taproot => {
-                        // TODO Move `tr_spent_amounts` and `tr_spent_script_pubkeys` logic to `Transaction::preimage_taproot_tx()`.
-                        let tr_spent_amounts: Vec<Amount> = unsigned_tx
-                            .input_args()
-                            .iter()
-                            .map(|utxo| utxo.amount)
-                            .collect();
-
-                        let tr_spent_script_pubkeys: Vec<Script> = unsigned_tx
-                            .input_args()
-                            .iter()
-                            .map(|utxo| {
-                                if utxo.signing_method == SigningMethod::Taproot {
-                                    // Taproot UTXOs scriptPubkeys should be signed as is.
-                                    utxo.script_pubkey.clone()
-                                } else {
-                                    // Use the original scriptPubkey declared in the unspent output.
-                                    utxo.prevout_script_pubkey.clone()
-                                }
-                            })
-                            .collect();
-
-                        let tr = UtxoTaprootPreimageArgs {
-                            args: utxo_args,
-                            spent_amounts: tr_spent_amounts,
-                            spent_script_pubkeys: tr_spent_script_pubkeys.clone(),
-                        };
-
-                        let sighash = unsigned_tx.transaction().preimage_taproot_tx(&tr)?;
-                        let taproot_tweak = Self::get_taproot_tweak(utxo);
-
-                        (sighash, taproot_tweak)
-                    },
-                };
-
-                Ok(UtxoSighash {
-                    signing_method,
-                    sighash,
-                    signer_pubkey: utxo.spender_public_key.clone(),
-                    taproot_tweak,
-                })
-            })
-            // Collect the results as [`SigningResult<Vec<UtxoSighash>>`].
-            .collect::<SigningResult<Vec<_>>>()
-            .map(|sighashes: Vec<UtxoSighash>| TxPreimage { sighashes })
+        let mut sighashes = Vec::with_capacity(unsigned_tx.input_args().len());
+
+        for (input_index, utxo) in unsigned_tx.input_args().iter().enumerate() {
+            let signing_method = utxo.signing_method;
+
+            let utxo_args = UtxoPreimageArgs {
+                input_index,
+                script_pubkey: utxo.script_pubkey.clone(),
+                amount: utxo.amount,
+                // TODO move `leaf_hash_code_separator` to `UtxoTaprootPreimageArgs`.
+                leaf_hash_code_separator: utxo.leaf_hash_code_separator,
+                sighash_ty: utxo.sighash_ty,
+                tx_hasher: utxo.tx_hasher,
+                signing_method,
+            };
+
+            let (sighash, taproot_tweak) = match signing_method {
+                SigningMethod::Legacy | SigningMethod::Segwit => {
+                    let sighash = unsigned_tx.transaction().preimage_tx(&utxo_args)?;
+                    (sighash, None)
+                },
+                SigningMethod::Taproot => {
+                    // TODO Move `tr_spent_amounts` and `tr_spent_script_pubkeys` logic to `Transaction::preimage_taproot_tx()`.
+                    let tr_spent_amounts: Vec<Amount> = unsigned_tx
+                        .input_args()
+                        .iter()
+                        .map(|utxo| utxo.amount)
+                        .collect();
+
+                    // BIP-341 commits to the original scriptPubkeys declared in the unspent outputs,
+                    // even if a UTXO is spent via the Taproot script path.
+                    let tr_spent_script_pubkeys: Vec<Script> = unsigned_tx
+                        .input_args()
+                        .iter()
+                        .map(|utxo| utxo.prevout_script_pubkey.clone())
+                        .collect();
+
+                    let tr = UtxoTaprootPreimageArgs {
+                        args: utxo_args,
+                        spent_amounts: tr_spent_amounts,
+                        spent_script_pubkeys: tr_spent_script_pubkeys.clone(),
+                    };
+
+                    let sighash = unsigned_tx.transaction().preimage_taproot_tx(&tr)?;
+                    let taproot_tweak = Self::get_taproot_tweak(utxo);
+
+                    (sighash, taproot_tweak)
+                },
+            };
+
+            // All signers of the UTXO sign the same sighash.
+            sighashes.extend(
+                utxo.spender_public_keys
+                    .iter()
+                    .map(|signer_pubkey| UtxoSighash {
+                        signing_method,
+                        sighash,
+                        signer_pubkey: signer_pubkey.clone(),
+                        taproot_tweak: taproot_tweak.clone(),
+                    }),
+            );
+        }
+
+        Ok(TxPreimage { sighashes })
     }
 
     pub fn get_taproot_tweak(utxo: &UtxoToSign) -> Option<TaprootTweak> {
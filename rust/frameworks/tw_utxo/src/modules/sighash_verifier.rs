@@ -7,7 +7,7 @@ use crate::signature::FromRawOrDerBytes;
 use crate::signing_mode::SigningMethod;
 use crate::transaction::transaction_interface::TransactionInterface;
 use crate::transaction::unsigned_transaction::UnsignedTransaction;
-use crate::transaction::{TransactionPreimage, UtxoToSign};
+use crate::transaction::TransactionPreimage;
 use std::marker::PhantomData;
 use tw_coin_entry::coin_entry::SignatureBytes;
 use tw_coin_entry::error::prelude::*;
@@ -39,34 +39,25 @@ where
         unsigned_tx: &UnsignedTransaction<Transaction>,
         signatures: &[SignatureBytes],
     ) -> SigningResult<()> {
-        Self::check_signatures_number(unsigned_tx.inputs().len(), signatures.len())?;
+        Self::check_signatures_number(unsigned_tx.signatures_number(), signatures.len())?;
 
         // Compute transaction preimage and verify if all given signatures correspond to the result sighashes.
         let tx_preimage = SighashComputer::preimage_tx(unsigned_tx)?;
 
-        for ((sighash, utxo_args), signature) in tx_preimage
-            .sighashes
-            .iter()
-            .zip(unsigned_tx.input_args())
-            .zip(signatures)
-        {
-            Self::verify_signature(sighash, utxo_args, signature)?;
+        for (sighash, signature) in tx_preimage.sighashes.iter().zip(signatures) {
+            Self::verify_signature(sighash, signature)?;
         }
 
         Ok(())
     }
 
-    pub fn verify_signature(
-        sighash: &UtxoSighash,
-        utxo_args: &UtxoToSign,
-        signature: &[u8],
-    ) -> SigningResult<()> {
-        match utxo_args.signing_method {
+    pub fn verify_signature(sighash: &UtxoSighash, signature: &[u8]) -> SigningResult<()> {
+        match sighash.signing_method {
             SigningMethod::Legacy | SigningMethod::Segwit => {
-                Self::verify_ecdsa_signature(sighash, &utxo_args.spender_public_key, signature)
+                Self::verify_ecdsa_signature(sighash, &sighash.signer_pubkey, signature)
             },
             SigningMethod::Taproot => {
-                Self::verify_schnorr_signature(sighash, &utxo_args.spender_public_key, signature)
+                Self::verify_schnorr_signature(sighash, &sighash.signer_pubkey, signature)
             },
         }
     }
@@ -126,12 +117,12 @@ where
     }
 
     pub(crate) fn check_signatures_number(
-        inputs_len: usize,
+        expected_len: usize,
         signatures_len: usize,
     ) -> SigningResult<()> {
-        if inputs_len != signatures_len {
+        if expected_len != signatures_len {
             return SigningError::err(SigningErrorType::Error_signatures_count).context(format!(
-                "SighashComputer's error: there are '{inputs_len}' transaction inputs, but given '{signatures_len}' signatures",
+                "SighashComputer's error: there are '{expected_len}' signatures expected, but given '{signatures_len}' signatures",
             ));
         }
         Ok(())
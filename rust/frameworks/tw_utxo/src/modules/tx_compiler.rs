@@ -6,7 +6,8 @@ use crate::modules::sighash_verifier::SighashVerifier;
 use crate::sighash::SighashType;
 use crate::signature::{BitcoinEcdsaSignature, BitcoinSchnorrSignature, FromRawOrDerBytes};
 use crate::spending_data::{
-    EcdsaSpendingDataConstructor, SchnorrSpendingDataConstructor, SpendingData,
+    EcdsaSpendingDataConstructor, MultiEcdsaSpendingDataConstructor,
+    MultiSchnorrSpendingDataConstructor, SchnorrSpendingDataConstructor, SpendingData,
     SpendingDataConstructor,
 };
 use crate::transaction::transaction_interface::{TransactionInterface, TxInputInterface};
@@ -41,25 +42,53 @@ where
         signatures: &[SignatureBytes],
     ) -> SigningResult<Transaction> {
         SighashVerifier::<Transaction>::check_signatures_number(
-            unsigned_tx.inputs().len(),
+            unsigned_tx.signatures_number(),
             signatures.len(),
         )?;
 
+        // Signatures are given in the same order as the UTXOs and their signers.
+        let mut signatures = signatures.iter();
         let claims = unsigned_tx
             .input_args()
             .iter()
-            .zip(signatures)
-            .map(|(utxo, sign)| match utxo.spending_data_constructor {
-                SpendingDataConstructor::Ecdsa(ref ecdsa_constructor) => {
-                    Self::get_ecdsa_spending_data(ecdsa_constructor.as_ref(), sign, utxo.sighash_ty)
-                },
-                SpendingDataConstructor::Schnorr(ref schnorr_constructor) => {
-                    Self::get_schnorr_spending_data(
-                        schnorr_constructor.as_ref(),
-                        sign,
-                        utxo.sighash_ty,
-                    )
-                },
+            .map(|utxo| {
+                let utxo_signatures: Vec<_> = signatures
+                    .by_ref()
+                    .take(utxo.spender_public_keys.len())
+                    .collect();
+
+                match utxo.spending_data_constructor {
+                    SpendingDataConstructor::Ecdsa(ref ecdsa_constructor) => {
+                        let sign = Self::single_signature(&utxo_signatures)?;
+                        Self::get_ecdsa_spending_data(
+                            ecdsa_constructor.as_ref(),
+                            sign,
+                            utxo.sighash_ty,
+                        )
+                    },
+                    SpendingDataConstructor::MultiEcdsa(ref multi_ecdsa_constructor) => {
+                        Self::get_multi_ecdsa_spending_data(
+                            multi_ecdsa_constructor.as_ref(),
+                            &utxo_signatures,
+                            utxo.sighash_ty,
+                        )
+                    },
+                    SpendingDataConstructor::Schnorr(ref schnorr_constructor) => {
+                        let sign = Self::single_signature(&utxo_signatures)?;
+                        Self::get_schnorr_spending_data(
+                            schnorr_constructor.as_ref(),
+                            sign,
+                            utxo.sighash_ty,
+                        )
+                    },
+                    SpendingDataConstructor::MultiSchnorr(ref multi_schnorr_constructor) => {
+                        Self::get_multi_schnorr_spending_data(
+                            multi_schnorr_constructor.as_ref(),
+                            &utxo_signatures,
+                            utxo.sighash_ty,
+                        )
+                    },
+                }
             })
             .collect::<SigningResult<_>>()?;
 
@@ -71,15 +100,18 @@ where
         unsigned_tx: UnsignedTransaction<Transaction>,
         claims: Vec<SpendingData>,
     ) -> SigningResult<Transaction> {
-        SighashVerifier::<Transaction>::check_signatures_number(
-            unsigned_tx.inputs().len(),
-            claims.len(),
-        )?;
+        if unsigned_tx.inputs().len() != claims.len() {
+            return SigningError::err(SigningErrorType::Error_internal).context(format!(
+                "There are '{}' transaction inputs, but given '{}' spending data",
+                unsigned_tx.inputs().len(),
+                claims.len()
+            ));
+        }
 
         let mut signed_tx = unsigned_tx.into_transaction();
 
         // Add the claiming script (scriptSig or Witness) to the transaction inputs.
-        for (utxo, claim) in signed_tx.inputs_mut().iter_mut().zip(claims.into_iter()) {
+        for (utxo, claim) in signed_tx.inputs_mut().iter_mut().zip(claims) {
             if claim.script_sig.is_empty() && claim.witness.is_empty() {
                 return SigningError::err(SigningErrorType::Error_invalid_params).context(
                     "Either scriptSig or witness must be provided to compile a transaction",
@@ -93,31 +125,77 @@ where
         Ok(signed_tx)
     }
 
-    fn get_ecdsa_spending_data(
-        ecdsa_constructor: &dyn EcdsaSpendingDataConstructor,
+    fn single_signature<'a>(signatures: &[&'a SignatureBytes]) -> SigningResult<&'a [u8]> {
+        match signatures {
+            [sign] => Ok(sign.as_slice()),
+            _ => SigningError::err(SigningErrorType::Error_signatures_count)
+                .context("Expected exactly one signature for the UTXO"),
+        }
+    }
+
+    fn to_bitcoin_ecdsa_signature(
         sign: &[u8],
         sighash_ty: SighashType,
-    ) -> SigningResult<SpendingData> {
+    ) -> SigningResult<BitcoinEcdsaSignature> {
         let ecdsa_sign = ecdsa::secp256k1::VerifySignature::from_raw_or_der_bytes(sign)?;
         let der_sign = ecdsa_sign
             .to_der()
             .into_tw()
             .context("Error getting ASN.1 DER-encoded signature")?;
 
-        let signature = BitcoinEcdsaSignature::new(der_sign, sighash_ty)?;
-        Ok(ecdsa_constructor.get_spending_data(&signature))
+        BitcoinEcdsaSignature::new(der_sign, sighash_ty)
     }
 
-    fn get_schnorr_spending_data(
-        schnorr_constructor: &dyn SchnorrSpendingDataConstructor,
+    fn get_ecdsa_spending_data(
+        ecdsa_constructor: &dyn EcdsaSpendingDataConstructor,
         sign: &[u8],
         sighash_ty: SighashType,
     ) -> SigningResult<SpendingData> {
+        let signature = Self::to_bitcoin_ecdsa_signature(sign, sighash_ty)?;
+        Ok(ecdsa_constructor.get_spending_data(&signature))
+    }
+
+    fn get_multi_ecdsa_spending_data(
+        multi_ecdsa_constructor: &dyn MultiEcdsaSpendingDataConstructor,
+        signs: &[&SignatureBytes],
+        sighash_ty: SighashType,
+    ) -> SigningResult<SpendingData> {
+        let signatures = signs
+            .iter()
+            .map(|sign| Self::to_bitcoin_ecdsa_signature(sign, sighash_ty))
+            .collect::<SigningResult<Vec<_>>>()?;
+        Ok(multi_ecdsa_constructor.get_spending_data(&signatures))
+    }
+
+    fn to_bitcoin_schnorr_signature(
+        sign: &[u8],
+        sighash_ty: SighashType,
+    ) -> SigningResult<BitcoinSchnorrSignature> {
         let schnorr_sign = schnorr::Signature::try_from(sign)
             .into_tw()
             .context("Invalid schnorr signature")?;
 
-        let signature = BitcoinSchnorrSignature::new(schnorr_sign, sighash_ty)?;
+        BitcoinSchnorrSignature::new(schnorr_sign, sighash_ty)
+    }
+
+    fn get_schnorr_spending_data(
+        schnorr_constructor: &dyn SchnorrSpendingDataConstructor,
+        sign: &[u8],
+        sighash_ty: SighashType,
+    ) -> SigningResult<SpendingData> {
+        let signature = Self::to_bitcoin_schnorr_signature(sign, sighash_ty)?;
         Ok(schnorr_constructor.get_spending_data(&signature))
     }
+
+    fn get_multi_schnorr_spending_data(
+        multi_schnorr_constructor: &dyn MultiSchnorrSpendingDataConstructor,
+        signs: &[&SignatureBytes],
+        sighash_ty: SighashType,
+    ) -> SigningResult<SpendingData> {
+        let signatures = signs
+            .iter()
+            .map(|sign| Self::to_bitcoin_schnorr_signature(sign, sighash_ty))
+            .collect::<SigningResult<Vec<_>>>()?;
+        Ok(multi_schnorr_constructor.get_spending_data(&signatures))
+    }
 }
@@ -25,6 +25,9 @@ pub struct PlanRequest<Transaction: TransactionInterface> {
     pub ty: RequestType<Transaction>,
     pub dust_policy: DustPolicy,
     pub fee_per_vbyte: Amount,
+    /// The fee rate at which UTXOs are expected to be spent in the long term.
+    /// Used to compute the selection waste. Defaults to `fee_per_vbyte`.
+    pub long_term_fee_per_vbyte: Option<Amount>,
 }
 
 pub enum RequestType<Transaction: TransactionInterface> {
@@ -64,6 +67,7 @@ where
                     .context("Error filtering dust UTXOs")?;
 
                 MaxInputSelector::new(unsigned_tx)
+                    .maybe_long_term_fee_rate(request.long_term_fee_per_vbyte)
                     .select_max(request.fee_per_vbyte, request.dust_policy)
             },
             RequestType::SendExact {
@@ -77,6 +81,7 @@ where
 
                 ExactInputSelector::new(unsigned_tx)
                     .maybe_change_output(change_output)
+                    .maybe_long_term_fee_rate(request.long_term_fee_per_vbyte)
                     .select_inputs(request.dust_policy, input_selector, request.fee_per_vbyte)
             },
         }
@@ -0,0 +1,476 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+//! Waste-aware coin selection algorithms ported from Bitcoin Core:
+//! https://github.com/bitcoin/bitcoin/blob/v27.0/src/wallet/coinselection.cpp
+
+use crate::transaction::transaction_interface::TransactionInterface;
+use crate::transaction::transaction_parts::{Amount, OutPoint};
+use tw_hash::sha2::sha256;
+use tw_memory::Data;
+
+/// The maximum number of BnB iterations.
+const BNB_TOTAL_TRIES: usize = 100_000;
+/// The number of Knapsack stochastic approximation iterations.
+const KNAPSACK_ITERATIONS: usize = 1000;
+
+/// A UTXO considered by the selection algorithms.
+#[derive(Clone, Copy, Debug)]
+pub struct SelectionCandidate {
+    /// UTXO amount.
+    pub value: Amount,
+    /// The fee to spend the UTXO at the current fee rate.
+    pub fee: Amount,
+    /// The fee to spend the UTXO at the long-term fee rate.
+    pub long_term_fee: Amount,
+}
+
+impl SelectionCandidate {
+    /// The UTXO amount minus the fee to spend it.
+    pub fn effective_value(&self) -> Amount {
+        self.value - self.fee
+    }
+
+    /// Positive if spending the UTXO now is more expensive than in the long term.
+    pub fn input_waste(&self) -> Amount {
+        self.fee - self.long_term_fee
+    }
+}
+
+/// Computes the fee for the given weight at the given fee rate (per vbyte).
+pub fn fee_for_weight(weight: usize, fee_rate: Amount) -> Amount {
+    const WITNESS_SCALE_FACTOR: Amount = 4;
+    (weight as Amount * fee_rate + WITNESS_SCALE_FACTOR - 1) / WITNESS_SCALE_FACTOR
+}
+
+/// Estimates the weight of the given input (with an estimated script sig or witness)
+/// as the difference between the transaction with and without the input.
+/// `tx` is expected to have no inputs.
+pub fn input_weight<Transaction: TransactionInterface>(
+    tx: &Transaction,
+    input: &Transaction::Input,
+) -> usize {
+    let mut tx_with_input = tx.clone();
+    tx_with_input.replace_inputs(vec![input.clone()]);
+    tx_with_input.weight().saturating_sub(tx.weight())
+}
+
+/// Searches for a changeless selection which effective value is in `target..=target + cost_of_change`,
+/// and has the least waste.
+/// Returns indexes of the selected candidates.
+pub fn branch_and_bound(
+    candidates: &[SelectionCandidate],
+    target: Amount,
+    cost_of_change: Amount,
+) -> Option<Vec<usize>> {
+    // Sort the UTXOs with a positive effective value in the descending order.
+    let mut pool: Vec<usize> = (0..candidates.len())
+        .filter(|i| candidates[*i].effective_value() > 0)
+        .collect();
+    pool.sort_by(|a, b| {
+        candidates[*b]
+            .effective_value()
+            .cmp(&candidates[*a].effective_value())
+    });
+    let candidate = |pool_index: usize| &candidates[pool[pool_index]];
+
+    let mut curr_available: Amount = pool.iter().map(|i| candidates[*i].effective_value()).sum();
+    if curr_available < target {
+        return None;
+    }
+
+    let is_fee_rate_high = pool
+        .first()
+        .map(|i| candidates[*i].fee > candidates[*i].long_term_fee)
+        .unwrap_or_default();
+
+    let mut curr_value: Amount = 0;
+    let mut curr_waste: Amount = 0;
+    // Indexes of the `pool` included into the current selection.
+    let mut curr_selection: Vec<usize> = Vec::with_capacity(pool.len());
+
+    let mut best_selection: Option<Vec<usize>> = None;
+    let mut best_waste = Amount::MAX;
+
+    let mut pool_index = 0;
+    for _ in 0..BNB_TOTAL_TRIES {
+        let mut backtrack = false;
+
+        if curr_value + curr_available < target
+            || curr_value > target + cost_of_change
+            || (curr_waste > best_waste && is_fee_rate_high)
+        {
+            // Cannot reach the target with the remaining amounts, or the selection exceeds the target too much,
+            // or the selection is more wasteful than the best one.
+            backtrack = true;
+        } else if curr_value >= target {
+            // Found a solution, consider the excess as waste.
+            let waste = curr_waste + curr_value - target;
+            if waste <= best_waste {
+                best_selection = Some(curr_selection.clone());
+                best_waste = waste;
+            }
+            backtrack = true;
+        }
+
+        if backtrack {
+            let Some(&last_selected) = curr_selection.last() else {
+                // The whole tree has been searched.
+                break;
+            };
+
+            // Add omitted UTXOs back before traversing the omission branch of the last included UTXO.
+            pool_index -= 1;
+            while pool_index > last_selected {
+                curr_available += candidate(pool_index).effective_value();
+                pool_index -= 1;
+            }
+
+            // The UTXO was included on previous iterations, try excluding it now.
+            let utxo = candidate(pool_index);
+            curr_value -= utxo.effective_value();
+            curr_waste -= utxo.input_waste();
+            curr_selection.pop();
+        } else {
+            let utxo = candidate(pool_index);
+            curr_available -= utxo.effective_value();
+
+            // Avoid searching a branch if the previous UTXO has the same value and the same waste, and was excluded.
+            let prev_excluded_equivalent = pool_index > 0
+                && curr_selection.last() != Some(&(pool_index - 1))
+                && candidate(pool_index - 1).effective_value() == utxo.effective_value()
+                && candidate(pool_index - 1).fee == utxo.fee;
+            if !prev_excluded_equivalent {
+                curr_selection.push(pool_index);
+                curr_value += utxo.effective_value();
+                curr_waste += utxo.input_waste();
+            }
+        }
+
+        pool_index += 1;
+    }
+
+    best_selection.map(|selection| selection.into_iter().map(|i| pool[i]).collect())
+}
+
+/// Selects the candidates with the Bitcoin Core's Knapsack solver.
+/// Tries to find either a selection equal to `target`, or at least `target + min_change`.
+/// Returns indexes of the selected candidates.
+pub fn knapsack(
+    candidates: &[SelectionCandidate],
+    target: Amount,
+    min_change: Amount,
+    rng: &mut SelectionRng,
+) -> Option<Vec<usize>> {
+    let mut pool: Vec<usize> = (0..candidates.len())
+        .filter(|i| candidates[*i].effective_value() > 0)
+        .collect();
+    rng.shuffle(&mut pool);
+    let value = |i: usize| candidates[i].effective_value();
+
+    let mut applicable = Vec::with_capacity(pool.len());
+    let mut total_lower: Amount = 0;
+    let mut lowest_larger: Option<usize> = None;
+
+    for i in pool {
+        if value(i) == target {
+            return Some(vec![i]);
+        }
+        if value(i) < target + min_change {
+            applicable.push(i);
+            total_lower += value(i);
+        } else if lowest_larger.is_none_or(|lowest| value(i) < value(lowest)) {
+            lowest_larger = Some(i);
+        }
+    }
+
+    if total_lower == target {
+        return Some(applicable);
+    }
+    if total_lower < target {
+        return lowest_larger.map(|i| vec![i]);
+    }
+
+    // Solve subset sum by stochastic approximation.
+    applicable.sort_by_key(|i| std::cmp::Reverse(value(*i)));
+    let values: Vec<Amount> = applicable.iter().map(|i| value(*i)).collect();
+
+    let (mut best, mut best_value) = approximate_best_subset(&values, total_lower, target, rng);
+    if best_value != target && total_lower >= target + min_change {
+        (best, best_value) =
+            approximate_best_subset(&values, total_lower, target + min_change, rng);
+    }
+
+    // If the next larger UTXO is closer, return it.
+    if let Some(lowest) = lowest_larger {
+        let best_is_not_enough = best_value != target && best_value < target + min_change;
+        if best_is_not_enough || value(lowest) <= best_value {
+            return Some(vec![lowest]);
+        }
+    }
+
+    let selected = applicable
+        .into_iter()
+        .zip(best)
+        .filter_map(|(i, included)| included.then_some(i))
+        .collect();
+    Some(selected)
+}
+
+/// Selects random candidates until their effective value reaches `target`.
+/// Returns indexes of the selected candidates.
+pub fn single_random_draw(
+    candidates: &[SelectionCandidate],
+    target: Amount,
+    rng: &mut SelectionRng,
+) -> Option<Vec<usize>> {
+    let mut pool: Vec<usize> = (0..candidates.len())
+        .filter(|i| candidates[*i].effective_value() > 0)
+        .collect();
+    rng.shuffle(&mut pool);
+
+    let mut selected = Vec::with_capacity(pool.len());
+    let mut selected_value: Amount = 0;
+    for i in pool {
+        selected.push(i);
+        selected_value += candidates[i].effective_value();
+        if selected_value >= target {
+            return Some(selected);
+        }
+    }
+    None
+}
+
+fn approximate_best_subset(
+    values: &[Amount],
+    total_lower: Amount,
+    target: Amount,
+    rng: &mut SelectionRng,
+) -> (Vec<bool>, Amount) {
+    let mut best = vec![true; values.len()];
+    let mut best_value = total_lower;
+
+    for _ in 0..KNAPSACK_ITERATIONS {
+        if best_value == target {
+            break;
+        }
+
+        let mut included = vec![false; values.len()];
+        let mut total: Amount = 0;
+        let mut reached_target = false;
+
+        for pass in 0..2 {
+            if reached_target {
+                break;
+            }
+            for (i, value) in values.iter().enumerate() {
+                // The first pass includes random UTXOs, the second pass includes all the remaining.
+                let include = if pass == 0 {
+                    rng.gen_bool()
+                } else {
+                    !included[i]
+                };
+                if !include {
+                    continue;
+                }
+
+                total += value;
+                included[i] = true;
+                if total >= target {
+                    reached_target = true;
+                    if total < best_value {
+                        best_value = total;
+                        best.clone_from(&included);
+                    }
+                    total -= value;
+                    included[i] = false;
+                }
+            }
+        }
+    }
+
+    (best, best_value)
+}
+
+/// A deterministic random generator used by the randomized selection algorithms.
+/// It's seeded with the UTXO outpoints, so the same UTXOs are selected
+/// on every call with the same input (e.g. at the `preimage_hashes` and `compile` steps).
+pub struct SelectionRng {
+    seed: Data,
+    counter: u64,
+}
+
+impl SelectionRng {
+    pub fn from_outpoints<'a, I>(outpoints: I) -> Self
+    where
+        I: IntoIterator<Item = &'a OutPoint>,
+    {
+        let mut preimage = Data::new();
+        for outpoint in outpoints {
+            preimage.extend_from_slice(outpoint.hash.as_slice());
+            preimage.extend_from_slice(&outpoint.index.to_le_bytes());
+        }
+        SelectionRng {
+            seed: sha256(&preimage),
+            counter: 0,
+        }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        let mut preimage = self.seed.clone();
+        preimage.extend_from_slice(&self.counter.to_le_bytes());
+        self.counter += 1;
+
+        let hash = sha256(&preimage);
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(&hash[..8]);
+        u64::from_le_bytes(bytes)
+    }
+
+    pub fn gen_bool(&mut self) -> bool {
+        self.next_u64() & 1 == 1
+    }
+
+    /// Fisher-Yates shuffle.
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = (self.next_u64() % (i as u64 + 1)) as usize;
+            items.swap(i, j);
+        }
+    }
+}
+
+/// Computes the waste metric of a selection:
+/// `sum(input.fee - input.long_term_fee) + (cost_of_change if a change output is created, otherwise excess)`.
+pub fn selection_waste<'a, I>(selected: I, change_cost_or_excess: Amount) -> Amount
+where
+    I: IntoIterator<Item = &'a SelectionCandidate>,
+{
+    selected
+        .into_iter()
+        .map(SelectionCandidate::input_waste)
+        .sum::<Amount>()
+        + change_cost_or_excess
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FEE: Amount = 68;
+    const LONG_TERM_FEE: Amount = 34;
+
+    fn candidates(values: &[Amount]) -> Vec<SelectionCandidate> {
+        values
+            .iter()
+            .map(|value| SelectionCandidate {
+                value: value + FEE,
+                fee: FEE,
+                long_term_fee: LONG_TERM_FEE,
+            })
+            .collect()
+    }
+
+    fn selected_values(candidates: &[SelectionCandidate], selected: &[usize]) -> Vec<Amount> {
+        let mut values: Vec<_> = selected
+            .iter()
+            .map(|i| candidates[*i].effective_value())
+            .collect();
+        values.sort();
+        values
+    }
+
+    fn rng() -> SelectionRng {
+        SelectionRng::from_outpoints(&[OutPoint::default()])
+    }
+
+    #[test]
+    fn test_branch_and_bound_exact_match() {
+        let pool = candidates(&[1_000, 2_000, 3_000, 4_000, 8_000]);
+
+        let selected = branch_and_bound(&pool, 7_000, 0).unwrap();
+        assert_eq!(selected_values(&pool, &selected), vec![3_000, 4_000]);
+
+        let selected = branch_and_bound(&pool, 18_000, 0).unwrap();
+        assert_eq!(
+            selected_values(&pool, &selected),
+            vec![1_000, 2_000, 3_000, 4_000, 8_000]
+        );
+    }
+
+    #[test]
+    fn test_branch_and_bound_cost_of_change() {
+        let pool = candidates(&[1_000, 2_000, 4_000]);
+
+        // No exact match.
+        assert_eq!(branch_and_bound(&pool, 4_500, 0), None);
+        // `5_000` is within `4_500..=4_500 + 500`.
+        let selected = branch_and_bound(&pool, 4_500, 500).unwrap();
+        assert_eq!(selected_values(&pool, &selected), vec![1_000, 4_000]);
+        // Not enough funds.
+        assert_eq!(branch_and_bound(&pool, 7_001, 1_000), None);
+    }
+
+    #[test]
+    fn test_branch_and_bound_prefers_less_inputs_at_high_fee_rate() {
+        let pool = candidates(&[1_000, 1_000, 1_000, 3_000]);
+
+        let selected = branch_and_bound(&pool, 3_000, 0).unwrap();
+        assert_eq!(selected_values(&pool, &selected), vec![3_000]);
+    }
+
+    #[test]
+    fn test_knapsack() {
+        let pool = candidates(&[1_000, 2_000, 5_000, 20_000]);
+
+        // Exact match.
+        let selected = knapsack(&pool, 5_000, 1_000, &mut rng()).unwrap();
+        assert_eq!(selected_values(&pool, &selected), vec![5_000]);
+
+        // The sum of lower UTXOs is an exact match.
+        let selected = knapsack(&pool, 8_000, 1_000, &mut rng()).unwrap();
+        assert_eq!(selected_values(&pool, &selected), vec![1_000, 2_000, 5_000]);
+
+        // The sum of lower UTXOs is not enough, use the lowest larger.
+        let selected = knapsack(&pool, 9_000, 1_000, &mut rng()).unwrap();
+        assert_eq!(selected_values(&pool, &selected), vec![20_000]);
+
+        // Subset with enough change.
+        let selected = knapsack(&pool, 5_500, 1_000, &mut rng()).unwrap();
+        assert_eq!(selected_values(&pool, &selected), vec![2_000, 5_000]);
+
+        // Not enough funds.
+        assert_eq!(knapsack(&pool, 30_000, 1_000, &mut rng()), None);
+    }
+
+    #[test]
+    fn test_single_random_draw() {
+        let pool = candidates(&[1_000, 2_000, 5_000, 20_000]);
+
+        let selected = single_random_draw(&pool, 6_000, &mut rng()).unwrap();
+        let total: Amount = selected_values(&pool, &selected).iter().sum();
+        assert!(total >= 6_000);
+
+        // The same seed leads to the same selection.
+        assert_eq!(
+            single_random_draw(&pool, 6_000, &mut rng()).unwrap(),
+            selected
+        );
+
+        assert_eq!(single_random_draw(&pool, 28_001, &mut rng()), None);
+    }
+
+    #[test]
+    fn test_selection_waste() {
+        let pool = candidates(&[1_000, 2_000]);
+        assert_eq!(selection_waste(&pool, 100), 2 * (FEE - LONG_TERM_FEE) + 100);
+    }
+
+    #[test]
+    fn test_fee_for_weight() {
+        assert_eq!(fee_for_weight(272, 10), 680);
+        assert_eq!(fee_for_weight(273, 10), 683);
+        assert_eq!(fee_for_weight(0, 10), 0);
+    }
+}
@@ -5,6 +5,9 @@
 use crate::constants::MAX_TRANSACTION_WEIGHT;
 use crate::dust::DustPolicy;
 use crate::modules::fee_estimator::FeeEstimator;
+use crate::modules::utxo_selector::coin_selection::{
+    self, fee_for_weight, input_weight, SelectionCandidate, SelectionRng,
+};
 use crate::modules::utxo_selector::{InputSelector, SelectPlan, SelectResult};
 use crate::script::{Script, Witness};
 use crate::transaction::transaction_interface::{
@@ -13,12 +16,45 @@ use crate::transaction::transaction_interface::{
 use crate::transaction::transaction_parts::Amount;
 use crate::transaction::unsigned_transaction::UnsignedTransaction;
 use crate::transaction::UtxoToSign;
+use std::cmp::Reverse;
 use tw_coin_entry::error::prelude::*;
 
 /// UTXO selector used to send the exact amounts specified in outputs.
 pub struct ExactInputSelector<Transaction: TransactionInterface> {
     unsigned_tx: UnsignedTransaction<Transaction>,
     change_output: Option<Transaction::Output>,
+    long_term_fee_rate: Option<Amount>,
+}
+
+/// A candidate selection of UTXOs.
+struct Selection {
+    /// Indexes of the selected UTXOs.
+    utxos: Vec<usize>,
+    total_in: Amount,
+    vsize: usize,
+    /// Whether the change output should be added.
+    has_change: bool,
+    /// Change amount. Zero if the change output should not be added.
+    change: Amount,
+    waste: Amount,
+}
+
+/// Parameters shared by all UTXO selection strategies.
+struct SelectionContext<Transaction: TransactionInterface> {
+    /// Estimated transaction without inputs.
+    /// Contains the change output if specified.
+    tx: Transaction,
+    /// Available UTXOs with estimated script sigs.
+    utxos: Vec<(Transaction::Input, UtxoToSign)>,
+    candidates: Vec<SelectionCandidate>,
+    total_out: Amount,
+    fee_rate: Amount,
+    dust_threshold: Amount,
+    /// The fee to add the change output at the current fee rate.
+    /// `None` if the change output is not specified.
+    change_fee: Option<Amount>,
+    /// The fee to spend the change output at the long-term fee rate.
+    change_spend_fee: Amount,
 }
 
 impl<Transaction> ExactInputSelector<Transaction>
@@ -29,6 +65,7 @@ where
         ExactInputSelector {
             unsigned_tx,
             change_output: None,
+            long_term_fee_rate: None,
         }
     }
 
@@ -37,6 +74,13 @@ where
         self
     }
 
+    /// Sets the fee rate at which the UTXOs are expected to be spent in the long term.
+    /// It is used to compute the waste metric. The current fee rate is used if not specified.
+    pub fn maybe_long_term_fee_rate(mut self, fee_rate: Option<Amount>) -> Self {
+        self.long_term_fee_rate = fee_rate;
+        self
+    }
+
     pub fn select_inputs(
         mut self,
         dust_policy: DustPolicy,
@@ -45,8 +89,15 @@ where
     ) -> SigningResult<SelectResult<Transaction>> {
         let mut estimated_tx = self.unsigned_tx.estimate_transaction();
 
-        // Calculate the total output amount.
-        let mut total_out = self.unsigned_tx.total_output()?;
+        // Prepare the available UTXOs.
+        // Please note all the Transaction inputs contain estimated script sigs.
+        let utxos: Vec<(Transaction::Input, UtxoToSign)> = estimated_tx
+            .inputs()
+            .iter()
+            .cloned()
+            .zip(self.unsigned_tx.input_args().iter().cloned())
+            .collect();
+        estimated_tx.replace_inputs(Vec::new());
 
         if let Some(ref change_output) = self.change_output {
             // Push the change output to the temporary "estimated" transaction only to get the most max possible fee.
@@ -55,35 +106,138 @@ where
             estimated_tx.push_output(change_output.clone());
         }
 
-        // Prepare the available UTXOs.
-        // Please note all the Transaction inputs contain estimated script sigs.
-        let mut utxos: Vec<(Transaction::Input, &UtxoToSign)> = estimated_tx
-            .inputs()
+        let change_fee = match self.change_output {
+            Some(_) => {
+                let mut tx_without_change = estimated_tx.clone();
+                tx_without_change.replace_outputs(self.unsigned_tx.outputs().to_vec());
+                Some(
+                    FeeEstimator::estimate_fee(&estimated_tx, fee_rate)?
+                        - FeeEstimator::estimate_fee(&tx_without_change, fee_rate)?,
+                )
+            },
+            None => None,
+        };
+
+        let long_term_fee_rate = self.long_term_fee_rate.unwrap_or(fee_rate);
+        let candidates: Vec<_> = utxos
             .iter()
-            .cloned()
-            .zip(self.unsigned_tx.input_args())
+            .map(|(input, arg)| {
+                let weight = input_weight(&estimated_tx, input);
+                SelectionCandidate {
+                    value: arg.amount,
+                    fee: fee_for_weight(weight, fee_rate),
+                    long_term_fee: fee_for_weight(weight, long_term_fee_rate),
+                }
+            })
             .collect();
 
+        // The change is expected to be sent back to the sender, so it's estimated to be spent
+        // as the first of the given UTXOs.
+        let change_spend_fee = candidates
+            .first()
+            .map(|utxo| utxo.long_term_fee)
+            .unwrap_or_default();
+
+        // Dust threshold of the change output.
+        // If the change output is not specified, it's used to limit the excess of a changeless selection.
+        let dust_threshold = self
+            .change_output
+            .as_ref()
+            .or_else(|| self.unsigned_tx.outputs().first())
+            .map(|output| dust_policy.dust_threshold(output.script_pubkey()))
+            .unwrap_or_default();
+
+        let ctx = SelectionContext {
+            tx: estimated_tx,
+            utxos,
+            candidates,
+            // Calculate the total output amount.
+            total_out: self.unsigned_tx.total_output()?,
+            fee_rate,
+            dust_threshold,
+            change_fee,
+            change_spend_fee,
+        };
+
+        let selection = match selector {
+            InputSelector::UseAll
+            | InputSelector::Ascending
+            | InputSelector::Descending
+            | InputSelector::InOrder => self.select_sequentially(&ctx, selector)?,
+            InputSelector::BranchAndBound
+            | InputSelector::Knapsack
+            | InputSelector::SingleRandomDraw
+            | InputSelector::MinimizeWaste => self.select_least_waste(&ctx, selector)?,
+        };
+
+        let mut total_out = ctx.total_out;
+        if let Some(mut change_output) = self.change_output.take() {
+            if selection.has_change {
+                change_output.set_value(selection.change);
+                self.unsigned_tx.push_output(change_output);
+                total_out += selection.change;
+            }
+        }
+
+        let (mut selected_utxos, selected_utxo_args): (Vec<_>, Vec<_>) = selection
+            .utxos
+            .iter()
+            .map(|i| ctx.utxos[*i].clone())
+            .unzip();
+
+        // Clear script_sig's and witnesses before updating the result transaction.
+        for utxo in selected_utxos.iter_mut() {
+            utxo.set_script_sig(Script::default());
+            utxo.set_witness(Witness::default());
+        }
+
+        self.unsigned_tx
+            .set_inputs(selected_utxos, selected_utxo_args)?;
+
+        debug_assert!(total_out <= selection.total_in);
+        let fee_estimate = selection.total_in - total_out;
+
+        let plan = SelectPlan {
+            total_spend: selection.total_in,
+            total_send: total_out,
+            vsize_estimate: selection.vsize,
+            fee_estimate,
+            change: selection.change,
+            waste: selection.waste,
+        };
+        Ok(SelectResult {
+            unsigned_tx: self.unsigned_tx,
+            plan,
+        })
+    }
+
+    /// Selects UTXOs one by one in the order specified by the selector until the outputs and the fee are covered.
+    fn select_sequentially(
+        &self,
+        ctx: &SelectionContext<Transaction>,
+        selector: InputSelector,
+    ) -> SigningResult<Selection> {
+        let mut order: Vec<usize> = (0..ctx.utxos.len()).collect();
+
         // Sort the UTXOs.
         match selector {
+            InputSelector::Ascending => order.sort_by_key(|i| ctx.utxos[*i].1.amount),
+            InputSelector::Descending => order.sort_by_key(|i| Reverse(ctx.utxos[*i].1.amount)),
             // Nothing to do.
-            InputSelector::UseAll | InputSelector::InOrder => (),
-            InputSelector::Ascending => {
-                utxos.sort_by(|(_, a), (_, b)| a.amount.cmp(&b.amount));
-            },
-            InputSelector::Descending => {
-                utxos.sort_by(|(_, a), (_, b)| b.amount.cmp(&a.amount));
-            },
+            _ => (),
         }
 
         // Select the UTXOs to cover all the outputs and the fee.
+        let mut estimated_tx = ctx.tx.clone();
         let mut total_in = 0;
         let mut tx_fee = 0;
-        let mut selected_utxos = Vec::with_capacity(utxos.len());
-        let mut selected_utxo_args = Vec::with_capacity(utxos.len());
+        let mut selected = Vec::with_capacity(order.len());
+        let mut selected_utxos = Vec::with_capacity(order.len());
 
         let mut total_covered = false;
-        for (input, arg) in utxos {
+        for i in order {
+            let (input, arg) = &ctx.utxos[i];
+
             // Estimated script_sig or witness MUST be set.
             debug_assert!(input.has_witness() || input.has_script_sig());
 
@@ -91,8 +245,8 @@ where
             total_in += arg.amount;
 
             // Track the selected UTXOs.
+            selected.push(i);
             selected_utxos.push(input.clone());
-            selected_utxo_args.push(arg.clone());
 
             // Update the transaction with (all) the selected in UTXOs.
             estimated_tx.replace_inputs(selected_utxos.clone());
@@ -104,11 +258,11 @@ where
             }
 
             // Estimate the transaction fee.
-            tx_fee = FeeEstimator::estimate_fee(&estimated_tx, fee_rate)?;
+            tx_fee = FeeEstimator::estimate_fee(&estimated_tx, ctx.fee_rate)?;
 
             // Check if the total input amount covers the total output amount
             // and the fee.
-            if total_in >= total_out + tx_fee {
+            if total_in >= ctx.total_out + tx_fee {
                 total_covered = true;
 
                 // Unless we're told to use all inputs, we can stop here.
@@ -120,52 +274,146 @@ where
 
         if !total_covered {
             // Insufficient funds.
+            let total_out = ctx.total_out;
             return SigningError::err(SigningErrorType::Error_not_enough_utxos)
                 .context(format!("Insufficient funds to generate a transaction. Available '{total_in}', required '{total_out}' + fee '{tx_fee}'"));
         }
 
-        // Calculate the change amount.
-        debug_assert!(
-            total_in >= total_out + tx_fee,
-            "All amounts must be checked already"
+        self.evaluate(ctx, selected, true)?
+            .or_tw_err(SigningErrorType::Error_internal)
+            .context("All amounts must be checked already")
+    }
+
+    /// Selects UTXOs with one of the waste-aware algorithms.
+    fn select_least_waste(
+        &self,
+        ctx: &SelectionContext<Transaction>,
+        selector: InputSelector,
+    ) -> SigningResult<Selection> {
+        // The effective value of the selected UTXOs must cover the outputs
+        // and the fee of the transaction without inputs and the change output.
+        let mut base_tx = ctx.tx.clone();
+        base_tx.replace_outputs(self.unsigned_tx.outputs().to_vec());
+        let target = ctx.total_out + FeeEstimator::estimate_fee(&base_tx, ctx.fee_rate)?;
+
+        // The cost of creating a change output now, and spending it later.
+        // If the change output is not specified, allow the excess up to the dust threshold.
+        let cost_of_change = match ctx.change_fee {
+            Some(change_fee) => change_fee + ctx.change_spend_fee,
+            None => ctx.dust_threshold,
+        };
+        // If the change output is specified, the selection should also cover its fee and a non-dust change amount.
+        let (target_with_change, min_change) = match ctx.change_fee {
+            Some(change_fee) => (target + change_fee, ctx.dust_threshold),
+            None => (target, 0),
+        };
+
+        let mut rng = SelectionRng::from_outpoints(
+            ctx.utxos.iter().map(|(input, _)| input.previous_output()),
         );
-        let mut change = total_in - total_out - tx_fee;
 
-        let dust_threshold = dust_policy.dust_threshold();
-        match self.change_output {
-            Some(mut change_output) if change >= dust_threshold => {
-                change_output.set_value(change);
-                self.unsigned_tx.push_output(change_output);
-                total_out += change;
-            },
-            // Either not specified or the change amount is dust.
-            _ => change = 0,
+        let bnb = || -> SigningResult<Option<Selection>> {
+            match coin_selection::branch_and_bound(&ctx.candidates, target, cost_of_change) {
+                Some(mut selected) => {
+                    // Keep the UTXOs in the given order.
+                    selected.sort();
+                    self.evaluate(ctx, selected, false)
+                },
+                None => Ok(None),
+            }
+        };
+        let knapsack = |rng: &mut SelectionRng| -> SigningResult<Option<Selection>> {
+            match coin_selection::knapsack(&ctx.candidates, target_with_change, min_change, rng) {
+                Some(mut selected) => {
+                    selected.sort();
+                    self.evaluate(ctx, selected, true)
+                },
+                None => Ok(None),
+            }
+        };
+        let srd = |rng: &mut SelectionRng| -> SigningResult<Option<Selection>> {
+            let srd_target = target_with_change + min_change;
+            match coin_selection::single_random_draw(&ctx.candidates, srd_target, rng) {
+                Some(mut selected) => {
+                    selected.sort();
+                    self.evaluate(ctx, selected, true)
+                },
+                None => Ok(None),
+            }
+        };
+
+        let selection = match selector {
+            InputSelector::BranchAndBound => bnb()?,
+            InputSelector::Knapsack => knapsack(&mut rng)?,
+            InputSelector::SingleRandomDraw => srd(&mut rng)?,
+            _ => [bnb()?, knapsack(&mut rng)?, srd(&mut rng)?]
+                .into_iter()
+                .flatten()
+                .enumerate()
+                // Prefer the first strategy if the waste is the same.
+                .min_by_key(|(i, selection)| (selection.waste, *i))
+                .map(|(_, selection)| selection),
+        };
+
+        selection
+            .or_tw_err(SigningErrorType::Error_not_enough_utxos)
+            .with_context(|| {
+                format!(
+                    "Insufficient funds to generate a transaction with the {selector:?} selector. Required '{}' + fee",
+                    ctx.total_out
+                )
+            })
+    }
+
+    /// Computes the fee, change and waste of the transaction with the given UTXOs.
+    /// Returns `None` if the UTXOs do not cover the outputs and the fee, or the transaction is too big.
+    fn evaluate(
+        &self,
+        ctx: &SelectionContext<Transaction>,
+        selected: Vec<usize>,
+        allow_change: bool,
+    ) -> SigningResult<Option<Selection>> {
+        let mut estimated_tx = ctx.tx.clone();
+        if !allow_change && self.change_output.is_some() {
+            // Remove the change output.
+            estimated_tx.replace_outputs(self.unsigned_tx.outputs().to_vec());
         }
+        estimated_tx.replace_inputs(selected.iter().map(|i| ctx.utxos[*i].0.clone()).collect());
 
-        // Clear script_sig's and witnesses before updating the result transaction.
-        for utxo in selected_utxos.iter_mut() {
-            utxo.set_script_sig(Script::default());
-            utxo.set_witness(Witness::default());
+        if estimated_tx.weight() > MAX_TRANSACTION_WEIGHT {
+            return Ok(None);
         }
 
-        self.unsigned_tx
-            .set_inputs(selected_utxos, selected_utxo_args)?;
+        let tx_fee = FeeEstimator::estimate_fee(&estimated_tx, ctx.fee_rate)?;
+        let total_in: Amount = selected.iter().map(|i| ctx.utxos[*i].1.amount).sum();
+        if total_in < ctx.total_out + tx_fee {
+            return Ok(None);
+        }
 
-        debug_assert!(total_out <= total_in);
-        let fee_estimate = total_in - total_out;
+        // The amount left after paying the outputs and the fee.
+        let excess = total_in - ctx.total_out - tx_fee;
+        let apply_change =
+            allow_change && self.change_output.is_some() && excess >= ctx.dust_threshold;
 
-        let plan = SelectPlan {
-            total_spend: total_in,
-            total_send: total_out,
+        let (change, change_cost_or_excess) = match ctx.change_fee {
+            Some(change_fee) if apply_change => (excess, change_fee + ctx.change_spend_fee),
+            // Either not specified or the change amount is dust.
+            _ => (0, excess),
+        };
+
+        let waste = coin_selection::selection_waste(
+            selected.iter().map(|i| &ctx.candidates[*i]),
+            change_cost_or_excess,
+        );
+        Ok(Some(Selection {
+            utxos: selected,
+            total_in,
+            has_change: apply_change,
             // vsize may also include the change output even if it was not included due to the dust amount.
             // This was made for simplicity. Consider fixing this later.
-            vsize_estimate: estimated_tx.vsize(),
-            fee_estimate,
+            vsize: estimated_tx.vsize(),
             change,
-        };
-        Ok(SelectResult {
-            unsigned_tx: self.unsigned_tx,
-            plan,
-        })
+            waste,
+        }))
     }
 }
@@ -5,6 +5,9 @@
 use crate::constants::MAX_TRANSACTION_WEIGHT;
 use crate::dust::DustPolicy;
 use crate::modules::fee_estimator::FeeEstimator;
+use crate::modules::utxo_selector::coin_selection::{
+    fee_for_weight, input_weight, selection_waste, SelectionCandidate,
+};
 use crate::modules::utxo_selector::{SelectPlan, SelectResult};
 use crate::script::{Script, Witness};
 use crate::transaction::transaction_interface::{
@@ -18,6 +21,7 @@ use tw_coin_entry::error::prelude::*;
 
 pub struct MaxInputSelector<Transaction> {
     unsigned_tx: UnsignedTransaction<Transaction>,
+    long_term_fee_rate: Option<Amount>,
 }
 
 impl<Transaction> MaxInputSelector<Transaction>
@@ -25,7 +29,17 @@ where
     Transaction: TransactionInterface,
 {
     pub fn new(unsigned_tx: UnsignedTransaction<Transaction>) -> Self {
-        MaxInputSelector { unsigned_tx }
+        MaxInputSelector {
+            unsigned_tx,
+            long_term_fee_rate: None,
+        }
+    }
+
+    /// Sets the fee rate at which the UTXOs are expected to be spent in the long term.
+    /// It is used to compute the waste metric. The current fee rate is used if not specified.
+    pub fn maybe_long_term_fee_rate(mut self, fee_rate: Option<Amount>) -> Self {
+        self.long_term_fee_rate = fee_rate;
+        self
     }
 
     pub fn select_max(
@@ -78,6 +92,24 @@ where
         // At this moment we have either a transaction with a normal weight, or an empty list of UTXOs.
         // If the list of UTXOs is empty, [`UnsignedTransaction::set_inputs`] will return an error.
 
+        // There is no change or excess when sending max amount,
+        // so the waste consists of the input fees only.
+        let mut tx_without_inputs = estimated_tx.clone();
+        tx_without_inputs.replace_inputs(Vec::new());
+        let long_term_fee_rate = self.long_term_fee_rate.unwrap_or(fee_rate);
+        let candidates: Vec<_> = selected_utxos
+            .iter()
+            .map(|input| {
+                let weight = input_weight(&tx_without_inputs, input);
+                SelectionCandidate {
+                    value: 0,
+                    fee: fee_for_weight(weight, fee_rate),
+                    long_term_fee: fee_for_weight(weight, long_term_fee_rate),
+                }
+            })
+            .collect();
+        let waste = selection_waste(&candidates, 0);
+
         // Clear script_sig's and witnesses before updating the result transaction.
         for utxo in selected_utxos.iter_mut() {
             utxo.set_script_sig(Script::default());
@@ -89,7 +121,13 @@ where
             .set_inputs(selected_utxos, selected_utxo_args)?;
 
         let tx_fee = FeeEstimator::estimate_fee(&estimated_tx, fee_rate)?;
-        let dust_threshold = dust_policy.dust_threshold();
+        let dust_threshold = dust_policy.dust_threshold(
+            self.unsigned_tx
+                .outputs()
+                .first()
+                .expect("Transaction output length must be checked already")
+                .script_pubkey(),
+        );
 
         // Check if the total input amount covers the fee, and the remaining amount is not dust.
         if total_in < tx_fee + dust_threshold {
@@ -115,6 +153,7 @@ where
             vsize_estimate: estimated_tx.vsize(),
             fee_estimate: tx_fee,
             change: 0,
+            waste,
         };
         Ok(SelectResult {
             unsigned_tx: self.unsigned_tx,
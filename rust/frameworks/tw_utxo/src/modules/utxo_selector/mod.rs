@@ -5,6 +5,7 @@
 use crate::transaction::transaction_parts::Amount;
 use crate::transaction::unsigned_transaction::UnsignedTransaction;
 
+pub mod coin_selection;
 pub mod exact_selector;
 pub mod max_selector;
 
@@ -22,6 +23,15 @@ pub enum InputSelector {
     // Automatically select enough inputs in the given order to cover the
     // outputs of the transaction.
     InOrder,
+    // Search for a changeless selection with the least waste (Bitcoin Core's Branch-and-Bound).
+    BranchAndBound,
+    // Select inputs with Bitcoin Core's Knapsack solver.
+    Knapsack,
+    // Select random inputs until the outputs of the transaction are covered.
+    SingleRandomDraw,
+    // Try `BranchAndBound`, `Knapsack` and `SingleRandomDraw`,
+    // and choose the selection with the least waste.
+    MinimizeWaste,
 }
 
 pub struct SelectPlan {
@@ -40,6 +50,10 @@ pub struct SelectPlan {
     // Remaining change.
     // Zero if not applied.
     pub change: Amount,
+    /// The waste metric of the selection:
+    /// `sum(input_fee - input_long_term_fee) + (change_cost if change is applied, otherwise excess)`.
+    /// Can be used to compare selections made with different [`InputSelector`] strategies.
+    pub waste: Amount,
 }
 
 pub struct SelectResult<Transaction> {
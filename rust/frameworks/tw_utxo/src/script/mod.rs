@@ -2,10 +2,13 @@
 //
 // Copyright © 2017 Trust Wallet.
 
+use crate::decode::reader::Reader;
+use crate::decode::Decodable;
 use crate::encode::compact_integer::CompactInteger;
 use crate::encode::stream::Stream;
 use crate::encode::Encodable;
 use standard_script::opcodes::*;
+use tw_coin_entry::error::prelude::*;
 use tw_memory::Data;
 
 pub mod standard_script;
@@ -28,6 +31,12 @@ impl Encodable for Script {
     }
 }
 
+impl Decodable for Script {
+    fn decode(reader: &mut Reader) -> SigningResult<Self> {
+        reader.read().map(|bytes: Data| Script { bytes })
+    }
+}
+
 impl Script {
     pub fn new() -> Self {
         Self::default()
@@ -125,6 +134,12 @@ impl Encodable for Witness {
     }
 }
 
+impl Decodable for Witness {
+    fn decode(reader: &mut Reader) -> SigningResult<Self> {
+        reader.read_list().map(|items| Witness { items })
+    }
+}
+
 impl Witness {
     pub fn new() -> Self {
         Self::default()
@@ -16,21 +16,19 @@ pub fn new_p2pk(sig: &BitcoinEcdsaSignature) -> Script {
     s
 }
 
-// TODO next iteration.
-// /// Creates script items to claim a P2SH spending condition.
-// /// (_witness_).
-// ///
-// /// ```txt
-// /// <push><item_1><push><item_2><push><redeem_script>
-// /// ```
-// pub fn new_p2sh(items: Vec<Script>, redeem_script: Script) -> Script {
-//     let mut s = Script::new();
-//     for item in items {
-//         s.push_slice(item.as_slice());
-//     }
-//     s.push_slice(redeem_script.as_slice());
-//     s
-// }
+/// Creates a script to claim a P2SH spending condition (_scriptSig_).
+///
+/// ```txt
+/// <push><item_1><push><item_2><push><redeem_script>
+/// ```
+pub fn new_p2sh(items: Vec<Script>, redeem_script: Script) -> Script {
+    let mut s = Script::new();
+    for item in items {
+        s.push_slice(item.as_slice());
+    }
+    s.push_slice(redeem_script.as_slice());
+    s
+}
 
 /// Creates a script to claim a P2PKH spending condition (_scriptSig_).
 /// See [`tw_keypair::ecdsa::der::Signature::der_bytes`].
@@ -45,24 +43,37 @@ pub fn new_p2pkh(sig: &BitcoinEcdsaSignature, pubkey: &H264) -> Script {
     s
 }
 
-// TODO next iteration.
-// /// Creates witness script items to claim a P2WSH spending condition
-// /// (_witness_).
-// ///
-// /// ```txt
-// /// <witness_item_1>
-// /// <witness_item_2>
-// /// ...
-// /// <redeem_script>
-// /// ```
-// pub fn new_p2wsh(items: Vec<Script>, redeem_script: Script) -> Witness {
-//     let mut w = Witness::new();
-//     for item in items {
-//         w.push_item(item.clone());
-//     }
-//     w.push_item(redeem_script);
-//     w
-// }
+/// Creates witness script items to claim a P2WSH spending condition
+/// (_witness_).
+///
+/// ```txt
+/// <witness_item_1>
+/// <witness_item_2>
+/// ...
+/// <witness_script>
+/// ```
+pub fn new_p2wsh(items: Vec<Script>, witness_script: Script) -> Witness {
+    let mut w = Witness::new();
+    for item in items {
+        w.push_item(item);
+    }
+    w.push_item(witness_script);
+    w
+}
+
+/// Creates script items to satisfy an `m-of-n` multisig redeem script.
+/// Signatures must be in the same order as the corresponding public keys in the redeem script.
+///
+/// ```txt
+/// OP_0 <sig_1> ... <sig_m>
+/// ```
+///
+/// The leading empty item is required due to the `OP_CHECKMULTISIG` off-by-one bug.
+pub fn new_multisig_items(sigs: &[BitcoinEcdsaSignature]) -> Vec<Script> {
+    std::iter::once(Script::new())
+        .chain(sigs.iter().map(|sig| Script::from(sig.serialize())))
+        .collect()
+}
 
 /// Creates witness script items to claim a P2WPKH spending condition
 /// (_witness_).
@@ -94,18 +105,49 @@ pub fn new_p2tr_key_path(sig: Vec<u8>) -> Witness {
 /// (_witness_).
 ///
 /// ```txt
-/// <sig>
+/// <witness_item_1>
+/// ...
+/// <witness_item_n>
 /// <payload>
 /// <control_block>
 /// ```
 pub fn new_p2tr_script_path(
-    sig: &BitcoinSchnorrSignature,
+    items: Vec<Script>,
     payload: Script,
     control_block: Vec<u8>,
 ) -> Witness {
     let mut w = Witness::new();
-    w.push_item(Script::from(sig.serialize()));
+    for item in items {
+        w.push_item(item);
+    }
     w.push_item(payload);
     w.push_item(Script::from(control_block));
     w
 }
+
+/// Creates witness items to satisfy an `m-of-n` `OP_CHECKSIGADD` tapscript multisig.
+/// `signed` tells whether each public key of the script signs, in the script order.
+/// Signatures must be in the same order as the corresponding public keys in the script.
+///
+/// ```txt
+/// <sig_n or empty> ... <sig_1 or empty>
+/// ```
+///
+/// The items are reversed as the first public key of the script consumes the top stack item.
+pub fn new_tapscript_multisig_items(
+    sigs: &[BitcoinSchnorrSignature],
+    signed: &[bool],
+) -> Vec<Script> {
+    let mut sigs = sigs.iter().rev();
+    signed
+        .iter()
+        .rev()
+        .map(|&is_signed| {
+            is_signed
+                .then(|| sigs.next())
+                .flatten()
+                .map(|sig| Script::from(sig.serialize()))
+                .unwrap_or_default()
+        })
+        .collect()
+}
@@ -11,6 +11,11 @@ use super::opcodes::*;
 use super::Script;
 use super::SEGWIT_VERSION;
 
+/// The max number of public keys in a standard multisig script.
+const MULTISIG_MAX_PUBKEYS: u8 = 16;
+/// The max number of public keys in a standard `OP_CHECKSIGADD` tapscript multisig.
+const TAPSCRIPT_MULTISIG_MAX_PUBKEYS: usize = 999;
+
 /// Creates a P2SH spending condition (_scriptPubkey_).
 ///
 /// ```txt
@@ -75,6 +80,53 @@ pub fn new_p2wpkh(pubkey_hash: &H160) -> Script {
     s
 }
 
+/// Creates an `m-of-n` multisig spending condition, usually used as a P2SH or P2WSH redeem script.
+/// Returns `None` if the threshold is zero, or exceeds the number of public keys, or there are more than 16 public keys.
+///
+/// ```txt
+/// OP_<m> <push><pubkey_1> ... <push><pubkey_n> OP_<n> OP_CHECKMULTISIG
+/// ```
+pub fn new_multisig(threshold: u8, pubkeys: &[H264]) -> Option<Script> {
+    let total = u8::try_from(pubkeys.len()).ok()?;
+    if threshold == 0 || threshold > total || total > MULTISIG_MAX_PUBKEYS {
+        return None;
+    }
+
+    let mut s = Script::with_capacity(3 + pubkeys.len() * (H264::LEN + 1));
+    s.push(OP_PUSHNUM_1 + threshold - 1);
+    for pubkey in pubkeys {
+        s.push_slice(pubkey.as_slice());
+    }
+    s.push(OP_PUSHNUM_1 + total - 1);
+    s.push(OP_CHECKMULTISIG);
+    Some(s)
+}
+
+/// Creates an `m-of-n` multisig tapscript leaf using `OP_CHECKSIGADD` (BIP-342).
+/// Returns `None` if the threshold is zero or exceeds the number of public keys, or there are more than 999 public keys.
+///
+/// ```txt
+/// <push><xonly_1> OP_CHECKSIG <push><xonly_2> OP_CHECKSIGADD ... <push><xonly_n> OP_CHECKSIGADD OP_<m> OP_NUMEQUAL
+/// ```
+pub fn new_tapscript_multisig(threshold: u8, pubkeys: &[H256]) -> Option<Script> {
+    if threshold == 0
+        || threshold > MULTISIG_MAX_PUBKEYS
+        || threshold as usize > pubkeys.len()
+        || pubkeys.len() > TAPSCRIPT_MULTISIG_MAX_PUBKEYS
+    {
+        return None;
+    }
+
+    let mut s = Script::with_capacity(2 + pubkeys.len() * (H256::LEN + 2));
+    for (i, pubkey) in pubkeys.iter().enumerate() {
+        s.push_slice(pubkey.as_slice());
+        s.push(if i == 0 { OP_CHECKSIG } else { OP_CHECKSIGADD });
+    }
+    s.push(OP_PUSHNUM_1 + threshold - 1);
+    s.push(OP_NUMEQUAL);
+    Some(s)
+}
+
 pub fn new_p2tr_key_path(pubkey: &H264) -> Script {
     // We're relying on the `bitcoin` crate to generate anything Taproot related.
     let pubkey = bitcoin::PublicKey::from_slice(pubkey.as_slice()).unwrap();
@@ -147,6 +199,18 @@ pub fn is_p2tr(s: &Script) -> bool {
     b.len() == 34 && b[0] == taproot_version_op_code && b[1] == OP_PUSHBYTES_32
 }
 
+/// Checks if the script is a witness program of any version:
+/// a version opcode followed by a single 2 to 40 bytes push.
+/// https://github.com/bitcoin/bitcoin/blob/v27.0/src/script/script.cpp#L247-L259
+pub fn is_witness_program(s: &Script) -> bool {
+    let b = s.as_slice();
+    if b.len() < 4 || b.len() > 42 {
+        return false;
+    }
+    let is_version = b[0] == SEGWIT_VERSION || (OP_PUSHNUM_1..=OP_PUSHNUM_16).contains(&b[0]);
+    is_version && b[1] as usize + 2 == b.len()
+}
+
 pub fn is_op_return(s: &Script) -> bool {
     let b = s.as_slice();
     !b.is_empty() && b[0] == OP_RETURN
@@ -215,3 +279,196 @@ pub fn match_op_return(s: &Script) -> Option<Data> {
         None
     }
 }
+
+/// Returns the data pushed by an `OP_RETURN <data>` script as built by [`new_op_return`].
+/// Unlike [`match_op_return`], the push opcode is not included into the result.
+pub fn match_op_return_data(s: &Script) -> Option<&[u8]> {
+    let data = match s.as_slice() {
+        [OP_RETURN, len @ 0..=OP_PUSHBYTES_75, data @ ..] if data.len() == *len as usize => data,
+        [OP_RETURN, OP_PUSHDATA1, len, data @ ..] if data.len() == *len as usize => data,
+        [OP_RETURN, OP_PUSHDATA2, lo, hi, data @ ..]
+            if data.len() == u16::from_le_bytes([*lo, *hi]) as usize =>
+        {
+            data
+        },
+        _ => return None,
+    };
+    // Check if the data is pushed with the minimal opcode.
+    (new_op_return(data) == *s).then_some(data)
+}
+
+/// Returns a threshold and either compressed or uncompressed public keys
+/// of an `m-of-n` multisig script if matched.
+pub fn match_multisig(s: &Script) -> Option<(u8, Vec<&[u8]>)> {
+    let (&threshold_op, rest) = s.as_slice().split_first()?;
+    let (&checkmultisig_op, rest) = rest.split_last()?;
+    let (&total_op, mut pubkeys_data) = rest.split_last()?;
+
+    if checkmultisig_op != OP_CHECKMULTISIG {
+        return None;
+    }
+    let threshold = decode_small_number(threshold_op)?;
+    let total = decode_small_number(total_op)?;
+
+    let mut pubkeys = Vec::with_capacity(total as usize);
+    while let Some((&push_op, rest)) = pubkeys_data.split_first() {
+        let pubkey_len = match push_op {
+            OP_PUSHBYTES_33 => 33,
+            OP_PUSHBYTES_65 => 65,
+            _ => return None,
+        };
+        if rest.len() < pubkey_len {
+            return None;
+        }
+        let (pubkey, rest) = rest.split_at(pubkey_len);
+        pubkeys.push(pubkey);
+        pubkeys_data = rest;
+    }
+
+    if threshold > total || pubkeys.len() != total as usize {
+        return None;
+    }
+    Some((threshold, pubkeys))
+}
+
+/// Returns a threshold and x-only public keys of an `m-of-n` `OP_CHECKSIGADD` tapscript multisig if matched.
+pub fn match_tapscript_multisig(s: &Script) -> Option<(u8, Vec<H256>)> {
+    let (&numequal_op, rest) = s.as_slice().split_last()?;
+    let (&threshold_op, mut pubkeys_data) = rest.split_last()?;
+
+    if numequal_op != OP_NUMEQUAL {
+        return None;
+    }
+    let threshold = decode_small_number(threshold_op)?;
+
+    let mut pubkeys = Vec::new();
+    while let Some((&push_op, rest)) = pubkeys_data.split_first() {
+        if push_op != OP_PUSHBYTES_32 || rest.len() <= H256::LEN {
+            return None;
+        }
+        let (pubkey, rest) = rest.split_at(H256::LEN);
+        let (&check_op, rest) = rest.split_first()?;
+
+        let expected_check_op = if pubkeys.is_empty() {
+            OP_CHECKSIG
+        } else {
+            OP_CHECKSIGADD
+        };
+        if check_op != expected_check_op {
+            return None;
+        }
+
+        pubkeys.push(H256::try_from(pubkey).ok()?);
+        pubkeys_data = rest;
+    }
+
+    if threshold as usize > pubkeys.len() || pubkeys.len() > TAPSCRIPT_MULTISIG_MAX_PUBKEYS {
+        return None;
+    }
+    Some((threshold, pubkeys))
+}
+
+/// Decodes `OP_1..=OP_16` opcode as a number.
+fn decode_small_number(op: u8) -> Option<u8> {
+    (OP_PUSHNUM_1..=OP_PUSHNUM_16)
+        .contains(&op)
+        .then(|| op - OP_PUSHNUM_1 + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tw_encoding::hex::DecodeHex;
+
+    #[test]
+    fn test_witness_program() {
+        let p2wpkh = new_p2wpkh(&H160::default());
+        let p2wsh = new_p2wsh(&H256::default());
+        let p2tr = new_p2tr_dangerous_assume_tweaked(&H256::from(
+            "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+        ));
+        assert!(is_witness_program(&p2wpkh));
+        assert!(is_witness_program(&p2wsh));
+        assert!(is_witness_program(&p2tr));
+
+        assert!(!is_witness_program(&new_p2pkh(&H160::default())));
+        assert!(!is_witness_program(&new_p2sh(&H160::default())));
+        assert!(!is_witness_program(&new_op_return(b"data")));
+
+        // Push length does not match the script length.
+        let mut invalid = p2wpkh.to_vec();
+        invalid.push(0);
+        assert!(!is_witness_program(&Script::from(invalid)));
+    }
+
+    #[test]
+    fn test_multisig() {
+        let pubkey1 =
+            H264::from("036666dd712e05a487916384bfcd5973eb53e8038eccbbf97f7eed775b87389536");
+        let pubkey2 =
+            H264::from("037ed9a436e11ec4947ac4b7823787e24ba73180f1edd2857bff19c9f4d62b65bf");
+
+        let script = new_multisig(1, &[pubkey1, pubkey2]).unwrap();
+        let expected = "5121036666dd712e05a487916384bfcd5973eb53e8038eccbbf97f7eed775b8738953621037ed9a436e11ec4947ac4b7823787e24ba73180f1edd2857bff19c9f4d62b65bf52ae";
+        assert_eq!(script.as_slice(), expected.decode_hex().unwrap());
+
+        let (threshold, pubkeys) = match_multisig(&script).unwrap();
+        assert_eq!(threshold, 1);
+        assert_eq!(pubkeys, vec![pubkey1.as_slice(), pubkey2.as_slice()]);
+
+        assert!(new_multisig(0, &[pubkey1]).is_none());
+        assert!(new_multisig(2, &[pubkey1]).is_none());
+        assert!(match_multisig(&new_p2pk(&pubkey1)).is_none());
+
+        // `m > n`.
+        let mut invalid = script.to_vec();
+        invalid[0] = OP_PUSHNUM_3;
+        assert!(match_multisig(&Script::from(invalid)).is_none());
+    }
+
+    #[test]
+    fn test_tapscript_multisig() {
+        let pubkey1 =
+            H256::from("0f209b6ada5edb42c77fd2bc64ad650ae38314c8f451f3e36d80bc8e26f132cb");
+        let pubkey2 =
+            H256::from("6666dd712e05a487916384bfcd5973eb53e8038eccbbf97f7eed775b87389536");
+
+        let script = new_tapscript_multisig(2, &[pubkey1, pubkey2]).unwrap();
+        let expected = "200f209b6ada5edb42c77fd2bc64ad650ae38314c8f451f3e36d80bc8e26f132cbac206666dd712e05a487916384bfcd5973eb53e8038eccbbf97f7eed775b87389536ba529c";
+        assert_eq!(script.as_slice(), expected.decode_hex().unwrap());
+
+        let (threshold, pubkeys) = match_tapscript_multisig(&script).unwrap();
+        assert_eq!(threshold, 2);
+        assert_eq!(pubkeys, vec![pubkey1, pubkey2]);
+
+        assert!(new_tapscript_multisig(0, &[pubkey1]).is_none());
+        assert!(new_tapscript_multisig(2, &[pubkey1]).is_none());
+        // `m > n`.
+        let no_pubkeys = Script::from(vec![OP_PUSHNUM_1, OP_NUMEQUAL]);
+        assert!(match_tapscript_multisig(&no_pubkeys).is_none());
+
+        // The first public key must be checked by `OP_CHECKSIG`.
+        let mut invalid = script.to_vec();
+        invalid[33] = OP_CHECKSIGADD;
+        assert!(match_tapscript_multisig(&Script::from(invalid)).is_none());
+    }
+
+    #[test]
+    fn test_op_return_data() {
+        let short = new_op_return(b"hello");
+        assert_eq!(match_op_return_data(&short), Some(b"hello".as_slice()));
+
+        let long = new_op_return(&[0xab; 80]);
+        assert_eq!(long.as_slice()[1], OP_PUSHDATA1);
+        assert_eq!(match_op_return_data(&long), Some([0xab; 80].as_slice()));
+
+        // Non-minimal push.
+        let non_minimal = Script::from("6a4c0568656c6c6f".decode_hex().unwrap());
+        assert_eq!(match_op_return_data(&non_minimal), None);
+        // Bare OP_RETURN.
+        assert_eq!(match_op_return_data(&Script::from(vec![OP_RETURN])), None);
+        // Trailing opcodes.
+        let trailing = Script::from("6a0568656c6c6f51".decode_hex().unwrap());
+        assert_eq!(match_op_return_data(&trailing), None);
+    }
+}
@@ -19,17 +19,31 @@ pub trait EcdsaSpendingDataConstructor: fmt::Debug {
     fn get_spending_data(&self, sig: &BitcoinEcdsaSignature) -> SpendingData;
 }
 
+/// A constructor of `SpendingData` that requires several ECDSA signatures, e.g. an `m-of-n` multisig.
+/// Signatures are given in the same order as [`crate::transaction::UtxoToSign::spender_public_keys`].
+pub trait MultiEcdsaSpendingDataConstructor: fmt::Debug {
+    fn get_spending_data(&self, sigs: &[BitcoinEcdsaSignature]) -> SpendingData;
+}
+
 pub trait SchnorrSpendingDataConstructor: fmt::Debug {
     fn get_spending_data(&self, sig: &BitcoinSchnorrSignature) -> SpendingData;
 }
 
+/// A constructor of `SpendingData` that requires several Schnorr signatures, e.g. a Taproot script-path spend.
+/// Signatures are given in the same order as [`crate::transaction::UtxoToSign::spender_public_keys`].
+pub trait MultiSchnorrSpendingDataConstructor: fmt::Debug {
+    fn get_spending_data(&self, sigs: &[BitcoinSchnorrSignature]) -> SpendingData;
+}
+
 /// A UTXO `SpendingData` constructor.
 /// Each enum variant differs with a signature type it expects to construct `SpendingData`.
 /// TODO consider adding a `UtxoContext` with a `UtxoContext::SpendingDataConstructor: Clone` associated type.
 #[derive(Clone, Debug)]
 pub enum SpendingDataConstructor {
     Ecdsa(Rc<dyn EcdsaSpendingDataConstructor>),
+    MultiEcdsa(Rc<dyn MultiEcdsaSpendingDataConstructor>),
     Schnorr(Rc<dyn SchnorrSpendingDataConstructor>),
+    MultiSchnorr(Rc<dyn MultiSchnorrSpendingDataConstructor>),
 }
 
 impl SpendingDataConstructor {
@@ -37,7 +51,15 @@ impl SpendingDataConstructor {
         SpendingDataConstructor::Ecdsa(Rc::new(constructor))
     }
 
+    pub fn multi_ecdsa<T: MultiEcdsaSpendingDataConstructor + 'static>(constructor: T) -> Self {
+        SpendingDataConstructor::MultiEcdsa(Rc::new(constructor))
+    }
+
     pub fn schnorr<T: SchnorrSpendingDataConstructor + 'static>(constructor: T) -> Self {
         SpendingDataConstructor::Schnorr(Rc::new(constructor))
     }
+
+    pub fn multi_schnorr<T: MultiSchnorrSpendingDataConstructor + 'static>(constructor: T) -> Self {
+        SpendingDataConstructor::MultiSchnorr(Rc::new(constructor))
+    }
 }
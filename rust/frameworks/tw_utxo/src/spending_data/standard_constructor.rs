@@ -6,7 +6,8 @@ use crate::script::standard_script::claims;
 use crate::script::{Script, Witness};
 use crate::signature::{BitcoinEcdsaSignature, BitcoinSchnorrSignature};
 use crate::spending_data::{
-    EcdsaSpendingDataConstructor, SchnorrSpendingDataConstructor, SpendingData,
+    EcdsaSpendingDataConstructor, MultiEcdsaSpendingDataConstructor,
+    MultiSchnorrSpendingDataConstructor, SchnorrSpendingDataConstructor, SpendingData,
 };
 use tw_hash::H264;
 use tw_memory::Data;
@@ -37,6 +38,73 @@ impl EcdsaSpendingDataConstructor for P2PK {
     }
 }
 
+/// Describes how to satisfy a P2SH redeem script or a P2WSH witness script with the given signatures.
+#[derive(Clone, Debug)]
+pub enum RedeemScriptClaim {
+    /// Signatures are pushed in the given order, e.g. a P2PK redeem script.
+    /// ```txt
+    /// <sig_1> ... <sig_n>
+    /// ```
+    Signatures,
+    /// A P2PKH redeem script expects one signature followed by the public key.
+    /// ```txt
+    /// <sig> <pubkey>
+    /// ```
+    P2PKH { pubkey: H264 },
+    /// An `m-of-n` multisig redeem script expects `m` signatures ordered as the public keys in the script.
+    /// ```txt
+    /// OP_0 <sig_1> ... <sig_m>
+    /// ```
+    Multisig,
+}
+
+impl RedeemScriptClaim {
+    pub fn get_items(&self, sigs: &[BitcoinEcdsaSignature]) -> Vec<Script> {
+        match self {
+            RedeemScriptClaim::Signatures => sigs
+                .iter()
+                .map(|sig| Script::from(sig.serialize()))
+                .collect(),
+            RedeemScriptClaim::P2PKH { pubkey } => sigs
+                .iter()
+                .map(|sig| Script::from(sig.serialize()))
+                .chain(std::iter::once(Script::from(pubkey.into_vec())))
+                .collect(),
+            RedeemScriptClaim::Multisig => claims::new_multisig_items(sigs),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct P2SH {
+    pub redeem_script: Script,
+    pub claim: RedeemScriptClaim,
+}
+
+impl MultiEcdsaSpendingDataConstructor for P2SH {
+    fn get_spending_data(&self, sigs: &[BitcoinEcdsaSignature]) -> SpendingData {
+        SpendingData {
+            script_sig: claims::new_p2sh(self.claim.get_items(sigs), self.redeem_script.clone()),
+            witness: Witness::default(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct P2WSH {
+    pub witness_script: Script,
+    pub claim: RedeemScriptClaim,
+}
+
+impl MultiEcdsaSpendingDataConstructor for P2WSH {
+    fn get_spending_data(&self, sigs: &[BitcoinEcdsaSignature]) -> SpendingData {
+        SpendingData {
+            script_sig: Script::default(),
+            witness: claims::new_p2wsh(self.claim.get_items(sigs), self.witness_script.clone()),
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct P2WPKH {
     pub pubkey: H264,
@@ -63,18 +131,49 @@ impl SchnorrSpendingDataConstructor for P2TRKeyPath {
     }
 }
 
+/// Describes how to satisfy a Taproot leaf script with the given signatures.
+#[derive(Clone, Debug)]
+pub enum TapscriptClaim {
+    /// Signatures are pushed in the given order, e.g. a `<pubkey> OP_CHECKSIG` leaf script.
+    /// ```txt
+    /// <sig_1> ... <sig_n>
+    /// ```
+    Signatures,
+    /// An `m-of-n` `OP_CHECKSIGADD` multisig expects either a signature or an empty item per each public key
+    /// in reverse order. `signed` tells whether each public key of the script signs, in the script order.
+    /// ```txt
+    /// <sig_n or empty> ... <sig_1 or empty>
+    /// ```
+    Multisig { signed: Vec<bool> },
+}
+
+impl TapscriptClaim {
+    pub fn get_items(&self, sigs: &[BitcoinSchnorrSignature]) -> Vec<Script> {
+        match self {
+            TapscriptClaim::Signatures => sigs
+                .iter()
+                .map(|sig| Script::from(sig.serialize()))
+                .collect(),
+            TapscriptClaim::Multisig { signed } => {
+                claims::new_tapscript_multisig_items(sigs, signed)
+            },
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct P2TRScriptPath {
     pub payload: Script,
     pub control_block: Data,
+    pub claim: TapscriptClaim,
 }
 
-impl SchnorrSpendingDataConstructor for P2TRScriptPath {
-    fn get_spending_data(&self, sig: &BitcoinSchnorrSignature) -> SpendingData {
+impl MultiSchnorrSpendingDataConstructor for P2TRScriptPath {
+    fn get_spending_data(&self, sigs: &[BitcoinSchnorrSignature]) -> SpendingData {
         SpendingData {
             script_sig: Script::default(),
             witness: claims::new_p2tr_script_path(
-                sig,
+                self.claim.get_items(sigs),
                 self.payload.clone(),
                 self.control_block.clone(),
             ),
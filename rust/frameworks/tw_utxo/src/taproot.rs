@@ -0,0 +1,231 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::script::Script;
+use bitcoin::hashes::Hash;
+use bitcoin::taproot::{LeafVersion, TapLeafHash, TapNodeHash, TaprootBuilder, TaprootSpendInfo};
+use tw_coin_entry::error::prelude::*;
+use tw_hash::{H256, H264};
+use tw_keypair::schnorr;
+use tw_memory::Data;
+
+/// The leaf version of BIP-342 tapscripts.
+pub const TAPSCRIPT_LEAF_VERSION: u8 = 0xc0;
+
+/// A script leaf of a Taproot script tree.
+#[derive(Clone, Debug)]
+pub struct TaprootLeaf {
+    pub script: Script,
+    /// Leaf version, usually [`TAPSCRIPT_LEAF_VERSION`].
+    pub version: u8,
+    /// Depth of the leaf in the tree, where the root has depth 0.
+    pub depth: u8,
+}
+
+/// A Taproot script tree committed to an internal public key.
+#[derive(Clone, Debug)]
+pub struct TaprootTree {
+    spend_info: TaprootSpendInfo,
+}
+
+impl TaprootTree {
+    /// Builds a script tree from the given leaves.
+    /// The leaves must be given in the depth-first walk order,
+    /// e.g. `[A(depth=1), B(depth=2), C(depth=2)]` results in the `{A, {B, C}}` tree.
+    pub fn new(
+        internal_pubkey: &schnorr::PublicKey,
+        leaves: &[TaprootLeaf],
+    ) -> SigningResult<TaprootTree> {
+        if leaves.is_empty() {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("Taproot script tree must contain at least one leaf");
+        }
+
+        let mut builder = TaprootBuilder::new();
+        for leaf in leaves {
+            let version = leaf_version(leaf.version)?;
+            let script = bitcoin::ScriptBuf::from_bytes(leaf.script.to_vec());
+            builder = builder
+                .add_leaf_with_ver(leaf.depth, script, version)
+                .map_err(|e| {
+                    SigningError::new(SigningErrorType::Error_invalid_params)
+                        .context(format!("Invalid Taproot script tree: {e}"))
+                })?;
+        }
+
+        let internal_key = to_x_only(internal_pubkey)?;
+        let spend_info = builder
+            .finalize(&secp256k1::Secp256k1::verification_only(), internal_key)
+            .tw_err(|_| SigningErrorType::Error_invalid_params)
+            .context(
+                "Taproot script tree is incomplete: leaf depths do not form a full binary tree",
+            )?;
+
+        Ok(TaprootTree { spend_info })
+    }
+
+    pub fn merkle_root(&self) -> SigningResult<H256> {
+        self.spend_info
+            .merkle_root()
+            .map(|root| H256::from(root.to_byte_array()))
+            .or_tw_err(SigningErrorType::Error_internal)
+            .context("No merkle root of the Taproot script tree")
+    }
+
+    /// Constructs a control block proving that the given leaf is committed to the tree.
+    pub fn control_block(&self, leaf_script: &Script, version: u8) -> SigningResult<ControlBlock> {
+        let script = bitcoin::ScriptBuf::from_bytes(leaf_script.to_vec());
+        let control_block = self
+            .spend_info
+            .control_block(&(script, leaf_version(version)?))
+            .or_tw_err(SigningErrorType::Error_invalid_params)
+            .context("The leaf script is not found in the Taproot script tree")?;
+        Ok(ControlBlock::from(control_block))
+    }
+}
+
+/// A control block is the last witness item of a Taproot script-path spend.
+/// It contains the leaf version, the output key parity, the internal public key
+/// and the merkle proof of the spent leaf script.
+#[derive(Clone, Debug)]
+pub struct ControlBlock {
+    inner: bitcoin::taproot::ControlBlock,
+}
+
+impl ControlBlock {
+    pub fn decode(bytes: &[u8]) -> SigningResult<ControlBlock> {
+        let inner = bitcoin::taproot::ControlBlock::decode(bytes).map_err(|e| {
+            SigningError::new(SigningErrorType::Error_invalid_params)
+                .context(format!("Invalid Taproot control block: {e}"))
+        })?;
+        Ok(ControlBlock { inner })
+    }
+
+    pub fn leaf_version(&self) -> u8 {
+        self.inner.leaf_version.to_consensus()
+    }
+
+    /// Returns the internal public key as a compressed public key with an even Y coordinate,
+    /// as it's serialized as an x-only public key.
+    pub fn internal_pubkey(&self) -> H264 {
+        let pubkey = secp256k1::PublicKey::from_x_only_public_key(
+            self.inner.internal_key,
+            secp256k1::Parity::Even,
+        );
+        H264::from(pubkey.serialize())
+    }
+
+    /// Computes the hash of the given leaf script.
+    pub fn leaf_hash(&self, leaf_script: &Script) -> H256 {
+        let script = bitcoin::ScriptBuf::from_bytes(leaf_script.to_vec());
+        let leaf_hash = TapLeafHash::from_script(&script, self.inner.leaf_version);
+        H256::from(leaf_hash.to_byte_array())
+    }
+
+    /// Computes the merkle root of the script tree by the given leaf script and the merkle proof.
+    pub fn merkle_root(&self, leaf_script: &Script) -> H256 {
+        let script = bitcoin::ScriptBuf::from_bytes(leaf_script.to_vec());
+        let root = self.inner.merkle_branch.as_inner().iter().fold(
+            TapNodeHash::from_script(&script, self.inner.leaf_version),
+            |node, sibling| TapNodeHash::from_node_hashes(node, *sibling),
+        );
+        H256::from(root.to_byte_array())
+    }
+
+    pub fn serialize(&self) -> Data {
+        self.inner.serialize()
+    }
+}
+
+impl From<bitcoin::taproot::ControlBlock> for ControlBlock {
+    fn from(inner: bitcoin::taproot::ControlBlock) -> Self {
+        ControlBlock { inner }
+    }
+}
+
+fn leaf_version(version: u8) -> SigningResult<LeafVersion> {
+    LeafVersion::from_consensus(version)
+        .tw_err(|_| SigningErrorType::Error_invalid_params)
+        .with_context(|| format!("Invalid Taproot leaf version: {version:#04x}"))
+}
+
+fn to_x_only(pubkey: &schnorr::PublicKey) -> SigningResult<secp256k1::XOnlyPublicKey> {
+    secp256k1::XOnlyPublicKey::from_slice(pubkey.x_only().bytes().as_slice())
+        .tw_err(|_| SigningErrorType::Error_invalid_params)
+        .context("Invalid Taproot internal public key")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tw_encoding::hex::{DecodeHex, ToHex};
+
+    fn leaf(script: &str, depth: u8) -> TaprootLeaf {
+        TaprootLeaf {
+            script: Script::from(script.decode_hex().unwrap()),
+            version: TAPSCRIPT_LEAF_VERSION,
+            depth,
+        }
+    }
+
+    #[test]
+    fn test_taproot_tree_control_block() {
+        let internal_pubkey = schnorr::PublicKey::try_from(
+            "030f209b6ada5edb42c77fd2bc64ad650ae38314c8f451f3e36d80bc8e26f132cb"
+                .decode_hex()
+                .unwrap()
+                .as_slice(),
+        )
+        .unwrap();
+
+        // `{A, {B, C}}`
+        let leaves = [leaf("51", 1), leaf("52", 2), leaf("53", 2)];
+        let tree = TaprootTree::new(&internal_pubkey, &leaves).unwrap();
+        let merkle_root = tree.merkle_root().unwrap();
+
+        for leaf in leaves.iter() {
+            let control_block = tree.control_block(&leaf.script, leaf.version).unwrap();
+            assert_eq!(control_block.merkle_root(&leaf.script), merkle_root);
+            assert_eq!(control_block.leaf_version(), TAPSCRIPT_LEAF_VERSION);
+            assert_eq!(
+                control_block.internal_pubkey().to_hex(),
+                "020f209b6ada5edb42c77fd2bc64ad650ae38314c8f451f3e36d80bc8e26f132cb"
+            );
+
+            let decoded = ControlBlock::decode(&control_block.serialize()).unwrap();
+            assert_eq!(decoded.serialize(), control_block.serialize());
+        }
+
+        // The leaf `A` is proven by a single node `{B, C}`, others - by two nodes.
+        let control_block = tree.control_block(&leaves[0].script, TAPSCRIPT_LEAF_VERSION);
+        assert_eq!(control_block.unwrap().serialize().len(), 33 + 32);
+        let control_block = tree.control_block(&leaves[1].script, TAPSCRIPT_LEAF_VERSION);
+        assert_eq!(control_block.unwrap().serialize().len(), 33 + 64);
+
+        // Unknown leaf.
+        let unknown = Script::from(vec![0x54]);
+        tree.control_block(&unknown, TAPSCRIPT_LEAF_VERSION)
+            .unwrap_err();
+    }
+
+    #[test]
+    fn test_taproot_tree_invalid() {
+        let internal_pubkey = schnorr::PublicKey::try_from(
+            "030f209b6ada5edb42c77fd2bc64ad650ae38314c8f451f3e36d80bc8e26f132cb"
+                .decode_hex()
+                .unwrap()
+                .as_slice(),
+        )
+        .unwrap();
+
+        // No leaves.
+        TaprootTree::new(&internal_pubkey, &[]).unwrap_err();
+        // Incomplete tree.
+        TaprootTree::new(&internal_pubkey, &[leaf("51", 1), leaf("52", 2)]).unwrap_err();
+        // Invalid leaf version.
+        let mut invalid_version = leaf("51", 0);
+        invalid_version.version = 0xc1;
+        TaprootTree::new(&internal_pubkey, &[invalid_version]).unwrap_err();
+    }
+}
@@ -6,6 +6,7 @@ use crate::script::Script;
 use crate::sighash::SighashType;
 use crate::signing_mode::SigningMethod;
 use crate::spending_data::SpendingDataConstructor;
+use crate::taproot::ControlBlock;
 use crate::transaction::transaction_parts::Amount;
 use tw_coin_entry::error::prelude::SigningResult;
 use tw_hash::hasher::Hasher;
@@ -58,11 +59,15 @@ pub struct UtxoToSign {
     pub script_pubkey: Script,
     pub signing_method: SigningMethod,
     pub spending_data_constructor: SpendingDataConstructor,
-    pub spender_public_key: Data,
+    /// Public keys whose signatures are required to spend the UTXO, in the order the signatures are expected.
+    /// Contains exactly one public key unless [`SpendingDataConstructor::MultiEcdsa`] or [`SpendingDataConstructor::MultiSchnorr`] is used.
+    pub spender_public_keys: Vec<Data>,
     pub amount: Amount,
     /// Taproot UTXO specific argument.
     /// TODO add `TaprootUtxoSignArgs`.
     pub leaf_hash_code_separator: Option<(H256, u32)>,
+    /// Control block of the spent leaf script, set for Taproot script-path UTXOs only.
+    pub control_block: Option<ControlBlock>,
     pub tx_hasher: Hasher,
     pub sighash_ty: SighashType,
 }
@@ -1,6 +1,7 @@
 use super::TransactionInput;
 use crate::sighash::SighashType;
 use crate::spending_data::{standard_constructor, SpendingDataConstructor};
+use crate::taproot::ControlBlock;
 use crate::transaction::UtxoToSign;
 use crate::{
     script::{standard_script::conditions, Script, Witness},
@@ -8,14 +9,15 @@ use crate::{
     transaction::asset::brc20::{BRC20TransferInscription, Brc20Ticker},
     transaction::transaction_parts::{Amount, OutPoint},
 };
-use bitcoin::hashes::Hash;
 use tw_coin_entry::error::prelude::*;
-use tw_hash::{hasher::Hasher, ripemd::bitcoin_hash_160, H160, H256};
+use tw_hash::{hasher::Hasher, ripemd::bitcoin_hash_160, sha2::sha256, H160, H256};
 use tw_keypair::{ecdsa, schnorr};
 use tw_memory::Data;
 use tw_misc::traits::ToBytesVec;
 
 pub const DEFAULT_TX_HASHER: Hasher = Hasher::Sha256d;
+/// The max size of a P2SH redeem script, as it must be pushed within a single stack element.
+pub const MAX_SCRIPT_ELEMENT_SIZE: usize = 520;
 
 pub struct UtxoBuilder {
     input: TransactionInput,
@@ -92,32 +94,58 @@ impl UtxoBuilder {
             .context("'UtxoBuilder::sighash_ty' is not set")
     }
 
-    // TODO next iteration.
-    // pub fn p2sh(self, redeem_script: Script) -> SigningResult<(TransactionInput, UtxoToSign)> {
-    //     let h = bitcoin_hash_160(redeem_script.as_slice());
-    //     let redeem_hash: H160 = h.as_slice().try_into().expect("hash length is 20 bytes");
-    //
-    //     self.p2sh_with_hash(redeem_hash)
-    // }
-    //
-    // pub fn p2sh_with_hash(
-    //     mut self,
-    //     redeem_hash: H160,
-    // ) -> SigningResult<(TransactionInput, UtxoToSign)> {
-    //     self.finalize_out_point()?;
-    //     let amount = self.finalize_amount()?;
-    //
-    //     Ok((
-    //         self.input,
-    //         UtxoToSign {
-    //             script_pubkey: conditions::new_p2sh(&redeem_hash),
-    //             // P2SH output can be spent by a legacy address only.
-    //             signing_method: SigningMethod::Legacy,
-    //             amount,
-    //             ..Default::default()
-    //         },
-    //     ))
-    // }
+    /// Spends a P2SH output with the given redeem script.
+    /// `signers` are the public keys whose signatures satisfy the redeem script,
+    /// see [`UtxoBuilder::redeem_script_claim`].
+    pub fn p2sh(
+        mut self,
+        redeem_script: Script,
+        signers: &[ecdsa::secp256k1::PublicKey],
+    ) -> SigningResult<(TransactionInput, UtxoToSign)> {
+        if conditions::is_p2wpkh(&redeem_script) || conditions::is_p2wsh(&redeem_script) {
+            return SigningError::err(SigningErrorType::Error_not_supported)
+                .context("P2SH-wrapped Segwit redeem scripts are not supported yet");
+        }
+        if redeem_script.len() > MAX_SCRIPT_ELEMENT_SIZE {
+            return SigningError::err(SigningErrorType::Error_script_redeem).context(format!(
+                "P2SH redeem script cannot exceed {MAX_SCRIPT_ELEMENT_SIZE} bytes"
+            ));
+        }
+
+        let h = bitcoin_hash_160(redeem_script.as_slice());
+        let script_hash: H160 = h.as_slice().try_into().expect("hash length is 20 bytes");
+
+        let (claim, spender_public_keys) = Self::redeem_script_claim(&redeem_script, signers)?;
+
+        self.finalize_out_point()?;
+        let amount = self.finalize_amount()?;
+        let sighash_ty = self.finalize_sighash_type()?;
+
+        Ok((
+            self.input,
+            UtxoToSign {
+                // Original P2SH scriptPubkey.
+                prevout_script_pubkey: conditions::new_p2sh(&script_hash),
+                // P2SH UTXO is signed with the redeem script as scriptPubkey.
+                script_pubkey: redeem_script.clone(),
+                // P2SH output can be spent by a legacy address only.
+                signing_method: SigningMethod::Legacy,
+                // When the sighashes are signed, build a P2SH script_sig.
+                spending_data_constructor: SpendingDataConstructor::multi_ecdsa(
+                    standard_constructor::P2SH {
+                        redeem_script,
+                        claim,
+                    },
+                ),
+                spender_public_keys,
+                amount,
+                leaf_hash_code_separator: None,
+                control_block: None,
+                tx_hasher: DEFAULT_TX_HASHER,
+                sighash_ty,
+            },
+        ))
+    }
 
     pub fn p2pk(
         mut self,
@@ -141,9 +169,10 @@ impl UtxoBuilder {
                 spending_data_constructor: SpendingDataConstructor::ecdsa(
                     standard_constructor::P2PK,
                 ),
-                spender_public_key: pubkey.compressed().to_vec(),
+                spender_public_keys: vec![pubkey.compressed().to_vec()],
                 amount,
                 leaf_hash_code_separator: None,
+                control_block: None,
                 tx_hasher: DEFAULT_TX_HASHER,
                 sighash_ty,
             },
@@ -177,38 +206,61 @@ impl UtxoBuilder {
                         pubkey: pubkey.compressed(),
                     },
                 ),
-                spender_public_key: pubkey.compressed().to_vec(),
+                spender_public_keys: vec![pubkey.compressed().to_vec()],
                 amount,
                 leaf_hash_code_separator: None,
+                control_block: None,
                 tx_hasher: DEFAULT_TX_HASHER,
                 sighash_ty,
             },
         ))
     }
 
-    // TODO next iteration.
-    // pub fn p2wsh(mut self, redeem_script: Script) -> SigningResult<(TransactionInput, UtxoToSign)> {
-    //     let h = sha256(redeem_script.as_slice());
-    //     let redeem_hash: H256 = h.as_slice().try_into().expect("hash length is 32 bytes");
-    //
-    //     self.finalize_out_point()?;
-    //     let amount = self.finalize_amount()?;
-    //     let sighash_ty = self.finalize_sighash_type()?;
-    //
-    //     Ok((
-    //         self.input,
-    //         UtxoToSign {
-    //             script_pubkey: conditions::new_p2wsh(&redeem_hash),
-    //             // P2WSH output can be spent by a legacy address only.
-    //             signing_method: SigningMethod::Segwit,
-    //             spending_data_constructor: SpendingDataConstructor::ecdsa(todo!()),
-    //             amount,
-    //             leaf_hash_code_separator: None,
-    //             tx_hasher: DEFAULT_TX_HASHER,
-    //             sighash_ty,
-    //         },
-    //     ))
-    // }
+    /// Spends a P2WSH output with the given witness script.
+    /// `signers` are the public keys whose signatures satisfy the witness script,
+    /// see [`UtxoBuilder::redeem_script_claim`].
+    pub fn p2wsh(
+        mut self,
+        witness_script: Script,
+        signers: &[ecdsa::secp256k1::PublicKey],
+    ) -> SigningResult<(TransactionInput, UtxoToSign)> {
+        let script_hash = sha256(witness_script.as_slice());
+        let script_hash: H256 = script_hash
+            .as_slice()
+            .try_into()
+            .expect("hash length is 32 bytes");
+
+        let (claim, spender_public_keys) = Self::redeem_script_claim(&witness_script, signers)?;
+
+        self.finalize_out_point()?;
+        let amount = self.finalize_amount()?;
+        let sighash_ty = self.finalize_sighash_type()?;
+
+        Ok((
+            self.input,
+            UtxoToSign {
+                // Original P2WSH scriptPubkey.
+                prevout_script_pubkey: conditions::new_p2wsh(&script_hash),
+                // P2WSH UTXO is signed with the witness script as scriptCode.
+                script_pubkey: witness_script.clone(),
+                // P2WSH output can be spent by a Witness (eg "bc1") address only.
+                signing_method: SigningMethod::Segwit,
+                // When the sighashes are signed, build a P2WSH witness.
+                spending_data_constructor: SpendingDataConstructor::multi_ecdsa(
+                    standard_constructor::P2WSH {
+                        witness_script,
+                        claim,
+                    },
+                ),
+                spender_public_keys,
+                amount,
+                leaf_hash_code_separator: None,
+                control_block: None,
+                tx_hasher: DEFAULT_TX_HASHER,
+                sighash_ty,
+            },
+        ))
+    }
 
     pub fn p2wpkh(
         mut self,
@@ -236,11 +288,12 @@ impl UtxoBuilder {
                         pubkey: pubkey.compressed(),
                     },
                 ),
-                spender_public_key: pubkey.compressed().to_vec(),
+                spender_public_keys: vec![pubkey.compressed().to_vec()],
                 // P2WPKH output can be spent by a Witness (eg "bc1") address only.
                 signing_method: SigningMethod::Segwit,
                 amount,
                 leaf_hash_code_separator: None,
+                control_block: None,
                 tx_hasher: DEFAULT_TX_HASHER,
                 sighash_ty,
             },
@@ -277,9 +330,10 @@ impl UtxoBuilder {
                 spending_data_constructor: SpendingDataConstructor::schnorr(
                     standard_constructor::P2TRKeyPath,
                 ),
-                spender_public_key: tweaked_pubkey.bytes().to_vec(),
+                spender_public_keys: vec![tweaked_pubkey.bytes().to_vec()],
                 amount,
                 leaf_hash_code_separator: None,
+                control_block: None,
                 // Note that we don't use the default double-hasher.
                 tx_hasher: Hasher::Sha256,
                 sighash_ty,
@@ -287,48 +341,52 @@ impl UtxoBuilder {
         ))
     }
 
+    /// Spends a Taproot output via the script path, i.e. by revealing and satisfying
+    /// the given leaf script committed to the output by the control block.
+    /// See [`UtxoBuilder::tapscript_claim`] for the expected signers.
     pub fn p2tr_script_path(
         mut self,
-        internal_pubkey: &schnorr::PublicKey,
-        payload: Script,
-        control_block: Data,
-        merkle_root: &H256,
+        leaf_script: Script,
+        control_block: &ControlBlock,
+        signers: &[schnorr::PublicKey],
     ) -> SigningResult<(TransactionInput, UtxoToSign)> {
-        // Construct the leaf hash.
-        let script_buf = bitcoin::ScriptBuf::from_bytes(payload.to_vec());
-        let leaf_hash = bitcoin::taproot::TapLeafHash::from_script(
-            &script_buf,
-            bitcoin::taproot::LeafVersion::TapScript,
-        );
+        if signers.is_empty() {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("P2TR script-path spend requires at least one signer");
+        }
+        let (claim, spender_public_keys) = Self::tapscript_claim(&leaf_script, signers)?;
 
-        // Convert to native.
-        let leaf_hash: H256 = H256::from(leaf_hash.to_byte_array());
+        // Construct the leaf hash.
+        let leaf_hash = control_block.leaf_hash(&leaf_script);
 
         self.finalize_out_point()?;
         let amount = self.finalize_amount()?;
         let sighash_ty = self.finalize_sighash_type()?;
 
         // Restore the original scriptPubkey declared at the unspent P2TR output.
+        let merkle_root = control_block.merkle_root(&leaf_script);
         let prevout_script_pubkey =
-            conditions::new_p2tr_script_path(&internal_pubkey.compressed(), merkle_root);
+            conditions::new_p2tr_script_path(&control_block.internal_pubkey(), &merkle_root);
 
         Ok((
             self.input,
             UtxoToSign {
                 prevout_script_pubkey,
                 // We use the full (revealed) script as scriptPubkey here.
-                script_pubkey: payload.clone(),
+                script_pubkey: leaf_script.clone(),
                 signing_method: SigningMethod::Taproot,
-                spending_data_constructor: SpendingDataConstructor::schnorr(
+                spending_data_constructor: SpendingDataConstructor::multi_schnorr(
                     standard_constructor::P2TRScriptPath {
-                        payload,
-                        control_block,
+                        payload: leaf_script,
+                        control_block: control_block.serialize(),
+                        claim,
                     },
                 ),
-                // Taproot ScriptPath input should be signed with a non-tweaked private key.
-                spender_public_key: internal_pubkey.compressed().to_vec(),
+                // Taproot ScriptPath input should be signed with non-tweaked private keys.
+                spender_public_keys,
                 amount,
                 leaf_hash_code_separator: Some((leaf_hash, u32::MAX)),
+                control_block: Some(control_block.clone()),
                 // Note that we don't use the default double-hasher.
                 tx_hasher: Hasher::Sha256,
                 sighash_ty,
@@ -355,16 +413,130 @@ impl UtxoBuilder {
             .or_tw_err(SigningErrorType::Error_internal)
             .context("'TaprootSpendInfo::control_block' is None")?;
 
-        let merkle_root = transfer.merkle_root()?;
         let transfer_payload = Script::from(transfer.script.to_bytes());
 
         self.p2tr_script_path(
-            pubkey,
             transfer_payload,
-            control_block.serialize(),
-            &merkle_root,
+            &ControlBlock::from(control_block),
+            std::slice::from_ref(pubkey),
         )
     }
+
+    /// Determines how a P2SH redeem script (or a P2WSH witness script) should be claimed,
+    /// and returns the signers in the order their signatures are expected:
+    /// - P2PKH script - exactly one signer whose public key hash matches;
+    /// - `m-of-n` multisig script - exactly `m` signers from the script,
+    ///   sorted by their positions in the script as `OP_CHECKMULTISIG` expects;
+    /// - any other script - signatures are pushed in the given order.
+    fn redeem_script_claim(
+        redeem_script: &Script,
+        signers: &[ecdsa::secp256k1::PublicKey],
+    ) -> SigningResult<(standard_constructor::RedeemScriptClaim, Vec<Data>)> {
+        use standard_constructor::RedeemScriptClaim;
+
+        if let Some(pubkey_hash) = conditions::match_p2pkh(redeem_script) {
+            let [signer] = signers else {
+                return SigningError::err(SigningErrorType::Error_script_redeem)
+                    .context("P2PKH redeem script requires exactly one signer");
+            };
+            let signer_hash = bitcoin_hash_160(signer.compressed().as_slice());
+            if signer_hash != pubkey_hash.as_slice() {
+                return SigningError::err(SigningErrorType::Error_script_redeem)
+                    .context("P2PKH redeem script does not match the signer public key");
+            }
+
+            let claim = RedeemScriptClaim::P2PKH {
+                pubkey: signer.compressed(),
+            };
+            return Ok((claim, vec![signer.compressed().to_vec()]));
+        }
+
+        if let Some((threshold, script_pubkeys)) = conditions::match_multisig(redeem_script) {
+            if signers.len() != threshold as usize {
+                return SigningError::err(SigningErrorType::Error_script_redeem).context(format!(
+                    "{threshold}-of-{} multisig redeem script requires exactly {threshold} signers, given {}",
+                    script_pubkeys.len(),
+                    signers.len()
+                ));
+            }
+
+            // Signatures must be in the same order as the public keys in the script.
+            let ordered_signers: Vec<Data> = script_pubkeys
+                .into_iter()
+                .filter(|script_pubkey| {
+                    signers.iter().any(|signer| {
+                        signer.compressed().as_slice() == *script_pubkey
+                            || signer.uncompressed().as_slice() == *script_pubkey
+                    })
+                })
+                .map(<[u8]>::to_vec)
+                .collect();
+            if ordered_signers.len() != signers.len() {
+                return SigningError::err(SigningErrorType::Error_script_redeem)
+                    .context("Multisig signers must be unique public keys from the redeem script");
+            }
+
+            return Ok((RedeemScriptClaim::Multisig, ordered_signers));
+        }
+
+        let signers = signers
+            .iter()
+            .map(|signer| signer.compressed().to_vec())
+            .collect();
+        Ok((RedeemScriptClaim::Signatures, signers))
+    }
+
+    /// Determines how a Taproot leaf script should be claimed,
+    /// and returns the signers in the order their signatures are expected:
+    /// - `m-of-n` `OP_CHECKSIGADD` multisig script - exactly `m` signers from the script,
+    ///   sorted by their positions in the script;
+    /// - any other script - signatures are pushed in the given order.
+    fn tapscript_claim(
+        leaf_script: &Script,
+        signers: &[schnorr::PublicKey],
+    ) -> SigningResult<(standard_constructor::TapscriptClaim, Vec<Data>)> {
+        use standard_constructor::TapscriptClaim;
+
+        if let Some((threshold, script_pubkeys)) = conditions::match_tapscript_multisig(leaf_script)
+        {
+            if signers.len() != threshold as usize {
+                return SigningError::err(SigningErrorType::Error_script_redeem).context(format!(
+                    "{threshold}-of-{} tapscript multisig requires exactly {threshold} signers, given {}",
+                    script_pubkeys.len(),
+                    signers.len()
+                ));
+            }
+
+            // Signatures must be in the same order as the public keys in the script.
+            let ordered_signers: Vec<Option<&schnorr::PublicKey>> = script_pubkeys
+                .iter()
+                .map(|script_pubkey| {
+                    signers
+                        .iter()
+                        .find(|signer| signer.x_only().bytes() == *script_pubkey)
+                })
+                .collect();
+            let signed = ordered_signers.iter().map(Option::is_some).collect();
+            let ordered_signers: Vec<Data> = ordered_signers
+                .into_iter()
+                .flatten()
+                .map(|signer| signer.compressed().to_vec())
+                .collect();
+            if ordered_signers.len() != signers.len() {
+                return SigningError::err(SigningErrorType::Error_script_redeem).context(
+                    "Tapscript multisig signers must be unique public keys from the script",
+                );
+            }
+
+            return Ok((TapscriptClaim::Multisig { signed }, ordered_signers));
+        }
+
+        let signers = signers
+            .iter()
+            .map(|signer| signer.compressed().to_vec())
+            .collect();
+        Ok((TapscriptClaim::Signatures, signers))
+    }
 }
 
 impl Default for UtxoBuilder {
@@ -2,6 +2,8 @@
 //
 // Copyright © 2017 Trust Wallet.
 
+use crate::decode::reader::Reader;
+use crate::decode::Decodable;
 use crate::encode::compact_integer::CompactInteger;
 use crate::encode::stream::Stream;
 use crate::encode::Encodable;
@@ -217,6 +219,49 @@ impl Encodable for Transaction {
     }
 }
 
+impl Decodable for Transaction {
+    fn decode(reader: &mut Reader) -> SigningResult<Self> {
+        let version = reader.read()?;
+
+        // Check if the transaction is serialized in the extended (BIP-144) format.
+        // Please note that a non-zero flag is required to distinguish the marker from an empty list of inputs.
+        let decode_witness = match reader.peek_slice(WITNESS_FLAG_MARKER) {
+            Some([WITNESS_MARKER, WITNESS_FLAG]) => true,
+            Some([WITNESS_MARKER, flag]) if *flag != 0 => {
+                return SigningError::err(SigningErrorType::Error_input_parse)
+                    .context(format!("Unsupported transaction witness flag: {flag}"));
+            },
+            _ => false,
+        };
+        if decode_witness {
+            reader.read_slice(WITNESS_FLAG_MARKER)?;
+        }
+
+        let mut inputs: Vec<TransactionInput> = reader.read_list()?;
+        let outputs = reader.read_list()?;
+
+        if decode_witness {
+            for input in inputs.iter_mut() {
+                input.witness = reader.read()?;
+            }
+            if inputs.iter().all(|input| !input.has_witness()) {
+                return SigningError::err(SigningErrorType::Error_input_parse).context(
+                    "Transaction is serialized in the extended format, but has no witness",
+                );
+            }
+        }
+
+        let locktime = reader.read()?;
+
+        Ok(Transaction {
+            version,
+            inputs,
+            outputs,
+            locktime,
+        })
+    }
+}
+
 impl TransactionPreimage for Transaction {
     fn preimage_tx(&self, args: &UtxoPreimageArgs) -> SigningResult<H256> {
         match args.signing_method {
@@ -317,6 +362,18 @@ impl Encodable for TransactionInput {
     }
 }
 
+impl Decodable for TransactionInput {
+    fn decode(reader: &mut Reader) -> SigningResult<Self> {
+        Ok(TransactionInput {
+            previous_output: reader.read()?,
+            script_sig: reader.read()?,
+            sequence: reader.read()?,
+            // Witness is decoded separately after all inputs and outputs.
+            witness: Witness::default(),
+        })
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct TransactionOutput {
     /// Transaction amount.
@@ -358,3 +415,12 @@ impl Encodable for TransactionOutput {
         self.value.encoded_size() + self.script_pubkey.encoded_size()
     }
 }
+
+impl Decodable for TransactionOutput {
+    fn decode(reader: &mut Reader) -> SigningResult<Self> {
+        Ok(TransactionOutput {
+            value: reader.read()?,
+            script_pubkey: reader.read()?,
+        })
+    }
+}
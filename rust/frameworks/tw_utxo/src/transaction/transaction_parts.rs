@@ -2,8 +2,11 @@
 //
 // Copyright © 2017 Trust Wallet.
 
+use crate::decode::reader::Reader;
+use crate::decode::Decodable;
 use crate::encode::stream::Stream;
 use crate::encode::Encodable;
+use tw_coin_entry::error::prelude::*;
 use tw_hash::H256;
 
 /// Amount in satoshis (Can be negative) in rare cases.
@@ -25,3 +28,12 @@ impl Encodable for OutPoint {
         self.hash.encoded_size() + self.index.encoded_size()
     }
 }
+
+impl Decodable for OutPoint {
+    fn decode(reader: &mut Reader) -> SigningResult<Self> {
+        Ok(OutPoint {
+            hash: reader.read()?,
+            index: reader.read()?,
+        })
+    }
+}
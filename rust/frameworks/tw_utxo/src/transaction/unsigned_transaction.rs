@@ -49,6 +49,14 @@ where
         &self.utxo_args
     }
 
+    /// Returns the total number of signatures required to sign the transaction.
+    pub fn signatures_number(&self) -> usize {
+        self.utxo_args
+            .iter()
+            .map(|utxo| utxo.spender_public_keys.len())
+            .sum()
+    }
+
     pub fn outputs(&self) -> &[Transaction::Output] {
         self.transaction.outputs()
     }
@@ -72,10 +80,26 @@ where
                     let estimated_sig = BitcoinEcdsaSignature::estimated();
                     ecdsa_constructor.get_spending_data(&estimated_sig)
                 },
+                SpendingDataConstructor::MultiEcdsa(ref multi_ecdsa_constructor) => {
+                    let estimated_sigs: Vec<_> = utxo_args
+                        .spender_public_keys
+                        .iter()
+                        .map(|_| BitcoinEcdsaSignature::estimated())
+                        .collect();
+                    multi_ecdsa_constructor.get_spending_data(&estimated_sigs)
+                },
                 SpendingDataConstructor::Schnorr(ref schnorr_constructor) => {
                     let estimated_sig = BitcoinSchnorrSignature::estimated();
                     schnorr_constructor.get_spending_data(&estimated_sig)
                 },
+                SpendingDataConstructor::MultiSchnorr(ref multi_schnorr_constructor) => {
+                    let estimated_sigs: Vec<_> = utxo_args
+                        .spender_public_keys
+                        .iter()
+                        .map(|_| BitcoinSchnorrSignature::estimated())
+                        .collect();
+                    multi_schnorr_constructor.get_spending_data(&estimated_sigs)
+                },
             };
 
             utxo.set_script_sig(estimated_spending.script_sig);
@@ -0,0 +1,82 @@
+use tw_encoding::hex::{DecodeHex, ToHex};
+use tw_utxo::decode::decode;
+use tw_utxo::transaction::standard_transaction::Transaction;
+use tw_utxo::transaction::transaction_interface::TransactionInterface;
+
+const LEGACY_TX: &str = "02000000017be4e642bb278018ab12277de9427773ad1c5f5b1d164a157e0d99aa48dc1c1e000000006a473044022078eda020d4b86fcb3af78ef919912e6d79b81164dbbb0b0b96da6ac58a2de4b102201a5fd8d48734d5a02371c4b5ee551a69dca3842edbf577d863cf8ae9fdbbd4590121036666dd712e05a487916384bfcd5973eb53e8038eccbbf97f7eed775b87389536ffffffff01c0aff629010000001976a9145eaaa4f458f9158f86afcba08dd7448d27045e3d88ac00000000";
+const SEGWIT_TX: &str = "010000000001027d4c6ade48ade606a8f9894222fb30f87b427c5861b0b7a293825ffc8bc1621f0100000000ffffffff8d0e94ed369601d13f32bd653071844dfe7139508d2e71d9c105d1faa58230b30100000000ffffffff01836d0000000000001600145360df8231ac5965147c9d90ca930a2aafb0523202483045022100f95f9ac5d39f4b47dcd8c86daaaeac86374258d9960f922333ba0d5fdaa15b7e0220761794672dc9fbd71398d608f72f5d21a0f6c1306c6b700ad0d82f747c221062012103a11506993946e20ea82686b157bf08f944759f43d91af8d84650ee73a482431c02483045022100eb6ba0dcc64af61b2186b7efdab1ff03784d585ee03437f9a53875e93429db080220015a268d308436d3564b83ceaed90bc7272ca164016298ea855d1936568002a7012103a11506993946e20ea82686b157bf08f944759f43d91af8d84650ee73a482431c00000000";
+
+fn txid_hex(tx: &Transaction) -> String {
+    tx.txid().to_hex()
+}
+
+#[test]
+fn decode_tx_legacy() {
+    let encoded = LEGACY_TX.decode_hex().unwrap();
+    let tx: Transaction = decode(&encoded).unwrap();
+
+    assert_eq!(tx.version, 2);
+    assert_eq!(tx.locktime, 0);
+    assert_eq!(tx.inputs.len(), 1);
+    assert_eq!(tx.outputs.len(), 1);
+    assert!(!tx.has_witness());
+    assert_eq!(tx.inputs[0].previous_output.index, 0);
+    assert_eq!(tx.inputs[0].sequence, u32::MAX);
+    assert_eq!(tx.outputs[0].value, 4_999_000_000);
+    assert_eq!(
+        tx.outputs[0].script_pubkey.to_vec().to_hex(),
+        "76a9145eaaa4f458f9158f86afcba08dd7448d27045e3d88ac"
+    );
+
+    assert_eq!(
+        txid_hex(&tx),
+        "c19f410bf1d70864220e93bca20f836aaaf8cdde84a46692616e9f4480d54885"
+    );
+    assert_eq!(tx.encode_out(), encoded);
+}
+
+#[test]
+fn decode_tx_segwit() {
+    let encoded = SEGWIT_TX.decode_hex().unwrap();
+    let tx: Transaction = decode(&encoded).unwrap();
+
+    assert_eq!(tx.version, 1);
+    assert_eq!(tx.inputs.len(), 2);
+    assert_eq!(tx.outputs.len(), 1);
+    assert!(tx.has_witness());
+    for input in tx.inputs.iter() {
+        assert!(input.script_sig.is_empty());
+        assert_eq!(input.witness.as_items().len(), 2);
+    }
+    assert_eq!(tx.outputs[0].value, 28_035);
+
+    assert_eq!(
+        txid_hex(&tx),
+        "5d6bf53576a54be4d92cd8abf58d28ecc9ea7956eaf970d24d6bfcb9fcfe9855"
+    );
+    assert_eq!(tx.encode_out(), encoded);
+}
+
+#[test]
+fn decode_tx_invalid() {
+    let legacy = LEGACY_TX.decode_hex().unwrap();
+    let segwit = SEGWIT_TX.decode_hex().unwrap();
+
+    // Truncated transaction.
+    decode::<Transaction>(&legacy[..legacy.len() - 1]).unwrap_err();
+    // Trailing bytes.
+    let mut trailing = legacy.clone();
+    trailing.push(0);
+    decode::<Transaction>(&trailing).unwrap_err();
+
+    // Unknown witness flag.
+    let mut unknown_flag = segwit.clone();
+    unknown_flag[5] = 0x02;
+    decode::<Transaction>(&unknown_flag).unwrap_err();
+
+    // Extended format without any witness.
+    let no_witness = "01000000000101000000000000000000000000000000000000000000000000000000000000000000000000ffffffff01000000000000000000000000000000"
+        .decode_hex()
+        .unwrap();
+    decode::<Transaction>(&no_witness).unwrap_err();
+}
@@ -2,12 +2,15 @@
 //
 // Copyright © 2017 Trust Wallet.
 
-use tw_coin_entry::derivation::Derivation;
+use tw_coin_entry::coin_context::CoinContext;
+use tw_coin_entry::derivation::{Derivation, DerivationPath};
 use tw_coin_entry::error::prelude::*;
 use tw_coin_entry::prefix::AddressPrefix;
 use tw_coin_registry::coin_type::CoinType;
 use tw_coin_registry::dispatcher::coin_dispatcher;
-use tw_keypair::tw::PublicKey;
+use tw_keypair::ecdsa::secp256k1;
+use tw_keypair::hd_wallet::{ExtendedPublicKey, Slip10Curve};
+use tw_keypair::tw::{PublicKey, PublicKeyType};
 use tw_memory::Data;
 use tw_misc::try_or_false;
 
@@ -63,6 +66,64 @@ impl AnyAddress {
         Ok(AnyAddress { coin, address })
     }
 
+    /// Creates an address of a `secp256k1` coin from an account extended public key
+    /// (`xpub`, `ypub`, `zpub`, ...) without knowing the private key.
+    ///
+    /// The `path` is the full derivation path of the address, e.g. `m/84'/0'/0'/0/2`.
+    /// Its leading account levels are skipped, and the rest must be non-hardened.
+    /// The last account level must match the index the extended key was derived with.
+    /// The address derivation and network are chosen by the SLIP-132 version of the extended key,
+    /// e.g. `ypub` derives P2SH-P2WPKH addresses, and `vpub` derives testnet P2WPKH addresses.
+    /// The network prefix can be overridden by `prefix`.
+    pub fn with_extended_public_key(
+        coin: CoinType,
+        extended_public_key: &str,
+        path: &DerivationPath,
+        prefix: Option<AddressPrefix>,
+    ) -> AddressResult<AnyAddress> {
+        let (ctx, entry) = coin_dispatcher(coin).map_err(|_| AddressError::UnknownCoinType)?;
+        let public_key_type = ctx.public_key_type();
+
+        let account = ExtendedPublicKey::from_base58(Slip10Curve::Secp256k1, extended_public_key)
+            .map_err(|_| AddressError::InvalidInput)?;
+        let derivation = Derivation::from_hd_version(account.version());
+        // Testnet prefixes make sense for coins with Base58 address prefixes only (UTXO based chains).
+        let prefix = match prefix {
+            Some(prefix) => Some(prefix),
+            None if ctx.p2pkh_prefix().is_some() => {
+                AddressPrefix::testnet_from_hd_version(&ctx, account.version())?
+            },
+            None => None,
+        };
+
+        // The skipped account levels can't be verified except the last one,
+        // which must be the index the account key was derived with.
+        let depth = account.depth() as usize;
+        if depth > path.path().len() {
+            return Err(AddressError::InvalidInput);
+        }
+        let (account_path, relative_path) = path.path().split_at(depth);
+        if let Some(account_index) = account_path.last() {
+            if account_index.to_bits() != account.child_number() {
+                return Err(AddressError::InvalidInput);
+            }
+        }
+        let child = account
+            .derive(relative_path)
+            .map_err(|_| AddressError::InvalidInput)?;
+
+        let public_key = secp256k1::PublicKey::try_from(child.public_key().as_slice())
+            .map_err(|_| AddressError::Internal)?;
+        let public_key = match public_key_type {
+            PublicKeyType::Secp256k1 => PublicKey::Secp256k1(public_key),
+            PublicKeyType::Secp256k1Extended => PublicKey::Secp256k1Extended(public_key),
+            _ => return Err(AddressError::PublicKeyTypeMismatch),
+        };
+
+        let address = entry.derive_address(&ctx, public_key, derivation, prefix)?;
+        Ok(AnyAddress { coin, address })
+    }
+
     /// Returns underlying data (public key or key hash).
     #[inline]
     pub fn get_data(&self) -> AddressResult<Data> {
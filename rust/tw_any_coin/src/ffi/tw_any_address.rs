@@ -5,7 +5,8 @@
 #![allow(clippy::missing_safety_doc)]
 
 use crate::any_address::AnyAddress;
-use tw_coin_entry::derivation::Derivation;
+use std::str::FromStr;
+use tw_coin_entry::derivation::{Derivation, DerivationPath};
 use tw_coin_entry::prefix::{AddressPrefix, BitcoinBase58Prefix};
 use tw_coin_registry::coin_type::CoinType;
 use tw_coin_registry::tw_derivation::TWDerivation;
@@ -122,6 +123,40 @@ pub unsafe extern "C" fn tw_any_address_create_with_public_key_derivation(
         .unwrap_or_else(|_| std::ptr::null_mut())
 }
 
+/// Creates an address from an account extended public key (xpub, ypub, zpub, ...) and a derivation path.
+/// The address derivation is chosen according to the extended public key version.
+///
+/// \param extended_public_key the account extended public key.
+/// \param coin coin type of the address. Must be a `secp256k1` coin.
+/// \param derivation_path the full derivation path of the address, e.g. `m/84'/0'/0'/0/2`.
+/// \return `TWAnyAddress` pointer or nullptr if the extended public key or the path is invalid.
+#[no_mangle]
+pub unsafe extern "C" fn tw_any_address_create_with_extended_public_key(
+    extended_public_key: *const TWString,
+    coin: u32,
+    derivation_path: *const TWString,
+) -> *mut TWAnyAddress {
+    let extended_public_key = try_or_else!(
+        TWString::from_ptr_as_ref(extended_public_key),
+        std::ptr::null_mut
+    );
+    let extended_public_key = try_or_else!(extended_public_key.as_str(), std::ptr::null_mut);
+    let derivation_path = try_or_else!(
+        TWString::from_ptr_as_ref(derivation_path),
+        std::ptr::null_mut
+    );
+    let derivation_path = try_or_else!(derivation_path.as_str(), std::ptr::null_mut);
+    let derivation_path = try_or_else!(
+        DerivationPath::from_str(derivation_path),
+        std::ptr::null_mut
+    );
+    let coin = try_or_else!(CoinType::try_from(coin), std::ptr::null_mut);
+
+    AnyAddress::with_extended_public_key(coin, extended_public_key, &derivation_path, None)
+        .map(|any_address| TWAnyAddress(any_address).into_ptr())
+        .unwrap_or_else(|_| std::ptr::null_mut())
+}
+
 /// Creates an bech32 address from a public key and a given hrp.
 ///
 /// \param public_key derivates the address from the public key.
@@ -4,6 +4,7 @@
 
 use crate::ffi::tw_any_address::{
     tw_any_address_create_base58_with_public_key, tw_any_address_create_bech32_with_public_key,
+    tw_any_address_create_with_extended_public_key,
     tw_any_address_create_with_public_key_derivation, tw_any_address_create_with_string,
     tw_any_address_data, tw_any_address_delete, tw_any_address_description,
     tw_any_address_is_valid, tw_any_address_is_valid_base58, tw_any_address_is_valid_bech32,
@@ -119,6 +120,34 @@ pub fn test_address_create_bech32_with_public_key(input: AddressCreateBech32With
     assert_eq!(actual.to_string(), Some(input.expected.to_string()));
 }
 
+pub struct AddressCreateWithExtendedPublicKey<'a> {
+    pub coin: CoinType,
+    pub extended_public_key: &'a str,
+    pub derivation_path: &'a str,
+    /// `None` if the address cannot be derived.
+    pub expected: Option<&'a str>,
+}
+
+pub fn test_address_create_with_extended_public_key(input: AddressCreateWithExtendedPublicKey<'_>) {
+    let extended_public_key = TWStringHelper::create(input.extended_public_key);
+    let derivation_path = TWStringHelper::create(input.derivation_path);
+
+    let any_address = TWAnyAddressHelper::wrap(unsafe {
+        tw_any_address_create_with_extended_public_key(
+            extended_public_key.ptr(),
+            input.coin as u32,
+            derivation_path.ptr(),
+        )
+    });
+
+    let Some(expected) = input.expected else {
+        assert!(any_address.ptr().is_null(), "{}", input.derivation_path);
+        return;
+    };
+    let actual = TWStringHelper::wrap(unsafe { tw_any_address_description(any_address.ptr()) });
+    assert_eq!(actual.to_string(), Some(expected.to_string()));
+}
+
 pub struct AddressBech32IsValid<'a> {
     pub coin: CoinType,
     pub address: &'a str,
@@ -17,6 +17,9 @@ pub trait CoinContext {
     /// Optional chain property.
     fn hrp(&self) -> Option<String>;
 
+    /// Optional testnet hrp (Bitcoin specific).
+    fn testnet_hrp(&self) -> Option<String>;
+
     /// Optional p2pkh prefix (Bitcoin specific).
     fn p2pkh_prefix(&self) -> Option<u8>;
 
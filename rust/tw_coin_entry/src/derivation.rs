@@ -5,6 +5,7 @@
 use serde::de::Error as DeError;
 use serde::{Deserialize, Deserializer};
 use std::str::FromStr;
+use tw_keypair::hd_wallet::HDVersion;
 
 pub use derivation_path::{ChildIndex, DerivationPath};
 
@@ -17,10 +18,12 @@ pub struct DerivationWithPath {
 }
 
 /// Extend this enum.
-#[derive(Clone, Copy, Default, Deserialize, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum Derivation {
     Segwit,
+    /// P2WPKH nested in P2SH (BIP49).
+    NestedSegwit,
     Legacy,
     Testnet,
     /// Default derivation.
@@ -29,6 +32,36 @@ pub enum Derivation {
     Default,
 }
 
+impl Derivation {
+    /// Returns a derivation of addresses that belong to an account
+    /// exported with the given SLIP-132 `version`, e.g. `zpub` to [`Derivation::Segwit`].
+    ///
+    /// Please note that the network is not taken into account,
+    /// use [`crate::prefix::AddressPrefix::testnet_from_hd_version`] to get a testnet address prefix.
+    pub fn from_hd_version(version: HDVersion) -> Derivation {
+        match version {
+            HDVersion::Xpub
+            | HDVersion::Xprv
+            | HDVersion::Tpub
+            | HDVersion::Tprv
+            | HDVersion::Ltub
+            | HDVersion::Ltpv
+            | HDVersion::Ttub
+            | HDVersion::Ttpv
+            | HDVersion::Dpub
+            | HDVersion::Dprv
+            | HDVersion::Dgub
+            | HDVersion::Dgpv => Derivation::Legacy,
+            HDVersion::Ypub | HDVersion::Yprv | HDVersion::Mtub | HDVersion::Mtpv => {
+                Derivation::NestedSegwit
+            },
+            HDVersion::Zpub | HDVersion::Zprv | HDVersion::Vpub | HDVersion::Vprv => {
+                Derivation::Segwit
+            },
+        }
+    }
+}
+
 fn deserialize_der_path<'de, D>(deserializer: D) -> Result<DerivationPath, D::Error>
 where
     D: Deserializer<'de>,
@@ -36,3 +69,32 @@ where
     let path = String::deserialize(deserializer)?;
     DerivationPath::from_str(&path).map_err(|e| DeError::custom(e.to_string()))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_derivation_from_hd_version() {
+        assert_eq!(
+            Derivation::from_hd_version(HDVersion::Xpub),
+            Derivation::Legacy
+        );
+        assert_eq!(
+            Derivation::from_hd_version(HDVersion::Ypub),
+            Derivation::NestedSegwit
+        );
+        assert_eq!(
+            Derivation::from_hd_version(HDVersion::Zpub),
+            Derivation::Segwit
+        );
+        assert_eq!(
+            Derivation::from_hd_version(HDVersion::Tpub),
+            Derivation::Legacy
+        );
+        assert_eq!(
+            Derivation::from_hd_version(HDVersion::Vpub),
+            Derivation::Segwit
+        );
+    }
+}
@@ -45,7 +45,10 @@ impl From<KeyPairError> for SigningError {
             | KeyPairError::InvalidSignature
             | KeyPairError::InvalidSignMessage
             | KeyPairError::SignatureVerifyError
-            | KeyPairError::InvalidEncryptedMessage => {
+            | KeyPairError::InvalidEncryptedMessage
+            | KeyPairError::InvalidMnemonic
+            | KeyPairError::InvalidDerivationPath
+            | KeyPairError::InvalidExtendedKey => {
                 TWError::new(SigningErrorType::Error_invalid_params)
             },
             KeyPairError::SigningError => TWError::new(SigningErrorType::Error_signing),
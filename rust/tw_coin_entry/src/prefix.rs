@@ -2,7 +2,9 @@
 //
 // Copyright © 2017 Trust Wallet.
 
+use crate::coin_context::CoinContext;
 use crate::error::prelude::*;
+use tw_keypair::hd_wallet::HDVersion;
 
 /// An address prefix. It can contain a bech32 prefix that can be used by `Cosmos` based chains.
 /// Extend when adding new blockchains.
@@ -12,6 +14,39 @@ pub enum AddressPrefix {
     BitcoinBase58(BitcoinBase58Prefix),
 }
 
+impl AddressPrefix {
+    /// Returns an address prefix of the testnet network an account exported
+    /// with the given SLIP-132 `version` belongs to.
+    /// The bech32 testnet prefix is taken from the `coin` registry entry.
+    ///
+    /// Returns `Ok(None)` if the version belongs to a mainnet network,
+    /// or an error if the `coin` has no testnet bech32 prefix.
+    pub fn testnet_from_hd_version(
+        coin: &dyn CoinContext,
+        version: HDVersion,
+    ) -> AddressResult<Option<AddressPrefix>> {
+        match version {
+            HDVersion::Tpub | HDVersion::Tprv => {
+                Ok(Some(AddressPrefix::BitcoinBase58(BitcoinBase58Prefix {
+                    p2pkh: 0x6f,
+                    p2sh: 0xc4,
+                })))
+            },
+            HDVersion::Vpub | HDVersion::Vprv => coin
+                .testnet_hrp()
+                .map(|hrp| Some(AddressPrefix::Hrp(hrp)))
+                .ok_or(AddressError::UnexpectedAddressPrefix),
+            HDVersion::Ttub | HDVersion::Ttpv => {
+                Ok(Some(AddressPrefix::BitcoinBase58(BitcoinBase58Prefix {
+                    p2pkh: 0x6f,
+                    p2sh: 0x3a,
+                })))
+            },
+            _ => Ok(None),
+        }
+    }
+}
+
 /// A blockchain's address prefix should be convertable from an `AddressPrefix`.
 pub trait Prefix: TryFrom<AddressPrefix, Error = AddressError> {}
 
@@ -39,6 +74,32 @@ pub struct BitcoinBase58Prefix {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_utils::test_context::TestCoinContext;
+
+    #[test]
+    fn test_testnet_from_hd_version() {
+        let bitcoin = TestCoinContext {
+            testnet_hrp: Some("tb".to_string()),
+            ..TestCoinContext::default()
+        };
+        let no_testnet_hrp = TestCoinContext::default();
+
+        let Some(AddressPrefix::Hrp(hrp)) =
+            AddressPrefix::testnet_from_hd_version(&bitcoin, HDVersion::Vpub).unwrap()
+        else {
+            panic!("Expected a bech32 prefix");
+        };
+        assert_eq!(hrp, "tb");
+
+        assert!(matches!(
+            AddressPrefix::testnet_from_hd_version(&no_testnet_hrp, HDVersion::Vpub),
+            Err(AddressError::UnexpectedAddressPrefix)
+        ));
+        assert!(matches!(
+            AddressPrefix::testnet_from_hd_version(&no_testnet_hrp, HDVersion::Zpub),
+            Ok(None)
+        ));
+    }
 
     #[test]
     fn test_no_prefix() {
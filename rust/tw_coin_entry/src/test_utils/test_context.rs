@@ -13,6 +13,7 @@ pub struct TestCoinContext {
     pub public_key_type: Option<PublicKeyType>,
     pub address_hasher: Option<Hasher>,
     pub hrp: Option<String>,
+    pub testnet_hrp: Option<String>,
     pub p2pkh: Option<u8>,
     pub p2sh: Option<u8>,
 }
@@ -43,6 +44,10 @@ impl CoinContext for TestCoinContext {
         self.hrp.clone()
     }
 
+    fn testnet_hrp(&self) -> Option<String> {
+        self.testnet_hrp.clone()
+    }
+
     fn p2pkh_prefix(&self) -> Option<u8> {
         self.p2pkh
     }
@@ -35,6 +35,11 @@ impl CoinContext for CoinRegistryContext {
         self.item.hrp.clone()
     }
 
+    #[inline]
+    fn testnet_hrp(&self) -> Option<String> {
+        self.item.testnet_hrp.clone()
+    }
+
     #[inline]
     fn p2pkh_prefix(&self) -> Option<u8> {
         self.item.p2pkh_prefix
@@ -2,17 +2,21 @@
 //
 // Copyright © 2017 Trust Wallet.
 
+use crate::abi::custom_error::CustomError;
+use crate::abi::event::Event;
 use crate::abi::function::Function;
 use crate::abi::{AbiErrorKind, AbiResult};
 use serde::{Deserialize, Deserializer};
 use std::collections::BTreeMap;
 use tw_coin_entry::error::prelude::*;
+use tw_hash::{H256, H32};
 
 /// API building calls to contracts ABI.
-/// Consider adding missing field such as `errors`, `events` etc.
 #[derive(Clone, Debug, Default)]
 pub struct Contract {
     pub functions: BTreeMap<String, Vec<Function>>,
+    pub events: BTreeMap<String, Vec<Event>>,
+    pub errors: BTreeMap<String, Vec<CustomError>>,
 }
 
 impl Contract {
@@ -26,6 +30,26 @@ impl Contract {
             .or_tw_err(AbiErrorKind::Error_abi_mismatch)
             .with_context(|| format!("The given Smart Contract does not have '{name}' function"))
     }
+
+    /// Get the non-anonymous event which signature hash is equal to the given `topic0`.
+    pub fn event_by_topic(&self, topic0: &H256) -> AbiResult<&Event> {
+        self.events
+            .values()
+            .flatten()
+            .find(|event| !event.anonymous && event.topic0() == *topic0)
+            .or_tw_err(AbiErrorKind::Error_abi_mismatch)
+            .with_context(|| format!("The given Smart Contract does not have {topic0} event"))
+    }
+
+    /// Get the custom error which selector is equal to the given one.
+    pub fn error_by_selector(&self, selector: &H32) -> AbiResult<&CustomError> {
+        self.errors
+            .values()
+            .flatten()
+            .find(|error| error.selector() == *selector)
+            .or_tw_err(AbiErrorKind::Error_abi_mismatch)
+            .with_context(|| format!("The given Smart Contract does not have {selector} error"))
+    }
 }
 
 impl<'de> Deserialize<'de> for Contract {
@@ -33,20 +57,19 @@ impl<'de> Deserialize<'de> for Contract {
     where
         D: Deserializer<'de>,
     {
-        /// Consider adding missing field such as `errors`, `events` etc.
         #[derive(Deserialize)]
         #[serde(tag = "type", rename_all = "snake_case")]
         enum Operation {
             Function(Function),
+            Event(Event),
+            Error(CustomError),
             #[serde(other)]
             Unsupported,
         }
 
         let operations: Vec<Operation> = Vec::deserialize(deserializer)?;
 
-        let mut result = Contract {
-            functions: BTreeMap::default(),
-        };
+        let mut result = Contract::default();
         for operation in operations {
             match operation {
                 Operation::Function(fun) => result
@@ -54,6 +77,16 @@ impl<'de> Deserialize<'de> for Contract {
                     .entry(fun.name.clone())
                     .or_default()
                     .push(fun),
+                Operation::Event(event) => result
+                    .events
+                    .entry(event.name.clone())
+                    .or_default()
+                    .push(event),
+                Operation::Error(error) => result
+                    .errors
+                    .entry(error.name.clone())
+                    .or_default()
+                    .push(error),
                 Operation::Unsupported => (),
             }
         }
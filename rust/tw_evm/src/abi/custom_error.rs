@@ -0,0 +1,50 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::abi::decode::decode_params;
+use crate::abi::param::Param;
+use crate::abi::param_token::NamedToken;
+use crate::abi::param_type::ParamType;
+use crate::abi::signature::short_signature;
+use crate::abi::AbiResult;
+use itertools::Itertools;
+use serde::Deserialize;
+use tw_hash::H32;
+
+/// A custom error declared in a Smart Contract ABI, like `error InsufficientBalance(uint256 available, uint256 required)`.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct CustomError {
+    /// Error name.
+    pub name: String,
+    /// Error parameters.
+    pub inputs: Vec<Param>,
+}
+
+impl CustomError {
+    /// Returns a signature that uniquely identifies this error.
+    ///
+    /// Example: `InsufficientBalance(uint256,uint256)`
+    pub fn signature(&self) -> String {
+        let inputs = self.inputs.iter().map(|p| p.kind.to_type_long()).join(",");
+        format!("{}({inputs})", self.name)
+    }
+
+    /// Returns the first four bytes of the Keccak-256 hash of the error signature.
+    pub fn selector(&self) -> H32 {
+        short_signature(&self.name, &self.param_types())
+    }
+
+    /// Parses the revert data excluding the selector to a list of tokens.
+    pub fn decode(&self, data: &[u8]) -> AbiResult<Vec<NamedToken>> {
+        // `decode_params` doesn't allow empty data, but it's fine if the error has no parameters.
+        if self.inputs.is_empty() {
+            return Ok(Vec::default());
+        }
+        decode_params(&self.inputs, data)
+    }
+
+    fn param_types(&self) -> Vec<ParamType> {
+        self.inputs.iter().map(|p| p.kind.clone()).collect()
+    }
+}
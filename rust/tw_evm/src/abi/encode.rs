@@ -3,6 +3,8 @@
 // Copyright © 2017 Trust Wallet.
 
 use crate::abi::token::Token;
+use crate::abi::{AbiError, AbiErrorKind, AbiResult};
+use tw_coin_entry::error::prelude::*;
 use tw_hash::H256;
 use tw_memory::Data;
 
@@ -15,6 +17,20 @@ pub fn encode_tokens(tokens: &[Token]) -> Data {
         .collect()
 }
 
+/// Encodes tokens in the non-standard packed mode, the same way as Solidity `abi.encodePacked` does:
+/// - types shorter than 32 bytes are concatenated directly, without padding or sign extension;
+/// - dynamic types are encoded in-place and without the length;
+/// - array elements are padded, but still encoded in-place.
+///
+/// Tuples, nested arrays and arrays of dynamic types are not supported.
+pub fn encode_packed(tokens: &[Token]) -> AbiResult<Data> {
+    let mut result = Data::default();
+    for token in tokens {
+        encode_packed_append(&mut result, token)?;
+    }
+    Ok(result)
+}
+
 #[derive(Debug)]
 enum Mediate<'a> {
     // head
@@ -162,6 +178,54 @@ fn encode_token_append(data: &mut Vec<H256>, token: &Token) {
     }
 }
 
+fn encode_packed_append(data: &mut Data, token: &Token) -> AbiResult<()> {
+    match token {
+        Token::Address(address) => data.extend_from_slice(address.as_slice()),
+        Token::Bytes(bytes) => data.extend_from_slice(bytes),
+        Token::String(s) => data.extend_from_slice(s.as_bytes()),
+        Token::FixedBytes(bytes) => data.extend_from_slice(bytes),
+        Token::Int { int, bits } => {
+            let encoded = int.to_big_endian();
+            data.extend_from_slice(&encoded[H256::LEN - bits.get() / 8..]);
+        },
+        Token::Uint { uint, bits } => {
+            let encoded = uint.to_big_endian();
+            data.extend_from_slice(&encoded[H256::LEN - bits.get() / 8..]);
+        },
+        Token::Bool(b) => data.push(*b as u8),
+        Token::Array { arr, .. } => encode_packed_array_append(data, arr)?,
+        Token::FixedArray { arr, .. } => encode_packed_array_append(data, arr)?,
+        Token::Tuple { .. } => {
+            return AbiError::err(AbiErrorKind::Error_invalid_param_type)
+                .context("Tuples are not supported in packed mode");
+        },
+    }
+    Ok(())
+}
+
+fn encode_packed_array_append(data: &mut Data, elements: &[Token]) -> AbiResult<()> {
+    for element in elements {
+        let is_nested = matches!(
+            element,
+            Token::Array { .. } | Token::FixedArray { .. } | Token::Tuple { .. }
+        );
+        if is_nested || element.is_dynamic() {
+            return AbiError::err(AbiErrorKind::Error_invalid_param_type).with_context(|| {
+                format!(
+                    "'{}' array elements are not supported in packed mode",
+                    element.type_short()
+                )
+            });
+        }
+
+        // Array elements are padded to 32 bytes.
+        let mut padded = Vec::with_capacity(1);
+        encode_token_append(&mut padded, element);
+        data.extend(padded.into_iter().flat_map(H256::take));
+    }
+    Ok(())
+}
+
 /// Converts a u32 to a right aligned array of 32 bytes.
 pub fn pad_u32(value: u32) -> H256 {
     let mut padded = H256::default();
@@ -210,6 +274,7 @@ mod tests {
     use crate::abi::param_token::NamedToken;
     use crate::abi::param_type::constructor::TypeConstructor;
     use crate::abi::param_type::ParamType;
+    use crate::abi::uint::UintBits;
     use tw_encoding::hex::DecodeHex;
     use tw_number::{I256, U256};
 
@@ -1083,4 +1148,61 @@ mod tests {
         .unwrap();
         assert_eq!(encoded, expected);
     }
+
+    #[test]
+    fn encode_packed_elementary_types() {
+        // abi.encodePacked(int16(-1), bytes1(0x42), uint16(0x03), string("Hello, world!"))
+        let tokens = [
+            Token::int(16, I256::from(-1_i64)).unwrap(),
+            Token::FixedBytes(NonEmptyBytes::new(vec![0x42]).unwrap()),
+            Token::uint(16, U256::from(3_u64)).unwrap(),
+            Token::String("Hello, world!".to_string()),
+        ];
+        let encoded = encode_packed(&tokens).unwrap();
+        let expected = "ffff42000348656c6c6f2c20776f726c6421".decode_hex().unwrap();
+        assert_eq!(encoded, expected);
+    }
+
+    #[test]
+    fn encode_packed_address_bool_and_array() {
+        let tokens = [
+            Token::Address("0x1111111111111111111111111111111111111111".into()),
+            Token::Bool(true),
+            Token::Array {
+                arr: vec![
+                    Token::uint(8, U256::from(1_u64)).unwrap(),
+                    Token::uint(8, U256::from(2_u64)).unwrap(),
+                ],
+                kind: ParamType::Uint {
+                    bits: UintBits::new(8).unwrap(),
+                },
+            },
+        ];
+        let encoded = encode_packed(&tokens).unwrap();
+        let expected = concat!(
+            "1111111111111111111111111111111111111111",
+            "01",
+            "0000000000000000000000000000000000000000000000000000000000000001",
+            "0000000000000000000000000000000000000000000000000000000000000002",
+        )
+        .decode_hex()
+        .unwrap();
+        assert_eq!(encoded, expected);
+    }
+
+    #[test]
+    fn encode_packed_unsupported() {
+        let tuple = Token::Tuple {
+            params: vec![NamedToken::with_token(Token::Bool(true))],
+        };
+        let err = encode_packed(&[tuple]).unwrap_err();
+        assert_eq!(*err.error_type(), AbiErrorKind::Error_invalid_param_type);
+
+        let array_of_strings = Token::Array {
+            arr: vec![Token::String("foo".to_string())],
+            kind: ParamType::String,
+        };
+        let err = encode_packed(&[array_of_strings]).unwrap_err();
+        assert_eq!(*err.error_type(), AbiErrorKind::Error_invalid_param_type);
+    }
 }
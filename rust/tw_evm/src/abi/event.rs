@@ -0,0 +1,198 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::abi::decode::{decode_params, decode_value};
+use crate::abi::non_empty_array::NonEmptyBytes;
+use crate::abi::param::Param;
+use crate::abi::param_token::NamedToken;
+use crate::abi::param_type::ParamType;
+use crate::abi::signature::long_signature;
+use crate::abi::token::Token;
+use crate::abi::{AbiError, AbiErrorKind, AbiResult};
+use itertools::Itertools;
+use serde::Deserialize;
+use tw_coin_entry::error::prelude::*;
+use tw_hash::H256;
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct EventParam {
+    #[serde(flatten)]
+    pub param: Param,
+    /// Whether the parameter is stored in the log topics instead of the log data.
+    #[serde(default)]
+    pub indexed: bool,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct Event {
+    /// Event name.
+    pub name: String,
+    /// Event parameters.
+    pub inputs: Vec<EventParam>,
+    /// Anonymous events do not have the event signature hash in the first topic.
+    #[serde(default)]
+    pub anonymous: bool,
+}
+
+impl Event {
+    /// Returns a signature that uniquely identifies this event.
+    ///
+    /// Example: `Transfer(address,address,uint256)`
+    pub fn signature(&self) -> String {
+        let inputs = self
+            .inputs
+            .iter()
+            .map(|p| p.param.kind.to_type_long())
+            .join(",");
+        format!("{}({inputs})", self.name)
+    }
+
+    /// Returns the Keccak-256 hash of the event signature, that is the first log topic of non-anonymous events.
+    pub fn topic0(&self) -> H256 {
+        let param_types: Vec<_> = self.inputs.iter().map(|p| p.param.kind.clone()).collect();
+        long_signature(&self.name, &param_types)
+    }
+
+    /// Parses the log topics and data to a list of tokens in the order the parameters are declared.
+    ///
+    /// Please note that indexed parameters of dynamic types (`string`, `bytes`, arrays and tuples)
+    /// are stored as Keccak-256 hashes of their values, so they are returned as `bytes32` tokens.
+    pub fn decode_log(&self, topics: &[H256], data: &[u8]) -> AbiResult<Vec<NamedToken>> {
+        let indexed_topics = if self.anonymous {
+            topics
+        } else {
+            let (topic0, indexed_topics) = topics
+                .split_first()
+                .or_tw_err(AbiErrorKind::Error_decoding_data)
+                .context("Expected at least one log topic")?;
+            if *topic0 != self.topic0() {
+                return AbiError::err(AbiErrorKind::Error_abi_mismatch).with_context(|| {
+                    format!("Log topic0 does not match '{}' event", self.signature())
+                });
+            }
+            indexed_topics
+        };
+
+        let indexed_count = self.inputs.iter().filter(|p| p.indexed).count();
+        if indexed_topics.len() != indexed_count {
+            return AbiError::err(AbiErrorKind::Error_abi_mismatch).with_context(|| {
+                format!(
+                    "Expected {indexed_count} indexed topics, found {}",
+                    indexed_topics.len()
+                )
+            });
+        }
+
+        let non_indexed: Vec<_> = self
+            .inputs
+            .iter()
+            .filter(|p| !p.indexed)
+            .map(|p| p.param.clone())
+            .collect();
+        // `decode_params` doesn't allow empty data, but it's fine if all parameters are indexed.
+        let mut non_indexed_tokens = if non_indexed.is_empty() {
+            Vec::default()
+        } else {
+            decode_params(&non_indexed, data)?
+        }
+        .into_iter();
+        let mut indexed_topics = indexed_topics.iter();
+
+        let mut tokens = Vec::with_capacity(self.inputs.len());
+        for input in self.inputs.iter() {
+            let token = if input.indexed {
+                let topic = indexed_topics
+                    .next()
+                    .expect("The number of indexed topics is checked above");
+                NamedToken::with_param_and_token(&input.param, decode_topic(&input.param, topic)?)
+            } else {
+                non_indexed_tokens
+                    .next()
+                    .or_tw_err(AbiErrorKind::Error_decoding_data)
+                    .context("Not enough non-indexed parameters in the log data")?
+            };
+            tokens.push(token);
+        }
+        Ok(tokens)
+    }
+}
+
+fn decode_topic(param: &Param, topic: &H256) -> AbiResult<Token> {
+    match param.kind {
+        ParamType::Address
+        | ParamType::FixedBytes { .. }
+        | ParamType::Int { .. }
+        | ParamType::Uint { .. }
+        | ParamType::Bool => decode_value(&param.kind, topic.as_slice()),
+        // Reference types are stored as hashes of their encoded values.
+        _ => {
+            let hash = NonEmptyBytes::new(topic.to_vec()).expect("H256 must not be empty");
+            Ok(Token::FixedBytes(hash))
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::address::Address;
+    use tw_encoding::hex::DecodeHex;
+    use tw_number::U256;
+
+    fn transfer_event() -> Event {
+        serde_json::from_str(
+            r#"{
+                "anonymous": false,
+                "inputs": [
+                    { "indexed": true, "name": "from", "type": "address" },
+                    { "indexed": true, "name": "to", "type": "address" },
+                    { "indexed": false, "name": "value", "type": "uint256" }
+                ],
+                "name": "Transfer",
+                "type": "event"
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_event_topic0() {
+        let event = transfer_event();
+        assert_eq!(event.signature(), "Transfer(address,address,uint256)");
+        assert_eq!(
+            event.topic0(),
+            H256::from("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef")
+        );
+    }
+
+    #[test]
+    fn test_decode_transfer_log() {
+        let topics = [
+            H256::from("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"),
+            H256::from("0000000000000000000000009d8a62f656a8d1615c1294fd71e9cfb3e4855a4f"),
+            H256::from("0000000000000000000000003535353535353535353535353535353535353535"),
+        ];
+        let data = "00000000000000000000000000000000000000000000000000000000000003e8"
+            .decode_hex()
+            .unwrap();
+
+        let tokens = transfer_event().decode_log(&topics, &data).unwrap();
+        let values: Vec<_> = tokens.into_iter().map(|t| t.value).collect();
+        assert_eq!(
+            values,
+            vec![
+                Token::Address(Address::from("0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F")),
+                Token::Address(Address::from("0x3535353535353535353535353535353535353535")),
+                Token::u256(U256::from(1000_u64)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_decode_log_topic0_mismatch() {
+        let topics = [H256::default(), H256::default(), H256::default()];
+        let err = transfer_event().decode_log(&topics, &[]).unwrap_err();
+        assert_eq!(*err.error_type(), AbiErrorKind::Error_abi_mismatch);
+    }
+}
@@ -5,14 +5,17 @@
 use tw_coin_entry::error::prelude::*;
 
 pub mod contract;
+pub mod custom_error;
 pub mod decode;
 pub mod encode;
+pub mod event;
 pub mod function;
 pub mod non_empty_array;
 pub mod param;
 pub mod param_token;
 pub mod param_type;
 pub mod prebuild;
+pub mod revert;
 pub mod signature;
 pub mod token;
 pub mod uint;
@@ -0,0 +1,35 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::abi::contract::Contract;
+use crate::abi::non_empty_array::NonEmptyBytes;
+use crate::abi::token::Token;
+use crate::abi::AbiResult;
+use lazy_static::lazy_static;
+use tw_hash::H256;
+use tw_memory::Data;
+
+/// Generated via https://remix.ethereum.org
+/// https://github.com/OpenZeppelin/openzeppelin-contracts/blob/v4.9.2/contracts/interfaces/IERC1271.sol
+const ERC1271_ABI: &str = include_str!("resource/erc1271.abi.json");
+
+/// The value returned by `isValidSignature` if the signature is valid.
+/// `bytes4(keccak256("isValidSignature(bytes32,bytes)"))`.
+pub const ERC1271_MAGIC_VALUE: [u8; 4] = [0x16, 0x26, 0xba, 0x7e];
+
+lazy_static! {
+    static ref ERC1271: Contract = serde_json::from_str(ERC1271_ABI).unwrap();
+}
+
+pub struct Erc1271;
+
+impl Erc1271 {
+    pub fn is_valid_signature(hash: H256, signature: Data) -> AbiResult<Data> {
+        let func = ERC1271.function("isValidSignature")?;
+        func.encode_input(&[
+            Token::FixedBytes(NonEmptyBytes::new(hash.to_vec())?),
+            Token::Bytes(signature),
+        ])
+    }
+}
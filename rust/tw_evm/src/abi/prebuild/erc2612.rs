@@ -0,0 +1,48 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::abi::contract::Contract;
+use crate::abi::non_empty_array::NonEmptyBytes;
+use crate::abi::token::Token;
+use crate::abi::AbiResult;
+use crate::address::Address;
+use lazy_static::lazy_static;
+use tw_hash::H256;
+use tw_memory::Data;
+use tw_number::U256;
+
+/// Generated via https://remix.ethereum.org
+/// https://github.com/OpenZeppelin/openzeppelin-contracts/blob/v4.9.2/contracts/token/ERC20/extensions/IERC20Permit.sol
+const ERC2612_ABI: &str = include_str!("resource/erc2612.abi.json");
+
+lazy_static! {
+    static ref ERC2612: Contract = serde_json::from_str(ERC2612_ABI).unwrap();
+}
+
+pub struct PermitArgs {
+    pub owner: Address,
+    pub spender: Address,
+    pub value: U256,
+    pub deadline: U256,
+    pub v: u8,
+    pub r: H256,
+    pub s: H256,
+}
+
+pub struct Erc2612;
+
+impl Erc2612 {
+    pub fn permit(args: PermitArgs) -> AbiResult<Data> {
+        let func = ERC2612.function("permit")?;
+        func.encode_input(&[
+            Token::Address(args.owner),
+            Token::Address(args.spender),
+            Token::u256(args.value),
+            Token::u256(args.deadline),
+            Token::uint(8, args.v)?,
+            Token::FixedBytes(NonEmptyBytes::new(args.r.to_vec())?),
+            Token::FixedBytes(NonEmptyBytes::new(args.s.to_vec())?),
+        ])
+    }
+}
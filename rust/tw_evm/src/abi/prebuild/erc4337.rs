@@ -4,6 +4,7 @@
 
 use crate::abi::contract::Contract;
 use crate::abi::param_type::ParamType;
+use crate::abi::prebuild::encode_function_call;
 use crate::abi::token::Token;
 use crate::abi::AbiResult;
 use crate::address::Address;
@@ -38,6 +39,12 @@ impl Erc4337SimpleAccount {
         ])
     }
 
+    /// `initialize(address anOwner)`, called by the account factory through the account proxy.
+    pub fn encode_initialize(owner: Address) -> AbiResult<Data> {
+        let func = ERC4337_SIMPLE_ACCOUNT.function("initialize")?;
+        func.encode_input(&[Token::Address(owner)])
+    }
+
     pub fn encode_execute_batch<I>(args: I) -> AbiResult<Data>
     where
         I: IntoIterator<Item = ExecuteArgs>,
@@ -67,3 +74,14 @@ impl Erc4337SimpleAccount {
         ])
     }
 }
+
+/// `SimpleAccountFactory` and compatible factories (e.g. `LightAccountFactory`).
+/// https://github.com/eth-infinitism/account-abstraction/blob/v0.7.0/contracts/samples/SimpleAccountFactory.sol
+pub struct Erc4337SimpleAccountFactory;
+
+impl Erc4337SimpleAccountFactory {
+    /// `createAccount(address owner, uint256 salt)`.
+    pub fn encode_create_account(owner: Address, salt: U256) -> Data {
+        encode_function_call("createAccount", &[Token::Address(owner), Token::u256(salt)])
+    }
+}
@@ -0,0 +1,114 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::abi::encode::encode_tokens;
+use crate::abi::non_empty_array::NonEmptyBytes;
+use crate::abi::param::Param;
+use crate::abi::param_type::ParamType;
+use crate::abi::prebuild::encode_function_call;
+use crate::abi::prebuild::erc4337::ExecuteArgs;
+use crate::abi::prebuild::unnamed;
+use crate::abi::token::Token;
+use crate::abi::AbiResult;
+use crate::address::Address;
+use tw_hash::H256;
+use tw_memory::Data;
+use tw_number::U256;
+
+/// ERC-7579 execution mode of a single call: `callType = 0x00`, `execType = 0x00` (revert on failure).
+const EXEC_MODE_SINGLE: H256 = H256::new();
+/// ERC-7579 `callType` of a batch call.
+const CALL_TYPE_BATCH: u8 = 0x01;
+/// Kernel validation type of a validator module.
+const VALIDATION_TYPE_VALIDATOR: u8 = 0x01;
+
+/// Kernel v3.1 smart account, ERC-7579 compatible.
+/// https://github.com/zerodevapp/kernel/blob/v3.1/src/Kernel.sol
+pub struct Kernel;
+
+impl Kernel {
+    /// `execute(bytes32 execMode, bytes executionCalldata)` of a single call,
+    /// where `executionCalldata = target (address) || value (uint256) || callData`.
+    pub fn encode_execute(args: ExecuteArgs) -> AbiResult<Data> {
+        let execution_calldata = [
+            args.to.as_slice(),
+            args.value.to_big_endian().as_slice(),
+            args.data.as_slice(),
+        ]
+        .concat();
+        Self::execute(EXEC_MODE_SINGLE, execution_calldata)
+    }
+
+    /// `execute(bytes32 execMode, bytes executionCalldata)` of a batch call,
+    /// where `executionCalldata = abi.encode(Execution[])`.
+    pub fn encode_execute_batch<I>(args: I) -> AbiResult<Data>
+    where
+        I: IntoIterator<Item = ExecuteArgs>,
+    {
+        let executions = args
+            .into_iter()
+            .map(|arg| Token::Tuple {
+                params: vec![
+                    unnamed(Token::Address(arg.to)),
+                    unnamed(Token::u256(arg.value)),
+                    unnamed(Token::Bytes(arg.data)),
+                ],
+            })
+            .collect();
+        let execution_calldata = encode_tokens(&[Token::array(Self::execution_type(), executions)]);
+
+        let mut exec_mode = H256::default();
+        exec_mode[0] = CALL_TYPE_BATCH;
+        Self::execute(exec_mode, execution_calldata)
+    }
+
+    /// `initialize(bytes21 _rootValidator, address hook, bytes validatorData, bytes hookData, bytes[] initConfig)`,
+    /// where `_rootValidator = 0x01 || validator`.
+    pub fn encode_initialize(validator: Address, validator_data: Data) -> AbiResult<Data> {
+        let root_validator = [&[VALIDATION_TYPE_VALIDATOR], validator.as_slice()].concat();
+        Ok(encode_function_call(
+            "initialize",
+            &[
+                Token::FixedBytes(NonEmptyBytes::new(root_validator)?),
+                Token::Address(Address::default()),
+                Token::Bytes(validator_data),
+                Token::Bytes(Data::default()),
+                Token::array(ParamType::Bytes, Vec::default()),
+            ],
+        ))
+    }
+
+    /// `KernelFactory.createAccount(bytes data, bytes32 salt)`.
+    pub fn encode_create_account(data: Data, salt: U256) -> AbiResult<Data> {
+        let salt = NonEmptyBytes::new(salt.to_big_endian().to_vec())?;
+        Ok(encode_function_call(
+            "createAccount",
+            &[Token::Bytes(data), Token::FixedBytes(salt)],
+        ))
+    }
+
+    fn execute(exec_mode: H256, execution_calldata: Data) -> AbiResult<Data> {
+        let exec_mode = NonEmptyBytes::new(exec_mode.to_vec())?;
+        Ok(encode_function_call(
+            "execute",
+            &[
+                Token::FixedBytes(exec_mode),
+                Token::Bytes(execution_calldata),
+            ],
+        ))
+    }
+
+    /// `Execution(address target, uint256 value, bytes callData)`.
+    fn execution_type() -> ParamType {
+        let params = [ParamType::Address, ParamType::u256(), ParamType::Bytes]
+            .into_iter()
+            .map(|kind| Param {
+                name: None,
+                kind,
+                internal_type: None,
+            })
+            .collect();
+        ParamType::Tuple { params }
+    }
+}
@@ -2,7 +2,34 @@
 //
 // Copyright © 2017 Trust Wallet.
 
+use crate::abi::encode::encode_tokens;
+use crate::abi::param_token::NamedToken;
+use crate::abi::signature::short_signature;
+use crate::abi::token::Token;
+use tw_memory::Data;
+
 pub mod erc1155;
+pub mod erc1271;
 pub mod erc20;
+pub mod erc2612;
 pub mod erc4337;
 pub mod erc721;
+pub mod kernel;
+pub mod permit2;
+pub mod safe;
+
+/// Encodes a function call with the selector derived from the given tokens.
+/// Used for contracts whose ABI is not worth being included as a JSON resource.
+pub(crate) fn encode_function_call(name: &str, tokens: &[Token]) -> Data {
+    let param_types: Vec<_> = tokens.iter().map(Token::to_param_type).collect();
+    let selector = short_signature(name, &param_types);
+    selector.into_iter().chain(encode_tokens(tokens)).collect()
+}
+
+pub(crate) fn unnamed(value: Token) -> NamedToken {
+    NamedToken {
+        name: None,
+        value,
+        internal_type: None,
+    }
+}
@@ -0,0 +1,127 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::abi::encode::encode_tokens;
+use crate::abi::param::Param;
+use crate::abi::param_token::NamedToken;
+use crate::abi::param_type::constructor::TypeConstructor;
+use crate::abi::param_type::ParamType;
+use crate::abi::signature::short_signature;
+use crate::abi::token::Token;
+use crate::abi::AbiResult;
+use crate::address::Address;
+use tw_memory::Data;
+use tw_number::U256;
+
+pub struct PermitDetailsArgs {
+    pub token: Address,
+    pub amount: U256,
+    pub expiration: U256,
+    pub nonce: U256,
+}
+
+pub struct PermitSingleArgs {
+    pub owner: Address,
+    pub details: PermitDetailsArgs,
+    pub spender: Address,
+    pub sig_deadline: U256,
+    pub signature: Data,
+}
+
+pub struct PermitBatchArgs {
+    pub owner: Address,
+    pub details: Vec<PermitDetailsArgs>,
+    pub spender: Address,
+    pub sig_deadline: U256,
+    pub signature: Data,
+}
+
+/// Uniswap Permit2 `IAllowanceTransfer` calls.
+/// https://github.com/Uniswap/permit2/blob/main/src/interfaces/IAllowanceTransfer.sol
+///
+/// `permit` is overloaded, so calls are encoded according to the given tokens.
+pub struct Permit2;
+
+impl Permit2 {
+    /// `permit(address owner, PermitSingle permitSingle, bytes signature)`.
+    pub fn permit_single(args: PermitSingleArgs) -> AbiResult<Data> {
+        let permit_single = Token::Tuple {
+            params: vec![
+                unnamed(Self::permit_details(args.details)?),
+                unnamed(Token::Address(args.spender)),
+                unnamed(Token::u256(args.sig_deadline)),
+            ],
+        };
+        Ok(encode_permit(&[
+            Token::Address(args.owner),
+            permit_single,
+            Token::Bytes(args.signature),
+        ]))
+    }
+
+    /// `permit(address owner, PermitBatch permitBatch, bytes signature)`.
+    pub fn permit_batch(args: PermitBatchArgs) -> AbiResult<Data> {
+        let details = args
+            .details
+            .into_iter()
+            .map(Self::permit_details)
+            .collect::<AbiResult<Vec<_>>>()?;
+        let details_type = Self::permit_details_type()?;
+
+        let permit_batch = Token::Tuple {
+            params: vec![
+                unnamed(Token::array(details_type, details)),
+                unnamed(Token::Address(args.spender)),
+                unnamed(Token::u256(args.sig_deadline)),
+            ],
+        };
+        Ok(encode_permit(&[
+            Token::Address(args.owner),
+            permit_batch,
+            Token::Bytes(args.signature),
+        ]))
+    }
+
+    fn permit_details(details: PermitDetailsArgs) -> AbiResult<Token> {
+        Ok(Token::Tuple {
+            params: vec![
+                unnamed(Token::Address(details.token)),
+                unnamed(Token::uint(160, details.amount)?),
+                unnamed(Token::uint(48, details.expiration)?),
+                unnamed(Token::uint(48, details.nonce)?),
+            ],
+        })
+    }
+
+    fn permit_details_type() -> AbiResult<ParamType> {
+        let params = [
+            ParamType::Address,
+            ParamType::uint(160)?,
+            ParamType::uint(48)?,
+            ParamType::uint(48)?,
+        ]
+        .into_iter()
+        .map(|kind| Param {
+            name: None,
+            kind,
+            internal_type: None,
+        })
+        .collect();
+        Ok(ParamType::Tuple { params })
+    }
+}
+
+fn encode_permit(tokens: &[Token]) -> Data {
+    let param_types: Vec<_> = tokens.iter().map(Token::to_param_type).collect();
+    let selector = short_signature("permit", &param_types);
+    selector.into_iter().chain(encode_tokens(tokens)).collect()
+}
+
+fn unnamed(value: Token) -> NamedToken {
+    NamedToken {
+        name: None,
+        value,
+        internal_type: None,
+    }
+}
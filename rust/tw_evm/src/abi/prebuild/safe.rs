@@ -0,0 +1,120 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::abi::param_type::ParamType;
+use crate::abi::prebuild::encode_function_call;
+use crate::abi::token::Token;
+use crate::abi::AbiResult;
+use crate::address::Address;
+use tw_memory::Data;
+use tw_number::U256;
+
+/// Safe operation type: a regular call.
+pub const SAFE_OPERATION_CALL: u8 = 0;
+/// Safe operation type: a delegate call, e.g. to a `MultiSend` contract.
+pub const SAFE_OPERATION_DELEGATE_CALL: u8 = 1;
+
+pub struct SafeSetupArgs {
+    pub owners: Vec<Address>,
+    pub threshold: U256,
+    pub to: Address,
+    pub data: Data,
+    pub fallback_handler: Address,
+    pub payment_token: Address,
+    pub payment: U256,
+    pub payment_receiver: Address,
+}
+
+pub struct MultiSendTransaction {
+    pub operation: u8,
+    pub to: Address,
+    pub value: U256,
+    pub data: Data,
+}
+
+/// Safe smart account v1.4.1 and its helper contracts.
+/// https://github.com/safe-global/safe-smart-account/blob/v1.4.1/contracts/Safe.sol
+/// https://github.com/safe-global/safe-modules/blob/main/modules/4337/contracts/Safe4337Module.sol
+pub struct Safe;
+
+impl Safe {
+    /// `Safe4337Module.executeUserOp(address to, uint256 value, bytes data, uint8 operation)`.
+    pub fn encode_execute_user_op(
+        to: Address,
+        value: U256,
+        data: Data,
+        operation: u8,
+    ) -> AbiResult<Data> {
+        Ok(encode_function_call(
+            "executeUserOp",
+            &[
+                Token::Address(to),
+                Token::u256(value),
+                Token::Bytes(data),
+                Token::uint(8, operation)?,
+            ],
+        ))
+    }
+
+    /// `MultiSend.multiSend(bytes transactions)`, where every transaction is packed as
+    /// `operation (uint8) || to (address) || value (uint256) || data length (uint256) || data`.
+    pub fn encode_multi_send<I>(transactions: I) -> Data
+    where
+        I: IntoIterator<Item = MultiSendTransaction>,
+    {
+        let mut packed = Data::default();
+        for tx in transactions {
+            packed.push(tx.operation);
+            packed.extend_from_slice(tx.to.as_slice());
+            packed.extend_from_slice(tx.value.to_big_endian().as_slice());
+            packed.extend_from_slice(U256::from(tx.data.len()).to_big_endian().as_slice());
+            packed.extend_from_slice(&tx.data);
+        }
+        encode_function_call("multiSend", &[Token::Bytes(packed)])
+    }
+
+    /// `Safe.setup(address[] _owners, uint256 _threshold, address to, bytes data, address fallbackHandler,
+    /// address paymentToken, uint256 payment, address paymentReceiver)`.
+    pub fn encode_setup(args: SafeSetupArgs) -> Data {
+        let owners = args.owners.into_iter().map(Token::Address).collect();
+        encode_function_call(
+            "setup",
+            &[
+                Token::array(ParamType::Address, owners),
+                Token::u256(args.threshold),
+                Token::Address(args.to),
+                Token::Bytes(args.data),
+                Token::Address(args.fallback_handler),
+                Token::Address(args.payment_token),
+                Token::u256(args.payment),
+                Token::Address(args.payment_receiver),
+            ],
+        )
+    }
+
+    /// `SafeModuleSetup.enableModules(address[] modules)`, delegate-called on the Safe setup.
+    pub fn encode_enable_modules(modules: Vec<Address>) -> Data {
+        let modules = modules.into_iter().map(Token::Address).collect();
+        encode_function_call(
+            "enableModules",
+            &[Token::array(ParamType::Address, modules)],
+        )
+    }
+
+    /// `SafeProxyFactory.createProxyWithNonce(address _singleton, bytes initializer, uint256 saltNonce)`.
+    pub fn encode_create_proxy_with_nonce(
+        singleton: Address,
+        initializer: Data,
+        salt_nonce: U256,
+    ) -> Data {
+        encode_function_call(
+            "createProxyWithNonce",
+            &[
+                Token::Address(singleton),
+                Token::Bytes(initializer),
+                Token::u256(salt_nonce),
+            ],
+        )
+    }
+}
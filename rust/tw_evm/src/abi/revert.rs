@@ -0,0 +1,150 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::abi::contract::Contract;
+use crate::abi::custom_error::CustomError;
+use crate::abi::param::Param;
+use crate::abi::param_token::NamedToken;
+use crate::abi::param_type::ParamType;
+use crate::abi::token::Token;
+use crate::abi::{AbiError, AbiErrorKind, AbiResult};
+use tw_coin_entry::error::prelude::*;
+use tw_hash::H32;
+use tw_number::U256;
+
+/// A decoded revert reason of a failed transaction or call.
+pub struct RevertReason {
+    /// The error signature, e.g `Error(string)`.
+    pub signature: String,
+    /// Human readable reason:
+    /// the message of `Error(string)`, the description of `Panic(uint256)` code,
+    /// or the name of a custom error.
+    pub reason: String,
+    /// Decoded error parameters.
+    pub params: Vec<NamedToken>,
+}
+
+/// Decodes revert data as either `Error(string)`, `Panic(uint256)` or a custom error declared in the `contract` ABI.
+pub fn decode_revert(contract: &Contract, data: &[u8]) -> AbiResult<RevertReason> {
+    if data.len() < H32::len() {
+        return AbiError::err(AbiErrorKind::Error_decoding_data)
+            .context("Revert data must contain at least 4 bytes of the error selector");
+    }
+    let (selector, encoded) = data.split_at(H32::len());
+    let selector = H32::try_from(selector).expect("The length expected to be checked above");
+
+    let error_string = error_string();
+    if selector == error_string.selector() {
+        let params = error_string.decode(encoded)?;
+        let reason = match params.first().map(|param| &param.value) {
+            Some(Token::String(message)) => message.clone(),
+            _ => return AbiError::err(AbiErrorKind::Error_decoding_data),
+        };
+        return Ok(RevertReason {
+            signature: error_string.signature(),
+            reason,
+            params,
+        });
+    }
+
+    let panic = panic();
+    if selector == panic.selector() {
+        let params = panic.decode(encoded)?;
+        let reason = match params.first().map(|param| &param.value) {
+            Some(Token::Uint { uint, .. }) => panic_description(*uint),
+            _ => return AbiError::err(AbiErrorKind::Error_decoding_data),
+        };
+        return Ok(RevertReason {
+            signature: panic.signature(),
+            reason,
+            params,
+        });
+    }
+
+    let custom_error = contract.error_by_selector(&selector)?;
+    Ok(RevertReason {
+        signature: custom_error.signature(),
+        reason: custom_error.name.clone(),
+        params: custom_error.decode(encoded)?,
+    })
+}
+
+/// `Error(string)` is used by `revert("message")` and `require(condition, "message")`.
+fn error_string() -> CustomError {
+    CustomError {
+        name: "Error".to_string(),
+        inputs: vec![Param {
+            name: Some("message".to_string()),
+            kind: ParamType::String,
+            internal_type: None,
+        }],
+    }
+}
+
+/// `Panic(uint256)` is used by failing assertions, arithmetic overflows etc.
+fn panic() -> CustomError {
+    CustomError {
+        name: "Panic".to_string(),
+        inputs: vec![Param {
+            name: Some("code".to_string()),
+            kind: ParamType::u256(),
+            internal_type: None,
+        }],
+    }
+}
+
+/// https://docs.soliditylang.org/en/latest/control-structures.html#panic-via-assert-and-error-via-require
+fn panic_description(code: U256) -> String {
+    let description = match u8::try_from(code) {
+        Ok(0x00) => "Generic compiler inserted panic",
+        Ok(0x01) => "Assertion failed",
+        Ok(0x11) => "Arithmetic operation overflowed or underflowed",
+        Ok(0x12) => "Division or modulo by zero",
+        Ok(0x21) => "Invalid enum value conversion",
+        Ok(0x22) => "Incorrectly encoded storage byte array",
+        Ok(0x31) => "Pop on an empty array",
+        Ok(0x32) => "Array index out of bounds",
+        Ok(0x41) => "Too much memory allocated",
+        Ok(0x51) => "Call to a zero-initialized internal function",
+        _ => return format!("Unknown panic code {code}"),
+    };
+    description.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tw_encoding::hex::DecodeHex;
+
+    #[test]
+    fn test_builtin_selectors() {
+        assert_eq!(error_string().selector(), H32::from("08c379a0"));
+        assert_eq!(panic().selector(), H32::from("4e487b71"));
+    }
+
+    #[test]
+    fn test_decode_revert_error_string() {
+        let data = "08c379a0000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000000124e6f7420656e6f7567682062616c616e63650000000000000000000000000000"
+            .decode_hex()
+            .unwrap();
+
+        let reason = decode_revert(&Contract::default(), &data).unwrap();
+        assert_eq!(reason.signature, "Error(string)");
+        assert_eq!(reason.reason, "Not enough balance");
+    }
+
+    #[test]
+    fn test_decode_revert_panic() {
+        let data = "4e487b710000000000000000000000000000000000000000000000000000000000000011"
+            .decode_hex()
+            .unwrap();
+
+        let reason = decode_revert(&Contract::default(), &data).unwrap();
+        assert_eq!(reason.signature, "Panic(uint256)");
+        assert_eq!(
+            reason.reason,
+            "Arithmetic operation overflowed or underflowed"
+        );
+    }
+}
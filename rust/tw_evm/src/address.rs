@@ -14,7 +14,9 @@ use tw_hash::{sha3::keccak256, H160, H256};
 use tw_keypair::ecdsa::secp256k1;
 use tw_memory::Data;
 
-pub trait EvmAddress: FromStr<Err = AddressError> + Into<Address> {
+pub trait EvmAddress:
+    FromStr<Err = AddressError> + Into<Address> + From<Address> + Display
+{
     /// Tries to parse an address from the string representation.
     /// Returns `Ok(None)` if the given `s` string is empty.
     #[inline]
@@ -52,6 +54,27 @@ impl Address {
         Address { bytes }
     }
 
+    /// Computes the address of a contract deployed by `deployer` via `CREATE2`:
+    /// `keccak256(0xff || deployer || salt || keccak256(init_code))[12:]`.
+    /// https://eips.ethereum.org/EIPS/eip-1014
+    pub fn create2(deployer: Address, salt: H256, init_code: &[u8]) -> Address {
+        const CREATE2_PREFIX: u8 = 0xff;
+
+        let init_code_hash = keccak256(init_code);
+        let data = [
+            &[CREATE2_PREFIX],
+            deployer.as_slice(),
+            salt.as_slice(),
+            init_code_hash.as_slice(),
+        ]
+        .concat();
+        let hash = keccak256(&data);
+
+        let bytes =
+            H160::try_from(&hash[H256::len() - H160::len()..]).expect("Expected 20 byte array");
+        Address { bytes }
+    }
+
     /// Constructs an address from the 20-length byte array.
     pub fn from_bytes(bytes: H160) -> Address {
         Address { bytes }
@@ -227,4 +250,28 @@ mod tests {
             "0xAc1ec44E4f0ca7D172B7803f6836De87Fb72b309"
         );
     }
+
+    /// https://eips.ethereum.org/EIPS/eip-1014#examples
+    #[test]
+    fn test_create2() {
+        let addr = Address::create2(
+            Address::from("0x00000000000000000000000000000000deadbeef"),
+            H256::from("00000000000000000000000000000000000000000000000000000000cafebabe"),
+            &hex::decode("0xdeadbeef").unwrap(),
+        );
+        assert_eq!(
+            addr.to_string(),
+            "0x60f3f640a8508fC6a86d45DF051962668E1e8AC7"
+        );
+
+        let addr = Address::create2(
+            Address::from("0x0000000000000000000000000000000000000000"),
+            H256::default(),
+            &[0x00],
+        );
+        assert_eq!(
+            addr.to_string(),
+            "0x4D1A2e2bB4F88F0250f26Ffff098B0b30B26BF38"
+        );
+    }
 }
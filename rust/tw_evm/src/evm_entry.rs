@@ -4,8 +4,13 @@
 
 use crate::evm_context::EvmContext;
 use crate::modules::abi_encoder::AbiEncoder;
+use crate::modules::authorization_signer::AuthorizationSigner;
+use crate::modules::permit_signer::PermitSigner;
 use crate::modules::rlp_encoder::RlpEncoder;
+use crate::modules::siwe_verifier::SiweVerifier;
+use crate::modules::smart_account_address::SmartAccountAddress;
 use tw_memory::Data;
+use tw_proto::Ethereum::Proto as EthProto;
 use tw_proto::EthereumAbi::Proto as AbiProto;
 use tw_proto::EthereumRlp::Proto as RlpProto;
 use tw_proto::{deserialize, serialize, ProtoResult};
@@ -28,6 +33,22 @@ pub trait EvmEntry {
         AbiEncoder::<Self::Context>::decode_contract_call(input)
     }
 
+    /// Decodes an event log according to a given ABI.
+    #[inline]
+    fn decode_abi_log(
+        input: AbiProto::LogDecodingInput<'_>,
+    ) -> AbiProto::LogDecodingOutput<'static> {
+        AbiEncoder::<Self::Context>::decode_log(input)
+    }
+
+    /// Decodes revert data as `Error(string)`, `Panic(uint256)` or a custom error declared in a given ABI.
+    #[inline]
+    fn decode_abi_revert(
+        input: AbiProto::RevertDecodingInput<'_>,
+    ) -> AbiProto::RevertDecodingOutput<'static> {
+        AbiEncoder::<Self::Context>::decode_revert(input)
+    }
+
     /// Decodes a function input or output data according to a given ABI.
     #[inline]
     fn decode_abi_params(
@@ -57,6 +78,63 @@ pub trait EvmEntry {
     ) -> AbiProto::FunctionEncodingOutput<'static> {
         AbiEncoder::<Self::Context>::encode_contract_call(input)
     }
+
+    /// Encodes tokens in packed mode, as Solidity `abi.encodePacked` does.
+    #[inline]
+    fn encode_abi_packed(
+        input: AbiProto::PackedEncodingInput<'_>,
+    ) -> AbiProto::PackedEncodingOutput<'static> {
+        AbiEncoder::<Self::Context>::encode_packed(input)
+    }
+
+    /// Computes the EIP-712 domain separator, message hash and signing hash of a typed data structure.
+    #[inline]
+    fn hash_abi_typed_data(
+        input: AbiProto::TypedDataHashingInput<'_>,
+    ) -> AbiProto::TypedDataHashingOutput<'static> {
+        AbiEncoder::<Self::Context>::hash_typed_data(input)
+    }
+
+    /// Signs an EIP-7702 authorization or returns its pre-hash only if no private key is specified.
+    #[inline]
+    fn sign_authorization(
+        input: EthProto::AuthorizationSigningInput<'_>,
+    ) -> EthProto::AuthorizationSigningOutput<'static> {
+        AuthorizationSigner::<Self::Context>::sign_proto(input)
+    }
+
+    /// Signs an EIP-2612 or Permit2 permit or returns its typed data and pre-hash only if no private key is specified.
+    #[inline]
+    fn sign_permit(
+        input: EthProto::PermitSigningInput<'_>,
+    ) -> EthProto::PermitSigningOutput<'static> {
+        PermitSigner::<Self::Context>::sign_proto(input)
+    }
+
+    /// Builds the `permit(...)` call of an EIP-2612 or Permit2 permit signed externally.
+    #[inline]
+    fn compile_permit(
+        input: EthProto::PermitSigningInput<'_>,
+        signature: &[u8],
+    ) -> EthProto::PermitSigningOutput<'static> {
+        PermitSigner::<Self::Context>::compile_proto(input, signature)
+    }
+
+    /// Verifies a Sign-In with Ethereum (EIP-4361) message signature.
+    #[inline]
+    fn verify_siwe(
+        input: EthProto::SiweVerifyingInput<'_>,
+    ) -> EthProto::SiweVerifyingOutput<'static> {
+        SiweVerifier::verify_proto(input)
+    }
+
+    /// Derives the counterfactual address of an ERC-4337 smart account and its factory call data.
+    #[inline]
+    fn smart_account_address(
+        input: EthProto::SmartAccountAddressInput<'_>,
+    ) -> EthProto::SmartAccountAddressOutput<'static> {
+        SmartAccountAddress::<Self::Context>::derive_proto(input)
+    }
 }
 
 /// The [`EvmEntry`] trait extension.
@@ -67,6 +145,12 @@ pub trait EvmEntryExt {
     /// Decodes function call data to human readable json format, according to input abi json.
     fn decode_abi_contract_call(&self, input: &[u8]) -> ProtoResult<Data>;
 
+    /// Decodes an event log according to a given ABI.
+    fn decode_abi_log(&self, input: &[u8]) -> ProtoResult<Data>;
+
+    /// Decodes revert data as `Error(string)`, `Panic(uint256)` or a custom error declared in a given ABI.
+    fn decode_abi_revert(&self, input: &[u8]) -> ProtoResult<Data>;
+
     /// Decodes a function input or output data according to a given ABI.
     fn decode_abi_params(&self, input: &[u8]) -> ProtoResult<Data>;
 
@@ -78,6 +162,27 @@ pub trait EvmEntryExt {
 
     /// Decodes an Eth ABI value according to a given type.
     fn decode_abi_value(&self, input: &[u8]) -> ProtoResult<Data>;
+
+    /// Encodes tokens in packed mode, as Solidity `abi.encodePacked` does.
+    fn encode_abi_packed(&self, input: &[u8]) -> ProtoResult<Data>;
+
+    /// Computes the EIP-712 domain separator, message hash and signing hash of a typed data structure.
+    fn hash_abi_typed_data(&self, input: &[u8]) -> ProtoResult<Data>;
+
+    /// Signs an EIP-7702 authorization or returns its pre-hash only if no private key is specified.
+    fn sign_authorization(&self, input: &[u8]) -> ProtoResult<Data>;
+
+    /// Signs an EIP-2612 or Permit2 permit or returns its typed data and pre-hash only if no private key is specified.
+    fn sign_permit(&self, input: &[u8]) -> ProtoResult<Data>;
+
+    /// Builds the `permit(...)` call of an EIP-2612 or Permit2 permit signed externally.
+    fn compile_permit(&self, input: &[u8], signature: &[u8]) -> ProtoResult<Data>;
+
+    /// Verifies a Sign-In with Ethereum (EIP-4361) message signature.
+    fn verify_siwe(&self, input: &[u8]) -> ProtoResult<Data>;
+
+    /// Derives the counterfactual address of an ERC-4337 smart account and its factory call data.
+    fn smart_account_address(&self, input: &[u8]) -> ProtoResult<Data>;
 }
 
 impl<T> EvmEntryExt for T
@@ -96,6 +201,18 @@ where
         serialize(&output)
     }
 
+    fn decode_abi_log(&self, input: &[u8]) -> ProtoResult<Data> {
+        let input = deserialize(input)?;
+        let output = <Self as EvmEntry>::decode_abi_log(input);
+        serialize(&output)
+    }
+
+    fn decode_abi_revert(&self, input: &[u8]) -> ProtoResult<Data> {
+        let input = deserialize(input)?;
+        let output = <Self as EvmEntry>::decode_abi_revert(input);
+        serialize(&output)
+    }
+
     fn decode_abi_params(&self, input: &[u8]) -> ProtoResult<Data> {
         let input = deserialize(input)?;
         let output = <Self as EvmEntry>::decode_abi_params(input);
@@ -118,4 +235,46 @@ where
         let output = <Self as EvmEntry>::decode_abi_value(input);
         serialize(&output)
     }
+
+    fn encode_abi_packed(&self, input: &[u8]) -> ProtoResult<Data> {
+        let input = deserialize(input)?;
+        let output = <Self as EvmEntry>::encode_abi_packed(input);
+        serialize(&output)
+    }
+
+    fn hash_abi_typed_data(&self, input: &[u8]) -> ProtoResult<Data> {
+        let input = deserialize(input)?;
+        let output = <Self as EvmEntry>::hash_abi_typed_data(input);
+        serialize(&output)
+    }
+
+    fn sign_authorization(&self, input: &[u8]) -> ProtoResult<Data> {
+        let input = deserialize(input)?;
+        let output = <Self as EvmEntry>::sign_authorization(input);
+        serialize(&output)
+    }
+
+    fn sign_permit(&self, input: &[u8]) -> ProtoResult<Data> {
+        let input = deserialize(input)?;
+        let output = <Self as EvmEntry>::sign_permit(input);
+        serialize(&output)
+    }
+
+    fn compile_permit(&self, input: &[u8], signature: &[u8]) -> ProtoResult<Data> {
+        let input = deserialize(input)?;
+        let output = <Self as EvmEntry>::compile_permit(input, signature);
+        serialize(&output)
+    }
+
+    fn verify_siwe(&self, input: &[u8]) -> ProtoResult<Data> {
+        let input = deserialize(input)?;
+        let output = <Self as EvmEntry>::verify_siwe(input);
+        serialize(&output)
+    }
+
+    fn smart_account_address(&self, input: &[u8]) -> ProtoResult<Data> {
+        let input = deserialize(input)?;
+        let output = <Self as EvmEntry>::smart_account_address(input);
+        serialize(&output)
+    }
 }
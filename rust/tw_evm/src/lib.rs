@@ -10,4 +10,5 @@ pub mod message;
 pub mod modules;
 pub mod rlp;
 pub mod signature;
+pub mod smart_account;
 pub mod transaction;
@@ -71,23 +71,34 @@ impl Eip712Message {
 
         Ok(msg)
     }
-}
 
-impl EthMessage for Eip712Message {
-    fn hash(&self) -> MessageSigningResult<H256> {
-        let domain_hash = encode_data(
-            &self.types,
-            PropertyType::Custom(EIP712_DOMAIN.to_string()),
-            &self.domain,
-        )
-        .context("Error encoding EIP712Domain")?;
+    /// Returns `hashStruct(eip712Domain)`.
+    pub fn domain_separator(&self) -> MessageSigningResult<H256> {
+        self.hash_struct(EIP712_DOMAIN, &self.domain)
+            .context("Error encoding EIP712Domain")
+    }
 
-        let primary_data_hash = encode_data(
+    /// Returns `hashStruct(s) = keccak256(typeHash ‖ encodeData(s))` of the given `data`
+    /// according to the `type_name` custom type declared in [`Eip712Message::types`].
+    pub fn hash_struct(&self, type_name: &str, data: &Json) -> MessageSigningResult<H256> {
+        let hash_data = encode_data(
             &self.types,
-            PropertyType::Custom(self.primary_type.clone()),
-            &self.message,
-        )
-        .context("Error encoding primary type")?;
+            PropertyType::Custom(type_name.to_string()),
+            data,
+        )?;
+        Ok(H256::try_from(hash_data.as_slice()).expect("Expected 32-byte hash"))
+    }
+
+    /// Returns `hashStruct(message)` of the primary type.
+    pub fn message_hash(&self) -> MessageSigningResult<H256> {
+        self.hash_struct(&self.primary_type, &self.message)
+            .context("Error encoding primary type")
+    }
+
+    /// Returns the hash to be signed: `keccak256("\x19\x01" ‖ domainSeparator ‖ hashStruct(message))`.
+    pub fn signing_hash(&self) -> MessageSigningResult<H256> {
+        let domain_hash = self.domain_separator()?;
+        let primary_data_hash = self.message_hash()?;
 
         let concat = [
             PREFIX.as_slice(),
@@ -101,6 +112,13 @@ impl EthMessage for Eip712Message {
     }
 }
 
+impl EthMessage for Eip712Message {
+    #[inline]
+    fn hash(&self) -> MessageSigningResult<H256> {
+        self.signing_hash()
+    }
+}
+
 fn encode_data(
     custom_types: &CustomTypes,
     data_type: PropertyType,
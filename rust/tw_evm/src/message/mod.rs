@@ -7,7 +7,9 @@ use tw_hash::H256;
 
 pub mod eip191;
 pub mod eip712;
+pub mod permit;
 pub mod signature;
+pub mod siwe;
 
 pub type EthMessageBoxed = Box<dyn EthMessage>;
 pub type MessageSigningResult<T> = Result<T, MessageSigningError>;
@@ -0,0 +1,257 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::address::Address;
+use crate::message::eip712::eip712_message::Eip712Message;
+use crate::message::eip712::message_types::CustomTypes;
+use crate::message::eip712::property::Property;
+use serde_json::{json, Value as Json};
+use tw_number::U256;
+
+/// The Uniswap Permit2 contract address, the same on all supported chains.
+pub const PERMIT2_ADDRESS: &str = "0x000000000022D473030F116dDEE9F6B43aC78BA3";
+
+const EIP712_DOMAIN: &str = "EIP712Domain";
+const PERMIT2_DOMAIN_NAME: &str = "Permit2";
+
+/// [EIP-2612](https://eips.ethereum.org/EIPS/eip-2612) `Permit` of an ERC20 token.
+pub struct Erc2612Permit {
+    pub token: Address,
+    pub token_name: String,
+    pub token_version: String,
+    pub owner: Address,
+    pub spender: Address,
+    pub value: U256,
+    pub nonce: U256,
+    pub deadline: U256,
+}
+
+impl Erc2612Permit {
+    pub fn to_eip712_message(&self, chain_id: U256) -> Eip712Message {
+        let types = CustomTypes::from([
+            (
+                EIP712_DOMAIN.to_string(),
+                properties(&[
+                    ("name", "string"),
+                    ("version", "string"),
+                    ("chainId", "uint256"),
+                    ("verifyingContract", "address"),
+                ]),
+            ),
+            (
+                "Permit".to_string(),
+                properties(&[
+                    ("owner", "address"),
+                    ("spender", "address"),
+                    ("value", "uint256"),
+                    ("nonce", "uint256"),
+                    ("deadline", "uint256"),
+                ]),
+            ),
+        ]);
+
+        Eip712Message {
+            types,
+            domain: json!({
+                "name": self.token_name,
+                "version": self.token_version,
+                "chainId": chain_id.to_string(),
+                "verifyingContract": self.token.to_string(),
+            }),
+            primary_type: "Permit".to_string(),
+            message: json!({
+                "owner": self.owner.to_string(),
+                "spender": self.spender.to_string(),
+                "value": self.value.to_string(),
+                "nonce": self.nonce.to_string(),
+                "deadline": self.deadline.to_string(),
+            }),
+        }
+    }
+}
+
+/// Permit2 `IAllowanceTransfer.PermitDetails`.
+pub struct Permit2Details {
+    pub token: Address,
+    /// `uint160` value.
+    pub amount: U256,
+    /// `uint48` value.
+    pub expiration: U256,
+    /// `uint48` value.
+    pub nonce: U256,
+}
+
+impl Permit2Details {
+    const TYPE_NAME: &'static str = "PermitDetails";
+
+    fn properties() -> Vec<Property> {
+        properties(&[
+            ("token", "address"),
+            ("amount", "uint160"),
+            ("expiration", "uint48"),
+            ("nonce", "uint48"),
+        ])
+    }
+
+    fn to_json(&self) -> Json {
+        json!({
+            "token": self.token.to_string(),
+            "amount": self.amount.to_string(),
+            "expiration": self.expiration.to_string(),
+            "nonce": self.nonce.to_string(),
+        })
+    }
+}
+
+/// Permit2 `IAllowanceTransfer.PermitSingle`.
+pub struct Permit2Single {
+    pub details: Permit2Details,
+    pub spender: Address,
+    pub sig_deadline: U256,
+}
+
+impl Permit2Single {
+    pub fn to_eip712_message(&self, chain_id: U256, permit2: Address) -> Eip712Message {
+        let types = CustomTypes::from([
+            (EIP712_DOMAIN.to_string(), permit2_domain_properties()),
+            (
+                "PermitSingle".to_string(),
+                properties(&[
+                    ("details", Permit2Details::TYPE_NAME),
+                    ("spender", "address"),
+                    ("sigDeadline", "uint256"),
+                ]),
+            ),
+            (
+                Permit2Details::TYPE_NAME.to_string(),
+                Permit2Details::properties(),
+            ),
+        ]);
+
+        Eip712Message {
+            types,
+            domain: permit2_domain(chain_id, permit2),
+            primary_type: "PermitSingle".to_string(),
+            message: json!({
+                "details": self.details.to_json(),
+                "spender": self.spender.to_string(),
+                "sigDeadline": self.sig_deadline.to_string(),
+            }),
+        }
+    }
+}
+
+/// Permit2 `IAllowanceTransfer.PermitBatch`.
+pub struct Permit2Batch {
+    pub details: Vec<Permit2Details>,
+    pub spender: Address,
+    pub sig_deadline: U256,
+}
+
+impl Permit2Batch {
+    pub fn to_eip712_message(&self, chain_id: U256, permit2: Address) -> Eip712Message {
+        let details_array_type = format!("{}[]", Permit2Details::TYPE_NAME);
+        let types = CustomTypes::from([
+            (EIP712_DOMAIN.to_string(), permit2_domain_properties()),
+            (
+                "PermitBatch".to_string(),
+                properties(&[
+                    ("details", &details_array_type),
+                    ("spender", "address"),
+                    ("sigDeadline", "uint256"),
+                ]),
+            ),
+            (
+                Permit2Details::TYPE_NAME.to_string(),
+                Permit2Details::properties(),
+            ),
+        ]);
+
+        let details: Vec<_> = self.details.iter().map(Permit2Details::to_json).collect();
+        Eip712Message {
+            types,
+            domain: permit2_domain(chain_id, permit2),
+            primary_type: "PermitBatch".to_string(),
+            message: json!({
+                "details": details,
+                "spender": self.spender.to_string(),
+                "sigDeadline": self.sig_deadline.to_string(),
+            }),
+        }
+    }
+}
+
+/// Permit2 `ISignatureTransfer.PermitTransferFrom`.
+/// Unlike allowance permits, the signature is consumed by the `spender` itself on `permitTransferFrom` call.
+pub struct Permit2TransferFrom {
+    pub token: Address,
+    pub amount: U256,
+    pub spender: Address,
+    pub nonce: U256,
+    pub deadline: U256,
+}
+
+impl Permit2TransferFrom {
+    pub fn to_eip712_message(&self, chain_id: U256, permit2: Address) -> Eip712Message {
+        let types = CustomTypes::from([
+            (EIP712_DOMAIN.to_string(), permit2_domain_properties()),
+            (
+                "PermitTransferFrom".to_string(),
+                properties(&[
+                    ("permitted", "TokenPermissions"),
+                    ("spender", "address"),
+                    ("nonce", "uint256"),
+                    ("deadline", "uint256"),
+                ]),
+            ),
+            (
+                "TokenPermissions".to_string(),
+                properties(&[("token", "address"), ("amount", "uint256")]),
+            ),
+        ]);
+
+        Eip712Message {
+            types,
+            domain: permit2_domain(chain_id, permit2),
+            primary_type: "PermitTransferFrom".to_string(),
+            message: json!({
+                "permitted": {
+                    "token": self.token.to_string(),
+                    "amount": self.amount.to_string(),
+                },
+                "spender": self.spender.to_string(),
+                "nonce": self.nonce.to_string(),
+                "deadline": self.deadline.to_string(),
+            }),
+        }
+    }
+}
+
+/// Permit2 domain doesn't have a version.
+fn permit2_domain_properties() -> Vec<Property> {
+    properties(&[
+        ("name", "string"),
+        ("chainId", "uint256"),
+        ("verifyingContract", "address"),
+    ])
+}
+
+fn permit2_domain(chain_id: U256, permit2: Address) -> Json {
+    json!({
+        "name": PERMIT2_DOMAIN_NAME,
+        "chainId": chain_id.to_string(),
+        "verifyingContract": permit2.to_string(),
+    })
+}
+
+/// Properties are declared by raw type names as `PropertyType` doesn't keep the `uintN` bits.
+fn properties(properties: &[(&str, &str)]) -> Vec<Property> {
+    properties
+        .iter()
+        .map(|(name, property_type)| Property {
+            name: name.to_string(),
+            property_type: property_type.to_string(),
+        })
+        .collect()
+}
@@ -0,0 +1,539 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::address::Address;
+use crate::message::eip191::Eip191Message;
+use crate::message::{
+    EthMessage, MessageSigningError, MessageSigningErrorKind, MessageSigningResult,
+};
+use std::fmt;
+use std::iter::Peekable;
+use std::str::{FromStr, Split};
+use tw_coin_entry::error::prelude::*;
+use tw_hash::H256;
+
+const PREAMBLE_SUFFIX: &str = " wants you to sign in with your Ethereum account:";
+const SCHEME_SEPARATOR: &str = "://";
+const URI_TAG: &str = "URI: ";
+const VERSION_TAG: &str = "Version: ";
+const CHAIN_ID_TAG: &str = "Chain ID: ";
+const NONCE_TAG: &str = "Nonce: ";
+const ISSUED_AT_TAG: &str = "Issued At: ";
+const EXPIRATION_TIME_TAG: &str = "Expiration Time: ";
+const NOT_BEFORE_TAG: &str = "Not Before: ";
+const REQUEST_ID_TAG: &str = "Request ID: ";
+const RESOURCES_TAG: &str = "Resources:";
+const RESOURCE_PREFIX: &str = "- ";
+
+/// The only version supported by EIP-4361.
+pub const SIWE_VERSION: &str = "1";
+/// The minimum length of an alphanumeric nonce.
+pub const SIWE_NONCE_MIN_LEN: usize = 8;
+
+/// [EIP-4361](https://eips.ethereum.org/EIPS/eip-4361) Sign-In with Ethereum message.
+///
+/// Use [`SiweMessage::from_str`] to parse a message and [`fmt::Display`] to build the text to be signed.
+/// The message is signed according to EIP-191.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SiweMessage {
+    /// Optional URI scheme of the origin of the request, e.g `https`.
+    pub scheme: Option<String>,
+    /// RFC 3986 authority that is requesting the signing.
+    pub domain: String,
+    /// EIP-55 checksummed address performing the signing.
+    pub address: Address,
+    /// Optional human-readable assertion that the user will sign. Must not contain `\n`.
+    pub statement: Option<String>,
+    /// RFC 3986 URI referring to the resource that is the subject of the signing.
+    pub uri: String,
+    /// Current version of the message, must be `1`.
+    pub version: String,
+    /// EIP-155 Chain ID to which the session is bound.
+    pub chain_id: u64,
+    /// Randomized token used to prevent replay attacks, at least 8 alphanumeric characters.
+    pub nonce: String,
+    /// RFC 3339 date-time of the current time.
+    pub issued_at: String,
+    /// Optional RFC 3339 date-time when the signed message expires.
+    pub expiration_time: Option<String>,
+    /// Optional RFC 3339 date-time when the signed message becomes valid.
+    pub not_before: Option<String>,
+    /// Optional system-specific identifier used to uniquely refer to the sign-in request.
+    pub request_id: Option<String>,
+    /// List of RFC 3986 URIs the user wishes to have resolved as part of authentication.
+    pub resources: Vec<String>,
+}
+
+impl SiweMessage {
+    /// Checks whether all the message fields conform EIP-4361.
+    pub fn validate(&self) -> MessageSigningResult<()> {
+        if let Some(ref scheme) = self.scheme {
+            if !is_valid_scheme(scheme) {
+                return invalid_value("Invalid scheme");
+            }
+        }
+        if self.domain.is_empty() || self.domain.contains(char::is_whitespace) {
+            return invalid_value("Invalid domain");
+        }
+        if let Some(ref statement) = self.statement {
+            if statement.contains('\n') {
+                return invalid_value("Statement must not contain line breaks");
+            }
+        }
+        validate_uri(&self.uri).context("Invalid URI")?;
+        if self.version != SIWE_VERSION {
+            return invalid_value("Unsupported version");
+        }
+        if self.nonce.len() < SIWE_NONCE_MIN_LEN
+            || !self.nonce.chars().all(|c| c.is_ascii_alphanumeric())
+        {
+            return invalid_value("Nonce must contain at least 8 alphanumeric characters");
+        }
+
+        let issued_at = parse_timestamp(&self.issued_at).context("Invalid 'Issued At'")?;
+        if let Some(ref expiration_time) = self.expiration_time {
+            let expiration_time =
+                parse_timestamp(expiration_time).context("Invalid 'Expiration Time'")?;
+            if expiration_time <= issued_at {
+                return invalid_value("'Expiration Time' must be later than 'Issued At'");
+            }
+        }
+        if let Some(ref not_before) = self.not_before {
+            parse_timestamp(not_before).context("Invalid 'Not Before'")?;
+        }
+        if let Some(ref request_id) = self.request_id {
+            if request_id.contains(char::is_whitespace) {
+                return invalid_value("Invalid 'Request ID'");
+            }
+        }
+        for resource in self.resources.iter() {
+            validate_uri(resource).context("Invalid resource")?;
+        }
+        Ok(())
+    }
+
+    /// Checks whether the message is valid at the given UNIX `timestamp` in seconds.
+    pub fn validate_time(&self, timestamp: i64) -> MessageSigningResult<()> {
+        if let Some(ref expiration_time) = self.expiration_time {
+            if parse_timestamp(expiration_time)? <= timestamp {
+                return invalid_value("The message has expired");
+            }
+        }
+        if let Some(ref not_before) = self.not_before {
+            if timestamp < parse_timestamp(not_before)? {
+                return invalid_value("The message is not yet valid");
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for SiweMessage {
+    type Err = MessageSigningError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = Lines(s.split('\n').peekable());
+
+        let preamble = lines.next()?;
+        let origin = preamble
+            .strip_suffix(PREAMBLE_SUFFIX)
+            .or_tw_err(MessageSigningErrorKind::InvalidParameterValue)
+            .context("Invalid preamble line")?;
+        let (scheme, domain) = match origin.split_once(SCHEME_SEPARATOR) {
+            Some((scheme, domain)) => (Some(scheme.to_string()), domain.to_string()),
+            None => (None, origin.to_string()),
+        };
+
+        let address_str = lines.next()?;
+        let address = Address::from_str(address_str)
+            .tw_err(|_| MessageSigningErrorKind::InvalidParameterValue)
+            .context("Invalid address")?;
+        if address.to_string() != address_str {
+            return invalid_value("Address must be EIP-55 checksummed");
+        }
+
+        lines.expect_empty()?;
+        // The statement is optional, but the empty line after it is required.
+        let statement = match lines.next()? {
+            "" => None,
+            statement => {
+                lines.expect_empty()?;
+                Some(statement.to_string())
+            },
+        };
+
+        let uri = lines.tagged(URI_TAG)?.to_string();
+        let version = lines.tagged(VERSION_TAG)?.to_string();
+        let chain_id = lines
+            .tagged(CHAIN_ID_TAG)?
+            .parse()
+            .tw_err(|_| MessageSigningErrorKind::InvalidChainId)
+            .context("Invalid 'Chain ID'")?;
+        let nonce = lines.tagged(NONCE_TAG)?.to_string();
+        let issued_at = lines.tagged(ISSUED_AT_TAG)?.to_string();
+
+        let expiration_time = lines.optional_tagged(EXPIRATION_TIME_TAG);
+        let not_before = lines.optional_tagged(NOT_BEFORE_TAG);
+        let request_id = lines.optional_tagged(REQUEST_ID_TAG);
+
+        let mut resources = Vec::default();
+        if lines.peek() == Some(RESOURCES_TAG) {
+            lines.next()?;
+            while let Some(resource) = lines.optional_tagged(RESOURCE_PREFIX) {
+                resources.push(resource);
+            }
+        }
+
+        if let Some(line) = lines.0.next() {
+            return MessageSigningError::err(MessageSigningErrorKind::InvalidParameterValue)
+                .with_context(|| format!("Unexpected line: '{line}'"));
+        }
+
+        let msg = SiweMessage {
+            scheme,
+            domain,
+            address,
+            statement,
+            uri,
+            version,
+            chain_id,
+            nonce,
+            issued_at,
+            expiration_time,
+            not_before,
+            request_id,
+            resources,
+        };
+        msg.validate()?;
+        Ok(msg)
+    }
+}
+
+impl fmt::Display for SiweMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(ref scheme) = self.scheme {
+            write!(f, "{scheme}{SCHEME_SEPARATOR}")?;
+        }
+        writeln!(f, "{}{PREAMBLE_SUFFIX}", self.domain)?;
+        writeln!(f, "{}", self.address)?;
+        writeln!(f)?;
+        if let Some(ref statement) = self.statement {
+            writeln!(f, "{statement}")?;
+        }
+        writeln!(f)?;
+        writeln!(f, "{URI_TAG}{}", self.uri)?;
+        writeln!(f, "{VERSION_TAG}{}", self.version)?;
+        writeln!(f, "{CHAIN_ID_TAG}{}", self.chain_id)?;
+        writeln!(f, "{NONCE_TAG}{}", self.nonce)?;
+        write!(f, "{ISSUED_AT_TAG}{}", self.issued_at)?;
+        if let Some(ref expiration_time) = self.expiration_time {
+            write!(f, "\n{EXPIRATION_TIME_TAG}{expiration_time}")?;
+        }
+        if let Some(ref not_before) = self.not_before {
+            write!(f, "\n{NOT_BEFORE_TAG}{not_before}")?;
+        }
+        if let Some(ref request_id) = self.request_id {
+            write!(f, "\n{REQUEST_ID_TAG}{request_id}")?;
+        }
+        if !self.resources.is_empty() {
+            write!(f, "\n{RESOURCES_TAG}")?;
+            for resource in self.resources.iter() {
+                write!(f, "\n{RESOURCE_PREFIX}{resource}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl EthMessage for SiweMessage {
+    fn hash(&self) -> MessageSigningResult<H256> {
+        Eip191Message::new(self.to_string()).hash()
+    }
+}
+
+struct Lines<'a>(Peekable<Split<'a, char>>);
+
+impl<'a> Lines<'a> {
+    fn next(&mut self) -> MessageSigningResult<&'a str> {
+        self.0
+            .next()
+            .or_tw_err(MessageSigningErrorKind::InvalidParameterValue)
+            .context("Unexpected end of the message")
+    }
+
+    fn peek(&mut self) -> Option<&'a str> {
+        self.0.peek().copied()
+    }
+
+    fn expect_empty(&mut self) -> MessageSigningResult<()> {
+        if !self.next()?.is_empty() {
+            return invalid_value("Expected an empty line");
+        }
+        Ok(())
+    }
+
+    fn tagged(&mut self, tag: &str) -> MessageSigningResult<&'a str> {
+        self.next()?
+            .strip_prefix(tag)
+            .or_tw_err(MessageSigningErrorKind::InvalidParameterValue)
+            .with_context(|| format!("Expected '{tag}' line"))
+    }
+
+    fn optional_tagged(&mut self, tag: &str) -> Option<String> {
+        let value = self.peek()?.strip_prefix(tag)?;
+        self.0.next();
+        Some(value.to_string())
+    }
+}
+
+fn invalid_value<T>(context: &'static str) -> MessageSigningResult<T> {
+    MessageSigningError::err(MessageSigningErrorKind::InvalidParameterValue).context(context)
+}
+
+/// Checks if the URI has a valid scheme and doesn't contain whitespaces.
+fn validate_uri(uri: &str) -> MessageSigningResult<()> {
+    let Some((scheme, _)) = uri.split_once(':') else {
+        return invalid_value("URI must contain a scheme");
+    };
+    if !is_valid_scheme(scheme) || uri.contains(char::is_whitespace) {
+        return invalid_value("Invalid URI");
+    }
+    Ok(())
+}
+
+/// RFC 3986 `scheme = ALPHA *( ALPHA / DIGIT / "+" / "-" / "." )`.
+fn is_valid_scheme(scheme: &str) -> bool {
+    scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+}
+
+/// Parses an RFC 3339 `date-time` like `2021-09-30T16:25:24.000Z` to a UNIX timestamp in seconds.
+fn parse_timestamp(s: &str) -> MessageSigningResult<i64> {
+    fn number(s: &str, range: std::ops::Range<usize>) -> Option<i64> {
+        let digits = s.get(range)?;
+        if !digits.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        digits.parse().ok()
+    }
+
+    fn parse(s: &str) -> Option<i64> {
+        let bytes = s.as_bytes();
+        if bytes.len() < 20
+            || bytes[4] != b'-'
+            || bytes[7] != b'-'
+            || !matches!(bytes[10], b'T' | b't')
+            || bytes[13] != b':'
+            || bytes[16] != b':'
+        {
+            return None;
+        }
+        let year = number(s, 0..4)?;
+        let month = number(s, 5..7)?;
+        let day = number(s, 8..10)?;
+        let hour = number(s, 11..13)?;
+        let minute = number(s, 14..16)?;
+        // Allow a leap second.
+        let second = number(s, 17..19)?;
+        if !(1..=12).contains(&month)
+            || day < 1
+            || day > days_in_month(year, month)
+            || hour > 23
+            || minute > 59
+            || second > 60
+        {
+            return None;
+        }
+
+        let mut offset = &s[19..];
+        if let Some(fraction) = offset.strip_prefix('.') {
+            let digits = fraction.chars().take_while(char::is_ascii_digit).count();
+            if digits == 0 {
+                return None;
+            }
+            offset = &fraction[digits..];
+        }
+        let offset_seconds = match offset {
+            "Z" | "z" => 0,
+            _ => {
+                let sign = match offset.as_bytes().first()? {
+                    b'+' => 1,
+                    b'-' => -1,
+                    _ => return None,
+                };
+                if offset.len() != 6 || offset.as_bytes()[3] != b':' {
+                    return None;
+                }
+                let offset_hour = number(offset, 1..3)?;
+                let offset_minute = number(offset, 4..6)?;
+                if offset_hour > 23 || offset_minute > 59 {
+                    return None;
+                }
+                sign * (offset_hour * 3600 + offset_minute * 60)
+            },
+        };
+
+        let days = days_from_civil(year, month, day);
+        Some(days * 86400 + hour * 3600 + minute * 60 + second - offset_seconds)
+    }
+
+    parse(s)
+        .or_tw_err(MessageSigningErrorKind::InvalidParameterValue)
+        .with_context(|| format!("Expected an RFC 3339 date-time, found '{s}'"))
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Returns the number of days since 1970-01-01.
+/// http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month_index = (month + 9) % 12;
+    let day_of_year = (153 * month_index + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MESSAGE: &str = "service.org wants you to sign in with your Ethereum account:
+0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F
+
+I accept the ServiceOrg Terms of Service: https://service.org/tos
+
+URI: https://service.org/login
+Version: 1
+Chain ID: 1
+Nonce: 32891756
+Issued At: 2021-09-30T16:25:24Z
+Expiration Time: 2021-10-01T16:25:24Z
+Resources:
+- ipfs://bafybeiemxf5abjwjbikoz4mc3a3dla6ual3jsgpdr4cjr3oz3evfyavhwq/
+- https://example.com/my-web2-claim.json";
+
+    #[test]
+    fn test_parse_siwe_message() {
+        let msg = SiweMessage::from_str(MESSAGE).unwrap();
+        assert_eq!(msg.scheme, None);
+        assert_eq!(msg.domain, "service.org");
+        assert_eq!(
+            msg.address,
+            Address::from("0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F")
+        );
+        assert_eq!(
+            msg.statement.as_deref(),
+            Some("I accept the ServiceOrg Terms of Service: https://service.org/tos")
+        );
+        assert_eq!(msg.uri, "https://service.org/login");
+        assert_eq!(msg.chain_id, 1);
+        assert_eq!(msg.nonce, "32891756");
+        assert_eq!(msg.expiration_time.as_deref(), Some("2021-10-01T16:25:24Z"));
+        assert_eq!(msg.resources.len(), 2);
+        assert_eq!(msg.to_string(), MESSAGE);
+    }
+
+    #[test]
+    fn test_build_siwe_message_without_statement() {
+        let msg = SiweMessage {
+            scheme: Some("https".to_string()),
+            domain: "example.com".to_string(),
+            address: Address::from("0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F"),
+            statement: None,
+            uri: "https://example.com".to_string(),
+            version: SIWE_VERSION.to_string(),
+            chain_id: 56,
+            nonce: "abcdef1234".to_string(),
+            issued_at: "2024-01-01T00:00:00.000+02:00".to_string(),
+            expiration_time: None,
+            not_before: None,
+            request_id: Some("42".to_string()),
+            resources: Vec::default(),
+        };
+        msg.validate().unwrap();
+
+        let text = msg.to_string();
+        assert_eq!(
+            text,
+            "https://example.com wants you to sign in with your Ethereum account:
+0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F
+
+
+URI: https://example.com
+Version: 1
+Chain ID: 56
+Nonce: abcdef1234
+Issued At: 2024-01-01T00:00:00.000+02:00
+Request ID: 42"
+        );
+        assert_eq!(SiweMessage::from_str(&text).unwrap(), msg);
+    }
+
+    #[test]
+    fn test_parse_siwe_message_invalid() {
+        let not_checksummed = MESSAGE.replace(
+            "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F",
+            "0x9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f",
+        );
+        let short_nonce = MESSAGE.replace("Nonce: 32891756", "Nonce: 1234");
+        let expired_before_issued = MESSAGE.replace("2021-10-01T16:25:24Z", "2021-09-29T16:25:24Z");
+        let invalid_time = MESSAGE.replace("2021-09-30T16:25:24Z", "2021-09-31T16:25:24Z");
+        let invalid_chain_id = MESSAGE.replace("Chain ID: 1", "Chain ID: one");
+        let unsupported_version = MESSAGE.replace("Version: 1", "Version: 2");
+        let trailing_line = format!("{MESSAGE}\nfoo");
+
+        for msg in [
+            not_checksummed,
+            short_nonce,
+            expired_before_issued,
+            invalid_time,
+            invalid_chain_id,
+            unsupported_version,
+            trailing_line,
+        ] {
+            SiweMessage::from_str(&msg).unwrap_err();
+        }
+    }
+
+    #[test]
+    fn test_siwe_validate_time() {
+        let msg = SiweMessage::from_str(MESSAGE).unwrap();
+        // 2021-09-30T16:25:24Z
+        msg.validate_time(1_633_019_124).unwrap();
+        // 2021-10-01T16:25:24Z
+        msg.validate_time(1_633_105_524).unwrap_err();
+    }
+
+    #[test]
+    fn test_parse_timestamp() {
+        assert_eq!(parse_timestamp("1970-01-01T00:00:00Z").unwrap(), 0);
+        assert_eq!(
+            parse_timestamp("2021-09-30T16:25:24.123Z").unwrap(),
+            1_633_019_124
+        );
+        assert_eq!(
+            parse_timestamp("2021-09-30T18:25:24+02:00").unwrap(),
+            1_633_019_124
+        );
+        assert_eq!(
+            parse_timestamp("2024-02-29T00:00:00Z").unwrap(),
+            1_709_164_800
+        );
+        parse_timestamp("2023-02-29T00:00:00Z").unwrap_err();
+        parse_timestamp("2021-09-30 16:25:24Z").unwrap_err();
+        parse_timestamp("2021-09-30T16:25:24").unwrap_err();
+    }
+}
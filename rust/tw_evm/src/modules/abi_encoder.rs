@@ -2,16 +2,21 @@
 //
 // Copyright © 2017 Trust Wallet.
 
+use crate::abi::contract::Contract;
 use crate::abi::decode::{decode_params, decode_value};
+use crate::abi::encode::encode_packed;
 use crate::abi::function::Function;
 use crate::abi::param::Param;
 use crate::abi::param_token::NamedToken;
 use crate::abi::param_type::ParamType;
+use crate::abi::revert::decode_revert;
 use crate::abi::token::Token;
 use crate::abi::{AbiError, AbiErrorKind, AbiResult};
 use crate::abi_output_error;
 use crate::address::Address;
 use crate::evm_context::EvmContext;
+use crate::message::eip712::eip712_message::Eip712Message;
+use crate::message::{MessageSigningError, MessageSigningErrorKind};
 use serde::Deserialize;
 use serde::Serialize;
 use std::borrow::Cow;
@@ -19,7 +24,7 @@ use std::collections::HashMap;
 use std::marker::PhantomData;
 use std::str::FromStr;
 use tw_encoding::hex::as_hex;
-use tw_hash::H32;
+use tw_hash::{H256, H32};
 use tw_misc::traits::ToBytesVec;
 use tw_number::{I256, U256};
 use tw_proto::EthereumAbi::Proto;
@@ -44,6 +49,20 @@ impl<Context: EvmContext> AbiEncoder<Context> {
             .unwrap_or_else(|err| abi_output_error!(Proto::ContractCallDecodingOutput, err))
     }
 
+    #[inline]
+    pub fn decode_log(input: Proto::LogDecodingInput<'_>) -> Proto::LogDecodingOutput<'static> {
+        Self::decode_log_impl(input)
+            .unwrap_or_else(|err| abi_output_error!(Proto::LogDecodingOutput, err))
+    }
+
+    #[inline]
+    pub fn decode_revert(
+        input: Proto::RevertDecodingInput<'_>,
+    ) -> Proto::RevertDecodingOutput<'static> {
+        Self::decode_revert_impl(input)
+            .unwrap_or_else(|err| abi_output_error!(Proto::RevertDecodingOutput, err))
+    }
+
     #[inline]
     pub fn decode_params(
         input: Proto::ParamsDecodingInput<'_>,
@@ -73,6 +92,22 @@ impl<Context: EvmContext> AbiEncoder<Context> {
             .unwrap_or_else(|err| abi_output_error!(Proto::FunctionEncodingOutput, err))
     }
 
+    #[inline]
+    pub fn encode_packed(
+        input: Proto::PackedEncodingInput<'_>,
+    ) -> Proto::PackedEncodingOutput<'static> {
+        Self::encode_packed_impl(input)
+            .unwrap_or_else(|err| abi_output_error!(Proto::PackedEncodingOutput, err))
+    }
+
+    #[inline]
+    pub fn hash_typed_data(
+        input: Proto::TypedDataHashingInput<'_>,
+    ) -> Proto::TypedDataHashingOutput<'static> {
+        Self::hash_typed_data_impl(input)
+            .unwrap_or_else(|err| abi_output_error!(Proto::TypedDataHashingOutput, err))
+    }
+
     fn decode_contract_call_impl(
         input: Proto::ContractCallDecodingInput,
     ) -> AbiResult<Proto::ContractCallDecodingOutput<'static>> {
@@ -129,6 +164,90 @@ impl<Context: EvmContext> AbiEncoder<Context> {
         })
     }
 
+    fn decode_log_impl(
+        input: Proto::LogDecodingInput<'_>,
+    ) -> AbiResult<Proto::LogDecodingOutput<'static>> {
+        let topics = input
+            .topics
+            .iter()
+            .map(|topic| H256::try_from(topic.as_ref()))
+            .collect::<Result<Vec<_>, _>>()
+            .tw_err(|_| AbiErrorKind::Error_decoding_data)
+            .context("Each log topic must be 32 bytes long")?;
+        let topic0 = topics
+            .first()
+            .or_tw_err(AbiErrorKind::Error_decoding_data)
+            .context("Expected at least one log topic")?;
+
+        let contract: Contract = serde_json::from_str(&input.smart_contract_abi_json)
+            .tw_err(|_| AbiErrorKind::Error_invalid_abi)
+            .context("Error deserializing Smart Contract ABI as JSON")?;
+
+        let event = contract.event_by_topic(topic0)?;
+        let decoded_tokens = event.decode_log(&topics, &input.data)?;
+        let event_signature = event.signature();
+
+        // Serialize the `decoded_json` result.
+        let decoded_res = SmartContractEventDecodedJson {
+            event: event_signature.clone(),
+            inputs: &decoded_tokens,
+        };
+        let decoded_json = serde_json::to_string(&decoded_res)
+            .tw_err(|_| AbiErrorKind::Error_internal)
+            .context("Error serializing Smart Contract Event as JSON")?;
+
+        // Serialize the Proto parameters.
+        let decoded_protos = decoded_tokens
+            .into_iter()
+            .map(Self::named_token_to_proto)
+            .collect();
+
+        Ok(Proto::LogDecodingOutput {
+            event_signature: Cow::Owned(event_signature),
+            decoded_json: Cow::Owned(decoded_json),
+            tokens: decoded_protos,
+            ..Proto::LogDecodingOutput::default()
+        })
+    }
+
+    fn decode_revert_impl(
+        input: Proto::RevertDecodingInput<'_>,
+    ) -> AbiResult<Proto::RevertDecodingOutput<'static>> {
+        let contract: Contract = if input.smart_contract_abi_json.is_empty() {
+            Contract::default()
+        } else {
+            serde_json::from_str(&input.smart_contract_abi_json)
+                .tw_err(|_| AbiErrorKind::Error_invalid_abi)
+                .context("Error deserializing Smart Contract ABI as JSON")?
+        };
+
+        let revert = decode_revert(&contract, &input.encoded)?;
+
+        // Serialize the `decoded_json` result.
+        let decoded_res = SmartContractErrorDecodedJson {
+            error: revert.signature.clone(),
+            inputs: &revert.params,
+        };
+        let decoded_json = serde_json::to_string(&decoded_res)
+            .tw_err(|_| AbiErrorKind::Error_internal)
+            .context("Error serializing Smart Contract Error as JSON")?;
+
+        // Serialize the Proto parameters.
+        let decoded_protos = revert
+            .params
+            .into_iter()
+            .map(Self::named_token_to_proto)
+            .collect();
+
+        Ok(Proto::RevertDecodingOutput {
+            error_signature: Cow::Owned(revert.signature),
+            reason: Cow::Owned(revert.reason),
+            decoded_json: Cow::Owned(decoded_json),
+            tokens: decoded_protos,
+            ..Proto::RevertDecodingOutput::default()
+        })
+    }
+
     fn decode_params_impl(
         input: Proto::ParamsDecodingInput<'_>,
     ) -> AbiResult<Proto::ParamsDecodingOutput<'static>> {
@@ -216,6 +335,39 @@ impl<Context: EvmContext> AbiEncoder<Context> {
         })
     }
 
+    fn encode_packed_impl(
+        input: Proto::PackedEncodingInput<'_>,
+    ) -> AbiResult<Proto::PackedEncodingOutput<'static>> {
+        let tokens = input
+            .tokens
+            .into_iter()
+            .map(Self::token_from_proto)
+            .collect::<AbiResult<Vec<_>>>()?;
+
+        let encoded = encode_packed(&tokens)?;
+        Ok(Proto::PackedEncodingOutput {
+            encoded: encoded.into(),
+            ..Proto::PackedEncodingOutput::default()
+        })
+    }
+
+    fn hash_typed_data_impl(
+        input: Proto::TypedDataHashingInput<'_>,
+    ) -> AbiResult<Proto::TypedDataHashingOutput<'static>> {
+        let msg = Eip712Message::new(&input.message_json).map_err(message_to_abi_error)?;
+
+        let domain_separator = msg.domain_separator().map_err(message_to_abi_error)?;
+        let message_hash = msg.message_hash().map_err(message_to_abi_error)?;
+        let signing_hash = msg.signing_hash().map_err(message_to_abi_error)?;
+
+        Ok(Proto::TypedDataHashingOutput {
+            domain_separator: domain_separator.to_vec().into(),
+            message_hash: message_hash.to_vec().into(),
+            signing_hash: signing_hash.to_vec().into(),
+            ..Proto::TypedDataHashingOutput::default()
+        })
+    }
+
     pub fn param_to_proto(param: Param) -> Proto::Param<'static> {
         Proto::Param {
             name: Cow::Owned(param.name.unwrap_or_default()),
@@ -468,6 +620,16 @@ impl<Context: EvmContext> AbiEncoder<Context> {
     }
 }
 
+fn message_to_abi_error(msg_err: MessageSigningError) -> AbiError {
+    msg_err.map_err(|msg_kind| match msg_kind {
+        MessageSigningErrorKind::InvalidParameterType => AbiErrorKind::Error_invalid_param_type,
+        MessageSigningErrorKind::InvalidParameterValue
+        | MessageSigningErrorKind::TypeValueMismatch
+        | MessageSigningErrorKind::InvalidChainId => AbiErrorKind::Error_abi_mismatch,
+        MessageSigningErrorKind::Internal => AbiErrorKind::Error_internal,
+    })
+}
+
 #[derive(Deserialize)]
 struct SmartContractCallAbiJson {
     #[serde(flatten)]
@@ -483,6 +645,18 @@ struct SmartContractCallDecodedInputJson<'a> {
     inputs: &'a [NamedToken],
 }
 
+#[derive(Serialize)]
+struct SmartContractEventDecodedJson<'a> {
+    event: String,
+    inputs: &'a [NamedToken],
+}
+
+#[derive(Serialize)]
+struct SmartContractErrorDecodedJson<'a> {
+    error: String,
+    inputs: &'a [NamedToken],
+}
+
 /// A value type used on [`AbiEncoder::decode_value`].
 /// Please note [`AbiEncoder::decode_value`] doesn't support `ParamType::Tuple` for decoding.
 struct DecodingValueType(ParamType);
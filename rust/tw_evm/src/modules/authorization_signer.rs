@@ -0,0 +1,63 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::evm_context::EvmContext;
+use crate::modules::tx_builder::TxBuilder;
+use std::borrow::Cow;
+use std::marker::PhantomData;
+use tw_coin_entry::error::prelude::*;
+use tw_coin_entry::signing_output_error;
+use tw_keypair::ecdsa::secp256k1;
+use tw_proto::Ethereum::Proto;
+
+/// Signs [EIP-7702](https://eips.ethereum.org/EIPS/eip-7702) authorizations.
+pub struct AuthorizationSigner<Context: EvmContext> {
+    _phantom: PhantomData<Context>,
+}
+
+impl<Context: EvmContext> AuthorizationSigner<Context> {
+    #[inline]
+    pub fn sign_proto(
+        input: Proto::AuthorizationSigningInput<'_>,
+    ) -> Proto::AuthorizationSigningOutput<'static> {
+        Self::sign_proto_impl(input)
+            .unwrap_or_else(|e| signing_output_error!(Proto::AuthorizationSigningOutput, e))
+    }
+
+    fn sign_proto_impl(
+        input: Proto::AuthorizationSigningInput<'_>,
+    ) -> SigningResult<Proto::AuthorizationSigningOutput<'static>> {
+        let Some(ref authorization_proto) = input.authorization else {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("No authorization specified");
+        };
+
+        let authorization = TxBuilder::<Context>::authorization_from_proto(authorization_proto)?;
+        let pre_hash = authorization.pre_hash();
+
+        if input.private_key.is_empty() {
+            // External signing, return the pre-hash only.
+            return Ok(Proto::AuthorizationSigningOutput {
+                pre_hash: pre_hash.to_vec().into(),
+                ..Proto::AuthorizationSigningOutput::default()
+            });
+        }
+
+        let private_key = secp256k1::PrivateKey::try_from(input.private_key.as_ref())?;
+        let signed = authorization.sign(&private_key)?;
+
+        let signed_proto = Proto::Authorization {
+            chain_id: authorization_proto.chain_id.to_vec().into(),
+            address: authorization_proto.address.to_string().into(),
+            nonce: authorization_proto.nonce.to_vec().into(),
+            signature: Cow::from(signed.signature().to_rsv_bytes().to_vec()),
+        };
+
+        Ok(Proto::AuthorizationSigningOutput {
+            authorization: Some(signed_proto),
+            pre_hash: pre_hash.to_vec().into(),
+            ..Proto::AuthorizationSigningOutput::default()
+        })
+    }
+}
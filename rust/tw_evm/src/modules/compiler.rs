@@ -86,6 +86,7 @@ impl<Context: EvmContext> Compiler<Context> {
             s: eth_signature.s().to_big_endian_compact().into(),
             data: signed.payload().into(),
             pre_hash: pre_hash.to_vec().into(),
+            encoded_network_wrapper: signed.encode_network_wrapper().unwrap_or_default().into(),
             ..Proto::SigningOutput::default()
         })
     }
@@ -5,6 +5,7 @@
 use crate::message::eip191::Eip191Message;
 use crate::message::eip712::eip712_message::Eip712Message;
 use crate::message::signature::{MessageSignature, SignatureType};
+use crate::message::siwe::SiweMessage;
 use crate::message::{to_signing, EthMessage, EthMessageBoxed};
 use std::borrow::Cow;
 use std::str::FromStr;
@@ -57,6 +58,20 @@ impl MessageSigner for EthMessageSigner {
 }
 
 impl EthMessageSigner {
+    /// Signs an EIP-712 typed data structure.
+    /// The signature has `v` equal to 27 or 28 as expected by `ecrecover`.
+    pub fn sign_typed_data(
+        msg: &Eip712Message,
+        private_key: &secp256k1::PrivateKey,
+    ) -> SigningResult<MessageSignature> {
+        let hash_to_sign = msg.hash().map_err(to_signing)?;
+        let secp_sign = private_key.sign(hash_to_sign)?;
+        Ok(MessageSignature::prepared(
+            secp_sign,
+            SignatureType::Legacy,
+        )?)
+    }
+
     fn message_preimage_hashes_impl(
         input: Proto::MessageSigningInput<'_>,
     ) -> SigningResult<CompilerProto::PreSigningOutput<'static>> {
@@ -111,6 +126,21 @@ impl EthMessageSigner {
             | Proto::MessageType::MessageType_immutable_x => {
                 Ok(Eip191Message::new(input.message).into_boxed())
             },
+            Proto::MessageType::MessageType_siwe => {
+                let msg = SiweMessage::from_str(&input.message).map_err(to_signing)?;
+                if let Some(expected_chain_id) = input.chain_id {
+                    if msg.chain_id != expected_chain_id.chain_id {
+                        return SigningError::err(SigningErrorType::Error_invalid_params)
+                            .with_context(|| {
+                                format!(
+                                    "Expected '{}' chainId, found '{}'",
+                                    expected_chain_id.chain_id, msg.chain_id
+                                )
+                            });
+                    }
+                }
+                Ok(msg.into_boxed())
+            },
             Proto::MessageType::MessageType_typed
             | Proto::MessageType::MessageType_typed_eip155 => match input.chain_id {
                 Some(expected_chain_id) => {
@@ -139,9 +169,9 @@ impl EthMessageSigner {
     ) -> SignatureType {
         match msg_type {
             Proto::MessageType::MessageType_immutable_x => SignatureType::Standard,
-            Proto::MessageType::MessageType_legacy | Proto::MessageType::MessageType_typed => {
-                SignatureType::Legacy
-            },
+            Proto::MessageType::MessageType_legacy
+            | Proto::MessageType::MessageType_typed
+            | Proto::MessageType::MessageType_siwe => SignatureType::Legacy,
             Proto::MessageType::MessageType_eip155
             | Proto::MessageType::MessageType_typed_eip155 => {
                 let chain_id = U256::from(maybe_chain_id.unwrap_or_default().chain_id);
@@ -3,9 +3,14 @@
 // Copyright © 2017 Trust Wallet.
 
 pub mod abi_encoder;
+pub mod authorization_signer;
 pub mod compiler;
 pub mod message_signer;
+pub mod permit_signer;
 pub mod rlp_encoder;
 pub mod signer;
+pub mod siwe_verifier;
+pub mod smart_account_address;
+pub mod transaction_decoder;
 pub mod transaction_util;
 pub mod tx_builder;
@@ -0,0 +1,371 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::abi::abi_to_signing_error;
+use crate::abi::prebuild::erc2612::{Erc2612, PermitArgs};
+use crate::abi::prebuild::permit2::{
+    Permit2, PermitBatchArgs, PermitDetailsArgs, PermitSingleArgs,
+};
+use crate::address::{Address, EvmAddress};
+use crate::evm_context::EvmContext;
+use crate::message::eip712::eip712_message::Eip712Message;
+use crate::message::permit::{
+    Erc2612Permit, Permit2Batch, Permit2Details, Permit2Single, Permit2TransferFrom,
+    PERMIT2_ADDRESS,
+};
+use crate::message::signature::{MessageSignature, SignatureType};
+use crate::message::{to_signing, EthMessage};
+use crate::modules::message_signer::EthMessageSigner;
+use std::borrow::Cow;
+use std::marker::PhantomData;
+use std::str::FromStr;
+use tw_coin_entry::error::prelude::*;
+use tw_coin_entry::signing_output_error;
+use tw_hash::H256;
+use tw_keypair::ecdsa::secp256k1;
+use tw_memory::Data;
+use tw_number::U256;
+use tw_proto::Ethereum::Proto;
+
+use Proto::mod_PermitSigningInput::OneOfpermit_oneof as PermitType;
+
+/// Builds and signs [EIP-2612](https://eips.ethereum.org/EIPS/eip-2612) and Uniswap Permit2 permits.
+pub struct PermitSigner<Context: EvmContext> {
+    _phantom: PhantomData<Context>,
+}
+
+impl<Context: EvmContext> PermitSigner<Context> {
+    #[inline]
+    pub fn sign_proto(input: Proto::PermitSigningInput<'_>) -> Proto::PermitSigningOutput<'static> {
+        Self::sign_proto_impl(input)
+            .unwrap_or_else(|e| signing_output_error!(Proto::PermitSigningOutput, e))
+    }
+
+    /// Builds the `permit(...)` call with a signature of the permit typed data produced externally.
+    /// `PermitSigningInput.owner` is required, `PermitSigningInput.private_key` is not used.
+    #[inline]
+    pub fn compile_proto(
+        input: Proto::PermitSigningInput<'_>,
+        signature: &[u8],
+    ) -> Proto::PermitSigningOutput<'static> {
+        Self::compile_proto_impl(input, signature)
+            .unwrap_or_else(|e| signing_output_error!(Proto::PermitSigningOutput, e))
+    }
+
+    fn sign_proto_impl(
+        input: Proto::PermitSigningInput<'_>,
+    ) -> SigningResult<Proto::PermitSigningOutput<'static>> {
+        let private_key = if input.private_key.is_empty() {
+            None
+        } else {
+            Some(secp256k1::PrivateKey::try_from(input.private_key.as_ref())?)
+        };
+        let prepared = Self::prepare(&input, private_key.as_ref())?;
+
+        let Some(private_key) = private_key else {
+            // External signing, return the typed data and its hash only.
+            return prepared.to_output(None);
+        };
+
+        let signature = EthMessageSigner::sign_typed_data(&prepared.typed_data, &private_key)?;
+        prepared.to_output(Some(&signature))
+    }
+
+    fn compile_proto_impl(
+        input: Proto::PermitSigningInput<'_>,
+        signature: &[u8],
+    ) -> SigningResult<Proto::PermitSigningOutput<'static>> {
+        if input.owner.is_empty() {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("Owner address must be specified to compile a permit");
+        }
+        let prepared = Self::prepare(&input, None)?;
+
+        let signature = MessageSignature::try_from(signature)
+            .and_then(|sign| sign.to_secp256k1_signature())
+            .into_tw()
+            .context("Invalid permit signature")?;
+        let public_key = secp256k1::PublicKey::recover(signature.clone(), prepared.pre_hash)
+            .into_tw()
+            .context("Error recovering the permit signer public key")?;
+        if Address::with_secp256k1_pubkey(&public_key) != prepared.owner {
+            return SigningError::err(SigningErrorType::Error_signing)
+                .context("The permit is not signed by the owner");
+        }
+
+        // Permit contracts expect `v` to be 27 or 28.
+        let signature = MessageSignature::prepared(signature, SignatureType::Legacy)?;
+        prepared.to_output(Some(&signature))
+    }
+
+    fn prepare(
+        input: &Proto::PermitSigningInput<'_>,
+        private_key: Option<&secp256k1::PrivateKey>,
+    ) -> SigningResult<PreparedPermit> {
+        let chain_id = U256::from_big_endian_slice(&input.chain_id)
+            .into_tw()
+            .context("Invalid chain ID")?;
+        let owner = Self::owner(&input.owner, private_key)?;
+
+        let permit2 = if input.permit2_address.is_empty() {
+            Self::parse_address(PERMIT2_ADDRESS)?
+        } else {
+            Self::parse_address(&input.permit2_address).context("Invalid Permit2 address")?
+        };
+
+        let permit = match input.permit_oneof {
+            PermitType::erc2612_permit(ref permit) => {
+                Permit::Erc2612(Self::erc2612_permit_from_proto(permit, owner)?)
+            },
+            PermitType::permit2_single(ref permit) => {
+                Permit::Single(Self::permit2_single_from_proto(permit)?)
+            },
+            PermitType::permit2_batch(ref permit) => {
+                Permit::Batch(Self::permit2_batch_from_proto(permit)?)
+            },
+            PermitType::permit2_transfer_from(ref permit) => {
+                Permit::TransferFrom(Self::permit2_transfer_from_from_proto(permit)?)
+            },
+            PermitType::None => {
+                return SigningError::err(SigningErrorType::Error_invalid_params)
+                    .context("No permit specified")
+            },
+        };
+
+        let typed_data = permit.to_eip712_message(chain_id, permit2);
+        let pre_hash = typed_data.hash().map_err(to_signing)?;
+
+        Ok(PreparedPermit {
+            permit,
+            owner,
+            permit2,
+            typed_data,
+            pre_hash,
+        })
+    }
+
+    /// Returns the owner address derived from the private key if given,
+    /// and checks it matches the explicitly specified owner if any.
+    fn owner(owner: &str, private_key: Option<&secp256k1::PrivateKey>) -> SigningResult<Address> {
+        let owner = Self::parse_address_optional(owner).context("Invalid owner address")?;
+        let Some(private_key) = private_key else {
+            return owner
+                .or_tw_err(SigningErrorType::Error_invalid_params)
+                .context("Either owner address or private key must be specified");
+        };
+
+        let derived_owner = Address::with_secp256k1_pubkey(&private_key.public());
+        match owner {
+            Some(owner) if owner != derived_owner => {
+                SigningError::err(SigningErrorType::Error_invalid_params)
+                    .context("Owner address does not match the private key")
+            },
+            _ => Ok(derived_owner),
+        }
+    }
+
+    fn erc2612_permit_from_proto(
+        permit: &Proto::Erc2612Permit,
+        owner: Address,
+    ) -> SigningResult<Erc2612Permit> {
+        Ok(Erc2612Permit {
+            token: Self::parse_address(&permit.token).context("Invalid token address")?,
+            token_name: permit.token_name.to_string(),
+            token_version: permit.token_version.to_string(),
+            owner,
+            spender: Self::parse_address(&permit.spender).context("Invalid spender address")?,
+            value: Self::parse_uint(&permit.value, 256).context("Invalid value")?,
+            nonce: Self::parse_uint(&permit.nonce, 256).context("Invalid nonce")?,
+            deadline: Self::parse_uint(&permit.deadline, 256).context("Invalid deadline")?,
+        })
+    }
+
+    fn permit2_details_from_proto(
+        details: &Proto::Permit2Details,
+    ) -> SigningResult<Permit2Details> {
+        Ok(Permit2Details {
+            token: Self::parse_address(&details.token).context("Invalid token address")?,
+            amount: Self::parse_uint(&details.amount, 160).context("Invalid amount")?,
+            expiration: Self::parse_uint(&details.expiration, 48).context("Invalid expiration")?,
+            nonce: Self::parse_uint(&details.nonce, 48).context("Invalid nonce")?,
+        })
+    }
+
+    fn permit2_single_from_proto(permit: &Proto::Permit2Single) -> SigningResult<Permit2Single> {
+        let details = permit
+            .details
+            .as_ref()
+            .or_tw_err(SigningErrorType::Error_invalid_params)
+            .context("No permit details specified")?;
+
+        Ok(Permit2Single {
+            details: Self::permit2_details_from_proto(details)?,
+            spender: Self::parse_address(&permit.spender).context("Invalid spender address")?,
+            sig_deadline: Self::parse_uint(&permit.sig_deadline, 256)
+                .context("Invalid signature deadline")?,
+        })
+    }
+
+    fn permit2_batch_from_proto(permit: &Proto::Permit2Batch) -> SigningResult<Permit2Batch> {
+        if permit.details.is_empty() {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("No permit details specified");
+        }
+        let details = permit
+            .details
+            .iter()
+            .map(Self::permit2_details_from_proto)
+            .collect::<SigningResult<Vec<_>>>()?;
+
+        Ok(Permit2Batch {
+            details,
+            spender: Self::parse_address(&permit.spender).context("Invalid spender address")?,
+            sig_deadline: Self::parse_uint(&permit.sig_deadline, 256)
+                .context("Invalid signature deadline")?,
+        })
+    }
+
+    fn permit2_transfer_from_from_proto(
+        permit: &Proto::Permit2TransferFrom,
+    ) -> SigningResult<Permit2TransferFrom> {
+        Ok(Permit2TransferFrom {
+            token: Self::parse_address(&permit.token).context("Invalid token address")?,
+            amount: Self::parse_uint(&permit.amount, 256).context("Invalid amount")?,
+            spender: Self::parse_address(&permit.spender).context("Invalid spender address")?,
+            nonce: Self::parse_uint(&permit.nonce, 256).context("Invalid nonce")?,
+            deadline: Self::parse_uint(&permit.deadline, 256).context("Invalid deadline")?,
+        })
+    }
+
+    /// Parses a big endian `uintN` value.
+    fn parse_uint(num: &[u8], bits: usize) -> SigningResult<U256> {
+        let num = U256::from_big_endian_slice(num).into_tw()?;
+        if num.bits() > bits {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .with_context(|| format!("The value does not fit 'uint{bits}'"));
+        }
+        Ok(num)
+    }
+
+    fn parse_address(addr: &str) -> SigningResult<Address> {
+        Context::Address::from_str(addr)
+            .map(Context::Address::into)
+            .map_err(SigningError::from)
+    }
+
+    fn parse_address_optional(addr: &str) -> SigningResult<Option<Address>> {
+        match Context::Address::from_str_optional(addr) {
+            Ok(Some(addr)) => Ok(Some(addr.into())),
+            Ok(None) => Ok(None),
+            Err(e) => Err(SigningError::from(e)),
+        }
+    }
+}
+
+/// A permit with its EIP-712 typed data, ready to be signed.
+struct PreparedPermit {
+    permit: Permit,
+    owner: Address,
+    permit2: Address,
+    typed_data: Eip712Message,
+    pre_hash: H256,
+}
+
+impl PreparedPermit {
+    /// Returns the typed data and its hash, and the `permit(...)` call if the signature is given.
+    fn to_output(
+        &self,
+        signature: Option<&MessageSignature>,
+    ) -> SigningResult<Proto::PermitSigningOutput<'static>> {
+        let typed_data_json = serde_json::to_string(&self.typed_data)
+            .tw_err(|_| SigningErrorType::Error_internal)
+            .context("Error serializing EIP712 typed data as JSON")?;
+        let mut output = Proto::PermitSigningOutput {
+            typed_data: typed_data_json.into(),
+            pre_hash: self.pre_hash.to_vec().into(),
+            to: self.permit.call_address(self.permit2).to_string().into(),
+            ..Proto::PermitSigningOutput::default()
+        };
+
+        if let Some(signature) = signature {
+            output.signature = signature.to_bytes().to_vec().into();
+            output.payload = Cow::from(self.permit.encode_call(self.owner, signature)?);
+        }
+        Ok(output)
+    }
+}
+
+enum Permit {
+    Erc2612(Erc2612Permit),
+    Single(Permit2Single),
+    Batch(Permit2Batch),
+    TransferFrom(Permit2TransferFrom),
+}
+
+impl Permit {
+    fn to_eip712_message(&self, chain_id: U256, permit2: Address) -> Eip712Message {
+        match self {
+            Permit::Erc2612(permit) => permit.to_eip712_message(chain_id),
+            Permit::Single(permit) => permit.to_eip712_message(chain_id, permit2),
+            Permit::Batch(permit) => permit.to_eip712_message(chain_id, permit2),
+            Permit::TransferFrom(permit) => permit.to_eip712_message(chain_id, permit2),
+        }
+    }
+
+    /// Returns the contract address the signature is verified by.
+    fn call_address(&self, permit2: Address) -> Address {
+        match self {
+            Permit::Erc2612(permit) => permit.token,
+            Permit::Single(_) | Permit::Batch(_) | Permit::TransferFrom(_) => permit2,
+        }
+    }
+
+    /// Encodes the `permit(...)` call.
+    /// Returns an empty payload for `PermitTransferFrom` as the signature is consumed by the spender.
+    fn encode_call(&self, owner: Address, signature: &MessageSignature) -> SigningResult<Data> {
+        let signature_bytes = signature.to_bytes();
+        let payload = match self {
+            Permit::Erc2612(permit) => {
+                let r = H256::try_from(&signature_bytes[secp256k1::Signature::R_RANGE])
+                    .expect("Expected 'r' 32 byte length array");
+                let s = H256::try_from(&signature_bytes[secp256k1::Signature::S_RANGE])
+                    .expect("Expected 's' 32 byte length array");
+                Erc2612::permit(PermitArgs {
+                    owner,
+                    spender: permit.spender,
+                    value: permit.value,
+                    deadline: permit.deadline,
+                    v: signature_bytes[secp256k1::Signature::RECOVERY_LAST],
+                    r,
+                    s,
+                })
+            },
+            Permit::Single(permit) => Permit2::permit_single(PermitSingleArgs {
+                owner,
+                details: permit_details_args(&permit.details),
+                spender: permit.spender,
+                sig_deadline: permit.sig_deadline,
+                signature: signature_bytes.to_vec(),
+            }),
+            Permit::Batch(permit) => Permit2::permit_batch(PermitBatchArgs {
+                owner,
+                details: permit.details.iter().map(permit_details_args).collect(),
+                spender: permit.spender,
+                sig_deadline: permit.sig_deadline,
+                signature: signature_bytes.to_vec(),
+            }),
+            Permit::TransferFrom(_) => Ok(Data::default()),
+        };
+        payload.map_err(abi_to_signing_error)
+    }
+}
+
+fn permit_details_args(details: &Permit2Details) -> PermitDetailsArgs {
+    PermitDetailsArgs {
+        token: details.token,
+        amount: details.amount,
+        expiration: details.expiration,
+        nonce: details.nonce,
+    }
+}
@@ -54,6 +54,7 @@ impl<Context: EvmContext> Signer<Context> {
             s: Cow::from(eth_signature.s().to_big_endian().to_vec()),
             data: Cow::from(signed.payload()),
             pre_hash: Cow::from(pre_hash.to_vec()),
+            encoded_network_wrapper: Cow::from(signed.encode_network_wrapper().unwrap_or_default()),
             ..Proto::SigningOutput::default()
         })
     }
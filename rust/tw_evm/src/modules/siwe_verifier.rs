@@ -0,0 +1,90 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::abi::abi_to_signing_error;
+use crate::abi::prebuild::erc1271::Erc1271;
+use crate::address::Address;
+use crate::message::signature::MessageSignature;
+use crate::message::siwe::SiweMessage;
+use crate::message::{to_signing, EthMessage};
+use std::borrow::Cow;
+use std::str::FromStr;
+use tw_coin_entry::error::prelude::*;
+use tw_coin_entry::signing_output_error;
+use tw_encoding::hex::DecodeHex;
+use tw_hash::H256;
+use tw_keypair::ecdsa::secp256k1;
+use tw_proto::Ethereum::Proto;
+
+/// Verifies [EIP-4361](https://eips.ethereum.org/EIPS/eip-4361) Sign-In with Ethereum messages.
+pub struct SiweVerifier;
+
+impl SiweVerifier {
+    #[inline]
+    pub fn verify_proto(
+        input: Proto::SiweVerifyingInput<'_>,
+    ) -> Proto::SiweVerifyingOutput<'static> {
+        Self::verify_proto_impl(input)
+            .unwrap_or_else(|e| signing_output_error!(Proto::SiweVerifyingOutput, e))
+    }
+
+    fn verify_proto_impl(
+        input: Proto::SiweVerifyingInput<'_>,
+    ) -> SigningResult<Proto::SiweVerifyingOutput<'static>> {
+        let msg = SiweMessage::from_str(&input.message)
+            .map_err(to_signing)
+            .context("Invalid Sign-In with Ethereum message")?;
+
+        if !input.domain.is_empty() && input.domain != msg.domain {
+            return SigningError::err(SigningErrorType::Error_invalid_params).with_context(|| {
+                format!("Expected '{}' domain, found '{}'", input.domain, msg.domain)
+            });
+        }
+        if !input.nonce.is_empty() && input.nonce != msg.nonce {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("Nonce mismatch");
+        }
+        if input.timestamp != 0 {
+            let timestamp = i64::try_from(input.timestamp)
+                .tw_err(|_| SigningErrorType::Error_invalid_params)
+                .context("Invalid timestamp")?;
+            msg.validate_time(timestamp)
+                .map_err(to_signing)
+                .context("The message is expired or not yet valid")?;
+        }
+
+        let signature = input
+            .signature
+            .as_ref()
+            .decode_hex()
+            .tw_err(|_| SigningErrorType::Error_invalid_params)
+            .context("Invalid signature hex")?;
+        let message_hash = msg.hash().map_err(to_signing)?;
+
+        let recovered_address = Self::recover_address(&signature, message_hash);
+        let valid = recovered_address == Some(msg.address);
+
+        let eip1271_payload =
+            Erc1271::is_valid_signature(message_hash, signature).map_err(abi_to_signing_error)?;
+
+        Ok(Proto::SiweVerifyingOutput {
+            valid,
+            recovered_address: recovered_address
+                .map(|addr| Cow::from(addr.to_string()))
+                .unwrap_or_default(),
+            message_hash: message_hash.to_vec().into(),
+            eip1271_payload: eip1271_payload.into(),
+            ..Proto::SiweVerifyingOutput::default()
+        })
+    }
+
+    /// Returns `None` if the signature is not a valid ECDSA signature, e.g of a smart contract account.
+    fn recover_address(signature: &[u8], hash: H256) -> Option<Address> {
+        let signature = MessageSignature::try_from(signature)
+            .and_then(|sign| sign.to_secp256k1_signature())
+            .ok()?;
+        let public_key = secp256k1::PublicKey::recover(signature, hash).ok()?;
+        Some(Address::with_secp256k1_pubkey(&public_key))
+    }
+}
@@ -0,0 +1,59 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::address::Address;
+use crate::evm_context::EvmContext;
+use crate::modules::tx_builder::TxBuilder;
+use std::marker::PhantomData;
+use std::str::FromStr;
+use tw_coin_entry::error::prelude::*;
+use tw_coin_entry::signing_output_error;
+use tw_number::U256;
+use tw_proto::Ethereum::Proto;
+
+/// Derives counterfactual ERC-4337 smart account addresses, i.e. addresses the accounts will be deployed at
+/// by their factories via `CREATE2`.
+pub struct SmartAccountAddress<Context: EvmContext> {
+    _phantom: PhantomData<Context>,
+}
+
+impl<Context: EvmContext> SmartAccountAddress<Context> {
+    #[inline]
+    pub fn derive_proto(
+        input: Proto::SmartAccountAddressInput<'_>,
+    ) -> Proto::SmartAccountAddressOutput<'static> {
+        Self::derive_proto_impl(input)
+            .unwrap_or_else(|e| signing_output_error!(Proto::SmartAccountAddressOutput, e))
+    }
+
+    fn derive_proto_impl(
+        input: Proto::SmartAccountAddressInput<'_>,
+    ) -> SigningResult<Proto::SmartAccountAddressOutput<'static>> {
+        let account = TxBuilder::<Context>::smart_account_from_proto(input.account.as_ref())?;
+
+        let factory =
+            Self::parse_address(&input.factory).context("Invalid account factory address")?;
+        let owner = Self::parse_address(&input.owner).context("Invalid account owner address")?;
+        let salt = U256::from_big_endian_slice(&input.salt)
+            .into_tw()
+            .context("Invalid salt")?;
+
+        let deployment = account.deployment(owner, salt, &input.proxy_bytecode)?;
+        let address = deployment.address(factory);
+        let init_code = [factory.as_slice(), deployment.factory_data.as_slice()].concat();
+
+        Ok(Proto::SmartAccountAddressOutput {
+            address: address.to_string().into(),
+            factory_data: deployment.factory_data.into(),
+            init_code: init_code.into(),
+            ..Proto::SmartAccountAddressOutput::default()
+        })
+    }
+
+    fn parse_address(addr: &str) -> SigningResult<Address> {
+        Context::Address::from_str(addr)
+            .map(Context::Address::into)
+            .map_err(SigningError::from)
+    }
+}
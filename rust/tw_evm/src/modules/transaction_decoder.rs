@@ -0,0 +1,655 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::abi::prebuild::erc1155::Erc1155;
+use crate::abi::prebuild::erc20::Erc20;
+use crate::abi::prebuild::erc721::Erc721;
+use crate::abi::AbiErrorKind;
+use crate::address::Address;
+use crate::evm_context::EvmContext;
+use crate::modules::abi_encoder::AbiEncoder;
+use crate::rlp::decode::{decode_list, list_items, RlpDecode};
+use crate::rlp::list::RlpList;
+use crate::signature::split_replay_protection;
+use crate::transaction::access_list::AccessList;
+use crate::transaction::authorization_list::AuthorizationList;
+use crate::transaction::blob::{rlp_decode_hashes, BlobSidecar};
+use rlp::Rlp;
+use std::borrow::Cow;
+use std::marker::PhantomData;
+use std::str::FromStr;
+use tw_coin_entry::coin_context::CoinContext;
+use tw_coin_entry::error::prelude::*;
+use tw_coin_entry::modules::transaction_decoder::TransactionDecoder;
+use tw_coin_entry::signing_output_error;
+use tw_hash::sha3::keccak256;
+use tw_hash::H256;
+use tw_keypair::ecdsa::secp256k1;
+use tw_memory::Data;
+use tw_number::U256;
+use tw_proto::Ethereum::Proto;
+use tw_proto::EthereumAbi::Proto as AbiProto;
+use AbiProto::mod_Token::OneOftoken as TokenEnum;
+use Proto::mod_Transaction::OneOftransaction_oneof as TransactionType;
+
+const LEGACY_TX_TYPE: u8 = 0x00;
+const EIP2930_TX_TYPE: u8 = 0x01;
+const EIP1559_TX_TYPE: u8 = 0x02;
+const EIP4844_TX_TYPE: u8 = 0x03;
+const EIP7702_TX_TYPE: u8 = 0x04;
+/// Legacy transactions are RLP lists, so they start with a byte in `0xc0..=0xff` range.
+const RLP_LIST_OFFSET: u8 = 0xc0;
+/// Number of the `v`, `r`, `s` signature items.
+const SIGNATURE_ITEMS: usize = 3;
+/// `v` is serialized the same way as in `Proto::SigningOutput`.
+const SIGNATURE_V_MIN_LEN: usize = 1;
+
+/// ERC20, ERC721 and ERC1155 functions that can be represented as a `Proto::Transaction` payload.
+const KNOWN_CALLS_ABI: &str = include_str!("../abi/prebuild/resource/known_calls.abi.json");
+const ERC20_TRANSFER: [u8; 4] = [0xa9, 0x05, 0x9c, 0xbb];
+const ERC20_APPROVE: [u8; 4] = [0x09, 0x5e, 0xa7, 0xb3];
+const ERC721_TRANSFER_FROM: [u8; 4] = [0x23, 0xb8, 0x72, 0xdd];
+const ERC1155_SAFE_TRANSFER_FROM: [u8; 4] = [0xf2, 0x42, 0x43, 0x2a];
+
+struct TransactionSignature {
+    v: U256,
+    r: U256,
+    s: U256,
+    recovery_id: u8,
+}
+
+/// Fields of a legacy, EIP-2930, EIP-1559, EIP-4844 or EIP-7702 transaction.
+#[derive(Default)]
+struct DecodedTransaction {
+    tx_type: u8,
+    chain_id: Option<U256>,
+    nonce: U256,
+    /// Legacy and EIP-2930 transactions only.
+    gas_price: U256,
+    /// EIP-1559, EIP-4844 and EIP-7702 transactions only.
+    max_inclusion_fee_per_gas: U256,
+    /// EIP-1559, EIP-4844 and EIP-7702 transactions only.
+    max_fee_per_gas: U256,
+    gas_limit: U256,
+    to: Option<Address>,
+    amount: U256,
+    payload: Data,
+    access_list: AccessList,
+    /// EIP-7702 transactions only.
+    authorization_list: AuthorizationList,
+    /// EIP-4844 transactions only.
+    max_fee_per_blob_gas: U256,
+    /// EIP-4844 transactions only.
+    blob_versioned_hashes: Vec<H256>,
+    /// EIP-4844 transactions in the network wrapper form only.
+    blob_sidecar: Option<BlobSidecar>,
+    signature: Option<TransactionSignature>,
+    /// The data to be signed.
+    pre_image: Data,
+    /// The signed transaction the hash is computed from, if it differs from the given one.
+    /// EIP-4844 transactions in the network wrapper form only.
+    canonical_tx: Option<Data>,
+}
+
+#[derive(Default)]
+pub struct EvmTransactionDecoder<Context: EvmContext> {
+    _phantom: PhantomData<Context>,
+}
+
+impl<Context: EvmContext> TransactionDecoder for EvmTransactionDecoder<Context> {
+    type Output = Proto::DecodingTransactionOutput<'static>;
+
+    fn decode_transaction(&self, coin: &dyn CoinContext, tx: &[u8]) -> Self::Output {
+        Self::decode_transaction_impl(coin, tx)
+            .unwrap_or_else(|e| signing_output_error!(Proto::DecodingTransactionOutput, e))
+    }
+}
+
+impl<Context: EvmContext> EvmTransactionDecoder<Context> {
+    fn decode_transaction_impl(
+        _coin: &dyn CoinContext,
+        tx: &[u8],
+    ) -> SigningResult<Proto::DecodingTransactionOutput<'static>> {
+        let decoded = match tx.first() {
+            Some(&EIP2930_TX_TYPE) => Self::decode_eip2930(&tx[1..])?,
+            Some(&EIP1559_TX_TYPE) => Self::decode_eip1559(&tx[1..])?,
+            Some(&EIP4844_TX_TYPE) => Self::decode_eip4844(&tx[1..])?,
+            Some(&EIP7702_TX_TYPE) => Self::decode_eip7702(&tx[1..])?,
+            Some(first) if *first >= RLP_LIST_OFFSET => Self::decode_legacy(tx)?,
+            Some(tx_type) => {
+                return SigningError::err(SigningErrorType::Error_not_supported)
+                    .context(format!("Unsupported transaction type: {tx_type:#04x}"));
+            },
+            None => {
+                return SigningError::err(SigningErrorType::Error_input_parse)
+                    .context("Empty transaction");
+            },
+        };
+
+        let pre_hash = hash(&decoded.pre_image);
+
+        let mut output = Proto::DecodingTransactionOutput {
+            tx_type: decoded.tx_type as u32,
+            pre_hash: pre_hash.to_vec().into(),
+            ..Proto::DecodingTransactionOutput::default()
+        };
+
+        if let Some(ref signature) = decoded.signature {
+            let sender = Self::recover_sender(signature, pre_hash)?;
+
+            output.is_signed = true;
+            output.sender = Self::address_to_string(sender).into();
+            output.v = signature
+                .v
+                .to_big_endian_compact_min_len(SIGNATURE_V_MIN_LEN)
+                .into();
+            output.r = signature.r.to_big_endian().to_vec().into();
+            output.s = signature.s.to_big_endian().to_vec().into();
+            output.tx_hash = hash(decoded.canonical_tx.as_deref().unwrap_or(tx))
+                .to_vec()
+                .into();
+        }
+
+        output.transaction = Some(Self::signing_input_from_tx(decoded));
+        Ok(output)
+    }
+
+    /// Decodes a non-typed transaction:
+    /// `rlp([nonce, gasPrice, gasLimit, to, value, data])` pre-EIP155 unsigned,
+    /// `rlp([nonce, gasPrice, gasLimit, to, value, data, chainId, 0, 0])` EIP155 unsigned,
+    /// `rlp([nonce, gasPrice, gasLimit, to, value, data, v, r, s])` signed.
+    fn decode_legacy(tx: &[u8]) -> SigningResult<DecodedTransaction> {
+        const FIELDS: usize = 6;
+
+        let items = decode_list(tx).context("Invalid legacy transaction")?;
+        let (fields, extra) = match items.len() {
+            FIELDS => (&items[..], None),
+            len if len == FIELDS + SIGNATURE_ITEMS => (&items[..FIELDS], Some(&items[FIELDS..])),
+            len => {
+                return SigningError::err(SigningErrorType::Error_input_parse).context(format!(
+                    "Unexpected number of legacy transaction items: {len}"
+                ));
+            },
+        };
+
+        let mut decoded = DecodedTransaction {
+            tx_type: LEGACY_TX_TYPE,
+            nonce: U256::rlp_decode(&fields[0]).context("Invalid nonce")?,
+            gas_price: U256::rlp_decode(&fields[1]).context("Invalid gas price")?,
+            gas_limit: U256::rlp_decode(&fields[2]).context("Invalid gas limit")?,
+            to: Option::<Address>::rlp_decode(&fields[3]).context("Invalid destination address")?,
+            amount: U256::rlp_decode(&fields[4]).context("Invalid amount")?,
+            payload: Data::rlp_decode(&fields[5]).context("Invalid payload")?,
+            ..DecodedTransaction::default()
+        };
+
+        let Some(extra) = extra else {
+            // Pre-EIP155 unsigned transaction.
+            decoded.pre_image = encode_pre_image(None, fields, None);
+            return Ok(decoded);
+        };
+
+        let v = U256::rlp_decode(&extra[0]).context("Invalid 'v'")?;
+        let r = U256::rlp_decode(&extra[1]).context("Invalid 'r'")?;
+        let s = U256::rlp_decode(&extra[2]).context("Invalid 's'")?;
+
+        if r.is_zero() && s.is_zero() {
+            // EIP155 unsigned transaction, where `v` is the chain ID.
+            decoded.chain_id = Some(v);
+            decoded.pre_image = tx.to_vec();
+            return Ok(decoded);
+        }
+
+        let (recovery_id, chain_id) = split_replay_protection(v)
+            .or_tw_err(SigningErrorType::Error_input_parse)
+            .context("Invalid legacy transaction 'v'")?;
+
+        decoded.chain_id = chain_id;
+        decoded.pre_image = encode_pre_image(None, fields, chain_id);
+        decoded.signature = Some(TransactionSignature {
+            v,
+            r,
+            s,
+            recovery_id,
+        });
+        Ok(decoded)
+    }
+
+    /// Decodes an EIP-2930 transaction payload:
+    /// `rlp([chainId, nonce, gasPrice, gasLimit, to, value, data, accessList, (yParity, r, s)])`.
+    fn decode_eip2930(tx_payload: &[u8]) -> SigningResult<DecodedTransaction> {
+        const FIELDS: usize = 8;
+
+        let items = decode_list(tx_payload).context("Invalid EIP-2930 transaction")?;
+        let (fields, signature) = Self::split_typed_items(&items, FIELDS)?;
+
+        Ok(DecodedTransaction {
+            tx_type: EIP2930_TX_TYPE,
+            chain_id: Some(U256::rlp_decode(&fields[0]).context("Invalid chain ID")?),
+            nonce: U256::rlp_decode(&fields[1]).context("Invalid nonce")?,
+            gas_price: U256::rlp_decode(&fields[2]).context("Invalid gas price")?,
+            gas_limit: U256::rlp_decode(&fields[3]).context("Invalid gas limit")?,
+            to: Option::<Address>::rlp_decode(&fields[4]).context("Invalid destination address")?,
+            amount: U256::rlp_decode(&fields[5]).context("Invalid amount")?,
+            payload: Data::rlp_decode(&fields[6]).context("Invalid payload")?,
+            access_list: AccessList::rlp_decode(&fields[7]).context("Invalid access list")?,
+            signature: signature.map(Self::decode_y_parity_signature).transpose()?,
+            pre_image: encode_pre_image(Some(EIP2930_TX_TYPE), fields, None),
+            ..DecodedTransaction::default()
+        })
+    }
+
+    /// Decodes an EIP-1559 transaction payload:
+    /// `rlp([chainId, nonce, maxPriorityFeePerGas, maxFeePerGas, gasLimit, to, value, data, accessList, (yParity, r, s)])`.
+    fn decode_eip1559(tx_payload: &[u8]) -> SigningResult<DecodedTransaction> {
+        const FIELDS: usize = 9;
+
+        let items = decode_list(tx_payload).context("Invalid EIP-1559 transaction")?;
+        let (fields, signature) = Self::split_typed_items(&items, FIELDS)?;
+
+        Ok(DecodedTransaction {
+            tx_type: EIP1559_TX_TYPE,
+            chain_id: Some(U256::rlp_decode(&fields[0]).context("Invalid chain ID")?),
+            nonce: U256::rlp_decode(&fields[1]).context("Invalid nonce")?,
+            max_inclusion_fee_per_gas: U256::rlp_decode(&fields[2])
+                .context("Invalid max inclusion fee per gas")?,
+            max_fee_per_gas: U256::rlp_decode(&fields[3]).context("Invalid max fee per gas")?,
+            gas_limit: U256::rlp_decode(&fields[4]).context("Invalid gas limit")?,
+            to: Option::<Address>::rlp_decode(&fields[5]).context("Invalid destination address")?,
+            amount: U256::rlp_decode(&fields[6]).context("Invalid amount")?,
+            payload: Data::rlp_decode(&fields[7]).context("Invalid payload")?,
+            access_list: AccessList::rlp_decode(&fields[8]).context("Invalid access list")?,
+            signature: signature.map(Self::decode_y_parity_signature).transpose()?,
+            pre_image: encode_pre_image(Some(EIP1559_TX_TYPE), fields, None),
+            ..DecodedTransaction::default()
+        })
+    }
+
+    /// Decodes an EIP-4844 transaction payload:
+    /// `rlp([chainId, nonce, maxPriorityFeePerGas, maxFeePerGas, gasLimit, to, value, data, accessList, maxFeePerBlobGas, blobVersionedHashes, (yParity, r, s)])`,
+    /// or the network wrapper: `rlp([tx_payload_body, blobs, commitments, proofs])`.
+    fn decode_eip4844(tx_payload: &[u8]) -> SigningResult<DecodedTransaction> {
+        const FIELDS: usize = 11;
+        const NETWORK_WRAPPER_ITEMS: usize = 4;
+
+        let items = decode_list(tx_payload).context("Invalid EIP-4844 transaction")?;
+
+        let (items, blob_sidecar, canonical_tx) = match items.first() {
+            Some(tx_payload_body) if tx_payload_body.is_list() => {
+                if items.len() != NETWORK_WRAPPER_ITEMS {
+                    return SigningError::err(SigningErrorType::Error_input_parse).context(
+                        format!(
+                            "Unexpected number of network wrapper items: {}",
+                            items.len()
+                        ),
+                    );
+                }
+                let sidecar = BlobSidecar::rlp_decode_items(&items[1], &items[2], &items[3])
+                    .context("Invalid blob sidecar")?;
+                let canonical_tx = [&[EIP4844_TX_TYPE], tx_payload_body.as_raw()].concat();
+                (
+                    list_items(tx_payload_body)?,
+                    Some(sidecar),
+                    Some(canonical_tx),
+                )
+            },
+            _ => (items, None, None),
+        };
+        let (fields, signature) = Self::split_typed_items(&items, FIELDS)?;
+
+        Ok(DecodedTransaction {
+            tx_type: EIP4844_TX_TYPE,
+            chain_id: Some(U256::rlp_decode(&fields[0]).context("Invalid chain ID")?),
+            nonce: U256::rlp_decode(&fields[1]).context("Invalid nonce")?,
+            max_inclusion_fee_per_gas: U256::rlp_decode(&fields[2])
+                .context("Invalid max inclusion fee per gas")?,
+            max_fee_per_gas: U256::rlp_decode(&fields[3]).context("Invalid max fee per gas")?,
+            gas_limit: U256::rlp_decode(&fields[4]).context("Invalid gas limit")?,
+            // Blob transactions cannot deploy a contract.
+            to: Some(Address::rlp_decode(&fields[5]).context("Invalid destination address")?),
+            amount: U256::rlp_decode(&fields[6]).context("Invalid amount")?,
+            payload: Data::rlp_decode(&fields[7]).context("Invalid payload")?,
+            access_list: AccessList::rlp_decode(&fields[8]).context("Invalid access list")?,
+            max_fee_per_blob_gas: U256::rlp_decode(&fields[9])
+                .context("Invalid max fee per blob gas")?,
+            blob_versioned_hashes: rlp_decode_hashes(&fields[10])
+                .context("Invalid blob versioned hashes")?,
+            blob_sidecar,
+            signature: signature.map(Self::decode_y_parity_signature).transpose()?,
+            pre_image: encode_pre_image(Some(EIP4844_TX_TYPE), fields, None),
+            canonical_tx,
+            ..DecodedTransaction::default()
+        })
+    }
+
+    /// Decodes an EIP-7702 transaction payload:
+    /// `rlp([chainId, nonce, maxPriorityFeePerGas, maxFeePerGas, gasLimit, to, value, data, accessList, authorizationList, (yParity, r, s)])`.
+    fn decode_eip7702(tx_payload: &[u8]) -> SigningResult<DecodedTransaction> {
+        const FIELDS: usize = 10;
+
+        let items = decode_list(tx_payload).context("Invalid EIP-7702 transaction")?;
+        let (fields, signature) = Self::split_typed_items(&items, FIELDS)?;
+
+        Ok(DecodedTransaction {
+            tx_type: EIP7702_TX_TYPE,
+            chain_id: Some(U256::rlp_decode(&fields[0]).context("Invalid chain ID")?),
+            nonce: U256::rlp_decode(&fields[1]).context("Invalid nonce")?,
+            max_inclusion_fee_per_gas: U256::rlp_decode(&fields[2])
+                .context("Invalid max inclusion fee per gas")?,
+            max_fee_per_gas: U256::rlp_decode(&fields[3]).context("Invalid max fee per gas")?,
+            gas_limit: U256::rlp_decode(&fields[4]).context("Invalid gas limit")?,
+            // Set-code transactions cannot deploy a contract.
+            to: Some(Address::rlp_decode(&fields[5]).context("Invalid destination address")?),
+            amount: U256::rlp_decode(&fields[6]).context("Invalid amount")?,
+            payload: Data::rlp_decode(&fields[7]).context("Invalid payload")?,
+            access_list: AccessList::rlp_decode(&fields[8]).context("Invalid access list")?,
+            authorization_list: AuthorizationList::rlp_decode(&fields[9])
+                .context("Invalid authorization list")?,
+            signature: signature.map(Self::decode_y_parity_signature).transpose()?,
+            pre_image: encode_pre_image(Some(EIP7702_TX_TYPE), fields, None),
+            ..DecodedTransaction::default()
+        })
+    }
+
+    /// Splits typed transaction items into the transaction fields and optional signature items.
+    fn split_typed_items<'a, 'b>(
+        items: &'a [Rlp<'b>],
+        fields: usize,
+    ) -> SigningResult<(&'a [Rlp<'b>], Option<&'a [Rlp<'b>]>)> {
+        match items.len() {
+            len if len == fields => Ok((items, None)),
+            len if len == fields + SIGNATURE_ITEMS => {
+                Ok((&items[..fields], Some(&items[fields..])))
+            },
+            len => SigningError::err(SigningErrorType::Error_input_parse)
+                .context(format!("Unexpected number of transaction items: {len}")),
+        }
+    }
+
+    fn decode_y_parity_signature(items: &[Rlp]) -> SigningResult<TransactionSignature> {
+        let v = U256::rlp_decode(&items[0]).context("Invalid 'yParity'")?;
+        let recovery_id = u8::try_from(v)
+            .ok()
+            .filter(|y_parity| *y_parity <= 1)
+            .or_tw_err(SigningErrorType::Error_input_parse)
+            .context("'yParity' must be either 0 or 1")?;
+
+        Ok(TransactionSignature {
+            v,
+            r: U256::rlp_decode(&items[1]).context("Invalid 'r'")?,
+            s: U256::rlp_decode(&items[2]).context("Invalid 's'")?,
+            recovery_id,
+        })
+    }
+
+    fn recover_sender(signature: &TransactionSignature, pre_hash: H256) -> SigningResult<Address> {
+        let signature = secp256k1::Signature::try_from_parts(
+            signature.r.to_big_endian(),
+            signature.s.to_big_endian(),
+            signature.recovery_id,
+        )
+        .into_tw()
+        .context("Invalid transaction signature")?;
+
+        let public_key = secp256k1::PublicKey::recover(signature, pre_hash)
+            .into_tw()
+            .context("Error recovering the sender public key")?;
+        Ok(Address::with_secp256k1_pubkey(&public_key))
+    }
+
+    fn signing_input_from_tx(decoded: DecodedTransaction) -> Proto::SigningInput<'static> {
+        let tx_mode = match decoded.tx_type {
+            EIP2930_TX_TYPE => Proto::TransactionMode::AccessList,
+            EIP1559_TX_TYPE => Proto::TransactionMode::Enveloped,
+            EIP4844_TX_TYPE => Proto::TransactionMode::Blob,
+            EIP7702_TX_TYPE => Proto::TransactionMode::SetCode,
+            _ => Proto::TransactionMode::Legacy,
+        };
+
+        let (to_address, transaction_oneof) =
+            Self::transaction_from_payload(decoded.to, decoded.amount, decoded.payload);
+
+        Proto::SigningInput {
+            chain_id: decoded
+                .chain_id
+                .map(|chain_id| chain_id.to_big_endian_compact())
+                .unwrap_or_default()
+                .into(),
+            nonce: decoded.nonce.to_big_endian_compact().into(),
+            tx_mode,
+            gas_price: decoded.gas_price.to_big_endian_compact().into(),
+            gas_limit: decoded.gas_limit.to_big_endian_compact().into(),
+            max_inclusion_fee_per_gas: decoded
+                .max_inclusion_fee_per_gas
+                .to_big_endian_compact()
+                .into(),
+            max_fee_per_gas: decoded.max_fee_per_gas.to_big_endian_compact().into(),
+            to_address: to_address.into(),
+            transaction: Some(Proto::Transaction { transaction_oneof }),
+            access_list: Self::access_list_to_proto(&decoded.access_list),
+            authorization_list: Self::authorization_list_to_proto(&decoded.authorization_list),
+            max_fee_per_blob_gas: decoded.max_fee_per_blob_gas.to_big_endian_compact().into(),
+            blob_versioned_hashes: decoded
+                .blob_versioned_hashes
+                .iter()
+                .map(|hash| hash.to_vec().into())
+                .collect(),
+            blob_sidecar: decoded.blob_sidecar.as_ref().map(blob_sidecar_to_proto),
+            ..Proto::SigningInput::default()
+        }
+    }
+
+    /// Returns the `to_address` and the transaction payload.
+    fn transaction_from_payload(
+        to: Option<Address>,
+        amount: U256,
+        payload: Data,
+    ) -> (String, TransactionType<'static>) {
+        use Proto::mod_Transaction as Tx;
+
+        let Some(to) = to else {
+            // Contract deployment.
+            let generic = Tx::ContractGeneric {
+                amount: amount.to_big_endian_compact().into(),
+                data: payload.into(),
+            };
+            return (
+                String::default(),
+                TransactionType::contract_generic(generic),
+            );
+        };
+
+        let to_address = Self::address_to_string(to);
+        if payload.is_empty() {
+            let transfer = Tx::Transfer {
+                amount: amount.to_big_endian_compact().into(),
+                data: Cow::default(),
+            };
+            return (to_address, TransactionType::transfer(transfer));
+        }
+
+        if amount.is_zero() {
+            if let Some(known_call) = Self::known_call_from_payload(&payload) {
+                return (to_address, known_call);
+            }
+        }
+
+        let generic = Tx::ContractGeneric {
+            amount: amount.to_big_endian_compact().into(),
+            data: payload.into(),
+        };
+        (to_address, TransactionType::contract_generic(generic))
+    }
+
+    /// Tries to decode the payload as one of the ERC20, ERC721 or ERC1155 calls supported by `Proto::Transaction`.
+    /// Please note that `transferFrom` is always considered as an ERC721 transfer,
+    /// and the call is recognized only if it's encoded exactly as the signer would encode it.
+    fn known_call_from_payload(payload: &[u8]) -> Option<TransactionType<'static>> {
+        use Proto::mod_Transaction as Tx;
+
+        let output =
+            AbiEncoder::<Context>::decode_contract_call(AbiProto::ContractCallDecodingInput {
+                encoded: Cow::Borrowed(payload),
+                smart_contract_abi_json: Cow::Borrowed(KNOWN_CALLS_ABI),
+            });
+        if output.error != AbiErrorKind::OK {
+            return None;
+        }
+
+        let selector: [u8; 4] = payload.get(..4)?.try_into().ok()?;
+        let tokens: Vec<_> = output.tokens.iter().map(|token| &token.token).collect();
+
+        let (transaction, expected_payload) = match (selector, tokens.as_slice()) {
+            (ERC20_TRANSFER, [to, amount]) => {
+                let (to, amount) = (token_address(to)?, token_uint(amount)?);
+                let transfer = Tx::ERC20Transfer {
+                    to: Self::address_to_string(to).into(),
+                    amount: amount.to_big_endian_compact().into(),
+                };
+                let expected = Erc20::transfer(to, amount).ok()?;
+                (TransactionType::erc20_transfer(transfer), expected)
+            },
+            (ERC20_APPROVE, [spender, amount]) => {
+                let (spender, amount) = (token_address(spender)?, token_uint(amount)?);
+                let approve = Tx::ERC20Approve {
+                    spender: Self::address_to_string(spender).into(),
+                    amount: amount.to_big_endian_compact().into(),
+                };
+                let expected = Erc20::approve(spender, amount).ok()?;
+                (TransactionType::erc20_approve(approve), expected)
+            },
+            (ERC721_TRANSFER_FROM, [from, to, token_id]) => {
+                let (from, to) = (token_address(from)?, token_address(to)?);
+                let token_id = token_uint(token_id)?;
+                let transfer = Tx::ERC721Transfer {
+                    from: Self::address_to_string(from).into(),
+                    to: Self::address_to_string(to).into(),
+                    token_id: token_id.to_big_endian_compact().into(),
+                };
+                let expected = Erc721::encode_transfer_from(from, to, token_id).ok()?;
+                (TransactionType::erc721_transfer(transfer), expected)
+            },
+            (ERC1155_SAFE_TRANSFER_FROM, [from, to, token_id, value, data]) => {
+                let (from, to) = (token_address(from)?, token_address(to)?);
+                let (token_id, value) = (token_uint(token_id)?, token_uint(value)?);
+                let data = token_bytes(data)?;
+                let transfer = Tx::ERC1155Transfer {
+                    from: Self::address_to_string(from).into(),
+                    to: Self::address_to_string(to).into(),
+                    token_id: token_id.to_big_endian_compact().into(),
+                    value: value.to_big_endian_compact().into(),
+                    data: data.clone().into(),
+                };
+                let expected =
+                    Erc1155::encode_safe_transfer_from(from, to, token_id, value, data).ok()?;
+                (TransactionType::erc1155_transfer(transfer), expected)
+            },
+            _ => return None,
+        };
+
+        (expected_payload == payload).then_some(transaction)
+    }
+
+    fn access_list_to_proto(access_list: &AccessList) -> Vec<Proto::Access<'static>> {
+        access_list
+            .accesses()
+            .iter()
+            .map(|access| Proto::Access {
+                address: Self::address_to_string(access.address).into(),
+                stored_keys: access
+                    .storage_keys
+                    .iter()
+                    .map(|key| key.to_vec().into())
+                    .collect(),
+            })
+            .collect()
+    }
+
+    fn authorization_list_to_proto(
+        authorization_list: &AuthorizationList,
+    ) -> Vec<Proto::Authorization<'static>> {
+        authorization_list
+            .authorizations()
+            .iter()
+            .map(|signed| {
+                let authorization = signed.authorization();
+                Proto::Authorization {
+                    chain_id: authorization.chain_id.to_big_endian_compact().into(),
+                    address: Self::address_to_string(authorization.address).into(),
+                    nonce: authorization.nonce.to_big_endian_compact().into(),
+                    signature: signed.signature().to_rsv_bytes().to_vec().into(),
+                }
+            })
+            .collect()
+    }
+
+    fn address_to_string(address: Address) -> String {
+        Context::Address::from(address).to_string()
+    }
+}
+
+/// Encodes the data to be signed: `[tx_type ||] rlp(fields [|| chain_id, 0, 0])`.
+fn encode_pre_image(tx_type: Option<u8>, fields: &[Rlp], eip155_chain_id: Option<U256>) -> Data {
+    let mut list = RlpList::new();
+    for field in fields {
+        list.append_raw_encoded(field.as_raw());
+    }
+    if let Some(chain_id) = eip155_chain_id {
+        list.append(&chain_id)
+            .append(&U256::zero())
+            .append(&U256::zero());
+    }
+    let encoded = list.finish();
+
+    match tx_type {
+        Some(tx_type) => [&[tx_type], encoded.as_slice()].concat(),
+        None => encoded,
+    }
+}
+
+fn blob_sidecar_to_proto(sidecar: &BlobSidecar) -> Proto::BlobSidecar<'static> {
+    Proto::BlobSidecar {
+        blobs: sidecar
+            .blobs()
+            .iter()
+            .map(|blob| blob.clone().into())
+            .collect(),
+        commitments: sidecar
+            .commitments()
+            .iter()
+            .map(|commitment| commitment.to_vec().into())
+            .collect(),
+        proofs: sidecar
+            .proofs()
+            .iter()
+            .map(|proof| proof.to_vec().into())
+            .collect(),
+    }
+}
+
+fn hash(data: &[u8]) -> H256 {
+    let hash = keccak256(data);
+    H256::try_from(hash.as_slice()).expect("keccak256 returns 32 bytes")
+}
+
+fn token_address(token: &TokenEnum) -> Option<Address> {
+    match token {
+        TokenEnum::address(address) => Address::from_str(address).ok(),
+        _ => None,
+    }
+}
+
+fn token_uint(token: &TokenEnum) -> Option<U256> {
+    match token {
+        TokenEnum::number_uint(number) => U256::from_big_endian_slice(&number.value).ok(),
+        _ => None,
+    }
+}
+
+fn token_bytes(token: &TokenEnum) -> Option<Data> {
+    match token {
+        TokenEnum::byte_array(bytes) => Some(bytes.to_vec()),
+        _ => None,
+    }
+}
@@ -2,6 +2,7 @@
 //
 // Copyright © 2017 Trust Wallet.
 
+use crate::transaction::transaction_eip4844::canonical_from_network_wrapper;
 use tw_coin_entry::coin_context::CoinContext;
 use tw_coin_entry::error::prelude::*;
 use tw_coin_entry::modules::transaction_util::TransactionUtil;
@@ -19,6 +20,8 @@ impl TransactionUtil for EvmTransactionUtil {
 impl EvmTransactionUtil {
     fn calc_tx_hash_impl(_coin: &dyn CoinContext, encoded_tx: &str) -> SigningResult<String> {
         let tx = decode(encoded_tx).map_err(|_| SigningErrorType::Error_input_parse)?;
+        // The hash of a blob transaction doesn't cover the blobs of the network wrapper.
+        let tx = canonical_from_network_wrapper(&tx)?.unwrap_or(tx);
 
         Ok(encode(keccak256(&tx), true))
     }
@@ -5,19 +5,33 @@
 use crate::abi::abi_to_signing_error;
 use crate::abi::prebuild::erc1155::Erc1155;
 use crate::abi::prebuild::erc20::Erc20;
-use crate::abi::prebuild::erc4337::{Erc4337SimpleAccount, ExecuteArgs};
+use crate::abi::prebuild::erc4337::ExecuteArgs;
 use crate::abi::prebuild::erc721::Erc721;
 use crate::address::{Address, EvmAddress};
 use crate::evm_context::EvmContext;
+use crate::smart_account::kernel::KernelAccount;
+use crate::smart_account::light_account::LightAccount;
+use crate::smart_account::safe::{Safe4337Account, DEFAULT_MULTI_SEND};
+use crate::smart_account::simple_account::SimpleAccount;
+use crate::smart_account::SmartAccount;
 use crate::transaction::access_list::{Access, AccessList};
+use crate::transaction::authorization_list::{
+    Authorization, AuthorizationList, SignedAuthorization,
+};
+use crate::transaction::blob::{BlobSidecar, KzgCommitment, KzgProof, VERSIONED_HASH_VERSION_KZG};
 use crate::transaction::transaction_eip1559::TransactionEip1559;
+use crate::transaction::transaction_eip2930::TransactionEip2930;
+use crate::transaction::transaction_eip4844::TransactionEip4844;
+use crate::transaction::transaction_eip7702::TransactionEip7702;
 use crate::transaction::transaction_non_typed::TransactionNonTyped;
 use crate::transaction::user_operation::UserOperation;
+use crate::transaction::user_operation_v0_7::PackedUserOperation;
 use crate::transaction::UnsignedTransactionBox;
 use std::marker::PhantomData;
 use std::str::FromStr;
 use tw_coin_entry::error::prelude::*;
 use tw_hash::H256;
+use tw_keypair::ecdsa::secp256k1;
 use tw_memory::Data;
 use tw_number::U256;
 use tw_proto::Common::Proto::SigningError as CommonError;
@@ -145,11 +159,12 @@ impl<Context: EvmContext> TxBuilder<Context> {
                     .iter()
                     .map(Self::erc4337_execute_call_from_proto)
                     .collect::<Result<Vec<_>, _>>()?;
-                let payload = Erc4337SimpleAccount::encode_execute_batch(calls)
+                let account = Self::user_operation_account_from_proto(input)?;
+                let payload = account
+                    .encode_execute_batch(calls)
                     .map_err(abi_to_signing_error)?;
 
-                return Self::user_operation_from_proto(input, payload)
-                    .map(UserOperation::into_boxed);
+                return Self::user_operation_from_proto(input, account, payload);
             },
             Tx::None => {
                 return SigningError::err(SigningErrorType::Error_invalid_params)
@@ -164,19 +179,36 @@ impl<Context: EvmContext> TxBuilder<Context> {
             TxMode::Enveloped => {
                 Self::transaction_eip1559_from_proto(input, eth_amount, payload, to)?.into_boxed()
             },
+            TxMode::AccessList => {
+                Self::transaction_eip2930_from_proto(input, eth_amount, payload, to)?.into_boxed()
+            },
+            TxMode::SetCode => {
+                let to = to
+                    .or_tw_err(SigningErrorType::Error_invalid_address)
+                    .context("No destination address specified")?;
+                Self::transaction_eip7702_from_proto(input, eth_amount, payload, to)?.into_boxed()
+            },
+            TxMode::Blob => {
+                let to = to
+                    .or_tw_err(SigningErrorType::Error_invalid_address)
+                    .context("No destination address specified")?;
+                Self::transaction_eip4844_from_proto(input, eth_amount, payload, to)?.into_boxed()
+            },
             TxMode::UserOp => {
                 let to = to
                     .or_tw_err(SigningErrorType::Error_invalid_address)
                     .context("No contract/destination address specified")?;
                 // Payload should match the ERC4337 standard.
-                let payload = Erc4337SimpleAccount::encode_execute(ExecuteArgs {
-                    to,
-                    value: eth_amount,
-                    data: payload,
-                })
-                .map_err(abi_to_signing_error)?;
-
-                Self::user_operation_from_proto(input, payload)?.into_boxed()
+                let account = Self::user_operation_account_from_proto(input)?;
+                let payload = account
+                    .encode_execute(ExecuteArgs {
+                        to,
+                        value: eth_amount,
+                        data: payload,
+                    })
+                    .map_err(abi_to_signing_error)?;
+
+                Self::user_operation_from_proto(input, account, payload)?
             },
         };
         Ok(tx)
@@ -266,15 +298,240 @@ impl<Context: EvmContext> TxBuilder<Context> {
         })
     }
 
+    #[inline]
+    fn transaction_eip2930_from_proto(
+        input: &Proto::SigningInput,
+        eth_amount: U256,
+        payload: Data,
+        to_address: Option<Address>,
+    ) -> SigningResult<TransactionEip2930> {
+        let nonce = U256::from_big_endian_slice(&input.nonce)
+            .into_tw()
+            .context("Invalid nonce")?;
+
+        let gas_price = U256::from_big_endian_slice(&input.gas_price)
+            .into_tw()
+            .context("Invalid gas price")?;
+
+        let gas_limit = U256::from_big_endian_slice(&input.gas_limit)
+            .into_tw()
+            .context("Invalid gas limit")?;
+
+        let access_list =
+            Self::parse_access_list(&input.access_list).context("Invalid access list")?;
+
+        Ok(TransactionEip2930 {
+            nonce,
+            gas_price,
+            gas_limit,
+            to: to_address,
+            amount: eth_amount,
+            payload,
+            access_list,
+        })
+    }
+
+    #[inline]
+    fn transaction_eip7702_from_proto(
+        input: &Proto::SigningInput,
+        eth_amount: U256,
+        payload: Data,
+        to_address: Address,
+    ) -> SigningResult<TransactionEip7702> {
+        let nonce = U256::from_big_endian_slice(&input.nonce)
+            .into_tw()
+            .context("Invalid nonce")?;
+
+        let gas_limit = U256::from_big_endian_slice(&input.gas_limit)
+            .into_tw()
+            .context("Invalid gas limit")?;
+
+        let max_inclusion_fee_per_gas =
+            U256::from_big_endian_slice(&input.max_inclusion_fee_per_gas)
+                .into_tw()
+                .context("Invalid max inclusion fee per gas")?;
+
+        let max_fee_per_gas = U256::from_big_endian_slice(&input.max_fee_per_gas)
+            .into_tw()
+            .context("Invalid max fee per gas")?;
+
+        let access_list =
+            Self::parse_access_list(&input.access_list).context("Invalid access list")?;
+
+        let authorization_list = Self::parse_authorization_list(input)?;
+
+        Ok(TransactionEip7702 {
+            nonce,
+            max_inclusion_fee_per_gas,
+            max_fee_per_gas,
+            gas_limit,
+            to: to_address,
+            amount: eth_amount,
+            payload,
+            access_list,
+            authorization_list,
+        })
+    }
+
+    fn transaction_eip4844_from_proto(
+        input: &Proto::SigningInput,
+        eth_amount: U256,
+        payload: Data,
+        to_address: Address,
+    ) -> SigningResult<TransactionEip4844> {
+        let nonce = U256::from_big_endian_slice(&input.nonce)
+            .into_tw()
+            .context("Invalid nonce")?;
+
+        let gas_limit = U256::from_big_endian_slice(&input.gas_limit)
+            .into_tw()
+            .context("Invalid gas limit")?;
+
+        let max_inclusion_fee_per_gas =
+            U256::from_big_endian_slice(&input.max_inclusion_fee_per_gas)
+                .into_tw()
+                .context("Invalid max inclusion fee per gas")?;
+
+        let max_fee_per_gas = U256::from_big_endian_slice(&input.max_fee_per_gas)
+            .into_tw()
+            .context("Invalid max fee per gas")?;
+
+        let max_fee_per_blob_gas = U256::from_big_endian_slice(&input.max_fee_per_blob_gas)
+            .into_tw()
+            .context("Invalid max fee per blob gas")?;
+
+        let access_list =
+            Self::parse_access_list(&input.access_list).context("Invalid access list")?;
+
+        let sidecar = input
+            .blob_sidecar
+            .as_ref()
+            .map(Self::blob_sidecar_from_proto)
+            .transpose()
+            .context("Invalid blob sidecar")?;
+        let blob_versioned_hashes = Self::parse_blob_versioned_hashes(input, sidecar.as_ref())?;
+
+        Ok(TransactionEip4844 {
+            nonce,
+            max_inclusion_fee_per_gas,
+            max_fee_per_gas,
+            gas_limit,
+            to: to_address,
+            amount: eth_amount,
+            payload,
+            access_list,
+            max_fee_per_blob_gas,
+            blob_versioned_hashes,
+            sidecar,
+        })
+    }
+
     fn user_operation_from_proto(
         input: &Proto::SigningInput,
+        account: Box<dyn SmartAccount>,
         erc4337_payload: Data,
-    ) -> SigningResult<UserOperation> {
-        let Some(ref user_op) = input.user_operation else {
-            return SigningError::err(CommonError::Error_invalid_params)
-                .context("No user operation specified");
+    ) -> SigningResult<Box<dyn UnsignedTransactionBox>> {
+        use Proto::mod_SigningInput::OneOfuser_operation_oneof as UserOp;
+
+        match input.user_operation_oneof {
+            UserOp::user_operation(ref user_op) => {
+                Self::user_operation_v0_6_from_proto(input, user_op, account, erc4337_payload)
+                    .map(UserOperation::into_boxed)
+            },
+            UserOp::user_operation_v0_7(ref user_op) => {
+                Self::user_operation_v0_7_from_proto(input, user_op, account, erc4337_payload)
+                    .map(PackedUserOperation::into_boxed)
+            },
+            UserOp::None => SigningError::err(CommonError::Error_invalid_params)
+                .context("No user operation specified"),
+        }
+    }
+
+    /// Returns the smart account of the user operation, or `SimpleAccount` if no user operation is specified.
+    fn user_operation_account_from_proto(
+        input: &Proto::SigningInput,
+    ) -> SigningResult<Box<dyn SmartAccount>> {
+        use Proto::mod_SigningInput::OneOfuser_operation_oneof as UserOp;
+
+        let account = match input.user_operation_oneof {
+            UserOp::user_operation(ref user_op) => user_op.account.as_ref(),
+            UserOp::user_operation_v0_7(ref user_op) => user_op.account.as_ref(),
+            UserOp::None => None,
         };
+        Self::smart_account_from_proto(account)
+    }
+
+    pub fn smart_account_from_proto(
+        account: Option<&Proto::SmartAccount>,
+    ) -> SigningResult<Box<dyn SmartAccount>> {
+        use Proto::SmartAccountType;
+
+        let Some(account) = account else {
+            return Ok(Box::new(SimpleAccount::default()));
+        };
+
+        match account.account_type {
+            SmartAccountType::SimpleAccount => {
+                let implementation = Self::parse_address_optional(&account.implementation)
+                    .context("Invalid SimpleAccount implementation")?;
+                Ok(Box::new(SimpleAccount { implementation }))
+            },
+            SmartAccountType::Safe4337 => {
+                let Some(ref safe) = account.safe else {
+                    return SigningError::err(SigningErrorType::Error_invalid_params)
+                        .context("Safe account parameters are required");
+                };
+                Self::safe_account_from_proto(safe).map(|safe| Box::new(safe) as _)
+            },
+            SmartAccountType::Kernel => {
+                let validator = match account.kernel {
+                    Some(ref kernel) => Self::parse_address_optional(&kernel.validator)
+                        .context("Invalid Kernel validator")?,
+                    None => None,
+                };
+                Ok(Box::new(KernelAccount { validator }))
+            },
+            SmartAccountType::LightAccount => Ok(Box::new(LightAccount)),
+        }
+    }
+
+    fn safe_account_from_proto(safe: &Proto::SafeAccount) -> SigningResult<Safe4337Account> {
+        /// The maximum value of `uint48`.
+        const MAX_UINT48: u64 = (1 << 48) - 1;
+
+        let module = Self::parse_address(&safe.module).context("Invalid Safe4337Module address")?;
+
+        let multi_send = Self::parse_address_optional(&safe.multi_send)
+            .context("Invalid MultiSend address")?
+            .unwrap_or_else(|| Address::from(DEFAULT_MULTI_SEND));
 
+        if safe.valid_after > MAX_UINT48 || safe.valid_until > MAX_UINT48 {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("Safe operation validity timestamps must fit uint48");
+        }
+
+        let singleton = Self::parse_address_optional(&safe.singleton)
+            .context("Invalid Safe singleton address")?;
+
+        let module_setup = Self::parse_address_optional(&safe.module_setup)
+            .context("Invalid SafeModuleSetup address")?;
+
+        Ok(Safe4337Account {
+            module,
+            multi_send,
+            valid_after: safe.valid_after,
+            valid_until: safe.valid_until,
+            singleton,
+            module_setup,
+        })
+    }
+
+    fn user_operation_v0_6_from_proto(
+        input: &Proto::SigningInput,
+        user_op: &Proto::UserOperation,
+        account: Box<dyn SmartAccount>,
+        erc4337_payload: Data,
+    ) -> SigningResult<UserOperation> {
         let nonce = U256::from_big_endian_slice(&input.nonce)
             .into_tw()
             .context("Invalid nonce")?;
@@ -318,9 +575,80 @@ impl<Context: EvmContext> TxBuilder<Context> {
             pre_verification_gas,
             paymaster_and_data: user_op.paymaster_and_data.to_vec(),
             payload: erc4337_payload,
+            account,
         })
     }
 
+    fn user_operation_v0_7_from_proto(
+        input: &Proto::SigningInput,
+        user_op: &Proto::UserOperationV0_7,
+        account: Box<dyn SmartAccount>,
+        erc4337_payload: Data,
+    ) -> SigningResult<PackedUserOperation> {
+        let nonce = U256::from_big_endian_slice(&input.nonce)
+            .into_tw()
+            .context("Invalid nonce")?;
+
+        let call_gas_limit = Self::parse_u128(&input.gas_limit).context("Invalid gas limit")?;
+
+        let max_priority_fee_per_gas = Self::parse_u128(&input.max_inclusion_fee_per_gas)
+            .context("Invalid max inclusion fee per gas")?;
+
+        let max_fee_per_gas =
+            Self::parse_u128(&input.max_fee_per_gas).context("Invalid max fee per gas")?;
+
+        let entry_point =
+            Self::parse_address(user_op.entry_point.as_ref()).context("Invalid entry point")?;
+
+        let sender = Self::parse_address(user_op.sender.as_ref())
+            .context("Invalid User Operation sender")?;
+
+        let factory = Self::parse_address_optional(user_op.factory.as_ref())
+            .context("Invalid User Operation factory")?;
+
+        let verification_gas_limit = Self::parse_u128(&user_op.verification_gas_limit)
+            .context("Invalid verification gas limit")?;
+
+        let pre_verification_gas = U256::from_big_endian_slice(&user_op.pre_verification_gas)
+            .into_tw()
+            .context("Invalid pre-verification gas")?;
+
+        let paymaster = Self::parse_address_optional(user_op.paymaster.as_ref())
+            .context("Invalid paymaster")?;
+
+        let paymaster_verification_gas_limit =
+            Self::parse_u128(&user_op.paymaster_verification_gas_limit)
+                .context("Invalid paymaster verification gas limit")?;
+
+        let paymaster_post_op_gas_limit = Self::parse_u128(&user_op.paymaster_post_op_gas_limit)
+            .context("Invalid paymaster post-operation gas limit")?;
+
+        Ok(PackedUserOperation {
+            nonce,
+            entry_point,
+            sender,
+            factory,
+            factory_data: user_op.factory_data.to_vec(),
+            call_gas_limit,
+            verification_gas_limit,
+            pre_verification_gas,
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+            paymaster,
+            paymaster_verification_gas_limit,
+            paymaster_post_op_gas_limit,
+            paymaster_data: user_op.paymaster_data.to_vec(),
+            payload: erc4337_payload,
+            account,
+        })
+    }
+
+    fn parse_u128(num: &[u8]) -> SigningResult<u128> {
+        U256::from_big_endian_slice(num)
+            .and_then(u128::try_from)
+            .into_tw()
+    }
+
     fn parse_address(addr: &str) -> SigningResult<Address> {
         Context::Address::from_str(addr)
             .map(Context::Address::into)
@@ -356,4 +684,126 @@ impl<Context: EvmContext> TxBuilder<Context> {
         }
         Ok(access)
     }
+
+    pub fn blob_sidecar_from_proto(sidecar: &Proto::BlobSidecar) -> SigningResult<BlobSidecar> {
+        let blobs = sidecar.blobs.iter().map(|blob| blob.to_vec()).collect();
+        let commitments = sidecar
+            .commitments
+            .iter()
+            .map(|commitment| {
+                KzgCommitment::try_from(commitment.as_ref())
+                    .tw_err(|_| SigningErrorType::Error_invalid_params)
+                    .context("Invalid KZG commitment")
+            })
+            .collect::<SigningResult<_>>()?;
+        let proofs = sidecar
+            .proofs
+            .iter()
+            .map(|proof| {
+                KzgProof::try_from(proof.as_ref())
+                    .tw_err(|_| SigningErrorType::Error_invalid_params)
+                    .context("Invalid KZG proof")
+            })
+            .collect::<SigningResult<_>>()?;
+        BlobSidecar::new(blobs, commitments, proofs)
+    }
+
+    /// Parses the blob versioned hashes.
+    /// The hashes are computed from the sidecar KZG commitments if not specified explicitly,
+    /// otherwise they must match the commitments.
+    fn parse_blob_versioned_hashes(
+        input: &Proto::SigningInput,
+        sidecar: Option<&BlobSidecar>,
+    ) -> SigningResult<Vec<H256>> {
+        let hashes = input
+            .blob_versioned_hashes
+            .iter()
+            .map(|hash| {
+                H256::try_from(hash.as_ref())
+                    .tw_err(|_| SigningErrorType::Error_invalid_params)
+                    .context("Invalid blob versioned hash")
+            })
+            .collect::<SigningResult<Vec<_>>>()?;
+
+        let hashes = match sidecar {
+            Some(sidecar) if hashes.is_empty() => sidecar.versioned_hashes(),
+            Some(sidecar) if hashes != sidecar.versioned_hashes() => {
+                return SigningError::err(SigningErrorType::Error_invalid_params)
+                    .context("Blob versioned hashes do not match the KZG commitments");
+            },
+            _ => hashes,
+        };
+
+        if hashes.is_empty() {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("Blob transaction must contain at least one blob versioned hash");
+        }
+        if hashes
+            .iter()
+            .any(|hash| hash[0] != VERSIONED_HASH_VERSION_KZG)
+        {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("Unsupported blob versioned hash version");
+        }
+        Ok(hashes)
+    }
+
+    /// Parses the authorization list.
+    /// Authorizations without a signature are signed with the `SigningInput.private_key`.
+    fn parse_authorization_list(input: &Proto::SigningInput) -> SigningResult<AuthorizationList> {
+        if input.authorization_list.is_empty() {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("Authorization list must not be empty");
+        }
+
+        let mut authorization_list = AuthorizationList::default();
+        for authorization_proto in input.authorization_list.iter() {
+            let authorization = Self::parse_signed_authorization(input, authorization_proto)
+                .context("Invalid authorization")?;
+            authorization_list.add_authorization(authorization);
+        }
+        Ok(authorization_list)
+    }
+
+    fn parse_signed_authorization(
+        input: &Proto::SigningInput,
+        authorization_proto: &Proto::Authorization,
+    ) -> SigningResult<SignedAuthorization> {
+        let authorization = Self::authorization_from_proto(authorization_proto)?;
+
+        if !authorization_proto.signature.is_empty() {
+            let signature = secp256k1::Signature::from_bytes(&authorization_proto.signature)
+                .into_tw()
+                .context("Invalid authorization signature")?;
+            return Ok(authorization.into_signed(signature));
+        }
+
+        if input.private_key.is_empty() {
+            return SigningError::err(SigningErrorType::Error_missing_private_key)
+                .context("Authorization is not signed and no private key is specified");
+        }
+        let private_key = secp256k1::PrivateKey::try_from(input.private_key.as_ref())?;
+        authorization.sign(&private_key)
+    }
+
+    pub fn authorization_from_proto(
+        authorization_proto: &Proto::Authorization,
+    ) -> SigningResult<Authorization> {
+        let chain_id = U256::from_big_endian_slice(&authorization_proto.chain_id)
+            .into_tw()
+            .context("Invalid authorization chain ID")?;
+
+        let address = Self::parse_address(authorization_proto.address.as_ref())
+            .context("Invalid authorization address")?;
+
+        let nonce = U256::from_big_endian_slice(&authorization_proto.nonce)
+            .into_tw()
+            .context("Invalid authorization nonce")?;
+
+        Ok(Authorization {
+            chain_id,
+            address,
+            nonce,
+        })
+    }
 }
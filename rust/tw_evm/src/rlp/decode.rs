@@ -0,0 +1,91 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::address::Address;
+use rlp::Rlp;
+use tw_coin_entry::error::prelude::*;
+use tw_hash::H256;
+use tw_memory::Data;
+use tw_number::U256;
+
+/// The trait should be implemented for all types that need to be decoded from RLP.
+pub trait RlpDecode: Sized {
+    fn rlp_decode(rlp: &Rlp) -> SigningResult<Self>;
+}
+
+/// Decodes an RLP list and returns its items.
+/// Returns an error if `data` is not a list or contains trailing bytes.
+pub fn decode_list(data: &[u8]) -> SigningResult<Vec<Rlp<'_>>> {
+    let rlp = Rlp::new(data);
+    if !rlp.is_list() {
+        return SigningError::err(SigningErrorType::Error_input_parse)
+            .context("Expected an RLP list");
+    }
+
+    let payload_info = rlp
+        .payload_info()
+        .tw_err(|_| SigningErrorType::Error_input_parse)
+        .context("Invalid RLP list header")?;
+    if payload_info.total() != data.len() {
+        return SigningError::err(SigningErrorType::Error_input_parse)
+            .context("Unexpected trailing bytes after the RLP list");
+    }
+
+    list_items(&rlp)
+}
+
+/// Returns items of the given RLP list.
+pub fn list_items<'a>(rlp: &Rlp<'a>) -> SigningResult<Vec<Rlp<'a>>> {
+    if !rlp.is_list() {
+        return SigningError::err(SigningErrorType::Error_input_parse)
+            .context("Expected an RLP list");
+    }
+    Ok(rlp.iter().collect())
+}
+
+/// Returns the byte string of the given RLP item.
+pub fn item_data<'a>(rlp: &Rlp<'a>) -> SigningResult<&'a [u8]> {
+    rlp.data()
+        .tw_err(|_| SigningErrorType::Error_input_parse)
+        .context("Expected an RLP byte string")
+}
+
+impl RlpDecode for U256 {
+    fn rlp_decode(rlp: &Rlp) -> SigningResult<Self> {
+        U256::from_big_endian_slice(item_data(rlp)?)
+            .tw_err(|_| SigningErrorType::Error_input_parse)
+            .context("Expected a uint256 number")
+    }
+}
+
+impl RlpDecode for H256 {
+    fn rlp_decode(rlp: &Rlp) -> SigningResult<Self> {
+        H256::try_from(item_data(rlp)?)
+            .tw_err(|_| SigningErrorType::Error_input_parse)
+            .context("Expected a 32 bytes hash")
+    }
+}
+
+impl RlpDecode for Address {
+    fn rlp_decode(rlp: &Rlp) -> SigningResult<Self> {
+        Address::try_from(item_data(rlp)?)
+            .tw_err(|_| SigningErrorType::Error_input_parse)
+            .context("Expected a 20 bytes address")
+    }
+}
+
+impl RlpDecode for Option<Address> {
+    fn rlp_decode(rlp: &Rlp) -> SigningResult<Self> {
+        if item_data(rlp)?.is_empty() {
+            return Ok(None);
+        }
+        Address::rlp_decode(rlp).map(Some)
+    }
+}
+
+impl RlpDecode for Data {
+    fn rlp_decode(rlp: &Rlp) -> SigningResult<Self> {
+        item_data(rlp).map(<[u8]>::to_vec)
+    }
+}
@@ -5,7 +5,7 @@
 use crate::address::Address;
 use crate::rlp::buffer::RlpBuffer;
 use crate::rlp::RlpEncode;
-use tw_hash::H256;
+use tw_hash::{H256, H384};
 use tw_number::U256;
 
 impl RlpEncode for U256 {
@@ -20,6 +20,12 @@ impl RlpEncode for H256 {
     }
 }
 
+impl RlpEncode for H384 {
+    fn rlp_append(&self, buf: &mut RlpBuffer) {
+        buf.append_data(self.as_slice())
+    }
+}
+
 impl RlpEncode for Address {
     fn rlp_append(&self, buf: &mut RlpBuffer) {
         buf.append_data(self.as_slice())
@@ -35,6 +35,22 @@ pub fn eip155_replay_protection(chain_id: U256, v: u8) -> NumberResult<U256> {
         .checked_add(v)
 }
 
+/// Splits a legacy transaction `v` into the recovery ID and an optional EIP155 `chain_id`.
+/// Returns `None` if `v` is neither `27 + recovery_id` nor `35 + chainID*2 + recovery_id`.
+pub fn split_replay_protection(v: U256) -> Option<(u8, Option<U256>)> {
+    const EIP155_V_OFFSET: u8 = 35;
+
+    if let Ok(legacy_v) = u8::try_from(v) {
+        if legacy_v == ETHEREUM_SIGNATURE_V_OFFSET || legacy_v == ETHEREUM_SIGNATURE_V_OFFSET + 1 {
+            return Some((legacy_v - ETHEREUM_SIGNATURE_V_OFFSET, None));
+        }
+    }
+
+    let chain_id_v = v.checked_sub(EIP155_V_OFFSET).ok()?;
+    let recovery_id = chain_id_v.low_u8() & 0x01;
+    Some((recovery_id, Some(chain_id_v >> 1)))
+}
+
 /// Removes EIP155 or legacy replay protection.
 #[inline]
 pub fn remove_replay_protection(v: u8) -> u8 {
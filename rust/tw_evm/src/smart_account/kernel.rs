@@ -0,0 +1,81 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::abi::prebuild::erc4337::ExecuteArgs;
+use crate::abi::prebuild::kernel::Kernel;
+use crate::abi::AbiResult;
+use crate::address::Address;
+use crate::smart_account::{keccak256_h256, AccountDeployment, SmartAccount};
+use tw_coin_entry::error::prelude::*;
+use tw_memory::Data;
+use tw_number::U256;
+
+/// ZeroDev Kernel v3.1 with an ECDSA root validator.
+/// The root validator is used if the user operation nonce key is zero.
+/// https://github.com/zerodevapp/kernel/blob/v3.1/src/Kernel.sol
+#[derive(Default)]
+pub struct KernelAccount {
+    /// The ECDSA validator module the account is initialized with.
+    /// Required to derive the account address only.
+    pub validator: Option<Address>,
+}
+
+impl SmartAccount for KernelAccount {
+    fn encode_execute(&self, call: ExecuteArgs) -> AbiResult<Data> {
+        Kernel::encode_execute(call)
+    }
+
+    fn encode_execute_batch(&self, calls: Vec<ExecuteArgs>) -> AbiResult<Data> {
+        Kernel::encode_execute_batch(calls)
+    }
+
+    /// `KernelFactory` deploys the account with `keccak256(data || salt)` salt,
+    /// where `data` is the `initialize` call.
+    /// `proxy_bytecode` is the `ERC1967` proxy creation code pointing to the Kernel implementation.
+    fn deployment(
+        &self,
+        owner: Address,
+        salt: U256,
+        proxy_bytecode: &[u8],
+    ) -> SigningResult<AccountDeployment> {
+        let validator = self
+            .validator
+            .or_tw_err(SigningErrorType::Error_invalid_params)
+            .context("Kernel validator address is required")?;
+        let initialize = Kernel::encode_initialize(validator, owner.as_slice().to_vec())
+            .tw_err(|_| SigningErrorType::Error_internal)
+            .context("Error encoding Kernel initialize call")?;
+        let factory_data = Kernel::encode_create_account(initialize.clone(), salt)
+            .tw_err(|_| SigningErrorType::Error_internal)
+            .context("Error encoding KernelFactory createAccount call")?;
+
+        let create2_salt = [initialize.as_slice(), salt.to_big_endian().as_slice()].concat();
+        Ok(AccountDeployment {
+            factory_data,
+            create2_salt: keccak256_h256(&create2_salt),
+            create2_init_code: proxy_bytecode.to_vec(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tw_encoding::hex;
+
+    #[test]
+    fn test_kernel_encode_execute_single() {
+        let call = ExecuteArgs {
+            to: Address::from("0x61061fCAE11fD5461535e134EfF67A98CFFF44E9"),
+            value: U256::from(10_000_000_000_000_000_u64),
+            data: Data::default(),
+        };
+        let encoded = KernelAccount::default().encode_execute(call).unwrap();
+        // `execute(bytes32(0), abi.encodePacked(to, value))`.
+        assert_eq!(
+            hex::encode(encoded, false),
+            "e9ae5c5300000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000040000000000000000000000000000000000000000000000000000000000000003461061fcae11fd5461535e134eff67a98cfff44e9000000000000000000000000000000000000000000000000002386f26fc10000000000000000000000000000"
+        );
+    }
+}
@@ -0,0 +1,71 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::abi::encode::encode_tokens;
+use crate::abi::prebuild::erc4337::{
+    Erc4337SimpleAccount, Erc4337SimpleAccountFactory, ExecuteArgs,
+};
+use crate::abi::token::Token;
+use crate::abi::AbiResult;
+use crate::address::Address;
+use crate::message::eip191::{ETHEREUM_MESSAGE_PREFIX, ETHEREUM_PREFIX};
+use crate::smart_account::{
+    keccak256_h256, rsv_signature, AccountDeployment, SmartAccount, UserOperationRef,
+};
+use crate::transaction::signature::Signature;
+use tw_coin_entry::error::prelude::*;
+use tw_hash::H256;
+use tw_memory::Data;
+use tw_number::U256;
+
+/// `LightAccount` v2 signature type of the account owner EOA signature.
+const SIGNATURE_TYPE_EOA: u8 = 0x00;
+
+/// Alchemy `LightAccount` v2.
+/// The owner signs the user operation hash as an EIP-191 personal message.
+/// https://github.com/alchemyplatform/light-account/blob/v2.0.0/src/LightAccount.sol
+pub struct LightAccount;
+
+impl SmartAccount for LightAccount {
+    fn encode_execute(&self, call: ExecuteArgs) -> AbiResult<Data> {
+        Erc4337SimpleAccount::encode_execute(call)
+    }
+
+    fn encode_execute_batch(&self, calls: Vec<ExecuteArgs>) -> AbiResult<Data> {
+        Erc4337SimpleAccount::encode_execute_batch(calls)
+    }
+
+    fn signing_hash(
+        &self,
+        _user_op: UserOperationRef<'_>,
+        user_op_hash: H256,
+        _chain_id: U256,
+    ) -> H256 {
+        let mut data = vec![ETHEREUM_PREFIX];
+        data.extend_from_slice(ETHEREUM_MESSAGE_PREFIX.as_bytes());
+        data.extend_from_slice(H256::LEN.to_string().as_bytes());
+        data.extend_from_slice(user_op_hash.as_slice());
+        keccak256_h256(&data)
+    }
+
+    fn wrap_signature(&self, signature: &Signature) -> Data {
+        [&[SIGNATURE_TYPE_EOA], rsv_signature(signature).as_slice()].concat()
+    }
+
+    /// `LightAccountFactory` deploys the Solady `LibClone` ERC1967 proxy with `keccak256(abi.encode(owner, salt))` salt.
+    /// `proxy_bytecode` is the proxy creation code including its constructor arguments.
+    fn deployment(
+        &self,
+        owner: Address,
+        salt: U256,
+        proxy_bytecode: &[u8],
+    ) -> SigningResult<AccountDeployment> {
+        let create2_salt = encode_tokens(&[Token::Address(owner), Token::u256(salt)]);
+        Ok(AccountDeployment {
+            factory_data: Erc4337SimpleAccountFactory::encode_create_account(owner, salt),
+            create2_salt: keccak256_h256(&create2_salt),
+            create2_init_code: proxy_bytecode.to_vec(),
+        })
+    }
+}
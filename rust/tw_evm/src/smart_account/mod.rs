@@ -0,0 +1,95 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+//! ERC-4337 smart contract accounts. An account type describes:
+//! - how calls are encoded into the user operation `callData` (`execute` and `executeBatch`);
+//! - which hash is signed by the account owner and how the signature is wrapped into the user operation;
+//! - how the account is deployed by its factory, used to derive the counterfactual account address.
+
+use crate::abi::prebuild::erc4337::ExecuteArgs;
+use crate::abi::AbiResult;
+use crate::address::Address;
+use crate::transaction::signature::Signature;
+use crate::transaction::user_operation::UserOperation;
+use crate::transaction::user_operation_v0_7::PackedUserOperation;
+use tw_coin_entry::error::prelude::*;
+use tw_hash::{H256, H520};
+use tw_memory::Data;
+use tw_number::U256;
+
+pub mod kernel;
+pub mod light_account;
+pub mod safe;
+pub mod simple_account;
+
+/// A user operation of any supported EntryPoint version.
+#[derive(Clone, Copy)]
+pub enum UserOperationRef<'a> {
+    V0_6(&'a UserOperation),
+    V0_7(&'a PackedUserOperation),
+}
+
+/// Describes how an account is deployed by its factory.
+pub struct AccountDeployment {
+    /// The factory call data: `factoryData` of EntryPoint v0.7 user operations,
+    /// or `initCode` of EntryPoint v0.6 user operations without the factory address prefix.
+    pub factory_data: Data,
+    /// The salt the account is deployed with via `CREATE2`.
+    pub create2_salt: H256,
+    /// The init code the account is deployed with via `CREATE2`.
+    pub create2_init_code: Data,
+}
+
+impl AccountDeployment {
+    /// Returns the counterfactual address of the account deployed by the given `factory`.
+    pub fn address(&self, factory: Address) -> Address {
+        Address::create2(factory, self.create2_salt, &self.create2_init_code)
+    }
+}
+
+pub trait SmartAccount {
+    /// Encodes a single call of the account.
+    fn encode_execute(&self, call: ExecuteArgs) -> AbiResult<Data>;
+
+    /// Encodes a batch of calls of the account.
+    fn encode_execute_batch(&self, calls: Vec<ExecuteArgs>) -> AbiResult<Data>;
+
+    /// Returns the hash to be signed by the account owner.
+    /// The user operation hash is signed as is by default.
+    fn signing_hash(
+        &self,
+        _user_op: UserOperationRef<'_>,
+        user_op_hash: H256,
+        _chain_id: U256,
+    ) -> H256 {
+        user_op_hash
+    }
+
+    /// Returns the user operation `signature` field.
+    /// The `r || s || v` signature is used as is by default.
+    fn wrap_signature(&self, signature: &Signature) -> Data {
+        rsv_signature(signature).to_vec()
+    }
+
+    /// Returns the deployment parameters of the account owned by `owner`.
+    /// `proxy_bytecode` is the creation code of the account proxy deployed by the factory.
+    fn deployment(
+        &self,
+        owner: Address,
+        salt: U256,
+        proxy_bytecode: &[u8],
+    ) -> SigningResult<AccountDeployment>;
+}
+
+/// Returns the `r || s || v` signature, where `v` is either 27 or 28.
+pub fn rsv_signature(signature: &Signature) -> H520 {
+    let mut rsv = signature.to_rsv_bytes();
+    rsv[64] += 27;
+    rsv
+}
+
+fn keccak256_h256(data: &[u8]) -> H256 {
+    let hash = tw_hash::sha3::keccak256(data);
+    H256::try_from(hash.as_slice()).expect("keccak256 returns 32 bytes")
+}
@@ -0,0 +1,199 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::abi::encode::encode_tokens;
+use crate::abi::non_empty_array::NonEmptyBytes;
+use crate::abi::prebuild::erc4337::ExecuteArgs;
+use crate::abi::prebuild::safe::{
+    MultiSendTransaction, Safe, SafeSetupArgs, SAFE_OPERATION_CALL, SAFE_OPERATION_DELEGATE_CALL,
+};
+use crate::abi::token::Token;
+use crate::abi::AbiResult;
+use crate::address::Address;
+use crate::smart_account::{
+    keccak256_h256, rsv_signature, AccountDeployment, SmartAccount, UserOperationRef,
+};
+use crate::transaction::signature::Signature;
+use tw_coin_entry::error::prelude::*;
+use tw_hash::H256;
+use tw_memory::Data;
+use tw_number::U256;
+
+/// `MultiSendCallOnly` v1.4.1, used to batch calls by default.
+pub const DEFAULT_MULTI_SEND: &str = "0x9641d764fc13c8B624c04430C7356C1C7C8102e2";
+
+const DOMAIN_TYPE: &str = "EIP712Domain(uint256 chainId,address verifyingContract)";
+/// `SafeOp` type of the `Safe4337Module` v0.2.0, EntryPoint v0.6.
+const SAFE_OP_V0_6_TYPE: &str = "SafeOp(address safe,uint256 nonce,bytes initCode,bytes callData,uint256 callGasLimit,uint256 verificationGasLimit,uint256 preVerificationGas,uint256 maxFeePerGas,uint256 maxPriorityFeePerGas,bytes paymasterAndData,uint48 validAfter,uint48 validUntil,address entryPoint)";
+/// `SafeOp` type of the `Safe4337Module` v0.3.0, EntryPoint v0.7.
+const SAFE_OP_V0_7_TYPE: &str = "SafeOp(address safe,uint256 nonce,bytes initCode,bytes callData,uint128 verificationGasLimit,uint128 callGasLimit,uint256 preVerificationGas,uint128 maxPriorityFeePerGas,uint128 maxFeePerGas,bytes paymasterAndData,uint48 validAfter,uint48 validUntil,address entryPoint)";
+
+/// Safe v1.4.1 account with the `Safe4337Module` enabled as a module and fallback handler.
+/// The owner signs the EIP-712 `SafeOp` hash, which is verified by the module.
+/// https://github.com/safe-global/safe-modules/blob/main/modules/4337/contracts/Safe4337Module.sol
+pub struct Safe4337Account {
+    /// `Safe4337Module` address, the EIP-712 verifying contract.
+    pub module: Address,
+    /// `MultiSend` contract delegate-called to batch calls.
+    pub multi_send: Address,
+    /// Timestamp the user operation is valid after, `uint48`.
+    pub valid_after: u64,
+    /// Timestamp the user operation is valid until, `uint48`. Zero means no expiration.
+    pub valid_until: u64,
+    /// Safe singleton the proxy points to.
+    /// Required to derive the account address only.
+    pub singleton: Option<Address>,
+    /// `SafeModuleSetup` contract delegate-called on the Safe setup to enable the module.
+    /// Required to derive the account address only.
+    pub module_setup: Option<Address>,
+}
+
+impl Safe4337Account {
+    fn safe_op_hash(&self, user_op: UserOperationRef<'_>) -> H256 {
+        let valid_after = U256::from(self.valid_after);
+        let valid_until = U256::from(self.valid_until);
+
+        let tokens = match user_op {
+            UserOperationRef::V0_6(op) => vec![
+                bytes32(keccak256_h256(SAFE_OP_V0_6_TYPE.as_bytes())),
+                Token::Address(op.sender),
+                Token::u256(op.nonce),
+                bytes32(keccak256_h256(&op.init_code)),
+                bytes32(keccak256_h256(&op.payload)),
+                Token::u256(op.gas_limit),
+                Token::u256(op.verification_gas_limit),
+                Token::u256(op.pre_verification_gas),
+                Token::u256(op.max_fee_per_gas),
+                Token::u256(op.max_inclusion_fee_per_gas),
+                bytes32(keccak256_h256(&op.paymaster_and_data)),
+                Token::u256(valid_after),
+                Token::u256(valid_until),
+                Token::Address(op.entry_point),
+            ],
+            UserOperationRef::V0_7(op) => vec![
+                bytes32(keccak256_h256(SAFE_OP_V0_7_TYPE.as_bytes())),
+                Token::Address(op.sender),
+                Token::u256(op.nonce),
+                bytes32(keccak256_h256(&op.init_code())),
+                bytes32(keccak256_h256(&op.payload)),
+                Token::u256(U256::from(op.verification_gas_limit)),
+                Token::u256(U256::from(op.call_gas_limit)),
+                Token::u256(op.pre_verification_gas),
+                Token::u256(U256::from(op.max_priority_fee_per_gas)),
+                Token::u256(U256::from(op.max_fee_per_gas)),
+                bytes32(keccak256_h256(&op.paymaster_and_data())),
+                Token::u256(valid_after),
+                Token::u256(valid_until),
+                Token::Address(op.entry_point),
+            ],
+        };
+        keccak256_h256(&encode_tokens(&tokens))
+    }
+
+    fn domain_separator(&self, chain_id: U256) -> H256 {
+        let tokens = [
+            bytes32(keccak256_h256(DOMAIN_TYPE.as_bytes())),
+            Token::u256(chain_id),
+            Token::Address(self.module),
+        ];
+        keccak256_h256(&encode_tokens(&tokens))
+    }
+}
+
+impl SmartAccount for Safe4337Account {
+    fn encode_execute(&self, call: ExecuteArgs) -> AbiResult<Data> {
+        Safe::encode_execute_user_op(call.to, call.value, call.data, SAFE_OPERATION_CALL)
+    }
+
+    /// Batches the calls via `MultiSend` delegate call.
+    fn encode_execute_batch(&self, calls: Vec<ExecuteArgs>) -> AbiResult<Data> {
+        let transactions = calls.into_iter().map(|call| MultiSendTransaction {
+            operation: SAFE_OPERATION_CALL,
+            to: call.to,
+            value: call.value,
+            data: call.data,
+        });
+        let multi_send = Safe::encode_multi_send(transactions);
+        Safe::encode_execute_user_op(
+            self.multi_send,
+            U256::zero(),
+            multi_send,
+            SAFE_OPERATION_DELEGATE_CALL,
+        )
+    }
+
+    /// Returns the EIP-712 hash of the `SafeOp` message.
+    fn signing_hash(
+        &self,
+        user_op: UserOperationRef<'_>,
+        _user_op_hash: H256,
+        chain_id: U256,
+    ) -> H256 {
+        let data = [
+            [0x19, 0x01].as_slice(),
+            self.domain_separator(chain_id).as_slice(),
+            self.safe_op_hash(user_op).as_slice(),
+        ]
+        .concat();
+        keccak256_h256(&data)
+    }
+
+    /// Returns `validAfter (uint48) || validUntil (uint48) || r || s || v`.
+    fn wrap_signature(&self, signature: &Signature) -> Data {
+        let valid_after = self.valid_after.to_be_bytes();
+        let valid_until = self.valid_until.to_be_bytes();
+        [
+            &valid_after[2..],
+            &valid_until[2..],
+            rsv_signature(signature).as_slice(),
+        ]
+        .concat()
+    }
+
+    /// `SafeProxyFactory` deploys the `SafeProxy(singleton)` with `keccak256(keccak256(initializer) || saltNonce)` salt,
+    /// where `initializer` is the Safe `setup` call enabling the module.
+    /// `proxy_bytecode` is the `SafeProxy` creation code.
+    fn deployment(
+        &self,
+        owner: Address,
+        salt: U256,
+        proxy_bytecode: &[u8],
+    ) -> SigningResult<AccountDeployment> {
+        let singleton = self
+            .singleton
+            .or_tw_err(SigningErrorType::Error_invalid_params)
+            .context("Safe singleton address is required")?;
+        let module_setup = self
+            .module_setup
+            .or_tw_err(SigningErrorType::Error_invalid_params)
+            .context("SafeModuleSetup address is required")?;
+
+        let initializer = Safe::encode_setup(SafeSetupArgs {
+            owners: vec![owner],
+            threshold: U256::from(1_u64),
+            to: module_setup,
+            data: Safe::encode_enable_modules(vec![self.module]),
+            fallback_handler: self.module,
+            payment_token: Address::default(),
+            payment: U256::zero(),
+            payment_receiver: Address::default(),
+        });
+
+        let create2_salt = [
+            keccak256_h256(&initializer).as_slice(),
+            salt.to_big_endian().as_slice(),
+        ]
+        .concat();
+        let singleton_arg = encode_tokens(&[Token::Address(singleton)]);
+        Ok(AccountDeployment {
+            factory_data: Safe::encode_create_proxy_with_nonce(singleton, initializer, salt),
+            create2_salt: keccak256_h256(&create2_salt),
+            create2_init_code: [proxy_bytecode, singleton_arg.as_slice()].concat(),
+        })
+    }
+}
+
+fn bytes32(hash: H256) -> Token {
+    Token::FixedBytes(NonEmptyBytes::new(hash.to_vec()).expect("H256 must not be empty"))
+}
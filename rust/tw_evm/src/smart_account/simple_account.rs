@@ -0,0 +1,59 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::abi::encode::encode_tokens;
+use crate::abi::prebuild::erc4337::{
+    Erc4337SimpleAccount, Erc4337SimpleAccountFactory, ExecuteArgs,
+};
+use crate::abi::token::Token;
+use crate::abi::AbiResult;
+use crate::address::Address;
+use crate::smart_account::{AccountDeployment, SmartAccount};
+use tw_coin_entry::error::prelude::*;
+use tw_memory::Data;
+use tw_number::U256;
+
+/// `SimpleAccount` of the eth-infinitism reference implementation.
+/// The same `execute`/`executeBatch` encodings are used by Barz accounts.
+/// https://github.com/eth-infinitism/account-abstraction/blob/develop/contracts/samples/SimpleAccount.sol
+#[derive(Default)]
+pub struct SimpleAccount {
+    /// The `SimpleAccount` implementation the account proxy points to.
+    /// Required to derive the account address only.
+    pub implementation: Option<Address>,
+}
+
+impl SmartAccount for SimpleAccount {
+    fn encode_execute(&self, call: ExecuteArgs) -> AbiResult<Data> {
+        Erc4337SimpleAccount::encode_execute(call)
+    }
+
+    fn encode_execute_batch(&self, calls: Vec<ExecuteArgs>) -> AbiResult<Data> {
+        Erc4337SimpleAccount::encode_execute_batch(calls)
+    }
+
+    /// `SimpleAccountFactory` deploys `ERC1967Proxy(implementation, initialize(owner))` with the given salt.
+    fn deployment(
+        &self,
+        owner: Address,
+        salt: U256,
+        proxy_bytecode: &[u8],
+    ) -> SigningResult<AccountDeployment> {
+        let implementation = self
+            .implementation
+            .or_tw_err(SigningErrorType::Error_invalid_params)
+            .context("SimpleAccount implementation address is required")?;
+        let initialize = Erc4337SimpleAccount::encode_initialize(owner)
+            .tw_err(|_| SigningErrorType::Error_internal)
+            .context("Error encoding SimpleAccount initialize call")?;
+
+        let constructor_args =
+            encode_tokens(&[Token::Address(implementation), Token::Bytes(initialize)]);
+        Ok(AccountDeployment {
+            factory_data: Erc4337SimpleAccountFactory::encode_create_account(owner, salt),
+            create2_salt: salt.to_big_endian(),
+            create2_init_code: [proxy_bytecode, constructor_args.as_slice()].concat(),
+        })
+    }
+}
@@ -4,7 +4,10 @@
 
 use crate::address::Address;
 use crate::rlp::buffer::RlpBuffer;
+use crate::rlp::decode::{list_items, RlpDecode};
 use crate::rlp::RlpEncode;
+use rlp::Rlp;
+use tw_coin_entry::error::prelude::*;
 use tw_hash::H256;
 
 /// A list of addresses and storage keys that the transaction plans to access.
@@ -47,6 +50,22 @@ impl RlpEncode for Access {
     }
 }
 
+impl RlpDecode for Access {
+    fn rlp_decode(rlp: &Rlp) -> SigningResult<Self> {
+        let items = list_items(rlp)?;
+        let [address, storage_keys] = items.as_slice() else {
+            return SigningError::err(SigningErrorType::Error_input_parse)
+                .context("Access list item must contain an address and storage keys");
+        };
+
+        let mut access = Access::new(Address::rlp_decode(address)?);
+        for storage_key in list_items(storage_keys)? {
+            access.add_storage_key(H256::rlp_decode(&storage_key)?);
+        }
+        Ok(access)
+    }
+}
+
 /// [EIP-2930](https://eips.ethereum.org/EIPS/eip-2930) access list.
 #[derive(Default)]
 pub struct AccessList(Vec<Access>);
@@ -57,6 +76,11 @@ impl AccessList {
         self.0.push(access);
         self
     }
+
+    #[inline]
+    pub fn accesses(&self) -> &[Access] {
+        &self.0
+    }
 }
 
 impl RlpEncode for AccessList {
@@ -70,3 +94,13 @@ impl RlpEncode for AccessList {
         buf.finalize_list();
     }
 }
+
+impl RlpDecode for AccessList {
+    fn rlp_decode(rlp: &Rlp) -> SigningResult<Self> {
+        let mut access_list = AccessList::default();
+        for access in list_items(rlp)? {
+            access_list.add_access(Access::rlp_decode(&access)?);
+        }
+        Ok(access_list)
+    }
+}
@@ -0,0 +1,238 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::address::Address;
+use crate::rlp::buffer::RlpBuffer;
+use crate::rlp::decode::{list_items, RlpDecode};
+use crate::rlp::list::RlpList;
+use crate::rlp::RlpEncode;
+use crate::transaction::signature::{EthSignature, Signature};
+use rlp::Rlp;
+use tw_coin_entry::error::prelude::*;
+use tw_hash::sha3::keccak256;
+use tw_hash::H256;
+use tw_keypair::ecdsa::secp256k1;
+use tw_keypair::traits::SigningKeyTrait;
+use tw_memory::Data;
+use tw_number::U256;
+
+/// The magic prefix of the authorization pre-image, see [EIP-7702](https://eips.ethereum.org/EIPS/eip-7702).
+pub const EIP7702_AUTHORIZATION_MAGIC: u8 = 0x05;
+
+/// An unsigned authorization to set the code of the authority account to the `address` contract.
+pub struct Authorization {
+    /// Zero chain ID means the authorization is valid on any chain.
+    pub chain_id: U256,
+    pub address: Address,
+    pub nonce: U256,
+}
+
+impl Authorization {
+    /// Encodes the data to be signed: `0x05 || rlp([chain_id, address, nonce])`.
+    pub fn encode(&self) -> Data {
+        let mut list = RlpList::new();
+        list.append(&self.chain_id)
+            .append(&self.address)
+            .append(&self.nonce);
+        let encoded = list.finish();
+
+        let mut pre_image = Vec::with_capacity(encoded.len() + 1);
+        pre_image.push(EIP7702_AUTHORIZATION_MAGIC);
+        pre_image.extend_from_slice(encoded.as_slice());
+        pre_image
+    }
+
+    pub fn pre_hash(&self) -> H256 {
+        let hash = keccak256(&self.encode());
+        H256::try_from(hash.as_slice()).expect("keccak256 returns 32 bytes")
+    }
+
+    /// Signs the authorization with the authority account private key.
+    pub fn sign(self, private_key: &secp256k1::PrivateKey) -> SigningResult<SignedAuthorization> {
+        let signature = private_key.sign(self.pre_hash())?;
+        Ok(self.into_signed(signature))
+    }
+
+    #[inline]
+    pub fn into_signed(self, signature: secp256k1::Signature) -> SignedAuthorization {
+        SignedAuthorization {
+            unsigned: self,
+            signature: Signature::new(signature),
+        }
+    }
+}
+
+/// An authorization signed by the authority account.
+pub struct SignedAuthorization {
+    unsigned: Authorization,
+    signature: Signature,
+}
+
+impl SignedAuthorization {
+    #[inline]
+    pub fn authorization(&self) -> &Authorization {
+        &self.unsigned
+    }
+
+    #[inline]
+    pub fn signature(&self) -> &Signature {
+        &self.signature
+    }
+}
+
+impl RlpEncode for SignedAuthorization {
+    fn rlp_append(&self, buf: &mut RlpBuffer) {
+        buf.begin_list();
+        self.unsigned.chain_id.rlp_append(buf);
+        self.unsigned.address.rlp_append(buf);
+        self.unsigned.nonce.rlp_append(buf);
+        self.signature.v().rlp_append(buf);
+        self.signature.r().rlp_append(buf);
+        self.signature.s().rlp_append(buf);
+        buf.finalize_list();
+    }
+}
+
+impl RlpDecode for SignedAuthorization {
+    fn rlp_decode(rlp: &Rlp) -> SigningResult<Self> {
+        let items = list_items(rlp)?;
+        let [chain_id, address, nonce, y_parity, r, s] = items.as_slice() else {
+            return SigningError::err(SigningErrorType::Error_input_parse)
+                .context("Authorization must contain a chain ID, address, nonce and signature");
+        };
+
+        let unsigned = Authorization {
+            chain_id: U256::rlp_decode(chain_id).context("Invalid authorization chain ID")?,
+            address: Address::rlp_decode(address).context("Invalid authorization address")?,
+            nonce: U256::rlp_decode(nonce).context("Invalid authorization nonce")?,
+        };
+
+        let y_parity = U256::rlp_decode(y_parity)
+            .ok()
+            .and_then(|y_parity| u8::try_from(y_parity).ok())
+            .filter(|y_parity| *y_parity <= 1)
+            .or_tw_err(SigningErrorType::Error_input_parse)
+            .context("Authorization 'yParity' must be either 0 or 1")?;
+        let r = U256::rlp_decode(r).context("Invalid authorization 'r'")?;
+        let s = U256::rlp_decode(s).context("Invalid authorization 's'")?;
+
+        let signature =
+            secp256k1::Signature::try_from_parts(r.to_big_endian(), s.to_big_endian(), y_parity)
+                .into_tw()
+                .context("Invalid authorization signature")?;
+        Ok(unsigned.into_signed(signature))
+    }
+}
+
+/// [EIP-7702](https://eips.ethereum.org/EIPS/eip-7702) authorization list.
+#[derive(Default)]
+pub struct AuthorizationList(Vec<SignedAuthorization>);
+
+impl AuthorizationList {
+    #[inline]
+    pub fn add_authorization(&mut self, authorization: SignedAuthorization) -> &mut Self {
+        self.0.push(authorization);
+        self
+    }
+
+    #[inline]
+    pub fn authorizations(&self) -> &[SignedAuthorization] {
+        &self.0
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl RlpEncode for AuthorizationList {
+    fn rlp_append(&self, buf: &mut RlpBuffer) {
+        buf.begin_list();
+
+        for authorization in self.0.iter() {
+            authorization.rlp_append(buf);
+        }
+
+        buf.finalize_list();
+    }
+}
+
+impl RlpDecode for AuthorizationList {
+    fn rlp_decode(rlp: &Rlp) -> SigningResult<Self> {
+        let mut authorization_list = AuthorizationList::default();
+        for authorization in list_items(rlp)? {
+            authorization_list.add_authorization(SignedAuthorization::rlp_decode(&authorization)?);
+        }
+        Ok(authorization_list)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tw_encoding::hex;
+
+    fn authorization() -> Authorization {
+        Authorization {
+            chain_id: U256::from(1_u64),
+            address: Address::from("0x63c0c19a282a1B52b07dD5a65b58948A07DAE32B"),
+            nonce: U256::from(1_u64),
+        }
+    }
+
+    #[test]
+    fn test_authorization_pre_hash() {
+        let authorization = authorization();
+
+        let expected_pre_image = "05d7019463c0c19a282a1b52b07dd5a65b58948a07dae32b01";
+        assert_eq!(
+            hex::encode(authorization.encode(), false),
+            expected_pre_image
+        );
+
+        let expected_pre_hash = "73c37d50a1a39a021d14f800a6e7b8ef6a29e1f8ea5fa2fcbf91ba4a5a58abac";
+        assert_eq!(
+            hex::encode(authorization.pre_hash(), false),
+            expected_pre_hash
+        );
+    }
+
+    #[test]
+    fn test_sign_authorization() {
+        // 0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F
+        let private_key = secp256k1::PrivateKey::try_from(
+            "0x4646464646464646464646464646464646464646464646464646464646464646",
+        )
+        .unwrap();
+
+        let signed = authorization().sign(&private_key).unwrap();
+
+        let expected_signature = "9a0abf766ca397a20ebe6f60715c6135c407095a86be6e1b998eb6a166dd3454258954de48df63a95157d8fa29712b26e33c703f06b0b84f8e9db756004d100500";
+        assert_eq!(
+            hex::encode(signed.signature().to_rsv_bytes(), false),
+            expected_signature
+        );
+
+        let mut authorization_list = AuthorizationList::default();
+        authorization_list.add_authorization(signed);
+
+        let mut buf = RlpBuffer::new();
+        authorization_list.rlp_append(&mut buf);
+        let encoded = buf.finish();
+
+        let expected_encoded = "f85cf85a019463c0c19a282a1b52b07dd5a65b58948a07dae32b0180a09a0abf766ca397a20ebe6f60715c6135c407095a86be6e1b998eb6a166dd3454a0258954de48df63a95157d8fa29712b26e33c703f06b0b84f8e9db756004d1005";
+        assert_eq!(hex::encode(&encoded, false), expected_encoded);
+
+        let decoded = AuthorizationList::rlp_decode(&Rlp::new(&encoded)).unwrap();
+        let [decoded] = decoded.authorizations() else {
+            panic!("Expected one authorization");
+        };
+        assert_eq!(decoded.authorization().nonce, U256::from(1_u64));
+        assert_eq!(
+            hex::encode(decoded.signature().to_rsv_bytes(), false),
+            expected_signature
+        );
+    }
+}
@@ -0,0 +1,166 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::rlp::decode::{item_data, list_items, RlpDecode};
+use crate::rlp::list::RlpList;
+use crate::rlp::RlpEncode;
+use rlp::Rlp;
+use tw_coin_entry::error::prelude::*;
+use tw_hash::sha2::sha256;
+use tw_hash::{Hash, H256, H384};
+use tw_memory::Data;
+
+/// The size of a blob: 4096 field elements of 32 bytes each.
+/// cbindgen:ignore
+pub const BYTES_PER_BLOB: usize = 131_072;
+/// The version byte of a versioned hash of a KZG commitment.
+/// cbindgen:ignore
+pub const VERSIONED_HASH_VERSION_KZG: u8 = 0x01;
+
+pub type KzgCommitment = H384;
+pub type KzgProof = H384;
+
+/// Computes a blob versioned hash: `VERSIONED_HASH_VERSION_KZG || sha256(commitment)[1:]`.
+pub fn kzg_to_versioned_hash(commitment: &KzgCommitment) -> H256 {
+    let mut hash =
+        H256::try_from(sha256(commitment.as_slice()).as_slice()).expect("sha256 returns 32 bytes");
+    hash[0] = VERSIONED_HASH_VERSION_KZG;
+    hash
+}
+
+/// [EIP-4844](https://eips.ethereum.org/EIPS/eip-4844) blobs with their KZG commitments and proofs.
+/// The sidecar is not signed, but it's required to broadcast a blob transaction in the network wrapper form.
+///
+/// Please note that commitments and proofs are supplied by the caller and not verified against the blobs.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BlobSidecar {
+    blobs: Vec<Data>,
+    commitments: Vec<KzgCommitment>,
+    proofs: Vec<KzgProof>,
+}
+
+impl BlobSidecar {
+    pub fn new(
+        blobs: Vec<Data>,
+        commitments: Vec<KzgCommitment>,
+        proofs: Vec<KzgProof>,
+    ) -> SigningResult<BlobSidecar> {
+        if blobs.is_empty() {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("Blob sidecar must contain at least one blob");
+        }
+        if blobs.len() != commitments.len() || blobs.len() != proofs.len() {
+            return SigningError::err(SigningErrorType::Error_invalid_params)
+                .context("Expected the same number of blobs, commitments and proofs");
+        }
+        if let Some(blob) = blobs.iter().find(|blob| blob.len() != BYTES_PER_BLOB) {
+            return SigningError::err(SigningErrorType::Error_invalid_params).with_context(|| {
+                format!("Expected {BYTES_PER_BLOB} bytes blob, found {}", blob.len())
+            });
+        }
+
+        Ok(BlobSidecar {
+            blobs,
+            commitments,
+            proofs,
+        })
+    }
+
+    #[inline]
+    pub fn blobs(&self) -> &[Data] {
+        &self.blobs
+    }
+
+    #[inline]
+    pub fn commitments(&self) -> &[KzgCommitment] {
+        &self.commitments
+    }
+
+    #[inline]
+    pub fn proofs(&self) -> &[KzgProof] {
+        &self.proofs
+    }
+
+    /// Returns the versioned hashes of the blob commitments.
+    pub fn versioned_hashes(&self) -> Vec<H256> {
+        self.commitments.iter().map(kzg_to_versioned_hash).collect()
+    }
+
+    /// Appends `blobs, commitments, proofs` items of the network wrapper.
+    pub fn rlp_append_items(&self, list: &mut RlpList) {
+        let mut blobs = RlpList::new();
+        for blob in self.blobs.iter() {
+            blobs.append(blob.as_slice());
+        }
+        list.append_raw_encoded(&blobs.finish())
+            .append_raw_encoded(&rlp_encode_hashes(&self.commitments))
+            .append_raw_encoded(&rlp_encode_hashes(&self.proofs));
+    }
+
+    /// Decodes `blobs, commitments, proofs` items of the network wrapper.
+    pub fn rlp_decode_items(
+        blobs: &Rlp,
+        commitments: &Rlp,
+        proofs: &Rlp,
+    ) -> SigningResult<BlobSidecar> {
+        let blobs = list_items(blobs)?
+            .iter()
+            .map(Data::rlp_decode)
+            .collect::<SigningResult<_>>()
+            .context("Invalid blobs")?;
+        let commitments = rlp_decode_hashes(commitments).context("Invalid KZG commitments")?;
+        let proofs = rlp_decode_hashes(proofs).context("Invalid KZG proofs")?;
+        BlobSidecar::new(blobs, commitments, proofs)
+    }
+}
+
+/// Encodes a list of 32 or 48 bytes hashes.
+pub fn rlp_encode_hashes<T: RlpEncode>(hashes: &[T]) -> Data {
+    let mut list = RlpList::new();
+    for hash in hashes.iter() {
+        list.append(hash);
+    }
+    list.finish()
+}
+
+/// Decodes a list of 32 or 48 bytes hashes.
+pub fn rlp_decode_hashes<const N: usize>(rlp: &Rlp) -> SigningResult<Vec<Hash<N>>> {
+    list_items(rlp)?
+        .iter()
+        .map(|item| {
+            Hash::<N>::try_from(item_data(item)?)
+                .tw_err(|_| SigningErrorType::Error_input_parse)
+                .with_context(|| format!("Expected a {N} bytes hash"))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The commitment of an empty blob is the point at infinity.
+    const EMPTY_BLOB_COMMITMENT: &str = "c00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000";
+
+    #[test]
+    fn test_kzg_to_versioned_hash() {
+        let commitment = KzgCommitment::from(EMPTY_BLOB_COMMITMENT);
+        assert_eq!(
+            kzg_to_versioned_hash(&commitment),
+            H256::from("010657f37554c781402a22917dee2f75def7ab966d7b770905398eba3c444014")
+        );
+    }
+
+    #[test]
+    fn test_blob_sidecar_invalid() {
+        let commitment = KzgCommitment::from(EMPTY_BLOB_COMMITMENT);
+
+        // Invalid blob size.
+        BlobSidecar::new(vec![vec![0; 32]], vec![commitment], vec![commitment]).unwrap_err();
+        // Missing proof.
+        BlobSidecar::new(vec![vec![0; BYTES_PER_BLOB]], vec![commitment], vec![]).unwrap_err();
+        // No blobs.
+        BlobSidecar::new(vec![], vec![], vec![]).unwrap_err();
+    }
+}
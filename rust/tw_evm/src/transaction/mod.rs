@@ -7,7 +7,11 @@
 //!   - simple ETH transfer
 //!   - others with payload, function call, e.g. ERC20 transfer
 //! - Typed transactions (enveloped, EIP2718), with specific type and transaction payload
-//! - User operations (EIP4337)
+//!   - EIP2930 access list transactions with a legacy gas price
+//!   - EIP1559 transactions
+//!   - EIP4844 blob transactions, optionally encoded with blobs in the network wrapper form
+//!   - EIP7702 set-code transactions with an authorization list
+//! - User operations (EIP4337), EntryPoint v0.6 and v0.7 (packed)
 
 use crate::transaction::signature::EthSignature;
 use tw_coin_entry::error::prelude::*;
@@ -17,10 +21,16 @@ use tw_memory::Data;
 use tw_number::U256;
 
 pub mod access_list;
+pub mod authorization_list;
+pub mod blob;
 pub mod signature;
 pub mod transaction_eip1559;
+pub mod transaction_eip2930;
+pub mod transaction_eip4844;
+pub mod transaction_eip7702;
 pub mod transaction_non_typed;
 pub mod user_operation;
+pub mod user_operation_v0_7;
 
 pub trait TransactionCommon {
     fn payload(&self) -> Data;
@@ -48,6 +58,12 @@ pub trait SignedTransaction: TransactionCommon {
 
     fn encode(&self) -> Data;
 
+    /// Encodes the transaction in a form that additionally contains data not covered by the signature,
+    /// e.g. blobs of an EIP4844 transaction. Returns `None` if the transaction has no such form.
+    fn encode_network_wrapper(&self) -> Option<Data> {
+        None
+    }
+
     fn signature(&self) -> &Self::Signature;
 }
 
@@ -95,6 +111,8 @@ where
 pub trait SignedTransactionBox: TransactionCommon {
     fn encode(&self) -> Data;
 
+    fn encode_network_wrapper(&self) -> Option<Data>;
+
     fn signature(&self) -> &dyn EthSignature;
 }
 
@@ -106,6 +124,10 @@ where
         <Self as SignedTransaction>::encode(self)
     }
 
+    fn encode_network_wrapper(&self) -> Option<Data> {
+        <Self as SignedTransaction>::encode_network_wrapper(self)
+    }
+
     fn signature(&self) -> &dyn EthSignature {
         <Self as SignedTransaction>::signature(self)
     }
@@ -0,0 +1,217 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::address::Address;
+use crate::rlp::decode::decode_list;
+use crate::rlp::list::RlpList;
+use crate::transaction::access_list::AccessList;
+use crate::transaction::blob::{rlp_encode_hashes, BlobSidecar};
+use crate::transaction::signature::{EthSignature, Signature};
+use crate::transaction::{SignedTransaction, TransactionCommon, UnsignedTransaction};
+use tw_coin_entry::error::prelude::*;
+use tw_hash::H256;
+use tw_keypair::ecdsa::secp256k1;
+use tw_memory::Data;
+use tw_number::U256;
+
+const EIP4844_TX_TYPE: u8 = 0x03;
+
+/// [EIP-4844](https://eips.ethereum.org/EIPS/eip-4844) blob transaction.
+/// Unlike EIP1559 transactions, the destination address is required.
+pub struct TransactionEip4844 {
+    pub nonce: U256,
+    pub max_inclusion_fee_per_gas: U256,
+    pub max_fee_per_gas: U256,
+    pub gas_limit: U256,
+    pub to: Address,
+    pub amount: U256,
+    pub payload: Data,
+    pub access_list: AccessList,
+    pub max_fee_per_blob_gas: U256,
+    pub blob_versioned_hashes: Vec<H256>,
+    /// Optional blobs to be included into the network wrapper form.
+    pub sidecar: Option<BlobSidecar>,
+}
+
+impl TransactionCommon for TransactionEip4844 {
+    #[inline]
+    fn payload(&self) -> Data {
+        self.payload.clone()
+    }
+}
+
+impl UnsignedTransaction for TransactionEip4844 {
+    type SignedTransaction = SignedTransactionEip4844;
+
+    #[inline]
+    fn encode(&self, chain_id: U256) -> Data {
+        encode_transaction(self, chain_id, None)
+    }
+
+    #[inline]
+    fn try_into_signed(
+        self,
+        signature: secp256k1::Signature,
+        chain_id: U256,
+    ) -> SigningResult<Self::SignedTransaction> {
+        Ok(SignedTransactionEip4844 {
+            unsigned: self,
+            signature: Signature::new(signature),
+            chain_id,
+        })
+    }
+}
+
+pub struct SignedTransactionEip4844 {
+    unsigned: TransactionEip4844,
+    signature: Signature,
+    chain_id: U256,
+}
+
+impl TransactionCommon for SignedTransactionEip4844 {
+    #[inline]
+    fn payload(&self) -> Data {
+        self.unsigned.payload.clone()
+    }
+}
+
+impl SignedTransaction for SignedTransactionEip4844 {
+    type Signature = Signature;
+
+    #[inline]
+    fn encode(&self) -> Data {
+        encode_transaction(&self.unsigned, self.chain_id, Some(&self.signature))
+    }
+
+    /// Encodes the transaction in the network wrapper form:
+    /// `0x03 || rlp([tx_payload_body, blobs, commitments, proofs])`.
+    fn encode_network_wrapper(&self) -> Option<Data> {
+        let sidecar = self.unsigned.sidecar.as_ref()?;
+
+        let tx_payload_body =
+            encode_payload_body(&self.unsigned, self.chain_id, Some(&self.signature));
+        let mut list = RlpList::new();
+        list.append_raw_encoded(&tx_payload_body);
+        sidecar.rlp_append_items(&mut list);
+
+        Some(envelope(list.finish()))
+    }
+
+    #[inline]
+    fn signature(&self) -> &Self::Signature {
+        &self.signature
+    }
+}
+
+/// Returns the canonical `0x03 || rlp(tx_payload_body)` encoding of a blob transaction
+/// if the given `encoded` transaction is in the network wrapper form.
+/// Returns `None` if the transaction is not a blob transaction or is already canonical.
+pub fn canonical_from_network_wrapper(encoded: &[u8]) -> SigningResult<Option<Data>> {
+    let Some((&EIP4844_TX_TYPE, tx_payload)) = encoded.split_first() else {
+        return Ok(None);
+    };
+
+    let items = decode_list(tx_payload).context("Invalid EIP-4844 transaction")?;
+    match items.first() {
+        // The network wrapper starts with the transaction payload body list.
+        Some(tx_payload_body) if tx_payload_body.is_list() => {
+            Ok(Some(envelope(tx_payload_body.as_raw().to_vec())))
+        },
+        _ => Ok(None),
+    }
+}
+
+fn encode_transaction(
+    tx: &TransactionEip4844,
+    chain_id: U256,
+    signature: Option<&Signature>,
+) -> Data {
+    envelope(encode_payload_body(tx, chain_id, signature))
+}
+
+fn encode_payload_body(
+    tx: &TransactionEip4844,
+    chain_id: U256,
+    signature: Option<&Signature>,
+) -> Data {
+    let mut list = RlpList::new();
+    list.append(&chain_id)
+        .append(&tx.nonce)
+        .append(&tx.max_inclusion_fee_per_gas)
+        .append(&tx.max_fee_per_gas)
+        .append(&tx.gas_limit)
+        .append(&tx.to)
+        .append(&tx.amount)
+        .append(tx.payload.as_slice())
+        .append(&tx.access_list)
+        .append(&tx.max_fee_per_blob_gas)
+        .append_raw_encoded(&rlp_encode_hashes(&tx.blob_versioned_hashes));
+
+    if let Some(signature) = signature {
+        list.append(&signature.v());
+        list.append(&signature.r());
+        list.append(&signature.s());
+    }
+
+    list.finish()
+}
+
+fn envelope(tx_encoded: Data) -> Data {
+    let mut envelope = Vec::with_capacity(tx_encoded.len() + 1);
+    envelope.push(EIP4844_TX_TYPE);
+    envelope.extend_from_slice(tx_encoded.as_slice());
+    envelope
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tw_encoding::hex;
+
+    #[test]
+    fn test_encode_transaction_eip4844() {
+        let tx = TransactionEip4844 {
+            nonce: U256::zero(),
+            max_inclusion_fee_per_gas: U256::from(1_000_000_000_u64),
+            max_fee_per_gas: U256::from(20_000_000_000_u64),
+            gas_limit: U256::from(21_000_u64),
+            to: Address::from("0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F"),
+            amount: U256::zero(),
+            payload: Data::default(),
+            access_list: AccessList::default(),
+            max_fee_per_blob_gas: U256::from(1_000_000_000_u64),
+            blob_versioned_hashes: vec![H256::from(
+                "010657f37554c781402a22917dee2f75def7ab966d7b770905398eba3c444014",
+            )],
+            sidecar: None,
+        };
+        let chain_id = U256::from(1_u64);
+        let actual = tx.encode(chain_id);
+
+        let expected = "03f84f0180843b9aca008504a817c800825208949d8a62f656a8d1615c1294fd71e9cfb3e4855a4f8080c0843b9aca00e1a0010657f37554c781402a22917dee2f75def7ab966d7b770905398eba3c444014";
+        assert_eq!(hex::encode(actual, false), expected);
+    }
+
+    #[test]
+    fn test_canonical_from_network_wrapper() {
+        let canonical = hex::decode("03f8920180843b9aca008504a817c800825208949d8a62f656a8d1615c1294fd71e9cfb3e4855a4f8080c0843b9aca00e1a0010657f37554c781402a22917dee2f75def7ab966d7b770905398eba3c44401401a0ba0f2837d74d1438a4dd8fef76f7e42fc7f4262d1efc0bf28d39ef17d41b5b88a0232ae1b976e6d80bfc2ebb2d8dfcd2e98030ed90b50c809e868f9fa9b5ce1d4e").unwrap();
+        assert_eq!(canonical_from_network_wrapper(&canonical).unwrap(), None);
+
+        // `0x03 || rlp([tx_payload_body, [], [], []])`.
+        let mut network_wrapper = RlpList::new();
+        network_wrapper
+            .append_raw_encoded(&canonical[1..])
+            .append_raw_encoded(&RlpList::new().finish())
+            .append_raw_encoded(&RlpList::new().finish())
+            .append_raw_encoded(&RlpList::new().finish());
+        let network_wrapper = envelope(network_wrapper.finish());
+        assert_eq!(
+            canonical_from_network_wrapper(&network_wrapper).unwrap(),
+            Some(canonical)
+        );
+
+        // Not a blob transaction.
+        assert_eq!(canonical_from_network_wrapper(&[0x02, 0xc0]).unwrap(), None);
+    }
+}
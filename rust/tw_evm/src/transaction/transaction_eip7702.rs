@@ -0,0 +1,154 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::address::Address;
+use crate::rlp::list::RlpList;
+use crate::transaction::access_list::AccessList;
+use crate::transaction::authorization_list::AuthorizationList;
+use crate::transaction::signature::{EthSignature, Signature};
+use crate::transaction::{SignedTransaction, TransactionCommon, UnsignedTransaction};
+use tw_coin_entry::error::prelude::*;
+use tw_keypair::ecdsa::secp256k1;
+use tw_memory::Data;
+use tw_number::U256;
+
+const EIP7702_TX_TYPE: u8 = 0x04;
+
+/// [EIP-7702](https://eips.ethereum.org/EIPS/eip-7702) set-code transaction.
+/// Unlike EIP1559 transactions, the destination address is required.
+pub struct TransactionEip7702 {
+    pub nonce: U256,
+    pub max_inclusion_fee_per_gas: U256,
+    pub max_fee_per_gas: U256,
+    pub gas_limit: U256,
+    pub to: Address,
+    pub amount: U256,
+    pub payload: Data,
+    pub access_list: AccessList,
+    pub authorization_list: AuthorizationList,
+}
+
+impl TransactionCommon for TransactionEip7702 {
+    #[inline]
+    fn payload(&self) -> Data {
+        self.payload.clone()
+    }
+}
+
+impl UnsignedTransaction for TransactionEip7702 {
+    type SignedTransaction = SignedTransactionEip7702;
+
+    #[inline]
+    fn encode(&self, chain_id: U256) -> Data {
+        encode_transaction(self, chain_id, None)
+    }
+
+    #[inline]
+    fn try_into_signed(
+        self,
+        signature: secp256k1::Signature,
+        chain_id: U256,
+    ) -> SigningResult<Self::SignedTransaction> {
+        Ok(SignedTransactionEip7702 {
+            unsigned: self,
+            signature: Signature::new(signature),
+            chain_id,
+        })
+    }
+}
+
+pub struct SignedTransactionEip7702 {
+    unsigned: TransactionEip7702,
+    signature: Signature,
+    chain_id: U256,
+}
+
+impl TransactionCommon for SignedTransactionEip7702 {
+    #[inline]
+    fn payload(&self) -> Data {
+        self.unsigned.payload.clone()
+    }
+}
+
+impl SignedTransaction for SignedTransactionEip7702 {
+    type Signature = Signature;
+
+    #[inline]
+    fn encode(&self) -> Data {
+        encode_transaction(&self.unsigned, self.chain_id, Some(&self.signature))
+    }
+
+    #[inline]
+    fn signature(&self) -> &Self::Signature {
+        &self.signature
+    }
+}
+
+fn encode_transaction(
+    tx: &TransactionEip7702,
+    chain_id: U256,
+    signature: Option<&Signature>,
+) -> Data {
+    let mut list = RlpList::new();
+    list.append(&chain_id)
+        .append(&tx.nonce)
+        .append(&tx.max_inclusion_fee_per_gas)
+        .append(&tx.max_fee_per_gas)
+        .append(&tx.gas_limit)
+        .append(&tx.to)
+        .append(&tx.amount)
+        .append(tx.payload.as_slice())
+        .append(&tx.access_list)
+        .append(&tx.authorization_list);
+
+    if let Some(signature) = signature {
+        list.append(&signature.v());
+        list.append(&signature.r());
+        list.append(&signature.s());
+    }
+
+    let tx_encoded = list.finish();
+
+    let mut envelope = Vec::with_capacity(tx_encoded.len() + 1);
+    envelope.push(EIP7702_TX_TYPE);
+    envelope.extend_from_slice(tx_encoded.as_slice());
+    envelope
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transaction::authorization_list::Authorization;
+    use tw_encoding::hex;
+
+    #[test]
+    fn test_encode_transaction_eip7702() {
+        let authorization = Authorization {
+            chain_id: U256::from(1_u64),
+            address: Address::from("0x63c0c19a282a1B52b07dD5a65b58948A07DAE32B"),
+            nonce: U256::from(1_u64),
+        };
+        let signature = secp256k1::Signature::from_bytes(&hex::decode("9a0abf766ca397a20ebe6f60715c6135c407095a86be6e1b998eb6a166dd3454258954de48df63a95157d8fa29712b26e33c703f06b0b84f8e9db756004d100500").unwrap()).unwrap();
+
+        let mut authorization_list = AuthorizationList::default();
+        authorization_list.add_authorization(authorization.into_signed(signature));
+
+        let tx = TransactionEip7702 {
+            nonce: U256::zero(),
+            max_inclusion_fee_per_gas: U256::from(1_000_000_000_u64),
+            max_fee_per_gas: U256::from(20_000_000_000_u64),
+            gas_limit: U256::from(100_000_u64),
+            to: Address::from("0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F"),
+            amount: U256::zero(),
+            payload: Data::default(),
+            access_list: AccessList::default(),
+            authorization_list,
+        };
+        let chain_id = U256::from(1_u64);
+        let actual = tx.encode(chain_id);
+
+        let expected = "04f8870180843b9aca008504a817c800830186a0949d8a62f656a8d1615c1294fd71e9cfb3e4855a4f8080c0f85cf85a019463c0c19a282a1b52b07dd5a65b58948a07dae32b0180a09a0abf766ca397a20ebe6f60715c6135c407095a86be6e1b998eb6a166dd3454a0258954de48df63a95157d8fa29712b26e33c703f06b0b84f8e9db756004d1005";
+        assert_eq!(hex::encode(actual, false), expected);
+    }
+}
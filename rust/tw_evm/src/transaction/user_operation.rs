@@ -6,6 +6,7 @@ use crate::abi::encode::encode_tokens;
 use crate::abi::non_empty_array::NonEmptyBytes;
 use crate::abi::token::Token;
 use crate::address::Address;
+use crate::smart_account::{SmartAccount, UserOperationRef};
 use crate::transaction::signature::Signature;
 use crate::transaction::{SignedTransaction, TransactionCommon, UnsignedTransaction};
 use serde::Serialize;
@@ -30,19 +31,13 @@ pub struct UserOperation {
     pub pre_verification_gas: U256,
     pub paymaster_and_data: Data,
     pub payload: Data,
+    /// The smart account type the user operation is sent from.
+    pub account: Box<dyn SmartAccount>,
 }
 
-impl TransactionCommon for UserOperation {
-    #[inline]
-    fn payload(&self) -> Data {
-        self.payload.clone()
-    }
-}
-
-impl UnsignedTransaction for UserOperation {
-    type SignedTransaction = SignedUserOperation;
-
-    fn pre_hash(&self, chain_id: U256) -> H256 {
+impl UserOperation {
+    /// Returns the user operation hash as computed by the EntryPoint `getUserOpHash`.
+    pub fn user_op_hash(&self, chain_id: U256) -> H256 {
         let encode_hash = keccak256(&self.encode(chain_id));
         let encode_hash =
             NonEmptyBytes::new(encode_hash).expect("keccak256 must not return an empty hash");
@@ -56,6 +51,23 @@ impl UnsignedTransaction for UserOperation {
         let pre_hash = keccak256(&encoded);
         H256::try_from(pre_hash.as_slice()).expect("keccak256 returns 32 bytes")
     }
+}
+
+impl TransactionCommon for UserOperation {
+    #[inline]
+    fn payload(&self) -> Data {
+        self.payload.clone()
+    }
+}
+
+impl UnsignedTransaction for UserOperation {
+    type SignedTransaction = SignedUserOperation;
+
+    fn pre_hash(&self, chain_id: U256) -> H256 {
+        let user_op_hash = self.user_op_hash(chain_id);
+        self.account
+            .signing_hash(UserOperationRef::V0_6(self), user_op_hash, chain_id)
+    }
 
     fn encode(&self, _chain_id: U256) -> Data {
         let init_code_hash = keccak256(&self.init_code);
@@ -115,8 +127,7 @@ impl SignedTransaction for SignedUserOperation {
     type Signature = Signature;
 
     fn encode(&self) -> Data {
-        let mut signature = self.signature.to_rsv_bytes();
-        signature[64] += 27;
+        let signature = self.unsigned.account.wrap_signature(&self.signature);
 
         let prefix = true;
         let tx = SignedUserOperationSerde {
@@ -129,7 +140,7 @@ impl SignedTransaction for SignedUserOperation {
             paymaster_and_data: hex::encode(&self.unsigned.paymaster_and_data, prefix),
             pre_verification_gas: self.unsigned.pre_verification_gas.to_string(),
             sender: self.unsigned.sender.to_string(),
-            signature: hex::encode(signature.as_slice(), prefix),
+            signature: hex::encode(&signature, prefix),
             verification_gas_limit: self.unsigned.verification_gas_limit.to_string(),
         };
         serde_json::to_string(&tx)
@@ -163,6 +174,7 @@ struct SignedUserOperationSerde {
 mod tests {
     use super::*;
     use crate::abi::prebuild::erc4337::{Erc4337SimpleAccount, ExecuteArgs};
+    use crate::smart_account::simple_account::SimpleAccount;
 
     #[test]
     fn test_encode_user_operation() {
@@ -187,6 +199,7 @@ mod tests {
             pre_verification_gas: U256::from(0xb708u64),
             paymaster_and_data: Vec::default(),
             payload,
+            account: Box::new(SimpleAccount::default()),
         };
 
         let encoded = hex::encode(user_op.encode(chain_id), false);
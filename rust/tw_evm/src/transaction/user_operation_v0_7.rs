@@ -0,0 +1,308 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::abi::encode::encode_tokens;
+use crate::abi::non_empty_array::NonEmptyBytes;
+use crate::abi::token::Token;
+use crate::address::Address;
+use crate::smart_account::{SmartAccount, UserOperationRef};
+use crate::transaction::signature::Signature;
+use crate::transaction::{SignedTransaction, TransactionCommon, UnsignedTransaction};
+use serde::Serialize;
+use tw_coin_entry::error::prelude::*;
+use tw_encoding::hex;
+use tw_hash::sha3::keccak256;
+use tw_hash::H256;
+use tw_memory::Data;
+use tw_number::U256;
+
+/// EIP4337 PackedUserOperation, EntryPoint v0.7.
+/// https://github.com/eth-infinitism/account-abstraction/blob/v0.7.0/contracts/interfaces/PackedUserOperation.sol
+pub struct PackedUserOperation {
+    pub nonce: U256,
+    pub entry_point: Address,
+    pub sender: Address,
+    pub factory: Option<Address>,
+    pub factory_data: Data,
+    pub call_gas_limit: u128,
+    pub verification_gas_limit: u128,
+    pub pre_verification_gas: U256,
+    pub max_fee_per_gas: u128,
+    pub max_priority_fee_per_gas: u128,
+    pub paymaster: Option<Address>,
+    pub paymaster_verification_gas_limit: u128,
+    pub paymaster_post_op_gas_limit: u128,
+    pub paymaster_data: Data,
+    pub payload: Data,
+    /// The smart account type the user operation is sent from.
+    pub account: Box<dyn SmartAccount>,
+}
+
+impl PackedUserOperation {
+    /// Returns the user operation hash as computed by the EntryPoint `getUserOpHash`.
+    pub fn user_op_hash(&self, chain_id: U256) -> H256 {
+        let encode_hash = keccak256(&self.encode(chain_id));
+        let encode_hash =
+            NonEmptyBytes::new(encode_hash).expect("keccak256 must not return an empty hash");
+
+        let tokens = [
+            Token::FixedBytes(encode_hash),
+            Token::Address(self.entry_point),
+            Token::u256(chain_id),
+        ];
+        let encoded = encode_tokens(&tokens);
+        let pre_hash = keccak256(&encoded);
+        H256::try_from(pre_hash.as_slice()).expect("keccak256 returns 32 bytes")
+    }
+
+    /// Returns `factory || factory_data`, or empty bytes if there is no factory.
+    pub fn init_code(&self) -> Data {
+        match self.factory {
+            Some(factory) => [factory.as_slice(), self.factory_data.as_slice()].concat(),
+            None => Data::default(),
+        }
+    }
+
+    /// Returns `verification_gas_limit (uint128) || call_gas_limit (uint128)`.
+    pub fn account_gas_limits(&self) -> H256 {
+        concat_u128_be(self.verification_gas_limit, self.call_gas_limit)
+    }
+
+    /// Returns `max_priority_fee_per_gas (uint128) || max_fee_per_gas (uint128)`.
+    pub fn gas_fees(&self) -> H256 {
+        concat_u128_be(self.max_priority_fee_per_gas, self.max_fee_per_gas)
+    }
+
+    /// Returns `paymaster || paymaster_verification_gas_limit (uint128) || paymaster_post_op_gas_limit (uint128) || paymaster_data`,
+    /// or empty bytes if there is no paymaster.
+    pub fn paymaster_and_data(&self) -> Data {
+        match self.paymaster {
+            Some(paymaster) => [
+                paymaster.as_slice(),
+                self.paymaster_verification_gas_limit
+                    .to_be_bytes()
+                    .as_slice(),
+                self.paymaster_post_op_gas_limit.to_be_bytes().as_slice(),
+                self.paymaster_data.as_slice(),
+            ]
+            .concat(),
+            None => Data::default(),
+        }
+    }
+}
+
+impl TransactionCommon for PackedUserOperation {
+    #[inline]
+    fn payload(&self) -> Data {
+        self.payload.clone()
+    }
+}
+
+impl UnsignedTransaction for PackedUserOperation {
+    type SignedTransaction = SignedPackedUserOperation;
+
+    fn pre_hash(&self, chain_id: U256) -> H256 {
+        let user_op_hash = self.user_op_hash(chain_id);
+        self.account
+            .signing_hash(UserOperationRef::V0_7(self), user_op_hash, chain_id)
+    }
+
+    fn encode(&self, _chain_id: U256) -> Data {
+        let init_code_hash = keccak256(&self.init_code());
+        let init_code_hash =
+            NonEmptyBytes::new(init_code_hash).expect("keccak256 must not return an empty hash");
+
+        let payload_hash = keccak256(&self.payload);
+        let payload_hash =
+            NonEmptyBytes::new(payload_hash).expect("keccak256 must not return an empty hash");
+
+        let account_gas_limits =
+            NonEmptyBytes::new(self.account_gas_limits().to_vec()).expect("H256 must not be empty");
+        let gas_fees =
+            NonEmptyBytes::new(self.gas_fees().to_vec()).expect("H256 must not be empty");
+
+        let paymaster_and_data_hash = keccak256(&self.paymaster_and_data());
+        let paymaster_and_data_hash = NonEmptyBytes::new(paymaster_and_data_hash)
+            .expect("keccak256 must not return an empty hash");
+
+        let tokens = [
+            Token::Address(self.sender),
+            Token::u256(self.nonce),
+            Token::FixedBytes(init_code_hash),
+            Token::FixedBytes(payload_hash),
+            Token::FixedBytes(account_gas_limits),
+            Token::u256(self.pre_verification_gas),
+            Token::FixedBytes(gas_fees),
+            Token::FixedBytes(paymaster_and_data_hash),
+        ];
+
+        encode_tokens(&tokens)
+    }
+
+    #[inline]
+    fn try_into_signed(
+        self,
+        signature: tw_keypair::ecdsa::secp256k1::Signature,
+        _chain_id: U256,
+    ) -> SigningResult<Self::SignedTransaction> {
+        Ok(SignedPackedUserOperation {
+            unsigned: self,
+            signature: Signature::new(signature),
+        })
+    }
+}
+
+pub struct SignedPackedUserOperation {
+    unsigned: PackedUserOperation,
+    signature: Signature,
+}
+
+impl TransactionCommon for SignedPackedUserOperation {
+    #[inline]
+    fn payload(&self) -> Data {
+        self.unsigned.payload.clone()
+    }
+}
+
+impl SignedTransaction for SignedPackedUserOperation {
+    type Signature = Signature;
+
+    fn encode(&self) -> Data {
+        let signature = self.unsigned.account.wrap_signature(&self.signature);
+
+        let prefix = true;
+        let unsigned = &self.unsigned;
+
+        // Factory and paymaster fields are omitted if not set.
+        let factory = unsigned.factory.map(|factory| FactorySerde {
+            factory: factory.to_string(),
+            factory_data: hex::encode(&unsigned.factory_data, prefix),
+        });
+        let paymaster = unsigned.paymaster.map(|paymaster| PaymasterSerde {
+            paymaster: paymaster.to_string(),
+            paymaster_verification_gas_limit: unsigned.paymaster_verification_gas_limit.to_string(),
+            paymaster_post_op_gas_limit: unsigned.paymaster_post_op_gas_limit.to_string(),
+            paymaster_data: hex::encode(&unsigned.paymaster_data, prefix),
+        });
+
+        let tx = SignedPackedUserOperationSerde {
+            sender: unsigned.sender.to_string(),
+            nonce: unsigned.nonce.to_string(),
+            factory,
+            call_data: hex::encode(&unsigned.payload, prefix),
+            call_gas_limit: unsigned.call_gas_limit.to_string(),
+            verification_gas_limit: unsigned.verification_gas_limit.to_string(),
+            pre_verification_gas: unsigned.pre_verification_gas.to_string(),
+            max_fee_per_gas: unsigned.max_fee_per_gas.to_string(),
+            max_priority_fee_per_gas: unsigned.max_priority_fee_per_gas.to_string(),
+            paymaster,
+            signature: hex::encode(&signature, prefix),
+        };
+        serde_json::to_string(&tx)
+            .expect("Simple structure should never fail on serialization")
+            .into_bytes()
+    }
+
+    #[inline]
+    fn signature(&self) -> &Self::Signature {
+        &self.signature
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FactorySerde {
+    factory: String,
+    factory_data: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PaymasterSerde {
+    paymaster: String,
+    paymaster_verification_gas_limit: String,
+    paymaster_post_op_gas_limit: String,
+    paymaster_data: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SignedPackedUserOperationSerde {
+    sender: String,
+    nonce: String,
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    factory: Option<FactorySerde>,
+    call_data: String,
+    call_gas_limit: String,
+    verification_gas_limit: String,
+    pre_verification_gas: String,
+    max_fee_per_gas: String,
+    max_priority_fee_per_gas: String,
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    paymaster: Option<PaymasterSerde>,
+    signature: String,
+}
+
+fn concat_u128_be(high: u128, low: u128) -> H256 {
+    let mut res = H256::default();
+    res[..16].copy_from_slice(&high.to_be_bytes());
+    res[16..].copy_from_slice(&low.to_be_bytes());
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abi::prebuild::erc4337::{Erc4337SimpleAccount, ExecuteArgs};
+    use crate::smart_account::simple_account::SimpleAccount;
+
+    #[test]
+    fn test_encode_packed_user_operation() {
+        let chain_id = U256::from(97u64);
+
+        let execute_args = ExecuteArgs {
+            to: Address::from("0x61061fCAE11fD5461535e134EfF67A98CFFF44E9"),
+            value: U256::from(0x23_86f2_6fc1_0000_u64),
+            data: Vec::default(),
+        };
+        let payload = Erc4337SimpleAccount::encode_execute(execute_args).unwrap();
+
+        let user_op = PackedUserOperation {
+            nonce: U256::from(0u64),
+            entry_point: Address::from("0x0000000071727De22E5E9d8BAf0edAc6f37da032"),
+            sender: Address::from("0x1392Ae041BfBdBAA0cFF9234a0C8F64df97B7218"),
+            factory: Some(Address::from("0x3fC708630d85A3B5ec217E53100eC2b735d4f800")),
+            factory_data: hex::decode("0x296601cd0000000000000000000000005034534efe9902779ed6ea6983f435c00f3bc51000000000000000000000000000000000000000000000000000000000000000600000000000000000000000000000000000000000000000000000000000000000").unwrap(),
+            call_gas_limit: 0x186a0,
+            verification_gas_limit: 0x2d_c6c0,
+            pre_verification_gas: U256::from(0xb708u64),
+            max_fee_per_gas: 0x1_a339_c9e9,
+            max_priority_fee_per_gas: 0x1_a339_c9e9,
+            paymaster: Some(Address::from("0xb0086171AC7b6BD4D046580bca6d6A4b0835c232")),
+            paymaster_verification_gas_limit: 0x1_86a0,
+            paymaster_post_op_gas_limit: 0xc350,
+            paymaster_data: hex::decode("0x00000000000000000000000000000000000000000000000000000000deadbeef").unwrap(),
+            payload,
+            account: Box::new(SimpleAccount::default()),
+        };
+
+        assert_eq!(
+            user_op.account_gas_limits(),
+            H256::from("000000000000000000000000002dc6c0000000000000000000000000000186a0")
+        );
+        assert_eq!(
+            user_op.gas_fees(),
+            H256::from("000000000000000000000001a339c9e9000000000000000000000001a339c9e9")
+        );
+        assert_eq!(
+            hex::encode(user_op.paymaster_and_data(), false),
+            "b0086171ac7b6bd4d046580bca6d6a4b0835c232000000000000000000000000000186a00000000000000000000000000000c35000000000000000000000000000000000000000000000000000000000deadbeef"
+        );
+
+        let pre_hash = user_op.pre_hash(chain_id);
+        let expected_pre_hash =
+            H256::from("0ed6e9032f88531cda3f0a8a557adbe70417b397c26b79239293aa3d72c5b220");
+        assert_eq!(pre_hash, expected_pre_hash);
+    }
+}
@@ -17,6 +17,7 @@ use Proto::mod_Token::OneOftoken as TokenEnum;
 
 const SWAP_V2_ABI: &str = include_str!("data/swap_v2.json");
 const SWAP_V2_DECODED: &str = include_str!("data/swap_v2_decoded.json");
+const EIP712_CASE_1: &str = include_str!("data/eip712_case_1.json");
 
 fn param(name: &str, kind: ParamTypeEnum<'static>) -> Proto::Param<'static> {
     Proto::Param {
@@ -685,3 +686,79 @@ fn test_decode_contract_call_error() {
     assert_eq!(output.error, AbiErrorKind::Error_abi_mismatch);
     assert!(!output.error_message.is_empty());
 }
+
+#[test]
+fn test_encode_packed() {
+    let input = Proto::PackedEncodingInput {
+        tokens: vec![
+            named_token(
+                "",
+                TokenEnum::address("0x1111111111111111111111111111111111111111".into()),
+            ),
+            named_token("", u_number_n::<16>(3)),
+            named_token("", TokenEnum::string_value("Hello, world!".into())),
+        ],
+    };
+
+    let output = AbiEncoder::<StandardEvmContext>::encode_packed(input);
+    assert_eq!(output.error, AbiErrorKind::OK);
+    assert_eq!(
+        output.encoded.to_hex(),
+        "1111111111111111111111111111111111111111000348656c6c6f2c20776f726c6421"
+    );
+}
+
+#[test]
+fn test_encode_packed_tuple_unsupported() {
+    let tuple = TokenEnum::tuple(Proto::TupleParam {
+        params: vec![named_token("", TokenEnum::boolean(true))],
+    });
+    let input = Proto::PackedEncodingInput {
+        tokens: vec![named_token("", tuple)],
+    };
+
+    let output = AbiEncoder::<StandardEvmContext>::encode_packed(input);
+    assert_eq!(output.error, AbiErrorKind::Error_invalid_param_type);
+    assert!(output.encoded.is_empty());
+}
+
+#[test]
+fn test_hash_typed_data() {
+    let input = Proto::TypedDataHashingInput {
+        message_json: EIP712_CASE_1.into(),
+    };
+
+    let output = AbiEncoder::<StandardEvmContext>::hash_typed_data(input);
+    assert_eq!(output.error, AbiErrorKind::OK);
+    assert_eq!(
+        output.domain_separator.to_hex(),
+        "f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f"
+    );
+    assert_eq!(
+        output.message_hash.to_hex(),
+        "c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e"
+    );
+    assert_eq!(
+        output.signing_hash.to_hex(),
+        "be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"
+    );
+}
+
+#[test]
+fn test_hash_typed_data_no_domain() {
+    let message_json = json!({
+        "types": {
+            "Person": [{ "name": "name", "type": "string" }]
+        },
+        "primaryType": "Person",
+        "domain": {},
+        "message": { "name": "Cow" }
+    });
+    let input = Proto::TypedDataHashingInput {
+        message_json: message_json.to_string().into(),
+    };
+
+    let output = AbiEncoder::<StandardEvmContext>::hash_typed_data(input);
+    assert_eq!(output.error, AbiErrorKind::Error_abi_mismatch);
+    assert!(output.signing_hash.is_empty());
+}
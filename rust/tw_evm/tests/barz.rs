@@ -29,6 +29,7 @@ fn test_barz_transfer_account_deployed() {
         pre_verification_gas: U256::encode_be_compact(0xb708),
         verification_gas_limit: U256::encode_be_compact(0x186a0),
         paymaster_and_data: Cow::default(),
+        account: None,
     };
 
     let input = Proto::SigningInput {
@@ -43,7 +44,9 @@ fn test_barz_transfer_account_deployed() {
         transaction: Some(Proto::Transaction {
             transaction_oneof: Proto::mod_Transaction::OneOftransaction_oneof::transfer(transfer),
         }),
-        user_operation: Some(user_op),
+        user_operation_oneof: Proto::mod_SigningInput::OneOfuser_operation_oneof::user_operation(
+            user_op,
+        ),
         ..Proto::SigningInput::default()
     };
 
@@ -82,6 +85,7 @@ fn test_barz_transfer_account_not_deployed() {
         pre_verification_gas: U256::encode_be_compact(0xb708),
         verification_gas_limit: U256::encode_be_compact(0x2D_C6C0),
         paymaster_and_data: Cow::default(),
+        account: None,
     };
 
     let input = Proto::SigningInput {
@@ -96,7 +100,9 @@ fn test_barz_transfer_account_not_deployed() {
         transaction: Some(Proto::Transaction {
             transaction_oneof: Proto::mod_Transaction::OneOftransaction_oneof::transfer(transfer),
         }),
-        user_operation: Some(user_op),
+        user_operation_oneof: Proto::mod_SigningInput::OneOfuser_operation_oneof::user_operation(
+            user_op,
+        ),
         ..Proto::SigningInput::default()
     };
 
@@ -157,6 +163,7 @@ fn test_barz_batched_account_deployed() {
         pre_verification_gas: U256::encode_be_compact(0xDAFC),
         verification_gas_limit: U256::encode_be_compact(0x07_F7C4),
         paymaster_and_data: Cow::default(),
+        account: None,
     };
 
     let input = Proto::SigningInput {
@@ -173,7 +180,9 @@ fn test_barz_batched_account_deployed() {
                 Proto::mod_Transaction::Batch { calls },
             ),
         }),
-        user_operation: Some(user_op),
+        user_operation_oneof: Proto::mod_SigningInput::OneOfuser_operation_oneof::user_operation(
+            user_op,
+        ),
         ..Proto::SigningInput::default()
     };
 
@@ -190,3 +199,152 @@ fn test_barz_batched_account_deployed() {
         "84d0464f5a2b191e06295443970ecdcd2d18f565d0d52b5a79443192153770ab"
     );
 }
+
+#[test]
+fn test_barz_transfer_account_deployed_v0_7() {
+    let private_key =
+        hex::decode("0x3c90badc15c4d35733769093d3733501e92e7f16e101df284cee9a310d36c483").unwrap();
+
+    let transfer = Proto::mod_Transaction::Transfer {
+        amount: U256::encode_be_compact(0x23_86f2_6fc1_0000),
+        data: Cow::default(),
+    };
+    let user_op = Proto::UserOperationV0_7 {
+        entry_point: "0x0000000071727De22E5E9d8BAf0edAc6f37da032".into(),
+        sender: "0xb16Db98B365B1f89191996942612B14F1Da4Bd5f".into(),
+        pre_verification_gas: U256::encode_be_compact(0xb708),
+        verification_gas_limit: U256::encode_be_compact(0x186a0),
+        ..Proto::UserOperationV0_7::default()
+    };
+
+    let input = Proto::SigningInput {
+        chain_id: U256::encode_be_compact(97),
+        nonce: U256::encode_be_compact(2),
+        tx_mode: Proto::TransactionMode::UserOp,
+        gas_limit: U256::encode_be_compact(0x186A0),
+        max_fee_per_gas: U256::encode_be_compact(0x1_a339_c9e9),
+        max_inclusion_fee_per_gas: U256::encode_be_compact(0x1_a339_c9e9),
+        to_address: "0x61061fCAE11fD5461535e134EfF67A98CFFF44E9".into(),
+        private_key: private_key.into(),
+        transaction: Some(Proto::Transaction {
+            transaction_oneof: Proto::mod_Transaction::OneOftransaction_oneof::transfer(transfer),
+        }),
+        user_operation_oneof:
+            Proto::mod_SigningInput::OneOfuser_operation_oneof::user_operation_v0_7(user_op),
+        ..Proto::SigningInput::default()
+    };
+
+    let output = Signer::<StandardEvmContext>::sign_proto(input);
+    assert_eq!(output.error, SigningErrorType::OK);
+    assert!(output.error_message.is_empty());
+
+    // Factory and paymaster fields are omitted.
+    let expected = r#"{"sender":"0xb16Db98B365B1f89191996942612B14F1Da4Bd5f","nonce":"2","callData":"0xb61d27f600000000000000000000000061061fcae11fd5461535e134eff67a98cfff44e9000000000000000000000000000000000000000000000000002386f26fc1000000000000000000000000000000000000000000000000000000000000000000600000000000000000000000000000000000000000000000000000000000000000","callGasLimit":"100000","verificationGasLimit":"100000","preVerificationGas":"46856","maxFeePerGas":"7033440745","maxPriorityFeePerGas":"7033440745","signature":"0x2725549f624f0cf7f78c96e1f20d92b8926246e8ee8bdd8d38312ee0f2451417150368739f69049f849f0098dd401505d7368bb3b47b309f13d42d3fcd8e04541c"}"#;
+    let actual = String::from_utf8(output.encoded.to_vec()).unwrap();
+    assert_eq!(actual, expected);
+
+    assert_eq!(
+        hex::encode(output.pre_hash, false),
+        "eb139097200394126d3c9a8623f199aa735d360ad78254b4278419f3501d99be"
+    );
+}
+
+#[test]
+fn test_barz_transfer_account_not_deployed_with_paymaster_v0_7() {
+    let private_key =
+        hex::decode("0x3c90badc15c4d35733769093d3733501e92e7f16e101df284cee9a310d36c483").unwrap();
+
+    let factory_data = hex::decode("0x296601cd0000000000000000000000005034534efe9902779ed6ea6983f435c00f3bc51000000000000000000000000000000000000000000000000000000000000000600000000000000000000000000000000000000000000000000000000000000000").unwrap();
+    let paymaster_data =
+        hex::decode("0x00000000000000000000000000000000000000000000000000000000deadbeef").unwrap();
+
+    let transfer = Proto::mod_Transaction::Transfer {
+        amount: U256::encode_be_compact(0x23_86f2_6fc1_0000),
+        data: Cow::default(),
+    };
+    let user_op = Proto::UserOperationV0_7 {
+        entry_point: "0x0000000071727De22E5E9d8BAf0edAc6f37da032".into(),
+        sender: "0x1392Ae041BfBdBAA0cFF9234a0C8F64df97B7218".into(),
+        factory: "0x3fC708630d85A3B5ec217E53100eC2b735d4f800".into(),
+        factory_data: factory_data.into(),
+        pre_verification_gas: U256::encode_be_compact(0xb708),
+        verification_gas_limit: U256::encode_be_compact(0x2D_C6C0),
+        paymaster: "0xb0086171AC7b6BD4D046580bca6d6A4b0835c232".into(),
+        paymaster_verification_gas_limit: U256::encode_be_compact(0x1_86a0),
+        paymaster_post_op_gas_limit: U256::encode_be_compact(0xc350),
+        paymaster_data: paymaster_data.into(),
+        account: None,
+    };
+
+    let input = Proto::SigningInput {
+        chain_id: U256::encode_be_compact(97),
+        nonce: U256::encode_be_compact(0),
+        tx_mode: Proto::TransactionMode::UserOp,
+        gas_limit: U256::encode_be_compact(0x186A0),
+        max_fee_per_gas: U256::encode_be_compact(0x1_a339_c9e9),
+        max_inclusion_fee_per_gas: U256::encode_be_compact(0x1_a339_c9e9),
+        to_address: "0x61061fCAE11fD5461535e134EfF67A98CFFF44E9".into(),
+        private_key: private_key.into(),
+        transaction: Some(Proto::Transaction {
+            transaction_oneof: Proto::mod_Transaction::OneOftransaction_oneof::transfer(transfer),
+        }),
+        user_operation_oneof:
+            Proto::mod_SigningInput::OneOfuser_operation_oneof::user_operation_v0_7(user_op),
+        ..Proto::SigningInput::default()
+    };
+
+    let output = Signer::<StandardEvmContext>::sign_proto(input);
+    assert_eq!(output.error, SigningErrorType::OK);
+    assert!(output.error_message.is_empty());
+
+    let expected = r#"{"sender":"0x1392Ae041BfBdBAA0cFF9234a0C8F64df97B7218","nonce":"0","factory":"0x3fC708630d85A3B5ec217E53100eC2b735d4f800","factoryData":"0x296601cd0000000000000000000000005034534efe9902779ed6ea6983f435c00f3bc51000000000000000000000000000000000000000000000000000000000000000600000000000000000000000000000000000000000000000000000000000000000","callData":"0xb61d27f600000000000000000000000061061fcae11fd5461535e134eff67a98cfff44e9000000000000000000000000000000000000000000000000002386f26fc1000000000000000000000000000000000000000000000000000000000000000000600000000000000000000000000000000000000000000000000000000000000000","callGasLimit":"100000","verificationGasLimit":"3000000","preVerificationGas":"46856","maxFeePerGas":"7033440745","maxPriorityFeePerGas":"7033440745","paymaster":"0xb0086171AC7b6BD4D046580bca6d6A4b0835c232","paymasterVerificationGasLimit":"100000","paymasterPostOpGasLimit":"50000","paymasterData":"0x00000000000000000000000000000000000000000000000000000000deadbeef","signature":"0xd49ec40b4b86e64c7e2f9bed05697df5793df0d191cf012ca7dd713d1cb868793b40343545b62e8fa00f46e17b278a47a960dfbe6a8884f773c2a2ca7207c0dd1b"}"#;
+    let actual = String::from_utf8(output.encoded.to_vec()).unwrap();
+    assert_eq!(actual, expected);
+
+    assert_eq!(
+        hex::encode(output.pre_hash, false),
+        "0ed6e9032f88531cda3f0a8a557adbe70417b397c26b79239293aa3d72c5b220"
+    );
+}
+
+#[test]
+fn test_barz_transfer_v0_7_gas_limit_overflow() {
+    let transfer = Proto::mod_Transaction::Transfer {
+        amount: U256::encode_be_compact(0x23_86f2_6fc1_0000),
+        data: Cow::default(),
+    };
+    let user_op = Proto::UserOperationV0_7 {
+        entry_point: "0x0000000071727De22E5E9d8BAf0edAc6f37da032".into(),
+        sender: "0xb16Db98B365B1f89191996942612B14F1Da4Bd5f".into(),
+        pre_verification_gas: U256::encode_be_compact(0xb708),
+        // Doesn't fit into uint128.
+        verification_gas_limit: hex::decode("0x0100000000000000000000000000000000")
+            .unwrap()
+            .into(),
+        ..Proto::UserOperationV0_7::default()
+    };
+
+    let input = Proto::SigningInput {
+        chain_id: U256::encode_be_compact(97),
+        nonce: U256::encode_be_compact(2),
+        tx_mode: Proto::TransactionMode::UserOp,
+        gas_limit: U256::encode_be_compact(0x186A0),
+        max_fee_per_gas: U256::encode_be_compact(0x1_a339_c9e9),
+        max_inclusion_fee_per_gas: U256::encode_be_compact(0x1_a339_c9e9),
+        to_address: "0x61061fCAE11fD5461535e134EfF67A98CFFF44E9".into(),
+        private_key: hex::decode(
+            "0x3c90badc15c4d35733769093d3733501e92e7f16e101df284cee9a310d36c483",
+        )
+        .unwrap()
+        .into(),
+        transaction: Some(Proto::Transaction {
+            transaction_oneof: Proto::mod_Transaction::OneOftransaction_oneof::transfer(transfer),
+        }),
+        user_operation_oneof:
+            Proto::mod_SigningInput::OneOfuser_operation_oneof::user_operation_v0_7(user_op),
+        ..Proto::SigningInput::default()
+    };
+
+    let output = Signer::<StandardEvmContext>::sign_proto(input);
+    assert_eq!(output.error, SigningErrorType::Error_invalid_params);
+}
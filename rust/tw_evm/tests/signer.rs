@@ -7,6 +7,8 @@ use tw_coin_entry::error::prelude::*;
 use tw_encoding::hex::{self, ToHex};
 use tw_evm::evm_context::StandardEvmContext;
 use tw_evm::modules::signer::Signer;
+use tw_evm::transaction::blob::BYTES_PER_BLOB;
+use tw_hash::sha2::sha256;
 use tw_number::U256;
 use tw_proto::Ethereum::Proto;
 use tw_proto::Ethereum::Proto::TransactionMode;
@@ -344,6 +346,233 @@ fn test_sign_transaction_eip1559_erc20_transfer_with_access_list() {
     );
 }
 
+#[test]
+fn test_sign_transaction_eip2930_erc20_transfer_with_access_list() {
+    // 0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F
+    let private_key =
+        parse_hex("0x4646464646464646464646464646464646464646464646464646464646464646");
+
+    let erc20_transfer = Proto::mod_Transaction::ERC20Transfer {
+        to: "0x5322b34c88ed0691971bf52a7047448f0f4efc84".into(),
+        amount: U256::encode_be_compact(1_000_000),
+    };
+
+    let input = Proto::SigningInput {
+        chain_id: U256::encode_be_compact(1),
+        nonce: U256::encode_be_compact(3),
+        tx_mode: TransactionMode::AccessList,
+        // 0x4A817C800
+        gas_price: U256::encode_be_compact(20_000_000_000),
+        // 0xEA60
+        gas_limit: U256::encode_be_compact(60_000),
+        // USDT
+        to_address: "0xdAC17F958D2ee523a2206206994597C13D831ec7".into(),
+        transaction: Some(Proto::Transaction {
+            transaction_oneof: Proto::mod_Transaction::OneOftransaction_oneof::erc20_transfer(
+                erc20_transfer,
+            ),
+        }),
+        private_key,
+        access_list: vec![Proto::Access {
+            address: "0xdAC17F958D2ee523a2206206994597C13D831ec7".into(),
+            stored_keys: vec![
+                parse_hex("0x0000000000000000000000000000000000000000000000000000000000000003"),
+                parse_hex("0x0000000000000000000000000000000000000000000000000000000000000004"),
+            ],
+        }],
+        ..Proto::SigningInput::default()
+    };
+
+    let output = Signer::<StandardEvmContext>::sign_proto(input);
+    assert_eq!(output.error, SigningErrorType::OK);
+    assert!(output.error_message.is_empty());
+
+    let expected = "01f9010701038504a817c80082ea6094dac17f958d2ee523a2206206994597c13d831ec780b844a9059cbb0000000000000000000000005322b34c88ed0691971bf52a7047448f0f4efc8400000000000000000000000000000000000000000000000000000000000f4240f85bf85994dac17f958d2ee523a2206206994597c13d831ec7f842a00000000000000000000000000000000000000000000000000000000000000003a0000000000000000000000000000000000000000000000000000000000000000401a03e2544c84504d8e5f284387710203c9b3d66f84ea751d8b5ffa3840898b7d87ba05c93e38ef86ae72d1cf2da37240240c19e643d2e50316d26466c38a6d596b87b";
+    assert_eq!(hex::encode(output.encoded, false), expected);
+    assert_eq!(hex::encode(output.v, false), "01");
+
+    assert_eq!(
+        hex::encode(output.pre_hash, false),
+        "70a0c578edab024b126e745c7a1c7305da83c3843f9f8ed6aa849e531ec9033b"
+    );
+}
+
+#[test]
+fn test_sign_transaction_eip7702_set_code() {
+    // 0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F
+    let private_key =
+        parse_hex("0x4646464646464646464646464646464646464646464646464646464646464646");
+
+    let transfer = Proto::mod_Transaction::Transfer {
+        amount: U256::encode_be_compact(0),
+        data: Cow::default(),
+    };
+
+    let input = Proto::SigningInput {
+        chain_id: U256::encode_be_compact(1),
+        nonce: U256::encode_be_compact(0),
+        tx_mode: TransactionMode::SetCode,
+        max_inclusion_fee_per_gas: U256::encode_be_compact(1_000_000_000),
+        max_fee_per_gas: U256::encode_be_compact(20_000_000_000),
+        gas_limit: U256::encode_be_compact(100_000),
+        to_address: "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F".into(),
+        transaction: Some(Proto::Transaction {
+            transaction_oneof: Proto::mod_Transaction::OneOftransaction_oneof::transfer(transfer),
+        }),
+        private_key,
+        // The authority is the transaction sender, so the authorization nonce is the transaction nonce + 1.
+        // The authorization is signed with the `private_key`.
+        authorization_list: vec![Proto::Authorization {
+            chain_id: U256::encode_be_compact(1),
+            address: "0x63c0c19a282a1B52b07dD5a65b58948A07DAE32B".into(),
+            nonce: U256::encode_be_compact(1),
+            ..Proto::Authorization::default()
+        }],
+        ..Proto::SigningInput::default()
+    };
+
+    let output = Signer::<StandardEvmContext>::sign_proto(input);
+    assert_eq!(output.error, SigningErrorType::OK);
+    assert!(output.error_message.is_empty());
+
+    let expected = "04f8ca0180843b9aca008504a817c800830186a0949d8a62f656a8d1615c1294fd71e9cfb3e4855a4f8080c0f85cf85a019463c0c19a282a1b52b07dd5a65b58948a07dae32b0180a09a0abf766ca397a20ebe6f60715c6135c407095a86be6e1b998eb6a166dd3454a0258954de48df63a95157d8fa29712b26e33c703f06b0b84f8e9db756004d100580a0ad2a6e7dfefb0c11de98386b3b66cda2c6d13d9a8f61fc13c94dc3c6f2c319c3a012f07c41fe837fc1f33fe5f963fe9a0870832c2832a926d21119ac60842cb684";
+    assert_eq!(hex::encode(output.encoded, false), expected);
+    assert_eq!(hex::encode(output.v, false), "00");
+
+    assert_eq!(
+        hex::encode(output.pre_hash, false),
+        "2296000d573cb5b83b476d85037acf2a9d8ea5ae1011932e4ee1ef4f8cf7fab2"
+    );
+}
+
+#[test]
+fn test_sign_transaction_eip7702_no_authorizations() {
+    let private_key =
+        parse_hex("0x4646464646464646464646464646464646464646464646464646464646464646");
+
+    let transfer = Proto::mod_Transaction::Transfer {
+        amount: U256::encode_be_compact(0),
+        data: Cow::default(),
+    };
+
+    let input = Proto::SigningInput {
+        chain_id: U256::encode_be_compact(1),
+        nonce: U256::encode_be_compact(0),
+        tx_mode: TransactionMode::SetCode,
+        max_inclusion_fee_per_gas: U256::encode_be_compact(1_000_000_000),
+        max_fee_per_gas: U256::encode_be_compact(20_000_000_000),
+        gas_limit: U256::encode_be_compact(100_000),
+        to_address: "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F".into(),
+        transaction: Some(Proto::Transaction {
+            transaction_oneof: Proto::mod_Transaction::OneOftransaction_oneof::transfer(transfer),
+        }),
+        private_key,
+        ..Proto::SigningInput::default()
+    };
+
+    let output = Signer::<StandardEvmContext>::sign_proto(input);
+    assert_eq!(output.error, SigningErrorType::Error_invalid_params);
+}
+
+#[test]
+fn test_sign_transaction_eip4844_blob() {
+    // 0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F
+    let private_key =
+        parse_hex("0x4646464646464646464646464646464646464646464646464646464646464646");
+
+    let transfer = Proto::mod_Transaction::Transfer {
+        amount: U256::encode_be_compact(0),
+        data: Cow::default(),
+    };
+
+    // The commitment and proof of an empty blob.
+    let commitment = parse_hex("0xc00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000");
+
+    let input = Proto::SigningInput {
+        chain_id: U256::encode_be_compact(1),
+        nonce: U256::encode_be_compact(0),
+        tx_mode: TransactionMode::Blob,
+        max_inclusion_fee_per_gas: U256::encode_be_compact(1_000_000_000),
+        max_fee_per_gas: U256::encode_be_compact(20_000_000_000),
+        gas_limit: U256::encode_be_compact(21_000),
+        to_address: "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F".into(),
+        transaction: Some(Proto::Transaction {
+            transaction_oneof: Proto::mod_Transaction::OneOftransaction_oneof::transfer(transfer),
+        }),
+        private_key,
+        max_fee_per_blob_gas: U256::encode_be_compact(1_000_000_000),
+        // Blob versioned hashes are computed from the commitments.
+        blob_sidecar: Some(Proto::BlobSidecar {
+            blobs: vec![vec![0; BYTES_PER_BLOB].into()],
+            commitments: vec![commitment.clone()],
+            proofs: vec![commitment],
+        }),
+        ..Proto::SigningInput::default()
+    };
+
+    let output = Signer::<StandardEvmContext>::sign_proto(input);
+    assert_eq!(output.error, SigningErrorType::OK);
+    assert!(output.error_message.is_empty());
+
+    let expected = "03f8920180843b9aca008504a817c800825208949d8a62f656a8d1615c1294fd71e9cfb3e4855a4f8080c0843b9aca00e1a0010657f37554c781402a22917dee2f75def7ab966d7b770905398eba3c44401401a0ba0f2837d74d1438a4dd8fef76f7e42fc7f4262d1efc0bf28d39ef17d41b5b88a0232ae1b976e6d80bfc2ebb2d8dfcd2e98030ed90b50c809e868f9fa9b5ce1d4e";
+    assert_eq!(hex::encode(output.encoded, false), expected);
+    assert_eq!(hex::encode(output.v, false), "01");
+
+    assert_eq!(
+        hex::encode(output.pre_hash, false),
+        "7a087cd5b98579cbd31bb1f446368df261f394ee1e3e3bca61cece1d190a120d"
+    );
+
+    // The network wrapper contains the signed transaction, the blob, its commitment and proof.
+    assert_eq!(output.encoded_network_wrapper.len(), 131_333);
+    let expected_network_wrapper_hash =
+        "e59fc7e8d153394d50a179545442c51e232758ae79c0e3e2a20a24d6d2503522";
+    assert_eq!(
+        sha256(&output.encoded_network_wrapper).to_hex(),
+        expected_network_wrapper_hash
+    );
+}
+
+#[test]
+fn test_sign_transaction_eip4844_versioned_hash_mismatch() {
+    let private_key =
+        parse_hex("0x4646464646464646464646464646464646464646464646464646464646464646");
+
+    let transfer = Proto::mod_Transaction::Transfer {
+        amount: U256::encode_be_compact(0),
+        data: Cow::default(),
+    };
+
+    let commitment = parse_hex("0xc00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000");
+
+    let input = Proto::SigningInput {
+        chain_id: U256::encode_be_compact(1),
+        nonce: U256::encode_be_compact(0),
+        tx_mode: TransactionMode::Blob,
+        max_inclusion_fee_per_gas: U256::encode_be_compact(1_000_000_000),
+        max_fee_per_gas: U256::encode_be_compact(20_000_000_000),
+        gas_limit: U256::encode_be_compact(21_000),
+        to_address: "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F".into(),
+        transaction: Some(Proto::Transaction {
+            transaction_oneof: Proto::mod_Transaction::OneOftransaction_oneof::transfer(transfer),
+        }),
+        private_key,
+        max_fee_per_blob_gas: U256::encode_be_compact(1_000_000_000),
+        blob_versioned_hashes: vec![parse_hex(
+            "0x0100000000000000000000000000000000000000000000000000000000000000",
+        )],
+        blob_sidecar: Some(Proto::BlobSidecar {
+            blobs: vec![vec![0; BYTES_PER_BLOB].into()],
+            commitments: vec![commitment.clone()],
+            proofs: vec![commitment],
+        }),
+        ..Proto::SigningInput::default()
+    };
+
+    let output = Signer::<StandardEvmContext>::sign_proto(input);
+    assert_eq!(output.error, SigningErrorType::Error_invalid_params);
+}
+
 #[test]
 fn test_sign_transaction_eip1559_erc20_approve() {
     let private_key =
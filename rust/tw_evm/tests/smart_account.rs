@@ -0,0 +1,395 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use serde_json::Value as Json;
+use std::borrow::Cow;
+use tw_coin_entry::error::prelude::*;
+use tw_encoding::hex;
+use tw_evm::abi::prebuild::erc20::Erc20;
+use tw_evm::address::Address;
+use tw_evm::evm_context::StandardEvmContext;
+use tw_evm::modules::signer::Signer;
+use tw_evm::modules::smart_account_address::SmartAccountAddress;
+use tw_number::U256;
+use tw_proto::Ethereum::Proto;
+
+const PRIVATE_KEY: &str = "0x4646464646464646464646464646464646464646464646464646464646464646";
+const OWNER: &str = "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F";
+const ENTRY_POINT_V0_6: &str = "0x5FF137D4b0FDCD49DcA30c7CF57E578a026d2789";
+const ENTRY_POINT_V0_7: &str = "0x0000000071727De22E5E9d8BAf0edAc6f37da032";
+const RECIPIENT: &str = "0x61061fCAE11fD5461535e134EfF67A98CFFF44E9";
+const RECIPIENT_2: &str = "0x3fC91A3afd70395Cd496C647d5a6CC9D4B2b7FAD";
+const USDC: &str = "0x1c7D4B196Cb0C7B01d743Fbc6116a902379C7238";
+/// A dummy account proxy creation code.
+const PROXY_BYTECODE: &str = "0x6080604052348015600f57600080fd5b50";
+
+const SAFE_4337_MODULE_V0_2_0: &str = "0xa581c4A4DB7175302464fF3C06380BC3270b4037";
+const SAFE_4337_MODULE_V0_3_0: &str = "0x75cf11467937ce3F2f357CE24ffc3DBF8fD5c226";
+const SAFE_MODULE_SETUP: &str = "0x2dd68b007B46fBe91B9A7c3EDa5A7a1063cB5b47";
+const SAFE_SINGLETON: &str = "0x29fcB43b46531BcA003ddC8FCB67FFE91900C762";
+const SAFE_PROXY_FACTORY: &str = "0x4e1DCf7AD4e460CfD30791CCC4F9c8a4f820ec67";
+
+const KERNEL_ECDSA_VALIDATOR: &str = "0x845ADb2C711129d4f3966735eD98a9F09fC4cE57";
+const KERNEL_FACTORY: &str = "0xaac5D4240AF87249B3f71BC8E4A2cae074A3E419";
+
+const LIGHT_ACCOUNT_FACTORY: &str = "0x0000000000400CdFef5E2714E63d8040b700BC24";
+const LIGHT_ACCOUNT_IMPLEMENTATION: &str = "0x8E8e658E22B12ada97B402fF0b044D6A325013C7";
+/// Solady `LibClone.initCodeERC1967(LIGHT_ACCOUNT_IMPLEMENTATION)`, i.e. the account proxy init code
+/// deployed by `LightAccountFactory` v2.0.0.
+const LIGHT_ACCOUNT_PROXY_BYTECODE: &str = "0x603d3d8160223d39738e8e658e22b12ada97b402ff0b044d6a325013c760095155f3363d3d373d3d363d7f360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc545af43d6000803e6038573d6000fd5b3d6000f3";
+
+const SIMPLE_ACCOUNT_FACTORY: &str = "0x91E60e0613810449d098b0b5Ec8b51A0FE8c8985";
+const SIMPLE_ACCOUNT_IMPLEMENTATION: &str = "0x8ABB13360b87Be5EEb1B98647A016adD927a136c";
+
+fn safe_account(module: &str, valid_after: u64, valid_until: u64) -> Proto::SmartAccount<'_> {
+    Proto::SmartAccount {
+        account_type: Proto::SmartAccountType::Safe4337,
+        safe: Some(Proto::SafeAccount {
+            module: module.into(),
+            valid_after,
+            valid_until,
+            singleton: SAFE_SINGLETON.into(),
+            module_setup: SAFE_MODULE_SETUP.into(),
+            ..Proto::SafeAccount::default()
+        }),
+        ..Proto::SmartAccount::default()
+    }
+}
+
+fn kernel_account() -> Proto::SmartAccount<'static> {
+    Proto::SmartAccount {
+        account_type: Proto::SmartAccountType::Kernel,
+        kernel: Some(Proto::KernelAccount {
+            validator: KERNEL_ECDSA_VALIDATOR.into(),
+        }),
+        ..Proto::SmartAccount::default()
+    }
+}
+
+fn light_account() -> Proto::SmartAccount<'static> {
+    Proto::SmartAccount {
+        account_type: Proto::SmartAccountType::LightAccount,
+        ..Proto::SmartAccount::default()
+    }
+}
+
+fn derive_address(
+    account: Proto::SmartAccount<'_>,
+    factory: &str,
+    salt: u64,
+) -> Proto::SmartAccountAddressOutput<'static> {
+    let input = Proto::SmartAccountAddressInput {
+        account: Some(account),
+        factory: factory.into(),
+        owner: OWNER.into(),
+        salt: U256::encode_be_compact(salt),
+        proxy_bytecode: hex::decode(PROXY_BYTECODE).unwrap().into(),
+    };
+    SmartAccountAddress::<StandardEvmContext>::derive_proto(input)
+}
+
+fn batch_calls() -> Proto::mod_Transaction::Batch<'static> {
+    let usdc_transfer =
+        Erc20::transfer(Address::from(RECIPIENT), U256::from(1_000_000_u64)).unwrap();
+    Proto::mod_Transaction::Batch {
+        calls: vec![
+            Proto::mod_Transaction::mod_Batch::BatchedCall {
+                address: USDC.into(),
+                amount: Cow::default(),
+                payload: usdc_transfer.into(),
+            },
+            Proto::mod_Transaction::mod_Batch::BatchedCall {
+                address: RECIPIENT_2.into(),
+                amount: U256::encode_be_compact(1_000_000_000_000_000),
+                payload: Cow::default(),
+            },
+        ],
+    }
+}
+
+fn transfer() -> Proto::Transaction<'static> {
+    Proto::Transaction {
+        transaction_oneof: Proto::mod_Transaction::OneOftransaction_oneof::transfer(
+            Proto::mod_Transaction::Transfer {
+                amount: U256::encode_be_compact(10_000_000_000_000_000),
+                data: Cow::default(),
+            },
+        ),
+    }
+}
+
+fn signing_input<'a>(
+    transaction: Proto::Transaction<'a>,
+    user_op: Proto::mod_SigningInput::OneOfuser_operation_oneof<'a>,
+    nonce: u64,
+    gas_limit: u64,
+) -> Proto::SigningInput<'a> {
+    Proto::SigningInput {
+        chain_id: U256::encode_be_compact(11_155_111),
+        nonce: U256::encode_be_compact(nonce),
+        tx_mode: Proto::TransactionMode::UserOp,
+        gas_limit: U256::encode_be_compact(gas_limit),
+        max_fee_per_gas: U256::encode_be_compact(2_000_000_000),
+        max_inclusion_fee_per_gas: U256::encode_be_compact(1_000_000_000),
+        to_address: RECIPIENT.into(),
+        private_key: hex::decode(PRIVATE_KEY).unwrap().into(),
+        transaction: Some(transaction),
+        user_operation_oneof: user_op,
+        ..Proto::SigningInput::default()
+    }
+}
+
+fn sign(input: Proto::SigningInput<'_>) -> (String, Json) {
+    let output = Signer::<StandardEvmContext>::sign_proto(input);
+    assert_eq!(output.error, SigningErrorType::OK);
+    assert!(output.error_message.is_empty());
+
+    let encoded: Json = serde_json::from_slice(&output.encoded).unwrap();
+    (hex::encode(output.pre_hash, false), encoded)
+}
+
+#[test]
+fn test_safe4337_deploy_and_transfer_v0_7() {
+    let address = derive_address(
+        safe_account(SAFE_4337_MODULE_V0_3_0, 0, 0),
+        SAFE_PROXY_FACTORY,
+        0,
+    );
+    assert_eq!(address.error, SigningErrorType::OK);
+    assert_eq!(
+        address.address,
+        "0xD0Eae8b753B484c6BcED103f20ca5E72C72D8e6e"
+    );
+    let expected_factory_data = "0x1688f0b900000000000000000000000029fcb43b46531bca003ddc8fcb67ffe91900c7620000000000000000000000000000000000000000000000000000000000000060000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001e4b63e800d000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000000010000000000000000000000002dd68b007b46fbe91b9a7c3eda5a7a1063cb5b47000000000000000000000000000000000000000000000000000000000000014000000000000000000000000075cf11467937ce3f2f357ce24ffc3dbf8fd5c22600000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000010000000000000000000000009d8a62f656a8d1615c1294fd71e9cfb3e4855a4f00000000000000000000000000000000000000000000000000000000000000648d0dc49f0000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000000100000000000000000000000075cf11467937ce3f2f357ce24ffc3dbf8fd5c2260000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000";
+    assert_eq!(
+        hex::encode(&address.factory_data, true),
+        expected_factory_data
+    );
+
+    let user_op = Proto::UserOperationV0_7 {
+        entry_point: ENTRY_POINT_V0_7.into(),
+        sender: address.address.clone(),
+        factory: SAFE_PROXY_FACTORY.into(),
+        factory_data: address.factory_data.clone(),
+        pre_verification_gas: U256::encode_be_compact(50_000),
+        verification_gas_limit: U256::encode_be_compact(500_000),
+        account: Some(safe_account(SAFE_4337_MODULE_V0_3_0, 0, 1_800_000_000)),
+        ..Proto::UserOperationV0_7::default()
+    };
+    let input = signing_input(
+        transfer(),
+        Proto::mod_SigningInput::OneOfuser_operation_oneof::user_operation_v0_7(user_op),
+        0,
+        100_000,
+    );
+
+    let (pre_hash, encoded) = sign(input);
+    // EIP-712 `SafeOp` hash.
+    assert_eq!(
+        pre_hash,
+        "57fe539726de663e68b3c4a60ef517e1a509120658c7268eb70d12e17572b5e2"
+    );
+    assert_eq!(
+        encoded["sender"],
+        "0xD0Eae8b753B484c6BcED103f20ca5E72C72D8e6e"
+    );
+    assert_eq!(encoded["factoryData"], expected_factory_data);
+    assert_eq!(encoded["callData"], "0x7bb3742800000000000000000000000061061fcae11fd5461535e134eff67a98cfff44e9000000000000000000000000000000000000000000000000002386f26fc10000000000000000000000000000000000000000000000000000000000000000008000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000");
+    // `validAfter || validUntil || r || s || v`.
+    assert_eq!(encoded["signature"], "0x00000000000000006b49d20017f7c496770d5d53c695873d4e830a75fd3ba5d6093cbedc65f93a8d94f6f7f813c66a5852cdd175d47a7a3b9df623e56544b6cd6ab2f3980033c84457d9f5d01b");
+}
+
+#[test]
+fn test_safe4337_batch_v0_6() {
+    let user_op = Proto::UserOperation {
+        entry_point: ENTRY_POINT_V0_6.into(),
+        init_code: Cow::default(),
+        sender: "0x8f6aC01Bf5F7b6bB1bb0bb8Bd29C1E1D3FC5d2C5".into(),
+        pre_verification_gas: U256::encode_be_compact(50_000),
+        verification_gas_limit: U256::encode_be_compact(150_000),
+        paymaster_and_data: Cow::default(),
+        account: Some(safe_account(SAFE_4337_MODULE_V0_2_0, 1_700_000_000, 0)),
+    };
+    let transaction = Proto::Transaction {
+        transaction_oneof: Proto::mod_Transaction::OneOftransaction_oneof::batch(batch_calls()),
+    };
+    let input = signing_input(
+        transaction,
+        Proto::mod_SigningInput::OneOfuser_operation_oneof::user_operation(user_op),
+        3,
+        200_000,
+    );
+
+    let (pre_hash, encoded) = sign(input);
+    assert_eq!(
+        pre_hash,
+        "eb5352668c9403b14d56266a1c16a4de4b827eb5671bebb96ff00462468d34af"
+    );
+    // `executeUserOp(MultiSendCallOnly, 0, multiSend(...), DelegateCall)`.
+    assert_eq!(encoded["callData"], "0x7bb374280000000000000000000000009641d764fc13c8b624c04430c7356c1c7c8102e200000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000080000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000000001448d80ff0a000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000000ee001c7d4b196cb0c7b01d743fbc6116a902379c723800000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000044a9059cbb00000000000000000000000061061fcae11fd5461535e134eff67a98cfff44e900000000000000000000000000000000000000000000000000000000000f4240003fc91a3afd70395cd496c647d5a6cc9d4b2b7fad00000000000000000000000000000000000000000000000000038d7ea4c68000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000");
+    assert_eq!(encoded["signature"], "0x00006553f10000000000000071af1121a57ab77f6fa2bcb87c4edc75f69c17191728134bd4c642a5988d3b8177fb1fc7dc687d3f33733d1fb5d58e15945451d394d44e43f6805a83351139181c");
+}
+
+#[test]
+fn test_kernel_deploy_and_batch_v0_7() {
+    let address = derive_address(kernel_account(), KERNEL_FACTORY, 0);
+    assert_eq!(address.error, SigningErrorType::OK);
+    assert_eq!(
+        address.address,
+        "0xEDF114585a5e9fcbce08280A13b0eD227bC8CfBF"
+    );
+    assert_eq!(hex::encode(&address.factory_data, false), "ea6d13ac0000000000000000000000000000000000000000000000000000000000000040000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001243c3b752b01845adb2c711129d4f3966735ed98a9f09fc4ce570000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000a000000000000000000000000000000000000000000000000000000000000000e0000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000000149d8a62f656a8d1615c1294fd71e9cfb3e4855a4f0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000");
+
+    let user_op = Proto::UserOperationV0_7 {
+        entry_point: ENTRY_POINT_V0_7.into(),
+        sender: address.address.clone(),
+        factory: KERNEL_FACTORY.into(),
+        factory_data: address.factory_data.clone(),
+        pre_verification_gas: U256::encode_be_compact(50_000),
+        verification_gas_limit: U256::encode_be_compact(500_000),
+        account: Some(kernel_account()),
+        ..Proto::UserOperationV0_7::default()
+    };
+    let transaction = Proto::Transaction {
+        transaction_oneof: Proto::mod_Transaction::OneOftransaction_oneof::batch(batch_calls()),
+    };
+    let input = signing_input(
+        transaction,
+        Proto::mod_SigningInput::OneOfuser_operation_oneof::user_operation_v0_7(user_op),
+        0,
+        200_000,
+    );
+
+    let (pre_hash, encoded) = sign(input);
+    // The user operation hash is signed as is.
+    assert_eq!(
+        pre_hash,
+        "d23ed668f20388f71b64abc07b3a4bab824ce92c028a789dba036e469019b27b"
+    );
+    // ERC-7579 `execute(bytes32 execMode, bytes executionCalldata)` in the batch mode.
+    assert_eq!(encoded["callData"], "0xe9ae5c530100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000004000000000000000000000000000000000000000000000000000000000000001e000000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000004000000000000000000000000000000000000000000000000000000000000001200000000000000000000000001c7d4b196cb0c7b01d743fbc6116a902379c7238000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000600000000000000000000000000000000000000000000000000000000000000044a9059cbb00000000000000000000000061061fcae11fd5461535e134eff67a98cfff44e900000000000000000000000000000000000000000000000000000000000f4240000000000000000000000000000000000000000000000000000000000000000000000000000000003fc91a3afd70395cd496c647d5a6cc9d4b2b7fad00000000000000000000000000000000000000000000000000038d7ea4c6800000000000000000000000000000000000000000000000000000000000000000600000000000000000000000000000000000000000000000000000000000000000");
+    assert_eq!(encoded["signature"], "0x0e0df7cca8fb358cb9a99f5a17e6154221c4c4eac8d6a0cde51c66c4cffaa5d33a7874c4c0ab87c4913728f1ea15b370fe355dde782b2b826c3b182b66f47ffc1b");
+}
+
+#[test]
+fn test_light_account_deploy_and_transfer_v0_7() {
+    let address = derive_address(light_account(), LIGHT_ACCOUNT_FACTORY, 1);
+    assert_eq!(address.error, SigningErrorType::OK);
+    assert_eq!(
+        address.address,
+        "0x94f7f64265195e9Da8fCe5aA01FC04618B497a97"
+    );
+    assert_eq!(
+        hex::encode(&address.factory_data, false),
+        "5fbfb9cf0000000000000000000000009d8a62f656a8d1615c1294fd71e9cfb3e4855a4f0000000000000000000000000000000000000000000000000000000000000001"
+    );
+
+    let user_op = Proto::UserOperationV0_7 {
+        entry_point: ENTRY_POINT_V0_7.into(),
+        sender: address.address.clone(),
+        factory: LIGHT_ACCOUNT_FACTORY.into(),
+        factory_data: address.factory_data.clone(),
+        pre_verification_gas: U256::encode_be_compact(50_000),
+        verification_gas_limit: U256::encode_be_compact(500_000),
+        account: Some(light_account()),
+        ..Proto::UserOperationV0_7::default()
+    };
+    let input = signing_input(
+        transfer(),
+        Proto::mod_SigningInput::OneOfuser_operation_oneof::user_operation_v0_7(user_op),
+        0,
+        100_000,
+    );
+
+    let (pre_hash, encoded) = sign(input);
+    // EIP-191 hash of the user operation hash `be347f0e12c74e0d9a999cd867c1d4b3f467054970043b9bc2aabce12e4976ac`.
+    assert_eq!(
+        pre_hash,
+        "5c3c119109c3ccac0aa686222d371ed60400cfe157b4a315e35f43f60dcac6da"
+    );
+    assert_eq!(encoded["callData"], "0xb61d27f600000000000000000000000061061fcae11fd5461535e134eff67a98cfff44e9000000000000000000000000000000000000000000000000002386f26fc1000000000000000000000000000000000000000000000000000000000000000000600000000000000000000000000000000000000000000000000000000000000000");
+    // `0x00 (EOA signature type) || r || s || v`.
+    assert_eq!(encoded["signature"], "0x0029e9564a48fc4085999a7c7850f031f2f72fe910c0ec8181141bedabaf7a47d22e8c55d39246627e83be394c1d7a59aa317cc80602883099b44bb2920ab086861b");
+}
+
+#[test]
+fn test_light_account_address_erc1967_proxy() {
+    // The proxy init code embeds the account implementation.
+    assert!(
+        LIGHT_ACCOUNT_PROXY_BYTECODE.contains(&LIGHT_ACCOUNT_IMPLEMENTATION[2..].to_lowercase())
+    );
+
+    let input = Proto::SmartAccountAddressInput {
+        account: Some(light_account()),
+        factory: LIGHT_ACCOUNT_FACTORY.into(),
+        owner: OWNER.into(),
+        salt: U256::encode_be_compact(0),
+        proxy_bytecode: hex::decode(LIGHT_ACCOUNT_PROXY_BYTECODE).unwrap().into(),
+    };
+    let address = SmartAccountAddress::<StandardEvmContext>::derive_proto(input);
+    assert_eq!(address.error, SigningErrorType::OK);
+    // `LightAccountFactory.getAddress(owner, 0)`.
+    assert_eq!(
+        address.address,
+        "0xf1215844d751DfD01Fddcae25c32A665d54f1c90"
+    );
+    assert_eq!(
+        hex::encode(&address.factory_data, false),
+        "5fbfb9cf0000000000000000000000009d8a62f656a8d1615c1294fd71e9cfb3e4855a4f0000000000000000000000000000000000000000000000000000000000000000"
+    );
+}
+
+#[test]
+fn test_simple_account_address() {
+    let account = Proto::SmartAccount {
+        account_type: Proto::SmartAccountType::SimpleAccount,
+        implementation: SIMPLE_ACCOUNT_IMPLEMENTATION.into(),
+        ..Proto::SmartAccount::default()
+    };
+    let address = derive_address(account, SIMPLE_ACCOUNT_FACTORY, 0);
+    assert_eq!(address.error, SigningErrorType::OK);
+    assert_eq!(
+        address.address,
+        "0x3485E917a057ac0E20d7851d46eE2f1846603522"
+    );
+    assert_eq!(
+        hex::encode(&address.init_code, false),
+        "91e60e0613810449d098b0b5ec8b51a0fe8c89855fbfb9cf0000000000000000000000009d8a62f656a8d1615c1294fd71e9cfb3e4855a4f0000000000000000000000000000000000000000000000000000000000000000"
+    );
+}
+
+#[test]
+fn test_smart_account_address_missing_parameters() {
+    // `SimpleAccount` implementation is required to derive the address.
+    let address = derive_address(Proto::SmartAccount::default(), SIMPLE_ACCOUNT_FACTORY, 0);
+    assert_eq!(address.error, SigningErrorType::Error_invalid_params);
+    assert!(address.address.is_empty());
+
+    // Safe module is required.
+    let account = Proto::SmartAccount {
+        account_type: Proto::SmartAccountType::Safe4337,
+        ..Proto::SmartAccount::default()
+    };
+    let address = derive_address(account, SAFE_PROXY_FACTORY, 0);
+    assert_eq!(address.error, SigningErrorType::Error_invalid_params);
+}
+
+#[test]
+fn test_safe4337_invalid_validity_timestamp() {
+    let user_op = Proto::UserOperationV0_7 {
+        entry_point: ENTRY_POINT_V0_7.into(),
+        sender: "0xD0Eae8b753B484c6BcED103f20ca5E72C72D8e6e".into(),
+        account: Some(safe_account(SAFE_4337_MODULE_V0_3_0, 0, 1 << 48)),
+        ..Proto::UserOperationV0_7::default()
+    };
+    let input = signing_input(
+        transfer(),
+        Proto::mod_SigningInput::OneOfuser_operation_oneof::user_operation_v0_7(user_op),
+        0,
+        100_000,
+    );
+
+    let output = Signer::<StandardEvmContext>::sign_proto(input);
+    assert_eq!(output.error, SigningErrorType::Error_invalid_params);
+}
@@ -16,6 +16,7 @@ pub type H192 = Hash<24>;
 pub type H256 = Hash<32>;
 pub type H264 = Hash<33>;
 pub type H288 = Hash<36>;
+pub type H384 = Hash<48>;
 pub type H512 = Hash<64>;
 pub type H520 = Hash<65>;
 
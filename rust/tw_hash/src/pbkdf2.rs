@@ -0,0 +1,74 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use hmac::{Hmac, Mac};
+use sha2::Sha512;
+
+type HmacSha512 = Hmac<Sha512>;
+
+/// Derives a key of `out_len` bytes from the given `password` and `salt`
+/// using PBKDF2 with HMAC-SHA512 as a pseudorandom function.
+///
+/// https://datatracker.ietf.org/doc/html/rfc8018#section-5.2
+pub fn pbkdf2_hmac_sha512(
+    password: &[u8],
+    salt: &[u8],
+    iterations: u32,
+    out_len: usize,
+) -> Vec<u8> {
+    let prf = HmacSha512::new_from_slice(password).expect("HMAC can take a key of any size");
+
+    let mut out = Vec::with_capacity(out_len);
+    let mut block_index = 1_u32;
+    while out.len() < out_len {
+        let mut mac = prf.clone();
+        mac.update(salt);
+        mac.update(&block_index.to_be_bytes());
+        let mut u = mac.finalize().into_bytes();
+        let mut block = u;
+
+        for _ in 1..iterations {
+            let mut mac = prf.clone();
+            mac.update(&u);
+            u = mac.finalize().into_bytes();
+            block
+                .iter_mut()
+                .zip(u.iter())
+                .for_each(|(acc, byte)| *acc ^= byte);
+        }
+
+        let to_take = (out_len - out.len()).min(block.len());
+        out.extend_from_slice(&block[..to_take]);
+        block_index += 1;
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tw_encoding::hex::ToHex;
+
+    #[test]
+    fn test_pbkdf2_hmac_sha512() {
+        // https://stackoverflow.com/questions/15593184/pbkdf2-hmac-sha-512-test-vectors
+        let actual = pbkdf2_hmac_sha512(b"password", b"salt", 1, 64);
+        assert_eq!(
+            actual.to_hex(),
+            "867f70cf1ade02cff3752599a3a53dc4af34c7a669815ae5d513554e1c8cf252c02d470a285a0501bad999bfe943c08f050235d7d68b1da55e63f73b60a57fce"
+        );
+
+        let actual = pbkdf2_hmac_sha512(b"password", b"salt", 4096, 64);
+        assert_eq!(
+            actual.to_hex(),
+            "d197b1b33db0143e018b12f3d1d1479e6cdebdcc97c5c0f87f6902e072f457b5143f30602641b3d55cd335988cb36b84376060ecd532e039b742a239434af2d5"
+        );
+    }
+
+    #[test]
+    fn test_pbkdf2_hmac_sha512_truncated() {
+        let actual = pbkdf2_hmac_sha512(b"password", b"salt", 2, 20);
+        assert_eq!(actual.to_hex(), "e1d9c16aa681708a45f5c7c4e215ceb66e011a2e");
+    }
+}
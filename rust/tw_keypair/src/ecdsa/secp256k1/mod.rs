@@ -109,6 +109,18 @@ mod tests {
         assert!(public_key.verify(verify_signature, hash_to_sign));
     }
 
+    #[test]
+    fn test_signature_normalize_s() {
+        let high_s = "a508f6fdb16c7a2025ef0729723633928484c29a5ba7e3371b72132dbb31ffb7fded61b788d9a1cf397b817cb2d3ecc6de17720f18af8d92dbc3ba2ff431f5f101";
+        let low_s = "a508f6fdb16c7a2025ef0729723633928484c29a5ba7e3371b72132dbb31ffb702129e4877265e30c6847e834d2c1337dc976ad7969912a8e40ea45cdc044b5000";
+
+        let signature = Signature::from_bytes(&hex::decode(high_s).unwrap()).unwrap();
+        assert_eq!(signature.normalize_s().to_bytes().to_hex(), low_s);
+
+        let signature = Signature::from_bytes(&hex::decode(low_s).unwrap()).unwrap();
+        assert_eq!(signature.normalize_s().to_bytes().to_hex(), low_s);
+    }
+
     #[test]
     fn test_public_key_from() {
         let compressed = "0399c6f51ad6f98c9c583f8e92bb7758ab2ca9a04110c0a1126ec43e5453d196c1";
@@ -78,6 +78,19 @@ impl<C: EcdsaCurve> Signature<C> {
         })
     }
 
+    /// Returns the signature with a low `s` value as specified in BIP62.
+    /// If `s` is high, it's replaced with `n - s`, and the recovery ID parity is flipped,
+    /// so the signature recovers the same public key.
+    pub fn normalize_s(&self) -> Self {
+        match self.signature.normalize_s() {
+            Some(signature) => Signature {
+                signature,
+                v: ecdsa::RecoveryId::new(!self.v.is_y_odd(), self.v.is_x_reduced()),
+            },
+            None => self.clone(),
+        }
+    }
+
     /// Returns a standard binary signature representation:
     /// RSV, where R - 32 byte array, S - 32 byte array, V - 1 byte.
     pub fn to_bytes(&self) -> H520 {
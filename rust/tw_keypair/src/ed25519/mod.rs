@@ -9,7 +9,7 @@ mod mangle;
 mod modifications;
 mod private;
 mod public;
-mod secret;
+pub(crate) mod secret;
 mod signature;
 
 pub use modifications::{cardano, waves};
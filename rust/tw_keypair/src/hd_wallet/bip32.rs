@@ -0,0 +1,681 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+//! BIP32 hierarchical deterministic key derivation generalized to other curves by SLIP-10.
+//! https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki
+//! https://github.com/satoshilabs/slips/blob/master/slip-0010.md
+
+use crate::ecdsa::{nist256p1, secp256k1, EcdsaCurve};
+use crate::ed25519;
+use crate::hd_wallet::slip132::HDVersion;
+use crate::tw::Curve;
+use crate::{KeyPairError, KeyPairResult};
+use derivation_path::{ChildIndex, DerivationPath};
+use ecdsa::elliptic_curve::ff::{Field, PrimeField};
+use ecdsa::elliptic_curve::group::{Curve as _, Group};
+use ecdsa::elliptic_curve::sec1::{FromEncodedPoint, ModulusSize, ToEncodedPoint};
+use ecdsa::elliptic_curve::{AffinePoint, FieldBytes, FieldBytesSize, ProjectivePoint, Scalar};
+use tw_encoding::base58::{self, Alphabet};
+use tw_hash::hasher::{sha256_d, sha256_ripemd};
+use tw_hash::hmac::hmac_sha512;
+use tw_hash::{H256, H264, H32, H512};
+use zeroize::{ZeroizeOnDrop, Zeroizing};
+
+/// The length of a serialized extended key without the checksum.
+const SERIALIZED_LEN: usize = 78;
+
+/// Elliptic curves that support SLIP-10 derivation from a seed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Slip10Curve {
+    Secp256k1,
+    Nist256p1,
+    Ed25519,
+    Curve25519,
+}
+
+impl Slip10Curve {
+    /// Returns a SLIP-10 curve used to derive keys of the given `curve`.
+    /// Returns `None` if the `curve` keys are derived using a custom scheme.
+    pub fn from_curve(curve: Curve) -> Option<Slip10Curve> {
+        match curve {
+            Curve::Secp256k1 | Curve::Schnorr | Curve::Starkex => Some(Slip10Curve::Secp256k1),
+            Curve::Nist256p1 => Some(Slip10Curve::Nist256p1),
+            Curve::Ed25519 | Curve::Ed25519Blake2bNano => Some(Slip10Curve::Ed25519),
+            Curve::Curve25519Waves => Some(Slip10Curve::Curve25519),
+            Curve::Ed25519ExtendedCardano => None,
+        }
+    }
+
+    /// Returns the HMAC key used to generate a master key from a seed.
+    fn seed_key(&self) -> &'static [u8] {
+        match self {
+            Slip10Curve::Secp256k1 => b"Bitcoin seed",
+            Slip10Curve::Nist256p1 => b"Nist256p1 seed",
+            Slip10Curve::Ed25519 => b"ed25519 seed",
+            Slip10Curve::Curve25519 => b"curve25519 seed",
+        }
+    }
+
+    /// Whether the curve supports non-hardened (public parent key to public child key) derivation.
+    pub fn supports_non_hardened(&self) -> bool {
+        matches!(self, Slip10Curve::Secp256k1 | Slip10Curve::Nist256p1)
+    }
+}
+
+/// Represents a BIP32 extended private key: a private key with a chain code
+/// and the position of the key in the tree.
+#[derive(Clone, ZeroizeOnDrop)]
+pub struct ExtendedPrivateKey {
+    #[zeroize(skip)]
+    curve: Slip10Curve,
+    secret: H256,
+    chain_code: H256,
+    depth: u8,
+    parent_fingerprint: H32,
+    child_number: u32,
+}
+
+impl ExtendedPrivateKey {
+    /// Generates a master key from the given `seed`.
+    pub fn new_master(curve: Slip10Curve, seed: &[u8]) -> KeyPairResult<ExtendedPrivateKey> {
+        let mut i = Zeroizing::new(hmac_sha512(curve.seed_key(), seed));
+        // SLIP-10: if the master key is invalid, hash the result again until it's valid.
+        // The loop is only reachable for `secp256k1` and `nist256p1` with negligible probability.
+        while !is_valid_secret(curve, &i[..32]) {
+            i = Zeroizing::new(hmac_sha512(curve.seed_key(), &i));
+        }
+
+        let (secret, chain_code) = split_hmac(&i);
+        Ok(ExtendedPrivateKey {
+            curve,
+            secret,
+            chain_code,
+            depth: 0,
+            parent_fingerprint: H32::default(),
+            child_number: 0,
+        })
+    }
+
+    /// Creates an extended private key from its raw components.
+    pub fn new(
+        curve: Slip10Curve,
+        secret: H256,
+        chain_code: H256,
+        depth: u8,
+        parent_fingerprint: H32,
+        child_number: u32,
+    ) -> KeyPairResult<ExtendedPrivateKey> {
+        if !is_valid_secret(curve, secret.as_slice()) {
+            return Err(KeyPairError::InvalidSecretKey);
+        }
+        Ok(ExtendedPrivateKey {
+            curve,
+            secret,
+            chain_code,
+            depth,
+            parent_fingerprint,
+            child_number,
+        })
+    }
+
+    /// Derives a child key at the given `path` relative to the current key.
+    ///
+    /// Please note non-hardened indexes are skipped for curves that support hardened derivation only,
+    /// the same way as it's done in the C++ `HDWallet`.
+    pub fn derive(&self, path: &DerivationPath) -> KeyPairResult<ExtendedPrivateKey> {
+        let mut key = self.clone();
+        for index in path.path() {
+            if !index.is_hardened() && !self.curve.supports_non_hardened() {
+                continue;
+            }
+            key = key.derive_child(*index)?;
+        }
+        Ok(key)
+    }
+
+    /// Derives a direct child key at the given `index`.
+    pub fn derive_child(&self, index: ChildIndex) -> KeyPairResult<ExtendedPrivateKey> {
+        let child_number = index.to_bits();
+
+        let mut data = Zeroizing::new(Vec::with_capacity(37));
+        if index.is_hardened() {
+            data.push(0);
+            data.extend_from_slice(self.secret.as_slice());
+        } else if self.curve.supports_non_hardened() {
+            data.extend_from_slice(self.public_key()?.as_slice());
+        } else {
+            return Err(KeyPairError::InvalidDerivationPath);
+        }
+        data.extend_from_slice(&child_number.to_be_bytes());
+
+        let mut i = Zeroizing::new(hmac_sha512(self.chain_code.as_slice(), &data));
+        loop {
+            let (tweak, chain_code) = split_hmac(&i);
+            let child_secret = match self.curve {
+                Slip10Curve::Secp256k1 => tweak_add::<k256::Secp256k1>(&self.secret, &tweak),
+                Slip10Curve::Nist256p1 => tweak_add::<p256::NistP256>(&self.secret, &tweak),
+                Slip10Curve::Ed25519 | Slip10Curve::Curve25519 => Some(tweak),
+            };
+
+            if let Some(secret) = child_secret {
+                return Ok(ExtendedPrivateKey {
+                    curve: self.curve,
+                    secret,
+                    chain_code,
+                    depth: self
+                        .depth
+                        .checked_add(1)
+                        .ok_or(KeyPairError::InvalidDerivationPath)?,
+                    parent_fingerprint: self.fingerprint()?,
+                    child_number,
+                });
+            }
+
+            // SLIP-10: if the child key is invalid, try again with `0x01 || IR || ser32(i)`.
+            data.clear();
+            data.push(1);
+            data.extend_from_slice(&i[32..]);
+            data.extend_from_slice(&child_number.to_be_bytes());
+            i = Zeroizing::new(hmac_sha512(self.chain_code.as_slice(), &data));
+        }
+    }
+
+    /// Returns the SLIP-10 curve of the key.
+    pub fn curve(&self) -> Slip10Curve {
+        self.curve
+    }
+
+    /// Returns the 32 byte private key.
+    pub fn secret(&self) -> &H256 {
+        &self.secret
+    }
+
+    /// Returns the 32 byte chain code.
+    pub fn chain_code(&self) -> &H256 {
+        &self.chain_code
+    }
+
+    /// Returns the depth of the key in the tree. The master key has depth 0.
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    /// Returns the fingerprint of the parent key. The master key has zero fingerprint.
+    pub fn parent_fingerprint(&self) -> H32 {
+        self.parent_fingerprint
+    }
+
+    /// Returns the index the key was derived with. The master key has index 0.
+    pub fn child_number(&self) -> u32 {
+        self.child_number
+    }
+
+    /// Returns the 33 byte serialized public key:
+    /// a compressed point for ECDSA curves, or `0x00 || public` for ed25519-like curves.
+    pub fn public_key(&self) -> KeyPairResult<H264> {
+        let mut res = H264::default();
+        match self.curve {
+            Slip10Curve::Secp256k1 => {
+                return Ok(secp256k1::PrivateKey::try_from(self.secret.as_slice())?
+                    .public()
+                    .compressed());
+            },
+            Slip10Curve::Nist256p1 => {
+                return Ok(nist256p1::PrivateKey::try_from(self.secret.as_slice())?
+                    .public()
+                    .compressed());
+            },
+            Slip10Curve::Ed25519 => {
+                let public = ed25519::sha512::PrivateKey::try_from(self.secret.as_slice())?
+                    .public()
+                    .to_bytes();
+                res[1..].copy_from_slice(public.as_slice());
+            },
+            Slip10Curve::Curve25519 => {
+                let public = ed25519::waves::PrivateKey::try_from(self.secret.as_slice())?
+                    .public()
+                    .to_bytes();
+                res[1..].copy_from_slice(public.as_slice());
+            },
+        }
+        Ok(res)
+    }
+
+    /// Returns the key fingerprint: the first 4 bytes of `hash160` of the serialized public key.
+    pub fn fingerprint(&self) -> KeyPairResult<H32> {
+        let hash = sha256_ripemd(self.public_key()?.as_slice());
+        Ok(H32::try_from(&hash[..H32::len()]).expect("hash160 is at least 4 bytes long"))
+    }
+
+    /// Returns the extended public key that can be used to derive non-hardened child public keys.
+    pub fn to_extended_public_key(&self, version: HDVersion) -> KeyPairResult<ExtendedPublicKey> {
+        if !self.curve.supports_non_hardened() || !version.is_public() {
+            return Err(KeyPairError::InvalidExtendedKey);
+        }
+        Ok(ExtendedPublicKey {
+            version,
+            curve: self.curve,
+            public_key: self.public_key()?,
+            chain_code: self.chain_code,
+            depth: self.depth,
+            parent_fingerprint: self.parent_fingerprint,
+            child_number: self.child_number,
+        })
+    }
+
+    /// Serializes the key as a Base58Check string with the given SLIP-132 `version`, e.g. `xprv...`.
+    pub fn to_base58(&self, version: HDVersion) -> KeyPairResult<Zeroizing<String>> {
+        if !self.curve.supports_non_hardened() || !version.is_private() {
+            return Err(KeyPairError::InvalidExtendedKey);
+        }
+        let mut key = Zeroizing::new(H264::default());
+        key[1..].copy_from_slice(self.secret.as_slice());
+
+        let data = serialize(
+            version,
+            self.depth,
+            &self.parent_fingerprint,
+            self.child_number,
+            &self.chain_code,
+            &key,
+        );
+        Ok(Zeroizing::new(encode_base58_check(&data)))
+    }
+}
+
+/// Represents a BIP32 extended public key: a compressed public key with a chain code
+/// and the position of the key in the tree.
+/// Used to derive non-hardened child public keys without knowing the private key, e.g. in watch-only wallets.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExtendedPublicKey {
+    version: HDVersion,
+    curve: Slip10Curve,
+    public_key: H264,
+    chain_code: H256,
+    depth: u8,
+    parent_fingerprint: H32,
+    child_number: u32,
+}
+
+impl ExtendedPublicKey {
+    /// Parses a Base58Check serialized extended public key with SLIP-132 version bytes,
+    /// e.g. `xpub...`, `ypub...`, `zpub...`, `tpub...`.
+    ///
+    /// Only `secp256k1` and `nist256p1` curves support non-hardened derivation.
+    pub fn from_base58(curve: Slip10Curve, s: &str) -> KeyPairResult<ExtendedPublicKey> {
+        if !curve.supports_non_hardened() {
+            return Err(KeyPairError::InvalidExtendedKey);
+        }
+        let data = decode_base58_check(s)?;
+        let decoded = Deserialized::from_bytes(&data)?;
+        if !decoded.version.is_public() || !is_valid_public(curve, &decoded.key) {
+            return Err(KeyPairError::InvalidExtendedKey);
+        }
+
+        Ok(ExtendedPublicKey {
+            version: decoded.version,
+            curve,
+            public_key: decoded.key,
+            chain_code: decoded.chain_code,
+            depth: decoded.depth,
+            parent_fingerprint: decoded.parent_fingerprint,
+            child_number: decoded.child_number,
+        })
+    }
+
+    /// Serializes the key as a Base58Check string with its SLIP-132 version.
+    pub fn to_base58(&self) -> String {
+        let data = serialize(
+            self.version,
+            self.depth,
+            &self.parent_fingerprint,
+            self.child_number,
+            &self.chain_code,
+            &self.public_key,
+        );
+        encode_base58_check(&data)
+    }
+
+    /// Derives a child key at the given non-hardened `path` relative to the current key.
+    pub fn derive(&self, path: &[ChildIndex]) -> KeyPairResult<ExtendedPublicKey> {
+        path.iter()
+            .try_fold(self.clone(), |key, index| key.derive_child(*index))
+    }
+
+    /// Derives a direct child key at the given non-hardened `index`.
+    pub fn derive_child(&self, index: ChildIndex) -> KeyPairResult<ExtendedPublicKey> {
+        if index.is_hardened() {
+            return Err(KeyPairError::InvalidDerivationPath);
+        }
+        let child_number = index.to_bits();
+
+        let mut data = Vec::with_capacity(37);
+        data.extend_from_slice(self.public_key.as_slice());
+        data.extend_from_slice(&child_number.to_be_bytes());
+
+        let mut i = Zeroizing::new(hmac_sha512(self.chain_code.as_slice(), &data));
+        loop {
+            let (tweak, chain_code) = split_hmac(&i);
+            let child_public = match self.curve {
+                Slip10Curve::Secp256k1 => {
+                    tweak_add_public::<k256::Secp256k1>(&self.public_key, &tweak)
+                },
+                Slip10Curve::Nist256p1 => {
+                    tweak_add_public::<p256::NistP256>(&self.public_key, &tweak)
+                },
+                Slip10Curve::Ed25519 | Slip10Curve::Curve25519 => {
+                    return Err(KeyPairError::InvalidExtendedKey)
+                },
+            };
+
+            if let Some(public_key) = child_public {
+                return Ok(ExtendedPublicKey {
+                    version: self.version,
+                    curve: self.curve,
+                    public_key,
+                    chain_code,
+                    depth: self
+                        .depth
+                        .checked_add(1)
+                        .ok_or(KeyPairError::InvalidDerivationPath)?,
+                    parent_fingerprint: self.fingerprint(),
+                    child_number,
+                });
+            }
+
+            // SLIP-10: if the child key is invalid, try again with `0x01 || IR || ser32(i)`.
+            data.clear();
+            data.push(1);
+            data.extend_from_slice(&i[32..]);
+            data.extend_from_slice(&child_number.to_be_bytes());
+            i = Zeroizing::new(hmac_sha512(self.chain_code.as_slice(), &data));
+        }
+    }
+
+    /// Returns the SLIP-132 version the key is serialized with.
+    pub fn version(&self) -> HDVersion {
+        self.version
+    }
+
+    /// Returns the SLIP-10 curve of the key.
+    pub fn curve(&self) -> Slip10Curve {
+        self.curve
+    }
+
+    /// Returns the 33 byte compressed public key.
+    pub fn public_key(&self) -> &H264 {
+        &self.public_key
+    }
+
+    /// Returns the 32 byte chain code.
+    pub fn chain_code(&self) -> &H256 {
+        &self.chain_code
+    }
+
+    /// Returns the depth of the key in the tree. The master key has depth 0.
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    /// Returns the fingerprint of the parent key. The master key has zero fingerprint.
+    pub fn parent_fingerprint(&self) -> H32 {
+        self.parent_fingerprint
+    }
+
+    /// Returns the index the key was derived with. The master key has index 0.
+    pub fn child_number(&self) -> u32 {
+        self.child_number
+    }
+
+    /// Returns the key fingerprint: the first 4 bytes of `hash160` of the public key.
+    pub fn fingerprint(&self) -> H32 {
+        let hash = sha256_ripemd(self.public_key.as_slice());
+        H32::try_from(&hash[..H32::len()]).expect("hash160 is at least 4 bytes long")
+    }
+}
+
+/// Fields of a serialized extended key.
+struct Deserialized {
+    version: HDVersion,
+    depth: u8,
+    parent_fingerprint: H32,
+    child_number: u32,
+    chain_code: H256,
+    key: H264,
+}
+
+impl Deserialized {
+    fn from_bytes(data: &[u8]) -> KeyPairResult<Deserialized> {
+        if data.len() != SERIALIZED_LEN {
+            return Err(KeyPairError::InvalidExtendedKey);
+        }
+        let version = u32::from_be_bytes(data[0..4].try_into().expect("Expected exactly 4 bytes"));
+        let version = HDVersion::from_u32(version).ok_or(KeyPairError::InvalidExtendedKey)?;
+        let child_number =
+            u32::from_be_bytes(data[9..13].try_into().expect("Expected exactly 4 bytes"));
+
+        Ok(Deserialized {
+            version,
+            depth: data[4],
+            parent_fingerprint: H32::try_from(&data[5..9]).expect("Expected exactly 4 bytes"),
+            child_number,
+            chain_code: H256::try_from(&data[13..45]).expect("Expected exactly 32 bytes"),
+            key: H264::try_from(&data[45..78]).expect("Expected exactly 33 bytes"),
+        })
+    }
+}
+
+/// Serializes an extended key as `version || depth || parent_fingerprint || child_number || chain_code || key`.
+fn serialize(
+    version: HDVersion,
+    depth: u8,
+    parent_fingerprint: &H32,
+    child_number: u32,
+    chain_code: &H256,
+    key: &H264,
+) -> Zeroizing<Vec<u8>> {
+    let mut data = Zeroizing::new(Vec::with_capacity(SERIALIZED_LEN));
+    data.extend_from_slice(&version.to_u32().to_be_bytes());
+    data.push(depth);
+    data.extend_from_slice(parent_fingerprint.as_slice());
+    data.extend_from_slice(&child_number.to_be_bytes());
+    data.extend_from_slice(chain_code.as_slice());
+    data.extend_from_slice(key.as_slice());
+    data
+}
+
+fn encode_base58_check(data: &[u8]) -> String {
+    let checksum = sha256_d(data);
+    let mut with_checksum = Zeroizing::new(data.to_vec());
+    with_checksum.extend_from_slice(&checksum[..base58::CHECKSUM_LEN]);
+    base58::encode(&with_checksum, Alphabet::Bitcoin)
+}
+
+fn decode_base58_check(s: &str) -> KeyPairResult<Zeroizing<Vec<u8>>> {
+    let mut data = Zeroizing::new(
+        base58::decode(s, Alphabet::Bitcoin).map_err(|_| KeyPairError::InvalidExtendedKey)?,
+    );
+    if data.len() < base58::CHECKSUM_LEN {
+        return Err(KeyPairError::InvalidExtendedKey);
+    }
+
+    let data_len = data.len() - base58::CHECKSUM_LEN;
+    let checksum = data.split_off(data_len);
+    if sha256_d(&data)[..base58::CHECKSUM_LEN] != checksum[..] {
+        return Err(KeyPairError::InvalidExtendedKey);
+    }
+    Ok(data)
+}
+
+/// Splits the HMAC-SHA512 output into `IL` and `IR` halves.
+fn split_hmac(i: &[u8]) -> (H256, H256) {
+    let i = Zeroizing::new(H512::try_from(i).expect("HMAC-SHA512 must be 64 byte length"));
+    i.split()
+}
+
+fn is_valid_secret(curve: Slip10Curve, secret: &[u8]) -> bool {
+    match curve {
+        Slip10Curve::Secp256k1 => secp256k1::PrivateKey::try_from(secret).is_ok(),
+        Slip10Curve::Nist256p1 => nist256p1::PrivateKey::try_from(secret).is_ok(),
+        Slip10Curve::Ed25519 | Slip10Curve::Curve25519 => secret.len() == H256::len(),
+    }
+}
+
+fn is_valid_public(curve: Slip10Curve, public: &H264) -> bool {
+    match curve {
+        Slip10Curve::Secp256k1 => secp256k1::PublicKey::try_from(public.as_slice()).is_ok(),
+        Slip10Curve::Nist256p1 => nist256p1::PublicKey::try_from(public.as_slice()).is_ok(),
+        Slip10Curve::Ed25519 | Slip10Curve::Curve25519 => false,
+    }
+}
+
+/// Computes `(secret + tweak) mod n`.
+/// Returns `None` if the `tweak` is not less than `n` or the result is zero.
+fn tweak_add<C: EcdsaCurve>(secret: &H256, tweak: &H256) -> Option<H256> {
+    let to_scalar = |bytes: &H256| -> Option<Scalar<C>> {
+        Scalar::<C>::from_repr(FieldBytes::<C>::clone_from_slice(bytes.as_slice())).into()
+    };
+
+    let sum = to_scalar(secret)? + to_scalar(tweak)?;
+    if bool::from(sum.is_zero()) {
+        return None;
+    }
+    H256::try_from(sum.to_repr().as_slice()).ok()
+}
+
+/// Computes `point(tweak) + public`.
+/// Returns `None` if the `tweak` is not less than `n` or the result is the point at infinity.
+fn tweak_add_public<C>(public: &H264, tweak: &H256) -> Option<H264>
+where
+    C: EcdsaCurve,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    FieldBytesSize<C>: ModulusSize,
+{
+    let public = ecdsa::elliptic_curve::PublicKey::<C>::from_sec1_bytes(public.as_slice()).ok()?;
+    let tweak: Option<Scalar<C>> =
+        Scalar::<C>::from_repr(FieldBytes::<C>::clone_from_slice(tweak.as_slice())).into();
+
+    let child = public.to_projective() + ProjectivePoint::<C>::generator() * tweak?;
+    let child = ecdsa::elliptic_curve::PublicKey::<C>::from_affine(child.to_affine()).ok()?;
+    H264::try_from(child.to_encoded_point(true).as_bytes()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use tw_encoding::hex::{DecodeHex, ToHex};
+
+    fn derive(curve: Slip10Curve, seed: &str, path: &str) -> ExtendedPrivateKey {
+        let seed = seed.decode_hex().unwrap();
+        let path = DerivationPath::from_str(path).unwrap();
+        ExtendedPrivateKey::new_master(curve, &seed)
+            .unwrap()
+            .derive(&path)
+            .unwrap()
+    }
+
+    /// https://github.com/satoshilabs/slips/blob/master/slip-0010.md#test-vector-1-for-secp256k1
+    #[test]
+    fn test_slip10_secp256k1() {
+        let key = derive(
+            Slip10Curve::Secp256k1,
+            "000102030405060708090a0b0c0d0e0f",
+            "m/0'/1/2'/2/1000000000",
+        );
+        assert_eq!(
+            key.secret().to_hex(),
+            "471b76e389e528d6de6d816857e012c5455051cad6660850e58372a6c3e6e7c8"
+        );
+        assert_eq!(
+            key.chain_code().to_hex(),
+            "c783e67b921d2beb8f6b389cc646d7263b4145701dadd2161548a8b078e65e9e"
+        );
+        assert_eq!(
+            key.public_key().unwrap().to_hex(),
+            "022a471424da5e657499d1ff51cb43c47481a03b1e77f951fe64cec9f5a48f7011"
+        );
+        assert_eq!(key.parent_fingerprint().to_hex(), "d880d7d8");
+        assert_eq!(key.depth(), 5);
+        assert_eq!(key.child_number(), 1000000000);
+    }
+
+    /// https://github.com/satoshilabs/slips/blob/master/slip-0010.md#test-vector-1-for-nist256p1
+    #[test]
+    fn test_slip10_nist256p1() {
+        let key = derive(
+            Slip10Curve::Nist256p1,
+            "000102030405060708090a0b0c0d0e0f",
+            "m/0'/1/2'/2/1000000000",
+        );
+        assert_eq!(
+            key.secret().to_hex(),
+            "21c4f269ef0a5fd1badf47eeacebeeaa3de22eb8e5b0adcd0f27dd99d34d0119"
+        );
+        assert_eq!(
+            key.chain_code().to_hex(),
+            "b9b7b82d326bb9cb5b5b121066feea4eb93d5241103c9e7a18aad40f1dde8059"
+        );
+        assert_eq!(
+            key.public_key().unwrap().to_hex(),
+            "02216cd26d31147f72427a453c443ed2cde8a1e53c9cc44e5ddf739725413fe3f4"
+        );
+    }
+
+    /// https://github.com/satoshilabs/slips/blob/master/slip-0010.md#test-derivation-retry-for-nist256p1
+    #[test]
+    fn test_slip10_nist256p1_retry() {
+        let key = derive(
+            Slip10Curve::Nist256p1,
+            "000102030405060708090a0b0c0d0e0f",
+            "m/28578'/33941",
+        );
+        assert_eq!(
+            key.secret().to_hex(),
+            "092154eed4af83e078ff9b84322015aefe5769e31270f62c3f66c33888335f3a"
+        );
+        assert_eq!(
+            key.chain_code().to_hex(),
+            "9e87fe95031f14736774cd82f25fd885065cb7c358c1edf813c72af535e83071"
+        );
+    }
+
+    /// https://github.com/satoshilabs/slips/blob/master/slip-0010.md#test-vector-1-for-ed25519
+    #[test]
+    fn test_slip10_ed25519() {
+        let key = derive(
+            Slip10Curve::Ed25519,
+            "000102030405060708090a0b0c0d0e0f",
+            "m/0'/1'/2'/2'/1000000000'",
+        );
+        assert_eq!(
+            key.secret().to_hex(),
+            "8f94d394a8e8fd6b1bc2f3f49f5c47e385281d5c17e65324b0f62483e37e8793"
+        );
+        assert_eq!(
+            key.chain_code().to_hex(),
+            "68789923a0cac2cd5a29172a475fe9e0fb14cd6adb5ad98a3fa70333e7afa230"
+        );
+        assert_eq!(
+            key.public_key().unwrap().to_hex(),
+            "003c24da049451555d51a7014a37337aa4e12d41e485abccfa46b47dfb2af54b7a"
+        );
+    }
+
+    #[test]
+    fn test_ed25519_skips_non_hardened() {
+        let seed = "000102030405060708090a0b0c0d0e0f";
+        let hardened = derive(Slip10Curve::Ed25519, seed, "m/0'/1'");
+        let mixed = derive(Slip10Curve::Ed25519, seed, "m/0'/5/1'/7");
+        assert_eq!(hardened.secret(), mixed.secret());
+
+        let master =
+            ExtendedPrivateKey::new_master(Slip10Curve::Ed25519, &seed.decode_hex().unwrap())
+                .unwrap();
+        assert_eq!(
+            master.derive_child(ChildIndex::Normal(0)).err(),
+            Some(KeyPairError::InvalidDerivationPath)
+        );
+    }
+}
@@ -0,0 +1,245 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+//! BIP39 mnemonic code for generating deterministic keys.
+//! https://github.com/bitcoin/bips/blob/master/bip-0039.mediawiki
+
+use crate::rand::{CryptoRngCore, OsRng};
+use crate::{KeyPairError, KeyPairResult};
+use lazy_static::lazy_static;
+use tw_hash::pbkdf2::pbkdf2_hmac_sha512;
+use tw_hash::sha2::sha256;
+use tw_hash::H512;
+use zeroize::{ZeroizeOnDrop, Zeroizing};
+
+const ENGLISH_WORDLIST: &str = include_str!("english.txt");
+
+lazy_static! {
+    static ref ENGLISH_WORDS: Vec<&'static str> = ENGLISH_WORDLIST.lines().collect();
+}
+
+/// Represents a valid BIP39 mnemonic phrase in English.
+#[derive(ZeroizeOnDrop)]
+pub struct Mnemonic {
+    phrase: String,
+    entropy: Vec<u8>,
+}
+
+impl Mnemonic {
+    /// The minimum strength of a mnemonic in bits (12 words).
+    pub const MIN_STRENGTH: usize = 128;
+    /// The maximum strength of a mnemonic in bits (24 words).
+    pub const MAX_STRENGTH: usize = 256;
+
+    const BITS_PER_WORD: usize = 11;
+    const SEED_ITERATIONS: u32 = 2048;
+    const SALT_PREFIX: &'static str = "mnemonic";
+
+    /// Generates a new mnemonic with the given `strength` in bits.
+    /// `strength` must be a multiple of 32 within the `[128, 256]` range.
+    pub fn generate(strength: usize) -> KeyPairResult<Mnemonic> {
+        Self::generate_with_rng(strength, &mut OsRng)
+    }
+
+    /// Generates a new mnemonic with the given `strength` in bits using the given random generator.
+    pub fn generate_with_rng(
+        strength: usize,
+        rng: &mut impl CryptoRngCore,
+    ) -> KeyPairResult<Mnemonic> {
+        if !Self::is_valid_strength(strength) {
+            return Err(KeyPairError::InvalidMnemonic);
+        }
+        let mut entropy = Zeroizing::new(vec![0; strength / 8]);
+        rng.fill_bytes(entropy.as_mut_slice());
+        Self::from_entropy(entropy.as_slice())
+    }
+
+    /// Encodes the given `entropy` as a mnemonic.
+    /// The entropy length must be a multiple of 4 bytes within the `[16, 32]` range.
+    pub fn from_entropy(entropy: &[u8]) -> KeyPairResult<Mnemonic> {
+        if !Self::is_valid_strength(entropy.len() * 8) {
+            return Err(KeyPairError::InvalidMnemonic);
+        }
+
+        let checksum_bits = entropy.len() * 8 / 32;
+        let checksum = sha256(entropy)[0];
+        let words_count = (entropy.len() * 8 + checksum_bits) / Self::BITS_PER_WORD;
+
+        let bit_at = |pos: usize| -> bool {
+            let byte = entropy.get(pos / 8).copied().unwrap_or(checksum);
+            (byte >> (7 - pos % 8)) & 1 == 1
+        };
+
+        let words: Vec<&str> = (0..words_count)
+            .map(|word_idx| {
+                let index = (0..Self::BITS_PER_WORD).fold(0_usize, |acc, bit| {
+                    (acc << 1) | bit_at(word_idx * Self::BITS_PER_WORD + bit) as usize
+                });
+                ENGLISH_WORDS[index]
+            })
+            .collect();
+
+        Ok(Mnemonic {
+            phrase: words.join(" "),
+            entropy: entropy.to_vec(),
+        })
+    }
+
+    /// Parses and validates the given mnemonic `phrase`.
+    /// Words must be lowercase English words separated by single spaces.
+    pub fn parse(phrase: &str) -> KeyPairResult<Mnemonic> {
+        let indexes: Zeroizing<Vec<usize>> = phrase
+            .split(' ')
+            .map(Self::word_index)
+            .collect::<Option<Vec<_>>>()
+            .map(Zeroizing::new)
+            .ok_or(KeyPairError::InvalidMnemonic)?;
+
+        let total_bits = indexes.len() * Self::BITS_PER_WORD;
+        let entropy_bits = total_bits * 32 / 33;
+        if total_bits != entropy_bits + entropy_bits / 32 || !Self::is_valid_strength(entropy_bits)
+        {
+            return Err(KeyPairError::InvalidMnemonic);
+        }
+
+        // Unpack the 11-bit word indexes into a bit string: entropy followed by checksum.
+        let mut bytes = Zeroizing::new(vec![0_u8; total_bits.div_ceil(8)]);
+        for (word_idx, index) in indexes.iter().enumerate() {
+            for bit in 0..Self::BITS_PER_WORD {
+                if (index >> (Self::BITS_PER_WORD - 1 - bit)) & 1 == 1 {
+                    let pos = word_idx * Self::BITS_PER_WORD + bit;
+                    bytes[pos / 8] |= 1 << (7 - pos % 8);
+                }
+            }
+        }
+
+        let entropy = &bytes[..entropy_bits / 8];
+        let checksum_bits = total_bits - entropy_bits;
+        let actual_checksum = bytes[entropy_bits / 8] >> (8 - checksum_bits);
+        let expected_checksum = sha256(entropy)[0] >> (8 - checksum_bits);
+        if actual_checksum != expected_checksum {
+            return Err(KeyPairError::InvalidMnemonic);
+        }
+
+        Ok(Mnemonic {
+            phrase: phrase.to_string(),
+            entropy: entropy.to_vec(),
+        })
+    }
+
+    /// Checks if the given `phrase` is a valid mnemonic.
+    pub fn is_valid(phrase: &str) -> bool {
+        Self::parse(phrase).is_ok()
+    }
+
+    /// Checks if the given `word` belongs to the English wordlist.
+    pub fn is_valid_word(word: &str) -> bool {
+        Self::word_index(word).is_some()
+    }
+
+    /// Returns the mnemonic phrase.
+    pub fn phrase(&self) -> &str {
+        &self.phrase
+    }
+
+    /// Returns the entropy encoded in the mnemonic.
+    pub fn entropy(&self) -> &[u8] {
+        &self.entropy
+    }
+
+    /// Derives a 64 byte seed from the mnemonic and the given `passphrase`.
+    ///
+    /// Please note the passphrase is not NFKD normalized,
+    /// the same way as it's done in the C++ `HDWallet`.
+    pub fn to_seed(&self, passphrase: &str) -> H512 {
+        let salt = Zeroizing::new(format!("{}{passphrase}", Self::SALT_PREFIX));
+        let seed = Zeroizing::new(pbkdf2_hmac_sha512(
+            self.phrase.as_bytes(),
+            salt.as_bytes(),
+            Self::SEED_ITERATIONS,
+            H512::len(),
+        ));
+        H512::try_from(seed.as_slice()).expect("Expected exactly 64 bytes")
+    }
+
+    fn is_valid_strength(strength: usize) -> bool {
+        (Self::MIN_STRENGTH..=Self::MAX_STRENGTH).contains(&strength) && strength & 31 == 0
+    }
+
+    fn word_index(word: &str) -> Option<usize> {
+        ENGLISH_WORDS.binary_search(&word).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tw_encoding::hex::ToHex;
+
+    #[test]
+    fn test_wordlist() {
+        assert_eq!(ENGLISH_WORDS.len(), 2048);
+        assert_eq!(ENGLISH_WORDS[0], "abandon");
+        assert_eq!(ENGLISH_WORDS[2047], "zoo");
+    }
+
+    #[test]
+    fn test_generate() {
+        for strength in [128, 160, 192, 224, 256] {
+            let mnemonic = Mnemonic::generate(strength).unwrap();
+            assert_eq!(mnemonic.entropy().len(), strength / 8);
+            assert!(Mnemonic::is_valid(mnemonic.phrase()));
+        }
+    }
+
+    #[test]
+    fn test_generate_invalid_strength() {
+        for strength in [0, 64, 129, 288, 512] {
+            assert_eq!(
+                Mnemonic::generate(strength).err(),
+                Some(KeyPairError::InvalidMnemonic)
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_entropy() {
+        let mnemonic = Mnemonic::parse(
+            "history step cheap card humble screen raise seek robot slot coral roof spoil wreck caution",
+        )
+        .unwrap();
+        assert_eq!(
+            mnemonic.entropy().to_hex(),
+            "6c3aac9b9146ef832c4e18bb3980c0dddd25fc49"
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        let invalid = [
+            "",
+            "THIS IS AN INVALID MNEMONIC",
+            // Invalid checksum.
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon",
+            // Double space.
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon  about",
+            // Uppercase.
+            "Abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+            // Spanish.
+            "llanto radical atraer riesgo actuar masa fondo cielo dieta archivo sonrisa mamut",
+            // Too short.
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon",
+        ];
+        for phrase in invalid {
+            assert!(!Mnemonic::is_valid(phrase), "{phrase}");
+        }
+    }
+
+    #[test]
+    fn test_is_valid_word() {
+        assert!(Mnemonic::is_valid_word("ripple"));
+        assert!(!Mnemonic::is_valid_word("ripp"));
+        assert!(!Mnemonic::is_valid_word("Ripple"));
+    }
+}
@@ -0,0 +1,170 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+//! Cardano BIP32-Ed25519 key derivation with the Icarus master key generation scheme.
+//! https://github.com/cardano-foundation/CIPs/blob/master/CIP-0003/Icarus.md
+//! https://input-output-hk.github.io/adrestia/static/Ed25519_BIP.pdf
+
+use crate::ed25519::secret::ExpandedSecretKey;
+use crate::ed25519::sha512::PublicKey;
+use crate::{KeyPairError, KeyPairResult};
+use derivation_path::{ChildIndex, DerivationPath};
+use sha2::Sha512;
+use tw_hash::hmac::hmac_sha512;
+use tw_hash::pbkdf2::pbkdf2_hmac_sha512;
+use tw_hash::{H256, H512};
+use zeroize::{ZeroizeOnDrop, Zeroizing};
+
+/// Cardano staking keys are derived at `m/purpose'/coin'/account'/2/0`.
+const STAKING_CHAIN: u32 = 2;
+const STAKING_ADDRESS_INDEX: u32 = 0;
+
+const ICARUS_PBKDF2_ITERATIONS: u32 = 4096;
+
+/// Derives a 192 byte Cardano extended private key at the given `path`:
+/// a spending extended key followed by a staking extended key of the same account.
+///
+/// The `path` must be at least `m/purpose'/coin'/account'/chain/...` with the `chain` either 0 or 1.
+pub(crate) fn derive_extended_key(
+    entropy: &[u8],
+    path: &DerivationPath,
+) -> KeyPairResult<Zeroizing<Vec<u8>>> {
+    let indexes = path.path();
+    if indexes.len() < 4 || indexes[3].to_u32() > 1 {
+        return Err(KeyPairError::InvalidDerivationPath);
+    }
+
+    let mut staking_indexes = indexes.to_vec();
+    staking_indexes[3] = with_value(indexes[3], STAKING_CHAIN);
+    if let Some(address_index) = staking_indexes.get_mut(4) {
+        *address_index = with_value(*address_index, STAKING_ADDRESS_INDEX);
+    }
+
+    let master = CardanoNode::from_entropy_icarus(entropy);
+    let spending = master.derive(indexes);
+    let staking = master.derive(&staking_indexes);
+
+    let mut res = spending.to_zeroizing_vec();
+    res.extend_from_slice(&staking.to_zeroizing_vec());
+    Ok(res)
+}
+
+/// Replaces the index value keeping its hardened flag.
+fn with_value(index: ChildIndex, value: u32) -> ChildIndex {
+    match index {
+        ChildIndex::Normal(_) => ChildIndex::Normal(value),
+        ChildIndex::Hardened(_) => ChildIndex::Hardened(value),
+    }
+}
+
+/// An extended BIP32-Ed25519 node: `kL || kR` extended secret and the chain code.
+#[derive(ZeroizeOnDrop)]
+struct CardanoNode {
+    secret: H256,
+    extension: H256,
+    chain_code: H256,
+}
+
+impl CardanoNode {
+    /// Generates the root node from BIP39 entropy with an empty passphrase
+    /// as it's done in the C++ `HDWallet`.
+    fn from_entropy_icarus(entropy: &[u8]) -> CardanoNode {
+        let secret = Zeroizing::new(pbkdf2_hmac_sha512(
+            &[],
+            entropy,
+            ICARUS_PBKDF2_ITERATIONS,
+            96,
+        ));
+
+        let mut node = CardanoNode {
+            secret: H256::try_from(&secret[0..32]).expect("Expected exactly 32 bytes"),
+            extension: H256::try_from(&secret[32..64]).expect("Expected exactly 32 bytes"),
+            chain_code: H256::try_from(&secret[64..96]).expect("Expected exactly 32 bytes"),
+        };
+        node.secret[0] &= 0xf8;
+        node.secret[31] &= 0x1f;
+        node.secret[31] |= 0x40;
+        node
+    }
+
+    fn derive(&self, indexes: &[ChildIndex]) -> CardanoNode {
+        let mut node = CardanoNode {
+            secret: self.secret,
+            extension: self.extension,
+            chain_code: self.chain_code,
+        };
+        for index in indexes {
+            node = node.derive_child(*index);
+        }
+        node
+    }
+
+    /// Ported: https://github.com/trustwallet/wallet-core/blob/master/trezor-crypto/crypto/cardano.c
+    fn derive_child(&self, index: ChildIndex) -> CardanoNode {
+        let index_bytes = index.to_bits().to_le_bytes();
+
+        let (key_tag, chain_code_tag) = if index.is_hardened() { (0, 1) } else { (2, 3) };
+        let mut data = Zeroizing::new(Vec::with_capacity(1 + 64 + 4));
+        data.push(key_tag);
+        if index.is_hardened() {
+            data.extend_from_slice(self.secret.as_slice());
+            data.extend_from_slice(self.extension.as_slice());
+        } else {
+            data.extend_from_slice(self.public_key().as_slice());
+        }
+        data.extend_from_slice(&index_bytes);
+
+        let z = Zeroizing::new(hmac_sha512(self.chain_code.as_slice(), &data));
+        let z = Zeroizing::new(H512::try_from(z.as_slice()).expect("Expected exactly 64 bytes"));
+        let (zl, zr): (H256, H256) = z.split();
+
+        // kL = 8 * zL[0..28] + parent kL
+        let mut zl8 = H256::default();
+        let mut carry = 0_u8;
+        for i in 0..28 {
+            zl8[i] = (zl[i] << 3) | carry;
+            carry = zl[i] >> 5;
+        }
+        zl8[28] = carry;
+        let secret = add_256_bits(&zl8, &self.secret);
+        // kR = zR + parent kR
+        let extension = add_256_bits(&zr, &self.extension);
+
+        data[0] = chain_code_tag;
+        let i = Zeroizing::new(hmac_sha512(self.chain_code.as_slice(), &data));
+        let chain_code = H256::try_from(&i[32..]).expect("Expected exactly 32 bytes");
+
+        CardanoNode {
+            secret,
+            extension,
+            chain_code,
+        }
+    }
+
+    fn public_key(&self) -> H256 {
+        let expanded =
+            ExpandedSecretKey::<Sha512>::with_extended_secret(self.secret, self.extension);
+        PublicKey::with_expanded_secret_no_mangle(&expanded).to_bytes()
+    }
+
+    fn to_zeroizing_vec(&self) -> Zeroizing<Vec<u8>> {
+        let mut res = Vec::with_capacity(H256::len() * 3);
+        res.extend_from_slice(self.secret.as_slice());
+        res.extend_from_slice(self.extension.as_slice());
+        res.extend_from_slice(self.chain_code.as_slice());
+        Zeroizing::new(res)
+    }
+}
+
+/// Adds two 256-bit little-endian numbers modulo `2^256`.
+fn add_256_bits(lhs: &H256, rhs: &H256) -> H256 {
+    let mut res = H256::default();
+    let mut carry = 0_u16;
+    for i in 0..H256::len() {
+        let sum = lhs[i] as u16 + rhs[i] as u16 + carry;
+        res[i] = sum as u8;
+        carry = sum >> 8;
+    }
+    res
+}
@@ -0,0 +1,135 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+//! Hierarchical deterministic wallet: BIP39 mnemonic and seed management,
+//! and BIP32/SLIP-10 key derivation for every [`Curve`].
+
+use crate::tw::{Curve, PrivateKey};
+use crate::{KeyPairError, KeyPairResult};
+use derivation_path::DerivationPath;
+use tw_hash::H512;
+use zeroize::{ZeroizeOnDrop, Zeroizing};
+
+pub mod bip32;
+pub mod bip39;
+mod cardano;
+pub mod slip132;
+mod starkex;
+
+pub use bip32::{ExtendedPrivateKey, ExtendedPublicKey, Slip10Curve};
+pub use bip39::Mnemonic;
+pub use slip132::HDVersion;
+
+/// Represents a hierarchical deterministic wallet generated from a BIP39 mnemonic and a passphrase.
+#[derive(ZeroizeOnDrop)]
+pub struct HDWallet {
+    mnemonic: Mnemonic,
+    passphrase: String,
+    seed: H512,
+}
+
+impl HDWallet {
+    /// Generates a new wallet with a random mnemonic of the given `strength` in bits.
+    pub fn generate(strength: usize, passphrase: &str) -> KeyPairResult<HDWallet> {
+        Ok(Self::with_valid_mnemonic(
+            Mnemonic::generate(strength)?,
+            passphrase,
+        ))
+    }
+
+    /// Creates a wallet from the given BIP39 `mnemonic` phrase.
+    pub fn with_mnemonic(mnemonic: &str, passphrase: &str) -> KeyPairResult<HDWallet> {
+        Ok(Self::with_valid_mnemonic(
+            Mnemonic::parse(mnemonic)?,
+            passphrase,
+        ))
+    }
+
+    /// Creates a wallet from the given mnemonic `entropy`.
+    pub fn with_entropy(entropy: &[u8], passphrase: &str) -> KeyPairResult<HDWallet> {
+        Ok(Self::with_valid_mnemonic(
+            Mnemonic::from_entropy(entropy)?,
+            passphrase,
+        ))
+    }
+
+    /// Returns the mnemonic phrase.
+    pub fn mnemonic(&self) -> &str {
+        self.mnemonic.phrase()
+    }
+
+    /// Returns the passphrase the wallet was created with.
+    pub fn passphrase(&self) -> &str {
+        &self.passphrase
+    }
+
+    /// Returns the entropy encoded in the mnemonic.
+    pub fn entropy(&self) -> &[u8] {
+        self.mnemonic.entropy()
+    }
+
+    /// Returns the 64 byte BIP39 seed.
+    pub fn seed(&self) -> &H512 {
+        &self.seed
+    }
+
+    /// Returns the SLIP-10 master key of the given `curve`.
+    pub fn get_master_key(&self, curve: Slip10Curve) -> KeyPairResult<ExtendedPrivateKey> {
+        ExtendedPrivateKey::new_master(curve, self.seed.as_slice())
+    }
+
+    /// Derives a SLIP-10 extended private key of the given `curve` at the given `path`.
+    pub fn get_extended_key(
+        &self,
+        curve: Slip10Curve,
+        path: &DerivationPath,
+    ) -> KeyPairResult<ExtendedPrivateKey> {
+        self.get_master_key(curve)?.derive(path)
+    }
+
+    /// Derives an extended public key of the given `curve` at the given account `path`,
+    /// e.g. `m/84'/0'/0'`, and serializes it with the SLIP-132 `version`.
+    pub fn get_extended_public_key(
+        &self,
+        curve: Slip10Curve,
+        path: &DerivationPath,
+        version: HDVersion,
+    ) -> KeyPairResult<String> {
+        let extended = self
+            .get_extended_key(curve, path)?
+            .to_extended_public_key(version)?;
+        Ok(extended.to_base58())
+    }
+
+    /// Derives a private key of the given `curve` at the given `path`.
+    /// The result can be used to sign messages with the same `curve`.
+    ///
+    /// * [`Curve::Ed25519ExtendedCardano`] keys are derived from the mnemonic entropy
+    ///   using the Icarus scheme, and consist of the spending and staking extended keys.
+    /// * [`Curve::Starkex`] keys are derived as `secp256k1` keys and then ground to the Stark curve order.
+    pub fn get_key(&self, curve: Curve, path: &DerivationPath) -> KeyPairResult<PrivateKey> {
+        if curve == Curve::Ed25519ExtendedCardano {
+            let key = cardano::derive_extended_key(self.entropy(), path)?;
+            return PrivateKey::new(key.to_vec());
+        }
+
+        let slip10_curve = Slip10Curve::from_curve(curve).ok_or(KeyPairError::InvalidSecretKey)?;
+        let extended = self.get_extended_key(slip10_curve, path)?;
+
+        let secret = Zeroizing::new(match curve {
+            Curve::Starkex => starkex::grind_key(extended.secret()),
+            _ => *extended.secret(),
+        });
+        PrivateKey::new(secret.to_vec())
+    }
+
+    fn with_valid_mnemonic(mnemonic: Mnemonic, passphrase: &str) -> HDWallet {
+        let seed = mnemonic.to_seed(passphrase);
+        HDWallet {
+            mnemonic,
+            passphrase: passphrase.to_string(),
+            seed,
+        }
+    }
+}
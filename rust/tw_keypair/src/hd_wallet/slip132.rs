@@ -0,0 +1,119 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+//! Registered HD version bytes for BIP32 serialized extended keys.
+//! https://github.com/satoshilabs/slips/blob/master/slip-0132.md
+
+/// Registered HD version bytes.
+/// Mirrors the `TWHDVersion` enum.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum HDVersion {
+    // Bitcoin
+    Xpub = 0x0488b21e,
+    Xprv = 0x0488ade4,
+    Ypub = 0x049d7cb2,
+    Yprv = 0x049d7878,
+    Zpub = 0x04b24746,
+    Zprv = 0x04b2430c,
+    Vpub = 0x045f1cf6,
+    Vprv = 0x045f18bc,
+    Tpub = 0x043587cf,
+    Tprv = 0x04358394,
+
+    // Litecoin
+    Ltub = 0x019da462,
+    Ltpv = 0x019d9cfe,
+    Mtub = 0x01b26ef6,
+    Mtpv = 0x01b26792,
+    Ttub = 0x0436f6e1,
+    Ttpv = 0x0436ef7d,
+
+    // Decred
+    Dpub = 0x02fda926,
+    Dprv = 0x02fda4e8,
+
+    // Dogecoin
+    Dgub = 0x02facafd,
+    Dgpv = 0x02fac398,
+}
+
+impl HDVersion {
+    /// Returns an HD version by its version bytes, or `None` if the version is not registered.
+    pub fn from_u32(version: u32) -> Option<HDVersion> {
+        const VERSIONS: [HDVersion; 20] = [
+            HDVersion::Xpub,
+            HDVersion::Xprv,
+            HDVersion::Ypub,
+            HDVersion::Yprv,
+            HDVersion::Zpub,
+            HDVersion::Zprv,
+            HDVersion::Vpub,
+            HDVersion::Vprv,
+            HDVersion::Tpub,
+            HDVersion::Tprv,
+            HDVersion::Ltub,
+            HDVersion::Ltpv,
+            HDVersion::Mtub,
+            HDVersion::Mtpv,
+            HDVersion::Ttub,
+            HDVersion::Ttpv,
+            HDVersion::Dpub,
+            HDVersion::Dprv,
+            HDVersion::Dgub,
+            HDVersion::Dgpv,
+        ];
+        VERSIONS.into_iter().find(|v| *v as u32 == version)
+    }
+
+    /// Returns the version bytes.
+    pub fn to_u32(self) -> u32 {
+        self as u32
+    }
+
+    /// Whether the version is used to serialize extended public keys.
+    pub fn is_public(self) -> bool {
+        matches!(
+            self,
+            HDVersion::Xpub
+                | HDVersion::Ypub
+                | HDVersion::Zpub
+                | HDVersion::Vpub
+                | HDVersion::Tpub
+                | HDVersion::Ltub
+                | HDVersion::Mtub
+                | HDVersion::Ttub
+                | HDVersion::Dpub
+                | HDVersion::Dgub
+        )
+    }
+
+    /// Whether the version is used to serialize extended private keys.
+    pub fn is_private(self) -> bool {
+        !self.is_public()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hd_version_from_u32() {
+        assert_eq!(HDVersion::from_u32(0x0488b21e), Some(HDVersion::Xpub));
+        assert_eq!(HDVersion::from_u32(0x04b2430c), Some(HDVersion::Zprv));
+        assert_eq!(HDVersion::from_u32(0x02fac398), Some(HDVersion::Dgpv));
+        assert_eq!(HDVersion::from_u32(0), None);
+        assert_eq!(HDVersion::from_u32(0x0488b21f), None);
+    }
+
+    #[test]
+    fn test_hd_version_is_public() {
+        assert!(HDVersion::Xpub.is_public());
+        assert!(HDVersion::Ltub.is_public());
+        assert!(!HDVersion::Xpub.is_private());
+        assert!(HDVersion::Zprv.is_private());
+        assert!(!HDVersion::Dgpv.is_public());
+    }
+}
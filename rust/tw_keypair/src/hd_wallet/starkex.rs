@@ -0,0 +1,33 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use ecdsa::elliptic_curve::bigint::{Encoding, U256};
+use tw_hash::sha2::sha256;
+use tw_hash::H256;
+
+/// The order of the Stark curve.
+const STARK_CURVE_N: U256 =
+    U256::from_be_hex("0800000000000010ffffffffffffffffb781126dcae7b2321e66a241adc64d2f");
+/// The largest multiple of [`STARK_CURVE_N`] that fits 256 bits.
+const STARK_DERIVE_BIAS: U256 =
+    U256::from_be_hex("f80000000000020efffffffffffffff738a13b4b920e9411ae6da5f40b0358b1");
+
+/// Grinds a `secp256k1` private key into a valid Stark private key.
+///
+/// Ported: https://github.com/trustwallet/wallet-core/blob/master/src/ImmutableX/StarkKey.cpp
+pub(crate) fn grind_key(seed: &H256) -> H256 {
+    let mut index = 0_u8;
+    let mut key = hash_key_with_index(seed.as_slice(), index);
+    while key >= STARK_DERIVE_BIAS {
+        key = hash_key_with_index(&key.to_be_bytes(), index);
+        index = index.wrapping_add(1);
+    }
+    H256::from(key.wrapping_rem(&STARK_CURVE_N).to_be_bytes())
+}
+
+fn hash_key_with_index(seed: &[u8], index: u8) -> U256 {
+    let mut data = seed.to_vec();
+    data.push(index);
+    U256::from_be_slice(&sha256(&data))
+}
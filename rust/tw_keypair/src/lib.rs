@@ -45,6 +45,7 @@
 pub mod ecdsa;
 pub mod ed25519;
 pub mod ffi;
+pub mod hd_wallet;
 pub mod nacl_crypto_box;
 pub mod rand;
 pub mod schnorr;
@@ -64,6 +65,9 @@ pub enum KeyPairError {
     InvalidSignature,
     InvalidSignMessage,
     InvalidEncryptedMessage,
+    InvalidMnemonic,
+    InvalidDerivationPath,
+    InvalidExtendedKey,
     SignatureVerifyError,
     SigningError,
     InternalError,
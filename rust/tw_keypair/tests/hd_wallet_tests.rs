@@ -0,0 +1,444 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use derivation_path::DerivationPath;
+use std::str::FromStr;
+use tw_encoding::hex::{DecodeHex, ToHex};
+use tw_keypair::hd_wallet::{ExtendedPublicKey, HDVersion, HDWallet, Slip10Curve};
+use tw_keypair::tw::{Curve, PublicKeyType};
+use tw_keypair::KeyPairError;
+
+/// BIP39 test vectors: `[entropy, mnemonic, seed, xprv]`.
+/// https://github.com/trezor/python-mnemonic/blob/master/vectors.json
+const BIP39_VECTORS: &str = include_str!("bip39_vectors.json");
+const BIP39_VECTORS_PASSPHRASE: &str = "TREZOR";
+
+const MNEMONIC_1: &str =
+    "ripple scissors kick mammal hire column oak again sun offer wealth tomorrow wagon turn fatal";
+
+fn derivation_path(path: &str) -> DerivationPath {
+    DerivationPath::from_str(path).unwrap()
+}
+
+#[test]
+fn test_hd_wallet_bip39_vectors() {
+    let vectors: serde_json::Value = serde_json::from_str(BIP39_VECTORS).unwrap();
+    for vector in vectors["english"].as_array().unwrap() {
+        let entropy = vector[0].as_str().unwrap();
+        let mnemonic = vector[1].as_str().unwrap();
+        let seed = vector[2].as_str().unwrap();
+        let xprv = vector[3].as_str().unwrap();
+
+        let wallet = HDWallet::with_mnemonic(mnemonic, BIP39_VECTORS_PASSPHRASE).unwrap();
+        assert_eq!(wallet.entropy().to_hex(), entropy);
+        assert_eq!(wallet.seed().to_hex(), seed);
+
+        let master = wallet.get_master_key(Slip10Curve::Secp256k1).unwrap();
+        assert_eq!(master.to_base58(HDVersion::Xprv).unwrap().as_str(), xprv);
+
+        let wallet =
+            HDWallet::with_entropy(&entropy.decode_hex().unwrap(), BIP39_VECTORS_PASSPHRASE)
+                .unwrap();
+        assert_eq!(wallet.mnemonic(), mnemonic);
+        assert_eq!(wallet.passphrase(), BIP39_VECTORS_PASSPHRASE);
+        assert_eq!(wallet.seed().to_hex(), seed);
+    }
+}
+
+#[test]
+fn test_hd_wallet_generate() {
+    let wallet = HDWallet::generate(128, "passphrase").unwrap();
+    assert_eq!(wallet.entropy().len(), 16);
+    assert_eq!(wallet.passphrase(), "passphrase");
+
+    let restored = HDWallet::with_mnemonic(wallet.mnemonic(), "passphrase").unwrap();
+    assert_eq!(restored.seed(), wallet.seed());
+
+    let wallet = HDWallet::generate(256, "").unwrap();
+    assert_eq!(wallet.entropy().len(), 32);
+}
+
+#[test]
+fn test_hd_wallet_invalid() {
+    for strength in [64, 129, 512] {
+        assert_eq!(
+            HDWallet::generate(strength, "").err(),
+            Some(KeyPairError::InvalidMnemonic)
+        );
+    }
+    assert_eq!(
+        HDWallet::with_mnemonic("THIS IS AN INVALID MNEMONIC", "").err(),
+        Some(KeyPairError::InvalidMnemonic)
+    );
+    assert_eq!(
+        HDWallet::with_entropy(&"123456".decode_hex().unwrap(), "").err(),
+        Some(KeyPairError::InvalidMnemonic)
+    );
+}
+
+#[test]
+fn test_hd_wallet_with_mnemonic_passphrase() {
+    let wallet = HDWallet::with_mnemonic(MNEMONIC_1, "passphrase").unwrap();
+    assert_eq!(
+        wallet.entropy().to_hex(),
+        "ba5821e8c356c05ba5f025d9532fe0f21f65d594"
+    );
+    assert_eq!(wallet.seed().to_hex(), "143cd5fc27ae46eb423efebc41610473f5e24a80f2ca2e2fa7bf167e537f58f4c68310ae487fce82e25bad29bab2530cf77fd724a5ebfc05a45872773d7ee2d6");
+
+    let wallet = HDWallet::with_mnemonic(MNEMONIC_1, "").unwrap();
+    assert_eq!(wallet.seed().to_hex(), "354c22aedb9a37407adc61f657a6f00d10ed125efa360215f36c6919abd94d6dbc193a5f9c495e21ee74118661e327e84a5f5f11fa373ec33b80897d4697557d");
+}
+
+#[test]
+fn test_hd_wallet_get_key_secp256k1_nist256p1() {
+    let wallet = HDWallet::with_mnemonic(MNEMONIC_1, "").unwrap();
+    let path = derivation_path("m/44'/539'/0'/0/0");
+
+    let key = wallet.get_key(Curve::Secp256k1, &path).unwrap();
+    assert_eq!(
+        key.key().to_hex(),
+        "4fb8657d6464adcaa086d6758d7f0b6b6fc026c98dc1671fcc6460b5a74abc62"
+    );
+
+    let key = wallet.get_key(Curve::Nist256p1, &path).unwrap();
+    assert_eq!(
+        key.key().to_hex(),
+        "a13df52d5a5b438bbf921bbf86276e4347fe8e2f2ed74feaaee12b77d6d26f86"
+    );
+}
+
+#[test]
+fn test_hd_wallet_get_key_leading_zeros() {
+    let wallet = HDWallet::with_mnemonic(
+        "name dash bleak force moral disease shine response menu rescue more will",
+        "",
+    )
+    .unwrap();
+    // https://blog.polychainlabs.com/bitcoin,/bip32,/bip39,/kdf/2021/05/17/inconsistent-bip32-derivations.html
+    let key = wallet
+        .get_key(Curve::Secp256k1, &derivation_path("m/44'/60'"))
+        .unwrap();
+    let public = key
+        .get_public_key_by_type(PublicKeyType::Secp256k1Extended)
+        .unwrap()
+        .to_bytes();
+    let address = tw_hash::sha3::keccak256(&public[1..]).split_off(12);
+    assert_eq!(address.to_hex(), "0ba17e928471c64aaeaf3abfb3900ef4c27b380d");
+}
+
+#[test]
+fn test_hd_wallet_get_key_ed25519() {
+    let wallet = HDWallet::with_mnemonic(MNEMONIC_1, "").unwrap();
+    let key = wallet
+        .get_key(Curve::Ed25519, &derivation_path("m/44'/637'/0'/0'/0'"))
+        .unwrap();
+    assert_eq!(
+        key.key().to_hex(),
+        "7f2634c0e2414a621e96e39c41d09021700cee12ee43328ed094c5580cd0bd6f"
+    );
+    let public = key.get_public_key_by_type(PublicKeyType::Ed25519).unwrap();
+    assert_eq!(
+        public.to_bytes().to_hex(),
+        "633e5c7e355bdd484706436ce1f06fdf280bd7c2229a7f9b6489684412c6967c"
+    );
+}
+
+#[test]
+fn test_hd_wallet_get_key_ed25519_blake2b_nano() {
+    let wallet = HDWallet::with_mnemonic(
+        "edge defense waste choose enrich upon flee junk siren film clown finish luggage leader kid quick brick print evidence swap drill paddle truly occur",
+        "some password",
+    )
+    .unwrap();
+    let key = wallet
+        .get_key(Curve::Ed25519Blake2bNano, &derivation_path("m/44'/165'/0'"))
+        .unwrap();
+    assert_eq!(
+        key.key().to_hex(),
+        "3be4fc2ef3f3b7374e6fc4fb6e7bb153f8a2998b3b3dab50853eabe128024143"
+    );
+    let public = key
+        .get_public_key_by_type(PublicKeyType::Ed25519Blake2b)
+        .unwrap();
+    assert_eq!(
+        public.to_bytes().to_hex(),
+        "5b65b0e8173ee0802c2c3e6c9080d1a16b06de1176c938a924f58670904e82c4"
+    );
+}
+
+#[test]
+fn test_hd_wallet_get_key_extended_cardano() {
+    let wallet = HDWallet::with_mnemonic(
+        "cost dash dress stove morning robust group affair stomach vacant route volume yellow salute laugh",
+        "",
+    )
+    .unwrap();
+    assert_eq!(
+        wallet.entropy().to_hex(),
+        "30a6f50aeb58ff7699b822d63e0ef27aeff17d9f"
+    );
+
+    let key = wallet
+        .get_key(
+            Curve::Ed25519ExtendedCardano,
+            &derivation_path("m/1852'/1815'/0'/0/0"),
+        )
+        .unwrap();
+    assert_eq!(
+        key.extended_cardano_key().unwrap().to_hex(),
+        "e8c8c5b2df13f3abed4e6b1609c808e08ff959d7e6fc3d849e3f2880550b574437aa559095324d78459b9bb2da069da32337e1cc5da78f48e1bd084670107f3110f3245ddf9132ecef98c670272ef39c03a232107733d4a1d28cb53318df26fae0d152bb611cb9ff34e945e4ff627e6fba81da687a601a879759cd76530b5744424db69a75edd4780a5fbc05d1a3c84ac4166ff8e424808481dd8e77627ce5f5bf2eea84515a4e16c4ff06c92381822d910b5cbf9e9c144e1fb76a6291af7276"
+    );
+    let public = key
+        .get_public_key_by_type(PublicKeyType::Ed25519ExtendedCardano)
+        .unwrap();
+    assert_eq!(
+        public.to_bytes().to_hex(),
+        "fafa7eb4146220db67156a03a5f7a79c666df83eb31abbfbe77c85e06d40da3110f3245ddf9132ecef98c670272ef39c03a232107733d4a1d28cb53318df26faf4b8d5201961e68f2e177ba594101f513ee70fe70a41324e8ea8eb787ffda6f4bf2eea84515a4e16c4ff06c92381822d910b5cbf9e9c144e1fb76a6291af7276"
+    );
+
+    // The chain index must be either 0 (external) or 1 (internal).
+    assert_eq!(
+        wallet
+            .get_key(
+                Curve::Ed25519ExtendedCardano,
+                &derivation_path("m/1852'/1815'/0'/2/0"),
+            )
+            .err(),
+        Some(KeyPairError::InvalidDerivationPath)
+    );
+    assert_eq!(
+        wallet
+            .get_key(
+                Curve::Ed25519ExtendedCardano,
+                &derivation_path("m/1852'/1815'/0'"),
+            )
+            .err(),
+        Some(KeyPairError::InvalidDerivationPath)
+    );
+}
+
+#[test]
+fn test_hd_wallet_get_key_starkex() {
+    // https://github.com/starkware-libs/starkware-crypto-utils/blob/d3a1e655105afd66ebc07f88a179a3042407cc7b/test/js/key_derivation.spec.js#L20
+    let wallet = HDWallet::with_mnemonic(
+        "range mountain blast problem vibrant void vivid doctor cluster enough melody salt layer language laptop boat major space monkey unit glimpse pause change vibrant",
+        "",
+    )
+    .unwrap();
+    let path = derivation_path("m/2645'/579218131'/891216374'/1961790679'/2135936222'/0");
+    let key = wallet.get_key(Curve::Starkex, &path).unwrap();
+    assert_eq!(
+        key.key().to_hex(),
+        "06cf0a8bf113352eb863157a45c5e5567abb34f8d32cddafd2c22aa803f4892c"
+    );
+    let public = key.get_public_key_by_type(PublicKeyType::Starkex).unwrap();
+    assert_eq!(
+        public.to_bytes().to_hex(),
+        "02d2bbdc1adaf887b0027cdde2113cfd81c60493aa6dc15d7887ddf1a82bc831"
+    );
+
+    let wallet = HDWallet::with_mnemonic(
+        "owner erupt swamp room swift final allow unaware hint identify figure cotton",
+        "",
+    )
+    .unwrap();
+    let path = derivation_path("m/2645'/579218131'/211006541'/1195162785'/289656960'/1");
+    let key = wallet.get_key(Curve::Starkex, &path).unwrap();
+    assert_eq!(
+        key.key().to_hex(),
+        "02d037bb9c1302295c2f9fa66bcc4ab8e353a3140600a390598777d69c1bc71a"
+    );
+}
+
+#[test]
+fn test_hd_wallet_sign_with_derived_key() {
+    let wallet = HDWallet::with_mnemonic(MNEMONIC_1, "").unwrap();
+    let path = derivation_path("m/44'/60'/0'/0/0");
+    let key = wallet.get_key(Curve::Secp256k1, &path).unwrap();
+
+    let hash = "1c6aa1b2ad2a9e3d0a2de4a8d5c2a0c28a1e1a7b57e62f7b35d9f06ea8ba5a1d"
+        .decode_hex()
+        .unwrap();
+    let signature = key.sign(&hash, Curve::Secp256k1).unwrap();
+    assert_eq!(signature.len(), 65);
+}
+
+#[test]
+fn test_hd_wallet_get_master_key() {
+    let wallet = HDWallet::with_mnemonic(MNEMONIC_1, "").unwrap();
+    let master = wallet.get_master_key(Slip10Curve::Secp256k1).unwrap();
+    assert_eq!(master.depth(), 0);
+    assert_eq!(master.child_number(), 0);
+    assert!(master.parent_fingerprint().is_zero());
+
+    let account = wallet
+        .get_extended_key(Slip10Curve::Secp256k1, &derivation_path("m/84'/0'/0'"))
+        .unwrap();
+    assert_eq!(account.depth(), 3);
+    assert_eq!(account.child_number(), 0x8000_0000);
+}
+
+#[test]
+fn test_hd_wallet_get_extended_keys() {
+    let wallet = HDWallet::with_mnemonic(
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+        "",
+    )
+    .unwrap();
+
+    let account = |path: &str| {
+        wallet
+            .get_extended_key(Slip10Curve::Secp256k1, &derivation_path(path))
+            .unwrap()
+    };
+    let xpub = |path: &str, version: HDVersion| {
+        wallet
+            .get_extended_public_key(Slip10Curve::Secp256k1, &derivation_path(path), version)
+            .unwrap()
+    };
+
+    assert_eq!(
+        account("m/44'/0'/0'").to_base58(HDVersion::Xprv).unwrap().as_str(),
+        "xprv9xpXFhFpqdQK3TmytPBqXtGSwS3DLjojFhTGht8gwAAii8py5X6pxeBnQ6ehJiyJ6nDjWGJfZ95WxByFXVkDxHXrqu53WCRGypk2ttuqncb"
+    );
+    assert_eq!(
+        xpub("m/44'/0'/0'", HDVersion::Xpub),
+        "xpub6BosfCnifzxcFwrSzQiqu2DBVTshkCXacvNsWGYJVVhhawA7d4R5WSWGFNbi8Aw6ZRc1brxMyWMzG3DSSSSoekkudhUd9yLb6qx39T9nMdj"
+    );
+    assert_eq!(
+        account("m/49'/0'/0'").to_base58(HDVersion::Yprv).unwrap().as_str(),
+        "yprvAHwhK6RbpuS3dgCYHM5jc2ZvEKd7Bi61u9FVhYMpgMSuZS613T1xxQeKTffhrHY79hZ5PsskBjcc6C2V7DrnsMsNaGDaWev3GLRQRgV7hxF"
+    );
+    assert_eq!(
+        xpub("m/49'/0'/0'", HDVersion::Ypub),
+        "ypub6Ww3ibxVfGzLrAH1PNcjyAWenMTbbAosGNB6VvmSEgytSER9azLDWCxoJwW7Ke7icmizBMXrzBx9979FfaHxHcrArf3zbeJJJUZPf663zsP"
+    );
+    assert_eq!(
+        xpub("m/84'/0'/0'", HDVersion::Zpub),
+        "zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs"
+    );
+    assert_eq!(
+        xpub("m/84'/0'/1'", HDVersion::Zpub),
+        "zpub6rFR7y4Q2AijF6Gk1bofHLs1d66hKFamhXWdWBup1Em25wfabZqkDqvaieV63fDQFaYmaatCG7jVNUpUiM2hAMo6SAVHcrUpSnHDpNzucB7"
+    );
+
+    // Versions and curves mismatch.
+    assert!(account("m/44'/0'/0'").to_base58(HDVersion::Xpub).is_err());
+    assert!(account("m/44'/0'/0'")
+        .to_extended_public_key(HDVersion::Xprv)
+        .is_err());
+    assert!(wallet
+        .get_extended_public_key(
+            Slip10Curve::Ed25519,
+            &derivation_path("m/44'/501'/0'"),
+            HDVersion::Xpub
+        )
+        .is_err());
+}
+
+#[test]
+fn test_extended_public_key_derive() {
+    // Derive_XpubPub_vs_PrivPub
+    let wallet = HDWallet::with_mnemonic(MNEMONIC_1, "").unwrap();
+    let zpub = wallet
+        .get_extended_public_key(
+            Slip10Curve::Secp256k1,
+            &derivation_path("m/84'/0'/0'"),
+            HDVersion::Zpub,
+        )
+        .unwrap();
+    assert_eq!(zpub, "zpub6rNUNtxSa9Gxvm4Bdxf1MPMwrvkzwDx6vP96Hkzw3jiQKdg3fhXBStxjn12YixQB8h88B3RMSRscRstf9AEVaYr3MAqVBEWBDuEJU4PGaT9");
+
+    let account = ExtendedPublicKey::from_base58(Slip10Curve::Secp256k1, &zpub).unwrap();
+    assert_eq!(account.version(), HDVersion::Zpub);
+    assert_eq!(account.depth(), 3);
+    assert_eq!(account.to_base58(), zpub);
+
+    for path in ["m/84'/0'/0'/0/0", "m/84'/0'/0'/0/2", "m/84'/0'/0'/1/7"] {
+        let path = derivation_path(path);
+        let expected = wallet
+            .get_extended_key(Slip10Curve::Secp256k1, &path)
+            .unwrap();
+        let actual = account.derive(&path.path()[3..]).unwrap();
+
+        assert_eq!(actual.public_key(), &expected.public_key().unwrap());
+        assert_eq!(actual.chain_code(), expected.chain_code());
+        assert_eq!(actual.parent_fingerprint(), expected.parent_fingerprint());
+        assert_eq!(actual.child_number(), expected.child_number());
+        assert_eq!(actual.depth(), 5);
+    }
+
+    let first = account.derive(derivation_path("m/0/0").path()).unwrap();
+    assert_eq!(
+        first.public_key().to_hex(),
+        "02df9ef2a7a5552765178b181e1e1afdefc7849985c7dfe9647706dd4fa40df6ac"
+    );
+}
+
+#[test]
+fn test_extended_public_key_from_xpub_ypub() {
+    let derive = |xpub: &str, path: &str| {
+        ExtendedPublicKey::from_base58(Slip10Curve::Secp256k1, xpub)
+            .unwrap()
+            .derive(derivation_path(path).path())
+            .unwrap()
+            .public_key()
+            .to_hex()
+    };
+
+    // PublicKeyFromX
+    let xpub = "xpub6BosfCnifzxcFwrSzQiqu2DBVTshkCXacvNsWGYJVVhhawA7d4R5WSWGFNbi8Aw6ZRc1brxMyWMzG3DSSSSoekkudhUd9yLb6qx39T9nMdj";
+    assert_eq!(
+        derive(xpub, "m/0/2"),
+        "0338994349b3a804c44bbec55c2824443ebb9e475dfdad14f4b1a01a97d42751b3"
+    );
+    assert_eq!(
+        derive(xpub, "m/0/9"),
+        "03786c1d274f2c804ff9a57d8e7289c281d4aef15e17187ad9f9c3722d81a6ae66"
+    );
+
+    // PublicKeyFromY
+    let ypub = "ypub6Ww3ibxVfGzLrAH1PNcjyAWenMTbbAosGNB6VvmSEgytSER9azLDWCxoJwW7Ke7icmizBMXrzBx9979FfaHxHcrArf3zbeJJJUZPf663zsP";
+    assert_eq!(
+        derive(ypub, "m/0/3"),
+        "0299bd0bdc081a9888fac95a33e8bebcdeeb57cf7477f2f0721362f3a51a157227"
+    );
+    assert_eq!(
+        derive(ypub, "m/0/10"),
+        "03a39ad9c0d19bb43c45643582614298c96b0f7c9462c0de789c69013b0d609d1c"
+    );
+}
+
+#[test]
+fn test_extended_public_key_invalid() {
+    let xpub = "xpub6BosfCnifzxcFwrSzQiqu2DBVTshkCXacvNsWGYJVVhhawA7d4R5WSWGFNbi8Aw6ZRc1brxMyWMzG3DSSSSoekkudhUd9yLb6qx39T9nMdj";
+    let invalid = [
+        "xpub0000",
+        "",
+        // Invalid checksum.
+        "xpub6BosfCnifzxcFwrSzQiqu2DBVTshkCXacvNsWGYJVVhhawA7d4R5WSWGFNbi8Aw6ZRc1brxMyWMzG3DSSSSoekkudhUd9yLb6qx39T9nMdk",
+        // Extended private key.
+        "xprv9xpXFhFpqdQK3TmytPBqXtGSwS3DLjojFhTGht8gwAAii8py5X6pxeBnQ6ehJiyJ6nDjWGJfZ95WxByFXVkDxHXrqu53WCRGypk2ttuqncb",
+    ];
+    for s in invalid {
+        assert_eq!(
+            ExtendedPublicKey::from_base58(Slip10Curve::Secp256k1, s),
+            Err(KeyPairError::InvalidExtendedKey),
+            "{s}"
+        );
+    }
+
+    for curve in [Slip10Curve::Ed25519, Slip10Curve::Curve25519] {
+        assert_eq!(
+            ExtendedPublicKey::from_base58(curve, xpub),
+            Err(KeyPairError::InvalidExtendedKey)
+        );
+    }
+
+    let account = ExtendedPublicKey::from_base58(Slip10Curve::Secp256k1, xpub).unwrap();
+    assert_eq!(
+        account.derive(derivation_path("m/0'/0").path()),
+        Err(KeyPairError::InvalidDerivationPath)
+    );
+}
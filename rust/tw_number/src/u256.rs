@@ -6,7 +6,7 @@ use crate::{NumberError, NumberResult};
 use std::borrow::Cow;
 use std::fmt;
 use std::fmt::Formatter;
-use std::ops::Add;
+use std::ops::{Add, Shr};
 use std::str::FromStr;
 use tw_hash::H256;
 use tw_memory::Data;
@@ -141,6 +141,19 @@ impl U256 {
             .ok_or(NumberError::IntegerOverflow)
     }
 
+    /// Checked subtraction. Returns `NumberError::IntegerOverflow` if underflow occurred.
+    #[inline]
+    pub fn checked_sub<T>(&self, rhs: T) -> NumberResult<U256>
+    where
+        T: Into<primitive_types::U256>,
+    {
+        let rhs = rhs.into();
+        self.0
+            .checked_sub(rhs)
+            .map(U256)
+            .ok_or(NumberError::IntegerOverflow)
+    }
+
     #[inline]
     fn leading_zero_bytes(&self) -> usize {
         U256::BYTES - (self.0.bits() + 7) / 8
@@ -191,6 +204,15 @@ where
     }
 }
 
+impl Shr<usize> for U256 {
+    type Output = U256;
+
+    #[inline]
+    fn shr(self, rhs: usize) -> Self::Output {
+        U256(self.0 >> rhs)
+    }
+}
+
 #[cfg(feature = "serde")]
 mod impl_serde {
     use super::U256;
@@ -245,6 +267,7 @@ impl_map_from!(U256, u8);
 impl_map_from!(U256, u16);
 impl_map_from!(U256, u32);
 impl_map_from!(U256, u64);
+impl_map_from!(U256, u128);
 impl_map_from!(U256, usize);
 
 #[cfg(test)]
@@ -5,9 +5,10 @@
 use tw_any_coin::test_utils::address_utils::{
     test_address_base58_is_valid, test_address_bech32_is_valid,
     test_address_create_base58_with_public_key, test_address_create_bech32_with_public_key,
-    test_address_get_data, test_address_invalid, test_address_normalization, test_address_valid,
-    AddressBase58IsValid, AddressBech32IsValid, AddressCreateBase58WithPublicKey,
-    AddressCreateBech32WithPublicKey,
+    test_address_create_with_extended_public_key, test_address_get_data, test_address_invalid,
+    test_address_normalization, test_address_valid, AddressBase58IsValid, AddressBech32IsValid,
+    AddressCreateBase58WithPublicKey, AddressCreateBech32WithPublicKey,
+    AddressCreateWithExtendedPublicKey,
 };
 use tw_coin_registry::coin_type::CoinType;
 use tw_keypair::tw::PublicKeyType;
@@ -139,3 +140,130 @@ fn test_bitcoin_address_create_bech32_with_public_key() {
         expected: "ltc1qytnqzjknvv03jwfgrsmzt0ycmwqgl0asjnaxwu",
     });
 }
+
+#[test]
+fn test_bitcoin_address_create_with_extended_public_key() {
+    const XPUB: &str = "xpub6BosfCnifzxcFwrSzQiqu2DBVTshkCXacvNsWGYJVVhhawA7d4R5WSWGFNbi8Aw6ZRc1brxMyWMzG3DSSSSoekkudhUd9yLb6qx39T9nMdj";
+    const YPUB: &str = "ypub6Ww3ibxVfGzLrAH1PNcjyAWenMTbbAosGNB6VvmSEgytSER9azLDWCxoJwW7Ke7icmizBMXrzBx9979FfaHxHcrArf3zbeJJJUZPf663zsP";
+    const ZPUB: &str = "zpub6rNUNtxSa9Gxvm4Bdxf1MPMwrvkzwDx6vP96Hkzw3jiQKdg3fhXBStxjn12YixQB8h88B3RMSRscRstf9AEVaYr3MAqVBEWBDuEJU4PGaT9";
+    const TPUB: &str = "tpubDC5FSnBiZDMmhiuCmWAYsLwgLYrrT9rAqvTySfuCCrgsWz8wxMXUS9Tb9iVMvcRbvFcAHGkMD5Kx8koh4GquNGNTfohfk7pgjhaPCdXpoba";
+    const VPUB: &str = "vpub5Y6cjg78GGuNLsaPhmYsiw4gYX3HoQiRBiSwDaBXKUafCt9bNwWQiitDk5VZ5BVxYnQdwoTyXSs2JHRPAgjAvtbBrf8ZhDYe2jWAqvZVnsc";
+
+    // xpub: P2PKH addresses.
+    test_address_create_with_extended_public_key(AddressCreateWithExtendedPublicKey {
+        coin: CoinType::Bitcoin,
+        extended_public_key: XPUB,
+        derivation_path: "m/44'/0'/0'/0/2",
+        expected: Some("1MNF5RSaabFwcbtJirJwKnDytsXXEsVsNb"),
+    });
+    test_address_create_with_extended_public_key(AddressCreateWithExtendedPublicKey {
+        coin: CoinType::Bitcoin,
+        extended_public_key: XPUB,
+        derivation_path: "m/44'/0'/0'/0/9",
+        expected: Some("1DUrqK4hj6vNNUTWXADpbqyjVWUYFD7xTZ"),
+    });
+
+    // zpub: P2WPKH addresses.
+    test_address_create_with_extended_public_key(AddressCreateWithExtendedPublicKey {
+        coin: CoinType::Bitcoin,
+        extended_public_key: ZPUB,
+        derivation_path: "m/84'/0'/0'/0/0",
+        expected: Some("bc1qpsp72plnsqe6e2dvtsetxtww2cz36ztmfxghpd"),
+    });
+    test_address_create_with_extended_public_key(AddressCreateWithExtendedPublicKey {
+        coin: CoinType::Bitcoin,
+        extended_public_key: ZPUB,
+        derivation_path: "m/84'/0'/0'/0/2",
+        expected: Some("bc1q7zddsunzaftf4zlsg9exhzlkvc5374a6v32jf6"),
+    });
+
+    // ypub: P2WPKH-nested-in-P2SH addresses.
+    test_address_create_with_extended_public_key(AddressCreateWithExtendedPublicKey {
+        coin: CoinType::Bitcoin,
+        extended_public_key: YPUB,
+        derivation_path: "m/49'/0'/0'/0/0",
+        expected: Some("37VucYSaXLCAsxYyAPfbSi9eh4iEcbShgf"),
+    });
+    test_address_create_with_extended_public_key(AddressCreateWithExtendedPublicKey {
+        coin: CoinType::Bitcoin,
+        extended_public_key: YPUB,
+        derivation_path: "m/49'/0'/0'/1/0",
+        expected: Some("34K56kSjgUCUSD8GTtuF7c9Zzwokbs6uZ7"),
+    });
+
+    // tpub: testnet P2PKH addresses.
+    test_address_create_with_extended_public_key(AddressCreateWithExtendedPublicKey {
+        coin: CoinType::Bitcoin,
+        extended_public_key: TPUB,
+        derivation_path: "m/44'/1'/0'/0/0",
+        expected: Some("mkpZhYtJu2r87Js3pDiWJDmPte2NRZ8bJV"),
+    });
+    test_address_create_with_extended_public_key(AddressCreateWithExtendedPublicKey {
+        coin: CoinType::Bitcoin,
+        extended_public_key: TPUB,
+        derivation_path: "m/44'/1'/0'/0/1",
+        expected: Some("mzpbWabUQm1w8ijuJnAof5eiSTep27deVH"),
+    });
+
+    // vpub: testnet P2WPKH addresses.
+    test_address_create_with_extended_public_key(AddressCreateWithExtendedPublicKey {
+        coin: CoinType::Bitcoin,
+        extended_public_key: VPUB,
+        derivation_path: "m/84'/1'/0'/0/0",
+        expected: Some("tb1q6rz28mcfaxtmd6v789l9rrlrusdprr9pqcpvkl"),
+    });
+    test_address_create_with_extended_public_key(AddressCreateWithExtendedPublicKey {
+        coin: CoinType::Bitcoin,
+        extended_public_key: VPUB,
+        derivation_path: "m/84'/1'/0'/1/0",
+        expected: Some("tb1q9u62588spffmq4dzjxsr5l297znf3z6j5p2688"),
+    });
+    // The testnet bech32 prefix is taken from the coin registry entry.
+    test_address_create_with_extended_public_key(AddressCreateWithExtendedPublicKey {
+        coin: CoinType::Litecoin,
+        extended_public_key: VPUB,
+        derivation_path: "m/84'/1'/0'/0/0",
+        expected: Some("tltc1q6rz28mcfaxtmd6v789l9rrlrusdprr9pesrjxk"),
+    });
+    // Dogecoin has no testnet bech32 prefix.
+    test_address_create_with_extended_public_key(AddressCreateWithExtendedPublicKey {
+        coin: CoinType::Dogecoin,
+        extended_public_key: VPUB,
+        derivation_path: "m/84'/1'/0'/0/0",
+        expected: None,
+    });
+
+    // Hardened indexes cannot be derived from an extended public key.
+    test_address_create_with_extended_public_key(AddressCreateWithExtendedPublicKey {
+        coin: CoinType::Bitcoin,
+        extended_public_key: ZPUB,
+        derivation_path: "m/84'/0'/0'/0'/0",
+        expected: None,
+    });
+    // The account levels of the path do not match the extended key depth and index.
+    test_address_create_with_extended_public_key(AddressCreateWithExtendedPublicKey {
+        coin: CoinType::Bitcoin,
+        extended_public_key: ZPUB,
+        derivation_path: "m/84'/0'/1'/0/0",
+        expected: None,
+    });
+    test_address_create_with_extended_public_key(AddressCreateWithExtendedPublicKey {
+        coin: CoinType::Bitcoin,
+        extended_public_key: ZPUB,
+        derivation_path: "m/84'/0'/0/0",
+        expected: None,
+    });
+    test_address_create_with_extended_public_key(AddressCreateWithExtendedPublicKey {
+        coin: CoinType::Bitcoin,
+        extended_public_key: ZPUB,
+        derivation_path: "m/84'/0'",
+        expected: None,
+    });
+
+    test_address_create_with_extended_public_key(AddressCreateWithExtendedPublicKey {
+        coin: CoinType::Bitcoin,
+        extended_public_key: "xpub0000",
+        derivation_path: "m/44'/0'/0'/0/0",
+        expected: None,
+    });
+}
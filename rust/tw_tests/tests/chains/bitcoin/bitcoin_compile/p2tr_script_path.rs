@@ -0,0 +1,148 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::chains::common::bitcoin::{
+    btc_info, compile, dust_threshold, input, output, preimage, TransactionOneof, DUST, SIGHASH_ALL,
+};
+use tw_any_coin::test_utils::sign_utils::CompilerHelper;
+use tw_coin_registry::coin_type::CoinType;
+use tw_hash::H256;
+use tw_keypair::schnorr;
+use tw_keypair::traits::SigningKeyTrait;
+use tw_misc::traits::ToBytesVec;
+use tw_proto::BitcoinV2::Proto;
+use tw_proto::Common::Proto::SigningError;
+use tw_utxo::script::standard_script::conditions;
+
+const ALICE_PRIVATE_KEY: &str = "e253373989199da27c48680e3a3fc0f648d50f9a727ef17a7fe6a4dc3b159129";
+const BOB_PRIVATE_KEY: &str = "b7da1ec42b19085fe09fec54b9d9eacd998ae4e6d2ad472be38d8393391b9ead";
+const CAROL_PRIVATE_KEY: &str = "3c3385ddc6fd95ba7282051aeb440bc75820b8c10db5c83c052d7586e3e98e84";
+
+fn schnorr_private(private_key: &str) -> schnorr::PrivateKey {
+    schnorr::PrivateKey::try_from(private_key)
+        .unwrap()
+        // Disable aux rand for the test.
+        .no_aux_rand()
+}
+
+/// Spends a Taproot output with a single 2-of-3 `OP_CHECKSIGADD` multisig leaf, signed by Carol and Bob.
+fn p2tr_multisig_signing_input() -> Proto::SigningInput<'static> {
+    let alice_pubkey = schnorr_private(ALICE_PRIVATE_KEY).public();
+    let bob_pubkey = schnorr_private(BOB_PRIVATE_KEY).public();
+    let carol_pubkey = schnorr_private(CAROL_PRIVATE_KEY).public();
+
+    let leaf_script = conditions::new_tapscript_multisig(
+        2,
+        &[
+            alice_pubkey.x_only().bytes(),
+            bob_pubkey.x_only().bytes(),
+            carol_pubkey.x_only().bytes(),
+        ],
+    )
+    .unwrap();
+
+    let txid = "1e1f1bcaa2ee0fc2e5cda6d4f7fba6d4ca5a1d38bbd2ae8e7e8a7cb3a9e1fc53";
+    let tx1 = Proto::Input {
+        out_point: input::out_point(txid, 0),
+        value: 10_000,
+        sighash_type: SIGHASH_ALL,
+        claiming_script: input::p2tr_script_tree(
+            leaf_script.to_vec(),
+            alice_pubkey.to_vec(),
+            vec![(leaf_script.to_vec(), 0)],
+            vec![carol_pubkey.to_vec(), bob_pubkey.to_vec()],
+        ),
+        ..Default::default()
+    };
+
+    let out1 = Proto::Output {
+        value: 9_000,
+        to_recipient: output::p2wpkh(alice_pubkey.to_vec()),
+    };
+
+    let builder = Proto::TransactionBuilder {
+        version: Proto::TransactionVersion::V2,
+        inputs: vec![tx1],
+        outputs: vec![out1],
+        input_selector: Proto::InputSelector::UseAll,
+        dust_policy: dust_threshold(DUST),
+        ..Default::default()
+    };
+
+    Proto::SigningInput {
+        chain_info: btc_info(),
+        transaction: TransactionOneof::builder(builder),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_bitcoin_compile_p2tr_script_path_multisig() {
+    let bob_private_key = schnorr_private(BOB_PRIVATE_KEY);
+    let bob_pubkey = bob_private_key.public();
+    let carol_private_key = schnorr_private(CAROL_PRIVATE_KEY);
+    let carol_pubkey = carol_private_key.public();
+
+    let signing = p2tr_multisig_signing_input();
+
+    // Both signers sign the same sighash with non-tweaked private keys.
+    // Sighashes are ordered as the public keys in the leaf script: Bob first, then Carol.
+    let sighash = H256::from("5b7905f2d5b018bf50fb73561ff421b47741e01477522d7003ac3de652a99df5");
+    preimage::BitcoinPreImageHelper::new(&signing)
+        .coin(CoinType::Bitcoin)
+        .add_expected_taproot(bob_pubkey.to_vec(), sighash.to_vec(), None)
+        .add_expected_taproot(carol_pubkey.to_vec(), sighash.to_vec(), None)
+        .pre_image();
+
+    let bob_signature = bob_private_key.sign(sighash).unwrap();
+    let carol_signature = carol_private_key.sign(sighash).unwrap();
+
+    // The witness contains Carol's and Bob's signatures, and an empty item for Alice.
+    compile::BitcoinCompileHelper::new(&signing)
+        .coin(CoinType::Bitcoin)
+        .add_pubkey_sig(bob_pubkey.to_vec(), bob_signature.to_vec())
+        .add_pubkey_sig(carol_pubkey.to_vec(), carol_signature.to_vec())
+        .compile(compile::Expected {
+            encoded: "0200000000010153fce1a9b37c8a7e8eaed2bb381d5acad4a6fbf7d4a6cde5c20feea2ca1b1f1e0000000000ffffffff012823000000000000160014e311b8d6ddff856ce8e9a4e03bc6d4fe5050a83d0540d9c80d4496fa756df748be179c2ee49a46151e811a12be0e39211964482fbbb31e152e413e473c721f1e939b7a607295d80738955132919b713d94d06aacc0d94014167fde9cca29143936230c4d88663581bf8fdc5ca4beccdbfa39f4834239595929162cf476cecec9ce3843d928ead87c4d8d8f6c533b5b809f734c0bea53a70068200f209b6ada5edb42c77fd2bc64ad650ae38314c8f451f3e36d80bc8e26f132cbac207ed9a436e11ec4947ac4b7823787e24ba73180f1edd2857bff19c9f4d62b65bfba20b85cc59b67c35851eb5060cfc3a759a482254553c5857075c9e247d74d412c91ba529c21c00f209b6ada5edb42c77fd2bc64ad650ae38314c8f451f3e36d80bc8e26f132cb00000000",
+            txid: "96e7071c88f423208bbc3fa17537764a9c5361a4902763a7a4abe3fc0f47e4c0",
+            inputs: vec![10_000],
+            outputs: vec![9_000],
+            vsize: 151,
+            weight: 601,
+            fee: 1_000,
+        });
+}
+
+#[test]
+fn test_bitcoin_compile_p2tr_script_path_multisig_error() {
+    let bob_private_key = schnorr_private(BOB_PRIVATE_KEY);
+    let bob_pubkey = bob_private_key.public();
+    let carol_private_key = schnorr_private(CAROL_PRIVATE_KEY);
+    let carol_pubkey = carol_private_key.public();
+
+    let signing = p2tr_multisig_signing_input();
+    let sighash = H256::from("5b7905f2d5b018bf50fb73561ff421b47741e01477522d7003ac3de652a99df5");
+    let bob_signature = bob_private_key.sign(sighash).unwrap();
+    let carol_signature = carol_private_key.sign(sighash).unwrap();
+
+    // Not enough signatures.
+    let mut compiler = CompilerHelper::<Proto::SigningOutput>::default();
+    let output = compiler.compile(
+        CoinType::Bitcoin,
+        &signing,
+        vec![bob_signature.to_vec()],
+        vec![bob_pubkey.to_vec()],
+    );
+    assert_eq!(output.error, SigningError::Error_signatures_count);
+
+    // Signatures must be ordered as the public keys in the leaf script.
+    let mut compiler = CompilerHelper::<Proto::SigningOutput>::default();
+    let output = compiler.compile(
+        CoinType::Bitcoin,
+        &signing,
+        vec![carol_signature.to_vec(), bob_signature.to_vec()],
+        vec![carol_pubkey.to_vec(), bob_pubkey.to_vec()],
+    );
+    assert_eq!(output.error, SigningError::Error_signing);
+}
@@ -0,0 +1,156 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::chains::common::bitcoin::{
+    btc_info, compile, dust_threshold, input, output, preimage, TransactionOneof, DUST, MINER_FEE,
+    ONE_BTC, SIGHASH_ALL,
+};
+use tw_any_coin::test_utils::sign_utils::CompilerHelper;
+use tw_coin_registry::coin_type::CoinType;
+use tw_encoding::hex::{DecodeHex, ToHex};
+use tw_keypair::ecdsa;
+use tw_keypair::traits::SigningKeyTrait;
+use tw_misc::traits::ToBytesVec;
+use tw_proto::BitcoinV2::Proto;
+use tw_proto::Common::Proto::SigningError;
+use tw_utxo::script::standard_script::conditions;
+
+const ALICE_PRIVATE_KEY: &str = "56429688a1a6b00b90ccd22a0de0a376b6569d8684022ae92229a28478bfb657";
+const BOB_PRIVATE_KEY: &str = "b7da1ec42b19085fe09fec54b9d9eacd998ae4e6d2ad472be38d8393391b9ead";
+const CAROL_PRIVATE_KEY: &str = "3c3385ddc6fd95ba7282051aeb440bc75820b8c10db5c83c052d7586e3e98e84";
+
+/// Spends a 2-of-3 multisig P2WSH output signed by Carol and Bob.
+fn p2wsh_multisig_signing_input() -> Proto::SigningInput<'static> {
+    let alice_pubkey = ecdsa::secp256k1::PrivateKey::try_from(ALICE_PRIVATE_KEY)
+        .unwrap()
+        .public();
+    let bob_pubkey = ecdsa::secp256k1::PrivateKey::try_from(BOB_PRIVATE_KEY)
+        .unwrap()
+        .public();
+    let carol_pubkey = ecdsa::secp256k1::PrivateKey::try_from(CAROL_PRIVATE_KEY)
+        .unwrap()
+        .public();
+
+    let witness_script = conditions::new_multisig(
+        2,
+        &[
+            alice_pubkey.compressed(),
+            bob_pubkey.compressed(),
+            carol_pubkey.compressed(),
+        ],
+    )
+    .unwrap();
+
+    let txid = "dd9d4ca23532f5c89d016e1aacef1210ab5b9d00527c633969841daca7dd17c7";
+    let tx1 = Proto::Input {
+        out_point: input::out_point(txid, 0),
+        value: 50 * ONE_BTC - 3 * MINER_FEE,
+        sighash_type: SIGHASH_ALL,
+        claiming_script: input::p2wsh(
+            witness_script.to_vec(),
+            vec![
+                carol_pubkey.compressed().to_vec(),
+                bob_pubkey.compressed().to_vec(),
+            ],
+        ),
+        ..Default::default()
+    };
+
+    let out1 = Proto::Output {
+        value: 50 * ONE_BTC - 4 * MINER_FEE,
+        to_recipient: output::p2pkh(alice_pubkey.compressed().to_vec()),
+    };
+
+    let builder = Proto::TransactionBuilder {
+        version: Proto::TransactionVersion::V2,
+        inputs: vec![tx1],
+        outputs: vec![out1],
+        input_selector: Proto::InputSelector::UseAll,
+        dust_policy: dust_threshold(DUST),
+        ..Default::default()
+    };
+
+    Proto::SigningInput {
+        chain_info: btc_info(),
+        transaction: TransactionOneof::builder(builder),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_bitcoin_compile_p2wsh_multisig() {
+    let bob_private_key = ecdsa::secp256k1::PrivateKey::try_from(BOB_PRIVATE_KEY).unwrap();
+    let bob_pubkey = bob_private_key.public().compressed();
+    let carol_private_key = ecdsa::secp256k1::PrivateKey::try_from(CAROL_PRIVATE_KEY).unwrap();
+    let carol_pubkey = carol_private_key.public().compressed();
+
+    let signing = p2wsh_multisig_signing_input();
+
+    // Both signers sign the same sighash.
+    // Sighashes are ordered as the public keys in the witness script: Bob first, then Carol.
+    let sighash = "186678ce5763885b5af2acc60177d39d92e56e17f7bf3cc03f5bd1147be42ed2"
+        .decode_hex()
+        .unwrap();
+    preimage::BitcoinPreImageHelper::new(&signing)
+        .coin(CoinType::Bitcoin)
+        .add_expected_segwit(bob_pubkey.to_vec(), sighash.clone())
+        .add_expected_segwit(carol_pubkey.to_vec(), sighash.clone())
+        .pre_image();
+
+    let sighash = sighash.as_slice().try_into().unwrap();
+    let bob_signature = bob_private_key.sign(sighash).unwrap();
+    let carol_signature = carol_private_key.sign(sighash).unwrap();
+    assert_eq!(bob_signature.to_vec().to_hex(), "04caeafd1be3e70693a05af5fb4a6c2b4f9d3620cb8c6ed0c711d5acb71eb6637f27deb6815f03887e5edb865526a08b0061c8d7bdfcfb97c953382fc6aeeef200");
+    assert_eq!(carol_signature.to_vec().to_hex(), "5a16419663116db808e0dfd8ee194ee99929bc19ea217e6baf6c1305f341d94852470c7003a82d6936ca6bf586db8b76756c4c85767d8672387945533f848c4001");
+
+    compile::BitcoinCompileHelper::new(&signing)
+        .coin(CoinType::Bitcoin)
+        .add_pubkey_sig(bob_pubkey.to_vec(), bob_signature.to_vec())
+        .add_pubkey_sig(carol_pubkey.to_vec(), carol_signature.to_vec())
+        .compile(compile::Expected {
+            encoded: "02000000000101c717dda7ac1d846939637c52009d5bab1012efac1a6e019dc8f53235a24c9ddd0000000000ffffffff0100e9c829010000001976a914e4c1ea86373d554b8f4efff2cfb0001ea19124d288ac0400473044022004caeafd1be3e70693a05af5fb4a6c2b4f9d3620cb8c6ed0c711d5acb71eb66302207f27deb6815f03887e5edb865526a08b0061c8d7bdfcfb97c953382fc6aeeef20147304402205a16419663116db808e0dfd8ee194ee99929bc19ea217e6baf6c1305f341d948022052470c7003a82d6936ca6bf586db8b76756c4c85767d8672387945533f848c4001695221036666dd712e05a487916384bfcd5973eb53e8038eccbbf97f7eed775b8738953621037ed9a436e11ec4947ac4b7823787e24ba73180f1edd2857bff19c9f4d62b65bf2103b85cc59b67c35851eb5060cfc3a759a482254553c5857075c9e247d74d412c9153ae00000000",
+            txid: "64a05a69415b2d49c601ee42293207bebb7c5a0c93c2b5fb1527aa5ba9616e76",
+            inputs: vec![50 * ONE_BTC - 3 * MINER_FEE],
+            outputs: vec![50 * ONE_BTC - 4 * MINER_FEE],
+            vsize: 149,
+            weight: 594,
+            fee: MINER_FEE,
+        });
+}
+
+#[test]
+fn test_bitcoin_compile_p2wsh_multisig_error() {
+    let bob_private_key = ecdsa::secp256k1::PrivateKey::try_from(BOB_PRIVATE_KEY).unwrap();
+    let bob_pubkey = bob_private_key.public().compressed();
+    let carol_private_key = ecdsa::secp256k1::PrivateKey::try_from(CAROL_PRIVATE_KEY).unwrap();
+    let carol_pubkey = carol_private_key.public().compressed();
+
+    let signing = p2wsh_multisig_signing_input();
+    let sighash = "186678ce5763885b5af2acc60177d39d92e56e17f7bf3cc03f5bd1147be42ed2"
+        .decode_hex()
+        .unwrap();
+    let sighash = sighash.as_slice().try_into().unwrap();
+    let bob_signature = bob_private_key.sign(sighash).unwrap();
+    let carol_signature = carol_private_key.sign(sighash).unwrap();
+
+    // Not enough signatures.
+    let mut compiler = CompilerHelper::<Proto::SigningOutput>::default();
+    let output = compiler.compile(
+        CoinType::Bitcoin,
+        &signing,
+        vec![bob_signature.to_vec()],
+        vec![bob_pubkey.to_vec()],
+    );
+    assert_eq!(output.error, SigningError::Error_signatures_count);
+
+    // Signatures must be ordered as the public keys in the witness script.
+    let mut compiler = CompilerHelper::<Proto::SigningOutput>::default();
+    let output = compiler.compile(
+        CoinType::Bitcoin,
+        &signing,
+        vec![carol_signature.to_vec(), bob_signature.to_vec()],
+        vec![carol_pubkey.to_vec(), bob_pubkey.to_vec()],
+    );
+    assert_eq!(output.error, SigningError::Error_signing);
+}
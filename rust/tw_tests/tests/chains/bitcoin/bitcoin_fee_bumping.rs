@@ -0,0 +1,256 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::chains::common::bitcoin::{
+    btc_info, input, output, sign, TransactionOneof, DUST, SIGHASH_ALL,
+};
+use tw_any_coin::test_utils::sign_utils::AnySignerHelper;
+use tw_coin_registry::coin_type::CoinType;
+use tw_encoding::hex::{DecodeHex, ToHex};
+use tw_keypair::ecdsa;
+use tw_misc::traits::{ToBytesVec, ToBytesZeroizing};
+use tw_proto::BitcoinV2::Proto;
+use tw_proto::Common::Proto::SigningError;
+
+const PRIVATE_KEY: &str = "9ea2172511ed73ae0096be8e593c3b75631700edaf729f1abbae607314a20e35";
+const RECIPIENT: &str = "bc1q2dsdlq3343vk29runkgv4yc292hmq53jedfjmp";
+const UTXO_HASH_0: &str = "b33082a5fad105c1d9712e8d503971fe4d84713065bd323fd1019636ed940e8d";
+const UTXO_HASH_1: &str = "1f62c18bfc5f8293a2b7b061587c427bf830fb224289f9a806e6ad48de6a4c7d";
+const UTXO_HASH_2: &str = "71c3343dfca5f1914e1bfc04153517d73650cb9c931e8511d24d1f5290120f6f";
+
+/// The original transaction spends `UTXO_0` (15_000) and `UTXO_1` (8_000),
+/// and sends 20_000 to the recipient and 2_582 back to the sender, paying 2 sat/vB.
+const ORIGINAL_TX: &str = "020000000001028d0e94ed369601d13f32bd653071844dfe7139508d2e71d9c105d1faa58230b30100000000ffffffff7d4c6ade48ade606a8f9894222fb30f87b427c5861b0b7a293825ffc8bc1621f0100000000ffffffff02204e0000000000001600145360df8231ac5965147c9d90ca930a2aafb05232160a00000000000016001460d7ee599766db323fb1916c7f9e5d818aaf8c1b02483045022100dbbbc50225b581376bfb024298790c8facc3d218e39e904cec42309aed7a65c002202e160800c8fd8306f8fab57c1994802854796e755db4307723c9378839c2ddd2012103a11506993946e20ea82686b157bf08f944759f43d91af8d84650ee73a482431c02473044022074f1827bd60f407ba6afad66fc6703f73d1597b8ffde81ee13b446f44b7fa0c3022033a1cf69cefd4d8e12da27c879835f111ffa100c087e7126c782a9f034e52805012103a11506993946e20ea82686b157bf08f944759f43d91af8d84650ee73a482431c00000000";
+const ORIGINAL_TXID: &str = "c74e00178a3d3d1930ade546104e6eb061af8160fc5bc4b29f7ed97b4d95b479";
+const ORIGINAL_FEE: i64 = 418;
+const ORIGINAL_VSIZE: i64 = 209;
+
+fn private_key() -> ecdsa::secp256k1::PrivateKey {
+    ecdsa::secp256k1::PrivateKey::try_from(PRIVATE_KEY).unwrap()
+}
+
+fn utxo(hash: &str, vout: u32, value: i64) -> Proto::Input<'static> {
+    Proto::Input {
+        out_point: input::out_point(hash, vout),
+        value,
+        sighash_type: SIGHASH_ALL,
+        claiming_script: input::p2wpkh(private_key().public().to_vec()),
+        ..Default::default()
+    }
+}
+
+fn original_utxos() -> Vec<Proto::Input<'static>> {
+    vec![utxo(UTXO_HASH_0, 1, 15_000), utxo(UTXO_HASH_1, 1, 8_000)]
+}
+
+fn change_output() -> Proto::Output<'static> {
+    Proto::Output {
+        value: 0,
+        to_recipient: output::p2wpkh(private_key().public().to_vec()),
+    }
+}
+
+fn replace_by_fee(
+    inputs: Vec<Proto::Input<'static>>,
+    fee_per_vb: i64,
+) -> Proto::SigningInput<'static> {
+    let rbf = Proto::ReplaceByFee {
+        original_transaction: ORIGINAL_TX.decode_hex().unwrap().into(),
+        inputs,
+        change_output: Some(change_output()),
+        fee_per_vb,
+        dust_policy: Proto::mod_ReplaceByFee::OneOfdust_policy::fixed_dust_threshold(DUST),
+        ..Default::default()
+    };
+    signing_input(TransactionOneof::replace_by_fee(rbf))
+}
+
+fn child_pays_for_parent(parent_output_index: u32) -> Proto::SigningInput<'static> {
+    let cpfp = Proto::ChildPaysForParent {
+        parent_transaction: ORIGINAL_TX.decode_hex().unwrap().into(),
+        parent_fee: ORIGINAL_FEE,
+        parent_output_index,
+        // `out_point` and `value` will be set from the parent transaction.
+        parent_output: Some(Proto::Input {
+            sighash_type: SIGHASH_ALL,
+            claiming_script: input::p2wpkh(private_key().public().to_vec()),
+            ..Default::default()
+        }),
+        extra_inputs: Vec::default(),
+        output: Some(Proto::Output {
+            value: 0,
+            to_recipient: output::to_address(RECIPIENT),
+        }),
+        package_fee_per_vb: 5,
+        dust_policy: Proto::mod_ChildPaysForParent::OneOfdust_policy::fixed_dust_threshold(DUST),
+    };
+    signing_input(TransactionOneof::child_pays_for_parent(cpfp))
+}
+
+fn signing_input(transaction: TransactionOneof<'static>) -> Proto::SigningInput<'static> {
+    Proto::SigningInput {
+        private_keys: vec![private_key().to_zeroizing_vec().to_vec().into()],
+        chain_info: btc_info(),
+        transaction,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_replace_by_fee_reduce_change() {
+    let input = replace_by_fee(original_utxos(), 10);
+    sign::BitcoinSignHelper::new(&input)
+        .coin(CoinType::Bitcoin)
+        .sign(sign::Expected {
+            encoded: "020000000001028d0e94ed369601d13f32bd653071844dfe7139508d2e71d9c105d1faa58230b30100000000fdffffff7d4c6ade48ade606a8f9894222fb30f87b427c5861b0b7a293825ffc8bc1621f0100000000fdffffff02204e0000000000001600145360df8231ac5965147c9d90ca930a2aafb052328e0300000000000016001460d7ee599766db323fb1916c7f9e5d818aaf8c1b0247304402201bd54902b86b08d3d8312833bb8d7e9408a05504c8d5c8e9c14e1a90970f3d71022015dce98bb40e084c42533bd49fad0e8027b961019022aa28916f83ad25642d65012103a11506993946e20ea82686b157bf08f944759f43d91af8d84650ee73a482431c02483045022100baa5fa98eed5c5d53f21f12b5b2c2c30ff4445c7929b37dddb163457604adbb402201fff8d8dce39ebd8cbf7e79c4420474004f1498076a9db43da76ab8b86e0c96b012103a11506993946e20ea82686b157bf08f944759f43d91af8d84650ee73a482431c00000000",
+            txid: "e7b21f6f68c1b8a1114aa880f9bddf508ba938d10ff8c636ec716105c2970313",
+            inputs: vec![15_000, 8_000],
+            outputs: vec![20_000, 910],
+            vsize: 209,
+            weight: 833,
+            fee: 2_090,
+        });
+}
+
+#[test]
+fn test_replace_by_fee_raise_fee_rate() {
+    // The same fee rate as the original transaction.
+    // It's raised to pay for the replacement bandwidth at least (BIP125 rule #4).
+    let input = replace_by_fee(original_utxos(), 2);
+    sign::BitcoinSignHelper::new(&input)
+        .coin(CoinType::Bitcoin)
+        .sign(sign::Expected {
+            encoded: "020000000001028d0e94ed369601d13f32bd653071844dfe7139508d2e71d9c105d1faa58230b30100000000fdffffff7d4c6ade48ade606a8f9894222fb30f87b427c5861b0b7a293825ffc8bc1621f0100000000fdffffff02204e0000000000001600145360df8231ac5965147c9d90ca930a2aafb05232450900000000000016001460d7ee599766db323fb1916c7f9e5d818aaf8c1b024730440220643dfeec95b7bb2900fd58981c9b32851190eb6124a78b88f3f62dfc929af67f0220247848902a5b28505d22a3cebdbd9777506668a5d5e988e2551842dcf35009e3012103a11506993946e20ea82686b157bf08f944759f43d91af8d84650ee73a482431c02483045022100818ac7c50b61d2d57443aa98d3914a3907c4d776ab1f5a0d3b14afbe1bb8e4b602205106032225db2ab12859f7be13830358a83938586d7131d38ddc969c55cdee04012103a11506993946e20ea82686b157bf08f944759f43d91af8d84650ee73a482431c00000000",
+            txid: "42f1b082f8f5b724578eec1d532a0f4eb355116582c0704b839549fc25b12b33",
+            inputs: vec![15_000, 8_000],
+            outputs: vec![20_000, 2_373],
+            vsize: 209,
+            weight: 833,
+            // `ORIGINAL_FEE` + `ORIGINAL_VSIZE` * 1 sat/vB at least.
+            fee: 627,
+        });
+}
+
+#[test]
+fn test_replace_by_fee_additional_utxo() {
+    // The original UTXOs do not cover the increased fee.
+    let mut inputs = original_utxos();
+    inputs.push(utxo(UTXO_HASH_2, 0, 10_000));
+
+    let input = replace_by_fee(inputs, 20);
+    sign::BitcoinSignHelper::new(&input)
+        .coin(CoinType::Bitcoin)
+        .sign(sign::Expected {
+            encoded: "020000000001038d0e94ed369601d13f32bd653071844dfe7139508d2e71d9c105d1faa58230b30100000000fdffffff7d4c6ade48ade606a8f9894222fb30f87b427c5861b0b7a293825ffc8bc1621f0100000000fdffffff6f0f1290521f4dd211851e939ccb5036d717351504fc1b4e91f1a5fc3d34c3710000000000fdffffff02204e0000000000001600145360df8231ac5965147c9d90ca930a2aafb05232101d00000000000016001460d7ee599766db323fb1916c7f9e5d818aaf8c1b02483045022100ba4489fc3d5cfefec296a212c3a76041186b5516ac84ec61427db39b78057bc3022050e6cb3a09ee0119ae019f42d7d73585b67b345404223d0b0adfa4c4489c5421012103a11506993946e20ea82686b157bf08f944759f43d91af8d84650ee73a482431c0247304402207676b512e0b8c61c91ee2e1ad1cea13e44c524cff6e7a90040539d199b4358d6022068f3d9e04131cebd03ecf73df154a8a1512ea4a23148bf2ec54c4e6e5382fd53012103a11506993946e20ea82686b157bf08f944759f43d91af8d84650ee73a482431c02483045022100d26502e1b4db03d3e05b3d8a74a4dedba2db9d38ee58e322c5e206a670156cf702205ebf24f109bdf391ddf8fd6a5158417304ff2e0e0044f9d9613d9d45cf95109a012103a11506993946e20ea82686b157bf08f944759f43d91af8d84650ee73a482431c00000000",
+            txid: "df0e3c1c8ca037f32e32a11cc98e578b93117079671bb91f42b4a47f4afbf32a",
+            inputs: vec![15_000, 8_000, 10_000],
+            outputs: vec![20_000, 7_440],
+            vsize: 277,
+            weight: 1105,
+            fee: 5_560,
+        });
+}
+
+#[test]
+fn test_replace_by_fee_keeps_all_original_utxos() {
+    // The first original UTXO alone covers the outputs and the increased fee.
+    let original_utxos = vec![utxo(UTXO_HASH_2, 0, 50_000), utxo(UTXO_HASH_1, 1, 8_000)];
+
+    let original = signing_input(TransactionOneof::builder(Proto::TransactionBuilder {
+        version: Proto::TransactionVersion::V2,
+        inputs: original_utxos.clone(),
+        outputs: vec![Proto::Output {
+            value: 20_000,
+            to_recipient: output::to_address(RECIPIENT),
+        }],
+        input_selector: Proto::InputSelector::UseAll,
+        fee_per_vb: 2,
+        change_output: Some(change_output()),
+        dust_policy: Proto::mod_TransactionBuilder::OneOfdust_policy::fixed_dust_threshold(DUST),
+        ..Default::default()
+    }));
+    let mut signer = AnySignerHelper::<Proto::SigningOutput>::default();
+    let original = signer.sign(CoinType::Bitcoin, original);
+    assert_eq!(
+        original.error,
+        SigningError::OK,
+        "{}",
+        original.error_message
+    );
+
+    let rbf = Proto::ReplaceByFee {
+        original_transaction: original.encoded.to_vec().into(),
+        inputs: original_utxos,
+        change_output: Some(change_output()),
+        fee_per_vb: 10,
+        dust_policy: Proto::mod_ReplaceByFee::OneOfdust_policy::fixed_dust_threshold(DUST),
+        ..Default::default()
+    };
+    let mut signer = AnySignerHelper::<Proto::SigningOutput>::default();
+    let output = signer.sign(
+        CoinType::Bitcoin,
+        signing_input(TransactionOneof::replace_by_fee(rbf)),
+    );
+    assert_eq!(output.error, SigningError::OK, "{}", output.error_message);
+
+    // Both original UTXOs are spent, so the replacement conflicts with the original transaction.
+    let original_tx = original.transaction.unwrap();
+    let tx = output.transaction.unwrap();
+    assert_eq!(tx.inputs.len(), 2);
+    for (input, original_input) in tx.inputs.iter().zip(original_tx.inputs.iter()) {
+        assert_eq!(input.out_point, original_input.out_point);
+    }
+    assert_eq!(tx.outputs[0].value, 20_000);
+    assert!(output.fee > original.fee);
+    assert_eq!(output.fee, 50_000 + 8_000 - 20_000 - tx.outputs[1].value);
+}
+
+#[test]
+fn test_replace_by_fee_missing_original_utxo() {
+    let input = replace_by_fee(vec![utxo(UTXO_HASH_0, 1, 15_000)], 10);
+
+    let mut signer = AnySignerHelper::<Proto::SigningOutput>::default();
+    let output = signer.sign(CoinType::Bitcoin, input);
+    assert_eq!(output.error, SigningError::Error_missing_input_utxos);
+}
+
+#[test]
+fn test_child_pays_for_parent() {
+    // Spend the change output of the original transaction.
+    let input = child_pays_for_parent(1);
+
+    let mut signer = AnySignerHelper::<Proto::SigningOutput>::default();
+    let output = signer.sign(CoinType::Bitcoin, input);
+    assert_eq!(output.error, SigningError::OK, "{}", output.error_message);
+
+    assert_eq!(
+        output.encoded.to_hex(),
+        "0100000000010179b4954d7bd97e9fb2c45bfc6081af61b06e4e1046e5ad30193d3d8a17004ec70100000000ffffffff015c050000000000001600145360df8231ac5965147c9d90ca930a2aafb052320247304402201ffae69018ffad08335612ddf46f226bd7ae7d1128f2a940419daa5c60b5b53e02206f6116898b63874b8a9b9f0c32b7ac86a7b9b312d3881cfcce0dc287a9b7f5e3012103a11506993946e20ea82686b157bf08f944759f43d91af8d84650ee73a482431c00000000"
+    );
+    assert_eq!(
+        output.txid.to_hex(),
+        "84ee1f405abd16f1a5db6582b08a3ef25663193c6f4ddbb0bc53c66c73299fe8"
+    );
+    assert_eq!(output.vsize, 110);
+    assert_eq!(output.fee, 1_210);
+
+    let tx = output.transaction.unwrap();
+    let mut parent_txid = tx.inputs[0].out_point.as_ref().unwrap().hash.to_vec();
+    parent_txid.reverse();
+    assert_eq!(parent_txid.to_hex(), ORIGINAL_TXID);
+    assert_eq!(tx.outputs[0].value, 2_582 - 1_210);
+
+    // The package pays at least 5 sat/vB.
+    let package_vsize = ORIGINAL_VSIZE + output.vsize as i64;
+    assert!(ORIGINAL_FEE + output.fee >= package_vsize * 5);
+}
+
+#[test]
+fn test_child_pays_for_parent_invalid_output_index() {
+    let input = child_pays_for_parent(2);
+
+    let mut signer = AnySignerHelper::<Proto::SigningOutput>::default();
+    let output = signer.sign(CoinType::Bitcoin, input);
+    assert_eq!(output.error, SigningError::Error_invalid_params);
+}
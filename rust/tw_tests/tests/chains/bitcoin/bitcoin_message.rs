@@ -0,0 +1,354 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use tw_any_coin::ffi::tw_message_signer::{
+    tw_message_signer_pre_image_hashes, tw_message_signer_sign, tw_message_signer_verify,
+};
+use tw_coin_entry::error::prelude::*;
+use tw_coin_registry::coin_type::CoinType;
+use tw_encoding::base64::{self, STANDARD};
+use tw_encoding::hex::{DecodeHex, ToHex};
+use tw_memory::test_utils::tw_data_helper::TWDataHelper;
+use tw_proto::BitcoinV2::Proto;
+use tw_proto::BitcoinV2::Proto::MessageSignatureFormat;
+use tw_proto::{deserialize, serialize, TxCompiler};
+
+const PRIVATE_KEY: &str = "afeefca74d9a325cf1d6b6911d61a65c32afa8e02bd5e78e2e4ac2910bab45f5";
+const PUBLIC_KEY: &str = "0399c6f51ad6f98c9c583f8e92bb7758ab2ca9a04110c0a1126ec43e5453d196c1";
+const P2PKH_ADDRESS: &str = "19cAJn4Ms8jodBBGtroBNNpCZiHAWGAq7X";
+const P2SH_P2WPKH_ADDRESS: &str = "3F4bWvE6GDANhDX9tBzGZzv3KvE7YH82ev";
+const P2WPKH_ADDRESS: &str = "bc1qten42eesehw0ktddcp0fws7d3ycsqez3f7d5yt";
+const P2TR_ADDRESS: &str = "bc1p4yq0ttapgxksdtq04njvs2mgc5sg06uuczk6ys6s2mvjkrcvydjqwea8hf";
+
+fn signing_input<'a>(
+    address: &'a str,
+    message: &'a str,
+    format: MessageSignatureFormat,
+) -> Proto::MessageSigningInput<'a> {
+    Proto::MessageSigningInput {
+        message: message.into(),
+        private_key: PRIVATE_KEY.decode_hex().unwrap().into(),
+        address: address.into(),
+        format,
+        dangerous_use_fixed_schnorr_rng: true,
+        ..Proto::MessageSigningInput::default()
+    }
+}
+
+/// Returns either the signature or the error.
+fn sign_message(input: &Proto::MessageSigningInput) -> Result<String, (SigningErrorType, String)> {
+    let input_data = TWDataHelper::create(serialize(input).unwrap());
+    let output = TWDataHelper::wrap(unsafe {
+        tw_message_signer_sign(input_data.ptr(), CoinType::Bitcoin as u32)
+    })
+    .to_vec()
+    .expect("!tw_message_signer_sign returned nullptr");
+
+    let output: Proto::MessageSigningOutput = deserialize(&output).unwrap();
+    if output.error != SigningErrorType::OK {
+        return Err((output.error, output.error_message.to_string()));
+    }
+    Ok(output.signature.to_string())
+}
+
+fn verify_message(address: &str, message: &str, signature: &str) -> bool {
+    let input = Proto::MessageVerifyingInput {
+        message: message.into(),
+        address: address.into(),
+        signature: signature.into(),
+    };
+
+    let input_data = TWDataHelper::create(serialize(&input).unwrap());
+    unsafe { tw_message_signer_verify(input_data.ptr(), CoinType::Bitcoin as u32) }
+}
+
+fn preimage_hash(input: &Proto::MessageSigningInput) -> String {
+    let input_data = TWDataHelper::create(serialize(input).unwrap());
+    let output = TWDataHelper::wrap(unsafe {
+        tw_message_signer_pre_image_hashes(input_data.ptr(), CoinType::Bitcoin as u32)
+    })
+    .to_vec()
+    .expect("!tw_message_signer_pre_image_hashes returned nullptr");
+
+    let output: TxCompiler::Proto::PreSigningOutput = deserialize(&output).unwrap();
+    assert_eq!(
+        output.error,
+        SigningErrorType::OK,
+        "{}",
+        output.error_message
+    );
+    output.data_hash.to_hex()
+}
+
+#[test]
+fn test_bitcoin_message_legacy_sign_p2pkh() {
+    let input = signing_input(
+        P2PKH_ADDRESS,
+        "test signature",
+        MessageSignatureFormat::Legacy,
+    );
+    let signature = sign_message(&input).unwrap();
+    assert_eq!(
+        signature,
+        "ILH5K7JQLaRGaKGXXH5mYM6FIIy9IWyY4JUPI+PHYY4WaupxUbg+zy0bhBCrDuehy9x4WidwjkRR1GSLnWvOXBo="
+    );
+
+    let input = signing_input(
+        P2PKH_ADDRESS,
+        "another text",
+        MessageSignatureFormat::Legacy,
+    );
+    let signature = sign_message(&input).unwrap();
+    assert_eq!(
+        signature,
+        "H7vrF2C+TlFiHyegAw3QLv6SK0myuEEXUOgfx0+Qio1YVDuSa6p/OHpoQVlUt3F8QJdbdZN9M1h/fYEAnEz16V0="
+    );
+}
+
+#[test]
+fn test_bitcoin_message_legacy_verify() {
+    let vectors = [
+        (
+            "1B8Qea79tsxmn4dTiKKRVvsJpHwL2fMQnr",
+            "test signature",
+            "H+3L5IbSVcejp4S2VwLXCxLEMQAWDvKbE8lQyq0ocdvyM1aoEudkzN/S/qLI3vnNOFY6V13BXWSFrPr3OjGa5Dk=",
+        ),
+        (
+            "1HZwkjkeaoZfTSaJxDw6aKkxp45agDiEzN",
+            "This is an example of a signed message.",
+            "G39Qf0XrZHICWbz3r5gOkcgTRw3vM4leGjiR3refr/K1OezcKmmXaLn4zc8ji2rjbBUIMrIhH/jc5Z2qEEz7qVk=",
+        ),
+        (
+            "1H8X4u6CVZRTLLNbUQTKAnc5vCkqWMpwfF",
+            "compressed key",
+            "IKUI9v2xbHogJe8HKXI2M5KEhMKaW6fjNxtyEy27Mf+3/e1ht4jZoc85e4F8stPsxt4Xcg8Yr42S28O6L/Qx9fE=",
+        ),
+        // The same signature with a low `s` value.
+        (
+            "1H8X4u6CVZRTLLNbUQTKAnc5vCkqWMpwfF",
+            "compressed key",
+            "H6UI9v2xbHogJe8HKXI2M5KEhMKaW6fjNxtyEy27Mf+3AhKeSHcmXjDGhH6DTSwTN9yXateWmRKo5A6kXNwES1A=",
+        ),
+        // The first signature with a high `s` value and the flipped recovery ID.
+        (
+            "1B8Qea79tsxmn4dTiKKRVvsJpHwL2fMQnr",
+            "test signature",
+            "IO3L5IbSVcejp4S2VwLXCxLEMQAWDvKbE8lQyq0ocdvyzKlX7RibMyAtAV03IQYyxmR0hYjt6zu2EtdnUp6bXQg=",
+        ),
+        // Signed with an uncompressed public key.
+        (
+            "1E4T9JZ3mq6cdgiRJEWzHqDXb9t322fE6d",
+            "test signature",
+            "HLH5K7JQLaRGaKGXXH5mYM6FIIy9IWyY4JUPI+PHYY4WaupxUbg+zy0bhBCrDuehy9x4WidwjkRR1GSLnWvOXBo=",
+        ),
+    ];
+
+    for (address, message, signature) in vectors {
+        assert!(verify_message(address, message, signature), "{address}");
+    }
+
+    // Different message.
+    assert!(!verify_message(
+        "1B8Qea79tsxmn4dTiKKRVvsJpHwL2fMQnr",
+        "test signature 2",
+        "H+3L5IbSVcejp4S2VwLXCxLEMQAWDvKbE8lQyq0ocdvyM1aoEudkzN/S/qLI3vnNOFY6V13BXWSFrPr3OjGa5Dk=",
+    ));
+    // A high `s` value without the flipped recovery ID recovers another public key.
+    assert!(!verify_message(
+        "1B8Qea79tsxmn4dTiKKRVvsJpHwL2fMQnr",
+        "test signature",
+        "H+3L5IbSVcejp4S2VwLXCxLEMQAWDvKbE8lQyq0ocdvyzKlX7RibMyAtAV03IQYyxmR0hYjt6zu2EtdnUp6bXQg=",
+    ));
+    // The signature is made with the compressed public key, but the address is uncompressed.
+    assert!(!verify_message(
+        "1E4T9JZ3mq6cdgiRJEWzHqDXb9t322fE6d",
+        "test signature",
+        "ILH5K7JQLaRGaKGXXH5mYM6FIIy9IWyY4JUPI+PHYY4WaupxUbg+zy0bhBCrDuehy9x4WidwjkRR1GSLnWvOXBo=",
+    ));
+}
+
+#[test]
+fn test_bitcoin_message_legacy_sign_segwit() {
+    for (address, header) in [(P2SH_P2WPKH_ADDRESS, 35), (P2WPKH_ADDRESS, 39)] {
+        let input = signing_input(address, "test signature", MessageSignatureFormat::Legacy);
+        let signature = sign_message(&input).unwrap();
+
+        let signature_bytes = base64::decode(&signature, STANDARD).unwrap();
+        assert_eq!(signature_bytes.len(), 65);
+        assert!((header..header + 4).contains(&signature_bytes[0]));
+        // The same ECDSA signature as for the P2PKH address, only the header is different.
+        assert_eq!(signature_bytes[1..].to_vec().to_hex(), "b1f92bb2502da44668a1975c7e6660ce85208cbd216c98e0950f23e3c7618e166aea7151b83ecf2d1b8410ab0ee7a1cbdc785a27708e4451d4648b9d6bce5c1a");
+
+        assert!(verify_message(address, "test signature", &signature));
+        assert!(!verify_message(address, "another text", &signature));
+    }
+}
+
+#[test]
+fn test_bitcoin_message_legacy_sign_error() {
+    let input = signing_input(
+        P2TR_ADDRESS,
+        "test signature",
+        MessageSignatureFormat::Legacy,
+    );
+    let (error, _) = sign_message(&input).unwrap_err();
+    assert_eq!(error, SigningErrorType::Error_not_supported);
+
+    let input = signing_input(
+        "1B8Qea79tsxmn4dTiKKRVvsJpHwL2fMQnr",
+        "test signature",
+        MessageSignatureFormat::Legacy,
+    );
+    let (error, _) = sign_message(&input).unwrap_err();
+    assert_eq!(error, SigningErrorType::Error_invalid_address);
+
+    let input = signing_input(
+        "__THIS_IS_NOT_A_VALID_ADDRESS__",
+        "test signature",
+        MessageSignatureFormat::Legacy,
+    );
+    let (error, _) = sign_message(&input).unwrap_err();
+    assert_eq!(error, SigningErrorType::Error_invalid_address);
+}
+
+/// https://github.com/bitcoin/bips/blob/master/bip-0322.mediawiki#test-vectors
+#[test]
+fn test_bitcoin_message_bip322_verify_p2wpkh() {
+    let address = "bc1q9vza2e8x573nczrlzms0wvx3gsqjx7vavgkx0l";
+
+    let empty_signature = "AkcwRAIgM2gBAQqvZX15ZiysmKmQpDrG83avLIT492QBzLnQIxYCIBaTpOaD20qRlEylyxFSeEA2ba9YOixpX8z46TSDtS40ASECx/EgAxlkQpQ9hYjgGu6EBCPMVPwVIVJqO4XCsMvViHI=";
+    assert!(verify_message(address, "", empty_signature));
+    assert!(!verify_message(address, "Hello World", empty_signature));
+
+    let hello_signatures = [
+        "AkcwRAIgZRfIY3p7/DoVTty6YZbWS71bc5Vct9p9Fia83eRmw2QCICK/ENGfwLtptFluMGs2KsqoNSk89pO7F29zJLUx9a/sASECx/EgAxlkQpQ9hYjgGu6EBCPMVPwVIVJqO4XCsMvViHI=",
+        "AkgwRQIhAOzyynlqt93lOKJr+wmmxIens//zPzl9tqIOua93wO6MAiBi5n5EyAcPScOjf1lAqIUIQtr3zKNeavYabHyR8eGhowEhAsfxIAMZZEKUPYWI4BruhAQjzFT8FSFSajuFwrDL1Yhy",
+    ];
+    for signature in hello_signatures {
+        assert!(verify_message(address, "Hello World", signature));
+        assert!(!verify_message(address, "", signature));
+        // Valid signature, but of another address.
+        assert!(!verify_message(P2WPKH_ADDRESS, "Hello World", signature));
+    }
+}
+
+/// https://github.com/bitcoin/bips/blob/master/bip-0322.mediawiki#test-vectors
+#[test]
+fn test_bitcoin_message_bip322_verify_p2tr() {
+    let address = "bc1ppv609nr0vr25u07u95waq5lucwfm6tde4nydujnu8npg4q75mr5sxq8lt3";
+    // The signature has an explicit `SIGHASH_ALL` sighash type.
+    let signature = "AUHd69PrJQEv+oKTfZ8l+WROBHuy9HKrbFCJu7U1iK2iiEy1vMU5EfMtjc+VSHM7aU0SDbak5IUZRVno2P5mjSafAQ==";
+
+    assert!(verify_message(address, "Hello World", signature));
+    assert!(!verify_message(address, "", signature));
+}
+
+#[test]
+fn test_bitcoin_message_bip322_sign_p2wpkh() {
+    let input = signing_input(
+        P2WPKH_ADDRESS,
+        "Hello World",
+        MessageSignatureFormat::Bip322Simple,
+    );
+    let signature = sign_message(&input).unwrap();
+    assert_eq!(signature, "AkgwRQIhANQRX37seb/QsPrpS1UgoBgnBYYXvtTvUjjdgjtmRRMOAiAGkmJcsr/FF4tWWagpAxJf1TV+RFYzQ1B7n9xAOIgGNgEhA5nG9RrW+YycWD+Okrt3WKssqaBBEMChEm7EPlRT0ZbB");
+    assert!(verify_message(P2WPKH_ADDRESS, "Hello World", &signature));
+    assert!(!verify_message(P2WPKH_ADDRESS, "Hello World!", &signature));
+
+    let input = signing_input(
+        P2WPKH_ADDRESS,
+        "Hello World",
+        MessageSignatureFormat::Bip322Full,
+    );
+    let signature = sign_message(&input).unwrap();
+    assert_eq!(signature, "AAAAAAABAY99JI7nUpy5pjxbH+sbaOEkgJQHoa+Q4hsYGMDqRjniAAAAAAAAAAAAAQAAAAAAAAAAAWoCSDBFAiEA1BFffux5v9Cw+ulLVSCgGCcFhhe+1O9SON2CO2ZFEw4CIAaSYlyyv8UXi1ZZqCkDEl/VNX5EVjNDUHuf3EA4iAY2ASEDmcb1Gtb5jJxYP46Su3dYqyypoEEQwKESbsQ+VFPRlsEAAAAA");
+    assert!(verify_message(P2WPKH_ADDRESS, "Hello World", &signature));
+    assert!(!verify_message(P2WPKH_ADDRESS, "Hello World!", &signature));
+}
+
+#[test]
+fn test_bitcoin_message_bip322_sign_p2tr() {
+    let input = signing_input(
+        P2TR_ADDRESS,
+        "Hello World",
+        MessageSignatureFormat::Bip322Simple,
+    );
+    let signature = sign_message(&input).unwrap();
+    assert_eq!(
+        signature,
+        "AUBcc5vn6PxFErMySqBAMUs6W65WCNcvnC/Jo04OoPqjfpSHfMIwJixeFg7yrCiVb7IT0K7tNFsn2D78BNk5/3cE"
+    );
+    assert!(verify_message(P2TR_ADDRESS, "Hello World", &signature));
+    assert!(!verify_message(P2TR_ADDRESS, "Hello World!", &signature));
+
+    let input = signing_input(
+        P2TR_ADDRESS,
+        "Hello World",
+        MessageSignatureFormat::Bip322Full,
+    );
+    let signature = sign_message(&input).unwrap();
+    assert_eq!(signature, "AAAAAAABAR0AMZZs+klLf3JgodBSf6Ox7QmKE+kWnHBnA1aGySV3AAAAAAAAAAAAAQAAAAAAAAAAAWoBQFxzm+fo/EUSszJKoEAxSzpbrlYI1y+cL8mjTg6g+qN+lId8wjAmLF4WDvKsKJVvshPQru00WyfYPvwE2Tn/dwQAAAAA");
+    assert!(verify_message(P2TR_ADDRESS, "Hello World", &signature));
+    assert!(!verify_message(P2TR_ADDRESS, "Hello World!", &signature));
+}
+
+#[test]
+fn test_bitcoin_message_bip322_sign_error() {
+    // BIP322 is not supported for legacy addresses.
+    let input = signing_input(
+        P2PKH_ADDRESS,
+        "Hello World",
+        MessageSignatureFormat::Bip322Simple,
+    );
+    let (error, _) = sign_message(&input).unwrap_err();
+    assert_eq!(error, SigningErrorType::Error_not_supported);
+
+    // The address doesn't belong to the private key.
+    let input = signing_input(
+        "bc1q9vza2e8x573nczrlzms0wvx3gsqjx7vavgkx0l",
+        "Hello World",
+        MessageSignatureFormat::Bip322Simple,
+    );
+    let (error, _) = sign_message(&input).unwrap_err();
+    assert_eq!(error, SigningErrorType::Error_invalid_address);
+}
+
+#[test]
+fn test_bitcoin_message_preimage_hashes() {
+    let input = signing_input(
+        P2PKH_ADDRESS,
+        "test signature",
+        MessageSignatureFormat::Legacy,
+    );
+    assert_eq!(
+        preimage_hash(&input),
+        "8e81cc5bca9862d8b7f22be1f7cb762b49121cf4e1611c27906a041f9a9eb21f"
+    );
+
+    let input = signing_input(
+        P2WPKH_ADDRESS,
+        "Hello World",
+        MessageSignatureFormat::Bip322Simple,
+    );
+    let expected = "f14f20b6aff4c167982efe63e79f09eda8e305559afd856cfe40bc3d99ec1e2c";
+    assert_eq!(preimage_hash(&input), expected);
+
+    // The public key can be used instead of the private key.
+    let input = Proto::MessageSigningInput {
+        private_key: Default::default(),
+        public_key: PUBLIC_KEY.decode_hex().unwrap().into(),
+        ..input
+    };
+    assert_eq!(preimage_hash(&input), expected);
+
+    let input = signing_input(
+        P2TR_ADDRESS,
+        "Hello World",
+        MessageSignatureFormat::Bip322Simple,
+    );
+    assert_eq!(
+        preimage_hash(&input),
+        "798bcfc9442c5527c244f0704a3e7041627890eb9826f653a5823054b5d48930"
+    );
+}
@@ -0,0 +1,98 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::chains::common::bitcoin::{
+    btc_info, dynamic_dust, input, output, plan, TransactionOneof, SIGHASH_ALL,
+};
+use tw_any_coin::test_utils::plan_utils::AnyPlannerHelper;
+use tw_coin_registry::coin_type::CoinType;
+use tw_encoding::hex::DecodeHex;
+use tw_proto::BitcoinV2::Proto;
+use tw_proto::Common::Proto as CommonProto;
+
+const PUBKEY: &str = "037ed9a436e11ec4947ac4b7823787e24ba73180f1edd2857bff19c9f4d62b65bf";
+const DUMMY_TXID: &str = "06953f715201d085d0607097bc7571fcebe92bd4aa30038bd480a72357b28796";
+/// Bitcoin Core's default `DUST_RELAY_TX_FEE`.
+const DUST_RELAY_TX_FEE: i64 = 3000;
+
+fn p2wpkh_utxo(vout: u32, value: i64) -> Proto::Input<'static> {
+    Proto::Input {
+        out_point: input::out_point(DUMMY_TXID, vout),
+        value,
+        sighash_type: SIGHASH_ALL,
+        claiming_script: input::p2wpkh(PUBKEY.decode_hex().unwrap()),
+        ..Proto::Input::default()
+    }
+}
+
+fn make_planning_input(
+    inputs: Vec<Proto::Input<'static>>,
+    outputs: Vec<Proto::Output<'static>>,
+) -> Proto::SigningInput<'static> {
+    let builder = Proto::TransactionBuilder {
+        inputs,
+        outputs,
+        input_selector: Proto::InputSelector::UseAll,
+        fee_per_vb: 1,
+        change_output: Some(Proto::Output {
+            value: 0,
+            to_recipient: output::p2wpkh(PUBKEY.decode_hex().unwrap()),
+        }),
+        dust_policy: dynamic_dust(DUST_RELAY_TX_FEE),
+        ..Default::default()
+    };
+
+    Proto::SigningInput {
+        chain_info: btc_info(),
+        transaction: TransactionOneof::builder(builder),
+        ..Proto::SigningInput::default()
+    }
+}
+
+#[test]
+fn test_dynamic_dust_segwit_amounts() {
+    let input = make_planning_input(
+        // P2WPKH dust threshold is 294 at the default relay fee, so the second UTXO is filtered out.
+        vec![p2wpkh_utxo(0, 10_000), p2wpkh_utxo(1, 293)],
+        vec![
+            // The amounts would be dust with the fixed 546 threshold.
+            Proto::Output {
+                value: 294,
+                to_recipient: output::p2wpkh(PUBKEY.decode_hex().unwrap()),
+            },
+            Proto::Output {
+                value: 330,
+                to_recipient: output::p2tr_key_path(PUBKEY.decode_hex().unwrap()),
+            },
+        ],
+    );
+    plan::BitcoinPlanHelper::new(&input)
+        .coin(CoinType::Bitcoin)
+        .plan(plan::Expected {
+            inputs: vec![10_000],
+            outputs: vec![294, 330, 9_192],
+            vsize_estimate: 184,
+            fee_estimate: 184,
+            change: 9_192,
+        });
+}
+
+#[test]
+fn test_dynamic_dust_p2pkh_output_error() {
+    // P2PKH dust threshold is 546 at the default relay fee.
+    let input = make_planning_input(
+        vec![p2wpkh_utxo(0, 10_000)],
+        vec![Proto::Output {
+            value: 545,
+            to_recipient: output::p2pkh(PUBKEY.decode_hex().unwrap()),
+        }],
+    );
+
+    let mut planner = AnyPlannerHelper::<Proto::TransactionPlan>::default();
+    let plan = planner.plan(CoinType::Bitcoin, input);
+    assert_eq!(
+        plan.error,
+        CommonProto::SigningError::Error_dust_amount_requested
+    );
+}
@@ -0,0 +1,217 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::chains::common::bitcoin::{plan, Amount, TransactionOneof, DUST};
+use tw_any_coin::test_utils::plan_utils::AnyPlannerHelper;
+use tw_coin_registry::coin_type::CoinType;
+use tw_proto::BitcoinV2::Proto;
+use tw_proto::Common::Proto as CommonProto;
+
+const UTXOS: [Amount; 4] = [10_000, 5_000, 3_000, 2_000];
+
+fn make_waste_planning_input(
+    args: plan::PlanArgs,
+    long_term_fee_per_vb: Amount,
+) -> Proto::SigningInput<'static> {
+    let mut input = plan::make_planning_input(args);
+    if let TransactionOneof::builder(ref mut builder) = input.transaction {
+        builder.long_term_fee_per_vb = long_term_fee_per_vb;
+    }
+    input
+}
+
+fn plan_waste(input: &Proto::SigningInput<'static>) -> Amount {
+    let mut planner = AnyPlannerHelper::<Proto::TransactionPlan>::default();
+    let plan = planner.plan(CoinType::Bitcoin, input.clone());
+    assert_eq!(plan.error, CommonProto::SigningError::OK);
+    plan.waste
+}
+
+#[test]
+fn test_branch_and_bound_changeless_exact_match() {
+    // Effective values of 5_000 and 3_000 UTXOs (149 vbytes each) exactly cover
+    // the output and the fee of the transaction without inputs (44 vbytes).
+    let input = make_waste_planning_input(
+        plan::PlanArgs {
+            inputs: UTXOS.to_vec(),
+            outputs: vec![7_658],
+            change: true,
+            max: false,
+            dust_threshold: DUST,
+            order: Proto::InputSelector::BranchAndBound,
+            fee_per_vb: 1,
+        },
+        1,
+    );
+    plan::BitcoinPlanHelper::new(&input)
+        .coin(CoinType::Bitcoin)
+        .plan(plan::Expected {
+            inputs: vec![5_000, 3_000],
+            outputs: vec![7_658],
+            vsize_estimate: 342,
+            fee_estimate: 342,
+            change: 0,
+        });
+    assert_eq!(plan_waste(&input), 0);
+}
+
+#[test]
+fn test_branch_and_bound_changeless_with_excess() {
+    // The excess (58) is less than the cost of creating and spending a change output,
+    // so the excess is added to the fee.
+    let input = make_waste_planning_input(
+        plan::PlanArgs {
+            inputs: UTXOS.to_vec(),
+            outputs: vec![7_600],
+            change: true,
+            max: false,
+            dust_threshold: DUST,
+            order: Proto::InputSelector::BranchAndBound,
+            fee_per_vb: 1,
+        },
+        1,
+    );
+    plan::BitcoinPlanHelper::new(&input)
+        .coin(CoinType::Bitcoin)
+        .plan(plan::Expected {
+            inputs: vec![5_000, 3_000],
+            outputs: vec![7_600],
+            vsize_estimate: 342,
+            fee_estimate: 400,
+            change: 0,
+        });
+    assert_eq!(plan_waste(&input), 58);
+}
+
+#[test]
+fn test_knapsack_with_change() {
+    let input = make_waste_planning_input(
+        plan::PlanArgs {
+            inputs: UTXOS.to_vec(),
+            outputs: vec![7_600],
+            change: true,
+            max: false,
+            dust_threshold: DUST,
+            order: Proto::InputSelector::Knapsack,
+            fee_per_vb: 1,
+        },
+        1,
+    );
+    plan::BitcoinPlanHelper::new(&input)
+        .coin(CoinType::Bitcoin)
+        .plan(plan::Expected {
+            inputs: vec![5_000, 3_000, 2_000],
+            outputs: vec![7_600, 1_875],
+            vsize_estimate: 525,
+            fee_estimate: 525,
+            change: 1_875,
+        });
+    // Cost of the change output (34) + cost of spending it later (149).
+    assert_eq!(plan_waste(&input), 183);
+}
+
+#[test]
+fn test_single_random_draw_with_change() {
+    let input = make_waste_planning_input(
+        plan::PlanArgs {
+            inputs: UTXOS.to_vec(),
+            outputs: vec![7_600],
+            change: true,
+            max: false,
+            dust_threshold: DUST,
+            order: Proto::InputSelector::SingleRandomDraw,
+            fee_per_vb: 1,
+        },
+        1,
+    );
+    // The selection is deterministic for the same set of UTXOs.
+    plan::BitcoinPlanHelper::new(&input)
+        .coin(CoinType::Bitcoin)
+        .plan(plan::Expected {
+            inputs: vec![5_000, 3_000, 2_000],
+            outputs: vec![7_600, 1_875],
+            vsize_estimate: 525,
+            fee_estimate: 525,
+            change: 1_875,
+        });
+    assert_eq!(plan_waste(&input), 183);
+}
+
+#[test]
+fn test_minimize_waste_prefers_changeless() {
+    let input = make_waste_planning_input(
+        plan::PlanArgs {
+            inputs: UTXOS.to_vec(),
+            outputs: vec![7_600],
+            change: true,
+            max: false,
+            dust_threshold: DUST,
+            order: Proto::InputSelector::MinimizeWaste,
+            fee_per_vb: 1,
+        },
+        1,
+    );
+    plan::BitcoinPlanHelper::new(&input)
+        .coin(CoinType::Bitcoin)
+        .plan(plan::Expected {
+            inputs: vec![5_000, 3_000],
+            outputs: vec![7_600],
+            vsize_estimate: 342,
+            fee_estimate: 400,
+            change: 0,
+        });
+    assert_eq!(plan_waste(&input), 58);
+}
+
+#[test]
+fn test_minimize_waste_high_fee_rate() {
+    // Spending UTXOs now is much more expensive than in the long term,
+    // so the selection with the least number of inputs is preferred.
+    let input = make_waste_planning_input(
+        plan::PlanArgs {
+            inputs: UTXOS.to_vec(),
+            outputs: vec![6_000],
+            change: false,
+            max: false,
+            dust_threshold: DUST,
+            order: Proto::InputSelector::MinimizeWaste,
+            fee_per_vb: 10,
+        },
+        2,
+    );
+    plan::BitcoinPlanHelper::new(&input)
+        .coin(CoinType::Bitcoin)
+        .plan(plan::Expected {
+            inputs: vec![10_000],
+            outputs: vec![6_000],
+            vsize_estimate: 193,
+            fee_estimate: 4_000,
+            change: 0,
+        });
+    // Input waste (1490 - 298) + excess (2070).
+    assert_eq!(plan_waste(&input), 3_262);
+}
+
+#[test]
+fn test_branch_and_bound_no_changeless_solution() {
+    let input = make_waste_planning_input(
+        plan::PlanArgs {
+            inputs: UTXOS.to_vec(),
+            outputs: vec![6_000],
+            change: false,
+            max: false,
+            dust_threshold: DUST,
+            order: Proto::InputSelector::BranchAndBound,
+            fee_per_vb: 10,
+        },
+        2,
+    );
+
+    let mut planner = AnyPlannerHelper::<Proto::TransactionPlan>::default();
+    let plan = planner.plan(CoinType::Bitcoin, input);
+    assert_eq!(
+        plan.error,
+        CommonProto::SigningError::Error_not_enough_utxos
+    );
+}
@@ -0,0 +1,662 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::chains::common::bitcoin::{
+    btc_info, dust_threshold, input, output, TransactionOneof, DUST, MINER_FEE, ONE_BTC,
+    SIGHASH_ALL,
+};
+use tw_coin_registry::coin_type::CoinType;
+use tw_encoding::base64::{self, STANDARD};
+use tw_encoding::hex::{DecodeHex, ToHex};
+use tw_keypair::{ecdsa, schnorr};
+use tw_memory::ffi::tw_data::TWData;
+use tw_memory::test_utils::tw_data_helper::TWDataHelper;
+use tw_misc::traits::ToBytesVec;
+use tw_proto::BitcoinV2::Proto;
+use tw_proto::Common::Proto::SigningError;
+use tw_proto::{deserialize, serialize, MessageWrite};
+use tw_utxo::script::standard_script::{conditions, opcodes};
+use tw_utxo::script::Script;
+use wallet_core_rs::ffi::bitcoin::psbt::{
+    tw_bitcoin_psbt_combine, tw_bitcoin_psbt_create, tw_bitcoin_psbt_extract,
+    tw_bitcoin_psbt_finalize, tw_bitcoin_psbt_sign,
+};
+
+const ALICE_PRIVATE_KEY: &str = "56429688a1a6b00b90ccd22a0de0a376b6569d8684022ae92229a28478bfb657";
+const BOB_PRIVATE_KEY: &str = "b7da1ec42b19085fe09fec54b9d9eacd998ae4e6d2ad472be38d8393391b9ead";
+const CAROL_PRIVATE_KEY: &str = "3c3385ddc6fd95ba7282051aeb440bc75820b8c10db5c83c052d7586e3e98e84";
+
+const MULTISIG_UTXO_TXID: &str = "dd9d4ca23532f5c89d016e1aacef1210ab5b9d00527c633969841daca7dd17c7";
+/// The same transaction as signed by `BitcoinSigner` in `test_bitcoin_sign_input_p2wsh_multisig`.
+const MULTISIG_SIGNED_TX: &str = "02000000000101c717dda7ac1d846939637c52009d5bab1012efac1a6e019dc8f53235a24c9ddd0000000000ffffffff0100e9c829010000001976a914e4c1ea86373d554b8f4efff2cfb0001ea19124d288ac0400473044022004caeafd1be3e70693a05af5fb4a6c2b4f9d3620cb8c6ed0c711d5acb71eb66302207f27deb6815f03887e5edb865526a08b0061c8d7bdfcfb97c953382fc6aeeef20147304402205a16419663116db808e0dfd8ee194ee99929bc19ea217e6baf6c1305f341d948022052470c7003a82d6936ca6bf586db8b76756c4c85767d8672387945533f848c4001695221036666dd712e05a487916384bfcd5973eb53e8038eccbbf97f7eed775b8738953621037ed9a436e11ec4947ac4b7823787e24ba73180f1edd2857bff19c9f4d62b65bf2103b85cc59b67c35851eb5060cfc3a759a482254553c5857075c9e247d74d412c9153ae00000000";
+const MULTISIG_SIGNED_TXID: &str =
+    "64a05a69415b2d49c601ee42293207bebb7c5a0c93c2b5fb1527aa5ba9616e76";
+
+fn public_key(private_key: &str) -> ecdsa::secp256k1::PublicKey {
+    ecdsa::secp256k1::PrivateKey::try_from(private_key)
+        .unwrap()
+        .public()
+}
+
+fn bip32_derivation(private_key: &str, fingerprint: &str) -> Proto::Bip32Derivation<'static> {
+    Proto::Bip32Derivation {
+        public_key: public_key(private_key).compressed().to_vec().into(),
+        master_fingerprint: fingerprint.decode_hex().unwrap().into(),
+        derivation_path: "m/48'/0'/0'/2'/0/0".into(),
+    }
+}
+
+/// Spends a 2-of-3 multisig P2WSH UTXO that can be signed by any two of Alice, Bob and Carol.
+fn multisig_creating_input(
+    output_value: i64,
+    version: Proto::PsbtVersion,
+) -> Proto::PsbtCreatingInput<'static> {
+    let witness_script = conditions::new_multisig(
+        2,
+        &[
+            public_key(ALICE_PRIVATE_KEY).compressed(),
+            public_key(BOB_PRIVATE_KEY).compressed(),
+            public_key(CAROL_PRIVATE_KEY).compressed(),
+        ],
+    )
+    .unwrap();
+
+    let tx1 = Proto::Input {
+        out_point: input::out_point(MULTISIG_UTXO_TXID, 0),
+        value: 50 * ONE_BTC - 3 * MINER_FEE,
+        sighash_type: SIGHASH_ALL,
+        claiming_script: input::p2wsh(
+            witness_script.to_vec(),
+            vec![
+                public_key(BOB_PRIVATE_KEY).compressed().to_vec(),
+                public_key(CAROL_PRIVATE_KEY).compressed().to_vec(),
+            ],
+        ),
+        ..Default::default()
+    };
+
+    let out1 = Proto::Output {
+        value: output_value,
+        to_recipient: output::p2pkh(public_key(ALICE_PRIVATE_KEY).compressed().to_vec()),
+    };
+
+    let builder = Proto::TransactionBuilder {
+        version: Proto::TransactionVersion::V2,
+        inputs: vec![tx1],
+        outputs: vec![out1],
+        input_selector: Proto::InputSelector::UseAll,
+        dust_policy: dust_threshold(DUST),
+        ..Default::default()
+    };
+
+    Proto::PsbtCreatingInput {
+        signing_input: Some(Proto::SigningInput {
+            chain_info: btc_info(),
+            transaction: TransactionOneof::builder(builder),
+            ..Default::default()
+        }),
+        bip32_derivations: vec![
+            bip32_derivation(BOB_PRIVATE_KEY, "d90c6a4f"),
+            bip32_derivation(CAROL_PRIVATE_KEY, "b1a2c3d4"),
+        ],
+        version,
+        ..Default::default()
+    }
+}
+
+fn psbt(bytes: &[u8]) -> Proto::Psbt<'_> {
+    Proto::Psbt { psbt: bytes.into() }
+}
+
+type PsbtResult = Result<Vec<u8>, (SigningError, String)>;
+
+fn call_ffi<I: MessageWrite>(
+    input: &I,
+    f: unsafe extern "C" fn(*const TWData) -> *mut TWData,
+) -> Vec<u8> {
+    let input_data = TWDataHelper::create(serialize(input).unwrap());
+    TWDataHelper::wrap(unsafe { f(input_data.ptr()) })
+        .to_vec()
+        .expect("Expected a non-null output data")
+}
+
+/// Returns the resulting PSBT or an error.
+fn psbt_result(output_data: &[u8]) -> PsbtResult {
+    let output: Proto::PsbtOutput = deserialize(output_data).unwrap();
+    if output.error != SigningError::OK {
+        return Err((output.error, output.error_message.to_string()));
+    }
+    Ok(output.psbt.unwrap().psbt.to_vec())
+}
+
+fn create(input: &Proto::PsbtCreatingInput) -> PsbtResult {
+    let input_data = TWDataHelper::create(serialize(input).unwrap());
+    let output_data = TWDataHelper::wrap(unsafe {
+        tw_bitcoin_psbt_create(CoinType::Bitcoin as u32, input_data.ptr())
+    })
+    .to_vec()
+    .expect("Expected a non-null output data");
+    psbt_result(&output_data)
+}
+
+fn sign(psbt_bytes: &[u8], private_key: &str) -> PsbtResult {
+    let input = Proto::PsbtSigningInput {
+        psbt: Some(psbt(psbt_bytes)),
+        private_keys: vec![private_key.decode_hex().unwrap().into()],
+        // Deterministic Schnorr signatures to compare with the `BitcoinSigner` test vectors.
+        dangerous_use_fixed_schnorr_rng: true,
+    };
+    psbt_result(&call_ffi(&input, tw_bitcoin_psbt_sign))
+}
+
+fn combine(psbts: &[&[u8]]) -> PsbtResult {
+    let input = Proto::PsbtCombiningInput {
+        psbts: psbts.iter().map(|bytes| psbt(bytes)).collect(),
+    };
+    psbt_result(&call_ffi(&input, tw_bitcoin_psbt_combine))
+}
+
+fn finalize(psbt_bytes: &[u8]) -> PsbtResult {
+    psbt_result(&call_ffi(&psbt(psbt_bytes), tw_bitcoin_psbt_finalize))
+}
+
+/// Returns serialized `Proto::SigningOutput`.
+fn extract(psbt_bytes: &[u8]) -> Vec<u8> {
+    call_ffi(&psbt(psbt_bytes), tw_bitcoin_psbt_extract)
+}
+
+fn unwrap_psbt(result: PsbtResult) -> Vec<u8> {
+    result.unwrap_or_else(|(error, message)| panic!("{error:?}: {message}"))
+}
+
+fn error_of(result: PsbtResult) -> SigningError {
+    result.expect_err("Expected an error").0
+}
+
+fn test_bitcoin_psbt_multisig_lifecycle(version: Proto::PsbtVersion) {
+    let input = multisig_creating_input(50 * ONE_BTC - 4 * MINER_FEE, version);
+    let created = unwrap_psbt(create(&input));
+
+    // Bob (e.g. a hardware device) and Carol (e.g. a server) sign the PSBT independently.
+    let signed_by_bob = unwrap_psbt(sign(&created, BOB_PRIVATE_KEY));
+    let signed_by_carol = unwrap_psbt(sign(&created, CAROL_PRIVATE_KEY));
+
+    let combined = unwrap_psbt(combine(&[&signed_by_bob, &signed_by_carol]));
+    let finalized = unwrap_psbt(finalize(&combined));
+
+    let output_data = extract(&finalized);
+    let output: Proto::SigningOutput = deserialize(&output_data).unwrap();
+    assert_eq!(output.error, SigningError::OK, "{}", output.error_message);
+    assert_eq!(output.encoded.to_hex(), MULTISIG_SIGNED_TX);
+    assert_eq!(output.txid.to_hex(), MULTISIG_SIGNED_TXID);
+    assert_eq!(output.vsize, 149);
+    assert_eq!(output.weight, 594);
+    assert_eq!(output.fee, MINER_FEE);
+}
+
+#[test]
+fn test_bitcoin_psbt_v0_multisig_lifecycle() {
+    test_bitcoin_psbt_multisig_lifecycle(Proto::PsbtVersion::PsbtV0);
+}
+
+#[test]
+fn test_bitcoin_psbt_v2_multisig_lifecycle() {
+    test_bitcoin_psbt_multisig_lifecycle(Proto::PsbtVersion::PsbtV2);
+}
+
+#[test]
+fn test_bitcoin_psbt_v2_keeps_version() {
+    let v0_input =
+        multisig_creating_input(50 * ONE_BTC - 4 * MINER_FEE, Proto::PsbtVersion::PsbtV0);
+    let v2_input =
+        multisig_creating_input(50 * ONE_BTC - 4 * MINER_FEE, Proto::PsbtVersion::PsbtV2);
+
+    let v0 = unwrap_psbt(create(&v0_input));
+    let v2 = unwrap_psbt(create(&v2_input));
+    assert_ne!(v0, v2);
+
+    // PSBTv2 has no `PSBT_GLOBAL_UNSIGNED_TX`, but `PSBT_GLOBAL_VERSION = 2` and the per-input/output fields.
+    let v2_signed = unwrap_psbt(sign(&v2, BOB_PRIVATE_KEY));
+    assert_eq!(v2_signed.to_hex(), "70736274ff0102040200000001030400000000010401010105010101fb04020000000001012b402bd8290100000022002002991ae6e9098056c62bb288020e89cf7ca6bb06d7a269bd5c92befcab5a43dd2202037ed9a436e11ec4947ac4b7823787e24ba73180f1edd2857bff19c9f4d62b65bf473044022004caeafd1be3e70693a05af5fb4a6c2b4f9d3620cb8c6ed0c711d5acb71eb66302207f27deb6815f03887e5edb865526a08b0061c8d7bdfcfb97c953382fc6aeeef2010105695221036666dd712e05a487916384bfcd5973eb53e8038eccbbf97f7eed775b8738953621037ed9a436e11ec4947ac4b7823787e24ba73180f1edd2857bff19c9f4d62b65bf2103b85cc59b67c35851eb5060cfc3a759a482254553c5857075c9e247d74d412c9153ae2206037ed9a436e11ec4947ac4b7823787e24ba73180f1edd2857bff19c9f4d62b65bf1cd90c6a4f300000800000008000000080020000800000000000000000220603b85cc59b67c35851eb5060cfc3a759a482254553c5857075c9e247d74d412c911cb1a2c3d4300000800000008000000080020000800000000000000000010e20c717dda7ac1d846939637c52009d5bab1012efac1a6e019dc8f53235a24c9ddd010f0400000000011004ffffffff0001030800e9c8290100000001041976a914e4c1ea86373d554b8f4efff2cfb0001ea19124d288ac00");
+
+    let v0_signed = unwrap_psbt(sign(&v0, BOB_PRIVATE_KEY));
+    assert_eq!(v0_signed.to_hex(), "70736274ff0100550200000001c717dda7ac1d846939637c52009d5bab1012efac1a6e019dc8f53235a24c9ddd0000000000ffffffff0100e9c829010000001976a914e4c1ea86373d554b8f4efff2cfb0001ea19124d288ac000000000001012b402bd8290100000022002002991ae6e9098056c62bb288020e89cf7ca6bb06d7a269bd5c92befcab5a43dd2202037ed9a436e11ec4947ac4b7823787e24ba73180f1edd2857bff19c9f4d62b65bf473044022004caeafd1be3e70693a05af5fb4a6c2b4f9d3620cb8c6ed0c711d5acb71eb66302207f27deb6815f03887e5edb865526a08b0061c8d7bdfcfb97c953382fc6aeeef2010105695221036666dd712e05a487916384bfcd5973eb53e8038eccbbf97f7eed775b8738953621037ed9a436e11ec4947ac4b7823787e24ba73180f1edd2857bff19c9f4d62b65bf2103b85cc59b67c35851eb5060cfc3a759a482254553c5857075c9e247d74d412c9153ae2206037ed9a436e11ec4947ac4b7823787e24ba73180f1edd2857bff19c9f4d62b65bf1cd90c6a4f300000800000008000000080020000800000000000000000220603b85cc59b67c35851eb5060cfc3a759a482254553c5857075c9e247d74d412c911cb1a2c3d43000008000000080000000800200008000000000000000000000");
+}
+
+#[test]
+fn test_bitcoin_psbt_finalize_not_enough_signatures() {
+    let input = multisig_creating_input(50 * ONE_BTC - 4 * MINER_FEE, Proto::PsbtVersion::PsbtV0);
+    let created = unwrap_psbt(create(&input));
+    let signed_by_bob = unwrap_psbt(sign(&created, BOB_PRIVATE_KEY));
+
+    assert_eq!(
+        error_of(finalize(&signed_by_bob)),
+        SigningError::Error_signatures_count
+    );
+
+    let output_data = extract(&signed_by_bob);
+    let output: Proto::SigningOutput = deserialize(&output_data).unwrap();
+    assert_eq!(output.error, SigningError::Error_signatures_count);
+}
+
+#[test]
+fn test_bitcoin_psbt_combine_different_transactions() {
+    let input = multisig_creating_input(50 * ONE_BTC - 4 * MINER_FEE, Proto::PsbtVersion::PsbtV0);
+    let first = unwrap_psbt(create(&input));
+    let input = multisig_creating_input(50 * ONE_BTC - 5 * MINER_FEE, Proto::PsbtVersion::PsbtV0);
+    let second = unwrap_psbt(create(&input));
+
+    assert_eq!(
+        error_of(combine(&[&first, &second])),
+        SigningError::Error_invalid_params
+    );
+}
+
+#[test]
+fn test_bitcoin_psbt_sign_not_involved_key() {
+    let input = multisig_creating_input(50 * ONE_BTC - 4 * MINER_FEE, Proto::PsbtVersion::PsbtV0);
+    let created = unwrap_psbt(create(&input));
+
+    // The key is not a part of the multisig.
+    let other_private_key = "9ea2172511ed73ae0096be8e593c3b75631700edaf729f1abbae607314a20e35";
+    assert_eq!(
+        error_of(sign(&created, other_private_key)),
+        SigningError::Error_missing_private_key
+    );
+}
+
+#[test]
+fn test_bitcoin_psbt_create_legacy_input_without_previous_transaction() {
+    let alice_pubkey = public_key(ALICE_PRIVATE_KEY);
+
+    let tx1 = Proto::Input {
+        out_point: input::out_point(
+            "c01007bb55bde4e70278e1154c34db72f34a833687d3f37443bd5c49137ee5fe",
+            0,
+        ),
+        value: 50 * ONE_BTC - 2 * MINER_FEE,
+        sighash_type: SIGHASH_ALL,
+        claiming_script: input::p2pkh(alice_pubkey.compressed().to_vec()),
+        ..Default::default()
+    };
+    let out1 = Proto::Output {
+        value: 50 * ONE_BTC - 3 * MINER_FEE,
+        to_recipient: output::p2wpkh(alice_pubkey.compressed().to_vec()),
+    };
+
+    let builder = Proto::TransactionBuilder {
+        version: Proto::TransactionVersion::V2,
+        inputs: vec![tx1],
+        outputs: vec![out1],
+        input_selector: Proto::InputSelector::UseAll,
+        dust_policy: dust_threshold(DUST),
+        ..Default::default()
+    };
+    let input = Proto::PsbtCreatingInput {
+        signing_input: Some(Proto::SigningInput {
+            chain_info: btc_info(),
+            transaction: TransactionOneof::builder(builder),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        error_of(create(&input)),
+        SigningError::Error_missing_input_utxos
+    );
+}
+
+/// Signs, combines, finalizes and extracts the PSBT, and checks the resulting transaction.
+fn test_bitcoin_psbt_taproot_lifecycle(
+    input: &Proto::PsbtCreatingInput,
+    signers: &[&str],
+    expected_tx: &str,
+    expected_txid: &str,
+) {
+    let created = unwrap_psbt(create(input));
+
+    let signed: Vec<_> = signers
+        .iter()
+        .map(|private_key| unwrap_psbt(sign(&created, private_key)))
+        .collect();
+    let signed: Vec<&[u8]> = signed.iter().map(Vec::as_slice).collect();
+
+    let combined = unwrap_psbt(combine(&signed));
+    let finalized = unwrap_psbt(finalize(&combined));
+
+    let output_data = extract(&finalized);
+    let output: Proto::SigningOutput = deserialize(&output_data).unwrap();
+    assert_eq!(output.error, SigningError::OK, "{}", output.error_message);
+    assert_eq!(output.encoded.to_hex(), expected_tx);
+    assert_eq!(output.txid.to_hex(), expected_txid);
+}
+
+#[test]
+fn test_bitcoin_psbt_p2tr_key_path_lifecycle() {
+    // The same transaction as signed by `BitcoinSigner` in `test_bitcoin_sign_input_p2tr_key_path_with_change_output_a9c63d`.
+    const PRIVATE_KEY: &str = "7fa638b0df495b2968ae6dc7011c4db08c86df16c91aa71a77ee6a222954e5bb";
+    let public_key = schnorr::PrivateKey::try_from(PRIVATE_KEY).unwrap().public();
+
+    let utxo0 = Proto::Input {
+        out_point: input::out_point(
+            "75ed78f0ae2bad924065d2357ef01184ceee2181c44e03337746512be9371a82",
+            1,
+        ),
+        value: 8_802,
+        sighash_type: SIGHASH_ALL,
+        claiming_script: input::p2tr_key_path(public_key.to_vec()),
+        ..Default::default()
+    };
+    let out0 = Proto::Output {
+        value: 3_000,
+        to_recipient: output::to_address("bc1qtaquch7d90x37qre6f75z5a6l0luzh0c03epyz"),
+    };
+    let change_output = Proto::Output {
+        to_recipient: output::p2tr_key_path(public_key.to_vec()),
+        ..Proto::Output::default()
+    };
+
+    let builder = Proto::TransactionBuilder {
+        version: Proto::TransactionVersion::V2,
+        inputs: vec![utxo0],
+        outputs: vec![out0],
+        input_selector: Proto::InputSelector::SelectDescending,
+        fee_per_vb: 8,
+        change_output: Some(change_output),
+        dust_policy: dust_threshold(DUST),
+        ..Default::default()
+    };
+    let input = Proto::PsbtCreatingInput {
+        signing_input: Some(Proto::SigningInput {
+            chain_info: btc_info(),
+            transaction: TransactionOneof::builder(builder),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    test_bitcoin_psbt_taproot_lifecycle(
+        &input,
+        &[PRIVATE_KEY],
+        "02000000000101821a37e92b51467733034ec48121eece8411f07e35d2654092ad2baef078ed750100000000ffffffff02b80b0000000000001600145f41cc5fcd2bcd1f0079d27d4153bafbffc15df83212000000000000225120412a773e0bba5cfb5462d024cd4bf2cce1b8688a9e7a7a3f8507ebba8f00de580140cbe4d13bc9e067b042179e2c217e4e4b1d552119d12839aa4df11c21282f9159e2c4b58a4f22b291c200c0d0c5f277902282bdd78589dff0edbea89d3f00d77400000000",
+        "a9c63dfe54f6ff462155d966a54226c456b3e43b52a9abe55d7fa87d6564c6e4",
+    );
+}
+
+#[test]
+fn test_bitcoin_psbt_p2tr_script_path_multisig_lifecycle() {
+    // The same transaction as signed by `BitcoinSigner` in `test_bitcoin_sign_input_p2tr_script_path_multisig`:
+    // the 2-of-3 `OP_CHECKSIGADD` leaf of the `{multisig, recovery}` script tree with Alice's internal key.
+    const ALICE_INTERNAL_PRIVATE_KEY: &str =
+        "e253373989199da27c48680e3a3fc0f648d50f9a727ef17a7fe6a4dc3b159129";
+    let schnorr_pubkey =
+        |private_key: &str| schnorr::PrivateKey::try_from(private_key).unwrap().public();
+    let alice_pubkey = schnorr_pubkey(ALICE_INTERNAL_PRIVATE_KEY);
+    let bob_pubkey = schnorr_pubkey(BOB_PRIVATE_KEY);
+    let carol_pubkey = schnorr_pubkey(CAROL_PRIVATE_KEY);
+
+    let multisig_leaf = conditions::new_tapscript_multisig(
+        2,
+        &[&alice_pubkey, &bob_pubkey, &carol_pubkey].map(|pubkey| pubkey.x_only().bytes()),
+    )
+    .unwrap();
+    // `<144> OP_CSV OP_DROP <alice> OP_CHECKSIG`
+    let mut recovery_leaf = Script::new();
+    recovery_leaf.push_slice(&[0x90, 0x00]);
+    recovery_leaf.push(opcodes::OP_CSV);
+    recovery_leaf.push(opcodes::OP_DROP);
+    recovery_leaf.push_slice(alice_pubkey.x_only().bytes().as_slice());
+    recovery_leaf.push(opcodes::OP_CHECKSIG);
+
+    let tx1 = Proto::Input {
+        out_point: input::out_point(
+            "1e1f1bcaa2ee0fc2e5cda6d4f7fba6d4ca5a1d38bbd2ae8e7e8a7cb3a9e1fc53",
+            0,
+        ),
+        value: 10_000,
+        sighash_type: SIGHASH_ALL,
+        claiming_script: input::p2tr_script_tree(
+            multisig_leaf.to_vec(),
+            alice_pubkey.to_vec(),
+            vec![(multisig_leaf.to_vec(), 1), (recovery_leaf.to_vec(), 1)],
+            vec![carol_pubkey.to_vec(), bob_pubkey.to_vec()],
+        ),
+        ..Default::default()
+    };
+    let out1 = Proto::Output {
+        value: 9_000,
+        to_recipient: output::p2wpkh(alice_pubkey.to_vec()),
+    };
+
+    let builder = Proto::TransactionBuilder {
+        version: Proto::TransactionVersion::V2,
+        inputs: vec![tx1],
+        outputs: vec![out1],
+        input_selector: Proto::InputSelector::UseAll,
+        dust_policy: dust_threshold(DUST),
+        ..Default::default()
+    };
+    let input = Proto::PsbtCreatingInput {
+        signing_input: Some(Proto::SigningInput {
+            chain_info: btc_info(),
+            transaction: TransactionOneof::builder(builder),
+            ..Default::default()
+        }),
+        bip32_derivations: vec![
+            bip32_derivation(BOB_PRIVATE_KEY, "d90c6a4f"),
+            bip32_derivation(CAROL_PRIVATE_KEY, "b1a2c3d4"),
+        ],
+        ..Default::default()
+    };
+
+    // Bob and Carol sign the multisig leaf independently.
+    test_bitcoin_psbt_taproot_lifecycle(
+        &input,
+        &[BOB_PRIVATE_KEY, CAROL_PRIVATE_KEY],
+        "0200000000010153fce1a9b37c8a7e8eaed2bb381d5acad4a6fbf7d4a6cde5c20feea2ca1b1f1e0000000000ffffffff012823000000000000160014e311b8d6ddff856ce8e9a4e03bc6d4fe5050a83d05408fe85e0beaf0d6f6df58c3e4761008a078361449f112656c7bdd272dd9fac9de2e796cc3b66c2116993f2f6f0b090e53b4169e7d24a81f03fd1062169b64263b4003beb151238e9c7fc25435f3eb2510c216df29be14b91794dca8060467a84cc325dd79ffa2c78fa55092c3263e398cb95ca6e5316481af8fe4a7c25cc80e07a20068200f209b6ada5edb42c77fd2bc64ad650ae38314c8f451f3e36d80bc8e26f132cbac207ed9a436e11ec4947ac4b7823787e24ba73180f1edd2857bff19c9f4d62b65bfba20b85cc59b67c35851eb5060cfc3a759a482254553c5857075c9e247d74d412c91ba529c41c00f209b6ada5edb42c77fd2bc64ad650ae38314c8f451f3e36d80bc8e26f132cb694c31a4b6f1c12af306e11fe061502150fb8313fe60f63456ab0fd292f4fdab00000000",
+        "96e7071c88f423208bbc3fa17537764a9c5361a4902763a7a4abe3fc0f47e4c0",
+    );
+}
+
+/// BIP174 test vectors: the PSBT after the Combiner role with signatures of both signers,
+/// the PSBT after the Input Finalizer role, and the transaction after the Transaction Extractor role.
+/// https://github.com/bitcoin/bips/blob/master/bip-0174.mediawiki#test-vectors
+const BIP174_COMBINED_PSBT: &str = "cHNidP8BAJoCAAAAAljoeiG1ba8MI76OcHBFbDNvfLqlyHV5JPVFiHuyq911AAAAAAD/////g40EJ9DsZQpoqka7CwmK6kQiwHGyyng1Kgd5WdB86h0BAAAAAP////8CcKrwCAAAAAAWABTYXCtx0AYLCcmIauuBXlCZHdoSTQDh9QUAAAAAFgAUAK6pouXw+HaliN9VRuh0LR2HAI8AAAAAAAEAuwIAAAABqtc5MQGL0l+ErkALaISL4J23BurCrBgpi6vucatlb4sAAAAASEcwRAIgWPb8fGoz4bMVSNSByCbAFb0wE1qtQs1neQ2rZtKtJDsCIEoc7SYExnNbY5PltBaR3XiwDwxZQvufdRhW+qk4FX26Af7///8CgPD6AgAAAAAXqRQPuUY0IWlrgsgzryQceMF9295JNIfQ8gonAQAAABepFCnKdPigj4GZlCgYXJe12FLkBj9hh2UAAAAiAgKVg785rgpgl0etGZrd1jT6YQhVnWxc05tMIYPxq5bgf0cwRAIgdAGK1BgAl7hzMjwAFXILNoTMgSOJEEjn282bVa1nnJkCIHPTabdA4+tT3O+jOCPIBwUUylWn3ZVE8VfBZ5EyYRGMASICAtq2H/SaFNtqfQKwzR+7ePxLGDErW05U2uTbovv+9TbXSDBFAiEA9hA4swjcHahlo0hSdG8BV3KTQgjG0kRUOTzZm98iF3cCIAVuZ1pnWm0KArhbFOXikHTYolqbV2C+ooFvZhkQoAbqAQEDBAEAAAABBEdSIQKVg785rgpgl0etGZrd1jT6YQhVnWxc05tMIYPxq5bgfyEC2rYf9JoU22p9ArDNH7t4/EsYMStbTlTa5Nui+/71NtdSriIGApWDvzmuCmCXR60Zmt3WNPphCFWdbFzTm0whg/GrluB/ENkMak8AAACAAAAAgAAAAIAiBgLath/0mhTban0CsM0fu3j8SxgxK1tOVNrk26L7/vU21xDZDGpPAAAAgAAAAIABAACAAAEBIADC6wsAAAAAF6kUt/X69A49QKWkWbHbNTXyty+pIeiHIgICOt2QTz1tz1nduQaw3uI1Kbf/ue1Q5ehhUZJoYCIfDnNHMEQCIGX0W6WZi1mif/4ae+0BavHx+Q1Us6qPdFCqX1aiUQO9AiB/ckcDrR7blmgLKEtW1P/LiPf7dZ6rvgiqMPKbhROD0gEiAgMIncEMesbbVPkTKa9hczPbOIzq0MIx9yM3nRuZAwsC3EcwRAIgYut6VWEHp8c/RaxKtaHd329wdfsSdZaafzg+//eEvLICIAwF27dHDb8vCFV901bHMlwe0wkT6ZbNOECUXbEiKNpfAQEDBAEAAAABBCIAIIwjUxc3Q7WV37Sge3K6jkLjeX2nTof+fZ10l+OyAokDAQVHUiEDCJ3BDHrG21T5EymvYXMz2ziM6tDCMfcjN50bmQMLAtwhAjrdkE89bc9Z3bkGsN7iNSm3/7ntUOXoYVGSaGAiHw5zUq4iBgI63ZBPPW3PWd25BrDe4jUpt/+57VDl6GFRkmhgIh8OcxDZDGpPAAAAgAAAAIADAACAIgYDCJ3BDHrG21T5EymvYXMz2ziM6tDCMfcjN50bmQMLAtwQ2QxqTwAAAIAAAACAAgAAgAAiAgOppMN/WZbTqiXbrGtXCvBlA5RJKUJGCzVHU+2e7KWHcRDZDGpPAAAAgAAAAIAEAACAACICAn9jmXV9Lv9VoTatAsaEsYOLZVbl8bazQoKpS2tQBRCWENkMak8AAACAAAAAgAUAAIAA";
+const BIP174_FINALIZED_PSBT: &str = "cHNidP8BAJoCAAAAAljoeiG1ba8MI76OcHBFbDNvfLqlyHV5JPVFiHuyq911AAAAAAD/////g40EJ9DsZQpoqka7CwmK6kQiwHGyyng1Kgd5WdB86h0BAAAAAP////8CcKrwCAAAAAAWABTYXCtx0AYLCcmIauuBXlCZHdoSTQDh9QUAAAAAFgAUAK6pouXw+HaliN9VRuh0LR2HAI8AAAAAAAEAuwIAAAABqtc5MQGL0l+ErkALaISL4J23BurCrBgpi6vucatlb4sAAAAASEcwRAIgWPb8fGoz4bMVSNSByCbAFb0wE1qtQs1neQ2rZtKtJDsCIEoc7SYExnNbY5PltBaR3XiwDwxZQvufdRhW+qk4FX26Af7///8CgPD6AgAAAAAXqRQPuUY0IWlrgsgzryQceMF9295JNIfQ8gonAQAAABepFCnKdPigj4GZlCgYXJe12FLkBj9hh2UAAAABB9oARzBEAiB0AYrUGACXuHMyPAAVcgs2hMyBI4kQSOfbzZtVrWecmQIgc9Npt0Dj61Pc76M4I8gHBRTKVafdlUTxV8FnkTJhEYwBSDBFAiEA9hA4swjcHahlo0hSdG8BV3KTQgjG0kRUOTzZm98iF3cCIAVuZ1pnWm0KArhbFOXikHTYolqbV2C+ooFvZhkQoAbqAUdSIQKVg785rgpgl0etGZrd1jT6YQhVnWxc05tMIYPxq5bgfyEC2rYf9JoU22p9ArDNH7t4/EsYMStbTlTa5Nui+/71NtdSrgABASAAwusLAAAAABepFLf1+vQOPUClpFmx2zU18rcvqSHohwEHIyIAIIwjUxc3Q7WV37Sge3K6jkLjeX2nTof+fZ10l+OyAokDAQjaBABHMEQCIGLrelVhB6fHP0WsSrWh3d9vcHX7EnWWmn84Pv/3hLyyAiAMBdu3Rw2/LwhVfdNWxzJcHtMJE+mWzThAlF2xIijaXwFHMEQCIGX0W6WZi1mif/4ae+0BavHx+Q1Us6qPdFCqX1aiUQO9AiB/ckcDrR7blmgLKEtW1P/LiPf7dZ6rvgiqMPKbhROD0gFHUiEDCJ3BDHrG21T5EymvYXMz2ziM6tDCMfcjN50bmQMLAtwhAjrdkE89bc9Z3bkGsN7iNSm3/7ntUOXoYVGSaGAiHw5zUq4AIgIDqaTDf1mW06ol26xrVwrwZQOUSSlCRgs1R1Ptnuylh3EQ2QxqTwAAAIAAAACABAAAgAAiAgJ/Y5l1fS7/VaE2rQLGhLGDi2VW5fG2s0KCqUtrUAUQlhDZDGpPAAAAgAAAAIAFAACAAA==";
+const BIP174_EXTRACTED_TX: &str = "0200000000010258e87a21b56daf0c23be8e7070456c336f7cbaa5c8757924f545887bb2abdd7500000000da00473044022074018ad4180097b873323c0015720b3684cc8123891048e7dbcd9b55ad679c99022073d369b740e3eb53dcefa33823c8070514ca55a7dd9544f157c167913261118c01483045022100f61038b308dc1da865a34852746f015772934208c6d24454393cd99bdf2217770220056e675a675a6d0a02b85b14e5e29074d8a25a9b5760bea2816f661910a006ea01475221029583bf39ae0a609747ad199addd634fa6108559d6c5cd39b4c2183f1ab96e07f2102dab61ff49a14db6a7d02b0cd1fbb78fc4b18312b5b4e54dae4dba2fbfef536d752aeffffffff838d0427d0ec650a68aa46bb0b098aea4422c071b2ca78352a077959d07cea1d01000000232200208c2353173743b595dfb4a07b72ba8e42e3797da74e87fe7d9d7497e3b2028903ffffffff0270aaf00800000000160014d85c2b71d0060b09c9886aeb815e50991dda124d00e1f5050000000016001400aea9a2e5f0f876a588df5546e8742d1d87008f000400473044022062eb7a556107a7c73f45ac4ab5a1dddf6f7075fb1275969a7f383efff784bcb202200c05dbb7470dbf2f08557dd356c7325c1ed30913e996cd3840945db12228da5f01473044022065f45ba5998b59a27ffe1a7bed016af1f1f90d54b3aa8f7450aa5f56a25103bd02207f724703ad1edb96680b284b56d4ffcb88f7fb759eabbe08aa30f29b851383d20147522103089dc10c7ac6db54f91329af617333db388cead0c231f723379d1b99030b02dc21023add904f3d6dcf59ddb906b0dee23529b7ffb9ed50e5e86151926860221f0e7352ae00000000";
+const BIP174_EXTRACTED_TXID: &str =
+    "c001dff12b319c432360072394690d2e9ef1a28a5d77e3f5346ecc46dff966cd";
+
+/// BIP370 test vector: 1 input, 2 output PSBTv2 with the required fields only.
+/// https://github.com/bitcoin/bips/blob/master/bip-0370.mediawiki#test-vectors
+const BIP370_PSBT_V2: &str = "cHNidP8BAgQCAAAAAQQBAQEFAQIB+wQCAAAAAAEOIAsK2SFBnByHGXNdctxzn56p4GONH+TB7vD5lECEgV/IAQ8EAAAAAAABAwgACK8vAAAAAAEEFgAUxDD2TEdW2jENvRoIVXLvKZkmJywAAQMIi73rCwAAAAABBBYAFE3Rk6yWSlasG54cyoRU/i9HT4UTAA==";
+
+fn decode_base64(s: &str) -> Vec<u8> {
+    base64::decode(s, STANDARD).unwrap()
+}
+
+/// Finalizes the signed PSBT and extracts the network transaction.
+fn test_bitcoin_psbt_finalize_extract(signed: &[u8], expected_finalized: &[u8], expected_tx: &str) {
+    let finalized = unwrap_psbt(finalize(signed));
+    assert_eq!(finalized.to_hex(), expected_finalized.to_hex());
+
+    let output_data = extract(&finalized);
+    let output: Proto::SigningOutput = deserialize(&output_data).unwrap();
+    assert_eq!(output.error, SigningError::OK, "{}", output.error_message);
+    assert_eq!(output.encoded.to_hex(), expected_tx);
+}
+
+#[test]
+fn test_bitcoin_psbt_bip174_finalize_extract() {
+    let combined = decode_base64(BIP174_COMBINED_PSBT);
+    let finalized = decode_base64(BIP174_FINALIZED_PSBT);
+    test_bitcoin_psbt_finalize_extract(&combined, &finalized, BIP174_EXTRACTED_TX);
+
+    let output_data = extract(&finalized);
+    let output: Proto::SigningOutput = deserialize(&output_data).unwrap();
+    assert_eq!(output.txid.to_hex(), BIP174_EXTRACTED_TXID);
+    // 0.5 BTC + 2 BTC inputs, 1.4999 BTC + 1 BTC outputs.
+    assert_eq!(output.fee, 10_000);
+}
+
+#[test]
+fn test_bitcoin_psbt_bip370_v2_round_trip() {
+    let v2 = decode_base64(BIP370_PSBT_V2);
+    // Combining a single PSBT keeps the version and all the fields,
+    // but makes the default `PSBT_GLOBAL_FALLBACK_LOCKTIME` and `PSBT_IN_SEQUENCE` explicit.
+    let combined = unwrap_psbt(combine(&[&v2]));
+    assert_eq!(combined.to_hex(), "70736274ff0102040200000001030400000000010401010105010201fb040200000000010e200b0ad921419c1c8719735d72dc739f9ea9e0638d1fe4c1eef0f9944084815fc8010f0400000000011004ffffffff000103080008af2f000000000104160014c430f64c4756da310dbd1a085572ef299926272c000103088bbdeb0b0000000001041600144dd193ac964a56ac1b9e1cca8454fe2f474f851300");
+    assert_eq!(unwrap_psbt(combine(&[&combined])), combined);
+}
+
+#[test]
+fn test_bitcoin_psbt_bip370_invalid() {
+    let v2 = decode_base64(BIP370_PSBT_V2).to_hex();
+    // BIP370 invalid test cases built from the valid vector.
+    let invalid = [
+        // PSBTv2 missing PSBT_GLOBAL_INPUT_COUNT.
+        v2.replacen("01040101", "", 1),
+        // PSBTv2 missing PSBT_GLOBAL_OUTPUT_COUNT.
+        v2.replacen("01050102", "", 1),
+        // PSBTv2 missing PSBT_IN_PREVIOUS_TXID.
+        v2.replacen(
+            "010e200b0ad921419c1c8719735d72dc739f9ea9e0638d1fe4c1eef0f9944084815fc8",
+            "",
+            1,
+        ),
+        // PSBTv2 missing PSBT_IN_OUTPUT_INDEX.
+        v2.replacen("010f0400000000", "", 1),
+        // PSBTv2 missing PSBT_OUT_AMOUNT.
+        v2.replacen("0103080008af2f00000000", "", 1),
+        // PSBTv2 missing PSBT_OUT_SCRIPT.
+        v2.replacen("0104160014c430f64c4756da310dbd1a085572ef299926272c", "", 1),
+    ];
+
+    for psbt_hex in invalid {
+        assert_ne!(psbt_hex, v2);
+        let psbt_bytes = psbt_hex.as_str().decode_hex().unwrap();
+        assert!(combine(&[&psbt_bytes]).is_err(), "{psbt_hex}");
+    }
+}
+
+#[test]
+fn test_bitcoin_psbt_bip370_invalid_version_fields() {
+    let v0 = decode_base64(BIP174_COMBINED_PSBT).to_hex();
+    let v2 = decode_base64(BIP370_PSBT_V2).to_hex();
+    // The unsigned transaction of `BIP370_PSBT_V2`.
+    let unsigned_tx = "02000000010b0ad921419c1c8719735d72dc739f9ea9e0638d1fe4c1eef0f9944084815fc80000000000ffffffff020008af2f00000000160014c430f64c4756da310dbd1a085572ef299926272c8bbdeb0b000000001600144dd193ac964a56ac1b9e1cca8454fe2f474f851300000000";
+
+    let invalid = [
+        // PSBTv2 with PSBT_GLOBAL_UNSIGNED_TX.
+        v2.replacen("70736274ff", &format!("70736274ff010071{unsigned_tx}"), 1),
+        // PSBTv0 but with PSBT_GLOBAL_VERSION set to 2.
+        v0.replacen("70736274ff", "70736274ff01fb0402000000", 1),
+        // PSBTv0 with PSBT_GLOBAL_TX_VERSION.
+        v0.replacen("70736274ff", "70736274ff01020402000000", 1),
+        // PSBTv0 with PSBT_GLOBAL_TX_MODIFIABLE.
+        v0.replacen("70736274ff", "70736274ff01060100", 1),
+        // PSBTv0 with PSBT_IN_SEQUENCE.
+        v0.replacen("0100bb", "011004ffffffff0100bb", 1),
+        // PSBTv0 with PSBT_OUT_AMOUNT.
+        v0.replacen("2202027f6399", "01030800e1f505000000002202027f6399", 1),
+    ];
+
+    for psbt_hex in invalid {
+        assert_ne!(psbt_hex, v0);
+        assert_ne!(psbt_hex, v2);
+        let psbt_bytes = psbt_hex.as_str().decode_hex().unwrap();
+        assert_eq!(
+            error_of(combine(&[&psbt_bytes])),
+            SigningError::Error_input_parse,
+            "{psbt_hex}"
+        );
+    }
+}
+
+#[test]
+fn test_bitcoin_psbt_finalize_p2wpkh() {
+    let alice_pubkey = public_key(ALICE_PRIVATE_KEY).compressed();
+    let bob_pubkey = public_key(BOB_PRIVATE_KEY).compressed();
+
+    let tx1 = Proto::Input {
+        out_point: input::out_point(
+            "5fdea64d02adcd0c9af4d1fdc82f8ca5e0a5a76e5ab4bbf7b4a7d0f4e62a4f11",
+            0,
+        ),
+        value: ONE_BTC,
+        sighash_type: SIGHASH_ALL,
+        claiming_script: input::p2wpkh(alice_pubkey.to_vec()),
+        ..Default::default()
+    };
+    let out1 = Proto::Output {
+        value: ONE_BTC - MINER_FEE,
+        to_recipient: output::p2wpkh(bob_pubkey.to_vec()),
+    };
+
+    let builder = Proto::TransactionBuilder {
+        version: Proto::TransactionVersion::V2,
+        inputs: vec![tx1],
+        outputs: vec![out1],
+        input_selector: Proto::InputSelector::UseAll,
+        dust_policy: dust_threshold(DUST),
+        ..Default::default()
+    };
+    let input = Proto::PsbtCreatingInput {
+        signing_input: Some(Proto::SigningInput {
+            chain_info: btc_info(),
+            transaction: TransactionOneof::builder(builder),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let created = unwrap_psbt(create(&input));
+    let signed = unwrap_psbt(sign(&created, ALICE_PRIVATE_KEY));
+    let finalized = unwrap_psbt(finalize(&signed));
+
+    let output_data = extract(&finalized);
+    let output: Proto::SigningOutput = deserialize(&output_data).unwrap();
+    assert_eq!(output.error, SigningError::OK, "{}", output.error_message);
+    assert_eq!(output.encoded.to_hex(), "02000000000101114f2ae6f4d0a7b4f7bbb45a6ea7a5e0a58c2fc8fdd1f49a0ccdad024da6de5f0000000000ffffffff01c09ee605000000001600145eaaa4f458f9158f86afcba08dd7448d27045e3d02473044022079faa1a58d7ae304e02631c410632e2efb969a107087a5f69916e0181814b5c502203e6f406ebb01756b5c72362db2ec31bb446f4ecb5d0e7e7a46c7fe35f5f2afa20121036666dd712e05a487916384bfcd5973eb53e8038eccbbf97f7eed775b8738953600000000");
+    assert_eq!(output.fee, MINER_FEE);
+}
+
+#[test]
+fn test_bitcoin_psbt_finalize_p2sh_p2wpkh() {
+    // Alice's P2WPKH nested in P2SH input signed by Alice, sends to Bob's P2WPKH.
+    let signed = "70736274ff0100520200000001c1e0a3f2a5b6c0e8c5a4d8a3c6bbfdd3758b6e6ac85042b02ab444637fb0bcb80000000000ffffffff01c09ee605000000001600145eaaa4f458f9158f86afcba08dd7448d27045e3d000000000001012000e1f5050000000017a914bf78ab136711a59a08f2f5b85ce3300a8506a96e872202036666dd712e05a487916384bfcd5973eb53e8038eccbbf97f7eed775b8738953647304402204e7e81da9ddda818fa445f85cec9519c3ff845d3ddc5879ad331221d858bd7b70220408191b81c6467067005546ba1eafd936128ae50219bc30ce87e2e33056d4bd5010104160014e4c1ea86373d554b8f4efff2cfb0001ea19124d20000".decode_hex().unwrap();
+    let expected_finalized = "70736274ff0100520200000001c1e0a3f2a5b6c0e8c5a4d8a3c6bbfdd3758b6e6ac85042b02ab444637fb0bcb80000000000ffffffff01c09ee605000000001600145eaaa4f458f9158f86afcba08dd7448d27045e3d000000000001012000e1f5050000000017a914bf78ab136711a59a08f2f5b85ce3300a8506a96e87010717160014e4c1ea86373d554b8f4efff2cfb0001ea19124d201086b0247304402204e7e81da9ddda818fa445f85cec9519c3ff845d3ddc5879ad331221d858bd7b70220408191b81c6467067005546ba1eafd936128ae50219bc30ce87e2e33056d4bd50121036666dd712e05a487916384bfcd5973eb53e8038eccbbf97f7eed775b873895360000".decode_hex().unwrap();
+    test_bitcoin_psbt_finalize_extract(
+        &signed,
+        &expected_finalized,
+        "02000000000101c1e0a3f2a5b6c0e8c5a4d8a3c6bbfdd3758b6e6ac85042b02ab444637fb0bcb80000000017160014e4c1ea86373d554b8f4efff2cfb0001ea19124d2ffffffff01c09ee605000000001600145eaaa4f458f9158f86afcba08dd7448d27045e3d0247304402204e7e81da9ddda818fa445f85cec9519c3ff845d3ddc5879ad331221d858bd7b70220408191b81c6467067005546ba1eafd936128ae50219bc30ce87e2e33056d4bd50121036666dd712e05a487916384bfcd5973eb53e8038eccbbf97f7eed775b8738953600000000",
+    );
+}
+
+#[test]
+fn test_bitcoin_psbt_finalize_p2tr_key_path() {
+    // The same input as signed by `BitcoinSigner` in `test_bitcoin_sign_input_p2tr_key_path`.
+    let signed = "70736274ff01005e0200000001ac6058397e18c277e98defda1bc38bdf3ab304563d7df7afed0ca5f63220589a0000000000ffffffff01806de72901000000225120a5c027857e359d19f625e52a106b8ac6ca2d6a8728f6cf2107cd7958ee0787c2000000000001012bc0aff62901000000225120e01cfdd05da8fa1d71f987373f3790d45dea9861acb0525c86656fe50f4397a6011340ec2d3910d41506b60aaa20520bb72f15e2d2cbd97e3a8e26ee7bad5f4c56b0f2fb0ceaddac33cb2813a33ba017ba6b1d011bab74a0426f12a2bcf47b4ed5bc860000".decode_hex().unwrap();
+    let expected_finalized = "70736274ff01005e0200000001ac6058397e18c277e98defda1bc38bdf3ab304563d7df7afed0ca5f63220589a0000000000ffffffff01806de72901000000225120a5c027857e359d19f625e52a106b8ac6ca2d6a8728f6cf2107cd7958ee0787c2000000000001012bc0aff62901000000225120e01cfdd05da8fa1d71f987373f3790d45dea9861acb0525c86656fe50f4397a60108420140ec2d3910d41506b60aaa20520bb72f15e2d2cbd97e3a8e26ee7bad5f4c56b0f2fb0ceaddac33cb2813a33ba017ba6b1d011bab74a0426f12a2bcf47b4ed5bc860000".decode_hex().unwrap();
+    test_bitcoin_psbt_finalize_extract(
+        &signed,
+        &expected_finalized,
+        "02000000000101ac6058397e18c277e98defda1bc38bdf3ab304563d7df7afed0ca5f63220589a0000000000ffffffff01806de72901000000225120a5c027857e359d19f625e52a106b8ac6ca2d6a8728f6cf2107cd7958ee0787c20140ec2d3910d41506b60aaa20520bb72f15e2d2cbd97e3a8e26ee7bad5f4c56b0f2fb0ceaddac33cb2813a33ba017ba6b1d011bab74a0426f12a2bcf47b4ed5bc8600000000",
+    );
+}
+
+#[test]
+fn test_bitcoin_psbt_finalize_p2tr_script_path_extra_signature() {
+    // The 2-of-3 `OP_CHECKSIGADD` leaf of `test_bitcoin_sign_input_p2tr_script_path_multisig`
+    // signed by all of Alice, Bob and Carol.
+    // `OP_NUMEQUAL` requires exactly 2 signatures, so Carol's signature must be replaced with an empty item.
+    let signed = "70736274ff010052020000000153fce1a9b37c8a7e8eaed2bb381d5acad4a6fbf7d4a6cde5c20feea2ca1b1f1e0000000000ffffffff012823000000000000160014e311b8d6ddff856ce8e9a4e03bc6d4fe5050a83d000000000001012b102700000000000022512024ed6b078834135ea08abd6cecd0555b4e3e675d8a5559948804fe1b541fb63f41140f209b6ada5edb42c77fd2bc64ad650ae38314c8f451f3e36d80bc8e26f132cb51202577acc83ce4aac18f2185c02d88c5abf4edd7db54c5c892024bc1aa7cf340cdf72a99cd5c664d87fc26b75593bbeb6f19ebe10f03470926d02a1745996bcd1cdb1a9fc5a9153680bbaabf92221f865047200f28effa44383ec76d88f26d1341147ed9a436e11ec4947ac4b7823787e24ba73180f1edd2857bff19c9f4d62b65bf51202577acc83ce4aac18f2185c02d88c5abf4edd7db54c5c892024bc1aa7cf34003beb151238e9c7fc25435f3eb2510c216df29be14b91794dca8060467a84cc325dd79ffa2c78fa55092c3263e398cb95ca6e5316481af8fe4a7c25cc80e07a24114b85cc59b67c35851eb5060cfc3a759a482254553c5857075c9e247d74d412c9151202577acc83ce4aac18f2185c02d88c5abf4edd7db54c5c892024bc1aa7cf3408fe85e0beaf0d6f6df58c3e4761008a078361449f112656c7bdd272dd9fac9de2e796cc3b66c2116993f2f6f0b090e53b4169e7d24a81f03fd1062169b64263b4215c00f209b6ada5edb42c77fd2bc64ad650ae38314c8f451f3e36d80bc8e26f132cb694c31a4b6f1c12af306e11fe061502150fb8313fe60f63456ab0fd292f4fdab69200f209b6ada5edb42c77fd2bc64ad650ae38314c8f451f3e36d80bc8e26f132cbac207ed9a436e11ec4947ac4b7823787e24ba73180f1edd2857bff19c9f4d62b65bfba20b85cc59b67c35851eb5060cfc3a759a482254553c5857075c9e247d74d412c91ba529cc00000".decode_hex().unwrap();
+    let expected_finalized = "70736274ff010052020000000153fce1a9b37c8a7e8eaed2bb381d5acad4a6fbf7d4a6cde5c20feea2ca1b1f1e0000000000ffffffff012823000000000000160014e311b8d6ddff856ce8e9a4e03bc6d4fe5050a83d000000000001012b102700000000000022512024ed6b078834135ea08abd6cecd0555b4e3e675d8a5559948804fe1b541fb63f0108fd2f0105004003beb151238e9c7fc25435f3eb2510c216df29be14b91794dca8060467a84cc325dd79ffa2c78fa55092c3263e398cb95ca6e5316481af8fe4a7c25cc80e07a240cdf72a99cd5c664d87fc26b75593bbeb6f19ebe10f03470926d02a1745996bcd1cdb1a9fc5a9153680bbaabf92221f865047200f28effa44383ec76d88f26d1368200f209b6ada5edb42c77fd2bc64ad650ae38314c8f451f3e36d80bc8e26f132cbac207ed9a436e11ec4947ac4b7823787e24ba73180f1edd2857bff19c9f4d62b65bfba20b85cc59b67c35851eb5060cfc3a759a482254553c5857075c9e247d74d412c91ba529c41c00f209b6ada5edb42c77fd2bc64ad650ae38314c8f451f3e36d80bc8e26f132cb694c31a4b6f1c12af306e11fe061502150fb8313fe60f63456ab0fd292f4fdab0000".decode_hex().unwrap();
+    test_bitcoin_psbt_finalize_extract(
+        &signed,
+        &expected_finalized,
+        "0200000000010153fce1a9b37c8a7e8eaed2bb381d5acad4a6fbf7d4a6cde5c20feea2ca1b1f1e0000000000ffffffff012823000000000000160014e311b8d6ddff856ce8e9a4e03bc6d4fe5050a83d05004003beb151238e9c7fc25435f3eb2510c216df29be14b91794dca8060467a84cc325dd79ffa2c78fa55092c3263e398cb95ca6e5316481af8fe4a7c25cc80e07a240cdf72a99cd5c664d87fc26b75593bbeb6f19ebe10f03470926d02a1745996bcd1cdb1a9fc5a9153680bbaabf92221f865047200f28effa44383ec76d88f26d1368200f209b6ada5edb42c77fd2bc64ad650ae38314c8f451f3e36d80bc8e26f132cbac207ed9a436e11ec4947ac4b7823787e24ba73180f1edd2857bff19c9f4d62b65bfba20b85cc59b67c35851eb5060cfc3a759a482254553c5857075c9e247d74d412c91ba529c41c00f209b6ada5edb42c77fd2bc64ad650ae38314c8f451f3e36d80bc8e26f132cb694c31a4b6f1c12af306e11fe061502150fb8313fe60f63456ab0fd292f4fdab00000000",
+    );
+}
@@ -10,6 +10,17 @@ use tw_keypair::ecdsa;
 use tw_proto::BitcoinV2::Proto;
 use tw_utxo::address::legacy::LegacyAddress;
 use tw_utxo::script::standard_script::conditions;
+use tw_utxo::script::Script;
+
+const ALICE_PRIVATE_KEY: &str = "56429688a1a6b00b90ccd22a0de0a376b6569d8684022ae92229a28478bfb657";
+const BOB_PRIVATE_KEY: &str = "b7da1ec42b19085fe09fec54b9d9eacd998ae4e6d2ad472be38d8393391b9ead";
+const CAROL_PRIVATE_KEY: &str = "3c3385ddc6fd95ba7282051aeb440bc75820b8c10db5c83c052d7586e3e98e84";
+
+fn p2pkh_redeem_script(public_key: &ecdsa::secp256k1::PublicKey) -> Script {
+    let pubkey_hash = sha256_ripemd(public_key.compressed().as_slice());
+    let pubkey_hash = H160::try_from(pubkey_hash.as_slice()).unwrap();
+    conditions::new_p2pkh(&pubkey_hash)
+}
 
 enum P2SHRecipientType {
     RedeemScript,
@@ -108,84 +119,146 @@ fn test_bitcoin_sign_output_p2sh_with_address() {
     test_bitcoin_sign_input_p2pkh_output_p2sh(P2SHRecipientType::P2SHAddress);
 }
 
-// TODO uncomment when P2SH spend is ready
-// #[test]
-// fn test_bitcoin_sign_input_p2sh_output_p2pkh() {
-//     // Create transaction with P2SH as input (claim).
-//
-//     let txid: Vec<u8> = hex("5d99b77a411a879fb6fa5b442f0d121965346d8e5ab61e0d189967fd5f49bd82")
-//         .into_iter()
-//         .rev()
-//         .collect();
-//
-//     let tx1 = Proto::Input {
-//         txid: txid.as_slice().into(),
-//         vout: 0,
-//         value: 50 * ONE_BTC - MINER_FEE,
-//         sighash_type: UtxoProto::SighashType::All,
-//         to_recipient: ProtoInputRecipient::builder(Proto::mod_Input::InputBuilder {
-//             // The way P2SH is signed in Bitcoin, we first place the redeem script directly here.
-//             variant: ProtoInputBuilder::p2sh(redeem_script.as_bytes().into()),
-//         }),
-//         ..Default::default()
-//     };
-//
-//     let out1 = Proto::Output {
-//         value: 50 * ONE_BTC - MINER_FEE - MINER_FEE,
-//         to_recipient: ProtoOutputRecipient::builder(Proto::mod_Output::OutputBuilder {
-//             variant: ProtoOutputBuilder::p2pkh(Proto::ToPublicKeyOrHash {
-//                 to_address: ProtoPubkeyOrHash::pubkey(alice_pubkey.as_slice().into()),
-//             }),
-//         }),
-//     };
-//
-//     let mut signing = Proto::SigningInput {
-//         inputs: vec![tx1],
-//         outputs: vec![out1],
-//         input_selector: UtxoProto::InputSelector::UseAll,
-//         disable_change_output: true,
-//         ..Default::default()
-//     };
-//
-//     // Generate the sighashes.
-//     let sighashes = BitcoinEntry.preimage_hashes(&coin, signing.clone());
-//     assert_eq!(sighashes.error, Proto::Error::OK);
-//
-//     // Sign the sighashes.
-//     let signatures = Signer::signatures_from_proto(
-//         &sighashes,
-//         bob_private_key.to_vec(),
-//         Default::default(),
-//         false,
-//     )
-//     .unwrap();
-//
-//     let sig = &signatures[0];
-//
-//     // Construc the final redeem scrip with the necessary stack items (signature + pubkey).
-//     let mut sig_buf = PushBytesBuf::new();
-//     sig_buf.extend_from_slice(sig).unwrap();
-//
-//     let mut redeem_buf = PushBytesBuf::new();
-//     redeem_buf
-//         .extend_from_slice(redeem_script.as_bytes())
-//         .unwrap();
-//
-//     let finalized = ScriptBuf::builder()
-//         .push_slice(sig_buf)
-//         .push_key(&bob_native_pubkey)
-//         .push_slice(redeem_buf.as_push_bytes())
-//         .into_script();
-//
-//     // Now that we've signed the input, we update the input with the complete,
-//     // finalized redeem script.
-//     signing.inputs[0].to_recipient = ProtoInputRecipient::builder(Proto::mod_Input::InputBuilder {
-//         variant: ProtoInputBuilder::p2sh(finalized.as_bytes().into()),
-//     });
-//
-//     // Compile the final transaction.
-//     let signed = BitcoinEntry.compile(&coin, signing, signatures, vec![]);
-//     let encoded = tw_encoding::hex::encode(signed.encoded, false);
-//     assert_eq!(signed.error, Proto::Error::OK);
-//     assert_eq!(&encoded, "020000000182bd495ffd6799180d1eb65a8e6d346519120d2f445bfab69f871a417ab7995d000000008447304402207aad4b72c6d78c81a1e795325bd5ddb449f0a1363205903f5e37950e6b89054102202aaf4dd919700d21fe2431352df99c434378bd0d46b778b445079579300effdf0121037ed9a436e11ec4947ac4b7823787e24ba73180f1edd2857bff19c9f4d62b65bf1976a9145eaaa4f458f9158f86afcba08dd7448d27045e3d88acffffffff01806de729010000001976a914e4c1ea86373d554b8f4efff2cfb0001ea19124d288ac00000000");
-// }
+#[test]
+fn test_bitcoin_sign_input_p2sh_p2pkh_output_p2pkh() {
+    let alice_private_key = ecdsa::secp256k1::PrivateKey::try_from(ALICE_PRIVATE_KEY).unwrap();
+    let alice_pubkey = alice_private_key.public();
+
+    let bob_private_key = ecdsa::secp256k1::PrivateKey::try_from(BOB_PRIVATE_KEY).unwrap();
+    let bob_pubkey = bob_private_key.public();
+
+    // Spend the P2SH output created at `test_bitcoin_sign_output_p2sh_with_redeem_script`.
+    let redeem_script = p2pkh_redeem_script(&bob_pubkey);
+
+    let txid = "5d99b77a411a879fb6fa5b442f0d121965346d8e5ab61e0d189967fd5f49bd82";
+    let tx0 = Proto::Input {
+        out_point: input::out_point(txid, 0),
+        value: 50 * ONE_BTC - MINER_FEE,
+        sighash_type: SIGHASH_ALL,
+        claiming_script: input::p2sh(
+            redeem_script.to_vec(),
+            vec![bob_pubkey.compressed().to_vec()],
+        ),
+        ..Default::default()
+    };
+
+    let out0 = Proto::Output {
+        value: 50 * ONE_BTC - 2 * MINER_FEE,
+        to_recipient: output::p2pkh(alice_pubkey.compressed().to_vec()),
+    };
+
+    let builder = Proto::TransactionBuilder {
+        version: Proto::TransactionVersion::V2,
+        inputs: vec![tx0],
+        outputs: vec![out0],
+        input_selector: Proto::InputSelector::UseAll,
+        dust_policy: dust_threshold(DUST),
+        ..Default::default()
+    };
+
+    let input = Proto::SigningInput {
+        private_keys: vec![BOB_PRIVATE_KEY.decode_hex().unwrap().into()],
+        chain_info: btc_info(),
+        transaction: TransactionOneof::builder(builder),
+        ..Default::default()
+    };
+
+    sign::BitcoinSignHelper::new(&input)
+        .coin(CoinType::Bitcoin)
+        .sign(sign::Expected {
+            encoded: "020000000182bd495ffd6799180d1eb65a8e6d346519120d2f445bfab69f871a417ab7995d000000008447304402207aad4b72c6d78c81a1e795325bd5ddb449f0a1363205903f5e37950e6b89054102202aaf4dd919700d21fe2431352df99c434378bd0d46b778b445079579300effdf0121037ed9a436e11ec4947ac4b7823787e24ba73180f1edd2857bff19c9f4d62b65bf1976a9145eaaa4f458f9158f86afcba08dd7448d27045e3d88acffffffff01806de729010000001976a914e4c1ea86373d554b8f4efff2cfb0001ea19124d288ac00000000",
+            txid: "c01007bb55bde4e70278e1154c34db72f34a833687d3f37443bd5c49137ee5fe",
+            inputs: vec![50 * ONE_BTC - MINER_FEE],
+            outputs: vec![50 * ONE_BTC - 2 * MINER_FEE],
+            vsize: 217,
+            weight: 868,
+            fee: MINER_FEE,
+        });
+}
+
+fn test_bitcoin_sign_input_p2sh_multisig_impl(signers: Vec<Vec<u8>>) {
+    let alice_private_key = ecdsa::secp256k1::PrivateKey::try_from(ALICE_PRIVATE_KEY).unwrap();
+    let alice_pubkey = alice_private_key.public();
+    let bob_pubkey = ecdsa::secp256k1::PrivateKey::try_from(BOB_PRIVATE_KEY)
+        .unwrap()
+        .public();
+    let carol_pubkey = ecdsa::secp256k1::PrivateKey::try_from(CAROL_PRIVATE_KEY)
+        .unwrap()
+        .public();
+
+    // 2-of-3 multisig redeem script.
+    let redeem_script = conditions::new_multisig(
+        2,
+        &[
+            alice_pubkey.compressed(),
+            bob_pubkey.compressed(),
+            carol_pubkey.compressed(),
+        ],
+    )
+    .unwrap();
+
+    let txid = "5d99b77a411a879fb6fa5b442f0d121965346d8e5ab61e0d189967fd5f49bd82";
+    let tx0 = Proto::Input {
+        out_point: input::out_point(txid, 0),
+        value: 50 * ONE_BTC - MINER_FEE,
+        sighash_type: SIGHASH_ALL,
+        claiming_script: input::p2sh(redeem_script.to_vec(), signers),
+        ..Default::default()
+    };
+
+    let out0 = Proto::Output {
+        value: 50 * ONE_BTC - 2 * MINER_FEE,
+        to_recipient: output::p2pkh(alice_pubkey.compressed().to_vec()),
+    };
+
+    let builder = Proto::TransactionBuilder {
+        version: Proto::TransactionVersion::V2,
+        inputs: vec![tx0],
+        outputs: vec![out0],
+        input_selector: Proto::InputSelector::UseAll,
+        dust_policy: dust_threshold(DUST),
+        ..Default::default()
+    };
+
+    let input = Proto::SigningInput {
+        private_keys: vec![
+            CAROL_PRIVATE_KEY.decode_hex().unwrap().into(),
+            ALICE_PRIVATE_KEY.decode_hex().unwrap().into(),
+        ],
+        chain_info: btc_info(),
+        transaction: TransactionOneof::builder(builder),
+        ..Default::default()
+    };
+
+    sign::BitcoinSignHelper::new(&input)
+        .coin(CoinType::Bitcoin)
+        .sign(sign::Expected {
+            encoded: "020000000182bd495ffd6799180d1eb65a8e6d346519120d2f445bfab69f871a417ab7995d00000000fc004730440220682cd8214b603eaafdeb32255220707a24bfd41f3d5f88ec1d442dfa17234dc202200e36c60ce4501bb4c29e9c60925f70ee5a601ad331e13aa5df570537b42eb33e014730440220392fc7f4d30f20640faf8bfe2618d2ec7b4497bca19a5fe4e81712d1c1fc771c02205b020aa37a02432c28fa03531728ea317955129e907678afd2c292d3f902799a014c695221036666dd712e05a487916384bfcd5973eb53e8038eccbbf97f7eed775b8738953621037ed9a436e11ec4947ac4b7823787e24ba73180f1edd2857bff19c9f4d62b65bf2103b85cc59b67c35851eb5060cfc3a759a482254553c5857075c9e247d74d412c9153aeffffffff01806de729010000001976a914e4c1ea86373d554b8f4efff2cfb0001ea19124d288ac00000000",
+            txid: "53a7a3ca89ebee1ec42a99783279d5c90a925847de0eac2fa91f7530782d8f74",
+            inputs: vec![50 * ONE_BTC - MINER_FEE],
+            outputs: vec![50 * ONE_BTC - 2 * MINER_FEE],
+            vsize: 337,
+            weight: 1348,
+            fee: MINER_FEE,
+        });
+}
+
+#[test]
+fn test_bitcoin_sign_input_p2sh_multisig() {
+    let alice_pubkey = ecdsa::secp256k1::PrivateKey::try_from(ALICE_PRIVATE_KEY)
+        .unwrap()
+        .public();
+    let carol_pubkey = ecdsa::secp256k1::PrivateKey::try_from(CAROL_PRIVATE_KEY)
+        .unwrap()
+        .public();
+
+    test_bitcoin_sign_input_p2sh_multisig_impl(vec![
+        alice_pubkey.compressed().to_vec(),
+        carol_pubkey.compressed().to_vec(),
+    ]);
+    // Signatures must be ordered as the public keys in the redeem script regardless of the signers order.
+    test_bitcoin_sign_input_p2sh_multisig_impl(vec![
+        carol_pubkey.compressed().to_vec(),
+        alice_pubkey.compressed().to_vec(),
+    ]);
+}
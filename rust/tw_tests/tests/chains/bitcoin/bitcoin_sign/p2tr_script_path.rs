@@ -6,9 +6,48 @@ use tw_encoding::hex::DecodeHex;
 use tw_keypair::schnorr;
 use tw_misc::traits::ToBytesVec;
 use tw_proto::BitcoinV2::Proto;
+use tw_utxo::script::standard_script::{conditions, opcodes};
+use tw_utxo::script::Script;
+use tw_utxo::taproot::{TaprootLeaf, TaprootTree, TAPSCRIPT_LEAF_VERSION};
 use tw_utxo::transaction::asset::brc20::{BRC20TransferInscription, Brc20Ticker};
 
 const ALICE_PRIVATE_KEY: &str = "e253373989199da27c48680e3a3fc0f648d50f9a727ef17a7fe6a4dc3b159129";
+const BOB_PRIVATE_KEY: &str = "b7da1ec42b19085fe09fec54b9d9eacd998ae4e6d2ad472be38d8393391b9ead";
+const CAROL_PRIVATE_KEY: &str = "3c3385ddc6fd95ba7282051aeb440bc75820b8c10db5c83c052d7586e3e98e84";
+/// Relative timelock of the recovery leaf in blocks.
+const RECOVERY_DELAY: u32 = 144;
+
+fn schnorr_pubkey(private_key: &str) -> schnorr::PublicKey {
+    schnorr::PrivateKey::try_from(private_key).unwrap().public()
+}
+
+/// `<144> OP_CSV OP_DROP <alice> OP_CHECKSIG`
+fn recovery_leaf_script(alice_pubkey: &schnorr::PublicKey) -> Script {
+    let mut s = Script::new();
+    // 144 is pushed as a minimally-encoded script number.
+    s.push_slice(&[0x90, 0x00]);
+    s.push(opcodes::OP_CSV);
+    s.push(opcodes::OP_DROP);
+    s.push_slice(alice_pubkey.x_only().bytes().as_slice());
+    s.push(opcodes::OP_CHECKSIG);
+    s
+}
+
+/// 2-of-3 `OP_CHECKSIGADD` multisig of Alice, Bob and Carol.
+fn multisig_leaf_script() -> Script {
+    let pubkeys = [ALICE_PRIVATE_KEY, BOB_PRIVATE_KEY, CAROL_PRIVATE_KEY]
+        .map(|private_key| schnorr_pubkey(private_key).x_only().bytes());
+    conditions::new_tapscript_multisig(2, &pubkeys).unwrap()
+}
+
+/// `{multisig, recovery}` script tree with Alice's internal public key.
+fn script_tree_leaves() -> Vec<(Vec<u8>, u32)> {
+    let alice_pubkey = schnorr_pubkey(ALICE_PRIVATE_KEY);
+    vec![
+        (multisig_leaf_script().to_vec(), 1),
+        (recovery_leaf_script(&alice_pubkey).to_vec(), 1),
+    ]
+}
 
 /// A test for the custom P2TR script-path builders. This test essentially
 /// reconstruct the BRC20 transfer tests, but without using the convenience
@@ -78,73 +117,185 @@ fn test_bitcoin_sign_output_p2tr_custom_script_path() {
         });
 }
 
-// TODO uncomment when input P2TR script path
-// #[test]
-// fn test_bitcoin_sign_input_p2tr_custom_script_path() {
-//     // https://www.blockchain.com/explorer/transactions/btc/797d17d47ae66e598341f9dfdea020b04d4017dcf9cc33f0e51f7a6082171fb1
-//     let txid: Vec<u8> = hex("797d17d47ae66e598341f9dfdea020b04d4017dcf9cc33f0e51f7a6082171fb1")
-//         .into_iter()
-//         .rev()
-//         .collect();
-//
-//     // Prepare the BRC20 payload and control block.
-//     let payload = &transfer.script;
-//     let control_block = transfer
-//         .spend_info
-//         .control_block(&(payload.to_owned(), LeafVersion::TapScript))
-//         .unwrap();
-//
-//     // Provide the payload and control block directly to the builder.
-//     let tx1 = Proto::Input {
-//         txid: txid.as_slice().into(),
-//         vout: 0,
-//         value: 7_000,
-//         sighash_type: UtxoProto::SighashType::UseDefault,
-//         to_recipient: ProtoInputRecipient::builder(Proto::mod_Input::InputBuilder {
-//             variant: ProtoInputBuilder::p2tr_script_path(
-//                 Proto::mod_Input::InputTaprootScriptPath {
-//                     one_prevout: false,
-//                     payload: payload.to_vec().into(),
-//                     control_block: control_block.serialize().into(),
-//                 },
-//             ),
-//         }),
-//         ..Default::default()
-//     };
-//
-//     let out1 = Proto::Output {
-//         value: 546,
-//         to_recipient: ProtoOutputRecipient::builder(Proto::mod_Output::OutputBuilder {
-//             variant: ProtoOutputBuilder::p2wpkh(Proto::ToPublicKeyOrHash {
-//                 to_address: ProtoPubkeyOrHash::pubkey(alice_pubkey.as_slice().into()),
-//             }),
-//         }),
-//     };
-//
-//     let signing = Proto::SigningInput {
-//         private_key: alice_private_key.as_slice().into(),
-//         inputs: vec![tx1],
-//         outputs: vec![out1],
-//         input_selector: UtxoProto::InputSelector::UseAll,
-//         disable_change_output: true,
-//         // We enable deterministic Schnorr signatures here
-//         dangerous_use_fixed_schnorr_rng: true,
-//         ..Default::default()
-//     };
-//
-//     let signed = BitcoinEntry.sign(&coin, signing);
-//     assert_eq!(signed.error, Proto::Error::OK);
-//
-//     // https://www.blockchain.com/explorer/transactions/btc/7046dc2689a27e143ea2ad1039710885147e9485ab6453fa7e87464aa7dd3eca
-//     assert_eq!(
-//         signed.txid,
-//         hex("7046dc2689a27e143ea2ad1039710885147e9485ab6453fa7e87464aa7dd3eca")
-//     );
-//
-//     let encoded = tw_encoding::hex::encode(signed.encoded, false);
-//     let transaction = signed.transaction.unwrap();
-//
-//     assert_eq!(encoded, "02000000000101b11f1782607a1fe5f033ccf9dc17404db020a0dedff94183596ee67ad4177d790000000000ffffffff012202000000000000160014e311b8d6ddff856ce8e9a4e03bc6d4fe5050a83d03406a35548b8fa4620028e021a944c1d3dc6e947243a7bfc901bf63fefae0d2460efa149a6440cab51966aa4f09faef2d1e5efcba23ab4ca6e669da598022dbcfe35b0063036f7264010118746578742f706c61696e3b636861727365743d7574662d3800377b2270223a226272632d3230222c226f70223a227472616e73666572222c227469636b223a226f616466222c22616d74223a223230227d6821c00f209b6ada5edb42c77fd2bc64ad650ae38314c8f451f3e36d80bc8e26f132cb00000000");
-//     assert_eq!(transaction.inputs.len(), 1);
-//     assert_eq!(transaction.outputs.len(), 1);
-// }
+/// Spends the BRC20 commit output via the custom P2TR script-path builder.
+/// This test essentially reconstructs the BRC20 reveal transaction, but without using the convenience builders.
+#[test]
+fn test_bitcoin_sign_input_p2tr_custom_script_path() {
+    let alice_pubkey = schnorr_pubkey(ALICE_PRIVATE_KEY);
+
+    let ticker = Brc20Ticker::new("oadf".to_string()).unwrap();
+    let transfer =
+        BRC20TransferInscription::new(&alice_pubkey.compressed(), &ticker, "20").unwrap();
+    let payload = Script::from(transfer.script.to_bytes());
+
+    // Prepare the control block outside the library.
+    let leaf = TaprootLeaf {
+        script: payload.clone(),
+        version: TAPSCRIPT_LEAF_VERSION,
+        depth: 0,
+    };
+    let control_block = TaprootTree::new(&alice_pubkey, &[leaf])
+        .unwrap()
+        .control_block(&payload, TAPSCRIPT_LEAF_VERSION)
+        .unwrap();
+
+    let claiming_scripts = [
+        // Provide the payload and control block directly to the builder.
+        input::p2tr_script_path(
+            payload.to_vec(),
+            control_block.serialize(),
+            vec![alice_pubkey.to_vec()],
+        ),
+        // Or let the builder construct the control block from the script tree.
+        input::p2tr_script_tree(
+            payload.to_vec(),
+            alice_pubkey.to_vec(),
+            vec![(payload.to_vec(), 0)],
+            vec![alice_pubkey.to_vec()],
+        ),
+    ];
+
+    for claiming_script in claiming_scripts {
+        let txid = "797d17d47ae66e598341f9dfdea020b04d4017dcf9cc33f0e51f7a6082171fb1";
+        let tx1 = Proto::Input {
+            out_point: input::out_point(txid, 0),
+            value: 7_000,
+            sighash_type: SIGHASH_ALL,
+            claiming_script,
+            ..Default::default()
+        };
+
+        let out1 = Proto::Output {
+            value: DUST,
+            to_recipient: output::p2wpkh(alice_pubkey.to_vec()),
+        };
+
+        let builder = Proto::TransactionBuilder {
+            version: Proto::TransactionVersion::V2,
+            inputs: vec![tx1],
+            outputs: vec![out1],
+            input_selector: Proto::InputSelector::UseAll,
+            dust_policy: dust_threshold(DUST),
+            ..Default::default()
+        };
+
+        let signing = Proto::SigningInput {
+            private_keys: vec![ALICE_PRIVATE_KEY.decode_hex().unwrap().into()],
+            chain_info: btc_info(),
+            // We enable deterministic Schnorr signatures here
+            dangerous_use_fixed_schnorr_rng: true,
+            transaction: TransactionOneof::builder(builder),
+            ..Default::default()
+        };
+
+        // Self-generated vector: it reuses the BRC-20 reveal inputs, but the witness is not the one broadcasted on mainnet.
+        sign::BitcoinSignHelper::new(&signing)
+            .coin(CoinType::Bitcoin)
+            .sign(sign::Expected {
+                encoded: "02000000000101b11f1782607a1fe5f033ccf9dc17404db020a0dedff94183596ee67ad4177d790000000000ffffffff012202000000000000160014e311b8d6ddff856ce8e9a4e03bc6d4fe5050a83d0340694b812b91c4143c759d717109283fda9a195629d3a742a8132165cad13232fd2ef50c050198f404fbc56ae6923c111c417c8bf53af8fca9f5b076c8e0ec39795b0063036f7264010118746578742f706c61696e3b636861727365743d7574662d3800377b2270223a226272632d3230222c226f70223a227472616e73666572222c227469636b223a226f616466222c22616d74223a223230227d6821c00f209b6ada5edb42c77fd2bc64ad650ae38314c8f451f3e36d80bc8e26f132cb00000000",
+                txid: "7046dc2689a27e143ea2ad1039710885147e9485ab6453fa7e87464aa7dd3eca",
+                inputs: vec![7_000],
+                outputs: vec![DUST],
+                vsize: 131,
+                weight: 522,
+                fee: 7_000 - DUST,
+            });
+    }
+}
+
+fn script_tree_signing_input(
+    payload: Script,
+    signers: Vec<Vec<u8>>,
+    sequence: u32,
+) -> Proto::SigningInput<'static> {
+    let alice_pubkey = schnorr_pubkey(ALICE_PRIVATE_KEY);
+
+    let txid = "1e1f1bcaa2ee0fc2e5cda6d4f7fba6d4ca5a1d38bbd2ae8e7e8a7cb3a9e1fc53";
+    let tx1 = Proto::Input {
+        out_point: input::out_point(txid, 0),
+        value: 10_000,
+        sighash_type: SIGHASH_ALL,
+        sequence: input::sequence(sequence),
+        claiming_script: input::p2tr_script_tree(
+            payload.to_vec(),
+            alice_pubkey.to_vec(),
+            script_tree_leaves(),
+            signers,
+        ),
+    };
+
+    let out1 = Proto::Output {
+        value: 9_000,
+        to_recipient: output::p2wpkh(alice_pubkey.to_vec()),
+    };
+
+    let builder = Proto::TransactionBuilder {
+        version: Proto::TransactionVersion::V2,
+        inputs: vec![tx1],
+        outputs: vec![out1],
+        input_selector: Proto::InputSelector::UseAll,
+        dust_policy: dust_threshold(DUST),
+        ..Default::default()
+    };
+
+    Proto::SigningInput {
+        private_keys: [ALICE_PRIVATE_KEY, BOB_PRIVATE_KEY, CAROL_PRIVATE_KEY]
+            .iter()
+            .map(|private_key| private_key.decode_hex().unwrap().into())
+            .collect(),
+        chain_info: btc_info(),
+        // We enable deterministic Schnorr signatures here
+        dangerous_use_fixed_schnorr_rng: true,
+        transaction: TransactionOneof::builder(builder),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_bitcoin_sign_input_p2tr_script_path_multisig() {
+    let bob_pubkey = schnorr_pubkey(BOB_PRIVATE_KEY);
+    let carol_pubkey = schnorr_pubkey(CAROL_PRIVATE_KEY);
+
+    // Signers can be given in any order.
+    let signing = script_tree_signing_input(
+        multisig_leaf_script(),
+        vec![carol_pubkey.to_vec(), bob_pubkey.to_vec()],
+        u32::MAX,
+    );
+
+    // The witness contains Carol's and Bob's signatures, and an empty item for Alice.
+    sign::BitcoinSignHelper::new(&signing)
+        .coin(CoinType::Bitcoin)
+        .sign(sign::Expected {
+            encoded: "0200000000010153fce1a9b37c8a7e8eaed2bb381d5acad4a6fbf7d4a6cde5c20feea2ca1b1f1e0000000000ffffffff012823000000000000160014e311b8d6ddff856ce8e9a4e03bc6d4fe5050a83d05408fe85e0beaf0d6f6df58c3e4761008a078361449f112656c7bdd272dd9fac9de2e796cc3b66c2116993f2f6f0b090e53b4169e7d24a81f03fd1062169b64263b4003beb151238e9c7fc25435f3eb2510c216df29be14b91794dca8060467a84cc325dd79ffa2c78fa55092c3263e398cb95ca6e5316481af8fe4a7c25cc80e07a20068200f209b6ada5edb42c77fd2bc64ad650ae38314c8f451f3e36d80bc8e26f132cbac207ed9a436e11ec4947ac4b7823787e24ba73180f1edd2857bff19c9f4d62b65bfba20b85cc59b67c35851eb5060cfc3a759a482254553c5857075c9e247d74d412c91ba529c41c00f209b6ada5edb42c77fd2bc64ad650ae38314c8f451f3e36d80bc8e26f132cb694c31a4b6f1c12af306e11fe061502150fb8313fe60f63456ab0fd292f4fdab00000000",
+            txid: "96e7071c88f423208bbc3fa17537764a9c5361a4902763a7a4abe3fc0f47e4c0",
+            inputs: vec![10_000],
+            outputs: vec![9_000],
+            vsize: 159,
+            weight: 633,
+            fee: 1_000,
+        });
+}
+
+#[test]
+fn test_bitcoin_sign_input_p2tr_script_path_timelock() {
+    let alice_pubkey = schnorr_pubkey(ALICE_PRIVATE_KEY);
+
+    let signing = script_tree_signing_input(
+        recovery_leaf_script(&alice_pubkey),
+        vec![alice_pubkey.to_vec()],
+        RECOVERY_DELAY,
+    );
+
+    sign::BitcoinSignHelper::new(&signing)
+        .coin(CoinType::Bitcoin)
+        .sign(sign::Expected {
+            encoded: "0200000000010153fce1a9b37c8a7e8eaed2bb381d5acad4a6fbf7d4a6cde5c20feea2ca1b1f1e000000000090000000012823000000000000160014e311b8d6ddff856ce8e9a4e03bc6d4fe5050a83d03400b3ea63846138345247eaac442f6bc7095095c41cca5bf673695ec299c48e91a1d51cc1276ec4b00aac263a05263c407d9b927ec85739099ec871e2f2e660f6e27029000b275200f209b6ada5edb42c77fd2bc64ad650ae38314c8f451f3e36d80bc8e26f132cbac41c00f209b6ada5edb42c77fd2bc64ad650ae38314c8f451f3e36d80bc8e26f132cb51202577acc83ce4aac18f2185c02d88c5abf4edd7db54c5c892024bc1aa7cf300000000",
+            txid: "cd46ca2ca200983bd67a132d03aef9dec78e30e0127c7550d0d6b10649e276ed",
+            inputs: vec![10_000],
+            outputs: vec![9_000],
+            vsize: 126,
+            weight: 502,
+            fee: 1_000,
+        });
+}
@@ -16,6 +16,7 @@ use tw_utxo::script::Script;
 
 const ALICE_PRIVATE_KEY: &str = "56429688a1a6b00b90ccd22a0de0a376b6569d8684022ae92229a28478bfb657";
 const BOB_PRIVATE_KEY: &str = "b7da1ec42b19085fe09fec54b9d9eacd998ae4e6d2ad472be38d8393391b9ead";
+const CAROL_PRIVATE_KEY: &str = "3c3385ddc6fd95ba7282051aeb440bc75820b8c10db5c83c052d7586e3e98e84";
 
 fn p2pkh_redeem_script(public_key: &ecdsa::secp256k1::PublicKey) -> Script {
     let pubkey_hash = sha256_ripemd(public_key.compressed().as_slice());
@@ -111,72 +112,134 @@ fn test_bitcoin_sign_output_p2wsh_address() {
     test_bitcoin_sign_output_p2wsh(P2WSHRecipientType::P2WSHAddress);
 }
 
-// TODO uncomment when P2WSH is ready
-// #[test]
-// fn test_bitcoin_sign_input_p2wsh() {
-//     // Create transaction with P2WSH as input (claim).
-//     let txid: Vec<u8> = hex("dd9d4ca23532f5c89d016e1aacef1210ab5b9d00527c633969841daca7dd17c7")
-//         .into_iter()
-//         .rev()
-//         .collect();
-//
-//     let tx1 = Proto::Input {
-//         txid: txid.as_slice().into(),
-//         vout: 0,
-//         value: 50 * ONE_BTC - 3 * MINER_FEE,
-//         sighash_type: UtxoProto::SighashType::All,
-//         to_recipient: ProtoInputRecipient::builder(Proto::mod_Input::InputBuilder {
-//             // The way P2WSH is signed in Bitcoin, we first place the redeem script directly here.
-//             variant: ProtoInputBuilder::p2wsh(redeem_script.to_bytes().into()),
-//         }),
-//         ..Default::default()
-//     };
-//
-//     let out1 = Proto::Output {
-//         value: 50 * ONE_BTC - 4 * MINER_FEE,
-//         to_recipient: ProtoOutputRecipient::builder(Proto::mod_Output::OutputBuilder {
-//             variant: ProtoOutputBuilder::p2pkh(Proto::ToPublicKeyOrHash {
-//                 to_address: ProtoPubkeyOrHash::pubkey(alice_pubkey.as_slice().into()),
-//             }),
-//         }),
-//     };
-//
-//     let mut signing = Proto::SigningInput {
-//         inputs: vec![tx1],
-//         outputs: vec![out1],
-//         input_selector: UtxoProto::InputSelector::UseAll,
-//         disable_change_output: true,
-//         ..Default::default()
-//     };
-//
-//     // Generate the sighashes.
-//     let sighashes = BitcoinEntry.preimage_hashes(&coin, signing.clone());
-//     let sighash = sighashes.sighashes.iter().next().unwrap().sighash.as_ref();
-//     let sighash = H256::try_from(sighash).unwrap();
-//
-//     // Sign the sighash externally.
-//     let private_key = ecdsa::secp256k1::PrivateKey::try_from(bob_private_key.as_slice()).unwrap();
-//     let signature = private_key.sign(sighash).unwrap();
-//
-//     // Construct the final redeem witness stack with the necessary witness stack items (signature + pubkey).
-//     let mut finalized = Witness::new();
-//     finalized.push(sig);
-//     finalized.push(bob_native_pubkey.to_bytes());
-//     finalized.push(redeem_script);
-//
-//     // The witness stack must be encoded correctly.
-//     let mut encoded = vec![];
-//     let _ = finalized.consensus_encode(&mut encoded).unwrap();
-//
-//     // Now that we've signed the input, we update the input with the complete,
-//     // finalized redeem witness stack..
-//     signing.inputs[0].to_recipient = ProtoInputRecipient::builder(Proto::mod_Input::InputBuilder {
-//         variant: ProtoInputBuilder::p2wsh(encoded.into()),
-//     });
-//
-//     // Compile the final transaction.
-//     let signed = BitcoinEntry.compile(&coin, signing, signatures, vec![]);
-//     let encoded = tw_encoding::hex::encode(signed.encoded, false);
-//     assert_eq!(signed.error, Proto::Error::OK);
-//     assert_eq!(&encoded, "02000000000101c717dda7ac1d846939637c52009d5bab1012efac1a6e019dc8f53235a24c9ddd0000000000ffffffff0100e9c829010000001976a914e4c1ea86373d554b8f4efff2cfb0001ea19124d288ac0347304402201d22810b5580a49a2e73d7c4ea90754b5d70d36adb9a8f0c9cb7393da1d1d28f02207683b2e3d31a5c7e74126681f1f2a7249b7a3a918d5890ef69b94bd3bb4fb9300121037ed9a436e11ec4947ac4b7823787e24ba73180f1edd2857bff19c9f4d62b65bf1976a9145eaaa4f458f9158f86afcba08dd7448d27045e3d88ac00000000");
-// }
+#[test]
+fn test_bitcoin_sign_input_p2wsh_p2pkh_output_p2pkh() {
+    let alice_private_key = ecdsa::secp256k1::PrivateKey::try_from(ALICE_PRIVATE_KEY).unwrap();
+    let alice_pubkey = alice_private_key.public();
+
+    let bob_private_key = ecdsa::secp256k1::PrivateKey::try_from(BOB_PRIVATE_KEY).unwrap();
+    let bob_pubkey = bob_private_key.public();
+
+    // Spend the P2WSH output created at `test_bitcoin_sign_output_p2wsh`.
+    let redeem_script = p2pkh_redeem_script(&bob_pubkey);
+
+    let txid = "dd9d4ca23532f5c89d016e1aacef1210ab5b9d00527c633969841daca7dd17c7";
+    let tx1 = Proto::Input {
+        out_point: input::out_point(txid, 0),
+        value: 50 * ONE_BTC - 3 * MINER_FEE,
+        sighash_type: SIGHASH_ALL,
+        claiming_script: input::p2wsh(
+            redeem_script.to_vec(),
+            vec![bob_pubkey.compressed().to_vec()],
+        ),
+        ..Default::default()
+    };
+
+    let out1 = Proto::Output {
+        value: 50 * ONE_BTC - 4 * MINER_FEE,
+        to_recipient: output::p2pkh(alice_pubkey.compressed().to_vec()),
+    };
+
+    let builder = Proto::TransactionBuilder {
+        version: Proto::TransactionVersion::V2,
+        inputs: vec![tx1],
+        outputs: vec![out1],
+        input_selector: Proto::InputSelector::UseAll,
+        dust_policy: dust_threshold(DUST),
+        ..Default::default()
+    };
+
+    let signing = Proto::SigningInput {
+        private_keys: vec![BOB_PRIVATE_KEY.decode_hex().unwrap().into()],
+        chain_info: btc_info(),
+        transaction: TransactionOneof::builder(builder),
+        ..Default::default()
+    };
+
+    sign::BitcoinSignHelper::new(&signing)
+        .coin(CoinType::Bitcoin)
+        .sign(sign::Expected {
+            encoded: "02000000000101c717dda7ac1d846939637c52009d5bab1012efac1a6e019dc8f53235a24c9ddd0000000000ffffffff0100e9c829010000001976a914e4c1ea86373d554b8f4efff2cfb0001ea19124d288ac0347304402201d22810b5580a49a2e73d7c4ea90754b5d70d36adb9a8f0c9cb7393da1d1d28f02207683b2e3d31a5c7e74126681f1f2a7249b7a3a918d5890ef69b94bd3bb4fb9300121037ed9a436e11ec4947ac4b7823787e24ba73180f1edd2857bff19c9f4d62b65bf1976a9145eaaa4f458f9158f86afcba08dd7448d27045e3d88ac00000000",
+            txid: "64a05a69415b2d49c601ee42293207bebb7c5a0c93c2b5fb1527aa5ba9616e76",
+            inputs: vec![50 * ONE_BTC - 3 * MINER_FEE],
+            outputs: vec![50 * ONE_BTC - 4 * MINER_FEE],
+            vsize: 119,
+            weight: 475,
+            fee: MINER_FEE,
+        });
+}
+
+#[test]
+fn test_bitcoin_sign_input_p2wsh_multisig() {
+    let alice_private_key = ecdsa::secp256k1::PrivateKey::try_from(ALICE_PRIVATE_KEY).unwrap();
+    let alice_pubkey = alice_private_key.public();
+    let bob_pubkey = ecdsa::secp256k1::PrivateKey::try_from(BOB_PRIVATE_KEY)
+        .unwrap()
+        .public();
+    let carol_pubkey = ecdsa::secp256k1::PrivateKey::try_from(CAROL_PRIVATE_KEY)
+        .unwrap()
+        .public();
+
+    // 2-of-3 multisig witness script.
+    let witness_script = conditions::new_multisig(
+        2,
+        &[
+            alice_pubkey.compressed(),
+            bob_pubkey.compressed(),
+            carol_pubkey.compressed(),
+        ],
+    )
+    .unwrap();
+
+    let txid = "dd9d4ca23532f5c89d016e1aacef1210ab5b9d00527c633969841daca7dd17c7";
+    let tx1 = Proto::Input {
+        out_point: input::out_point(txid, 0),
+        value: 50 * ONE_BTC - 3 * MINER_FEE,
+        sighash_type: SIGHASH_ALL,
+        // Signers order doesn't matter.
+        claiming_script: input::p2wsh(
+            witness_script.to_vec(),
+            vec![
+                carol_pubkey.compressed().to_vec(),
+                bob_pubkey.compressed().to_vec(),
+            ],
+        ),
+        ..Default::default()
+    };
+
+    let out1 = Proto::Output {
+        value: 50 * ONE_BTC - 4 * MINER_FEE,
+        to_recipient: output::p2pkh(alice_pubkey.compressed().to_vec()),
+    };
+
+    let builder = Proto::TransactionBuilder {
+        version: Proto::TransactionVersion::V2,
+        inputs: vec![tx1],
+        outputs: vec![out1],
+        input_selector: Proto::InputSelector::UseAll,
+        dust_policy: dust_threshold(DUST),
+        ..Default::default()
+    };
+
+    let signing = Proto::SigningInput {
+        private_keys: vec![
+            BOB_PRIVATE_KEY.decode_hex().unwrap().into(),
+            CAROL_PRIVATE_KEY.decode_hex().unwrap().into(),
+        ],
+        chain_info: btc_info(),
+        transaction: TransactionOneof::builder(builder),
+        ..Default::default()
+    };
+
+    sign::BitcoinSignHelper::new(&signing)
+        .coin(CoinType::Bitcoin)
+        .sign(sign::Expected {
+            encoded: "02000000000101c717dda7ac1d846939637c52009d5bab1012efac1a6e019dc8f53235a24c9ddd0000000000ffffffff0100e9c829010000001976a914e4c1ea86373d554b8f4efff2cfb0001ea19124d288ac0400473044022004caeafd1be3e70693a05af5fb4a6c2b4f9d3620cb8c6ed0c711d5acb71eb66302207f27deb6815f03887e5edb865526a08b0061c8d7bdfcfb97c953382fc6aeeef20147304402205a16419663116db808e0dfd8ee194ee99929bc19ea217e6baf6c1305f341d948022052470c7003a82d6936ca6bf586db8b76756c4c85767d8672387945533f848c4001695221036666dd712e05a487916384bfcd5973eb53e8038eccbbf97f7eed775b8738953621037ed9a436e11ec4947ac4b7823787e24ba73180f1edd2857bff19c9f4d62b65bf2103b85cc59b67c35851eb5060cfc3a759a482254553c5857075c9e247d74d412c9153ae00000000",
+            txid: "64a05a69415b2d49c601ee42293207bebb7c5a0c93c2b5fb1527aa5ba9616e76",
+            inputs: vec![50 * ONE_BTC - 3 * MINER_FEE],
+            outputs: vec![50 * ONE_BTC - 4 * MINER_FEE],
+            vsize: 149,
+            weight: 594,
+            fee: MINER_FEE,
+        });
+}
@@ -0,0 +1,179 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::chains::common::bitcoin::output;
+use tw_any_coin::test_utils::transaction_decode_utils::TransactionDecoderHelper;
+use tw_coin_registry::coin_type::CoinType;
+use tw_encoding::hex::{DecodeHex, ToHex};
+use tw_proto::BitcoinV2::Proto;
+use tw_proto::BitcoinV2::Proto::mod_Output::OneOfto_recipient as RecipientType;
+use tw_proto::Common::Proto::SigningError;
+
+/// https://mempool.space/tx/2b871b6c1112ad0a777f6db1f7a7709154c4d9af8e771ba4eca148915f830e9d
+const ZETACHAIN_DEPOSIT_TX: &str = "01000000000101200e3119012b1ccff35c011523050038bbdd1ca38aa56774c8331edbb5ada6170000000000ffffffff031027000000000000160014daaae0d3de9d8fdee31661e61aea828b59be78640000000000000000166a14a8491d40d4f71a752ca41da0516aed80c33a1b56fc1c000000000000160014540371330ae036602f2a715adaa044ac0856312c02483045022100e29731f7474f9103c6df3434c8c62a540a21ad0e10e23df343b1e81e4b26110602202d37fb4fee5341a41f9e4e65ba2d3e0d2309425ea9806d94eb268efe6f21007001210369cdaf80b4a5fdad91e9face90e848225512884ec2e3ed572ca11dc68e75054700000000";
+
+#[test]
+fn test_bitcoin_decode_transaction_segwit_op_return() {
+    let mut decoder = TransactionDecoderHelper::<Proto::DecodingTransactionOutput>::default();
+    let output = decoder.decode(
+        CoinType::Bitcoin,
+        ZETACHAIN_DEPOSIT_TX.decode_hex().unwrap(),
+    );
+
+    assert_eq!(output.error, SigningError::OK);
+    assert_eq!(
+        output.txid.to_hex(),
+        "2b871b6c1112ad0a777f6db1f7a7709154c4d9af8e771ba4eca148915f830e9d"
+    );
+    assert_eq!(output.vsize, 172);
+    assert_eq!(output.weight, 686);
+
+    let tx = output.transaction.unwrap();
+    assert_eq!(tx.version, 1);
+    assert_eq!(tx.lock_time, 0);
+    assert_eq!(tx.inputs.len(), 1);
+    assert_eq!(tx.inputs[0].witness_items.len(), 2);
+    assert!(tx.inputs[0].script_sig.is_empty());
+    assert_eq!(tx.outputs.len(), 3);
+
+    let expected_outputs = vec![
+        Proto::Output {
+            value: 10_000,
+            to_recipient: output::to_address("bc1qm24wp577nk8aacckv8np465z3dvmu7ry45el6y"),
+        },
+        Proto::Output {
+            value: 0,
+            to_recipient: output::op_return(
+                "a8491D40d4F71A752cA41DA0516AEd80c33a1B56"
+                    .decode_hex()
+                    .unwrap(),
+            ),
+        },
+        Proto::Output {
+            value: 7_420,
+            to_recipient: output::to_address("bc1q2sphzvc2uqmxqte2w9dd4gzy4sy9vvfv0me9ke"),
+        },
+    ];
+    assert_eq!(output.outputs, expected_outputs);
+}
+
+#[test]
+fn test_bitcoin_decode_transaction_unsigned() {
+    // The same transaction as [`ZETACHAIN_DEPOSIT_TX`], but without witness.
+    let unsigned_tx = "01000000" // version
+        .to_string()
+        + "01200e3119012b1ccff35c011523050038bbdd1ca38aa56774c8331edbb5ada6170000000000ffffffff" // inputs
+        + "031027000000000000160014daaae0d3de9d8fdee31661e61aea828b59be78640000000000000000166a14a8491d40d4f71a752ca41da0516aed80c33a1b56fc1c000000000000160014540371330ae036602f2a715adaa044ac0856312c" // outputs
+        + "00000000"; // locktime
+
+    let mut decoder = TransactionDecoderHelper::<Proto::DecodingTransactionOutput>::default();
+    let output = decoder.decode(
+        CoinType::Bitcoin,
+        unsigned_tx.as_str().decode_hex().unwrap(),
+    );
+
+    assert_eq!(output.error, SigningError::OK);
+    // Witness doesn't affect the transaction ID.
+    assert_eq!(
+        output.txid.to_hex(),
+        "2b871b6c1112ad0a777f6db1f7a7709154c4d9af8e771ba4eca148915f830e9d"
+    );
+    let tx = output.transaction.unwrap();
+    assert!(tx.inputs[0].witness_items.is_empty());
+    assert_eq!(output.outputs.len(), 3);
+}
+
+#[test]
+fn test_bitcoin_decode_transaction_p2tr_output() {
+    let encoded = "02000000000101821a37e92b51467733034ec48121eece8411f07e35d2654092ad2baef078ed750100000000ffffffff02b80b0000000000001600145f41cc5fcd2bcd1f0079d27d4153bafbffc15df83212000000000000225120412a773e0bba5cfb5462d024cd4bf2cce1b8688a9e7a7a3f8507ebba8f00de580140cbe4d13bc9e067b042179e2c217e4e4b1d552119d12839aa4df11c21282f9159e2c4b58a4f22b291c200c0d0c5f277902282bdd78589dff0edbea89d3f00d77400000000";
+
+    let mut decoder = TransactionDecoderHelper::<Proto::DecodingTransactionOutput>::default();
+    let output = decoder.decode(CoinType::Bitcoin, encoded.decode_hex().unwrap());
+
+    assert_eq!(output.error, SigningError::OK);
+    assert_eq!(
+        output.txid.to_hex(),
+        "a9c63dfe54f6ff462155d966a54226c456b3e43b52a9abe55d7fa87d6564c6e4"
+    );
+    assert_eq!(output.vsize, 142);
+    assert_eq!(output.weight, 568);
+
+    let expected_outputs = vec![
+        Proto::Output {
+            value: 3_000,
+            to_recipient: output::to_address("bc1qtaquch7d90x37qre6f75z5a6l0luzh0c03epyz"),
+        },
+        Proto::Output {
+            value: 4_658,
+            to_recipient: output::to_address(
+                "bc1pgy48w0sthfw0k4rz6qjv6jljensms6y2nea850u9ql4m4rcqmevqp3w344",
+            ),
+        },
+    ];
+    assert_eq!(output.outputs, expected_outputs);
+}
+
+#[test]
+fn test_bitcoin_decode_transaction_legacy_p2sh_output() {
+    let encoded = "0200000001f7a2070ccbb5568a46e3fa7dfcdbca8bbd5e6eb5da09b0b347058d26213750e7000000006a473044022007c88caf624c0a130fc79d2835ed5b6db49f2dea0d5e685f06138aaa4a904d690220243fe7744c8b48759e74a87075de3f548988252a770871fc1444652bb32ec46e0121036666dd712e05a487916384bfcd5973eb53e8038eccbbf97f7eed775b87389536ffffffff01c0aff6290100000017a914a519b524d55ae8972e8e0e6b9d645ab20eb2635e8700000000";
+
+    let mut decoder = TransactionDecoderHelper::<Proto::DecodingTransactionOutput>::default();
+    let output = decoder.decode(CoinType::Bitcoin, encoded.decode_hex().unwrap());
+
+    assert_eq!(output.error, SigningError::OK);
+    assert_eq!(
+        output.txid.to_hex(),
+        "5d99b77a411a879fb6fa5b442f0d121965346d8e5ab61e0d189967fd5f49bd82"
+    );
+    // For non-segwit transactions, `vsize` = `size`.
+    assert_eq!(output.vsize as usize, encoded.len() / 2);
+
+    let tx = output.transaction.unwrap();
+    assert_eq!(tx.version, 2);
+    assert!(!tx.inputs[0].script_sig.is_empty());
+    assert!(tx.inputs[0].witness_items.is_empty());
+
+    let expected_outputs = vec![Proto::Output {
+        value: 4_999_000_000,
+        to_recipient: output::to_address("3GjzDPcTmMW2Gr74F5A4Gd6UKq5Xi3ZFqR"),
+    }];
+    assert_eq!(output.outputs, expected_outputs);
+}
+
+#[test]
+fn test_bitcoin_decode_transaction_custom_script_pubkey() {
+    // Legacy transaction with a bare 1-of-1 multisig output that cannot be represented as an address.
+    let script_pubkey =
+        "5121036666dd712e05a487916384bfcd5973eb53e8038eccbbf97f7eed775b8738953651ae";
+    let encoded = "0200000001f7a2070ccbb5568a46e3fa7dfcdbca8bbd5e6eb5da09b0b347058d26213750e70000000000ffffffff01e803000000000000".to_string()
+        + "25"
+        + script_pubkey
+        + "00000000";
+
+    let mut decoder = TransactionDecoderHelper::<Proto::DecodingTransactionOutput>::default();
+    let output = decoder.decode(CoinType::Bitcoin, encoded.as_str().decode_hex().unwrap());
+
+    assert_eq!(output.error, SigningError::OK);
+    let expected_outputs = vec![Proto::Output {
+        value: 1_000,
+        to_recipient: RecipientType::custom_script_pubkey(
+            script_pubkey.decode_hex().unwrap().into(),
+        ),
+    }];
+    assert_eq!(output.outputs, expected_outputs);
+}
+
+#[test]
+fn test_bitcoin_decode_transaction_error() {
+    let mut encoded = ZETACHAIN_DEPOSIT_TX.decode_hex().unwrap();
+    // Truncate the locktime.
+    encoded.pop();
+
+    let mut decoder = TransactionDecoderHelper::<Proto::DecodingTransactionOutput>::default();
+    let output = decoder.decode(CoinType::Bitcoin, encoded);
+
+    assert_eq!(output.error, SigningError::Error_input_parse);
+    assert!(output.transaction.is_none());
+    assert!(output.outputs.is_empty());
+}
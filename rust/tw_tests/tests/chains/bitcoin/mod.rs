@@ -4,6 +4,10 @@
 
 mod bitcoin_address;
 mod bitcoin_compile;
+mod bitcoin_fee_bumping;
+mod bitcoin_message;
 mod bitcoin_plan;
+mod bitcoin_psbt;
 mod bitcoin_sign;
+mod bitcoin_transaction_decode;
 mod bitcoin_transaction_util;
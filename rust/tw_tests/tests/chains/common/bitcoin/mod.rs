@@ -55,6 +55,12 @@ pub fn dust_threshold(threshold: i64) -> Proto::mod_TransactionBuilder::OneOfdus
     Proto::mod_TransactionBuilder::OneOfdust_policy::fixed_dust_threshold(threshold)
 }
 
+pub fn dynamic_dust(min_relay_fee_per_kvb: i64) -> Proto::mod_TransactionBuilder::OneOfdust_policy {
+    Proto::mod_TransactionBuilder::OneOfdust_policy::dynamic_dust_min_relay_fee_per_kvb(
+        min_relay_fee_per_kvb,
+    )
+}
+
 pub fn transaction_psbt(hex: &str) -> TransactionOneof {
     TransactionOneof::psbt(Proto::Psbt {
         psbt: hex.decode_hex().unwrap().into(),
@@ -101,6 +107,27 @@ pub mod input {
         }))
     }
 
+    pub fn p2sh(redeem_script: Data, signers: Vec<Data>) -> ClaimingScriptType<'static> {
+        claiming_script_builder(InputBuilderType::p2sh(script_hash(redeem_script, signers)))
+    }
+
+    pub fn p2wsh(witness_script: Data, signers: Vec<Data>) -> ClaimingScriptType<'static> {
+        claiming_script_builder(InputBuilderType::p2wsh(script_hash(
+            witness_script,
+            signers,
+        )))
+    }
+
+    fn script_hash(
+        redeem_script: Data,
+        signers: Vec<Data>,
+    ) -> Proto::mod_Input::InputScriptHash<'static> {
+        Proto::mod_Input::InputScriptHash {
+            redeem_script: redeem_script.into(),
+            signers: signers.into_iter().map(Into::into).collect(),
+        }
+    }
+
     pub fn p2wpkh(pubkey: Data) -> ClaimingScriptType<'static> {
         claiming_script_builder(InputBuilderType::p2wpkh(Proto::PublicKeyOrHash {
             variant: PublicKeyOrHashType::pubkey(pubkey.into()),
@@ -111,6 +138,48 @@ pub mod input {
         claiming_script_builder(InputBuilderType::p2tr_key_path(pubkey.into()))
     }
 
+    pub fn p2tr_script_path(
+        payload: Data,
+        control_block: Data,
+        signers: Vec<Data>,
+    ) -> ClaimingScriptType<'static> {
+        claiming_script_builder(InputBuilderType::p2tr_script_path(
+            Proto::mod_Input::InputTaprootScriptPath {
+                payload: payload.into(),
+                control_block: control_block.into(),
+                signers: signers.into_iter().map(Into::into).collect(),
+                ..Default::default()
+            },
+        ))
+    }
+
+    /// `leaves` are `(leaf_script, depth)` pairs in depth-first order.
+    pub fn p2tr_script_tree(
+        payload: Data,
+        internal_pubkey: Data,
+        leaves: Vec<(Data, u32)>,
+        signers: Vec<Data>,
+    ) -> ClaimingScriptType<'static> {
+        let leaves = leaves
+            .into_iter()
+            .map(|(script, depth)| Proto::mod_Input::TaprootLeaf {
+                script: script.into(),
+                depth,
+                ..Default::default()
+            })
+            .collect();
+
+        claiming_script_builder(InputBuilderType::p2tr_script_path(
+            Proto::mod_Input::InputTaprootScriptPath {
+                payload: payload.into(),
+                internal_public_key: internal_pubkey.into(),
+                leaves,
+                signers: signers.into_iter().map(Into::into).collect(),
+                ..Default::default()
+            },
+        ))
+    }
+
     pub fn brc20_inscribe(
         inscribe_to: Data,
         ticker: &str,
@@ -77,10 +77,10 @@ impl<'a> BitcoinSignHelper<'a> {
 
     /// Gets the map of `{ OutPoint -> Amount }`.
     fn utxo_map(&self) -> UtxoMap {
-        let builder_input = self.transaction_builder();
+        let utxos = self.utxos();
 
-        let mut utxo_map = HashMap::with_capacity(builder_input.inputs.len());
-        for utxo in builder_input.inputs.iter() {
+        let mut utxo_map = HashMap::with_capacity(utxos.len());
+        for utxo in utxos.iter() {
             utxo_map.insert(OutPoint::from_proto(&utxo.out_point), utxo.value);
         }
         utxo_map
@@ -102,12 +102,16 @@ impl<'a> BitcoinSignHelper<'a> {
         output_inputs
     }
 
-    fn transaction_builder(&self) -> &Proto::TransactionBuilder<'_> {
+    fn utxos(&self) -> &[Proto::Input<'_>] {
         match self.input.transaction {
-            TransactionOneof::builder(ref builder) => builder,
+            TransactionOneof::builder(ref builder) => &builder.inputs,
+            TransactionOneof::replace_by_fee(ref rbf) => &rbf.inputs,
             TransactionOneof::psbt(_) => panic!(
                 "`BitcoinSignHelper` doesn't support PSBT. Consider using `BitcoinPsbtSignHelper`"
             ),
+            TransactionOneof::child_pays_for_parent(_) => panic!(
+                "`BitcoinSignHelper` doesn't support CPFP as the parent UTXO is not known in advance"
+            ),
             TransactionOneof::None => unreachable!(),
         }
     }
@@ -10,9 +10,10 @@ use tw_number::U256;
 use tw_proto::EthereumAbi::{Proto as AbiProto, Proto};
 use tw_proto::{deserialize, serialize};
 use wallet_core_rs::ffi::ethereum::abi::{
-    tw_ethereum_abi_decode_contract_call, tw_ethereum_abi_decode_params,
-    tw_ethereum_abi_decode_value, tw_ethereum_abi_encode_function,
-    tw_ethereum_abi_function_get_signature,
+    tw_ethereum_abi_decode_contract_call, tw_ethereum_abi_decode_log,
+    tw_ethereum_abi_decode_params, tw_ethereum_abi_decode_revert, tw_ethereum_abi_decode_value,
+    tw_ethereum_abi_encode_function, tw_ethereum_abi_encode_packed,
+    tw_ethereum_abi_function_get_signature, tw_ethereum_abi_hash_typed_data,
 };
 
 use tw_coin_registry::coin_type::CoinType;
@@ -191,3 +192,332 @@ fn test_ethereum_abi_decode_value() {
     assert!(output.error_message.is_empty());
     assert_eq!(output.param_str, "42");
 }
+
+const EVENTS_AND_ERRORS_ABI_JSON: &str = r#"[
+    {
+        "anonymous": false,
+        "inputs": [
+            { "indexed": true, "internalType": "address", "name": "from", "type": "address" },
+            { "indexed": true, "internalType": "address", "name": "to", "type": "address" },
+            { "indexed": false, "internalType": "uint256", "name": "value", "type": "uint256" }
+        ],
+        "name": "Transfer",
+        "type": "event"
+    },
+    {
+        "anonymous": false,
+        "inputs": [
+            { "indexed": true, "internalType": "string", "name": "name", "type": "string" },
+            { "indexed": true, "internalType": "address", "name": "owner", "type": "address" }
+        ],
+        "name": "NameRegistered",
+        "type": "event"
+    },
+    {
+        "inputs": [
+            { "internalType": "uint256", "name": "available", "type": "uint256" },
+            { "internalType": "uint256", "name": "required", "type": "uint256" }
+        ],
+        "name": "InsufficientBalance",
+        "type": "error"
+    },
+    {
+        "inputs": [
+            { "internalType": "address", "name": "to", "type": "address" },
+            { "internalType": "uint256", "name": "amount", "type": "uint256" }
+        ],
+        "name": "transfer",
+        "outputs": [{ "internalType": "bool", "name": "", "type": "bool" }],
+        "stateMutability": "nonpayable",
+        "type": "function"
+    }
+]"#;
+
+fn decode_log(topics: &[&str], data: &str) -> Vec<u8> {
+    let input = AbiProto::LogDecodingInput {
+        topics: topics
+            .iter()
+            .map(|topic| topic.decode_hex().unwrap().into())
+            .collect(),
+        data: data.decode_hex().unwrap().into(),
+        smart_contract_abi_json: EVENTS_AND_ERRORS_ABI_JSON.into(),
+    };
+    let input_data = TWDataHelper::create(serialize(&input).unwrap());
+
+    TWDataHelper::wrap(unsafe {
+        tw_ethereum_abi_decode_log(CoinType::Ethereum as u32, input_data.ptr())
+    })
+    .to_vec()
+    .expect("!tw_ethereum_abi_decode_log returned nullptr")
+}
+
+fn decode_revert(encoded: &str, abi_json: &str) -> Vec<u8> {
+    let input = AbiProto::RevertDecodingInput {
+        encoded: encoded.decode_hex().unwrap().into(),
+        smart_contract_abi_json: abi_json.into(),
+    };
+    let input_data = TWDataHelper::create(serialize(&input).unwrap());
+
+    TWDataHelper::wrap(unsafe {
+        tw_ethereum_abi_decode_revert(CoinType::Ethereum as u32, input_data.ptr())
+    })
+    .to_vec()
+    .expect("!tw_ethereum_abi_decode_revert returned nullptr")
+}
+
+#[test]
+fn test_ethereum_abi_decode_log_transfer() {
+    let output_data = decode_log(
+        &[
+            "ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef",
+            "0000000000000000000000009d8a62f656a8d1615c1294fd71e9cfb3e4855a4f",
+            "0000000000000000000000003535353535353535353535353535353535353535",
+        ],
+        "00000000000000000000000000000000000000000000000000000000000003e8",
+    );
+    let output: AbiProto::LogDecodingOutput = deserialize(&output_data).unwrap();
+
+    assert_eq!(output.error, AbiErrorKind::OK);
+    assert!(output.error_message.is_empty());
+    assert_eq!(output.event_signature, "Transfer(address,address,uint256)");
+
+    let actual: Json = serde_json::from_str(&output.decoded_json).unwrap();
+    let expected = json!({
+        "event": "Transfer(address,address,uint256)",
+        "inputs": [
+            {
+                "name": "from",
+                "type": "address",
+                "value": "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F"
+            },
+            {
+                "name": "to",
+                "type": "address",
+                "value": "0x3535353535353535353535353535353535353535"
+            },
+            {
+                "name": "value",
+                "type": "uint256",
+                "value": "1000"
+            }
+        ]
+    });
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn test_ethereum_abi_decode_log_indexed_string() {
+    // All parameters are indexed, so the log data is empty.
+    let output_data = decode_log(
+        &[
+            "1c6eac0e720ec22bb0653aec9c19985633a4fb07971cf973096c2f8e3c37c17f",
+            // keccak256("trusty")
+            "7e12e791ead4e204a0c57bfa45662888cb9352cf654b06a767c6d79b852e4395",
+            "0000000000000000000000009d8a62f656a8d1615c1294fd71e9cfb3e4855a4f",
+        ],
+        "",
+    );
+    let output: AbiProto::LogDecodingOutput = deserialize(&output_data).unwrap();
+
+    assert_eq!(output.error, AbiErrorKind::OK);
+    assert_eq!(output.event_signature, "NameRegistered(string,address)");
+
+    let expected_tokens = vec![
+        named_token(
+            "name",
+            TokenEnum::byte_array_fix(
+                "7e12e791ead4e204a0c57bfa45662888cb9352cf654b06a767c6d79b852e4395"
+                    .decode_hex()
+                    .unwrap()
+                    .into(),
+            ),
+        ),
+        named_token(
+            "owner",
+            TokenEnum::address("0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F".into()),
+        ),
+    ];
+    assert_eq!(output.tokens, expected_tokens);
+}
+
+#[test]
+fn test_ethereum_abi_decode_log_unknown_event() {
+    let output_data = decode_log(
+        &[
+            // Approval(address,address,uint256)
+            "8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925",
+            "0000000000000000000000009d8a62f656a8d1615c1294fd71e9cfb3e4855a4f",
+            "0000000000000000000000003535353535353535353535353535353535353535",
+        ],
+        "00000000000000000000000000000000000000000000000000000000000003e8",
+    );
+    let output: AbiProto::LogDecodingOutput = deserialize(&output_data).unwrap();
+    assert_eq!(output.error, AbiErrorKind::Error_abi_mismatch);
+}
+
+#[test]
+fn test_ethereum_abi_decode_revert_error_string() {
+    let output_data = decode_revert("08c379a0000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000000124e6f7420656e6f7567682062616c616e63650000000000000000000000000000", "");
+    let output: AbiProto::RevertDecodingOutput = deserialize(&output_data).unwrap();
+
+    assert_eq!(output.error, AbiErrorKind::OK);
+    assert!(output.error_message.is_empty());
+    assert_eq!(output.error_signature, "Error(string)");
+    assert_eq!(output.reason, "Not enough balance");
+}
+
+#[test]
+fn test_ethereum_abi_decode_revert_panic() {
+    let output_data = decode_revert(
+        "4e487b710000000000000000000000000000000000000000000000000000000000000012",
+        "",
+    );
+    let output: AbiProto::RevertDecodingOutput = deserialize(&output_data).unwrap();
+
+    assert_eq!(output.error, AbiErrorKind::OK);
+    assert_eq!(output.error_signature, "Panic(uint256)");
+    assert_eq!(output.reason, "Division or modulo by zero");
+    assert_eq!(
+        output.tokens,
+        vec![named_token(
+            "code",
+            TokenEnum::number_uint(number_n::<256>(0x12))
+        )]
+    );
+}
+
+#[test]
+fn test_ethereum_abi_decode_revert_custom_error() {
+    let output_data = decode_revert("cf479181000000000000000000000000000000000000000000000000000000000000006400000000000000000000000000000000000000000000000000000000000003e8", EVENTS_AND_ERRORS_ABI_JSON);
+    let output: AbiProto::RevertDecodingOutput = deserialize(&output_data).unwrap();
+
+    assert_eq!(output.error, AbiErrorKind::OK);
+    assert_eq!(
+        output.error_signature,
+        "InsufficientBalance(uint256,uint256)"
+    );
+    assert_eq!(output.reason, "InsufficientBalance");
+
+    let actual: Json = serde_json::from_str(&output.decoded_json).unwrap();
+    let expected = json!({
+        "error": "InsufficientBalance(uint256,uint256)",
+        "inputs": [
+            { "name": "available", "type": "uint256", "value": "100" },
+            { "name": "required", "type": "uint256", "value": "1000" }
+        ]
+    });
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn test_ethereum_abi_decode_revert_unknown_custom_error() {
+    // Unauthorized()
+    let output_data = decode_revert("82b42900", "");
+    let output: AbiProto::RevertDecodingOutput = deserialize(&output_data).unwrap();
+    assert_eq!(output.error, AbiErrorKind::Error_abi_mismatch);
+}
+
+#[test]
+fn test_ethereum_abi_encode_packed() {
+    // abi.encodePacked(int16(-1), bytes1(0x42), uint16(0x03), string("Hello, world!"))
+    let input = AbiProto::PackedEncodingInput {
+        tokens: vec![
+            named_token(
+                "",
+                TokenEnum::number_int(Proto::NumberNParam {
+                    bits: 16,
+                    value: "ffff".decode_hex().unwrap().into(),
+                }),
+            ),
+            named_token("", TokenEnum::byte_array_fix(vec![0x42].into())),
+            named_token("", TokenEnum::number_uint(number_n::<16>(3))),
+            named_token("", TokenEnum::string_value("Hello, world!".into())),
+        ],
+    };
+    let input_data = TWDataHelper::create(serialize(&input).unwrap());
+
+    let output_data = TWDataHelper::wrap(unsafe {
+        tw_ethereum_abi_encode_packed(CoinType::Ethereum as u32, input_data.ptr())
+    })
+    .to_vec()
+    .expect("!tw_ethereum_abi_encode_packed returned nullptr");
+
+    let output: AbiProto::PackedEncodingOutput = deserialize(&output_data)
+        .expect("!tw_ethereum_abi_encode_packed returned an invalid output");
+
+    assert_eq!(output.error, AbiErrorKind::OK);
+    assert!(output.error_message.is_empty());
+    assert_eq!(
+        output.encoded.to_hex(),
+        "ffff42000348656c6c6f2c20776f726c6421"
+    );
+}
+
+#[test]
+fn test_ethereum_abi_hash_typed_data() {
+    let message_json = json!({
+        "types": {
+            "EIP712Domain": [
+                { "name": "name", "type": "string" },
+                { "name": "version", "type": "string" },
+                { "name": "chainId", "type": "uint256" },
+                { "name": "verifyingContract", "type": "address" }
+            ],
+            "Person": [
+                { "name": "name", "type": "string" },
+                { "name": "wallet", "type": "address" }
+            ],
+            "Mail": [
+                { "name": "from", "type": "Person" },
+                { "name": "to", "type": "Person" },
+                { "name": "contents", "type": "string" }
+            ]
+        },
+        "primaryType": "Mail",
+        "domain": {
+            "name": "Ether Mail",
+            "version": "1",
+            "chainId": 1,
+            "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+        },
+        "message": {
+            "from": {
+                "name": "Cow",
+                "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"
+            },
+            "to": {
+                "name": "Bob",
+                "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"
+            },
+            "contents": "Hello, Bob!"
+        }
+    });
+    let input = AbiProto::TypedDataHashingInput {
+        message_json: message_json.to_string().into(),
+    };
+    let input_data = TWDataHelper::create(serialize(&input).unwrap());
+
+    let output_data = TWDataHelper::wrap(unsafe {
+        tw_ethereum_abi_hash_typed_data(CoinType::Ethereum as u32, input_data.ptr())
+    })
+    .to_vec()
+    .expect("!tw_ethereum_abi_hash_typed_data returned nullptr");
+
+    let output: AbiProto::TypedDataHashingOutput = deserialize(&output_data)
+        .expect("!tw_ethereum_abi_hash_typed_data returned an invalid output");
+
+    assert_eq!(output.error, AbiErrorKind::OK);
+    assert!(output.error_message.is_empty());
+    assert_eq!(
+        output.domain_separator.to_hex(),
+        "f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f"
+    );
+    assert_eq!(
+        output.message_hash.to_hex(),
+        "c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e"
+    );
+    assert_eq!(
+        output.signing_hash.to_hex(),
+        "be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"
+    );
+}
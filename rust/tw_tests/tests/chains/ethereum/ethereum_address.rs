@@ -3,9 +3,11 @@
 // Copyright © 2017 Trust Wallet.
 
 use tw_any_coin::test_utils::address_utils::{
-    test_address_get_data, test_address_invalid, test_address_normalization, test_address_valid,
+    test_address_create_with_extended_public_key, test_address_get_data, test_address_invalid,
+    test_address_normalization, test_address_valid, AddressCreateWithExtendedPublicKey,
 };
 use tw_coin_registry::blockchain_type::BlockchainType;
+use tw_coin_registry::coin_type::CoinType;
 use tw_coin_registry::registry::coin_items_by_blockchain;
 
 #[test]
@@ -44,3 +46,21 @@ fn test_ethereum_address_get_data() {
         );
     }
 }
+
+#[test]
+fn test_ethereum_address_create_with_extended_public_key() {
+    test_address_create_with_extended_public_key(AddressCreateWithExtendedPublicKey {
+        coin: CoinType::Ethereum,
+        extended_public_key: "xpub6C7LtZJgtz1BKXG9mExKUxYvX7HSF38UMMmGbpqNQw3DfYwAw8E6sH7VSVxFipvEEm2afSqTjoRgcLmycXX4zfxCWJ4HY73a9KdgvfHEQGB",
+        derivation_path: "m/44'/60'/0'/0/1",
+        expected: Some("0x996891c410FB76C19DBA72C6f6cEFF2d9DD069b1"),
+    });
+
+    // Extended public keys cannot be used to derive ed25519 addresses.
+    test_address_create_with_extended_public_key(AddressCreateWithExtendedPublicKey {
+        coin: CoinType::Solana,
+        extended_public_key: "xpub6BosfCnifzxcFwrSzQiqu2DBVTshkCXacvNsWGYJVVhhawA7d4R5WSWGFNbi8Aw6ZRc1brxMyWMzG3DSSSSoekkudhUd9yLb6qx39T9nMdj",
+        derivation_path: "m/44'/501'/0'/0/0",
+        expected: None,
+    });
+}
@@ -0,0 +1,99 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use tw_coin_entry::error::prelude::*;
+use tw_coin_registry::coin_type::CoinType;
+use tw_encoding::hex::{DecodeHex, ToHex};
+use tw_memory::test_utils::tw_data_helper::TWDataHelper;
+use tw_memory::Data;
+use tw_number::U256;
+use tw_proto::Ethereum::Proto;
+use tw_proto::{deserialize, serialize};
+use wallet_core_rs::ffi::ethereum::authorization::tw_ethereum_authorization_sign;
+
+fn sign_authorization(coin: CoinType, input: &Proto::AuthorizationSigningInput) -> Data {
+    let input_data = TWDataHelper::create(serialize(input).unwrap());
+    TWDataHelper::wrap(unsafe { tw_ethereum_authorization_sign(coin as u32, input_data.ptr()) })
+        .to_vec()
+        .expect("!tw_ethereum_authorization_sign returned nullptr")
+}
+
+#[test]
+fn test_ethereum_authorization_sign() {
+    let input = Proto::AuthorizationSigningInput {
+        authorization: Some(Proto::Authorization {
+            chain_id: U256::encode_be_compact(1),
+            address: "0x63c0c19a282a1B52b07dD5a65b58948A07DAE32B".into(),
+            nonce: U256::encode_be_compact(1),
+            ..Proto::Authorization::default()
+        }),
+        // 0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F
+        private_key: "4646464646464646464646464646464646464646464646464646464646464646"
+            .decode_hex()
+            .unwrap()
+            .into(),
+    };
+
+    let output_data = sign_authorization(CoinType::Ethereum, &input);
+    let output: Proto::AuthorizationSigningOutput =
+        deserialize(&output_data).expect("Coin entry returned an invalid output");
+    assert_eq!(output.error, SigningErrorType::OK);
+    assert!(output.error_message.is_empty());
+
+    assert_eq!(
+        output.pre_hash.to_hex(),
+        "73c37d50a1a39a021d14f800a6e7b8ef6a29e1f8ea5fa2fcbf91ba4a5a58abac"
+    );
+
+    let signed = output
+        .authorization
+        .expect("Expected a signed authorization");
+    assert_eq!(signed.chain_id.to_hex(), "01");
+    assert_eq!(signed.address, "0x63c0c19a282a1B52b07dD5a65b58948A07DAE32B");
+    assert_eq!(signed.nonce.to_hex(), "01");
+    assert_eq!(signed.signature.to_hex(), "9a0abf766ca397a20ebe6f60715c6135c407095a86be6e1b998eb6a166dd3454258954de48df63a95157d8fa29712b26e33c703f06b0b84f8e9db756004d100500");
+}
+
+#[test]
+fn test_ethereum_authorization_sign_ronin() {
+    let input = Proto::AuthorizationSigningInput {
+        authorization: Some(Proto::Authorization {
+            chain_id: U256::encode_be_compact(1),
+            address: "ronin:63c0c19a282a1B52b07dD5a65b58948A07DAE32B".into(),
+            nonce: U256::encode_be_compact(1),
+            ..Proto::Authorization::default()
+        }),
+        ..Proto::AuthorizationSigningInput::default()
+    };
+
+    let output_data = sign_authorization(CoinType::Ronin, &input);
+    let output: Proto::AuthorizationSigningOutput =
+        deserialize(&output_data).expect("Coin entry returned an invalid output");
+    assert_eq!(output.error, SigningErrorType::OK);
+    assert!(output.authorization.is_none());
+    // The same pre-hash as for the Ethereum address.
+    assert_eq!(
+        output.pre_hash.to_hex(),
+        "73c37d50a1a39a021d14f800a6e7b8ef6a29e1f8ea5fa2fcbf91ba4a5a58abac"
+    );
+}
+
+#[test]
+fn test_ethereum_authorization_sign_invalid_address() {
+    let input = Proto::AuthorizationSigningInput {
+        authorization: Some(Proto::Authorization {
+            chain_id: U256::encode_be_compact(1),
+            address: "0x63c0c19a282a1B52b07dD5a65b58948A07DAE3".into(),
+            nonce: U256::encode_be_compact(1),
+            ..Proto::Authorization::default()
+        }),
+        ..Proto::AuthorizationSigningInput::default()
+    };
+
+    let output_data = sign_authorization(CoinType::Ethereum, &input);
+    let output: Proto::AuthorizationSigningOutput =
+        deserialize(&output_data).expect("Coin entry returned an invalid output");
+    assert_eq!(output.error, SigningErrorType::Error_invalid_address);
+    assert!(output.pre_hash.is_empty());
+}
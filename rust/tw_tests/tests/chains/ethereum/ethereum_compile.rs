@@ -16,6 +16,10 @@ use tw_number::U256;
 use tw_proto::Ethereum::Proto;
 use tw_proto::TxCompiler::Proto as CompilerProto;
 use tw_proto::{deserialize, serialize};
+use wallet_core_rs::ffi::ethereum::authorization::tw_ethereum_authorization_sign;
+
+/// EIP-4844 blob size.
+const BYTES_PER_BLOB: usize = 131_072;
 
 #[test]
 fn test_transaction_compiler_eth() {
@@ -83,6 +87,319 @@ fn test_transaction_compiler_eth() {
     assert_eq!(output.encoded.to_hex(), expected_encoded);
 }
 
+#[test]
+fn test_transaction_compiler_eth_eip2930() {
+    let erc20_transfer = Proto::mod_Transaction::ERC20Transfer {
+        to: "0x5322b34c88ed0691971bf52a7047448f0f4efc84".into(),
+        amount: U256::encode_be_compact(1_000_000),
+    };
+    let input = Proto::SigningInput {
+        nonce: U256::encode_be_compact(3),
+        chain_id: U256::encode_be_compact(1),
+        tx_mode: Proto::TransactionMode::AccessList,
+        gas_price: U256::encode_be_compact(20_000_000_000),
+        gas_limit: U256::encode_be_compact(60_000),
+        to_address: "0xdAC17F958D2ee523a2206206994597C13D831ec7".into(),
+        transaction: Some(Proto::Transaction {
+            transaction_oneof: Proto::mod_Transaction::OneOftransaction_oneof::erc20_transfer(
+                erc20_transfer,
+            ),
+        }),
+        access_list: vec![Proto::Access {
+            address: "0xdAC17F958D2ee523a2206206994597C13D831ec7".into(),
+            stored_keys: vec![
+                "0000000000000000000000000000000000000000000000000000000000000003"
+                    .decode_hex()
+                    .unwrap()
+                    .into(),
+                "0000000000000000000000000000000000000000000000000000000000000004"
+                    .decode_hex()
+                    .unwrap()
+                    .into(),
+            ],
+        }],
+        ..Proto::SigningInput::default()
+    };
+
+    // Step 2: Obtain preimage hash
+    let input_data = TWDataHelper::create(serialize(&input).unwrap());
+    let preimage_data = TWDataHelper::wrap(unsafe {
+        tw_transaction_compiler_pre_image_hashes(CoinType::Ethereum as u32, input_data.ptr())
+    })
+    .to_vec()
+    .expect("!tw_transaction_compiler_pre_image_hashes returned nullptr");
+
+    let preimage: CompilerProto::PreSigningOutput =
+        deserialize(&preimage_data).expect("Coin entry returned an invalid output");
+
+    assert_eq!(preimage.error, SigningErrorType::OK);
+    assert!(preimage.error_message.is_empty());
+    assert_eq!(
+        preimage.data_hash.to_hex(),
+        "70a0c578edab024b126e745c7a1c7305da83c3843f9f8ed6aa849e531ec9033b"
+    );
+
+    // Step 3: Compile transaction info
+
+    // Simulate signature, normally obtained from signature server
+    let signature = "3e2544c84504d8e5f284387710203c9b3d66f84ea751d8b5ffa3840898b7d87b5c93e38ef86ae72d1cf2da37240240c19e643d2e50316d26466c38a6d596b87b01".decode_hex().unwrap();
+    let public_key = "044bc2a31265153f07e70e0bab08724e6b85e217f8cd628ceb62974247bb493382ce28cab79ad7119ee1ad3ebcdb98a16805211530ecc6cfefa1b88e6dff99232a".decode_hex().unwrap();
+
+    let signatures = TWDataVectorHelper::create([signature]);
+    let public_keys = TWDataVectorHelper::create([public_key]);
+
+    let input_data = TWDataHelper::create(serialize(&input).unwrap());
+    let output_data = TWDataHelper::wrap(unsafe {
+        tw_transaction_compiler_compile(
+            CoinType::Ethereum as u32,
+            input_data.ptr(),
+            signatures.ptr(),
+            public_keys.ptr(),
+        )
+    })
+    .to_vec()
+    .expect("!tw_transaction_compiler_compile returned nullptr");
+
+    let output: Proto::SigningOutput =
+        deserialize(&output_data).expect("Coin entry returned an invalid output");
+
+    assert_eq!(output.error, SigningErrorType::OK);
+    assert!(output.error_message.is_empty());
+    let expected_encoded = "01f9010701038504a817c80082ea6094dac17f958d2ee523a2206206994597c13d831ec780b844a9059cbb0000000000000000000000005322b34c88ed0691971bf52a7047448f0f4efc8400000000000000000000000000000000000000000000000000000000000f4240f85bf85994dac17f958d2ee523a2206206994597c13d831ec7f842a00000000000000000000000000000000000000000000000000000000000000003a0000000000000000000000000000000000000000000000000000000000000000401a03e2544c84504d8e5f284387710203c9b3d66f84ea751d8b5ffa3840898b7d87ba05c93e38ef86ae72d1cf2da37240240c19e643d2e50316d26466c38a6d596b87b";
+    assert_eq!(output.encoded.to_hex(), expected_encoded);
+}
+
+#[test]
+fn test_transaction_compiler_eth_eip7702() {
+    let mut authorization = Proto::Authorization {
+        chain_id: U256::encode_be_compact(1),
+        address: "0x63c0c19a282a1B52b07dD5a65b58948A07DAE32B".into(),
+        nonce: U256::encode_be_compact(1),
+        ..Proto::Authorization::default()
+    };
+
+    // Step 1: Obtain the authorization pre-hash
+    let authorization_input = Proto::AuthorizationSigningInput {
+        authorization: Some(authorization.clone()),
+        ..Proto::AuthorizationSigningInput::default()
+    };
+    let input_data = TWDataHelper::create(serialize(&authorization_input).unwrap());
+    let output_data = TWDataHelper::wrap(unsafe {
+        tw_ethereum_authorization_sign(CoinType::Ethereum as u32, input_data.ptr())
+    })
+    .to_vec()
+    .expect("!tw_ethereum_authorization_sign returned nullptr");
+
+    let authorization_output: Proto::AuthorizationSigningOutput =
+        deserialize(&output_data).expect("Coin entry returned an invalid output");
+
+    assert_eq!(authorization_output.error, SigningErrorType::OK);
+    assert!(authorization_output.authorization.is_none());
+    assert_eq!(
+        authorization_output.pre_hash.to_hex(),
+        "73c37d50a1a39a021d14f800a6e7b8ef6a29e1f8ea5fa2fcbf91ba4a5a58abac"
+    );
+
+    // Simulate the authorization signature, normally obtained from signature server
+    authorization.signature = "9a0abf766ca397a20ebe6f60715c6135c407095a86be6e1b998eb6a166dd3454258954de48df63a95157d8fa29712b26e33c703f06b0b84f8e9db756004d100500".decode_hex().unwrap().into();
+
+    let transfer = Proto::mod_Transaction::Transfer {
+        amount: U256::encode_be_compact(0),
+        data: Cow::default(),
+    };
+    let input = Proto::SigningInput {
+        nonce: U256::encode_be_compact(0),
+        chain_id: U256::encode_be_compact(1),
+        tx_mode: Proto::TransactionMode::SetCode,
+        max_inclusion_fee_per_gas: U256::encode_be_compact(1_000_000_000),
+        max_fee_per_gas: U256::encode_be_compact(20_000_000_000),
+        gas_limit: U256::encode_be_compact(100_000),
+        to_address: "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F".into(),
+        transaction: Some(Proto::Transaction {
+            transaction_oneof: Proto::mod_Transaction::OneOftransaction_oneof::transfer(transfer),
+        }),
+        authorization_list: vec![authorization],
+        ..Proto::SigningInput::default()
+    };
+
+    // Step 2: Obtain preimage hash
+    let input_data = TWDataHelper::create(serialize(&input).unwrap());
+    let preimage_data = TWDataHelper::wrap(unsafe {
+        tw_transaction_compiler_pre_image_hashes(CoinType::Ethereum as u32, input_data.ptr())
+    })
+    .to_vec()
+    .expect("!tw_transaction_compiler_pre_image_hashes returned nullptr");
+
+    let preimage: CompilerProto::PreSigningOutput =
+        deserialize(&preimage_data).expect("Coin entry returned an invalid output");
+
+    assert_eq!(preimage.error, SigningErrorType::OK);
+    assert!(preimage.error_message.is_empty());
+    assert_eq!(
+        preimage.data_hash.to_hex(),
+        "2296000d573cb5b83b476d85037acf2a9d8ea5ae1011932e4ee1ef4f8cf7fab2"
+    );
+
+    // Step 3: Compile transaction info
+
+    // Simulate signature, normally obtained from signature server
+    let signature = "ad2a6e7dfefb0c11de98386b3b66cda2c6d13d9a8f61fc13c94dc3c6f2c319c312f07c41fe837fc1f33fe5f963fe9a0870832c2832a926d21119ac60842cb68400".decode_hex().unwrap();
+    let public_key = "044bc2a31265153f07e70e0bab08724e6b85e217f8cd628ceb62974247bb493382ce28cab79ad7119ee1ad3ebcdb98a16805211530ecc6cfefa1b88e6dff99232a".decode_hex().unwrap();
+
+    let signatures = TWDataVectorHelper::create([signature]);
+    let public_keys = TWDataVectorHelper::create([public_key]);
+
+    let input_data = TWDataHelper::create(serialize(&input).unwrap());
+    let output_data = TWDataHelper::wrap(unsafe {
+        tw_transaction_compiler_compile(
+            CoinType::Ethereum as u32,
+            input_data.ptr(),
+            signatures.ptr(),
+            public_keys.ptr(),
+        )
+    })
+    .to_vec()
+    .expect("!tw_transaction_compiler_compile returned nullptr");
+
+    let output: Proto::SigningOutput =
+        deserialize(&output_data).expect("Coin entry returned an invalid output");
+
+    assert_eq!(output.error, SigningErrorType::OK);
+    assert!(output.error_message.is_empty());
+    let expected_encoded = "04f8ca0180843b9aca008504a817c800830186a0949d8a62f656a8d1615c1294fd71e9cfb3e4855a4f8080c0f85cf85a019463c0c19a282a1b52b07dd5a65b58948a07dae32b0180a09a0abf766ca397a20ebe6f60715c6135c407095a86be6e1b998eb6a166dd3454a0258954de48df63a95157d8fa29712b26e33c703f06b0b84f8e9db756004d100580a0ad2a6e7dfefb0c11de98386b3b66cda2c6d13d9a8f61fc13c94dc3c6f2c319c3a012f07c41fe837fc1f33fe5f963fe9a0870832c2832a926d21119ac60842cb684";
+    assert_eq!(output.encoded.to_hex(), expected_encoded);
+}
+
+#[test]
+fn test_transaction_compiler_eth_eip4844_blob() {
+    let transfer = Proto::mod_Transaction::Transfer {
+        amount: U256::encode_be_compact(0),
+        data: Cow::default(),
+    };
+    // The commitment and proof of an empty blob.
+    let commitment: Cow<[u8]> = "c00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000".decode_hex().unwrap().into();
+    let input = Proto::SigningInput {
+        nonce: U256::encode_be_compact(0),
+        chain_id: U256::encode_be_compact(1),
+        tx_mode: Proto::TransactionMode::Blob,
+        max_inclusion_fee_per_gas: U256::encode_be_compact(1_000_000_000),
+        max_fee_per_gas: U256::encode_be_compact(20_000_000_000),
+        gas_limit: U256::encode_be_compact(21_000),
+        to_address: "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F".into(),
+        transaction: Some(Proto::Transaction {
+            transaction_oneof: Proto::mod_Transaction::OneOftransaction_oneof::transfer(transfer),
+        }),
+        max_fee_per_blob_gas: U256::encode_be_compact(1_000_000_000),
+        blob_versioned_hashes: vec![
+            "010657f37554c781402a22917dee2f75def7ab966d7b770905398eba3c444014"
+                .decode_hex()
+                .unwrap()
+                .into(),
+        ],
+        blob_sidecar: Some(Proto::BlobSidecar {
+            blobs: vec![vec![0; BYTES_PER_BLOB].into()],
+            commitments: vec![commitment.clone()],
+            proofs: vec![commitment],
+        }),
+        ..Proto::SigningInput::default()
+    };
+
+    // Step 2: Obtain preimage hash
+    let input_data = TWDataHelper::create(serialize(&input).unwrap());
+    let preimage_data = TWDataHelper::wrap(unsafe {
+        tw_transaction_compiler_pre_image_hashes(CoinType::Ethereum as u32, input_data.ptr())
+    })
+    .to_vec()
+    .expect("!tw_transaction_compiler_pre_image_hashes returned nullptr");
+
+    let preimage: CompilerProto::PreSigningOutput =
+        deserialize(&preimage_data).expect("Coin entry returned an invalid output");
+
+    assert_eq!(preimage.error, SigningErrorType::OK);
+    assert!(preimage.error_message.is_empty());
+    assert_eq!(
+        preimage.data_hash.to_hex(),
+        "7a087cd5b98579cbd31bb1f446368df261f394ee1e3e3bca61cece1d190a120d"
+    );
+
+    // Step 3: Compile transaction info
+
+    // Simulate signature, normally obtained from signature server
+    let signature = "ba0f2837d74d1438a4dd8fef76f7e42fc7f4262d1efc0bf28d39ef17d41b5b88232ae1b976e6d80bfc2ebb2d8dfcd2e98030ed90b50c809e868f9fa9b5ce1d4e01".decode_hex().unwrap();
+    let public_key = "044bc2a31265153f07e70e0bab08724e6b85e217f8cd628ceb62974247bb493382ce28cab79ad7119ee1ad3ebcdb98a16805211530ecc6cfefa1b88e6dff99232a".decode_hex().unwrap();
+
+    let signatures = TWDataVectorHelper::create([signature]);
+    let public_keys = TWDataVectorHelper::create([public_key]);
+
+    let input_data = TWDataHelper::create(serialize(&input).unwrap());
+    let output_data = TWDataHelper::wrap(unsafe {
+        tw_transaction_compiler_compile(
+            CoinType::Ethereum as u32,
+            input_data.ptr(),
+            signatures.ptr(),
+            public_keys.ptr(),
+        )
+    })
+    .to_vec()
+    .expect("!tw_transaction_compiler_compile returned nullptr");
+
+    let output: Proto::SigningOutput =
+        deserialize(&output_data).expect("Coin entry returned an invalid output");
+
+    assert_eq!(output.error, SigningErrorType::OK);
+    assert!(output.error_message.is_empty());
+    let expected_encoded = "03f8920180843b9aca008504a817c800825208949d8a62f656a8d1615c1294fd71e9cfb3e4855a4f8080c0843b9aca00e1a0010657f37554c781402a22917dee2f75def7ab966d7b770905398eba3c44401401a0ba0f2837d74d1438a4dd8fef76f7e42fc7f4262d1efc0bf28d39ef17d41b5b88a0232ae1b976e6d80bfc2ebb2d8dfcd2e98030ed90b50c809e868f9fa9b5ce1d4e";
+    assert_eq!(output.encoded.to_hex(), expected_encoded);
+    // The network wrapper starts with the signed transaction payload followed by the blobs.
+    assert_eq!(output.encoded_network_wrapper.len(), 131_333);
+    assert!(output
+        .encoded_network_wrapper
+        .to_hex()
+        .starts_with(&format!(
+            "03fa020100{}fa020004ba020000",
+            &expected_encoded[2..]
+        )));
+}
+
+#[test]
+fn test_transaction_compiler_eth_eip7702_unsigned_authorization() {
+    let transfer = Proto::mod_Transaction::Transfer {
+        amount: U256::encode_be_compact(0),
+        data: Cow::default(),
+    };
+    let input = Proto::SigningInput {
+        nonce: U256::encode_be_compact(0),
+        chain_id: U256::encode_be_compact(1),
+        tx_mode: Proto::TransactionMode::SetCode,
+        max_inclusion_fee_per_gas: U256::encode_be_compact(1_000_000_000),
+        max_fee_per_gas: U256::encode_be_compact(20_000_000_000),
+        gas_limit: U256::encode_be_compact(100_000),
+        to_address: "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F".into(),
+        transaction: Some(Proto::Transaction {
+            transaction_oneof: Proto::mod_Transaction::OneOftransaction_oneof::transfer(transfer),
+        }),
+        // The authorization must be signed on external signing.
+        authorization_list: vec![Proto::Authorization {
+            chain_id: U256::encode_be_compact(1),
+            address: "0x63c0c19a282a1B52b07dD5a65b58948A07DAE32B".into(),
+            nonce: U256::encode_be_compact(1),
+            ..Proto::Authorization::default()
+        }],
+        ..Proto::SigningInput::default()
+    };
+
+    let input_data = TWDataHelper::create(serialize(&input).unwrap());
+    let preimage_data = TWDataHelper::wrap(unsafe {
+        tw_transaction_compiler_pre_image_hashes(CoinType::Ethereum as u32, input_data.ptr())
+    })
+    .to_vec()
+    .expect("!tw_transaction_compiler_pre_image_hashes returned nullptr");
+
+    let preimage: CompilerProto::PreSigningOutput =
+        deserialize(&preimage_data).expect("Coin entry returned an invalid output");
+    assert_eq!(preimage.error, SigningErrorType::Error_missing_private_key);
+}
+
 #[test]
 fn test_transaction_compiler_plan_not_supported() {
     let transfer = Proto::mod_Transaction::Transfer {
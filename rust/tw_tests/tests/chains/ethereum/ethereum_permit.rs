@@ -0,0 +1,376 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use std::borrow::Cow;
+use tw_any_coin::ffi::tw_any_signer::tw_any_signer_sign;
+use tw_coin_entry::error::prelude::*;
+use tw_coin_registry::coin_type::CoinType;
+use tw_encoding::hex::{DecodeHex, ToHex};
+use tw_hash::H256;
+use tw_keypair::ecdsa::secp256k1;
+use tw_keypair::traits::SigningKeyTrait;
+use tw_memory::test_utils::tw_data_helper::TWDataHelper;
+use tw_memory::Data;
+use tw_number::U256;
+use tw_proto::Ethereum::Proto;
+use tw_proto::{deserialize, serialize};
+use wallet_core_rs::ffi::ethereum::permit::{tw_ethereum_permit_compile, tw_ethereum_permit_sign};
+
+use Proto::mod_PermitSigningInput::OneOfpermit_oneof as PermitType;
+
+const PRIVATE_KEY: &str = "4646464646464646464646464646464646464646464646464646464646464646";
+const OWNER: &str = "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F";
+const PERMIT2: &str = "0x000000000022D473030F116dDEE9F6B43aC78BA3";
+const USDC: &str = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+const USDT: &str = "0xdAC17F958D2ee523a2206206994597C13D831ec7";
+const UNIVERSAL_ROUTER: &str = "0x3fC91A3afd70395Cd496C647d5a6CC9D4B2b7FAD";
+const RECIPIENT: &str = "0x61061fCAE11fD5461535e134EfF67A98CFFF44E9";
+/// `SimpleAccount` smart account of the owner.
+const SMART_ACCOUNT: &str = "0x8f6aC01Bf5F7b6bB1bb0bb8Bd29C1E1D3FC5d2C5";
+const ENTRY_POINT_V0_6: &str = "0x5FF137D4b0FDCD49DcA30c7CF57E578a026d2789";
+
+fn sign_permit(input: &Proto::PermitSigningInput) -> Data {
+    let input_data = TWDataHelper::create(serialize(input).unwrap());
+    TWDataHelper::wrap(unsafe {
+        tw_ethereum_permit_sign(CoinType::Ethereum as u32, input_data.ptr())
+    })
+    .to_vec()
+    .expect("!tw_ethereum_permit_sign returned nullptr")
+}
+
+fn compile_permit(input: &Proto::PermitSigningInput, signature: &[u8]) -> Data {
+    let input_data = TWDataHelper::create(serialize(input).unwrap());
+    let signature_data = TWDataHelper::create(signature.to_vec());
+    TWDataHelper::wrap(unsafe {
+        tw_ethereum_permit_compile(
+            CoinType::Ethereum as u32,
+            input_data.ptr(),
+            signature_data.ptr(),
+        )
+    })
+    .to_vec()
+    .expect("!tw_ethereum_permit_compile returned nullptr")
+}
+
+/// Signs the permit pre-hash outside of the library, `v` is 0 or 1.
+fn sign_externally(private_key: &str, pre_hash: &[u8]) -> Data {
+    secp256k1::PrivateKey::try_from(private_key)
+        .unwrap()
+        .sign(H256::try_from(pre_hash).unwrap())
+        .unwrap()
+        .to_bytes()
+        .to_vec()
+}
+
+/// EIP-2612 permit that allows the owner's smart account to spend 1 USDC.
+fn smart_account_erc2612_permit() -> Proto::PermitSigningInput<'static> {
+    Proto::PermitSigningInput {
+        chain_id: U256::encode_be_compact(1),
+        owner: OWNER.into(),
+        permit_oneof: PermitType::erc2612_permit(Proto::Erc2612Permit {
+            token: USDC.into(),
+            token_name: "USD Coin".into(),
+            token_version: "2".into(),
+            spender: SMART_ACCOUNT.into(),
+            value: U256::encode_be_compact(1_000_000),
+            nonce: U256::encode_be_compact(0),
+            deadline: U256::encode_be_compact(1_700_000_000),
+        }),
+        ..Proto::PermitSigningInput::default()
+    }
+}
+
+fn permit2_details(token: &str, amount: u64, nonce: u64) -> Proto::Permit2Details<'static> {
+    Proto::Permit2Details {
+        token: token.to_string().into(),
+        amount: U256::encode_be_compact(amount),
+        expiration: U256::encode_be_compact(1_700_000_000),
+        nonce: U256::encode_be_compact(nonce),
+    }
+}
+
+#[test]
+fn test_ethereum_permit_sign_erc2612() {
+    let input = Proto::PermitSigningInput {
+        chain_id: U256::encode_be_compact(1),
+        private_key: PRIVATE_KEY.decode_hex().unwrap().into(),
+        permit_oneof: PermitType::erc2612_permit(Proto::Erc2612Permit {
+            token: USDC.into(),
+            token_name: "USD Coin".into(),
+            token_version: "2".into(),
+            spender: UNIVERSAL_ROUTER.into(),
+            value: U256::encode_be_compact(1_000_000),
+            nonce: U256::encode_be_compact(0),
+            deadline: U256::encode_be_compact(1_700_000_000),
+        }),
+        ..Proto::PermitSigningInput::default()
+    };
+
+    let output_data = sign_permit(&input);
+    let output: Proto::PermitSigningOutput =
+        deserialize(&output_data).expect("Coin entry returned an invalid output");
+    assert_eq!(output.error, SigningErrorType::OK);
+    assert!(output.error_message.is_empty());
+
+    assert_eq!(
+        output.pre_hash.to_hex(),
+        "0247aff46364d25152dd7197831ccbdd98428410bcc8af72d42c36259ad6a3a1"
+    );
+    assert_eq!(output.to, USDC);
+    assert_eq!(output.signature.to_hex(), "231f38387a3d1e22be921bca705e2e4d0178f03a5e24dfdca433151766b4e828195aa84a027e9198bdeddfa004f2f65aa4cff60aa615e0ce44c99e90f5d404d11b");
+    // permit(owner, spender, value, deadline, v, r, s)
+    assert_eq!(output.payload.to_hex(), "d505accf0000000000000000000000009d8a62f656a8d1615c1294fd71e9cfb3e4855a4f0000000000000000000000003fc91a3afd70395cd496c647d5a6cc9d4b2b7fad00000000000000000000000000000000000000000000000000000000000f4240000000000000000000000000000000000000000000000000000000006553f100000000000000000000000000000000000000000000000000000000000000001b231f38387a3d1e22be921bca705e2e4d0178f03a5e24dfdca433151766b4e828195aa84a027e9198bdeddfa004f2f65aa4cff60aa615e0ce44c99e90f5d404d1");
+    // Custom types are not ordered, so compare JSON values.
+    let typed_data: serde_json::Value = serde_json::from_str(&output.typed_data).unwrap();
+    let expected: serde_json::Value = serde_json::from_str(r#"{"types":{"Permit":[{"name":"owner","type":"address"},{"name":"spender","type":"address"},{"name":"value","type":"uint256"},{"name":"nonce","type":"uint256"},{"name":"deadline","type":"uint256"}],"EIP712Domain":[{"name":"name","type":"string"},{"name":"version","type":"string"},{"name":"chainId","type":"uint256"},{"name":"verifyingContract","type":"address"}]},"domain":{"chainId":"1","name":"USD Coin","verifyingContract":"0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48","version":"2"},"primaryType":"Permit","message":{"deadline":"1700000000","nonce":"0","owner":"0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F","spender":"0x3fC91A3afd70395Cd496C647d5a6CC9D4B2b7FAD","value":"1000000"}}"#).unwrap();
+    assert_eq!(typed_data, expected);
+}
+
+#[test]
+fn test_ethereum_permit_sign_permit2_single() {
+    let input = Proto::PermitSigningInput {
+        chain_id: U256::encode_be_compact(1),
+        private_key: PRIVATE_KEY.decode_hex().unwrap().into(),
+        permit_oneof: PermitType::permit2_single(Proto::Permit2Single {
+            details: Some(permit2_details(USDC, 1_000_000, 0)),
+            spender: UNIVERSAL_ROUTER.into(),
+            sig_deadline: U256::encode_be_compact(1_700_000_000),
+        }),
+        ..Proto::PermitSigningInput::default()
+    };
+
+    let output_data = sign_permit(&input);
+    let output: Proto::PermitSigningOutput =
+        deserialize(&output_data).expect("Coin entry returned an invalid output");
+    assert_eq!(output.error, SigningErrorType::OK);
+
+    assert_eq!(
+        output.pre_hash.to_hex(),
+        "0cdcb2ce4e694f2680ab782e6132de6b0f5b8ebd22c687c4709dd10bf44b306e"
+    );
+    assert_eq!(output.to, PERMIT2);
+    assert_eq!(output.signature.to_hex(), "f0670f5080065140ae50c336767b1c85a78480f12c8cc576bf79f680ac97035b7b5e88eef305fbd18e1c583af0b2d4a2e08fa43a3050513239be4cf0884d2af11b");
+    // permit(address owner, PermitSingle permitSingle, bytes signature)
+    assert_eq!(output.payload.to_hex(), "2b67b5700000000000000000000000009d8a62f656a8d1615c1294fd71e9cfb3e4855a4f000000000000000000000000a0b86991c6218b36c1d19d4a2e9eb0ce3606eb4800000000000000000000000000000000000000000000000000000000000f4240000000000000000000000000000000000000000000000000000000006553f10000000000000000000000000000000000000000000000000000000000000000000000000000000000000000003fc91a3afd70395cd496c647d5a6cc9d4b2b7fad000000000000000000000000000000000000000000000000000000006553f10000000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000000041f0670f5080065140ae50c336767b1c85a78480f12c8cc576bf79f680ac97035b7b5e88eef305fbd18e1c583af0b2d4a2e08fa43a3050513239be4cf0884d2af11b00000000000000000000000000000000000000000000000000000000000000");
+}
+
+#[test]
+fn test_ethereum_permit_sign_permit2_batch() {
+    let input = Proto::PermitSigningInput {
+        chain_id: U256::encode_be_compact(1),
+        private_key: PRIVATE_KEY.decode_hex().unwrap().into(),
+        permit_oneof: PermitType::permit2_batch(Proto::Permit2Batch {
+            details: vec![
+                permit2_details(USDC, 1_000_000, 0),
+                permit2_details(USDT, 2_000_000, 1),
+            ],
+            spender: UNIVERSAL_ROUTER.into(),
+            sig_deadline: U256::encode_be_compact(1_700_000_000),
+        }),
+        ..Proto::PermitSigningInput::default()
+    };
+
+    let output_data = sign_permit(&input);
+    let output: Proto::PermitSigningOutput =
+        deserialize(&output_data).expect("Coin entry returned an invalid output");
+    assert_eq!(output.error, SigningErrorType::OK);
+
+    assert_eq!(
+        output.pre_hash.to_hex(),
+        "589e8607d7457461404c88a6910c55f6460bd353ff8deafb0f1775b5c81a1d0d"
+    );
+    assert_eq!(output.to, PERMIT2);
+    assert_eq!(output.signature.to_hex(), "e8717fd8d852906044c47fc745d227de363dd0e0f5be27f35efb981034f1351a7509f827d7f9e72246708acb5463462b5653df4991e76dd822d7d8218e0fb4de1c");
+    // permit(address owner, PermitBatch permitBatch, bytes signature)
+    assert_eq!(output.payload.to_hex(), "2a2d80d10000000000000000000000009d8a62f656a8d1615c1294fd71e9cfb3e4855a4f000000000000000000000000000000000000000000000000000000000000006000000000000000000000000000000000000000000000000000000000000001e000000000000000000000000000000000000000000000000000000000000000600000000000000000000000003fc91a3afd70395cd496c647d5a6cc9d4b2b7fad000000000000000000000000000000000000000000000000000000006553f1000000000000000000000000000000000000000000000000000000000000000002000000000000000000000000a0b86991c6218b36c1d19d4a2e9eb0ce3606eb4800000000000000000000000000000000000000000000000000000000000f4240000000000000000000000000000000000000000000000000000000006553f1000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000dac17f958d2ee523a2206206994597c13d831ec700000000000000000000000000000000000000000000000000000000001e8480000000000000000000000000000000000000000000000000000000006553f10000000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000041e8717fd8d852906044c47fc745d227de363dd0e0f5be27f35efb981034f1351a7509f827d7f9e72246708acb5463462b5653df4991e76dd822d7d8218e0fb4de1c00000000000000000000000000000000000000000000000000000000000000");
+}
+
+#[test]
+fn test_ethereum_permit_sign_permit2_transfer_from_external() {
+    let input = Proto::PermitSigningInput {
+        chain_id: U256::encode_be_compact(1),
+        owner: OWNER.into(),
+        permit_oneof: PermitType::permit2_transfer_from(Proto::Permit2TransferFrom {
+            token: USDC.into(),
+            amount: U256::encode_be_compact(1_000_000),
+            spender: UNIVERSAL_ROUTER.into(),
+            nonce: U256::encode_be_compact(0),
+            deadline: U256::encode_be_compact(1_700_000_000),
+        }),
+        ..Proto::PermitSigningInput::default()
+    };
+
+    let output_data = sign_permit(&input);
+    let output: Proto::PermitSigningOutput =
+        deserialize(&output_data).expect("Coin entry returned an invalid output");
+    assert_eq!(output.error, SigningErrorType::OK);
+
+    assert_eq!(
+        output.pre_hash.to_hex(),
+        "6bfd4ce5e9b916abf1c2fdadc4108e2fff7e2bbb4280cb7b0d6f2cca8dba396f"
+    );
+    assert_eq!(output.to, PERMIT2);
+    assert!(!output.typed_data.is_empty());
+    assert!(output.signature.is_empty());
+    assert!(output.payload.is_empty());
+}
+
+#[test]
+fn test_ethereum_permit_sign_owner_mismatch() {
+    let input = Proto::PermitSigningInput {
+        chain_id: U256::encode_be_compact(1),
+        private_key: PRIVATE_KEY.decode_hex().unwrap().into(),
+        owner: UNIVERSAL_ROUTER.into(),
+        permit_oneof: PermitType::permit2_single(Proto::Permit2Single {
+            details: Some(permit2_details(USDC, 1_000_000, 0)),
+            spender: UNIVERSAL_ROUTER.into(),
+            sig_deadline: U256::encode_be_compact(1_700_000_000),
+        }),
+        ..Proto::PermitSigningInput::default()
+    };
+
+    let output_data = sign_permit(&input);
+    let output: Proto::PermitSigningOutput =
+        deserialize(&output_data).expect("Coin entry returned an invalid output");
+    assert_eq!(output.error, SigningErrorType::Error_invalid_params);
+    assert!(output.signature.is_empty());
+}
+
+#[test]
+fn test_ethereum_permit_sign_permit2_amount_overflow() {
+    let mut details = permit2_details(USDC, 0, 0);
+    // uint160 max + 1
+    details.amount = "010000000000000000000000000000000000000000"
+        .decode_hex()
+        .unwrap()
+        .into();
+
+    let input = Proto::PermitSigningInput {
+        chain_id: U256::encode_be_compact(1),
+        owner: OWNER.into(),
+        permit_oneof: PermitType::permit2_single(Proto::Permit2Single {
+            details: Some(details),
+            spender: UNIVERSAL_ROUTER.into(),
+            sig_deadline: U256::encode_be_compact(1_700_000_000),
+        }),
+        ..Proto::PermitSigningInput::default()
+    };
+
+    let output_data = sign_permit(&input);
+    let output: Proto::PermitSigningOutput =
+        deserialize(&output_data).expect("Coin entry returned an invalid output");
+    assert_eq!(output.error, SigningErrorType::Error_invalid_params);
+    assert!(output.pre_hash.is_empty());
+}
+
+#[test]
+fn test_ethereum_permit_compile_erc2612_batch() {
+    let input = smart_account_erc2612_permit();
+
+    // Get the hash to be signed by an external signer, e.g. a hardware wallet.
+    let output_data = sign_permit(&input);
+    let output: Proto::PermitSigningOutput = deserialize(&output_data).unwrap();
+    assert_eq!(output.error, SigningErrorType::OK);
+    assert!(output.payload.is_empty());
+
+    let signature = sign_externally(PRIVATE_KEY, &output.pre_hash);
+    let compiled_data = compile_permit(&input, &signature);
+    let compiled: Proto::PermitSigningOutput = deserialize(&compiled_data).unwrap();
+    assert_eq!(compiled.error, SigningErrorType::OK);
+    assert!(compiled.error_message.is_empty());
+    assert_eq!(compiled.pre_hash, output.pre_hash);
+    assert_eq!(compiled.to, USDC);
+
+    // The result is the same as signed with the private key.
+    let signed_data = sign_permit(&Proto::PermitSigningInput {
+        private_key: PRIVATE_KEY.decode_hex().unwrap().into(),
+        ..smart_account_erc2612_permit()
+    });
+    let signed: Proto::PermitSigningOutput = deserialize(&signed_data).unwrap();
+    assert_eq!(signed.error, SigningErrorType::OK);
+    assert_eq!(compiled.signature, signed.signature);
+    assert_eq!(compiled.payload, signed.payload);
+
+    // `transferFrom(owner, recipient, 1_000_000)` called by the smart account right after `permit(...)`.
+    let transfer_from = format!(
+        "23b872dd{:0>64}{:0>64}{:0>64x}",
+        OWNER[2..].to_lowercase(),
+        RECIPIENT[2..].to_lowercase(),
+        1_000_000
+    );
+    let batch = Proto::mod_Transaction::Batch {
+        calls: vec![
+            Proto::mod_Transaction::mod_Batch::BatchedCall {
+                address: compiled.to.clone(),
+                amount: Cow::default(),
+                payload: compiled.payload.clone(),
+            },
+            Proto::mod_Transaction::mod_Batch::BatchedCall {
+                address: USDC.into(),
+                amount: Cow::default(),
+                payload: transfer_from.as_str().decode_hex().unwrap().into(),
+            },
+        ],
+    };
+
+    let signing = Proto::SigningInput {
+        chain_id: U256::encode_be_compact(1),
+        nonce: U256::encode_be_compact(0),
+        tx_mode: Proto::TransactionMode::UserOp,
+        gas_limit: U256::encode_be_compact(200_000),
+        max_fee_per_gas: U256::encode_be_compact(2_000_000_000),
+        max_inclusion_fee_per_gas: U256::encode_be_compact(1_000_000_000),
+        private_key: PRIVATE_KEY.decode_hex().unwrap().into(),
+        transaction: Some(Proto::Transaction {
+            transaction_oneof: Proto::mod_Transaction::OneOftransaction_oneof::batch(batch),
+        }),
+        user_operation_oneof: Proto::mod_SigningInput::OneOfuser_operation_oneof::user_operation(
+            Proto::UserOperation {
+                entry_point: ENTRY_POINT_V0_6.into(),
+                sender: SMART_ACCOUNT.into(),
+                pre_verification_gas: U256::encode_be_compact(50_000),
+                verification_gas_limit: U256::encode_be_compact(150_000),
+                ..Proto::UserOperation::default()
+            },
+        ),
+        ..Proto::SigningInput::default()
+    };
+
+    let input_data = TWDataHelper::create(serialize(&signing).unwrap());
+    let output_data = TWDataHelper::wrap(unsafe {
+        tw_any_signer_sign(input_data.ptr(), CoinType::Ethereum as u32)
+    })
+    .to_vec()
+    .expect("!tw_any_signer_sign returned nullptr");
+    let output: Proto::SigningOutput = deserialize(&output_data).unwrap();
+    assert_eq!(output.error, SigningErrorType::OK);
+    assert!(output.error_message.is_empty());
+
+    // `executeBatch(address[] dest, bytes[] func)` calls `permit(...)` and then `transferFrom(...)`.
+    let user_op: serde_json::Value = serde_json::from_slice(&output.encoded).unwrap();
+    let call_data = user_op["callData"].as_str().unwrap();
+    let permit_at = call_data.find(&compiled.payload.to_hex()).unwrap();
+    let transfer_from_at = call_data.find(&transfer_from).unwrap();
+    assert!(permit_at < transfer_from_at);
+}
+
+#[test]
+fn test_ethereum_permit_compile_not_owner_signature() {
+    let input = smart_account_erc2612_permit();
+    let output_data = sign_permit(&input);
+    let output: Proto::PermitSigningOutput = deserialize(&output_data).unwrap();
+
+    // Signed by another account.
+    let other_private_key = "9ea2172511ed73ae0096be8e593c3b75631700edaf729f1abbae607314a20e35";
+    let signature = sign_externally(other_private_key, &output.pre_hash);
+    let compiled_data = compile_permit(&input, &signature);
+    let compiled: Proto::PermitSigningOutput = deserialize(&compiled_data).unwrap();
+    assert_eq!(compiled.error, SigningErrorType::Error_signing);
+    assert!(compiled.payload.is_empty());
+
+    // Invalid signature length.
+    let compiled_data = compile_permit(&input, &signature[..64]);
+    let compiled: Proto::PermitSigningOutput = deserialize(&compiled_data).unwrap();
+    assert_eq!(compiled.error, SigningErrorType::Error_invalid_params);
+}
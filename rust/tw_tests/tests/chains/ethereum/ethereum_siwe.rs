@@ -0,0 +1,200 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use tw_any_coin::ffi::tw_message_signer::{
+    tw_message_signer_pre_image_hashes, tw_message_signer_sign,
+};
+use tw_coin_entry::error::prelude::*;
+use tw_coin_registry::coin_type::CoinType;
+use tw_encoding::hex::{DecodeHex, ToHex};
+use tw_memory::test_utils::tw_data_helper::TWDataHelper;
+use tw_memory::Data;
+use tw_proto::Ethereum::Proto;
+use tw_proto::{deserialize, serialize, TxCompiler};
+use wallet_core_rs::ffi::ethereum::siwe::tw_ethereum_siwe_verify;
+
+const PRIVATE_KEY: &str = "4646464646464646464646464646464646464646464646464646464646464646";
+const MESSAGE: &str = "service.org wants you to sign in with your Ethereum account:
+0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F
+
+I accept the ServiceOrg Terms of Service: https://service.org/tos
+
+URI: https://service.org/login
+Version: 1
+Chain ID: 1
+Nonce: 32891756
+Issued At: 2021-09-30T16:25:24Z
+Expiration Time: 2021-10-01T16:25:24Z
+Resources:
+- ipfs://bafybeiemxf5abjwjbikoz4mc3a3dla6ual3jsgpdr4cjr3oz3evfyavhwq/
+- https://example.com/my-web2-claim.json";
+const MESSAGE_HASH: &str = "07a243802b45a06916725ac6a41a415b04265a2a53361b6c2514547a42187977";
+const SIGNATURE: &str = "4ca01a58df965d0c27c81f63a791098f48a63894c2b6f1b85ed6a87ac7f8d6686ac11eacd9e6583e7092c6baec1aaaa7ef5d79e02edaf3812a785343763dcc821c";
+
+fn sign_siwe(message: &str, chain_id: Option<u64>) -> Data {
+    let input = Proto::MessageSigningInput {
+        private_key: PRIVATE_KEY.decode_hex().unwrap().into(),
+        message: message.to_string().into(),
+        chain_id: chain_id.map(|chain_id| Proto::MaybeChainId { chain_id }),
+        message_type: Proto::MessageType::MessageType_siwe,
+    };
+
+    let input_data = TWDataHelper::create(serialize(&input).unwrap());
+    TWDataHelper::wrap(unsafe {
+        tw_message_signer_sign(input_data.ptr(), CoinType::Ethereum as u32)
+    })
+    .to_vec()
+    .expect("!tw_message_signer_sign returned nullptr")
+}
+
+fn verify_siwe(input: &Proto::SiweVerifyingInput) -> Data {
+    let input_data = TWDataHelper::create(serialize(input).unwrap());
+    TWDataHelper::wrap(unsafe {
+        tw_ethereum_siwe_verify(CoinType::Ethereum as u32, input_data.ptr())
+    })
+    .to_vec()
+    .expect("!tw_ethereum_siwe_verify returned nullptr")
+}
+
+#[test]
+fn test_ethereum_siwe_sign() {
+    let output_data = sign_siwe(MESSAGE, Some(1));
+    let output: Proto::MessageSigningOutput = deserialize(&output_data).unwrap();
+    assert_eq!(output.error, SigningErrorType::OK);
+    assert!(output.error_message.is_empty());
+    assert_eq!(output.signature, SIGNATURE);
+}
+
+#[test]
+fn test_ethereum_siwe_verify() {
+    let input = Proto::SiweVerifyingInput {
+        message: MESSAGE.into(),
+        signature: SIGNATURE.into(),
+        domain: "service.org".into(),
+        nonce: "32891756".into(),
+        // 2021-10-01T00:00:00Z
+        timestamp: 1_633_046_400,
+    };
+
+    let output_data = verify_siwe(&input);
+    let output: Proto::SiweVerifyingOutput = deserialize(&output_data).unwrap();
+    assert_eq!(output.error, SigningErrorType::OK);
+    assert!(output.error_message.is_empty());
+    assert!(output.valid);
+    assert_eq!(
+        output.recovered_address,
+        "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F"
+    );
+    assert_eq!(output.message_hash.to_hex(), MESSAGE_HASH);
+    // isValidSignature(bytes32 hash, bytes signature)
+    assert_eq!(output.eip1271_payload.to_hex(), format!("1626ba7e{MESSAGE_HASH}00000000000000000000000000000000000000000000000000000000000000400000000000000000000000000000000000000000000000000000000000000041{SIGNATURE}00000000000000000000000000000000000000000000000000000000000000"));
+}
+
+#[test]
+fn test_ethereum_siwe_verify_smart_contract_signature() {
+    let input = Proto::SiweVerifyingInput {
+        message: MESSAGE.into(),
+        // A smart contract account signature can have an arbitrary format.
+        signature: "0xdeadbeef".into(),
+        ..Proto::SiweVerifyingInput::default()
+    };
+
+    let output_data = verify_siwe(&input);
+    let output: Proto::SiweVerifyingOutput = deserialize(&output_data).unwrap();
+    assert_eq!(output.error, SigningErrorType::OK);
+    assert!(!output.valid);
+    assert!(output.recovered_address.is_empty());
+    assert_eq!(output.message_hash.to_hex(), MESSAGE_HASH);
+    assert_eq!(output.eip1271_payload.to_hex(), format!("1626ba7e{MESSAGE_HASH}00000000000000000000000000000000000000000000000000000000000000400000000000000000000000000000000000000000000000000000000000000004deadbeef00000000000000000000000000000000000000000000000000000000"));
+}
+
+#[test]
+fn test_ethereum_siwe_verify_another_signer() {
+    // Signature of the message by another key.
+    let input = Proto::SiweVerifyingInput {
+        message: MESSAGE.into(),
+        signature: "21a779d499957e7fd39392d49a079679009e60e492d9654a148829be43d2490736ec72bc4a5644047d979c3cf4ebe2c1c514044cf436b063cb89fc6676be71101b".into(),
+        ..Proto::SiweVerifyingInput::default()
+    };
+
+    let output_data = verify_siwe(&input);
+    let output: Proto::SiweVerifyingOutput = deserialize(&output_data).unwrap();
+    assert_eq!(output.error, SigningErrorType::OK);
+    assert!(!output.valid);
+    assert_ne!(
+        output.recovered_address,
+        "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F"
+    );
+}
+
+#[test]
+fn test_ethereum_siwe_verify_expired() {
+    let input = Proto::SiweVerifyingInput {
+        message: MESSAGE.into(),
+        signature: SIGNATURE.into(),
+        // 2021-10-01T16:25:24Z
+        timestamp: 1_633_105_524,
+        ..Proto::SiweVerifyingInput::default()
+    };
+
+    let output_data = verify_siwe(&input);
+    let output: Proto::SiweVerifyingOutput = deserialize(&output_data).unwrap();
+    assert_eq!(output.error, SigningErrorType::Error_invalid_params);
+    assert!(!output.valid);
+}
+
+#[test]
+fn test_ethereum_siwe_verify_domain_mismatch() {
+    let input = Proto::SiweVerifyingInput {
+        message: MESSAGE.into(),
+        signature: SIGNATURE.into(),
+        domain: "phishing.org".into(),
+        ..Proto::SiweVerifyingInput::default()
+    };
+
+    let output_data = verify_siwe(&input);
+    let output: Proto::SiweVerifyingOutput = deserialize(&output_data).unwrap();
+    assert_eq!(output.error, SigningErrorType::Error_invalid_params);
+    assert!(!output.valid);
+}
+
+#[test]
+fn test_ethereum_siwe_pre_image_hashes() {
+    let input = Proto::MessageSigningInput {
+        message: MESSAGE.into(),
+        message_type: Proto::MessageType::MessageType_siwe,
+        ..Proto::MessageSigningInput::default()
+    };
+
+    let input_data = TWDataHelper::create(serialize(&input).unwrap());
+    let output = TWDataHelper::wrap(unsafe {
+        tw_message_signer_pre_image_hashes(input_data.ptr(), CoinType::Ethereum as u32)
+    })
+    .to_vec()
+    .expect("!tw_message_signer_pre_image_hashes returned nullptr");
+
+    let output: TxCompiler::Proto::PreSigningOutput = deserialize(&output).unwrap();
+    assert_eq!(output.error, SigningErrorType::OK);
+    assert_eq!(output.data_hash.to_hex(), MESSAGE_HASH);
+}
+
+#[test]
+fn test_ethereum_siwe_sign_chain_id_mismatch() {
+    let output_data = sign_siwe(MESSAGE, Some(56));
+    let output: Proto::MessageSigningOutput = deserialize(&output_data).unwrap();
+    assert_eq!(output.error, SigningErrorType::Error_invalid_params);
+    assert!(output.signature.is_empty());
+}
+
+#[test]
+fn test_ethereum_siwe_sign_invalid_message() {
+    let not_checksummed = MESSAGE.replace(
+        "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F",
+        "0x9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f",
+    );
+    let output_data = sign_siwe(&not_checksummed, None);
+    let output: Proto::MessageSigningOutput = deserialize(&output_data).unwrap();
+    assert_eq!(output.error, SigningErrorType::Error_invalid_params);
+    assert!(output.signature.is_empty());
+}
@@ -0,0 +1,68 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use tw_coin_entry::error::prelude::*;
+use tw_coin_registry::coin_type::CoinType;
+use tw_encoding::hex::{DecodeHex, ToHex};
+use tw_memory::test_utils::tw_data_helper::TWDataHelper;
+use tw_memory::Data;
+use tw_number::U256;
+use tw_proto::Ethereum::Proto;
+use tw_proto::{deserialize, serialize};
+use wallet_core_rs::ffi::ethereum::smart_account::tw_ethereum_smart_account_address;
+
+const OWNER: &str = "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F";
+const LIGHT_ACCOUNT_FACTORY: &str = "0x0000000000400CdFef5E2714E63d8040b700BC24";
+/// Solady `LibClone.initCodeERC1967` of the `LightAccount` v2.0.0 implementation `0x8E8e658E22B12ada97B402fF0b044D6A325013C7`.
+const LIGHT_ACCOUNT_PROXY_BYTECODE: &str = "603d3d8160223d39738e8e658e22b12ada97b402ff0b044d6a325013c760095155f3363d3d373d3d363d7f360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc545af43d6000803e6038573d6000fd5b3d6000f3";
+
+fn smart_account_address(input: &Proto::SmartAccountAddressInput) -> Data {
+    let input_data = TWDataHelper::create(serialize(input).unwrap());
+    TWDataHelper::wrap(unsafe {
+        tw_ethereum_smart_account_address(CoinType::Ethereum as u32, input_data.ptr())
+    })
+    .to_vec()
+    .expect("!tw_ethereum_smart_account_address returned nullptr")
+}
+
+#[test]
+fn test_ethereum_smart_account_address_light_account() {
+    let input = Proto::SmartAccountAddressInput {
+        account: Some(Proto::SmartAccount {
+            account_type: Proto::SmartAccountType::LightAccount,
+            ..Proto::SmartAccount::default()
+        }),
+        factory: LIGHT_ACCOUNT_FACTORY.into(),
+        owner: OWNER.into(),
+        salt: U256::encode_be_compact(1),
+        proxy_bytecode: LIGHT_ACCOUNT_PROXY_BYTECODE.decode_hex().unwrap().into(),
+    };
+
+    let output_data = smart_account_address(&input);
+    let output: Proto::SmartAccountAddressOutput =
+        deserialize(&output_data).expect("Coin entry returned an invalid output");
+    assert_eq!(output.error, SigningErrorType::OK);
+    assert!(output.error_message.is_empty());
+    assert_eq!(output.address, "0xa673e296370a1265De99FC05dF55754E44Bd7C92");
+    assert_eq!(
+        output.init_code.to_hex(),
+        "0000000000400cdfef5e2714e63d8040b700bc245fbfb9cf0000000000000000000000009d8a62f656a8d1615c1294fd71e9cfb3e4855a4f0000000000000000000000000000000000000000000000000000000000000001"
+    );
+}
+
+#[test]
+fn test_ethereum_smart_account_address_invalid_owner() {
+    let input = Proto::SmartAccountAddressInput {
+        account: None,
+        factory: LIGHT_ACCOUNT_FACTORY.into(),
+        owner: "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A".into(),
+        ..Proto::SmartAccountAddressInput::default()
+    };
+
+    let output_data = smart_account_address(&input);
+    let output: Proto::SmartAccountAddressOutput =
+        deserialize(&output_data).expect("Coin entry returned an invalid output");
+    assert_eq!(output.error, SigningErrorType::Error_invalid_address);
+    assert!(output.address.is_empty());
+}
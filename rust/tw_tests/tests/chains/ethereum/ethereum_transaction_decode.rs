@@ -0,0 +1,435 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use std::borrow::Cow;
+use tw_any_coin::test_utils::sign_utils::AnySignerHelper;
+use tw_any_coin::test_utils::transaction_decode_utils::TransactionDecoderHelper;
+use tw_coin_registry::coin_type::CoinType;
+use tw_encoding::hex::{DecodeHex, ToHex};
+use tw_number::U256;
+use tw_proto::Common::Proto::SigningError;
+use tw_proto::Ethereum::Proto;
+use tw_proto::Ethereum::Proto::mod_Transaction::OneOftransaction_oneof as TransactionType;
+
+const SENDER_PRIVATE_KEY: &str = "4646464646464646464646464646464646464646464646464646464646464646";
+const SENDER_ADDRESS: &str = "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F";
+
+/// EIP-4844 blob size.
+const BYTES_PER_BLOB: usize = 131_072;
+
+/// Signs the transaction, decodes it back and checks that the decoded signing input matches the original one.
+fn test_sign_decode_roundtrip(input: Proto::SigningInput<'_>) {
+    let mut signer = AnySignerHelper::<Proto::SigningOutput>::default();
+    let signed = signer.sign(CoinType::Ethereum, input.clone());
+    assert_eq!(signed.error, SigningError::OK, "{}", signed.error_message);
+
+    let mut decoder = TransactionDecoderHelper::<Proto::DecodingTransactionOutput>::default();
+    let output = decoder.decode(CoinType::Ethereum, signed.encoded.to_vec());
+    assert_eq!(output.error, SigningError::OK, "{}", output.error_message);
+    assert!(output.is_signed);
+    assert_eq!(output.pre_hash, signed.pre_hash);
+    assert_eq!(output.v, signed.v);
+    assert_eq!(output.r, signed.r);
+    assert_eq!(output.s, signed.s);
+
+    let expected = Proto::SigningInput {
+        private_key: Cow::default(),
+        ..input
+    };
+    assert_eq!(output.transaction.unwrap(), expected);
+}
+
+#[test]
+fn test_ethereum_decode_transaction_legacy_eip155() {
+    let tx = "f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83";
+    let mut decoder = TransactionDecoderHelper::<Proto::DecodingTransactionOutput>::default();
+    let output = decoder.decode(CoinType::Ethereum, tx.decode_hex().unwrap());
+
+    assert_eq!(output.error, SigningError::OK, "{}", output.error_message);
+    assert_eq!(output.tx_type, 0);
+    assert!(output.is_signed);
+    assert_eq!(output.sender, SENDER_ADDRESS);
+    assert_eq!(output.v.to_hex(), "25");
+    assert_eq!(
+        output.pre_hash.to_hex(),
+        "daf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53"
+    );
+    assert_eq!(
+        output.tx_hash.to_hex(),
+        "33469b22e9f636356c4160a87eb19df52b7412e8eac32a4a55ffe88ea8350788"
+    );
+
+    let transfer = Proto::mod_Transaction::Transfer {
+        amount: U256::encode_be_compact(1_000_000_000_000_000_000),
+        data: Cow::default(),
+    };
+    let expected = Proto::SigningInput {
+        chain_id: U256::encode_be_compact(1),
+        nonce: U256::encode_be_compact(9),
+        gas_price: U256::encode_be_compact(20_000_000_000),
+        gas_limit: U256::encode_be_compact(21_000),
+        to_address: "0x3535353535353535353535353535353535353535".into(),
+        transaction: Some(Proto::Transaction {
+            transaction_oneof: TransactionType::transfer(transfer),
+        }),
+        ..Proto::SigningInput::default()
+    };
+    assert_eq!(output.transaction.unwrap(), expected);
+}
+
+#[test]
+fn test_ethereum_decode_transaction_legacy_pre_eip155() {
+    let tx = "f86c808504a817c800825208943535353535353535353535353535353535353535880de0b6b3a7640000801ca0ae4ec29e5426516043e3bbfd7950486f0301c1b640f1173c05928ac063e1bcdba03006963cacd3ec33bfc3c37a8f56187a33a333b01a7e11e405424aaedcdbc3f4";
+    let mut decoder = TransactionDecoderHelper::<Proto::DecodingTransactionOutput>::default();
+    let output = decoder.decode(CoinType::Ethereum, tx.decode_hex().unwrap());
+
+    assert_eq!(output.error, SigningError::OK, "{}", output.error_message);
+    assert_eq!(output.tx_type, 0);
+    assert!(output.is_signed);
+    assert_eq!(output.sender, SENDER_ADDRESS);
+    assert_eq!(output.v.to_hex(), "1c");
+    assert_eq!(
+        output.pre_hash.to_hex(),
+        "597779acf7a80f7bd5089cbfe09ee7bb0749dc593e38b85d17c5f4ab81c34600"
+    );
+
+    let tx = output.transaction.unwrap();
+    // Chain ID is not specified in a pre-EIP155 transaction.
+    assert!(tx.chain_id.is_empty());
+    assert!(tx.nonce.is_empty());
+    assert_eq!(tx.to_address, "0x3535353535353535353535353535353535353535");
+}
+
+#[test]
+fn test_ethereum_decode_transaction_eip2930_erc20_transfer() {
+    let tx = "01f9010701038504a817c80082ea6094dac17f958d2ee523a2206206994597c13d831ec780b844a9059cbb0000000000000000000000005322b34c88ed0691971bf52a7047448f0f4efc8400000000000000000000000000000000000000000000000000000000000f4240f85bf85994dac17f958d2ee523a2206206994597c13d831ec7f842a00000000000000000000000000000000000000000000000000000000000000003a0000000000000000000000000000000000000000000000000000000000000000401a03e2544c84504d8e5f284387710203c9b3d66f84ea751d8b5ffa3840898b7d87ba05c93e38ef86ae72d1cf2da37240240c19e643d2e50316d26466c38a6d596b87b";
+    let mut decoder = TransactionDecoderHelper::<Proto::DecodingTransactionOutput>::default();
+    let output = decoder.decode(CoinType::Ethereum, tx.decode_hex().unwrap());
+
+    assert_eq!(output.error, SigningError::OK, "{}", output.error_message);
+    assert_eq!(output.tx_type, 1);
+    assert!(output.is_signed);
+    assert_eq!(output.sender, SENDER_ADDRESS);
+    assert_eq!(output.v.to_hex(), "01");
+    assert_eq!(
+        output.pre_hash.to_hex(),
+        "70a0c578edab024b126e745c7a1c7305da83c3843f9f8ed6aa849e531ec9033b"
+    );
+    assert_eq!(
+        output.tx_hash.to_hex(),
+        "2d27d5662a447bbefce5bb0e430533780312b1f3309e5e0abaf345ed9ed9f6af"
+    );
+
+    let erc20_transfer = Proto::mod_Transaction::ERC20Transfer {
+        to: "0x5322B34c88Ed0691971Bf52A7047448f0F4eFC84".into(),
+        amount: U256::encode_be_compact(1_000_000),
+    };
+    let access = Proto::Access {
+        address: "0xdAC17F958D2ee523a2206206994597C13D831ec7".into(),
+        stored_keys: vec![
+            "0000000000000000000000000000000000000000000000000000000000000003"
+                .decode_hex()
+                .unwrap()
+                .into(),
+            "0000000000000000000000000000000000000000000000000000000000000004"
+                .decode_hex()
+                .unwrap()
+                .into(),
+        ],
+    };
+    let expected = Proto::SigningInput {
+        chain_id: U256::encode_be_compact(1),
+        nonce: U256::encode_be_compact(3),
+        tx_mode: Proto::TransactionMode::AccessList,
+        gas_price: U256::encode_be_compact(20_000_000_000),
+        gas_limit: U256::encode_be_compact(60_000),
+        to_address: "0xdAC17F958D2ee523a2206206994597C13D831ec7".into(),
+        transaction: Some(Proto::Transaction {
+            transaction_oneof: TransactionType::erc20_transfer(erc20_transfer),
+        }),
+        access_list: vec![access],
+        ..Proto::SigningInput::default()
+    };
+    assert_eq!(output.transaction.unwrap(), expected);
+}
+
+#[test]
+fn test_ethereum_decode_transaction_eip7702_set_code() {
+    let tx = "04f8ca0180843b9aca008504a817c800830186a0949d8a62f656a8d1615c1294fd71e9cfb3e4855a4f8080c0f85cf85a019463c0c19a282a1b52b07dd5a65b58948a07dae32b0180a09a0abf766ca397a20ebe6f60715c6135c407095a86be6e1b998eb6a166dd3454a0258954de48df63a95157d8fa29712b26e33c703f06b0b84f8e9db756004d100580a0ad2a6e7dfefb0c11de98386b3b66cda2c6d13d9a8f61fc13c94dc3c6f2c319c3a012f07c41fe837fc1f33fe5f963fe9a0870832c2832a926d21119ac60842cb684";
+    let mut decoder = TransactionDecoderHelper::<Proto::DecodingTransactionOutput>::default();
+    let output = decoder.decode(CoinType::Ethereum, tx.decode_hex().unwrap());
+
+    assert_eq!(output.error, SigningError::OK, "{}", output.error_message);
+    assert_eq!(output.tx_type, 4);
+    assert!(output.is_signed);
+    assert_eq!(output.sender, SENDER_ADDRESS);
+    assert_eq!(output.v.to_hex(), "00");
+    assert_eq!(
+        output.pre_hash.to_hex(),
+        "2296000d573cb5b83b476d85037acf2a9d8ea5ae1011932e4ee1ef4f8cf7fab2"
+    );
+
+    let transfer = Proto::mod_Transaction::Transfer {
+        amount: U256::encode_be_compact(0),
+        data: Cow::default(),
+    };
+    let authorization = Proto::Authorization {
+        chain_id: U256::encode_be_compact(1),
+        address: "0x63c0c19a282a1B52b07dD5a65b58948A07DAE32B".into(),
+        nonce: U256::encode_be_compact(1),
+        signature: "9a0abf766ca397a20ebe6f60715c6135c407095a86be6e1b998eb6a166dd3454258954de48df63a95157d8fa29712b26e33c703f06b0b84f8e9db756004d100500".decode_hex().unwrap().into(),
+    };
+    let expected = Proto::SigningInput {
+        chain_id: U256::encode_be_compact(1),
+        nonce: U256::encode_be_compact(0),
+        tx_mode: Proto::TransactionMode::SetCode,
+        max_inclusion_fee_per_gas: U256::encode_be_compact(1_000_000_000),
+        max_fee_per_gas: U256::encode_be_compact(20_000_000_000),
+        gas_limit: U256::encode_be_compact(100_000),
+        to_address: SENDER_ADDRESS.into(),
+        transaction: Some(Proto::Transaction {
+            transaction_oneof: TransactionType::transfer(transfer),
+        }),
+        authorization_list: vec![authorization],
+        ..Proto::SigningInput::default()
+    };
+    assert_eq!(output.transaction.unwrap(), expected);
+}
+
+/// The commitment and proof of an empty blob.
+const EMPTY_BLOB_COMMITMENT: &str = "c00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000";
+const BLOB_VERSIONED_HASH: &str =
+    "010657f37554c781402a22917dee2f75def7ab966d7b770905398eba3c444014";
+
+fn blob_transaction_input() -> Proto::SigningInput<'static> {
+    let transfer = Proto::mod_Transaction::Transfer {
+        amount: U256::encode_be_compact(0),
+        data: Cow::default(),
+    };
+    Proto::SigningInput {
+        chain_id: U256::encode_be_compact(1),
+        nonce: U256::encode_be_compact(0),
+        tx_mode: Proto::TransactionMode::Blob,
+        max_inclusion_fee_per_gas: U256::encode_be_compact(1_000_000_000),
+        max_fee_per_gas: U256::encode_be_compact(20_000_000_000),
+        gas_limit: U256::encode_be_compact(21_000),
+        to_address: SENDER_ADDRESS.into(),
+        transaction: Some(Proto::Transaction {
+            transaction_oneof: TransactionType::transfer(transfer),
+        }),
+        max_fee_per_blob_gas: U256::encode_be_compact(1_000_000_000),
+        blob_versioned_hashes: vec![BLOB_VERSIONED_HASH.decode_hex().unwrap().into()],
+        ..Proto::SigningInput::default()
+    }
+}
+
+#[test]
+fn test_ethereum_decode_transaction_eip4844_blob() {
+    let tx = "03f8920180843b9aca008504a817c800825208949d8a62f656a8d1615c1294fd71e9cfb3e4855a4f8080c0843b9aca00e1a0010657f37554c781402a22917dee2f75def7ab966d7b770905398eba3c44401401a0ba0f2837d74d1438a4dd8fef76f7e42fc7f4262d1efc0bf28d39ef17d41b5b88a0232ae1b976e6d80bfc2ebb2d8dfcd2e98030ed90b50c809e868f9fa9b5ce1d4e";
+    let mut decoder = TransactionDecoderHelper::<Proto::DecodingTransactionOutput>::default();
+    let output = decoder.decode(CoinType::Ethereum, tx.decode_hex().unwrap());
+
+    assert_eq!(output.error, SigningError::OK, "{}", output.error_message);
+    assert_eq!(output.tx_type, 3);
+    assert!(output.is_signed);
+    assert_eq!(output.sender, SENDER_ADDRESS);
+    assert_eq!(output.v.to_hex(), "01");
+    assert_eq!(
+        output.pre_hash.to_hex(),
+        "7a087cd5b98579cbd31bb1f446368df261f394ee1e3e3bca61cece1d190a120d"
+    );
+    assert_eq!(
+        output.tx_hash.to_hex(),
+        "55c341754f180871af7b13cf6cab512645ea329fcb4369a15e97b77d49342182"
+    );
+    assert_eq!(output.transaction.unwrap(), blob_transaction_input());
+}
+
+#[test]
+fn test_ethereum_decode_transaction_eip4844_network_wrapper() {
+    let commitment: Cow<[u8]> = EMPTY_BLOB_COMMITMENT.decode_hex().unwrap().into();
+    let input = Proto::SigningInput {
+        private_key: SENDER_PRIVATE_KEY.decode_hex().unwrap().into(),
+        blob_sidecar: Some(Proto::BlobSidecar {
+            blobs: vec![vec![0; BYTES_PER_BLOB].into()],
+            commitments: vec![commitment.clone()],
+            proofs: vec![commitment],
+        }),
+        ..blob_transaction_input()
+    };
+
+    let mut signer = AnySignerHelper::<Proto::SigningOutput>::default();
+    let signed = signer.sign(CoinType::Ethereum, input.clone());
+    assert_eq!(signed.error, SigningError::OK, "{}", signed.error_message);
+
+    let mut decoder = TransactionDecoderHelper::<Proto::DecodingTransactionOutput>::default();
+    let output = decoder.decode(CoinType::Ethereum, signed.encoded_network_wrapper.to_vec());
+
+    assert_eq!(output.error, SigningError::OK, "{}", output.error_message);
+    assert_eq!(output.tx_type, 3);
+    assert_eq!(output.sender, SENDER_ADDRESS);
+    assert_eq!(output.pre_hash, signed.pre_hash);
+    // The transaction hash doesn't cover the blobs.
+    assert_eq!(
+        output.tx_hash.to_hex(),
+        "55c341754f180871af7b13cf6cab512645ea329fcb4369a15e97b77d49342182"
+    );
+
+    let expected = Proto::SigningInput {
+        private_key: Cow::default(),
+        ..input
+    };
+    assert_eq!(output.transaction.unwrap(), expected);
+}
+
+#[test]
+fn test_ethereum_decode_transaction_eip1559_unsigned() {
+    let tx = "02f86c0a06847735940084b2d05e0082526c946b175474e89094c44da98b954eedeac495271d0f80b844a9059cbb0000000000000000000000005322b34c88ed0691971bf52a7047448f0f4efc840000000000000000000000000000000000000000000000000001ee0c29f50cb1c0";
+    let mut decoder = TransactionDecoderHelper::<Proto::DecodingTransactionOutput>::default();
+    let output = decoder.decode(CoinType::Ethereum, tx.decode_hex().unwrap());
+
+    assert_eq!(output.error, SigningError::OK, "{}", output.error_message);
+    assert_eq!(output.tx_type, 2);
+    assert!(!output.is_signed);
+    assert!(output.sender.is_empty());
+    assert!(output.v.is_empty());
+    assert!(output.tx_hash.is_empty());
+
+    let erc20_transfer = Proto::mod_Transaction::ERC20Transfer {
+        to: "0x5322B34c88Ed0691971Bf52A7047448f0F4eFC84".into(),
+        amount: U256::encode_be_compact(0x1ee0c29f50cb1),
+    };
+    let expected = Proto::SigningInput {
+        chain_id: U256::encode_be_compact(10),
+        nonce: U256::encode_be_compact(6),
+        tx_mode: Proto::TransactionMode::Enveloped,
+        gas_limit: U256::encode_be_compact(21_100),
+        max_inclusion_fee_per_gas: U256::encode_be_compact(2_000_000_000),
+        max_fee_per_gas: U256::encode_be_compact(3_000_000_000),
+        to_address: "0x6B175474E89094C44Da98b954EedeAC495271d0F".into(),
+        transaction: Some(Proto::Transaction {
+            transaction_oneof: TransactionType::erc20_transfer(erc20_transfer),
+        }),
+        ..Proto::SigningInput::default()
+    };
+    assert_eq!(output.transaction.unwrap(), expected);
+}
+
+#[test]
+fn test_ethereum_decode_transaction_contract_deployment() {
+    // Unsigned EIP155 legacy transaction.
+    let tx = "f8440585012a05f200830f42408080b36080604052348015600f57600080fd5b50603f80601d6000396000f3fe6080604052600080fdfea164736f6c6343000819000a388080";
+    let mut decoder = TransactionDecoderHelper::<Proto::DecodingTransactionOutput>::default();
+    let output = decoder.decode(CoinType::Ethereum, tx.decode_hex().unwrap());
+
+    assert_eq!(output.error, SigningError::OK, "{}", output.error_message);
+    assert!(!output.is_signed);
+
+    let contract_generic = Proto::mod_Transaction::ContractGeneric {
+        amount: Cow::default(),
+        data: "6080604052348015600f57600080fd5b50603f80601d6000396000f3fe6080604052600080fdfea164736f6c6343000819000a"
+            .decode_hex()
+            .unwrap()
+            .into(),
+    };
+    let expected = Proto::SigningInput {
+        chain_id: U256::encode_be_compact(56),
+        nonce: U256::encode_be_compact(5),
+        gas_price: U256::encode_be_compact(5_000_000_000),
+        gas_limit: U256::encode_be_compact(1_000_000),
+        transaction: Some(Proto::Transaction {
+            transaction_oneof: TransactionType::contract_generic(contract_generic),
+        }),
+        ..Proto::SigningInput::default()
+    };
+    assert_eq!(output.transaction.unwrap(), expected);
+}
+
+#[test]
+fn test_ethereum_decode_transaction_eip1559_known_calls_roundtrip() {
+    use Proto::mod_Transaction as Tx;
+
+    let calls = [
+        TransactionType::erc20_approve(Tx::ERC20Approve {
+            spender: "0x5322B34c88Ed0691971Bf52A7047448f0F4eFC84".into(),
+            amount: U256::encode_be_compact(2_000_000_000_000_000_000),
+        }),
+        TransactionType::erc721_transfer(Tx::ERC721Transfer {
+            from: "0x718046867b5b1782379a14eA4fc0c9b724DA94Fc".into(),
+            to: "0x5322B34c88Ed0691971Bf52A7047448f0F4eFC84".into(),
+            token_id: "23c47ee5".decode_hex().unwrap().into(),
+        }),
+        TransactionType::erc1155_transfer(Tx::ERC1155Transfer {
+            from: "0x718046867b5b1782379a14eA4fc0c9b724DA94Fc".into(),
+            to: "0x5322B34c88Ed0691971Bf52A7047448f0F4eFC84".into(),
+            token_id: "23c47ee5".decode_hex().unwrap().into(),
+            value: U256::encode_be_compact(2_000_000_000_000_000_000),
+            data: "01020304".decode_hex().unwrap().into(),
+        }),
+        // Not a known call, since `transfer` has a non-zero amount.
+        TransactionType::contract_generic(Tx::ContractGeneric {
+            amount: U256::encode_be_compact(1),
+            data: "a9059cbb0000000000000000000000005322b34c88ed0691971bf52a7047448f0f4efc840000000000000000000000000000000000000000000000001bc16d674ec80000".decode_hex().unwrap().into(),
+        }),
+    ];
+
+    for transaction_oneof in calls {
+        test_sign_decode_roundtrip(Proto::SigningInput {
+            chain_id: U256::encode_be_compact(1),
+            nonce: U256::encode_be_compact(0),
+            tx_mode: Proto::TransactionMode::Enveloped,
+            gas_limit: U256::encode_be_compact(78_009),
+            max_inclusion_fee_per_gas: U256::encode_be_compact(2_000_000_000),
+            max_fee_per_gas: U256::encode_be_compact(3_000_000_000),
+            to_address: "0x4e45E92eD38f885d39A733c14f1817217a89D425".into(),
+            transaction: Some(Proto::Transaction { transaction_oneof }),
+            private_key: SENDER_PRIVATE_KEY.decode_hex().unwrap().into(),
+            ..Proto::SigningInput::default()
+        });
+    }
+}
+
+#[test]
+fn test_ronin_decode_transaction() {
+    let tx = "f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83";
+    let mut decoder = TransactionDecoderHelper::<Proto::DecodingTransactionOutput>::default();
+    let output = decoder.decode(CoinType::Ronin, tx.decode_hex().unwrap());
+
+    assert_eq!(output.error, SigningError::OK, "{}", output.error_message);
+    assert_eq!(
+        output.sender,
+        "ronin:9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F"
+    );
+    assert_eq!(
+        output.transaction.unwrap().to_address,
+        "ronin:3535353535353535353535353535353535353535"
+    );
+}
+
+#[test]
+fn test_ethereum_decode_transaction_error() {
+    // Unknown transaction type.
+    let mut decoder = TransactionDecoderHelper::<Proto::DecodingTransactionOutput>::default();
+    let output = decoder.decode(CoinType::Ethereum, "05c0".decode_hex().unwrap());
+    assert_eq!(output.error, SigningError::Error_not_supported);
+
+    // Blob transaction without fields.
+    let mut decoder = TransactionDecoderHelper::<Proto::DecodingTransactionOutput>::default();
+    let output = decoder.decode(CoinType::Ethereum, "03c0".decode_hex().unwrap());
+    assert_eq!(output.error, SigningError::Error_input_parse);
+
+    // Trailing bytes after the signed legacy transaction.
+    let mut decoder = TransactionDecoderHelper::<Proto::DecodingTransactionOutput>::default();
+    let output = decoder.decode(CoinType::Ethereum, "f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d8300".decode_hex().unwrap());
+    assert_eq!(output.error, SigningError::Error_input_parse);
+
+    // Invalid `yParity` = 2.
+    let mut decoder = TransactionDecoderHelper::<Proto::DecodingTransactionOutput>::default();
+    let output = decoder.decode(CoinType::Ethereum, "02f8710306847735940084b2d05e0082526c94b9f5771c27664bf2282d98e09d7f50cec7cb01a78701ee0c29f50cb180c002a092c336138f7d0231fe9422bb30ee9ef10bf222761fe9e04442e3a11e88880c64a06487026011dae03dc281bc21c7d7ede5c2226d197befb813a4ecad686b559e58".decode_hex().unwrap());
+    assert_eq!(output.error, SigningError::Error_input_parse);
+
+    let mut decoder = TransactionDecoderHelper::<Proto::DecodingTransactionOutput>::default();
+    let output = decoder.decode(CoinType::Ethereum, "".decode_hex().unwrap());
+    assert_eq!(output.error, SigningError::Error_input_parse);
+}
@@ -1,6 +1,9 @@
 use tw_any_coin::test_utils::transaction_calc_tx_hash_utils::TransactionUtilHelper;
 use tw_coin_registry::coin_type::CoinType;
 
+/// EIP-4844 blob size.
+const BYTES_PER_BLOB: usize = 131_072;
+
 #[test]
 fn test_ethereum_transaction_util_calc_tx_hash() {
     let encoded_tx = "f8aa808509c7652400830130b9946b175474e89094c44da98b954eedeac495271d0f80b844a9059cbb0000000000000000000000005322b34c88ed0691971bf52a7047448f0f4efc840000000000000000000000000000000000000000000000001bc16d674ec8000025a0724c62ad4fbf47346b02de06e603e013f26f26b56fdc0be7ba3d6273401d98cea0032131cae15da7ddcda66963e8bef51ca0d9962bfef0547d3f02597a4a58c931";
@@ -11,3 +14,43 @@ fn test_ethereum_transaction_util_calc_tx_hash() {
         "0x199a7829fc5149e49b452c2cab76d8fa5a9682fee6e4891b8acb697ac142513e"
     );
 }
+
+#[test]
+fn test_ethereum_transaction_util_calc_tx_hash_eip2930() {
+    let encoded_tx = "01f9010701038504a817c80082ea6094dac17f958d2ee523a2206206994597c13d831ec780b844a9059cbb0000000000000000000000005322b34c88ed0691971bf52a7047448f0f4efc8400000000000000000000000000000000000000000000000000000000000f4240f85bf85994dac17f958d2ee523a2206206994597c13d831ec7f842a00000000000000000000000000000000000000000000000000000000000000003a0000000000000000000000000000000000000000000000000000000000000000401a03e2544c84504d8e5f284387710203c9b3d66f84ea751d8b5ffa3840898b7d87ba05c93e38ef86ae72d1cf2da37240240c19e643d2e50316d26466c38a6d596b87b";
+    let tx_hash = TransactionUtilHelper::calc_tx_hash(CoinType::Ethereum, encoded_tx);
+
+    // The hash of a typed transaction is `keccak256(tx_type || rlp(payload))`.
+    assert_eq!(
+        tx_hash,
+        "0x2d27d5662a447bbefce5bb0e430533780312b1f3309e5e0abaf345ed9ed9f6af"
+    );
+}
+
+#[test]
+fn test_ethereum_transaction_util_calc_tx_hash_eip4844() {
+    let encoded_tx = "03f8920180843b9aca008504a817c800825208949d8a62f656a8d1615c1294fd71e9cfb3e4855a4f8080c0843b9aca00e1a0010657f37554c781402a22917dee2f75def7ab966d7b770905398eba3c44401401a0ba0f2837d74d1438a4dd8fef76f7e42fc7f4262d1efc0bf28d39ef17d41b5b88a0232ae1b976e6d80bfc2ebb2d8dfcd2e98030ed90b50c809e868f9fa9b5ce1d4e";
+    let tx_hash = TransactionUtilHelper::calc_tx_hash(CoinType::Ethereum, encoded_tx);
+
+    assert_eq!(
+        tx_hash,
+        "0x55c341754f180871af7b13cf6cab512645ea329fcb4369a15e97b77d49342182"
+    );
+}
+
+#[test]
+fn test_ethereum_transaction_util_calc_tx_hash_eip4844_network_wrapper() {
+    // `0x03 || rlp([tx_payload_body, blobs, commitments, proofs])` with an empty blob.
+    let tx_payload_body = &"03f8920180843b9aca008504a817c800825208949d8a62f656a8d1615c1294fd71e9cfb3e4855a4f8080c0843b9aca00e1a0010657f37554c781402a22917dee2f75def7ab966d7b770905398eba3c44401401a0ba0f2837d74d1438a4dd8fef76f7e42fc7f4262d1efc0bf28d39ef17d41b5b88a0232ae1b976e6d80bfc2ebb2d8dfcd2e98030ed90b50c809e868f9fa9b5ce1d4e"[2..];
+    let blobs = format!("fa020004ba020000{}", "00".repeat(BYTES_PER_BLOB));
+    let commitments = "f1b0c00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000";
+    let proofs = commitments;
+    let encoded_tx = format!("03fa020100{tx_payload_body}{blobs}{commitments}{proofs}");
+    let tx_hash = TransactionUtilHelper::calc_tx_hash(CoinType::Ethereum, &encoded_tx);
+
+    // The hash of a blob transaction is computed from the canonical form without blobs.
+    assert_eq!(
+        tx_hash,
+        "0x55c341754f180871af7b13cf6cab512645ea329fcb4369a15e97b77d49342182"
+    );
+}
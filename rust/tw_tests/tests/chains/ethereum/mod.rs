@@ -4,8 +4,13 @@
 
 mod ethereum_abi;
 mod ethereum_address;
+mod ethereum_authorization;
 mod ethereum_compile;
 mod ethereum_message_sign;
+mod ethereum_permit;
 mod ethereum_rlp;
 mod ethereum_sign;
+mod ethereum_siwe;
+mod ethereum_smart_account;
+mod ethereum_transaction_decode;
 mod ethereum_transaction_util;
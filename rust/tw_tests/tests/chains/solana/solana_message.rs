@@ -0,0 +1,424 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use std::borrow::Cow;
+use tw_any_coin::ffi::tw_message_signer::{
+    tw_message_signer_pre_image_hashes, tw_message_signer_sign, tw_message_signer_verify,
+};
+use tw_coin_entry::error::prelude::*;
+use tw_coin_registry::coin_type::CoinType;
+use tw_encoding::base58::{self, Alphabet};
+use tw_encoding::hex::{DecodeHex, ToHex};
+use tw_keypair::ed25519;
+use tw_keypair::traits::SigningKeyTrait;
+use tw_memory::test_utils::tw_data_helper::TWDataHelper;
+use tw_proto::Solana::Proto;
+use tw_proto::Solana::Proto::MessageSigningFormat;
+use tw_proto::{deserialize, serialize, TxCompiler};
+
+const PRIVATE_KEY: &str = "A7psj2GW7ZMdY4E5hJq14KMeYg7HFjULSsWSrTXZLvYr";
+const ADDRESS: &str = "7v91N7iZ9mNicL8WfG6cgSCKyRXydQjLh6UYBWwm6y1Q";
+const OTHER_ADDRESS: &str = "EN2sCsJ1WDV8UFqsiTXHcUPUxQ4juE71eCknHYYMifkd";
+const APPLICATION_DOMAIN: &str = "FeXRmSWmwChZbB2EC7Qjw9XKk28yBrPj3k3nzT1DKfak";
+
+fn b58(s: &str) -> Cow<'static, [u8]> {
+    base58::decode(s, Alphabet::Bitcoin).unwrap().into()
+}
+
+fn signing_input<'a>(
+    message: &'a str,
+    format: MessageSigningFormat,
+    preamble: Option<Proto::OffChainMessagePreamble<'a>>,
+) -> Proto::MessageSigningInput<'a> {
+    Proto::MessageSigningInput {
+        message: message.into(),
+        private_key: b58(PRIVATE_KEY),
+        format,
+        preamble,
+    }
+}
+
+/// Returns either the signature or the error.
+fn sign_message(input: &Proto::MessageSigningInput) -> Result<String, (SigningErrorType, String)> {
+    let input_data = TWDataHelper::create(serialize(input).unwrap());
+    let output = TWDataHelper::wrap(unsafe {
+        tw_message_signer_sign(input_data.ptr(), CoinType::Solana as u32)
+    })
+    .to_vec()
+    .expect("!tw_message_signer_sign returned nullptr");
+
+    let output: Proto::MessageSigningOutput = deserialize(&output).unwrap();
+    if output.error != SigningErrorType::OK {
+        return Err((output.error, output.error_message.to_string()));
+    }
+    Ok(output.signature.to_string())
+}
+
+fn verify_message(
+    address: &str,
+    message: &str,
+    signature: &str,
+    format: MessageSigningFormat,
+    preamble: Option<Proto::OffChainMessagePreamble>,
+) -> bool {
+    let input = Proto::MessageVerifyingInput {
+        message: message.into(),
+        address: address.into(),
+        signature: signature.into(),
+        format,
+        preamble,
+    };
+
+    let input_data = TWDataHelper::create(serialize(&input).unwrap());
+    unsafe { tw_message_signer_verify(input_data.ptr(), CoinType::Solana as u32) }
+}
+
+/// Returns either the preimage hex or the error.
+fn try_preimage(input: &Proto::MessageSigningInput) -> Result<String, SigningErrorType> {
+    let input_data = TWDataHelper::create(serialize(input).unwrap());
+    let output = TWDataHelper::wrap(unsafe {
+        tw_message_signer_pre_image_hashes(input_data.ptr(), CoinType::Solana as u32)
+    })
+    .to_vec()
+    .expect("!tw_message_signer_pre_image_hashes returned nullptr");
+
+    let output: TxCompiler::Proto::PreSigningOutput = deserialize(&output).unwrap();
+    if output.error != SigningErrorType::OK {
+        return Err(output.error);
+    }
+    Ok(output.data.to_hex())
+}
+
+fn preimage(input: &Proto::MessageSigningInput) -> String {
+    try_preimage(input).unwrap()
+}
+
+/// Signs the off-chain message `preimage` with its format byte replaced with the given `format`.
+fn sign_offchain_preimage_with_format(preimage: &str, format: u8) -> String {
+    // The format byte follows the signing domain, version and application domain.
+    const FORMAT_OFFSET: usize = 49;
+
+    let mut preimage = preimage.decode_hex().unwrap();
+    preimage[FORMAT_OFFSET] = format;
+
+    let key_pair = ed25519::sha512::KeyPair::try_from(b58(PRIVATE_KEY).as_ref()).unwrap();
+    let signature = key_pair.sign(preimage).unwrap();
+    base58::encode(signature.to_bytes().as_slice(), Alphabet::Bitcoin)
+}
+
+fn domain_preamble<'a>(signers: &[&'a str]) -> Option<Proto::OffChainMessagePreamble<'a>> {
+    Some(Proto::OffChainMessagePreamble {
+        application_domain: APPLICATION_DOMAIN.into(),
+        signers: signers.iter().map(|signer| (*signer).into()).collect(),
+    })
+}
+
+#[test]
+fn test_solana_message_sign_raw() {
+    let input = signing_input(
+        "Hello, world!",
+        MessageSigningFormat::MessageSigningFormat_Raw,
+        None,
+    );
+    assert_eq!(preimage(&input), "48656c6c6f2c20776f726c6421");
+
+    let signature = sign_message(&input).unwrap();
+    assert_eq!(
+        signature,
+        "5xD6yUBzkP32FtHzTMhJRV27JycCeKjjxftu2ePoFYE6qJHejF9WCeLEKr7Xj3GrHYobsT2CJDkrty4abaCZoWos"
+    );
+    assert!(verify_message(
+        ADDRESS,
+        "Hello, world!",
+        &signature,
+        MessageSigningFormat::MessageSigningFormat_Raw,
+        None
+    ));
+}
+
+#[test]
+fn test_solana_message_sign_offchain_restricted_ascii() {
+    let input = signing_input(
+        "Hello, world!",
+        MessageSigningFormat::MessageSigningFormat_OffChain,
+        None,
+    );
+    // Signing domain, version 0, zeroed application domain, restricted ASCII format,
+    // the signing account as the only signer, message length and body.
+    assert_eq!(
+        preimage(&input),
+        "ff736f6c616e61206f6666636861696e000000000000000000000000000000000000000000000000000000000000000000000166c2f508c9c555cacc9fb26d88e88dd54e210bb5a8bce5687f60d7e75c4cd07f0d0048656c6c6f2c20776f726c6421"
+    );
+
+    let signature = sign_message(&input).unwrap();
+    assert_eq!(
+        signature,
+        "57y26Y7Lc4vB6o629FSZwGkxQg9vvPBGTwBW4UE1taTXyu3EA8WzKWyR6j5u5vwcAJKyCzjbkkw8CKCfn1QQYJa8"
+    );
+    assert!(verify_message(
+        ADDRESS,
+        "Hello, world!",
+        &signature,
+        MessageSigningFormat::MessageSigningFormat_OffChain,
+        None
+    ));
+    // The same signature is not valid for the raw message.
+    assert!(!verify_message(
+        ADDRESS,
+        "Hello, world!",
+        &signature,
+        MessageSigningFormat::MessageSigningFormat_Raw,
+        None
+    ));
+}
+
+#[test]
+fn test_solana_message_sign_offchain_limited_utf8() {
+    let message = "Привет, мир!";
+    let input = signing_input(
+        message,
+        MessageSigningFormat::MessageSigningFormat_OffChain,
+        None,
+    );
+    let signature = sign_message(&input).unwrap();
+    assert_eq!(
+        signature,
+        "61ks8gfuEsZcDNyNjWW27jcRWgtL1zDTGzserKEvUMzmW633G9KAajC8svyq5Y1eqLWgZScMYddvaPihx5FjvHH6"
+    );
+    assert!(verify_message(
+        ADDRESS,
+        message,
+        &signature,
+        MessageSigningFormat::MessageSigningFormat_OffChain,
+        None
+    ));
+}
+
+#[test]
+fn test_solana_message_sign_offchain_extended_utf8() {
+    // Doesn't fit the 1232 bytes limit of the restricted and limited formats.
+    let message = "a".repeat(1200);
+    let input = signing_input(
+        &message,
+        MessageSigningFormat::MessageSigningFormat_OffChain,
+        None,
+    );
+    // The format byte follows the signing domain, version and application domain.
+    assert_eq!(&preimage(&input)[98..100], "02");
+
+    let signature = sign_message(&input).unwrap();
+    assert_eq!(
+        signature,
+        "288uMTQWf4uFkRLgJUFnp555pe1ZiStgHuNrjvGxiu43WiAsHq1qqZzTVx8eLmdSs87w9D9hJ9M4zV4W8fTvtdo7"
+    );
+    assert!(verify_message(
+        ADDRESS,
+        &message,
+        &signature,
+        MessageSigningFormat::MessageSigningFormat_OffChain,
+        None
+    ));
+}
+
+#[test]
+fn test_solana_message_sign_offchain_application_domain_and_signers() {
+    let preamble = domain_preamble(&[OTHER_ADDRESS, ADDRESS]);
+    let input = signing_input(
+        "Hello, world!",
+        MessageSigningFormat::MessageSigningFormat_OffChain,
+        preamble.clone(),
+    );
+    let signature = sign_message(&input).unwrap();
+    assert_eq!(
+        signature,
+        "3bMb1EV7Jw789ctDCo8L4HyDPXDbLWtybv7URR5ittTxrAvkTA3Ghcfm6tuV737PAWMLg7p9CJDfFJZJSvojHhk5"
+    );
+    assert!(verify_message(
+        ADDRESS,
+        "Hello, world!",
+        &signature,
+        MessageSigningFormat::MessageSigningFormat_OffChain,
+        preamble
+    ));
+
+    // Different signers order.
+    assert!(!verify_message(
+        ADDRESS,
+        "Hello, world!",
+        &signature,
+        MessageSigningFormat::MessageSigningFormat_OffChain,
+        domain_preamble(&[ADDRESS, OTHER_ADDRESS])
+    ));
+    // Missing application domain.
+    assert!(!verify_message(
+        ADDRESS,
+        "Hello, world!",
+        &signature,
+        MessageSigningFormat::MessageSigningFormat_OffChain,
+        Some(Proto::OffChainMessagePreamble {
+            signers: vec![OTHER_ADDRESS.into(), ADDRESS.into()],
+            ..Proto::OffChainMessagePreamble::default()
+        })
+    ));
+}
+
+#[test]
+fn test_solana_message_preimage_without_private_key() {
+    let format = MessageSigningFormat::MessageSigningFormat_OffChain;
+    let signed_input = signing_input("Hello, world!", format, None);
+
+    // E.g. a hardware wallet signs the preimage, so only the signer address is known.
+    let input = Proto::MessageSigningInput {
+        private_key: Cow::default(),
+        preamble: Some(Proto::OffChainMessagePreamble {
+            signers: vec![ADDRESS.into()],
+            ..Proto::OffChainMessagePreamble::default()
+        }),
+        ..signed_input.clone()
+    };
+    assert_eq!(preimage(&input), preimage(&signed_input));
+
+    // The raw message doesn't depend on the signer.
+    let input = Proto::MessageSigningInput {
+        private_key: Cow::default(),
+        format: MessageSigningFormat::MessageSigningFormat_Raw,
+        ..signed_input.clone()
+    };
+    assert_eq!(preimage(&input), "48656c6c6f2c20776f726c6421");
+
+    // The off-chain message signers can't be determined.
+    let input = Proto::MessageSigningInput {
+        private_key: Cow::default(),
+        ..signed_input
+    };
+    assert_eq!(
+        try_preimage(&input).unwrap_err(),
+        SigningErrorType::Error_invalid_params
+    );
+}
+
+#[test]
+fn test_solana_message_verify_offchain_any_allowed_format() {
+    let format = MessageSigningFormat::MessageSigningFormat_OffChain;
+
+    // Some wallets sign ASCII messages in the limited or extended UTF-8 format.
+    let ascii_preimage = preimage(&signing_input("Hello, world!", format, None));
+    for message_format in [0, 1, 2] {
+        let signature = sign_offchain_preimage_with_format(&ascii_preimage, message_format);
+        assert!(verify_message(
+            ADDRESS,
+            "Hello, world!",
+            &signature,
+            format,
+            None
+        ));
+    }
+
+    // A non-ASCII message is not allowed in the restricted ASCII format.
+    let utf8_preimage = preimage(&signing_input("Привет, мир!", format, None));
+    let signature = sign_offchain_preimage_with_format(&utf8_preimage, 0);
+    assert!(!verify_message(
+        ADDRESS,
+        "Привет, мир!",
+        &signature,
+        format,
+        None
+    ));
+    let signature = sign_offchain_preimage_with_format(&utf8_preimage, 2);
+    assert!(verify_message(
+        ADDRESS,
+        "Привет, мир!",
+        &signature,
+        format,
+        None
+    ));
+
+    // Unknown format.
+    let signature = sign_offchain_preimage_with_format(&ascii_preimage, 3);
+    assert!(!verify_message(
+        ADDRESS,
+        "Hello, world!",
+        &signature,
+        format,
+        None
+    ));
+}
+
+#[test]
+fn test_solana_message_verify_invalid() {
+    let signature =
+        "57y26Y7Lc4vB6o629FSZwGkxQg9vvPBGTwBW4UE1taTXyu3EA8WzKWyR6j5u5vwcAJKyCzjbkkw8CKCfn1QQYJa8";
+    let format = MessageSigningFormat::MessageSigningFormat_OffChain;
+
+    // Different message.
+    assert!(!verify_message(
+        ADDRESS,
+        "Hello, world",
+        signature,
+        format,
+        None
+    ));
+    // Different signer.
+    assert!(!verify_message(
+        OTHER_ADDRESS,
+        "Hello, world!",
+        signature,
+        format,
+        None
+    ));
+    // The signer is not in the signers list.
+    assert!(!verify_message(
+        ADDRESS,
+        "Hello, world!",
+        signature,
+        format,
+        domain_preamble(&[OTHER_ADDRESS])
+    ));
+    // Invalid signature encoding.
+    assert!(!verify_message(
+        ADDRESS,
+        "Hello, world!",
+        "0x00",
+        format,
+        None
+    ));
+    // Invalid address.
+    assert!(!verify_message(
+        "0x00",
+        "Hello, world!",
+        signature,
+        format,
+        None
+    ));
+}
+
+#[test]
+fn test_solana_message_sign_offchain_errors() {
+    let format = MessageSigningFormat::MessageSigningFormat_OffChain;
+
+    // The signing account is not in the signers list.
+    let input = signing_input("Hello, world!", format, domain_preamble(&[OTHER_ADDRESS]));
+    let (error, _) = sign_message(&input).unwrap_err();
+    assert_eq!(error, SigningErrorType::Error_invalid_params);
+
+    // Empty message.
+    let input = signing_input("", format, None);
+    let (error, _) = sign_message(&input).unwrap_err();
+    assert_eq!(error, SigningErrorType::Error_invalid_params);
+
+    // Too long message.
+    let message = "a".repeat(u16::MAX as usize + 1);
+    let input = signing_input(&message, format, None);
+    let (error, _) = sign_message(&input).unwrap_err();
+    assert_eq!(error, SigningErrorType::Error_invalid_params);
+
+    // Invalid application domain.
+    let preamble = Some(Proto::OffChainMessagePreamble {
+        application_domain: ADDRESS[..20].into(),
+        ..Proto::OffChainMessagePreamble::default()
+    });
+    let input = signing_input("Hello, world!", format, preamble);
+    let (error, _) = sign_message(&input).unwrap_err();
+    assert_eq!(error, SigningErrorType::Error_invalid_params);
+}
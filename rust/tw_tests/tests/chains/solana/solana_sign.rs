@@ -4,11 +4,14 @@
 
 use std::borrow::Cow;
 use tw_any_coin::test_utils::sign_utils::AnySignerHelper;
+use tw_any_coin::test_utils::transaction_decode_utils::TransactionDecoderHelper;
 use tw_coin_registry::coin_type::CoinType;
 use tw_encoding::base58::{self, Alphabet};
 use tw_encoding::hex::DecodeHex;
 use tw_proto::Common::Proto::SigningError;
 use tw_proto::Solana::Proto;
+use tw_proto::Solana::Proto::mod_DecodedInstruction as decoded;
+use tw_proto::Solana::Proto::mod_DecodedInstruction::OneOfinstruction as DecodedType;
 
 use tw_proto::Solana::Proto::mod_SigningInput::OneOftransaction_type as TransactionType;
 
@@ -16,6 +19,28 @@ fn b58(s: &str) -> Cow<'static, [u8]> {
     base58::decode(s, Alphabet::Bitcoin).unwrap().into()
 }
 
+/// Decodes the signed transaction and returns its decoded instructions
+/// along with the addresses of the accounts passed to each instruction.
+fn decode_instructions<'a>(
+    decoder: &'a mut TransactionDecoderHelper<'a, Proto::DecodingTransactionOutput<'a>>,
+    encoded: &str,
+) -> Vec<(Proto::DecodedInstruction<'a>, Vec<String>)> {
+    let output = decoder.decode(CoinType::Solana, b58(encoded).to_vec());
+    assert_eq!(output.error, SigningError::OK);
+
+    let Proto::mod_RawMessage::OneOfmessage::legacy(message) = output.transaction.unwrap().message
+    else {
+        panic!("Expected a legacy message");
+    };
+    let accounts = message.instructions.iter().map(|ix| {
+        ix.accounts
+            .iter()
+            .map(|idx| message.account_keys[*idx as usize].to_string())
+            .collect()
+    });
+    output.instructions.into_iter().zip(accounts).collect()
+}
+
 #[test]
 fn test_solana_sign_transfer() {
     let transfer = Proto::Transfer {
@@ -973,3 +998,287 @@ fn test_solana_sign_transfer_token_2022() {
     assert_eq!(output.encoded, "SAXNFUd7dNBu956Gi4XNuvMkKKjS9vp6puz45ErYMHFpMNwC3AQxDxGbweXt4GzY2FnUZ6ubm231NrdwWa8dg9bqgRMaHPLuPiy99YwtvcQ1E6mHxHqq8nL5VaN8wiVnrMU57zCLfHsSsVCHZc5peHHAPXMDE318uMCLLBwgDWuD1FfAvUAyXRSYniXzWG3jtBdDhuDohh13E2TMrtqTcKVv3crejFqFjtsNuW7KCqrZwxCv1ASNiiL2XScQBdHwStyjH2UTqLmT6wjGLiDYy7PZ88Tbz65r8NLr4Vb1aYSTChasfVjMLdybetfNaf4nJuBE4ZuXca7W66txKbHesxQbzrjUCXX12JFbKyaA8KJKBpbgkc9jWJjQkzyn");
     // https://explorer.solana.com/tx/Lg1xWzsC9GatQMu1ZXv23t7snC92RRvbKJe22bsS76GUb8C8a9q3HPkiUnFoK6AWKSoNSsmko1EBnvKkCnL8b7w?cluster=devnet
 }
+
+#[test]
+fn test_solana_sign_create_and_transfer_token_with_address_lookup_tables() {
+    const RECIPIENT_MAIN: &str = "71e8mDsh3PR6gN64zL1HjwuxyKpgRXrPDUJT7XXojsVd";
+    const TOKEN_MINT: &str = "SRMuApVNdxXokk5GT7XD5cUUgXMBCoAz2LHeuAoKWRt";
+    const RECIPIENT_TOKEN: &str = "EF6L8yJT1SoRoDCkAZfSVmaweqMzfhxZiptKi7Tgj5XY";
+    const SENDER_TOKEN: &str = "ANVCrmRw7Ww7rTFfMbrjApSPXEEcZpBa6YEiBdf98pAf";
+    const TOKEN_PROGRAM: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+    const SYSTEM_PROGRAM: &str = "11111111111111111111111111111111";
+
+    let create_transfer_token = Proto::CreateAndTransferToken {
+        recipient_main_address: RECIPIENT_MAIN.into(),
+        token_mint_address: TOKEN_MINT.into(),
+        recipient_token_address: RECIPIENT_TOKEN.into(),
+        sender_token_address: SENDER_TOKEN.into(),
+        // 0.0029
+        amount: 2900,
+        decimals: 6,
+        ..Proto::CreateAndTransferToken::default()
+    };
+    let address_lookup_tables = vec![
+        // Loads a single account only, so it doesn't reduce the transaction size.
+        Proto::AddressLookupTable {
+            account_key: "5cFsmTCEfmvpBUBHqsWZnf9n5vTWLYH2LT8X7HdShwxP".into(),
+            addresses: vec![SENDER_TOKEN.into()],
+        },
+        // Token program is invoked, so it cannot be loaded from the table.
+        Proto::AddressLookupTable {
+            account_key: "FeXRmSWmwChZbB2EC7Qjw9XKk28yBrPj3k3nzT1DKfak".into(),
+            addresses: vec![
+                TOKEN_PROGRAM.into(),
+                TOKEN_MINT.into(),
+                RECIPIENT_MAIN.into(),
+                RECIPIENT_TOKEN.into(),
+                SYSTEM_PROGRAM.into(),
+            ],
+        },
+    ];
+    let input = Proto::SigningInput {
+        private_key: b58("66ApBuKpo2uSzpjGBraHq7HP8UZMUJzp3um8FdEjkC9c"),
+        recent_blockhash: "DMmDdJP41M9mw8Z4586VSvxqGCrqPy5uciF6HsKUVDja".into(),
+        v0_msg: true,
+        transaction_type: TransactionType::create_and_transfer_token_transaction(
+            create_transfer_token,
+        ),
+        address_lookup_tables,
+        ..Proto::SigningInput::default()
+    };
+
+    let mut signer = AnySignerHelper::<Proto::SigningOutput>::default();
+    let output = signer.sign(CoinType::Solana, input);
+
+    assert_eq!(output.error, SigningError::OK);
+    // The recipient accounts, token mint and system program are loaded from the second table:
+    // writable indexes [3], readonly indexes [2, 1, 4].
+    // The transaction is 329 bytes long instead of 417 bytes of the legacy one.
+    assert_eq!(output.encoded, "2Wg6Lfb54z4HDGFHyQbVi5eN6YWrQUDPMoiVjPZPAWAYBYzsmn2DmVbRLCzwmJBRDpZaPsVFaYi3kgPWUmqSC1ewKvUbtB5ZkjSQ9ovnHmaN6Ve27HKsoXiDSAmgs6bZqyu2ZHauwMUPp9ifkmGChuLpSRx6JKBR5gGR78gQgneDs9F8piVL6c5YEsinmYccXtgsAgznYt1BAPDAPg4yYBwrxZ6Re7pVTMzN2Yy43xm4Cj46zf4Z9PnpYCeZS1ZK1HrGAjwZysgJ6LCYucvaT1tEYHGxccZcX3Mxm9wZiDgmkod2osKDZiA8CKD2d2waMwdcoWp2UBnJkLx1sZX8sxZgZN8bk5DrA2K4q9YaV65U57MaGZ8MPTC6msXCohyDUpNDezDYhnFDbnis2t66sEbDzRgRiZyLAVBQRQHEDJTUMhjFKsfY6N3vaxuZAMWSP");
+}
+
+#[test]
+fn test_solana_sign_address_lookup_tables_legacy_error() {
+    let transfer = Proto::Transfer {
+        recipient: "EN2sCsJ1WDV8UFqsiTXHcUPUxQ4juE71eCknHYYMifkd".into(),
+        value: 42,
+        ..Proto::Transfer::default()
+    };
+    let input = Proto::SigningInput {
+        private_key: b58("A7psj2GW7ZMdY4E5hJq14KMeYg7HFjULSsWSrTXZLvYr"),
+        recent_blockhash: "11111111111111111111111111111111".into(),
+        transaction_type: TransactionType::transfer_transaction(transfer),
+        address_lookup_tables: vec![Proto::AddressLookupTable {
+            account_key: "FeXRmSWmwChZbB2EC7Qjw9XKk28yBrPj3k3nzT1DKfak".into(),
+            addresses: vec!["EN2sCsJ1WDV8UFqsiTXHcUPUxQ4juE71eCknHYYMifkd".into()],
+        }],
+        ..Proto::SigningInput::default()
+    };
+
+    let mut signer = AnySignerHelper::<Proto::SigningOutput>::default();
+    let output = signer.sign(CoinType::Solana, input);
+
+    assert_eq!(output.error, SigningError::Error_invalid_params);
+}
+
+const TOKEN_2022_SIGNER: &str = "APLpMKDSAadaBQqNhNvyAnoyMg3s3fZKhcMfTmqdE8zd";
+/// `TransferCheckedWithFee` accounts: source, mint, destination, authority,
+/// followed by the hook extra account, the hook program ID and the hook validation account.
+const TOKEN_2022_TRANSFER_WITH_HOOK_ACCOUNTS: [&str; 7] = [
+    "EQxRyhzjyhRX4TJXt7FmQ3HfFdRcu49krjxHMszidQYS",
+    "BSQCmMAFB9itonyVSLsUxX92Ne1rgBZFqothBk3q91k6",
+    "FzsLNpzsLMBbm1LWpM6P3W4tKrCkd8KqnMmADNvArW5d",
+    TOKEN_2022_SIGNER,
+    "Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS",
+    "DrWbQtYJGtsoRwzKqAbHKHKsCJJfpysudF39GBVFSxub",
+    // PDA of ["extra-account-metas", mint] seeds of the hook program.
+    "144d5RwwMEKbg1qotpHjq3UZiZcaHewd9uvCTEBdzgpH",
+];
+
+fn token_2022_extensions() -> Proto::Token2022Extensions<'static> {
+    Proto::Token2022Extensions {
+        // 0.5% capped at 5000 base units.
+        transfer_fee: Some(Proto::TransferFeeConfig {
+            transfer_fee_basis_points: 50,
+            maximum_fee: 5000,
+        }),
+        recipient_requires_memo: true,
+        transfer_hook: Some(Proto::TransferHook {
+            program_id: "DrWbQtYJGtsoRwzKqAbHKHKsCJJfpysudF39GBVFSxub".into(),
+            extra_accounts: vec![Proto::TransferHookAccount {
+                address: "Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS".into(),
+                is_signer: false,
+                is_writable: true,
+            }],
+        }),
+    }
+}
+
+fn token_2022_memo(memo: &str) -> Proto::DecodedInstruction<'_> {
+    Proto::DecodedInstruction {
+        program_id: "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr".into(),
+        instruction: DecodedType::memo(decoded::Memo {
+            memo: memo.into(),
+            signers: Vec::default(),
+        }),
+    }
+}
+
+fn token_2022_transfer_with_fee() -> Proto::DecodedInstruction<'static> {
+    Proto::DecodedInstruction {
+        program_id: "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb".into(),
+        instruction: DecodedType::token_transfer_checked_with_fee(
+            decoded::TokenTransferCheckedWithFee {
+                source: "EQxRyhzjyhRX4TJXt7FmQ3HfFdRcu49krjxHMszidQYS".into(),
+                mint: "BSQCmMAFB9itonyVSLsUxX92Ne1rgBZFqothBk3q91k6".into(),
+                destination: "FzsLNpzsLMBbm1LWpM6P3W4tKrCkd8KqnMmADNvArW5d".into(),
+                authority: TOKEN_2022_SIGNER.into(),
+                amount: 1000000000,
+                decimals: 9,
+                // 0.5% of the amount capped at the maximum fee.
+                fee: 5000,
+            },
+        ),
+    }
+}
+
+#[test]
+fn test_solana_sign_transfer_token_2022_with_extensions() {
+    let transfer_token = Proto::TokenTransfer {
+        amount: 1000000000,
+        decimals: 9,
+        memo: "Invoice #42".into(),
+        token_program_id: Proto::TokenProgramId::Token2022Program,
+        token_mint_address: "BSQCmMAFB9itonyVSLsUxX92Ne1rgBZFqothBk3q91k6".into(),
+        sender_token_address: "EQxRyhzjyhRX4TJXt7FmQ3HfFdRcu49krjxHMszidQYS".into(),
+        recipient_token_address: "FzsLNpzsLMBbm1LWpM6P3W4tKrCkd8KqnMmADNvArW5d".into(),
+        token_2022_extensions: Some(token_2022_extensions()),
+        ..Proto::TokenTransfer::default()
+    };
+    let input = Proto::SigningInput {
+        private_key: b58("MCyXa2gTJELxTPemyVi5ydDcQ3vVgFyddQYXj6UM3tw"),
+        recent_blockhash: "9U2eTS9b2Essvo1s5hDmwgC1atkSCCUipj2FemLvdWbj".into(),
+        transaction_type: TransactionType::token_transfer_transaction(transfer_token),
+        ..Proto::SigningInput::default()
+    };
+    let mut signer = AnySignerHelper::<Proto::SigningOutput>::default();
+    let output = signer.sign(CoinType::Solana, input);
+    assert_eq!(output.error, SigningError::OK);
+    // Memo is followed by `TransferCheckedWithFee` with the 5000 fee. The transfer accounts are followed by
+    // the hook extra account, the hook program ID and the validation account 144d5RwwMEKbg1qotpHjq3UZiZcaHewd9uvCTEBdzgpH.
+    let mut decoder = TransactionDecoderHelper::default();
+    let instructions = decode_instructions(&mut decoder, &output.encoded);
+    assert_eq!(instructions.len(), 2);
+    assert_eq!(instructions[0].0, token_2022_memo("Invoice #42"));
+    assert_eq!(instructions[1].0, token_2022_transfer_with_fee());
+    assert_eq!(instructions[1].1, TOKEN_2022_TRANSFER_WITH_HOOK_ACCOUNTS);
+    assert_eq!(output.encoded, "2f6P7JNbi9oZnMUnMST47R6ofvNU5Go8hJHWu1zg5jcmZ8pWnPDE59DbzozR6U4qbUwcxFnhtSi59UpJv7NAibAZ8PsizvGn3aL961LQfXNmuKiyRxptsgb8zCgBKGJWtwqe5RewtpYHobAKp4x3yxWV25B1W7jhckAdWzLSPUjArymsE2Xv7m6a4MPawzQwdR62wdec98ncqsysz8BMxLhTC3x8o2re5Cq24bNU8wbbmwEPUH7KvtcvzsrLPZWwiiCQ14s3itdYMKwzQAK514kUJ9pvtEd61EUBDUkA7CEyDDMxcneh3XVmGBpdiyNeg2L262r3jNdZ65ijrFGVx913Mxqf3zYgUCDrG1EgXksu2sqvYhA3a3ZAk8Mu4Ztn4HTt7AtbkKsyUPcqNx8Gq8GNxE8962WsyCnvTWfD6ECiWG5yHoEmmtpJFjnA9cVr9yHKewLXNqhPVEmbj9jy35x7xfe2LWxz93YkJiHutBzmYa98z9BVRkWQRG2CjfaFqbFfR3RUtWRAQymeAYbFV4t2BX4iQ6mzoN1xmKUNx1yacXksRbtvTG5JowAw2nGi4p8v24BsmqHKzhV");
+}
+
+#[test]
+fn test_solana_sign_create_and_transfer_token_2022_with_extensions() {
+    let create_transfer_token = Proto::CreateAndTransferToken {
+        recipient_main_address: "EbHdsfVpWzeQV4TceYQ2xENS8meBHyztyTKVSFtgHPUw".into(),
+        token_mint_address: "BSQCmMAFB9itonyVSLsUxX92Ne1rgBZFqothBk3q91k6".into(),
+        recipient_token_address: "FzsLNpzsLMBbm1LWpM6P3W4tKrCkd8KqnMmADNvArW5d".into(),
+        sender_token_address: "EQxRyhzjyhRX4TJXt7FmQ3HfFdRcu49krjxHMszidQYS".into(),
+        amount: 1000000000,
+        decimals: 9,
+        memo: "Invoice #42".into(),
+        token_program_id: Proto::TokenProgramId::Token2022Program,
+        token_2022_extensions: Some(token_2022_extensions()),
+        ..Proto::CreateAndTransferToken::default()
+    };
+    let input = Proto::SigningInput {
+        private_key: b58("MCyXa2gTJELxTPemyVi5ydDcQ3vVgFyddQYXj6UM3tw"),
+        recent_blockhash: "5oba9g5nWnvutTTb935aBMkHBYGXoak1ot4U2p34zEiJ".into(),
+        transaction_type: TransactionType::create_and_transfer_token_transaction(
+            create_transfer_token,
+        ),
+        ..Proto::SigningInput::default()
+    };
+    let mut signer = AnySignerHelper::<Proto::SigningOutput>::default();
+    let output = signer.sign(CoinType::Solana, input);
+    assert_eq!(output.error, SigningError::OK, "{}", output.error_message);
+
+    let mut decoder = TransactionDecoderHelper::default();
+    let instructions = decode_instructions(&mut decoder, &output.encoded);
+    // The recipient token account is created before the memo and the transfer.
+    assert_eq!(instructions.len(), 3);
+    assert_eq!(
+        instructions[0].0,
+        Proto::DecodedInstruction {
+            program_id: "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL".into(),
+            instruction: DecodedType::create_associated_token_account(
+                decoded::CreateAssociatedTokenAccount {
+                    funding_account: TOKEN_2022_SIGNER.into(),
+                    associated_token_account: "FzsLNpzsLMBbm1LWpM6P3W4tKrCkd8KqnMmADNvArW5d".into(),
+                    wallet: "EbHdsfVpWzeQV4TceYQ2xENS8meBHyztyTKVSFtgHPUw".into(),
+                    mint: "BSQCmMAFB9itonyVSLsUxX92Ne1rgBZFqothBk3q91k6".into(),
+                    token_program_id: "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb".into(),
+                    idempotent: false,
+                },
+            ),
+        }
+    );
+    assert_eq!(instructions[1].0, token_2022_memo("Invoice #42"));
+    assert_eq!(instructions[2].0, token_2022_transfer_with_fee());
+    assert_eq!(instructions[2].1, TOKEN_2022_TRANSFER_WITH_HOOK_ACCOUNTS);
+    assert_eq!(output.encoded, "AgEcDL6CTtV9oQ8aurFeReJ9kchAjGCb7yEczhzu7gbWonZvPJNFtBPVE18wrqRjLA8Y2Nq8ir2VYCzvnrz67M87TrEqN8ZqfmAagRSXvJ28A6ABwf3KEAhX2Y1ANFnDmXQLHqHYPsau9nCZgFUdcgwssoEBycwG6LGKwnkFH2aDFdmfNLt8QKNmPg8HgMVi9ny36813DHejg2VeMW8tAgxkXfNrZjP4jUXLrcZZ9LEXpPrJ7feD7mff4SPc45gCxKygjqphem6wuPnnDgKAAk42bqZMg2WBQ4PKJU6Jvm8wryg81khhr9oVfdysHYt39rVN2TjPESbb3c7y69sBuT3uUxzGteV87EMtoSQWxAdgu7Nw8yMkzya8nyWA6RSDpUQKe7tAmJ9rNh9tS93FUrntRgaB5UYMCvkhMr733ZrwzJ58juFxsULB2p3apG8sGHSqXsE2ewVafsmj19aBT4jEBJ882FWKsZgosvkcENdjZN7cFovzzRKmA3j4kofZz1WRdDbcg3ybBkvbtVHWf7s8nKqeQnnpMdVF9DouCViqreBNbD46HWsPuCdHRuSPfuLET4321r4yiN4UEw8u4Rg2DJASUQUPmvGGyYTHA7hnT6w6CVNhDQgZngZXvUH3QgGFMG8c1QYfY5hNVT75bS2TgWqR28mGSxvDLBj7KEy84YCJHX5VrikcnvVTiakxdSVyWQ1nms5eJAp4nBZjcs3C6swndfuVVbK61SV5PGnguA9c9ZCLp4GuFZVX25PcX5u3cTK39if");
+}
+
+#[test]
+fn test_solana_sign_transfer_token_2022_extensions_errors() {
+    let sign = |token_program_id, memo: &'static str, extensions| {
+        let transfer_token = Proto::TokenTransfer {
+            amount: 1000000000,
+            decimals: 9,
+            memo: memo.into(),
+            token_program_id,
+            token_mint_address: "BSQCmMAFB9itonyVSLsUxX92Ne1rgBZFqothBk3q91k6".into(),
+            sender_token_address: "EQxRyhzjyhRX4TJXt7FmQ3HfFdRcu49krjxHMszidQYS".into(),
+            recipient_token_address: "FzsLNpzsLMBbm1LWpM6P3W4tKrCkd8KqnMmADNvArW5d".into(),
+            token_2022_extensions: Some(extensions),
+            ..Proto::TokenTransfer::default()
+        };
+        let input = Proto::SigningInput {
+            private_key: b58("MCyXa2gTJELxTPemyVi5ydDcQ3vVgFyddQYXj6UM3tw"),
+            recent_blockhash: "9U2eTS9b2Essvo1s5hDmwgC1atkSCCUipj2FemLvdWbj".into(),
+            transaction_type: TransactionType::token_transfer_transaction(transfer_token),
+            ..Proto::SigningInput::default()
+        };
+        let mut signer = AnySignerHelper::<Proto::SigningOutput>::default();
+        signer.sign(CoinType::Solana, input).error
+    };
+
+    // The recipient requires a memo.
+    let memo_required = Proto::Token2022Extensions {
+        recipient_requires_memo: true,
+        ..Proto::Token2022Extensions::default()
+    };
+    assert_eq!(
+        sign(
+            Proto::TokenProgramId::Token2022Program,
+            "",
+            memo_required.clone()
+        ),
+        SigningError::Error_invalid_params
+    );
+    // Extensions are not supported by the SPL Token program.
+    assert_eq!(
+        sign(Proto::TokenProgramId::TokenProgram, "Memo", memo_required),
+        SigningError::Error_invalid_params
+    );
+    // Transfer fee greater than 100%.
+    let transfer_fee = Proto::Token2022Extensions {
+        transfer_fee: Some(Proto::TransferFeeConfig {
+            transfer_fee_basis_points: 10_001,
+            maximum_fee: 5000,
+        }),
+        ..Proto::Token2022Extensions::default()
+    };
+    assert_eq!(
+        sign(Proto::TokenProgramId::Token2022Program, "", transfer_fee),
+        SigningError::Error_invalid_params
+    );
+}
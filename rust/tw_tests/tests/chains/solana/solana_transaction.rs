@@ -10,6 +10,8 @@ use tw_encoding::base58::{self, Alphabet};
 use tw_encoding::base64::{self, STANDARD};
 use tw_proto::Common::Proto::SigningError;
 use tw_proto::Solana::Proto;
+use tw_proto::Solana::Proto::mod_DecodedInstruction as decoded;
+use tw_proto::Solana::Proto::mod_DecodedInstruction::OneOfinstruction as DecodedType;
 use tw_proto::Solana::Proto::mod_RawMessage as raw_message;
 use tw_proto::Solana::Proto::mod_RawMessage::OneOfmessage as MessageType;
 
@@ -157,6 +159,78 @@ fn test_solana_decode_transaction() {
     };
 
     assert_eq!(output.transaction, Some(expected));
+
+    // Step 2: Check the instructions of known programs are decoded.
+
+    let expected_instructions = vec![
+        Proto::DecodedInstruction {
+            program_id: "ComputeBudget111111111111111111111111111111".into(),
+            instruction: DecodedType::set_compute_unit_limit(decoded::SetComputeUnitLimit {
+                units: 1_400_000,
+            }),
+        },
+        Proto::DecodedInstruction {
+            program_id: "ComputeBudget111111111111111111111111111111".into(),
+            instruction: DecodedType::set_compute_unit_price(decoded::SetComputeUnitPrice {
+                micro_lamports: 71_428,
+            }),
+        },
+        Proto::DecodedInstruction {
+            program_id: "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL".into(),
+            instruction: DecodedType::create_associated_token_account(
+                decoded::CreateAssociatedTokenAccount {
+                    funding_account: "AHy6YZA8BsHgQfVkk7MbwpAN94iyN7Nf1zN4nPqUN32Q".into(),
+                    associated_token_account: "GvgWmk8iPACw1AEMt47WzkuTkKoSGbn4Xk3aLM8vdbJD".into(),
+                    wallet: "AHy6YZA8BsHgQfVkk7MbwpAN94iyN7Nf1zN4nPqUN32Q".into(),
+                    // The mint is loaded from an address lookup table.
+                    mint: "".into(),
+                    token_program_id: "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA".into(),
+                    idempotent: true,
+                },
+            ),
+        },
+        Proto::DecodedInstruction {
+            program_id: "11111111111111111111111111111111".into(),
+            instruction: DecodedType::system_transfer(decoded::SystemTransfer {
+                from: "AHy6YZA8BsHgQfVkk7MbwpAN94iyN7Nf1zN4nPqUN32Q".into(),
+                to: "GvgWmk8iPACw1AEMt47WzkuTkKoSGbn4Xk3aLM8vdbJD".into(),
+                lamports: 1_000_000_000,
+            }),
+        },
+        // `SyncNative` is not supported.
+        Proto::DecodedInstruction {
+            program_id: "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA".into(),
+            instruction: DecodedType::unknown(decoded::Unknown {
+                accounts: vec!["GvgWmk8iPACw1AEMt47WzkuTkKoSGbn4Xk3aLM8vdbJD".into()],
+                data: b58("J"),
+            }),
+        },
+        Proto::DecodedInstruction {
+            program_id: "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA".into(),
+            instruction: DecodedType::token_close_account(decoded::TokenCloseAccount {
+                account: "GvgWmk8iPACw1AEMt47WzkuTkKoSGbn4Xk3aLM8vdbJD".into(),
+                destination: "AHy6YZA8BsHgQfVkk7MbwpAN94iyN7Nf1zN4nPqUN32Q".into(),
+                owner: "AHy6YZA8BsHgQfVkk7MbwpAN94iyN7Nf1zN4nPqUN32Q".into(),
+            }),
+        },
+    ];
+    assert_eq!(output.instructions.len(), 7);
+    // The Jupiter aggregator instruction is unknown.
+    let jupiter = &output.instructions[5];
+    assert_eq!(
+        jupiter.program_id,
+        "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4"
+    );
+    assert!(matches!(jupiter.instruction, DecodedType::unknown(_)));
+
+    let known_instructions: Vec<_> = output
+        .instructions
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| *i != 5)
+        .map(|(_, ix)| ix.clone())
+        .collect();
+    assert_eq!(known_instructions, expected_instructions);
 }
 
 #[test]
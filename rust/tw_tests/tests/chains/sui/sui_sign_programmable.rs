@@ -0,0 +1,396 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+use crate::chains::sui::object_ref;
+use crate::chains::sui::test_cases::{transfer_d4ay9tdb, PRIVATE_KEY_54E80D76, SENDER_54E80D76};
+use tw_any_coin::test_utils::sign_utils::AnySignerHelper;
+use tw_coin_registry::coin_type::CoinType;
+use tw_encoding::hex::DecodeHex;
+use tw_proto::Common::Proto::SigningError;
+use tw_proto::Sui::Proto::mod_Argument::OneOfargument as ArgumentType;
+use tw_proto::Sui::Proto::mod_CallArg::OneOfarg as CallArgType;
+use tw_proto::Sui::Proto::mod_Command::OneOfcommand as CommandType;
+use tw_proto::Sui::Proto::mod_PureValue::OneOfvalue as PureValueType;
+use tw_proto::Sui::Proto::{self, mod_SigningInput::OneOftransaction_payload as TransactionType};
+
+fn pure(value: PureValueType<'static>) -> Proto::CallArg<'static> {
+    Proto::CallArg {
+        arg: CallArgType::pure_value(Proto::PureValue { value }),
+    }
+}
+
+fn pure_address(address: &'static str) -> Proto::CallArg<'static> {
+    pure(PureValueType::address(address.into()))
+}
+
+fn input(index: u32) -> Proto::Argument {
+    Proto::Argument {
+        argument: ArgumentType::input(index),
+    }
+}
+
+fn result(command_index: u32) -> Proto::Argument {
+    Proto::Argument {
+        argument: ArgumentType::result(command_index),
+    }
+}
+
+fn nested_result(command_index: u32, result_index: u32) -> Proto::Argument {
+    Proto::Argument {
+        argument: ArgumentType::nested_result(Proto::NestedResult {
+            command_index,
+            result_index,
+        }),
+    }
+}
+
+fn gas_coin() -> Proto::Argument {
+    Proto::Argument {
+        argument: ArgumentType::gas_coin(Proto::GasCoin {}),
+    }
+}
+
+fn command(command: CommandType<'static>) -> Proto::Command<'static> {
+    Proto::Command { command }
+}
+
+fn programmable_input(
+    pt: Proto::ProgrammableTransaction<'static>,
+    gas_budget: u64,
+) -> Proto::SigningInput<'static> {
+    Proto::SigningInput {
+        transaction_payload: TransactionType::programmable_transaction(pt),
+        private_key: PRIVATE_KEY_54E80D76.decode_hex().unwrap().into(),
+        gas_budget,
+        reference_gas_price: 750,
+        ..Proto::SigningInput::default()
+    }
+}
+
+fn test_sign_programmable_impl(
+    pt: Proto::ProgrammableTransaction<'static>,
+    gas_budget: u64,
+    expected_unsigned_tx: &str,
+    expected_signature: &str,
+) {
+    let mut signer = AnySignerHelper::<Proto::SigningOutput>::default();
+    let output = signer.sign(CoinType::Sui, programmable_input(pt, gas_budget));
+
+    assert_eq!(output.error, SigningError::OK);
+    assert_eq!(output.unsigned_tx, expected_unsigned_tx);
+    assert_eq!(output.signature, expected_signature);
+}
+
+fn test_sign_programmable_error(pt: Proto::ProgrammableTransaction<'static>) {
+    let mut signer = AnySignerHelper::<Proto::SigningOutput>::default();
+    let output = signer.sign(CoinType::Sui, programmable_input(pt, 10_000_000));
+    assert_eq!(output.error, SigningError::Error_invalid_params);
+}
+
+/// The same transaction as `transfer_d4ay9tdb` built from commands.
+#[test]
+fn test_sui_sign_programmable_pay_sui() {
+    let pt = Proto::ProgrammableTransaction {
+        inputs: vec![
+            pure(PureValueType::u64_value(1000)),
+            pure(PureValueType::u64_value(50_000)),
+            pure_address("0xa7175abdd5ed92ebe3ad390db366c6a706478cdf517cde6cf98630065cda377a"),
+            pure_address(SENDER_54E80D76),
+        ],
+        commands: vec![
+            command(CommandType::split_coins(Proto::SplitCoins {
+                coin: Some(gas_coin()),
+                amounts: vec![input(0), input(1)],
+            })),
+            command(CommandType::transfer_objects(Proto::TransferObjects {
+                objects: vec![nested_result(0, 0)],
+                address: Some(input(2)),
+            })),
+            command(CommandType::transfer_objects(Proto::TransferObjects {
+                objects: vec![nested_result(0, 1)],
+                address: Some(input(3)),
+            })),
+        ],
+        gas: vec![object_ref(
+            "0x636020b3a7dc7b11c3aa6f419b17f8a9c12e7f79a31d1bdd2de670b4edd63005",
+            85619064,
+            "2eKuWbZSVfpFVfg8FXY9wP6W5AFXnTchSoUdp7obyYZ5",
+        )],
+    };
+
+    // Successfully broadcasted: https://suiscan.xyz/mainnet/tx/D4Ay9TdBJjXkGmrZSstZakpEWskEQHaWURP6xWPRXbAm
+    test_sign_programmable_impl(
+        pt,
+        // 0.003 SUI
+        3000000,
+        transfer_d4ay9tdb::UNSIGNED_TX,
+        transfer_d4ay9tdb::SIGNATURE,
+    );
+}
+
+/// The same transaction as in `test_sui_sign_delegate_sui` built from commands.
+#[test]
+fn test_sui_sign_programmable_add_stake() {
+    let sui_system_state = Proto::CallArg {
+        arg: CallArgType::shared_object(Proto::SharedObject {
+            object_id: "0x0000000000000000000000000000000000000000000000000000000000000005".into(),
+            initial_shared_version: 1,
+            mutable: true,
+        }),
+    };
+    let coin = |id, version, digest| Proto::CallArg {
+        arg: CallArgType::imm_or_owned_object(object_ref(id, version, digest)),
+    };
+    // `Option<u64>` amount: `Some(1.00095 SUI)`.
+    let amount = "01f048a93b00000000".decode_hex().unwrap();
+
+    let pt = Proto::ProgrammableTransaction {
+        inputs: vec![
+            sui_system_state,
+            coin(
+                "0xff1af62d35654956964437882b33d3256aad20214f18a234c62b5e258ca163ee",
+                83160977,
+                "FAugxdfWPQrMu57mMc9FmgNSjkt613pixR6V5M9nashw",
+            ),
+            coin(
+                "0x5ef77d20c7d6745d3d9b5f69e7825aae733fa5c8a3f82f7192749e3169791c8c",
+                85887695,
+                "F3JgSqdQJgzBsNnzJiYkr2XkjTEXmq7NEybixjEYrSf4",
+            ),
+            pure(PureValueType::bcs_encoded(amount.into())),
+            pure_address("0x61953ea72709eed72f4441dd944eec49a11b4acabfc8e04015e89c63be81b6ab"),
+        ],
+        commands: vec![
+            command(CommandType::make_move_vec(Proto::MakeMoveVec {
+                element_type: "".into(),
+                elements: vec![input(1), input(2)],
+            })),
+            command(CommandType::move_call(Proto::MoveCall {
+                package: "0x0000000000000000000000000000000000000000000000000000000000000003"
+                    .into(),
+                module: "sui_system".into(),
+                function: "request_add_stake_mul_coin".into(),
+                type_arguments: Vec::default(),
+                arguments: vec![input(0), result(0), input(3), input(4)],
+            })),
+        ],
+        gas: vec![object_ref(
+            "0x102054b7676a46b1bae724134dc962db729f3389acf79d3d6f3c27ba018a0404",
+            85989207,
+            "GXGhEVNJGNBsvaTiLi85bGask5PbVXTZUKyN6CLR3N7D",
+        )],
+    };
+
+    // Successfully broadcasted: https://suiscan.xyz/mainnet/tx/9CHdn8h68pnC7pKxFN7ABCCiufFkYQQ6EwFEQEPiz6bp
+    test_sign_programmable_impl(
+        pt,
+        // 0.009 SUI
+        9000000,
+        "AAAFAQEAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAABQEAAAAAAAAAAQEA/xr2LTVlSVaWRDeIKzPTJWqtICFPGKI0xiteJYyhY+6R7/QEAAAAACDSjWt6fM4gT8LU9OmUKUD0oeVAN3195wXyRgLAAkj/RgEAXvd9IMfWdF09m19p54JarnM/pcij+C9xknSeMWl5HIzPih4FAAAAACDQmsUAK2qhMxauQja6zUchci2O+VpXNpKHQPa5uzG92wAJAfBIqTsAAAAAACBhlT6nJwnu1y9EQd2UTuxJoRtKyr/I4EAV6JxjvoG2qwIFAAIBAQABAgAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAMKc3VpX3N5c3RlbRpyZXF1ZXN0X2FkZF9zdGFrZV9tdWxfY29pbgAEAQAAAgAAAQMAAQQAVOgNdteQwnf1pE886S9T0m9YlIkr85Xe5jdZiIdr5rIBECBUt2dqRrG65yQTTcli23KfM4ms9509bzwnugGKBARXFyAFAAAAACDmoHkZ4Q2u0tMpkkJOmnK9WHxAXfwVxtKnoGoU3ZecTFToDXbXkMJ39aRPPOkvU9JvWJSJK/OV3uY3WYiHa+ay7gIAAAAAAABAVIkAAAAAAAA=",
+        "AF7oDeTkRQT23xGuW1WsILvm2FQIycaP6bvbTA8oQ8QJU75VQcJDTgEscfxfg8GAN60uzSLKVAJKXKOu8O6vugmF69FEH+T5VPvl3GB3vwCOEZpeJpKXxvcIPQAdKsh2/g==",
+    );
+}
+
+/// Merge coins and split an amount off, swap it through a shared pool using type arguments,
+/// claim an object sent to the pool, and transfer all the results to the sender.
+#[test]
+fn test_sui_sign_programmable_swap() {
+    const POOL_PACKAGE: &str = "0x1eabed72c53feb3805120a081dc15963c204dc8d091542592abaf7a35689b2fb";
+
+    let pt = Proto::ProgrammableTransaction {
+        inputs: vec![
+            // #0: Swap pool.
+            Proto::CallArg {
+                arg: CallArgType::shared_object(Proto::SharedObject {
+                    object_id: "0xcf994611fd4c48e277ce3ffd4d4364c914af2c3cbb05f7bf6facd371de688630"
+                        .into(),
+                    initial_shared_version: 1580450,
+                    mutable: true,
+                }),
+            },
+            // #1: Clock.
+            Proto::CallArg {
+                arg: CallArgType::shared_object(Proto::SharedObject {
+                    object_id: "0x0000000000000000000000000000000000000000000000000000000000000006"
+                        .into(),
+                    initial_shared_version: 1,
+                    mutable: false,
+                }),
+            },
+            // #2-3: BUCK coins.
+            Proto::CallArg {
+                arg: CallArgType::imm_or_owned_object(object_ref(
+                    "0x7c91902ea14bc1e1a27358d7aa44f7ab9f10890642ae97d03b4e8a4c804662cd",
+                    85887687,
+                    "DWJeDBNn5Uyb69E6xxoMZL2wupH9VaGxY3Pf7asJfRCQ",
+                )),
+            },
+            Proto::CallArg {
+                arg: CallArgType::imm_or_owned_object(object_ref(
+                    "0x3a5edd52deb7535dadb6cf92b9ed5e0d0eb959a6ce19ea075a3f7e1a8fe29070",
+                    85887687,
+                    "79CmNvfmneL651e4ND2Kqje13ZJ2sbpGk6oXsa87TkQv",
+                )),
+            },
+            // #4: Amount to swap.
+            pure(PureValueType::u64_value(5_000_000)),
+            // #5: Swap direction.
+            pure(PureValueType::bool_value(true)),
+            // #6: Price limit.
+            pure(PureValueType::u128_value(
+                "79226673515401279992447579055".into(),
+            )),
+            // #7: An object sent to the pool.
+            Proto::CallArg {
+                arg: CallArgType::receiving(object_ref(
+                    "0x2e2355a7e5f857a67c237d27e5a2184f9c683f4275d54bf90dcc70f6117f4a03",
+                    86012337,
+                    "2XTKVJGNZm7i6ZYGQ6ikZowFu855TpTUTip8JJ1jf1ch",
+                )),
+            },
+            // #8: Recipient.
+            pure_address(SENDER_54E80D76),
+        ],
+        commands: vec![
+            command(CommandType::merge_coins(Proto::MergeCoins {
+                destination: Some(input(2)),
+                sources: vec![input(3)],
+            })),
+            command(CommandType::split_coins(Proto::SplitCoins {
+                coin: Some(input(2)),
+                amounts: vec![input(4)],
+            })),
+            // Returns `(Coin<BUCK>, Coin<SUI>)`.
+            command(CommandType::move_call(Proto::MoveCall {
+                package: POOL_PACKAGE.into(),
+                module: "router".into(),
+                function: "swap".into(),
+                type_arguments: vec![
+                    "0xce7ff77a83ea0cb6fd39bd8748e2ec89a3f41e8efdc3f4eb123e0ca37b184db2::buck::BUCK"
+                        .into(),
+                    "0x2::sui::SUI".into(),
+                ],
+                arguments: vec![input(0), nested_result(1, 0), input(5), input(6), input(1)],
+            })),
+            command(CommandType::move_call(Proto::MoveCall {
+                package: POOL_PACKAGE.into(),
+                module: "pool".into(),
+                function: "claim".into(),
+                type_arguments: Vec::default(),
+                arguments: vec![input(0), input(7)],
+            })),
+            command(CommandType::transfer_objects(Proto::TransferObjects {
+                objects: vec![nested_result(2, 0), nested_result(2, 1), result(3)],
+                address: Some(input(8)),
+            })),
+        ],
+        gas: vec![object_ref(
+            "0x636020b3a7dc7b11c3aa6f419b17f8a9c12e7f79a31d1bdd2de670b4edd63005",
+            85887688,
+            "8dZgJm7rWBPfnj3q3DdgVXqyuZGQpCHCvbYiBBBwpnw2",
+        )],
+    };
+
+    test_sign_programmable_impl(
+        pt,
+        // 0.01 SUI
+        10_000_000,
+        "AAAJAQHPmUYR/UxI4nfOP/1NQ2TJFK8sPLsF979vrNNx3miGMKIdGAAAAAAAAQEBAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAYBAAAAAAAAAAABAHyRkC6hS8HhonNY16pE96ufEIkGQq6X0DtOikyARmLNx4oeBQAAAAAguc4IsKFBb4prpNioMIrFMpeTttvHF9E6BECf1wCp1nUBADpe3VLet1NdrbbPkrntXg0OuVmmzhnqB1o/fhqP4pBwx4oeBQAAAAAgW0ASJ3y4pJZtoRqQHdCV3vk1jL00TS5S1UbvPY6P6YcACEBLTAAAAAAAAAEBABCvMxuoMn+7NbHE/v8AAAAAAQIuI1Wn5fhXpnwjfSflohhPnGg/QnXVS/kNzHD2EX9KA7FxIAUAAAAAIBao3rKI2yzUDXKOPPSFi5jBZ1LcO6LZBIeN0qxDPTxmACBU6A1215DCd/WkTzzpL1PSb1iUiSvzld7mN1mIh2vmsgUDAQIAAQEDAAIBAgABAQQAAB6r7XLFP+s4BRIKCB3BWWPCBNyNCRVCWSq696NWibL7BnJvdXRlcgRzd2FwAgfOf/d6g+oMtv05vYdI4uyJo/Qejv3D9OsSPgyjexhNsgRidWNrBEJVQ0sABwAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAACA3N1aQNTVUkABQEAAAMBAAAAAQUAAQYAAQEAAB6r7XLFP+s4BRIKCB3BWWPCBNyNCRVCWSq696NWibL7BHBvb2wFY2xhaW0AAgEAAAEHAAEDAwIAAAADAgABAAIDAAEIAFToDXbXkMJ39aRPPOkvU9JvWJSJK/OV3uY3WYiHa+ayAWNgILOn3HsRw6pvQZsX+KnBLn95ox0b3S3mcLTt1jAFyIoeBQAAAAAgcV+qmPzogpZeCxFZ9yVA8pMA2c2cMi9q9Ti6a1hvmQlU6A1215DCd/WkTzzpL1PSb1iUiSvzld7mN1mIh2vmsu4CAAAAAAAAgJaYAAAAAAAA",
+        "AEJxugRl70UhyvAphbg8Wafde243c6N4EBurd17CspFRDD+53Xy1EJ8vDSbnP2SAYUgOCscEevwKzt9aPRUVMAiF69FEH+T5VPvl3GB3vwCOEZpeJpKXxvcIPQAdKsh2/g==",
+    );
+}
+
+#[test]
+fn test_sui_sign_programmable_invalid_arguments() {
+    let gas_object = || {
+        object_ref(
+            "0x636020b3a7dc7b11c3aa6f419b17f8a9c12e7f79a31d1bdd2de670b4edd63005",
+            85619064,
+            "2eKuWbZSVfpFVfg8FXY9wP6W5AFXnTchSoUdp7obyYZ5",
+        )
+    };
+    let split_gas = |amount: Proto::Argument| {
+        command(CommandType::split_coins(Proto::SplitCoins {
+            coin: Some(gas_coin()),
+            amounts: vec![amount],
+        }))
+    };
+
+    // Input index is out of range.
+    test_sign_programmable_error(Proto::ProgrammableTransaction {
+        inputs: vec![pure(PureValueType::u64_value(1000))],
+        commands: vec![split_gas(input(1))],
+        gas: vec![gas_object()],
+    });
+
+    // Result of a command that has not been executed yet.
+    test_sign_programmable_error(Proto::ProgrammableTransaction {
+        inputs: vec![pure_address(SENDER_54E80D76)],
+        commands: vec![command(CommandType::transfer_objects(
+            Proto::TransferObjects {
+                objects: vec![nested_result(0, 0)],
+                address: Some(input(0)),
+            },
+        ))],
+        gas: vec![gas_object()],
+    });
+
+    // Gas object is used as an input.
+    test_sign_programmable_error(Proto::ProgrammableTransaction {
+        inputs: vec![Proto::CallArg {
+            arg: CallArgType::imm_or_owned_object(gas_object()),
+        }],
+        commands: vec![command(CommandType::merge_coins(Proto::MergeCoins {
+            destination: Some(gas_coin()),
+            sources: vec![input(0)],
+        }))],
+        gas: vec![gas_object()],
+    });
+
+    // No gas objects.
+    test_sign_programmable_error(Proto::ProgrammableTransaction {
+        inputs: vec![pure(PureValueType::u64_value(1000))],
+        commands: vec![split_gas(input(0))],
+        gas: Vec::default(),
+    });
+
+    // u8 value is out of range.
+    test_sign_programmable_error(Proto::ProgrammableTransaction {
+        inputs: vec![pure(PureValueType::u8_value(256))],
+        commands: vec![split_gas(input(0))],
+        gas: vec![gas_object()],
+    });
+
+    // Invalid type tag.
+    test_sign_programmable_error(Proto::ProgrammableTransaction {
+        inputs: Vec::default(),
+        commands: vec![command(CommandType::make_move_vec(Proto::MakeMoveVec {
+            element_type: "0x2::sui".into(),
+            elements: Vec::default(),
+        }))],
+        gas: vec![gas_object()],
+    });
+
+    // Element type of an empty vector is not specified.
+    test_sign_programmable_error(Proto::ProgrammableTransaction {
+        inputs: Vec::default(),
+        commands: vec![command(CommandType::make_move_vec(
+            Proto::MakeMoveVec::default(),
+        ))],
+        gas: vec![gas_object()],
+    });
+
+    // Invalid Move function name.
+    test_sign_programmable_error(Proto::ProgrammableTransaction {
+        inputs: Vec::default(),
+        commands: vec![command(CommandType::move_call(Proto::MoveCall {
+            package: "0x0000000000000000000000000000000000000000000000000000000000000002".into(),
+            module: "coin".into(),
+            function: "zero-value".into(),
+            type_arguments: vec!["0x2::sui::SUI".into()],
+            arguments: Vec::default(),
+        }))],
+        gas: vec![gas_object()],
+    });
+}
@@ -0,0 +1,104 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+#![allow(clippy::missing_safety_doc)]
+
+use tw_bitcoin::modules::psbt::BitcoinPsbt;
+use tw_coin_registry::coin_context::CoinRegistryContext;
+use tw_coin_registry::coin_type::CoinType;
+use tw_coin_registry::registry::get_coin_item;
+use tw_memory::ffi::tw_data::TWData;
+use tw_memory::ffi::RawPtrTrait;
+use tw_misc::try_or_else;
+use tw_proto::BitcoinV2::Proto;
+
+/// Creates a PSBT from a transaction builder and fills in the information required to sign it.
+///
+/// \param coin Bitcoin-compatible coin type.
+/// \param input The serialized data of `TW.BitcoinV2.Proto.PsbtCreatingInput`.
+/// \return The serialized data of a `TW.BitcoinV2.Proto.PsbtOutput` proto object.
+#[no_mangle]
+pub unsafe extern "C" fn tw_bitcoin_psbt_create(coin: u32, input: *const TWData) -> *mut TWData {
+    let coin = try_or_else!(CoinType::try_from(coin), std::ptr::null_mut);
+    let coin_item = try_or_else!(get_coin_item(coin), std::ptr::null_mut);
+    let coin_context = CoinRegistryContext::with_coin_item(coin_item);
+
+    let input_data = try_or_else!(TWData::from_ptr_as_ref(input), std::ptr::null_mut);
+    let input: Proto::PsbtCreatingInput = try_or_else!(
+        tw_proto::deserialize(input_data.as_slice()),
+        std::ptr::null_mut
+    );
+
+    let output = BitcoinPsbt::create(&coin_context, &input);
+    let output_proto = try_or_else!(tw_proto::serialize(&output), std::ptr::null_mut);
+    TWData::from(output_proto).into_ptr()
+}
+
+/// Adds partial signatures of the given private keys to a PSBT.
+///
+/// \param input The serialized data of `TW.BitcoinV2.Proto.PsbtSigningInput`.
+/// \return The serialized data of a `TW.BitcoinV2.Proto.PsbtOutput` proto object.
+#[no_mangle]
+pub unsafe extern "C" fn tw_bitcoin_psbt_sign(input: *const TWData) -> *mut TWData {
+    let input_data = try_or_else!(TWData::from_ptr_as_ref(input), std::ptr::null_mut);
+    let input: Proto::PsbtSigningInput = try_or_else!(
+        tw_proto::deserialize(input_data.as_slice()),
+        std::ptr::null_mut
+    );
+
+    let output = BitcoinPsbt::sign(&input);
+    let output_proto = try_or_else!(tw_proto::serialize(&output), std::ptr::null_mut);
+    TWData::from(output_proto).into_ptr()
+}
+
+/// Combines PSBTs of the same transaction signed by different parties.
+///
+/// \param input The serialized data of `TW.BitcoinV2.Proto.PsbtCombiningInput`.
+/// \return The serialized data of a `TW.BitcoinV2.Proto.PsbtOutput` proto object.
+#[no_mangle]
+pub unsafe extern "C" fn tw_bitcoin_psbt_combine(input: *const TWData) -> *mut TWData {
+    let input_data = try_or_else!(TWData::from_ptr_as_ref(input), std::ptr::null_mut);
+    let input: Proto::PsbtCombiningInput = try_or_else!(
+        tw_proto::deserialize(input_data.as_slice()),
+        std::ptr::null_mut
+    );
+
+    let output = BitcoinPsbt::combine(&input);
+    let output_proto = try_or_else!(tw_proto::serialize(&output), std::ptr::null_mut);
+    TWData::from(output_proto).into_ptr()
+}
+
+/// Builds the final scriptSig and witness of every PSBT input from the partial signatures.
+///
+/// \param input The serialized data of `TW.BitcoinV2.Proto.Psbt`.
+/// \return The serialized data of a `TW.BitcoinV2.Proto.PsbtOutput` proto object.
+#[no_mangle]
+pub unsafe extern "C" fn tw_bitcoin_psbt_finalize(input: *const TWData) -> *mut TWData {
+    let input_data = try_or_else!(TWData::from_ptr_as_ref(input), std::ptr::null_mut);
+    let input: Proto::Psbt = try_or_else!(
+        tw_proto::deserialize(input_data.as_slice()),
+        std::ptr::null_mut
+    );
+
+    let output = BitcoinPsbt::finalize(&input);
+    let output_proto = try_or_else!(tw_proto::serialize(&output), std::ptr::null_mut);
+    TWData::from(output_proto).into_ptr()
+}
+
+/// Extracts the signed network transaction from a finalized PSBT.
+///
+/// \param input The serialized data of `TW.BitcoinV2.Proto.Psbt`.
+/// \return The serialized data of a `TW.BitcoinV2.Proto.SigningOutput` proto object.
+#[no_mangle]
+pub unsafe extern "C" fn tw_bitcoin_psbt_extract(input: *const TWData) -> *mut TWData {
+    let input_data = try_or_else!(TWData::from_ptr_as_ref(input), std::ptr::null_mut);
+    let input: Proto::Psbt = try_or_else!(
+        tw_proto::deserialize(input_data.as_slice()),
+        std::ptr::null_mut
+    );
+
+    let output = BitcoinPsbt::extract(&input);
+    let output_proto = try_or_else!(tw_proto::serialize(&output), std::ptr::null_mut);
+    TWData::from(output_proto).into_ptr()
+}
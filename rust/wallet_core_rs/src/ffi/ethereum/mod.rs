@@ -3,4 +3,8 @@
 // Copyright © 2017 Trust Wallet.
 
 pub mod abi;
+pub mod authorization;
+pub mod permit;
 pub mod rlp;
+pub mod siwe;
+pub mod smart_account;
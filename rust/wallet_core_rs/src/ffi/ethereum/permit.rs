@@ -0,0 +1,51 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+#![allow(clippy::missing_safety_doc)]
+
+use tw_coin_registry::coin_type::CoinType;
+use tw_coin_registry::dispatcher::evm_dispatcher;
+use tw_memory::ffi::tw_data::TWData;
+use tw_memory::ffi::RawPtrTrait;
+use tw_misc::try_or_else;
+
+/// Builds and signs an EIP-2612 or Permit2 permit.
+/// Returns the EIP-712 typed data and its pre-hash only if no private key is specified (external signing).
+///
+/// \param coin EVM-compatible coin type.
+/// \param input Non-null serialized `Ethereum::Proto::PermitSigningInput`.
+/// \return serialized `Ethereum::Proto::PermitSigningOutput`.
+#[no_mangle]
+pub unsafe extern "C" fn tw_ethereum_permit_sign(coin: u32, input: *const TWData) -> *mut TWData {
+    let coin = try_or_else!(CoinType::try_from(coin), std::ptr::null_mut);
+    let input_data = try_or_else!(TWData::from_ptr_as_ref(input), std::ptr::null_mut);
+    let evm_dispatcher = try_or_else!(evm_dispatcher(coin), std::ptr::null_mut);
+    evm_dispatcher
+        .sign_permit(input_data.as_slice())
+        .map(|data| TWData::from(data).into_ptr())
+        .unwrap_or_else(|_| std::ptr::null_mut())
+}
+
+/// Builds the `permit(...)` call of an EIP-2612 or Permit2 permit signed externally.
+/// The signature must be produced by the permit owner over the `pre_hash` returned by `tw_ethereum_permit_sign`.
+///
+/// \param coin EVM-compatible coin type.
+/// \param input Non-null serialized `Ethereum::Proto::PermitSigningInput` with the `owner` set.
+/// \param signature Non-null 65 bytes `r || s || v` signature.
+/// \return serialized `Ethereum::Proto::PermitSigningOutput`.
+#[no_mangle]
+pub unsafe extern "C" fn tw_ethereum_permit_compile(
+    coin: u32,
+    input: *const TWData,
+    signature: *const TWData,
+) -> *mut TWData {
+    let coin = try_or_else!(CoinType::try_from(coin), std::ptr::null_mut);
+    let input_data = try_or_else!(TWData::from_ptr_as_ref(input), std::ptr::null_mut);
+    let signature = try_or_else!(TWData::from_ptr_as_ref(signature), std::ptr::null_mut);
+    let evm_dispatcher = try_or_else!(evm_dispatcher(coin), std::ptr::null_mut);
+    evm_dispatcher
+        .compile_permit(input_data.as_slice(), signature.as_slice())
+        .map(|data| TWData::from(data).into_ptr())
+        .unwrap_or_else(|_| std::ptr::null_mut())
+}
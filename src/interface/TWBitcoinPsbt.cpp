@@ -0,0 +1,46 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+#include "TrustWalletCore/TWBitcoinPsbt.h"
+#include "Data.h"
+#include "rust/Wrapper.h"
+
+using namespace TW;
+
+template <typename F>
+static TWData* _Nonnull bitcoinPsbtForwardToRust(F rustFunction, TWData* _Nonnull input) {
+    const Data& inputData = *(reinterpret_cast<const Data*>(input));
+
+    const Rust::TWDataWrapper dataInPtr(inputData);
+    Rust::TWDataWrapper dataOutPtr = rustFunction(dataInPtr.get());
+
+    auto dataOut = dataOutPtr.toDataOrDefault();
+    return TWDataCreateWithBytes(dataOut.data(), dataOut.size());
+}
+
+TWData* _Nonnull TWBitcoinPsbtCreate(enum TWCoinType coin, TWData* _Nonnull input) {
+    const Data& inputData = *(reinterpret_cast<const Data*>(input));
+
+    const Rust::TWDataWrapper dataInPtr(inputData);
+    Rust::TWDataWrapper dataOutPtr = Rust::tw_bitcoin_psbt_create(static_cast<uint32_t>(coin), dataInPtr.get());
+
+    auto dataOut = dataOutPtr.toDataOrDefault();
+    return TWDataCreateWithBytes(dataOut.data(), dataOut.size());
+}
+
+TWData* _Nonnull TWBitcoinPsbtSign(TWData* _Nonnull input) {
+    return bitcoinPsbtForwardToRust(Rust::tw_bitcoin_psbt_sign, input);
+}
+
+TWData* _Nonnull TWBitcoinPsbtCombine(TWData* _Nonnull input) {
+    return bitcoinPsbtForwardToRust(Rust::tw_bitcoin_psbt_combine, input);
+}
+
+TWData* _Nonnull TWBitcoinPsbtFinalize(TWData* _Nonnull input) {
+    return bitcoinPsbtForwardToRust(Rust::tw_bitcoin_psbt_finalize, input);
+}
+
+TWData* _Nonnull TWBitcoinPsbtExtract(TWData* _Nonnull input) {
+    return bitcoinPsbtForwardToRust(Rust::tw_bitcoin_psbt_extract, input);
+}
@@ -32,6 +32,14 @@ TWData* _Nonnull TWEthereumAbiDecodeContractCall(enum TWCoinType coin, TWData* _
     return ethereumAbiForwardToRust(Rust::tw_ethereum_abi_decode_contract_call, coin, input);
 }
 
+TWData* _Nonnull TWEthereumAbiDecodeLog(enum TWCoinType coin, TWData* _Nonnull input) {
+    return ethereumAbiForwardToRust(Rust::tw_ethereum_abi_decode_log, coin, input);
+}
+
+TWData* _Nonnull TWEthereumAbiDecodeRevert(enum TWCoinType coin, TWData* _Nonnull input) {
+    return ethereumAbiForwardToRust(Rust::tw_ethereum_abi_decode_revert, coin, input);
+}
+
 TWData* _Nonnull TWEthereumAbiDecodeParams(enum TWCoinType coin, TWData* _Nonnull input) {
     return ethereumAbiForwardToRust(Rust::tw_ethereum_abi_decode_params, coin, input);
 }
@@ -44,6 +52,14 @@ TWData* _Nonnull TWEthereumAbiEncodeFunction(enum TWCoinType coin, TWData* _Nonn
     return ethereumAbiForwardToRust(Rust::tw_ethereum_abi_encode_function, coin, input);
 }
 
+TWData* _Nonnull TWEthereumAbiEncodePacked(enum TWCoinType coin, TWData* _Nonnull input) {
+    return ethereumAbiForwardToRust(Rust::tw_ethereum_abi_encode_packed, coin, input);
+}
+
+TWData* _Nonnull TWEthereumAbiHashTypedData(enum TWCoinType coin, TWData* _Nonnull input) {
+    return ethereumAbiForwardToRust(Rust::tw_ethereum_abi_hash_typed_data, coin, input);
+}
+
 TWData* _Nonnull TWEthereumAbiEncode(struct TWEthereumAbiFunction* _Nonnull func_in) {
     assert(func_in != nullptr);
     Data encodedData;
@@ -0,0 +1,32 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+#include <TrustWalletCore/TWEthereumPermit.h>
+
+#include "rust/Wrapper.h"
+#include "Data.h"
+
+using namespace TW;
+
+TWData* _Nonnull TWEthereumPermitSign(enum TWCoinType coin, TWData* _Nonnull input) {
+    const Data& dataIn = *(reinterpret_cast<const Data*>(input));
+
+    const Rust::TWDataWrapper dataInPtr(dataIn);
+    Rust::TWDataWrapper dataOutPtr = Rust::tw_ethereum_permit_sign(static_cast<uint32_t>(coin), dataInPtr.get());
+
+    auto dataOut = dataOutPtr.toDataOrDefault();
+    return TWDataCreateWithBytes(dataOut.data(), dataOut.size());
+}
+
+TWData* _Nonnull TWEthereumPermitCompile(enum TWCoinType coin, TWData* _Nonnull input, TWData* _Nonnull signature) {
+    const Data& dataIn = *(reinterpret_cast<const Data*>(input));
+    const Data& signatureData = *(reinterpret_cast<const Data*>(signature));
+
+    const Rust::TWDataWrapper dataInPtr(dataIn);
+    const Rust::TWDataWrapper signaturePtr(signatureData);
+    Rust::TWDataWrapper dataOutPtr = Rust::tw_ethereum_permit_compile(static_cast<uint32_t>(coin), dataInPtr.get(), signaturePtr.get());
+
+    auto dataOut = dataOutPtr.toDataOrDefault();
+    return TWDataCreateWithBytes(dataOut.data(), dataOut.size());
+}
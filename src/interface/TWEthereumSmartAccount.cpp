@@ -0,0 +1,20 @@
+// SPDX-License-Identifier: Apache-2.0
+//
+// Copyright © 2017 Trust Wallet.
+
+#include <TrustWalletCore/TWEthereumSmartAccount.h>
+
+#include "rust/Wrapper.h"
+#include "Data.h"
+
+using namespace TW;
+
+TWData* _Nonnull TWEthereumSmartAccountAddress(enum TWCoinType coin, TWData* _Nonnull input) {
+    const Data& dataIn = *(reinterpret_cast<const Data*>(input));
+
+    const Rust::TWDataWrapper dataInPtr(dataIn);
+    Rust::TWDataWrapper dataOutPtr = Rust::tw_ethereum_smart_account_address(static_cast<uint32_t>(coin), dataInPtr.get());
+
+    auto dataOut = dataOutPtr.toDataOrDefault();
+    return TWDataCreateWithBytes(dataOut.data(), dataOut.size());
+}